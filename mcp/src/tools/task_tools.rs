@@ -2,6 +2,7 @@ use crate::models::{
     CreateUserTask, GetTaskById, SearchRequest, UpdateSearchRequest, UpdateUserTask,
 };
 use crate::utils::{as_content_string, get_content_from_response_task, get_content_from_tasks};
+use reqwest::header::{AUTHORIZATION, HeaderMap, HeaderValue};
 use reqwest::Client;
 use rmcp::ErrorData as RmcpError;
 use rmcp::handler::server::tool::{Parameters, ToolRouter};
@@ -11,17 +12,43 @@ use rmcp::model::{
 use rmcp::{ServerHandler, tool, tool_handler, tool_router};
 use shared::models::task_model::Task;
 
+const API_URL_VAR: &str = "MINDVAULT_API_URL";
+/// Personal access token the API attributes this server's requests to, e.g. one created with
+/// `mindvault-api token create mcp --scope tasks:read --scope tasks:write`
+const API_TOKEN_VAR: &str = "MINDVAULT_API_TOKEN";
+const DEFAULT_API_URL: &str = "http://localhost:4500";
+
 #[derive(Debug)]
 pub struct TaskTool {
     base_url: String,
+    client: Client,
     pub tool_router: ToolRouter<Self>,
 }
 
+/// HTTP client that sends the configured access token with every request
+fn api_client() -> Client {
+    let mut headers = HeaderMap::new();
+    match std::env::var(API_TOKEN_VAR) {
+        Ok(token) => {
+            let mut value = HeaderValue::from_str(&format!("Bearer {}", token.trim()))
+                .expect("access token must be a valid header value");
+            value.set_sensitive(true);
+            headers.insert(AUTHORIZATION, value);
+        }
+        Err(_) => eprintln!("{} is not set; the MindVault API will reject requests", API_TOKEN_VAR),
+    }
+    Client::builder()
+        .default_headers(headers)
+        .build()
+        .expect("failed to build HTTP client")
+}
+
 #[tool_router]
 impl TaskTool {
     pub(crate) fn new() -> TaskTool {
         TaskTool {
-            base_url: "http://localhost:4500".to_string(),
+            base_url: std::env::var(API_URL_VAR).unwrap_or_else(|_| DEFAULT_API_URL.to_string()),
+            client: api_client(),
             tool_router: Self::tool_router(),
         }
     }
@@ -31,7 +58,7 @@ impl TaskTool {
         description = "Get all available tasks for the user"
     )]
     pub async fn get_user_tasks(&self) -> Result<CallToolResult, RmcpError> {
-        let client = Client::new(); // Create an HTTP client instance
+        let client = &self.client;
         let get_all_tasks_url = format!("{}/tasks", self.base_url);
         let response = client.get(get_all_tasks_url).send().await;
         let tasks: Vec<Task> = response.unwrap().json().await.unwrap();
@@ -46,7 +73,7 @@ impl TaskTool {
         &self,
         Parameters(GetTaskById { task_id }): Parameters<GetTaskById>,
     ) -> Result<CallToolResult, RmcpError> {
-        let client = Client::new(); // Create an HTTP client instance
+        let client = &self.client;
         let get_task_by_id_url = format!("{}/tasks/{}", self.base_url, task_id);
         let response = client.get(get_task_by_id_url).send().await;
         let task: Task = response.unwrap().json().await.unwrap();
@@ -68,7 +95,7 @@ impl TaskTool {
             schedule,
         }): Parameters<CreateUserTask>,
     ) -> Result<CallToolResult, RmcpError> {
-        let client = Client::new();
+        let client = &self.client;
         let create_task_url = format!("{}/tasks", self.base_url);
         let user_new_task = CreateUserTask {
             name,
@@ -95,7 +122,7 @@ impl TaskTool {
         &self,
         Parameters(UpdateUserTask { task_id, user_task }): Parameters<UpdateUserTask>,
     ) -> Result<CallToolResult, RmcpError> {
-        let client = Client::new();
+        let client = &self.client;
         let update_task_url = format!("{}/tasks/{}", self.base_url, task_id);
         let response = client
             .put(update_task_url)
@@ -115,7 +142,7 @@ impl TaskTool {
         &self,
         Parameters(GetTaskById { task_id }): Parameters<GetTaskById>,
     ) -> Result<CallToolResult, RmcpError> {
-        let client = Client::new();
+        let client = &self.client;
         let delete_task_url = format!("{}/tasks/{}", self.base_url, task_id);
         let response = client
             .delete(delete_task_url)
@@ -133,7 +160,7 @@ impl TaskTool {
         &self,
         Parameters(SearchRequest { search_term }): Parameters<SearchRequest>,
     ) -> Result<CallToolResult, RmcpError> {
-        let client = Client::new();
+        let client = &self.client;
         let search_task_url = format!("{}/tasks/search?search_term={}", self.base_url, search_term);
         let response = client
             .get(search_task_url)
@@ -155,7 +182,7 @@ impl TaskTool {
             user_task,
         }): Parameters<UpdateSearchRequest>,
     ) -> Result<CallToolResult, RmcpError> {
-        let client = Client::new();
+        let client = &self.client;
         let search_task_url = format!("{}/tasks/search?search_term={}", self.base_url, search_term);
         let response = client
             .post(search_task_url)
@@ -175,7 +202,7 @@ impl TaskTool {
         &self,
         Parameters(SearchRequest { search_term }): Parameters<SearchRequest>,
     ) -> Result<CallToolResult, RmcpError> {
-        let client = Client::new();
+        let client = &self.client;
         let search_task_url = format!("{}/tasks/search?search_term={}", self.base_url, search_term);
         let response = client
             .delete(search_task_url)
@@ -0,0 +1,239 @@
+pub(crate) mod workspace_access;
+
+use crate::services::access_token_service::AccessTokenService;
+use axum::extract::{Request, State};
+use axum::http::header::{AUTHORIZATION, WWW_AUTHENTICATE};
+use axum::http::{Method, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use mindvault_shared::models::access_tokens_model::{AccessToken, ETokenScope};
+use mindvault_shared::utils::date_input::DatePreferences;
+use std::sync::Arc;
+use tracing::info;
+
+/// Query parameter accepted in place of the `Authorization` header on read-only requests to
+/// routes that allow it, for `EventSource` and browser WebSocket clients, which cannot set
+/// headers. Elsewhere it would leave tokens in proxy and access logs.
+const ACCESS_TOKEN_QUERY_PARAM: &str = "access_token";
+
+/// Scopes a group of routes needs: `read` for GET and HEAD, `write` for everything else
+#[derive(Clone)]
+pub(crate) struct RequiredScopes {
+    access_token_service: Arc<AccessTokenService>,
+    read: ETokenScope,
+    write: ETokenScope,
+    allow_query_token: bool,
+}
+
+impl RequiredScopes {
+    pub(crate) fn new(
+        access_token_service: Arc<AccessTokenService>,
+        read: ETokenScope,
+        write: ETokenScope,
+    ) -> Self {
+        Self {
+            access_token_service,
+            read,
+            write,
+            allow_query_token: false,
+        }
+    }
+
+    /// Also accepts the token as `?access_token=` on read-only requests
+    pub(crate) fn allowing_query_token(mut self) -> Self {
+        self.allow_query_token = true;
+        self
+    }
+
+    /// The scope a request with `method` needs
+    fn scope_for(&self, method: &Method) -> ETokenScope {
+        if is_read_only(method) {
+            self.read
+        } else {
+            self.write
+        }
+    }
+}
+
+/// Who made an authenticated request; handlers scope everything they touch to `user_id` and
+/// read and show dates with `dates`
+#[derive(Debug, Clone)]
+pub(crate) struct Caller {
+    pub(crate) user_id: i64,
+    pub(crate) dates: DatePreferences,
+}
+
+fn is_read_only(method: &Method) -> bool {
+    matches!(*method, Method::GET | Method::HEAD)
+}
+
+fn presented_token(request: &Request, allow_query_token: bool) -> Option<String> {
+    let header_token = request
+        .headers()
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .map(|token| token.trim().to_string());
+    if header_token.is_some() || !allow_query_token || !is_read_only(request.method()) {
+        return header_token;
+    }
+    request.uri().query().and_then(|query| {
+        query.split('&').find_map(|pair| {
+            pair.strip_prefix(ACCESS_TOKEN_QUERY_PARAM)
+                .and_then(|rest| rest.strip_prefix('='))
+                .map(str::to_string)
+        })
+    })
+}
+
+fn unauthorized(message: &str) -> Response {
+    (
+        StatusCode::UNAUTHORIZED,
+        [(WWW_AUTHENTICATE, "Bearer")],
+        message.to_string(),
+    )
+        .into_response()
+}
+
+/// The answer to a request whose token lacks `scope`, if it does
+fn missing_scope(token: &AccessToken, scope: ETokenScope) -> Option<Response> {
+    if token.has_scope(scope) {
+        return None;
+    }
+    Some(
+        (
+            StatusCode::FORBIDDEN,
+            format!("Access token '{}' lacks the '{}' scope", token.name, scope),
+        )
+            .into_response(),
+    )
+}
+
+/// Rejects requests without a personal access token holding the scope the route requires,
+/// logs which token made each accepted request and hands its user to the handler as a `Caller`
+pub(crate) async fn require_scopes(
+    State(required): State<RequiredScopes>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    let Some(secret) = presented_token(&request, required.allow_query_token) else {
+        return unauthorized("Missing access token");
+    };
+    let (token, user) = match required.access_token_service.authenticate(&secret).await {
+        Ok(Some(authenticated)) => authenticated,
+        Ok(None) => return unauthorized("Invalid or revoked access token, or disabled user"),
+        Err(_) => {
+            return (StatusCode::INTERNAL_SERVER_ERROR, "Failed to check access token".to_string())
+                .into_response();
+        }
+    };
+
+    if let Some(forbidden) = missing_scope(&token, required.scope_for(request.method())) {
+        return forbidden;
+    }
+
+    info!(
+        "{} {} by access token '{}' ({}) of user {}",
+        request.method(),
+        request.uri().path(),
+        token.name,
+        token.id,
+        token.user_id
+    );
+    request.extensions_mut().insert(Caller {
+        user_id: token.user_id,
+        dates: user.date_preferences(),
+    });
+    next.run(request).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::router::tests::{offline_database, offline_router, send};
+    use axum::body::{to_bytes, Body};
+    use bson::DateTime as BsonDateTime;
+
+    fn token(scopes: &[ETokenScope]) -> AccessToken {
+        AccessToken {
+            id: 1,
+            user_id: 1,
+            name: "reporting".to_string(),
+            token_hash: String::new(),
+            prefix: "mv_abcd".to_string(),
+            scopes: scopes.to_vec(),
+            created_at: BsonDateTime::now(),
+            last_used_at: None,
+            revoked_at: None,
+        }
+    }
+
+    async fn required(read: ETokenScope, write: ETokenScope) -> RequiredScopes {
+        let access_token_service = Arc::new(AccessTokenService::new(offline_database().await));
+        RequiredScopes::new(access_token_service, read, write)
+    }
+
+    fn status(token: &AccessToken, scope: ETokenScope) -> StatusCode {
+        missing_scope(token, scope).map_or(StatusCode::OK, |response| response.status())
+    }
+
+    #[tokio::test]
+    async fn read_tokens_cannot_write() {
+        let tasks = required(ETokenScope::TasksRead, ETokenScope::TasksWrite).await;
+        let read = token(&[ETokenScope::TasksRead]);
+        for method in [Method::POST, Method::PUT, Method::DELETE] {
+            let scope = tasks.scope_for(&method);
+            assert_eq!(status(&read, scope), StatusCode::FORBIDDEN, "{}", method);
+        }
+        assert_eq!(status(&read, tasks.scope_for(&Method::GET)), StatusCode::OK);
+
+        let write = token(&[ETokenScope::TasksWrite]);
+        assert_eq!(status(&write, tasks.scope_for(&Method::GET)), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn only_admin_tokens_reach_admin_routes() {
+        // `/webhooks`, `/notifications`, `/admin` and `/auth` are protected like this
+        let admin_routes = required(ETokenScope::Admin, ETokenScope::Admin).await;
+        let read_and_write = token(&ETokenScope::ALL[..4]);
+        for method in [Method::GET, Method::POST] {
+            let scope = admin_routes.scope_for(&method);
+            assert_eq!(status(&read_and_write, scope), StatusCode::FORBIDDEN);
+            assert_eq!(status(&token(&[ETokenScope::Admin]), scope), StatusCode::OK);
+        }
+    }
+
+    fn request(method: Method, uri: &str) -> Request {
+        Request::builder().method(method).uri(uri).body(Body::empty()).unwrap()
+    }
+
+    #[test]
+    fn query_tokens_are_only_taken_where_allowed() {
+        let get = request(Method::GET, "/events?types=task&access_token=mv_secret");
+        assert_eq!(presented_token(&get, true).as_deref(), Some("mv_secret"));
+        assert_eq!(presented_token(&get, false), None);
+
+        let post = request(Method::POST, "/events?access_token=mv_secret");
+        assert_eq!(presented_token(&post, true), None);
+    }
+
+    async fn body_of(response: Response) -> String {
+        let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        String::from_utf8(bytes.to_vec()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn only_event_routes_read_the_token_from_the_query() {
+        let router = offline_router().await;
+        // Not a MindVault token, so it is turned away without a database lookup
+        let response = send(router.clone(), Method::GET, "/v1/events?access_token=nope").await;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        assert!(body_of(response).await.starts_with("Invalid or revoked access token"));
+
+        for uri in ["/v1/tasks?access_token=nope", "/v1/calendar.ics?access_token=nope"] {
+            let response = send(router.clone(), Method::GET, uri).await;
+            assert_eq!(response.status(), StatusCode::UNAUTHORIZED, "{}", uri);
+            assert_eq!(body_of(response).await, "Missing access token", "{}", uri);
+        }
+    }
+}
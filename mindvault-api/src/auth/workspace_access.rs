@@ -0,0 +1,148 @@
+use axum::http::StatusCode;
+use mindvault_core::models::{AppDatabase, DbError};
+use mindvault_core::repository::task_repo::TaskVault;
+use mindvault_core::repository::workspace_repo::WorkspaceRepository;
+use mindvault_shared::models::workspaces_model::{EWorkspaceRole, Workspace};
+use std::fmt;
+
+/// Why a caller may not do something to a workspace or a vault's tasks
+#[derive(Debug)]
+pub(crate) enum EAccessDenial {
+    /// The workspace does not exist or the caller is not a member. Both read the same, so
+    /// outsiders cannot probe which workspace ids exist.
+    UnknownWorkspace(i64),
+    /// Member whose role is below the one the action needs
+    InsufficientRole {
+        workspace_id: i64,
+        role: EWorkspaceRole,
+        required: EWorkspaceRole,
+    },
+    /// Request that breaks a rule, such as assigning a task to a non-member, removing the last
+    /// admin or sending an ambiguous date
+    Rejected(String),
+}
+
+impl fmt::Display for EAccessDenial {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EAccessDenial::UnknownWorkspace(workspace_id) => {
+                write!(f, "Workspace {} not found", workspace_id)
+            }
+            EAccessDenial::InsufficientRole {
+                workspace_id,
+                role,
+                required,
+            } => write!(
+                f,
+                "{} role in workspace {} is required, you are {}",
+                required, workspace_id, role
+            ),
+            EAccessDenial::Rejected(message) => f.write_str(message),
+        }
+    }
+}
+
+impl From<EAccessDenial> for (StatusCode, String) {
+    fn from(denial: EAccessDenial) -> Self {
+        let status = match denial {
+            EAccessDenial::UnknownWorkspace(_) => StatusCode::NOT_FOUND,
+            EAccessDenial::InsufficientRole { .. } => StatusCode::FORBIDDEN,
+            EAccessDenial::Rejected(_) => StatusCode::BAD_REQUEST,
+        };
+        (status, denial.to_string())
+    }
+}
+
+/// Splits a denial off a service result, so handlers can return it with `?` and pass the rest
+/// to `handle_service_response!`
+pub(crate) fn split_denial<T, E>(
+    result: Result<Result<T, EAccessDenial>, E>,
+) -> Result<Result<T, E>, (StatusCode, String)> {
+    match result {
+        Ok(Ok(value)) => Ok(Ok(value)),
+        Ok(Err(denial)) => Err(denial.into()),
+        Err(e) => Ok(Err(e)),
+    }
+}
+
+/// Vault a caller was allowed to act on
+pub(crate) struct TaskAccess {
+    pub(crate) vault: TaskVault,
+    workspace: Option<Workspace>,
+}
+
+impl TaskAccess {
+    /// Tasks can only be assigned to members of their workspace; private tasks have no assignee
+    pub(crate) fn check_assignee(&self, assignee_id: Option<i64>) -> Result<(), EAccessDenial> {
+        let Some(assignee_id) = assignee_id else {
+            return Ok(());
+        };
+        match &self.workspace {
+            Some(workspace) if workspace.role_of(assignee_id).is_some() => Ok(()),
+            _ => Err(EAccessDenial::Rejected(format!(
+                "User {} cannot be assigned: only members of the task's workspace can",
+                assignee_id
+            ))),
+        }
+    }
+}
+
+/// Checks what a caller may do in a workspace, and which vault a task request acts on.
+/// Private vaults belong to their owner alone; workspaces go by the member's role.
+pub(crate) struct WorkspaceAuthorizer {
+    workspace_repository: WorkspaceRepository,
+}
+
+impl WorkspaceAuthorizer {
+    pub(crate) fn new(app_database: AppDatabase) -> Self {
+        Self {
+            workspace_repository: WorkspaceRepository::new(app_database),
+        }
+    }
+
+    /// The workspace, if `user_id` holds at least `required` there
+    pub(crate) async fn authorize_workspace(
+        &self,
+        user_id: i64,
+        workspace_id: i64,
+        required: EWorkspaceRole,
+    ) -> Result<Result<Workspace, EAccessDenial>, DbError> {
+        let workspace = self.workspace_repository.find_by_id(workspace_id).await?;
+        let Some((workspace, role)) = workspace
+            .and_then(|workspace| workspace.role_of(user_id).map(|role| (workspace, role)))
+        else {
+            return Ok(Err(EAccessDenial::UnknownWorkspace(workspace_id)));
+        };
+        if role < required {
+            return Ok(Err(EAccessDenial::InsufficientRole {
+                workspace_id,
+                role,
+                required,
+            }));
+        }
+        Ok(Ok(workspace))
+    }
+
+    /// Grants `user_id` their private vault when `workspace_id` is `None`, otherwise the
+    /// workspace if they hold at least `required` there
+    pub(crate) async fn authorize_tasks(
+        &self,
+        user_id: i64,
+        workspace_id: Option<i64>,
+        required: EWorkspaceRole,
+    ) -> Result<Result<TaskAccess, EAccessDenial>, DbError> {
+        let Some(workspace_id) = workspace_id else {
+            return Ok(Ok(TaskAccess {
+                vault: TaskVault::User(user_id),
+                workspace: None,
+            }));
+        };
+        Ok(self
+            .authorize_workspace(user_id, workspace_id, required)
+            .await?
+            .map(|workspace| TaskAccess {
+                vault: TaskVault::Workspace(workspace_id),
+                workspace: Some(workspace),
+            }))
+    }
+}
@@ -0,0 +1,243 @@
+use mindvault_core::access_tokens::generate_access_token;
+use mindvault_core::backup::{backup_dir_from_env, read_backup, restore_backup, write_backup_file};
+use mindvault_core::encryption::rotate_encryption_keys;
+use mindvault_core::models::AppDatabase;
+use mindvault_core::ownership::{ensure_user_vaults, DEFAULT_USERNAME};
+use mindvault_core::repository::access_token_repo::AccessTokenRepository;
+use mindvault_core::repository::user_repo::UserRepository;
+use mindvault_shared::models::access_tokens_model::{
+    AccessTokenResponse, CreatedAccessTokenResponse, ETokenScope,
+};
+use mindvault_shared::models::users_model::{normalize_username, User, UserResponse};
+use mindvault_shared::utils::field_encryption::FieldKeyring;
+use std::error::Error;
+use std::fs;
+use std::path::PathBuf;
+use tracing::info;
+
+pub(crate) const USAGE: &str = "\
+Usage:
+  mindvault-api                               Run the HTTP server
+  mindvault-api backup [--output DIR]         Write a backup archive (default: $MINDVAULT_BACKUP_DIR or ./backups)
+  mindvault-api restore ARCHIVE [--dry-run]   Validate a backup and load it into an empty database
+  mindvault-api generate-key KEY_ID           Print a new keyfile line for field encryption
+  mindvault-api rotate-keys [--dry-run]       Re-encrypt sensitive fields with the active key
+  mindvault-api token create NAME [--user USERNAME] --scope SCOPE [--scope SCOPE ...]
+                                              Create a personal access token and print it once
+  mindvault-api token list                    List access tokens
+  mindvault-api token revoke ID               Revoke an access token
+  mindvault-api user create USERNAME [--display-name NAME]
+                                              Create a user with an empty vault
+  mindvault-api user list                     List users
+  mindvault-api user disable|enable USERNAME  Reject or accept again every token of a user
+
+Tokens belong to the user 'owner' unless --user is given.
+Scopes: tasks:read, tasks:write, people:read, people:write, admin";
+
+pub(crate) enum Command {
+    Serve,
+    Backup { output_dir: Option<PathBuf> },
+    Restore { archive: PathBuf, dry_run: bool },
+    GenerateKey { key_id: String },
+    RotateKeys { dry_run: bool },
+    CreateToken { name: String, username: String, scopes: Vec<ETokenScope> },
+    ListTokens,
+    RevokeToken { id: i64 },
+    CreateUser { username: String, display_name: Option<String> },
+    ListUsers,
+    SetUserDisabled { username: String, disabled: bool },
+}
+
+fn parse_token_args(rest: &[String]) -> Result<Command, String> {
+    match rest {
+        [action] if action == "list" => Ok(Command::ListTokens),
+        [action, id] if action == "revoke" => id
+            .parse()
+            .map(|id| Command::RevokeToken { id })
+            .map_err(|_| format!("Invalid token id '{}'\n\n{}", id, USAGE)),
+        [action, name, options @ ..] if action == "create" && !options.is_empty() => {
+            let mut scopes = Vec::new();
+            let mut username = DEFAULT_USERNAME.to_string();
+            for pair in options.chunks(2) {
+                match pair {
+                    [flag, scope] if flag == "--scope" => scopes.push(scope.parse()?),
+                    [flag, user] if flag == "--user" => username = user.clone(),
+                    _ => return Err(USAGE.to_string()),
+                }
+            }
+            if scopes.is_empty() {
+                return Err(USAGE.to_string());
+            }
+            Ok(Command::CreateToken {
+                name: name.clone(),
+                username,
+                scopes,
+            })
+        }
+        _ => Err(USAGE.to_string()),
+    }
+}
+
+fn parse_user_args(rest: &[String]) -> Result<Command, String> {
+    match rest {
+        [action] if action == "list" => Ok(Command::ListUsers),
+        [action, username] if action == "create" => Ok(Command::CreateUser {
+            username: username.clone(),
+            display_name: None,
+        }),
+        [action, username, flag, display_name]
+            if action == "create" && flag == "--display-name" =>
+        {
+            Ok(Command::CreateUser {
+                username: username.clone(),
+                display_name: Some(display_name.clone()),
+            })
+        }
+        [action, username] if action == "disable" || action == "enable" => {
+            Ok(Command::SetUserDisabled {
+                username: username.clone(),
+                disabled: action == "disable",
+            })
+        }
+        _ => Err(USAGE.to_string()),
+    }
+}
+
+async fn find_user(users: &UserRepository, username: &str) -> Result<User, Box<dyn Error>> {
+    users
+        .find_by_username(username)
+        .await?
+        .ok_or_else(|| format!("User '{}' not found", username).into())
+}
+
+pub(crate) fn parse_args(args: impl IntoIterator<Item = String>) -> Result<Command, String> {
+    let mut args = args.into_iter();
+    let Some(command) = args.next() else {
+        return Ok(Command::Serve);
+    };
+    let rest: Vec<String> = args.collect();
+    match command.as_str() {
+        "serve" if rest.is_empty() => Ok(Command::Serve),
+        "backup" => match rest.as_slice() {
+            [] => Ok(Command::Backup { output_dir: None }),
+            [flag, dir] if flag == "--output" => Ok(Command::Backup {
+                output_dir: Some(PathBuf::from(dir)),
+            }),
+            _ => Err(USAGE.to_string()),
+        },
+        "restore" => match rest.as_slice() {
+            [archive] => Ok(Command::Restore {
+                archive: PathBuf::from(archive),
+                dry_run: false,
+            }),
+            [archive, flag] | [flag, archive] if flag == "--dry-run" => Ok(Command::Restore {
+                archive: PathBuf::from(archive),
+                dry_run: true,
+            }),
+            _ => Err(USAGE.to_string()),
+        },
+        "generate-key" => match rest.as_slice() {
+            [key_id] => Ok(Command::GenerateKey {
+                key_id: key_id.clone(),
+            }),
+            _ => Err(USAGE.to_string()),
+        },
+        "rotate-keys" => match rest.as_slice() {
+            [] => Ok(Command::RotateKeys { dry_run: false }),
+            [flag] if flag == "--dry-run" => Ok(Command::RotateKeys { dry_run: true }),
+            _ => Err(USAGE.to_string()),
+        },
+        "token" => parse_token_args(&rest),
+        "user" => parse_user_args(&rest),
+        "help" | "--help" | "-h" => Err(USAGE.to_string()),
+        _ => Err(format!("Unknown command '{}'\n\n{}", command, USAGE)),
+    }
+}
+
+/// Runs a one-off command against the database and prints its summary as JSON
+pub(crate) async fn run(command: Command, app_database: AppDatabase) -> Result<(), Box<dyn Error>> {
+    match command {
+        Command::Serve => unreachable!("the server is started by main"),
+        Command::Backup { output_dir } => {
+            let dir = output_dir.unwrap_or_else(backup_dir_from_env);
+            let summary = write_backup_file(&app_database, &dir).await?;
+            println!("{}", serde_json::to_string_pretty(&summary)?);
+        }
+        Command::Restore { archive, dry_run } => {
+            info!("Validating backup {}", archive.display());
+            let backup = read_backup(&fs::read(&archive)?)?;
+            info!(
+                "Backup from {} (schema v{}) is valid",
+                backup.manifest.created_at, backup.manifest.schema_version
+            );
+            let summary = restore_backup(&app_database, backup, dry_run).await?;
+            println!("{}", serde_json::to_string_pretty(&summary)?);
+        }
+        Command::GenerateKey { key_id } => {
+            println!("{}", FieldKeyring::generate_keyfile_line(&key_id)?);
+        }
+        Command::RotateKeys { dry_run } => {
+            let summary = rotate_encryption_keys(&app_database, dry_run).await?;
+            println!("{}", serde_json::to_string_pretty(&summary)?);
+        }
+        Command::CreateToken {
+            name,
+            username,
+            scopes,
+        } => {
+            ensure_user_vaults(&app_database).await?;
+            let user = find_user(&UserRepository::new(app_database.clone()), &username).await?;
+            if user.disabled {
+                return Err(format!("User '{}' is disabled", user.username).into());
+            }
+            let new_token = generate_access_token();
+            let token = AccessTokenRepository::new(app_database)
+                .create_token(user.id, name, scopes, new_token.hash, new_token.prefix)
+                .await?;
+            let created = CreatedAccessTokenResponse {
+                token: new_token.secret,
+                details: AccessTokenResponse::from(token),
+            };
+            println!("{}", serde_json::to_string_pretty(&created)?);
+        }
+        Command::ListTokens => {
+            ensure_user_vaults(&app_database).await?;
+            let tokens = AccessTokenRepository::new(app_database).find_all().await?;
+            println!("{}", serde_json::to_string_pretty(&AccessTokenResponse::from_vec(tokens))?);
+        }
+        Command::RevokeToken { id } => {
+            match AccessTokenRepository::new(app_database).revoke_by_id(id).await? {
+                Some(token) => {
+                    println!("{}", serde_json::to_string_pretty(&AccessTokenResponse::from(token))?)
+                }
+                None => return Err(format!("Access token {} not found or already revoked", id).into()),
+            }
+        }
+        Command::CreateUser {
+            username,
+            display_name,
+        } => {
+            ensure_user_vaults(&app_database).await?;
+            let username = normalize_username(&username)?;
+            let users = UserRepository::new(app_database);
+            if users.find_by_username(&username).await?.is_some() {
+                return Err(format!("User '{}' already exists", username).into());
+            }
+            let user = users.create_user(&username, display_name).await?;
+            println!("{}", serde_json::to_string_pretty(&UserResponse::from(user))?);
+        }
+        Command::ListUsers => {
+            ensure_user_vaults(&app_database).await?;
+            let users = UserRepository::new(app_database).find_all().await?;
+            println!("{}", serde_json::to_string_pretty(&UserResponse::from_vec(users))?);
+        }
+        Command::SetUserDisabled { username, disabled } => {
+            let users = UserRepository::new(app_database);
+            let user = find_user(&users, &username).await?;
+            if let Some(user) = users.set_disabled(user.id, disabled).await? {
+                println!("{}", serde_json::to_string_pretty(&UserResponse::from(user))?);
+            }
+        }
+    }
+    Ok(())
+}
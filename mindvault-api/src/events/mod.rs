@@ -0,0 +1,261 @@
+use crate::webhooks::{TaskChange, WebhookOutboxRelay};
+use chrono::Utc;
+use futures_util::stream::{self, Stream, StreamExt};
+use mindvault_core::models::AppDatabase;
+use mindvault_core::task_watcher::{ETaskStreamChange, TaskChangeStream};
+use mindvault_shared::models::events_model::{ChangeEvent, FeedMessage, TASK_ENTITY_TYPE};
+use mindvault_shared::models::saved_filters_model::TaskFilterCriteria;
+use mindvault_shared::models::tasks_model::{ETaskStatus, TaskResponse};
+use mindvault_shared::models::webhooks_model::ETaskEvent;
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::broadcast;
+use tokio::sync::broadcast::error::RecvError;
+use tracing::{info, warn};
+
+const HISTORY_SIZE: usize = 1000;
+const CHANNEL_CAPACITY: usize = 256;
+const REOPEN_DELAY: Duration = Duration::from_secs(5);
+
+/// Which events a subscriber wants; empty lists match everything. Private tasks only match
+/// for `owner_id`; workspace tasks match here and are checked against the subscriber's current
+/// membership when delivered, see `EventService::subscribe`.
+#[derive(Debug, Default)]
+pub(crate) struct EventFilter {
+    pub(crate) owner_id: i64,
+    pub(crate) entity_types: Vec<String>,
+    pub(crate) events: Vec<ETaskEvent>,
+    pub(crate) criteria: Option<TaskFilterCriteria>,
+}
+
+impl EventFilter {
+    fn matches(&self, event: &ChangeEvent) -> bool {
+        let Some(task) = &event.task else {
+            return false;
+        };
+        let visible = task.workspace_id.is_some() || task.owner_id == self.owner_id;
+        visible
+            && (self.entity_types.is_empty() || self.entity_types.contains(&event.entity_type))
+            && (self.events.is_empty() || self.events.contains(&event.event))
+            && self
+                .criteria
+                .as_ref()
+                .is_none_or(|criteria| criteria.matches(task))
+    }
+}
+
+/// Live feed of task changes with resumable ids.
+///
+/// When MongoDB supports change streams the feed follows the tasks collection, so writes
+/// from other processes (such as the MCP server) show up too. Otherwise it falls back to the
+/// changes made through this API. The same goes for the webhook outbox, see
+/// `WebhookOutboxRelay`. Ids have the form `<epoch>-<sequence>`, where the epoch
+/// changes on every start; the last `HISTORY_SIZE` events can be resumed, anything older or
+/// from another epoch gets a reset message instead.
+pub(crate) struct ChangeFeed {
+    sender: broadcast::Sender<ChangeEvent>,
+    history: Mutex<VecDeque<ChangeEvent>>,
+    epoch: i64,
+    sequence: AtomicU64,
+    database_stream_active: AtomicBool,
+    outbox_relay: Arc<WebhookOutboxRelay>,
+}
+
+impl ChangeFeed {
+    fn new(outbox_relay: Arc<WebhookOutboxRelay>) -> Self {
+        let (sender, _) = broadcast::channel(CHANNEL_CAPACITY);
+        Self {
+            sender,
+            history: Mutex::new(VecDeque::with_capacity(HISTORY_SIZE)),
+            epoch: Utc::now().timestamp_millis(),
+            sequence: AtomicU64::new(0),
+            database_stream_active: AtomicBool::new(false),
+            outbox_relay,
+        }
+    }
+
+    /// Creates the feed and starts following MongoDB change streams, for the feed and the
+    /// webhook outbox, if they are available
+    pub(crate) async fn start(app_database: AppDatabase) -> Arc<Self> {
+        let outbox_relay = WebhookOutboxRelay::start(app_database.clone()).await;
+        let change_feed = Arc::new(ChangeFeed::new(outbox_relay));
+        match TaskChangeStream::open(&app_database, None).await {
+            Ok(stream) => {
+                info!("Change feed following MongoDB change streams");
+                change_feed.database_stream_active.store(true, Ordering::SeqCst);
+                tokio::spawn(change_feed.clone().follow(app_database, stream));
+            }
+            Err(e) => {
+                info!("Change streams unavailable ({}); using the in-process change feed", e);
+            }
+        }
+        change_feed
+    }
+
+    pub(crate) fn source(&self) -> &'static str {
+        if self.database_stream_active.load(Ordering::SeqCst) {
+            "changeStream"
+        } else {
+            "inProcess"
+        }
+    }
+
+    async fn follow(self: Arc<Self>, app_database: AppDatabase, mut stream: TaskChangeStream) {
+        loop {
+            match stream.next().await {
+                Some(Ok(change)) => self.emit_stream_change(change),
+                Some(Err(e)) => {
+                    warn!("Task change stream failed: {:?}", e);
+                    tokio::time::sleep(REOPEN_DELAY).await;
+                    match TaskChangeStream::open(&app_database, stream.resume_token()).await {
+                        Ok(reopened) => stream = reopened,
+                        Err(e) => {
+                            warn!("Could not reopen task change stream, falling back to in-process events: {:?}", e);
+                            self.database_stream_active.store(false, Ordering::SeqCst);
+                            return;
+                        }
+                    }
+                }
+                None => {
+                    warn!("Task change stream was invalidated, falling back to in-process events");
+                    self.database_stream_active.store(false, Ordering::SeqCst);
+                    return;
+                }
+            }
+        }
+    }
+
+    fn emit_stream_change(&self, change: ETaskStreamChange) {
+        match change {
+            ETaskStreamChange::Inserted(task) => {
+                self.emit(ETaskEvent::Created, task.id, Some(TaskResponse::from(task)));
+            }
+            ETaskStreamChange::Updated {
+                task,
+                changed_fields,
+                ..
+            } => {
+                let changed = |field: &str| changed_fields.iter().any(|f| f == field);
+                let task_id = task.id;
+                if changed("deleted") && task.deleted == Some(true) {
+                    self.emit(ETaskEvent::Deleted, task_id, Some(TaskResponse::from(task)));
+                    return;
+                }
+                let completed = changed("status") && task.status == ETaskStatus::Completed;
+                let task = TaskResponse::from(task);
+                self.emit(ETaskEvent::Updated, task_id, Some(task.clone()));
+                if completed {
+                    self.emit(ETaskEvent::Completed, task_id, Some(task));
+                }
+            }
+        }
+    }
+
+    /// Whether task webhooks are queued from the change stream rather than by the task service
+    pub(crate) fn relays_webhooks(&self) -> bool {
+        self.outbox_relay.is_active()
+    }
+
+    /// Publishes changes made through this API; ignored while change streams report them
+    pub(crate) fn publish_task_changes(&self, changes: &[TaskChange]) {
+        if self.database_stream_active.load(Ordering::SeqCst) {
+            return;
+        }
+        for change in changes {
+            let task = change.after.as_ref().or(change.before.as_ref());
+            self.emit(change.event, change.task_id, task.cloned().map(TaskResponse::from));
+        }
+    }
+
+    fn emit(&self, event: ETaskEvent, entity_id: i64, task: Option<TaskResponse>) {
+        let mut history = self.history.lock().expect("change feed history lock");
+        let sequence = self.sequence.fetch_add(1, Ordering::SeqCst) + 1;
+        let change = ChangeEvent {
+            id: format!("{}-{}", self.epoch, sequence),
+            sequence,
+            entity_type: TASK_ENTITY_TYPE.to_string(),
+            entity_id,
+            event,
+            occurred_at: Utc::now().to_rfc3339(),
+            task,
+        };
+        if history.len() == HISTORY_SIZE {
+            history.pop_front();
+        }
+        history.push_back(change.clone());
+        // Sending fails only when nobody is listening
+        let _ = self.sender.send(change);
+    }
+
+    /// Events after `last_event_id` still in the history, or `None` when it cannot be resumed
+    fn backlog(&self, last_event_id: &str, history: &VecDeque<ChangeEvent>) -> Option<Vec<ChangeEvent>> {
+        let (epoch, sequence) = last_event_id.split_once('-')?;
+        let epoch: i64 = epoch.parse().ok()?;
+        let sequence: u64 = sequence.parse().ok()?;
+        if epoch != self.epoch {
+            return None;
+        }
+        let oldest = history.front().map(|e| e.sequence).unwrap_or(sequence + 1);
+        if sequence + 1 < oldest {
+            return None;
+        }
+        Some(
+            history
+                .iter()
+                .filter(|event| event.sequence > sequence)
+                .cloned()
+                .collect(),
+        )
+    }
+
+    /// Subscribes to the feed, first replaying what was missed since `last_event_id`
+    pub(crate) fn subscribe(
+        &self,
+        last_event_id: Option<&str>,
+        filter: EventFilter,
+    ) -> impl Stream<Item = FeedMessage> + Send + use<> {
+        // Events are recorded and sent under the history lock, so subscribing while holding it
+        // means the receiver starts exactly after the last event in the history
+        let history = self.history.lock().expect("change feed history lock");
+        let receiver = self.sender.subscribe();
+        let mut initial = Vec::new();
+        if let Some(last_event_id) = last_event_id {
+            match self.backlog(last_event_id, &history) {
+                Some(backlog) => {
+                    initial.extend(backlog.into_iter().map(|event| FeedMessage::Change(Box::new(event))));
+                }
+                None => initial.push(FeedMessage::Reset {
+                    reason: format!("event {} can no longer be resumed", last_event_id),
+                }),
+            }
+        }
+        drop(history);
+        let filter = Arc::new(filter);
+        initial.retain(|message| match message {
+            FeedMessage::Change(event) => filter.matches(event),
+            FeedMessage::Reset { .. } => true,
+        });
+
+        let live = stream::unfold(receiver, move |mut receiver| {
+            let filter = filter.clone();
+            async move {
+                loop {
+                    match receiver.recv().await {
+                        Ok(event) if filter.matches(&event) => {
+                            return Some((FeedMessage::Change(Box::new(event)), receiver));
+                        }
+                        Ok(_) => continue,
+                        Err(RecvError::Lagged(missed)) => {
+                            let reason = format!("subscriber fell behind by {} events", missed);
+                            return Some((FeedMessage::Reset { reason }, receiver));
+                        }
+                        Err(RecvError::Closed) => return None,
+                    }
+                }
+            }
+        });
+        stream::iter(initial).chain(live)
+    }
+}
@@ -2,28 +2,62 @@ mod router;
 mod services;
 mod models;
 mod response_macro;
+mod scheduler;
+mod notifications;
+mod webhooks;
+mod events;
+mod cli;
+mod auth;
 
 use mindvault_core::db::bootstrap_db;
+use mindvault_core::encryption::check_encryption_keys;
 use mindvault_core::models::AppDatabase;
+use mindvault_core::ownership::ensure_user_vaults;
 use mindvault_shared::logger::init_logger;
 use std::error::Error;
 use tokio::net::TcpListener;
 use tracing::info;
+use crate::cli::Command;
+use crate::events::ChangeFeed;
 use crate::router::MindVaultRouter;
+use crate::scheduler::jobs::build_scheduler;
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let command = match cli::parse_args(std::env::args().skip(1)) {
+        Ok(command) => command,
+        Err(usage) => {
+            eprintln!("{}", usage);
+            std::process::exit(2);
+        }
+    };
     let log_guard = init_logger();
-    info!("--- Starting MindVault Service ---");
     let db_client = bootstrap_db().await.expect("Failed to connect to db");
-    bootstrap_server(db_client).await?;
-    info!("--- MindVault Service Stopped ---");
+    match command {
+        Command::Serve => {
+            if let Err(e) = check_encryption_keys(&db_client).await {
+                eprintln!("Refusing to start: {}", e);
+                std::process::exit(1);
+            }
+            if let Err(e) = ensure_user_vaults(&db_client).await {
+                eprintln!("Refusing to start: could not prepare user vaults: {}", e);
+                std::process::exit(1);
+            }
+            info!("--- Starting MindVault Service ---");
+            bootstrap_server(db_client).await?;
+            info!("--- MindVault Service Stopped ---");
+        }
+        command => cli::run(command, db_client).await?,
+    }
     drop(log_guard);
     Ok(())
 }
 
 async fn bootstrap_server(db_client: AppDatabase) -> Result<(), Box<dyn Error>> {
-    let app_router = MindVaultRouter::new(db_client);
+    let scheduler = build_scheduler(db_client.clone());
+    tokio::spawn(scheduler.run());
+    let change_feed = ChangeFeed::start(db_client.clone()).await;
+    let app_router = MindVaultRouter::new(db_client, change_feed);
     let mind_vault_router = app_router.get_router();
     let listener = TcpListener::bind("127.0.0.1:4500").await?;
     info!("Listening on {}", &listener.local_addr()?);
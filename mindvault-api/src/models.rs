@@ -1,5 +1,29 @@
 use axum::http::StatusCode;
 use axum::Json;
+use mindvault_shared::models::review_templates_model::{FieldError, ValidationErrorResponse};
 
 pub(crate) type ApiResponse<T> = Result<Json<T>, (StatusCode, String)>;
-pub(crate) type ApiTextResponse = Result<String, (StatusCode, String)>;
\ No newline at end of file
+pub(crate) type ApiTextResponse = Result<String, (StatusCode, String)>;
+pub(crate) type ApiValidatedResponse<T> = Result<Json<T>, (StatusCode, Json<ValidationErrorResponse>)>;
+
+/// Builds the error half of an `ApiValidatedResponse` with field-level details
+pub(crate) fn validation_error(
+    status: StatusCode,
+    message: impl Into<String>,
+    errors: Vec<FieldError>,
+) -> (StatusCode, Json<ValidationErrorResponse>) {
+    (
+        status,
+        Json(ValidationErrorResponse {
+            message: message.into(),
+            errors,
+        }),
+    )
+}
+
+/// Lifts a plain `(StatusCode, String)` error into the validated response shape
+pub(crate) fn plain_error(
+    (status, message): (StatusCode, String),
+) -> (StatusCode, Json<ValidationErrorResponse>) {
+    validation_error(status, message, Vec::new())
+}
@@ -0,0 +1,49 @@
+use crate::notifications::{Notifier, NotifyFuture};
+use mindvault_shared::models::notifications_model::NotificationMessage;
+use std::process::Stdio;
+use std::time::Duration;
+use tokio::process::Command;
+
+const COMMAND_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Runs a local program such as `notify-send` or a desktop hook script.
+///
+/// `{title}` and `{body}` in the arguments are replaced with the message, which is also
+/// passed as `MINDVAULT_TITLE`, `MINDVAULT_BODY` and `MINDVAULT_NOTIFICATION_TYPE`.
+pub(crate) struct CommandNotifier {
+    pub(crate) program: String,
+    pub(crate) args: Vec<String>,
+}
+
+impl Notifier for CommandNotifier {
+    fn send<'a>(&'a self, message: &'a NotificationMessage) -> NotifyFuture<'a> {
+        Box::pin(async move {
+            let notification_type = format!("{:?}", message.notification_type);
+            let args = self.args.iter().map(|arg| {
+                arg.replace("{title}", &message.title)
+                    .replace("{body}", &message.body)
+            });
+            let child = Command::new(&self.program)
+                .args(args)
+                .env("MINDVAULT_TITLE", &message.title)
+                .env("MINDVAULT_BODY", &message.body)
+                .env("MINDVAULT_NOTIFICATION_TYPE", notification_type)
+                .stdin(Stdio::null())
+                .stdout(Stdio::null())
+                .stderr(Stdio::piped())
+                .kill_on_drop(true)
+                .output();
+
+            let output = tokio::time::timeout(COMMAND_TIMEOUT, child)
+                .await
+                .map_err(|_| format!("command timed out after {}s", COMMAND_TIMEOUT.as_secs()))?
+                .map_err(|e| format!("failed to run {}: {}", self.program, e))?;
+            if output.status.success() {
+                Ok(())
+            } else {
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                Err(format!("{} exited with {}: {}", self.program, output.status, stderr.trim()))
+            }
+        })
+    }
+}
@@ -0,0 +1,53 @@
+use crate::notifications::{Notifier, NotifyFuture};
+use lettre::message::header::ContentType;
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+use mindvault_core::notification_config::SmtpConfig;
+use mindvault_shared::models::notifications_model::NotificationMessage;
+
+/// Sends the message as a plain-text email through the configured SMTP server
+pub(crate) struct EmailNotifier {
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+    from: String,
+    to: String,
+}
+
+impl EmailNotifier {
+    pub(crate) fn new(smtp: &SmtpConfig, to: &str) -> Result<Self, String> {
+        let builder = if smtp.tls {
+            AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&smtp.host)
+                .map_err(|e| e.to_string())?
+        } else {
+            // Plain connection, e.g. a local development sink
+            AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(&smtp.host)
+        };
+        let mut builder = builder.port(smtp.port);
+        if let (Some(username), Some(password)) = (&smtp.username, &smtp.password) {
+            builder = builder.credentials(Credentials::new(username.clone(), password.clone()));
+        }
+        Ok(Self {
+            transport: builder.build(),
+            from: smtp.from.clone(),
+            to: to.to_string(),
+        })
+    }
+}
+
+impl Notifier for EmailNotifier {
+    fn send<'a>(&'a self, message: &'a NotificationMessage) -> NotifyFuture<'a> {
+        Box::pin(async move {
+            let email = Message::builder()
+                .from(self.from.parse().map_err(|e| format!("invalid sender: {}", e))?)
+                .to(self.to.parse().map_err(|e| format!("invalid recipient: {}", e))?)
+                .subject(&message.title)
+                .header(ContentType::TEXT_PLAIN)
+                .body(message.body.clone())
+                .map_err(|e| e.to_string())?;
+            self.transport
+                .send(email)
+                .await
+                .map(|_| ())
+                .map_err(|e| e.to_string())
+        })
+    }
+}
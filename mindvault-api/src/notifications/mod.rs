@@ -0,0 +1,168 @@
+pub(crate) mod command;
+pub(crate) mod email;
+pub(crate) mod webhook;
+
+use crate::notifications::command::CommandNotifier;
+use crate::notifications::email::EmailNotifier;
+use crate::notifications::webhook::WebhookNotifier;
+use bson::DateTime as BsonDateTime;
+use chrono::Utc;
+use mindvault_core::models::{AppDatabase, DbError};
+use mindvault_core::notification_config::NotificationConfig;
+use mindvault_core::repository::notification_repo::NotificationRepository;
+use mindvault_shared::models::notifications_model::{
+    DeliveryAttempt, EChannelTarget, EDeliveryState, NotificationDelivery, NotificationMessage,
+};
+use std::future::Future;
+use std::pin::Pin;
+use std::time::{Duration, Instant};
+use tracing::{info, warn};
+
+pub(crate) type NotifyFuture<'a> = Pin<Box<dyn Future<Output = Result<(), String>> + Send + 'a>>;
+
+/// A transport that can deliver a notification; errors are kept in the delivery log
+pub(crate) trait Notifier: Send + Sync {
+    fn send<'a>(&'a self, message: &'a NotificationMessage) -> NotifyFuture<'a>;
+}
+
+const SEND_TIMEOUT: Duration = Duration::from_secs(30);
+const DELIVERY_BATCH_SIZE: i64 = 50;
+
+/// Fans messages out to the subscribed channels through the outbox and works the outbox off,
+/// retrying failures with exponential backoff until they are delivered or dead-lettered
+pub(crate) struct NotificationDispatcher {
+    notification_repository: NotificationRepository,
+    config: NotificationConfig,
+    http_client: reqwest::Client,
+}
+
+impl NotificationDispatcher {
+    pub(crate) fn new(app_database: AppDatabase) -> Self {
+        let http_client = reqwest::Client::builder()
+            .timeout(SEND_TIMEOUT)
+            .build()
+            .expect("HTTP client configuration is valid");
+        Self {
+            notification_repository: NotificationRepository::new(app_database),
+            config: NotificationConfig::from_env(),
+            http_client,
+        }
+    }
+
+    pub(crate) fn config(&self) -> &NotificationConfig {
+        &self.config
+    }
+
+    fn notifier_for(&self, target: &EChannelTarget) -> Result<Box<dyn Notifier>, String> {
+        match target {
+            EChannelTarget::Webhook { url, secret } => Ok(Box::new(WebhookNotifier {
+                client: self.http_client.clone(),
+                url: url.clone(),
+                secret: secret.clone(),
+            })),
+            EChannelTarget::Email { to } => Ok(Box::new(EmailNotifier::new(&self.config.smtp, to)?)),
+            EChannelTarget::Command { .. } if !self.config.allow_commands => {
+                Err("command channels are disabled on this server".to_string())
+            }
+            EChannelTarget::Command { program, args } => Ok(Box::new(CommandNotifier {
+                program: program.clone(),
+                args: args.clone(),
+            })),
+        }
+    }
+
+    /// Queues the message for every enabled channel of `owner_ids` subscribed to its type and
+    /// returns how many deliveries were queued; `dedupe_key` keeps the same event from being
+    /// sent twice
+    pub(crate) async fn notify(
+        &self,
+        owner_ids: &[i64],
+        message: NotificationMessage,
+        dedupe_key: Option<String>,
+    ) -> Result<usize, DbError> {
+        let channels = self
+            .notification_repository
+            .find_channels_for_type(message.notification_type, owner_ids)
+            .await?;
+
+        let mut queued = 0;
+        for channel in channels {
+            let delivery = self
+                .notification_repository
+                .enqueue_delivery(channel.id, message.clone(), dedupe_key.clone())
+                .await?;
+            if delivery.is_some() {
+                queued += 1;
+            }
+        }
+        Ok(queued)
+    }
+
+    /// Attempts every delivery whose next attempt is due and returns how many were attempted
+    pub(crate) async fn deliver_pending(&self) -> Result<usize, DbError> {
+        let deliveries = self
+            .notification_repository
+            .find_ready_deliveries(BsonDateTime::now(), DELIVERY_BATCH_SIZE)
+            .await?;
+        let attempted = deliveries.len();
+        for delivery in deliveries {
+            self.deliver(delivery).await?;
+        }
+        Ok(attempted)
+    }
+
+    /// Makes one attempt at a delivery and records the outcome in its log
+    pub(crate) async fn deliver(
+        &self,
+        delivery: NotificationDelivery,
+    ) -> Result<Option<NotificationDelivery>, DbError> {
+        let attempted_at = Utc::now();
+        let started = Instant::now();
+
+        let channel = self
+            .notification_repository
+            .find_channel_for_delivery(delivery.channel_id)
+            .await?;
+        let result = match channel {
+            Some(channel) if channel.enabled => match self.notifier_for(&channel.target) {
+                Ok(notifier) => {
+                    match tokio::time::timeout(SEND_TIMEOUT, notifier.send(&delivery.message)).await
+                    {
+                        Ok(result) => result,
+                        Err(_) => Err(format!("timed out after {}s", SEND_TIMEOUT.as_secs())),
+                    }
+                }
+                Err(e) => Err(e),
+            },
+            Some(_) => Err("channel is disabled".to_string()),
+            None => Err("channel no longer exists".to_string()),
+        };
+
+        let attempts = delivery.attempts.len() as u32 + 1;
+        let (state, next_attempt_at) = match &result {
+            Ok(()) => (EDeliveryState::Delivered, None),
+            Err(_) if attempts >= self.config.max_attempts => (EDeliveryState::DeadLetter, None),
+            Err(_) => {
+                let delay = self.config.retry_delay_seconds(attempts);
+                let next = Utc::now() + chrono::Duration::seconds(delay as i64);
+                (EDeliveryState::Pending, Some(BsonDateTime::from(next)))
+            }
+        };
+        match &result {
+            Ok(()) => info!("Delivered notification {} to channel {}", delivery.id, delivery.channel_id),
+            Err(e) => warn!(
+                "Delivery {} to channel {} failed (attempt {}): {}",
+                delivery.id, delivery.channel_id, attempts, e
+            ),
+        }
+
+        let attempt = DeliveryAttempt {
+            attempted_at: BsonDateTime::from(attempted_at),
+            duration_ms: started.elapsed().as_millis() as i64,
+            error: result.err(),
+        };
+        self.notification_repository
+            .record_attempt(delivery.id, attempt, state, next_attempt_at)
+            .await
+    }
+}
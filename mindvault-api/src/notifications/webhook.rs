@@ -0,0 +1,65 @@
+use crate::notifications::{Notifier, NotifyFuture};
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use mindvault_shared::models::notifications_model::NotificationMessage;
+use serde::Serialize;
+use sha2::Sha256;
+
+pub(crate) const SIGNATURE_HEADER: &str = "X-MindVault-Signature";
+pub(crate) const EVENT_HEADER: &str = "X-MindVault-Event";
+
+/// `sha256=<hex>` HMAC of `payload`, as sent in the signature header
+pub(crate) fn sign_payload(secret: &str, payload: &[u8]) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any size");
+    mac.update(payload);
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct WebhookPayload<'a> {
+    #[serde(flatten)]
+    message: &'a NotificationMessage,
+    sent_at: String,
+}
+
+/// POSTs the message as JSON; receivers verify the body against `X-MindVault-Signature`
+pub(crate) struct WebhookNotifier {
+    pub(crate) client: reqwest::Client,
+    pub(crate) url: String,
+    pub(crate) secret: Option<String>,
+}
+
+impl Notifier for WebhookNotifier {
+    fn send<'a>(&'a self, message: &'a NotificationMessage) -> NotifyFuture<'a> {
+        Box::pin(async move {
+            let payload = WebhookPayload {
+                message,
+                sent_at: Utc::now().to_rfc3339(),
+            };
+            let body = serde_json::to_vec(&payload).map_err(|e| e.to_string())?;
+            let event = serde_json::to_value(message.notification_type)
+                .ok()
+                .and_then(|value| value.as_str().map(str::to_string))
+                .unwrap_or_default();
+
+            let mut request = self
+                .client
+                .post(&self.url)
+                .header(reqwest::header::CONTENT_TYPE, "application/json")
+                .header(EVENT_HEADER, event);
+            if let Some(secret) = &self.secret {
+                request = request.header(SIGNATURE_HEADER, sign_payload(secret, &body));
+            }
+
+            let response = request.body(body).send().await.map_err(|e| e.to_string())?;
+            let status = response.status();
+            if status.is_success() {
+                Ok(())
+            } else {
+                Err(format!("webhook responded with {}", status))
+            }
+        })
+    }
+}
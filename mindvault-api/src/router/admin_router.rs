@@ -0,0 +1,120 @@
+use crate::handle_service_response;
+use crate::models::ApiResponse;
+use crate::services::admin_service::AdminService;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use mindvault_core::models::AppDatabase;
+use mindvault_shared::dtos::user_dtos::CreateUserRequest;
+use mindvault_shared::models::backup_model::BackupSummary;
+use mindvault_shared::models::jobs_model::JobStateResponse;
+use mindvault_shared::models::users_model::UserResponse;
+use std::sync::Arc;
+use tracing::info;
+
+pub(crate) struct AdminRouter {
+    admin_service: Arc<AdminService>,
+}
+
+impl AdminRouter {
+    pub(crate) fn new(app_database: AppDatabase) -> Self {
+        let admin_service = Arc::new(AdminService::new(app_database));
+        Self { admin_service }
+    }
+
+    pub(crate) fn get_routes(&self) -> Router {
+        Router::new()
+            .route("/jobs", get(AdminRouter::get_jobs_handler))
+            .route("/backup", post(AdminRouter::create_backup_handler))
+            .route(
+                "/users",
+                get(AdminRouter::get_users_handler).post(AdminRouter::create_user_handler),
+            )
+            .route("/users/{:id}/disable", post(AdminRouter::disable_user_handler))
+            .route("/users/{:id}/enable", post(AdminRouter::enable_user_handler))
+            .with_state(self.admin_service.clone())
+    }
+
+    async fn get_jobs_handler(
+        State(admin_service): State<Arc<AdminService>>,
+    ) -> ApiResponse<Vec<JobStateResponse>> {
+        info!("Fetching scheduler jobs");
+        handle_service_response!(
+            admin_service.get_jobs().await,
+            "Fetched {} scheduler jobs",
+            |data: &Vec<JobStateResponse>| data.len(),
+            "Failed to get scheduler jobs"
+        )
+    }
+
+    async fn create_backup_handler(
+        State(admin_service): State<Arc<AdminService>>,
+    ) -> ApiResponse<BackupSummary> {
+        info!("Creating database backup");
+        handle_service_response!(
+            admin_service.create_backup().await,
+            "Wrote backup to {}",
+            |data: &BackupSummary| data.path.clone(),
+            "Failed to create backup"
+        )
+    }
+
+    async fn get_users_handler(
+        State(admin_service): State<Arc<AdminService>>,
+    ) -> ApiResponse<Vec<UserResponse>> {
+        info!("Fetching users");
+        handle_service_response!(
+            admin_service.get_users().await,
+            "Fetched {} users",
+            |data: &Vec<UserResponse>| data.len(),
+            "Failed to get users"
+        )
+    }
+
+    async fn create_user_handler(
+        State(admin_service): State<Arc<AdminService>>,
+        Json(payload): Json<CreateUserRequest>,
+    ) -> ApiResponse<UserResponse> {
+        info!("Creating user '{}'", payload.username);
+        match admin_service.create_user(payload).await {
+            Ok(Ok(user)) => Ok(Json(user)),
+            Ok(Err(message)) => Err((StatusCode::BAD_REQUEST, message)),
+            Err(e) => {
+                let error_message = format!("Failed to create user: {:?}", e);
+                Err((StatusCode::INTERNAL_SERVER_ERROR, error_message))
+            }
+        }
+    }
+
+    async fn disable_user_handler(
+        State(admin_service): State<Arc<AdminService>>,
+        Path(id): Path<i64>,
+    ) -> ApiResponse<UserResponse> {
+        info!("Disabling user {}", id);
+        AdminRouter::set_user_disabled(&admin_service, id, true).await
+    }
+
+    async fn enable_user_handler(
+        State(admin_service): State<Arc<AdminService>>,
+        Path(id): Path<i64>,
+    ) -> ApiResponse<UserResponse> {
+        info!("Enabling user {}", id);
+        AdminRouter::set_user_disabled(&admin_service, id, false).await
+    }
+
+    async fn set_user_disabled(
+        admin_service: &AdminService,
+        id: i64,
+        disabled: bool,
+    ) -> ApiResponse<UserResponse> {
+        match admin_service.set_user_disabled(id, disabled).await {
+            Ok(Some(user)) => Ok(Json(user)),
+            Ok(None) => Err((StatusCode::NOT_FOUND, "User not found".to_string())),
+            Err(e) => {
+                let error_message = format!("Failed to update user: {:?}", e);
+                Err((StatusCode::INTERNAL_SERVER_ERROR, error_message))
+            }
+        }
+    }
+}
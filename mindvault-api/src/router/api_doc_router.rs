@@ -0,0 +1,150 @@
+use axum::routing::get;
+use axum::{Json, Router};
+use mindvault_shared::dtos::task_dtos::{
+    BulkCreateTaskRequest, CreateTaskRequest, MoveTaskRequest, QuickAddRequest,
+    ReorderTasksRequest, SearchAndUpdateRequest, UpdateTaskRequest,
+};
+use mindvault_shared::dtos::time_entry_dtos::{LogTimeRequest, TimerRequest};
+use mindvault_shared::models::tasks_model::{
+    ETaskPriority, ETaskStatus, TaskResponse, TaskResponseV2,
+};
+use mindvault_shared::models::time_entries_model::{
+    TaskTimeResponse, TimeEntryResponse, TimerResponse,
+};
+use mindvault_shared::utils::quick_add::{
+    EQuickAddPart, QuickAddParse, QuickAddPart, ReporteeMention,
+};
+use utoipa::openapi::security::{HttpAuthScheme, HttpBuilder, SecurityScheme};
+use utoipa::openapi::OpenApi as OpenApiDocument;
+use utoipa::{Modify, OpenApi};
+use utoipa_scalar::{Scalar, Servable};
+
+/// OpenAPI document built from the task handlers and the shared DTOs
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "MindVault API",
+        description = "Every route needs a personal access token as `Authorization: Bearer <token>`. \
+            `/v2/tasks` takes the same requests but answers with `TaskResponseV2`."
+    ),
+    servers((url = "/v1", description = "Current API")),
+    paths(
+        super::task_router::get_tasks_handler,
+        super::task_router::create_task_handler,
+        super::task_router::get_task_by_id_handler,
+        super::task_router::update_task_handler,
+        super::task_router::delete_task_handler,
+        super::task_router::search_tasks_by_text_handler,
+        super::task_router::search_and_update_handler,
+        super::task_router::bulk_create_tasks_handler,
+        super::task_router::quick_add_handler,
+        super::task_router::move_task_handler,
+        super::task_router::reorder_tasks_handler,
+        super::task_router::start_timer_handler,
+        super::task_router::stop_timer_handler,
+        super::task_router::get_task_time_handler,
+        super::task_router::log_time_handler,
+        super::task_router::delete_time_entry_handler,
+        super::task_router::bulk_delete_by_status_handler,
+    ),
+    components(schemas(
+        ETaskStatus,
+        ETaskPriority,
+        TaskResponse,
+        TaskResponseV2,
+        CreateTaskRequest,
+        BulkCreateTaskRequest,
+        UpdateTaskRequest,
+        SearchAndUpdateRequest,
+        QuickAddRequest,
+        MoveTaskRequest,
+        ReorderTasksRequest,
+        QuickAddParse,
+        QuickAddPart,
+        EQuickAddPart,
+        ReporteeMention,
+        TimerRequest,
+        LogTimeRequest,
+        TimerResponse,
+        TimeEntryResponse,
+        TaskTimeResponse,
+    )),
+    modifiers(&BearerToken),
+    security(("bearer_token" = [])),
+    tags((name = "tasks", description = "Tasks in the caller's vault or a shared workspace"))
+)]
+pub(crate) struct ApiDoc;
+
+struct BearerToken;
+
+impl Modify for BearerToken {
+    fn modify(&self, openapi: &mut OpenApiDocument) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+        components.add_security_scheme(
+            "bearer_token",
+            SecurityScheme::Http(HttpBuilder::new().scheme(HttpAuthScheme::Bearer).build()),
+        );
+    }
+}
+
+/// Serves the OpenAPI document and a docs UI for it; neither needs a token
+pub(crate) struct ApiDocRouter;
+
+impl ApiDocRouter {
+    pub(crate) fn get_routes() -> Router {
+        Router::new()
+            .route("/openapi.json", get(ApiDocRouter::openapi_handler))
+            .merge(Scalar::with_url("/docs", ApiDoc::openapi()))
+    }
+
+    async fn openapi_handler() -> Json<OpenApiDocument> {
+        Json(ApiDoc::openapi())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ApiDoc;
+    use crate::router::task_router::TASK_ROUTES;
+    use utoipa::OpenApi;
+
+    /// Relative to the `/v1` server the spec declares
+    const TASKS_PREFIX: &str = "/tasks";
+
+    #[test]
+    fn every_task_route_is_in_the_spec() {
+        let spec = ApiDoc::openapi();
+        let mut missing = Vec::new();
+        for (path, methods) in TASK_ROUTES {
+            let path = match path {
+                "/" => TASKS_PREFIX.to_string(),
+                _ => format!("{}{}", TASKS_PREFIX, path.replace("{:", "{")),
+            };
+            for method in methods {
+                let item = spec.paths.paths.get(&path);
+                let operation = item.and_then(|item| match method.as_str() {
+                    "GET" => item.get.as_ref(),
+                    "POST" => item.post.as_ref(),
+                    "PUT" => item.put.as_ref(),
+                    "DELETE" => item.delete.as_ref(),
+                    _ => item.patch.as_ref(),
+                });
+                if operation.is_none() {
+                    missing.push(format!("{} {}", method, path));
+                }
+            }
+        }
+        assert!(missing.is_empty(), "routes missing from the OpenAPI spec: {:?}", missing);
+    }
+
+    #[test]
+    fn task_enums_list_their_values() {
+        let json = ApiDoc::openapi().to_json().expect("spec serializes");
+        for value in [
+            "NotStarted", "InProgress", "Completed", "Cancelled", "Low", "Normal", "High", "Urgent",
+        ] {
+            assert!(json.contains(&format!("\"{}\"", value)), "{} missing", value);
+        }
+        assert!(json.contains("YYYY-MM-DD"), "accepted date formats missing");
+    }
+}
@@ -0,0 +1,82 @@
+use crate::auth::Caller;
+use crate::handle_service_response;
+use crate::models::ApiResponse;
+use crate::services::access_token_service::AccessTokenService;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::routing::{delete, get};
+use axum::{Extension, Json, Router};
+use mindvault_shared::dtos::access_token_dtos::CreateAccessTokenRequest;
+use mindvault_shared::models::access_tokens_model::{
+    AccessTokenResponse, CreatedAccessTokenResponse,
+};
+use std::sync::Arc;
+use tracing::info;
+
+pub(crate) struct AuthRouter {
+    access_token_service: Arc<AccessTokenService>,
+}
+
+impl AuthRouter {
+    pub(crate) fn new(access_token_service: Arc<AccessTokenService>) -> Self {
+        Self {
+            access_token_service,
+        }
+    }
+
+    pub(crate) fn get_routes(&self) -> Router {
+        Router::new()
+            .route(
+                "/tokens",
+                get(AuthRouter::get_tokens_handler).post(AuthRouter::create_token_handler),
+            )
+            .route("/tokens/{:id}", delete(AuthRouter::revoke_token_handler))
+            .with_state(self.access_token_service.clone())
+    }
+
+    async fn get_tokens_handler(
+        State(access_token_service): State<Arc<AccessTokenService>>,
+    ) -> ApiResponse<Vec<AccessTokenResponse>> {
+        info!("Fetching access tokens");
+        handle_service_response!(
+            access_token_service.get_tokens().await,
+            "Found {} access tokens",
+            |data: &Vec<AccessTokenResponse>| data.len(),
+            "Failed to get access tokens"
+        )
+    }
+
+    async fn create_token_handler(
+        State(access_token_service): State<Arc<AccessTokenService>>,
+        Extension(caller): Extension<Caller>,
+        Json(payload): Json<CreateAccessTokenRequest>,
+    ) -> ApiResponse<CreatedAccessTokenResponse> {
+        info!("Creating access token '{}'", payload.name);
+        match access_token_service.create_token(caller.user_id, payload).await {
+            Ok(Ok(created)) => Ok(Json(created)),
+            Ok(Err(message)) => Err((StatusCode::BAD_REQUEST, message)),
+            Err(e) => {
+                let error_message = format!("Failed to create access token: {:?}", e);
+                Err((StatusCode::INTERNAL_SERVER_ERROR, error_message))
+            }
+        }
+    }
+
+    async fn revoke_token_handler(
+        State(access_token_service): State<Arc<AccessTokenService>>,
+        Path(id): Path<i64>,
+    ) -> ApiResponse<AccessTokenResponse> {
+        info!("Revoking access token {}", id);
+        match access_token_service.revoke_token(id).await {
+            Ok(Some(token)) => Ok(Json(token)),
+            Ok(None) => Err((
+                StatusCode::NOT_FOUND,
+                "Access token not found or already revoked".to_string(),
+            )),
+            Err(e) => {
+                let error_message = format!("Failed to revoke access token: {:?}", e);
+                Err((StatusCode::INTERNAL_SERVER_ERROR, error_message))
+            }
+        }
+    }
+}
@@ -0,0 +1,48 @@
+use crate::auth::workspace_access::split_denial;
+use crate::auth::Caller;
+use crate::events::ChangeFeed;
+use crate::handle_service_response;
+use crate::models::ApiResponse;
+use crate::services::task_service::TaskService;
+use axum::extract::{Query, State};
+use axum::routing::get;
+use axum::{Extension, Router};
+use mindvault_core::models::AppDatabase;
+use mindvault_shared::dtos::task_dtos::TaskVaultParams;
+use mindvault_shared::models::boards_model::BoardResponse;
+use std::sync::Arc;
+use tracing::info;
+
+/// Kanban view of the caller's private vault or `?workspace=<id>`: a column per workflow
+/// status, or per built-in status without a workflow. Tasks are moved with
+/// `POST /tasks/{id}/move`.
+pub(crate) struct BoardRouter {
+    task_service: Arc<TaskService>,
+}
+
+impl BoardRouter {
+    pub(crate) fn new(app_database: AppDatabase, change_feed: Arc<ChangeFeed>) -> Self {
+        let task_service = Arc::new(TaskService::new(app_database, change_feed));
+        Self { task_service }
+    }
+
+    pub(crate) fn get_routes(&self) -> Router {
+        Router::new()
+            .route("/", get(BoardRouter::get_board_handler))
+            .with_state(self.task_service.clone())
+    }
+
+    async fn get_board_handler(
+        State(task_service): State<Arc<TaskService>>,
+        Extension(caller): Extension<Caller>,
+        Query(vault): Query<TaskVaultParams>,
+    ) -> ApiResponse<BoardResponse> {
+        info!("Fetching board");
+        handle_service_response!(
+            split_denial(task_service.get_board(caller.user_id, vault.workspace).await)?,
+            "Built a board with {} columns",
+            |data: &BoardResponse| data.columns.len(),
+            "Failed to get board"
+        )
+    }
+}
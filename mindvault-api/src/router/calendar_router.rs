@@ -0,0 +1,53 @@
+use crate::auth::Caller;
+use crate::services::calendar_service::CalendarService;
+use axum::extract::{Query, State};
+use axum::http::{header, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::{Extension, Router};
+use mindvault_core::models::AppDatabase;
+use mindvault_shared::dtos::calendar_dtos::CalendarParams;
+use mindvault_shared::models::transfer_model::ETransferFormat;
+use std::sync::Arc;
+use tracing::info;
+
+pub(crate) struct CalendarRouter {
+    calendar_service: Arc<CalendarService>,
+}
+
+impl CalendarRouter {
+    pub(crate) fn new(app_database: AppDatabase) -> Self {
+        let calendar_service = Arc::new(CalendarService::new(app_database));
+        Self { calendar_service }
+    }
+
+    pub(crate) fn get_routes(&self) -> Router {
+        Router::new()
+            .route("/calendar.ics", get(CalendarRouter::get_calendar_handler))
+            .with_state(self.calendar_service.clone())
+    }
+
+    // ?filter_id=3&tag=work&include_events=true
+    async fn get_calendar_handler(
+        State(calendar_service): State<Arc<CalendarService>>,
+        Extension(caller): Extension<Caller>,
+        Query(params): Query<CalendarParams>,
+    ) -> Result<Response, (StatusCode, String)> {
+        info!("Rendering calendar feed with params {:?}", params);
+        let calendar = calendar_service
+            .get_calendar(caller.user_id, &params, &caller.dates)
+            .await;
+        match calendar {
+            Ok(Some(calendar)) => Ok((
+                [(header::CONTENT_TYPE, ETransferFormat::Ics.content_type())],
+                calendar,
+            )
+                .into_response()),
+            Ok(None) => Err((StatusCode::NOT_FOUND, "Saved filter not found".to_string())),
+            Err(e) => {
+                let error_message = format!("Failed to render calendar: {:?}", e);
+                Err((StatusCode::INTERNAL_SERVER_ERROR, error_message))
+            }
+        }
+    }
+}
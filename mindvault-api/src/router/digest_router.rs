@@ -0,0 +1,81 @@
+use crate::auth::Caller;
+use crate::services::digest_service::DigestService;
+use axum::extract::{Query, State};
+use axum::http::{header, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::{Extension, Json, Router};
+use mindvault_core::digest::{render_markdown, render_text, DigestOptions};
+use mindvault_core::models::AppDatabase;
+use mindvault_shared::dtos::digest_dtos::DigestParams;
+use mindvault_shared::models::digest_model::EDigestFormat;
+use mindvault_shared::utils::time_zone::parse_time_zone;
+use std::sync::Arc;
+use tracing::info;
+
+pub(crate) struct DigestRouter {
+    digest_service: Arc<DigestService>,
+}
+
+impl DigestRouter {
+    pub(crate) fn new(app_database: AppDatabase) -> Self {
+        let digest_service = Arc::new(DigestService::new(app_database));
+        Self { digest_service }
+    }
+
+    pub(crate) fn get_routes(&self) -> Router {
+        Router::new()
+            .route("/", get(DigestRouter::get_digest_handler))
+            .with_state(self.digest_service.clone())
+    }
+
+    // ?period=weekly&format=markdown&time_zone=Europe/Berlin&stalled_days=5
+    async fn get_digest_handler(
+        State(digest_service): State<Arc<DigestService>>,
+        Extension(caller): Extension<Caller>,
+        Query(params): Query<DigestParams>,
+    ) -> Result<Response, (StatusCode, String)> {
+        info!("Generating digest with params {:?}", params);
+
+        let mut options = DigestOptions::from_env();
+        options.time_zone = match params.time_zone {
+            Some(time_zone) => {
+                parse_time_zone(&time_zone).map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+                time_zone
+            }
+            None => caller.dates.time_zone.clone(),
+        };
+        if let Some(stalled_days) = params.stalled_days {
+            if stalled_days < 1 {
+                return Err((
+                    StatusCode::BAD_REQUEST,
+                    "stalled_days must be at least 1".to_string(),
+                ));
+            }
+            options.stalled_days = stalled_days;
+        }
+
+        let digest = digest_service
+            .get_digest(caller.user_id, params.period.unwrap_or_default(), &options)
+            .await
+            .map_err(|e| {
+                let error_message = format!("Failed to generate digest: {:?}", e);
+                (StatusCode::INTERNAL_SERVER_ERROR, error_message)
+            })?;
+
+        let response = match params.format.unwrap_or_default() {
+            EDigestFormat::Json => Json(digest).into_response(),
+            EDigestFormat::Markdown => (
+                [(header::CONTENT_TYPE, "text/markdown; charset=utf-8")],
+                render_markdown(&digest),
+            )
+                .into_response(),
+            EDigestFormat::Text => (
+                [(header::CONTENT_TYPE, "text/plain; charset=utf-8")],
+                render_text(&digest),
+            )
+                .into_response(),
+        };
+        Ok(response)
+    }
+}
@@ -0,0 +1,141 @@
+use crate::auth::Caller;
+use crate::events::{ChangeFeed, EventFilter};
+use crate::services::event_service::EventService;
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::extract::{Query, State};
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::sse::{Event, KeepAlive, Sse};
+use axum::response::Response;
+use axum::routing::get;
+use axum::{Extension, Router};
+use futures_util::{Stream, StreamExt};
+use mindvault_core::models::AppDatabase;
+use mindvault_shared::dtos::event_dtos::EventStreamParams;
+use mindvault_shared::models::events_model::{FeedMessage, TASK_ENTITY_TYPE};
+use std::convert::Infallible;
+use std::sync::Arc;
+use tracing::{info, warn};
+
+pub(crate) struct EventRouter {
+    event_service: Arc<EventService>,
+}
+
+const LAST_EVENT_ID_HEADER: &str = "last-event-id";
+const RESET_EVENT: &str = "reset";
+
+impl EventRouter {
+    pub(crate) fn new(app_database: AppDatabase, change_feed: Arc<ChangeFeed>) -> Self {
+        let event_service = Arc::new(EventService::new(app_database, change_feed));
+        Self { event_service }
+    }
+
+    pub(crate) fn get_routes(&self) -> Router {
+        Router::new()
+            .route("/", get(EventRouter::sse_handler))
+            .route("/ws", get(EventRouter::websocket_handler))
+            .with_state(self.event_service.clone())
+    }
+
+    async fn resolve_filter(
+        event_service: &EventService,
+        owner_id: i64,
+        params: &EventStreamParams,
+    ) -> Result<EventFilter, (StatusCode, String)> {
+        let filter = match event_service.resolve_filter(owner_id, params).await {
+            Ok(Ok(filter)) => filter,
+            Ok(Err(message)) => return Err((StatusCode::BAD_REQUEST, message)),
+            Err(e) => {
+                let error_message = format!("Failed to load event filter: {:?}", e);
+                return Err((StatusCode::INTERNAL_SERVER_ERROR, error_message));
+            }
+        };
+        if let Some(unknown) = filter
+            .entity_types
+            .iter()
+            .find(|entity| entity.as_str() != TASK_ENTITY_TYPE)
+        {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                format!("Unknown entity type '{}'", unknown),
+            ));
+        }
+        Ok(filter)
+    }
+
+    // ?entity=task&events=task.created,task.completed&filter_id=2&status=InProgress
+    async fn sse_handler(
+        State(event_service): State<Arc<EventService>>,
+        Extension(caller): Extension<Caller>,
+        headers: HeaderMap,
+        Query(params): Query<EventStreamParams>,
+    ) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, (StatusCode, String)> {
+        let filter = EventRouter::resolve_filter(&event_service, caller.user_id, &params).await?;
+        let last_event_id = headers
+            .get(LAST_EVENT_ID_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string)
+            .or(params.last_event_id);
+        info!(
+            "Opening event stream from {} (resuming after {:?})",
+            event_service.source(),
+            last_event_id
+        );
+
+        let stream = event_service
+            .subscribe(last_event_id.as_deref(), filter)
+            .map(|message| {
+                let event = match &message {
+                    FeedMessage::Change(change) => Event::default()
+                        .id(change.id.clone())
+                        .event(change.event.as_str()),
+                    FeedMessage::Reset { .. } => Event::default().event(RESET_EVENT),
+                };
+                Ok(event
+                    .json_data(&message)
+                    .unwrap_or_else(|_| Event::default().event(RESET_EVENT)))
+            });
+        Ok(Sse::new(stream).keep_alive(KeepAlive::default()))
+    }
+
+    /// Same feed as the SSE endpoint; resume with `?last_event_id=`
+    async fn websocket_handler(
+        State(event_service): State<Arc<EventService>>,
+        Extension(caller): Extension<Caller>,
+        Query(params): Query<EventStreamParams>,
+        upgrade: WebSocketUpgrade,
+    ) -> Result<Response, (StatusCode, String)> {
+        let filter = EventRouter::resolve_filter(&event_service, caller.user_id, &params).await?;
+        let feed = event_service.subscribe(params.last_event_id.as_deref(), filter);
+        Ok(upgrade.on_upgrade(move |socket| EventRouter::forward_to_socket(socket, feed)))
+    }
+
+    async fn forward_to_socket(
+        mut socket: WebSocket,
+        feed: impl Stream<Item = FeedMessage> + Send + 'static,
+    ) {
+        let mut feed = Box::pin(feed);
+        loop {
+            tokio::select! {
+                message = feed.next() => {
+                    let Some(message) = message else { break };
+                    let text = match serde_json::to_string(&message) {
+                        Ok(text) => text,
+                        Err(e) => {
+                            warn!("Failed to serialize change event: {}", e);
+                            continue;
+                        }
+                    };
+                    if socket.send(Message::Text(text.into())).await.is_err() {
+                        break;
+                    }
+                }
+                incoming = socket.recv() => {
+                    match incoming {
+                        Some(Ok(Message::Close(_))) | None | Some(Err(_)) => break,
+                        Some(Ok(_)) => {}
+                    }
+                }
+            }
+        }
+    }
+}
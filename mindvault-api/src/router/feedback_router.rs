@@ -0,0 +1,190 @@
+use crate::auth::Caller;
+use crate::handle_service_response;
+use crate::models::{ApiResponse, ApiTextResponse};
+use crate::services::feedback_service::FeedbackService;
+use crate::services::task_service::resolve_day;
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::routing::get;
+use axum::{Extension, Json, Router};
+use mindvault_core::models::AppDatabase;
+use mindvault_shared::dtos::feedback_dtos::{
+    CreateFeedbackRequest, FeedbackSearchParams, FeedbackSummaryParams, UpdateFeedbackRequest,
+};
+use mindvault_shared::models::feedback_model::{FeedbackEntryResponse, FeedbackSummaryResponse};
+use mindvault_shared::utils::date_input::DateInput;
+use std::sync::Arc;
+use tracing::info;
+
+pub(crate) struct FeedbackRouter {
+    feedback_service: Arc<FeedbackService>,
+}
+
+impl FeedbackRouter {
+    pub(crate) fn new(app_database: AppDatabase) -> Self {
+        let feedback_service = Arc::new(FeedbackService::new(app_database));
+        Self { feedback_service }
+    }
+
+    pub(crate) fn get_routes(&self) -> Router {
+        Router::new()
+            .route(
+                "/",
+                get(FeedbackRouter::search_feedback_handler)
+                    .post(FeedbackRouter::create_feedback_handler),
+            )
+            .route("/summary", get(FeedbackRouter::feedback_summary_handler))
+            .route(
+                "/{:id}",
+                get(FeedbackRouter::get_feedback_by_id_handler)
+                    .put(FeedbackRouter::update_feedback_handler)
+                    .delete(FeedbackRouter::delete_feedback_handler),
+            )
+            .with_state(self.feedback_service.clone())
+    }
+
+    async fn create_feedback_handler(
+        State(feedback_service): State<Arc<FeedbackService>>,
+        Extension(caller): Extension<Caller>,
+        Json(mut payload): Json<CreateFeedbackRequest>,
+    ) -> ApiResponse<FeedbackEntryResponse> {
+        if payload.note.trim().is_empty() {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                "Note field is required".to_string(),
+            ));
+        }
+        resolve_day(&mut payload.observed_on, &caller.dates)?;
+        match feedback_service.find_reportee(caller.user_id, payload.reportee_id).await {
+            Ok(Some(_)) => {}
+            Ok(None) => {
+                return Err((
+                    StatusCode::BAD_REQUEST,
+                    format!("Reportee with id {} not found", payload.reportee_id),
+                ));
+            }
+            Err(e) => {
+                let error_message = format!("Failed to validate reportee: {:?}", e);
+                return Err((StatusCode::INTERNAL_SERVER_ERROR, error_message));
+            }
+        }
+
+        handle_service_response!(
+            feedback_service.create_entry(caller.user_id, payload).await,
+            "Created a new feedback entry {}",
+            |data: &FeedbackEntryResponse| data.id,
+            "Unable to insert a new feedback entry into database"
+        )
+    }
+
+    async fn search_feedback_handler(
+        State(feedback_service): State<Arc<FeedbackService>>,
+        Extension(caller): Extension<Caller>,
+        Query(mut params): Query<FeedbackSearchParams>,
+    ) -> ApiResponse<Vec<FeedbackEntryResponse>> {
+        info!("Searching feedback with params {:?}", params);
+        resolve_day(&mut params.from, &caller.dates)?;
+        resolve_day(&mut params.to, &caller.dates)?;
+        handle_service_response!(
+            feedback_service.search_entries(caller.user_id, params).await,
+            "Found {} feedback entries",
+            |data: &Vec<FeedbackEntryResponse>| data.len(),
+            "Failed to search feedback"
+        )
+    }
+
+    async fn get_feedback_by_id_handler(
+        State(feedback_service): State<Arc<FeedbackService>>,
+        Extension(caller): Extension<Caller>,
+        Path(id): Path<i64>,
+    ) -> ApiResponse<FeedbackEntryResponse> {
+        info!("Fetching feedback entry with id {} from database", id);
+        match feedback_service.get_by_id(caller.user_id, id).await {
+            Ok(Some(entry)) => Ok(Json(entry)),
+            Ok(None) => Err((StatusCode::NOT_FOUND, "Feedback entry not found".to_string())),
+            Err(e) => {
+                let error_message = format!("Failed to get feedback entry: {:?}", e);
+                Err((StatusCode::INTERNAL_SERVER_ERROR, error_message))
+            }
+        }
+    }
+
+    async fn update_feedback_handler(
+        State(feedback_service): State<Arc<FeedbackService>>,
+        Extension(caller): Extension<Caller>,
+        Path(id): Path<i64>,
+        Json(mut payload): Json<UpdateFeedbackRequest>,
+    ) -> ApiResponse<FeedbackEntryResponse> {
+        info!("Updating feedback entry with id {}", id);
+
+        if payload.note.is_none()
+            && payload.sentiment.is_none()
+            && payload.tags.is_none()
+            && payload.observed_on.is_none()
+        {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                "At least one field must be provided for update".to_string(),
+            ));
+        }
+
+        resolve_day(&mut payload.observed_on, &caller.dates)?;
+
+        match feedback_service.update_entry(caller.user_id, id, payload).await {
+            Ok(Some(entry)) => Ok(Json(entry)),
+            Ok(None) => Err((StatusCode::NOT_FOUND, "Feedback entry not found".to_string())),
+            Err(e) => {
+                let error_message = format!("Failed to update feedback entry: {:?}", e);
+                Err((StatusCode::INTERNAL_SERVER_ERROR, error_message))
+            }
+        }
+    }
+
+    async fn delete_feedback_handler(
+        State(feedback_service): State<Arc<FeedbackService>>,
+        Extension(caller): Extension<Caller>,
+        Path(id): Path<i64>,
+    ) -> ApiTextResponse {
+        info!("Soft deleting feedback entry with id {}", id);
+        match feedback_service.soft_delete_entry(caller.user_id, id).await {
+            Ok(true) => Ok("Feedback entry deleted successfully".to_string()),
+            Ok(false) => Err((StatusCode::NOT_FOUND, "Feedback entry not found".to_string())),
+            Err(e) => {
+                let error_message = format!("Failed to delete feedback entry: {:?}", e);
+                Err((StatusCode::INTERNAL_SERVER_ERROR, error_message))
+            }
+        }
+    }
+
+    // ?reportee_id=1&from=2026-01-01&to=2026-06-30
+    async fn feedback_summary_handler(
+        State(feedback_service): State<Arc<FeedbackService>>,
+        Extension(caller): Extension<Caller>,
+        Query(mut params): Query<FeedbackSummaryParams>,
+    ) -> ApiResponse<FeedbackSummaryResponse> {
+        info!("Summarizing feedback with params {:?}", params);
+
+        resolve_day(&mut params.from, &caller.dates)?;
+        resolve_day(&mut params.to, &caller.dates)?;
+        if let (Some(from), Some(to)) = (
+            params.from.as_ref().and_then(DateInput::as_day),
+            params.to.as_ref().and_then(DateInput::as_day),
+        )
+            && from > to
+        {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                "'from' must not be after 'to'".to_string(),
+            ));
+        }
+
+        match feedback_service.summarize(caller.user_id, params).await {
+            Ok(Some(summary)) => Ok(Json(summary)),
+            Ok(None) => Err((StatusCode::NOT_FOUND, "Reportee not found".to_string())),
+            Err(e) => {
+                let error_message = format!("Failed to summarize feedback: {:?}", e);
+                Err((StatusCode::INTERNAL_SERVER_ERROR, error_message))
+            }
+        }
+    }
+}
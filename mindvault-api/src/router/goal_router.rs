@@ -0,0 +1,350 @@
+use crate::auth::Caller;
+use crate::handle_service_response;
+use crate::models::{ApiResponse, ApiTextResponse};
+use crate::services::goal_service::GoalService;
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::response::ErrorResponse;
+use axum::routing::{delete, get, post, put};
+use axum::{Extension, Json, Router};
+use mindvault_core::models::AppDatabase;
+use mindvault_shared::dtos::goal_dtos::{
+    CreateGoalRequest, CreateKeyResultRequest, GoalSearchParams, KeyResultCheckInRequest,
+    LinkTasksRequest, UpdateGoalRequest, UpdateKeyResultRequest,
+};
+use mindvault_shared::models::goals_model::{
+    EProgressSource, GoalResponse, KeyResultCheckInResponse,
+};
+use std::sync::Arc;
+use tracing::info;
+
+pub(crate) struct GoalRouter {
+    goal_service: Arc<GoalService>,
+}
+
+/// Maps an optional goal lookup onto the usual 404/500 responses
+fn goal_or_not_found(
+    result: Result<Option<GoalResponse>, ErrorResponse>,
+    not_found_message: &str,
+    error_context: &str,
+) -> ApiResponse<GoalResponse> {
+    match result {
+        Ok(Some(goal)) => Ok(Json(goal)),
+        Ok(None) => Err((StatusCode::NOT_FOUND, not_found_message.to_string())),
+        Err(e) => {
+            let error_message = format!("{}: {:?}", error_context, e);
+            Err((StatusCode::INTERNAL_SERVER_ERROR, error_message))
+        }
+    }
+}
+
+impl GoalRouter {
+    pub(crate) fn new(app_database: AppDatabase) -> Self {
+        let goal_service = Arc::new(GoalService::new(app_database));
+        Self { goal_service }
+    }
+
+    pub(crate) fn get_routes(&self) -> Router {
+        Router::new()
+            .route(
+                "/",
+                get(GoalRouter::search_goals_handler).post(GoalRouter::create_goal_handler),
+            )
+            .route(
+                "/{:id}",
+                get(GoalRouter::get_goal_by_id_handler)
+                    .put(GoalRouter::update_goal_handler)
+                    .delete(GoalRouter::delete_goal_handler),
+            )
+            .route("/{:id}/key-results", post(GoalRouter::add_key_result_handler))
+            .route(
+                "/{:id}/key-results/{:kr_id}",
+                put(GoalRouter::update_key_result_handler)
+                    .delete(GoalRouter::remove_key_result_handler),
+            )
+            .route(
+                "/{:id}/key-results/{:kr_id}/check-ins",
+                get(GoalRouter::key_result_history_handler).post(GoalRouter::check_in_handler),
+            )
+            .route(
+                "/{:id}/key-results/{:kr_id}/tasks",
+                post(GoalRouter::link_tasks_handler),
+            )
+            .route(
+                "/{:id}/key-results/{:kr_id}/tasks/{:task_id}",
+                delete(GoalRouter::unlink_task_handler),
+            )
+            .route(
+                "/{:id}/key-results/{:kr_id}/sync",
+                post(GoalRouter::sync_key_result_handler),
+            )
+            .with_state(self.goal_service.clone())
+    }
+
+    fn validate_key_result(request: &CreateKeyResultRequest) -> Result<(), (StatusCode, String)> {
+        if request.title.trim().is_empty() {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                "Key result title is required".to_string(),
+            ));
+        }
+        if request.start_value.unwrap_or(0.0) == request.target_value {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                format!(
+                    "Key result '{}' needs a target different from its start value",
+                    request.title
+                ),
+            ));
+        }
+        Ok(())
+    }
+
+    async fn create_goal_handler(
+        State(goal_service): State<Arc<GoalService>>,
+        Extension(caller): Extension<Caller>,
+        Json(payload): Json<CreateGoalRequest>,
+    ) -> ApiResponse<GoalResponse> {
+        if payload.objective.trim().is_empty() || payload.period.trim().is_empty() {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                "Objective and period fields are required".to_string(),
+            ));
+        }
+        for key_result in &payload.key_results {
+            GoalRouter::validate_key_result(key_result)?;
+        }
+        handle_service_response!(
+            goal_service.create_goal(caller.user_id, payload).await,
+            "Created a new goal {}",
+            |data: &GoalResponse| data.id,
+            "Unable to insert a new goal into database"
+        )
+    }
+
+    async fn search_goals_handler(
+        State(goal_service): State<Arc<GoalService>>,
+        Extension(caller): Extension<Caller>,
+        Query(params): Query<GoalSearchParams>,
+    ) -> ApiResponse<Vec<GoalResponse>> {
+        info!("Searching goals with params {:?}", params);
+        handle_service_response!(
+            goal_service.search_goals(caller.user_id, params).await,
+            "Found {} goals",
+            |data: &Vec<GoalResponse>| data.len(),
+            "Failed to search goals"
+        )
+    }
+
+    async fn get_goal_by_id_handler(
+        State(goal_service): State<Arc<GoalService>>,
+        Extension(caller): Extension<Caller>,
+        Path(id): Path<i64>,
+    ) -> ApiResponse<GoalResponse> {
+        info!("Fetching goal with id {} from database", id);
+        goal_or_not_found(
+            goal_service.get_by_id(caller.user_id, id).await,
+            "Goal not found",
+            "Failed to get goal",
+        )
+    }
+
+    async fn update_goal_handler(
+        State(goal_service): State<Arc<GoalService>>,
+        Extension(caller): Extension<Caller>,
+        Path(id): Path<i64>,
+        Json(payload): Json<UpdateGoalRequest>,
+    ) -> ApiResponse<GoalResponse> {
+        info!("Updating goal with id {} with payload {:?}", id, payload);
+        if payload.objective.is_none()
+            && payload.description.is_none()
+            && payload.owner_reportee_id.is_none()
+            && payload.period.is_none()
+        {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                "At least one field must be provided for update".to_string(),
+            ));
+        }
+        goal_or_not_found(
+            goal_service.update_goal(caller.user_id, id, payload).await,
+            "Goal not found",
+            "Failed to update goal",
+        )
+    }
+
+    async fn delete_goal_handler(
+        State(goal_service): State<Arc<GoalService>>,
+        Extension(caller): Extension<Caller>,
+        Path(id): Path<i64>,
+    ) -> ApiTextResponse {
+        info!("Soft deleting goal with id {}", id);
+        match goal_service.soft_delete_goal(caller.user_id, id).await {
+            Ok(true) => Ok("Goal deleted successfully".to_string()),
+            Ok(false) => Err((StatusCode::NOT_FOUND, "Goal not found".to_string())),
+            Err(e) => {
+                let error_message = format!("Failed to delete goal: {:?}", e);
+                Err((StatusCode::INTERNAL_SERVER_ERROR, error_message))
+            }
+        }
+    }
+
+    async fn add_key_result_handler(
+        State(goal_service): State<Arc<GoalService>>,
+        Extension(caller): Extension<Caller>,
+        Path(id): Path<i64>,
+        Json(payload): Json<CreateKeyResultRequest>,
+    ) -> ApiResponse<GoalResponse> {
+        info!("Adding key result to goal {}", id);
+        GoalRouter::validate_key_result(&payload)?;
+        goal_or_not_found(
+            goal_service.add_key_result(caller.user_id, id, payload).await,
+            "Goal not found",
+            "Failed to add key result",
+        )
+    }
+
+    async fn update_key_result_handler(
+        State(goal_service): State<Arc<GoalService>>,
+        Extension(caller): Extension<Caller>,
+        Path((id, kr_id)): Path<(i64, i64)>,
+        Json(payload): Json<UpdateKeyResultRequest>,
+    ) -> ApiResponse<GoalResponse> {
+        info!("Updating key result {} of goal {}", kr_id, id);
+        if payload.title.is_none()
+            && payload.start_value.is_none()
+            && payload.target_value.is_none()
+            && payload.unit.is_none()
+            && payload.progress_source.is_none()
+        {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                "At least one field must be provided for update".to_string(),
+            ));
+        }
+        goal_or_not_found(
+            goal_service.update_key_result(caller.user_id, id, kr_id, payload).await,
+            "Goal or key result not found",
+            "Failed to update key result",
+        )
+    }
+
+    async fn remove_key_result_handler(
+        State(goal_service): State<Arc<GoalService>>,
+        Extension(caller): Extension<Caller>,
+        Path((id, kr_id)): Path<(i64, i64)>,
+    ) -> ApiResponse<GoalResponse> {
+        info!("Removing key result {} from goal {}", kr_id, id);
+        goal_or_not_found(
+            goal_service.remove_key_result(caller.user_id, id, kr_id).await,
+            "Goal or key result not found",
+            "Failed to remove key result",
+        )
+    }
+
+    async fn check_in_handler(
+        State(goal_service): State<Arc<GoalService>>,
+        Extension(caller): Extension<Caller>,
+        Path((id, kr_id)): Path<(i64, i64)>,
+        Json(payload): Json<KeyResultCheckInRequest>,
+    ) -> ApiResponse<GoalResponse> {
+        info!("Recording check-in for key result {} of goal {}", kr_id, id);
+        match goal_service.key_result_source(caller.user_id, id, kr_id).await {
+            Ok(Some(EProgressSource::Manual)) => {}
+            Ok(Some(EProgressSource::LinkedTasks)) => {
+                return Err((
+                    StatusCode::CONFLICT,
+                    "Key result progress is computed from linked tasks".to_string(),
+                ));
+            }
+            Ok(None) => {
+                return Err((
+                    StatusCode::NOT_FOUND,
+                    "Goal or key result not found".to_string(),
+                ));
+            }
+            Err(e) => {
+                let error_message = format!("Failed to record check-in: {:?}", e);
+                return Err((StatusCode::INTERNAL_SERVER_ERROR, error_message));
+            }
+        }
+        goal_or_not_found(
+            goal_service.check_in(caller.user_id, id, kr_id, payload).await,
+            "Goal or key result not found",
+            "Failed to record check-in",
+        )
+    }
+
+    async fn key_result_history_handler(
+        State(goal_service): State<Arc<GoalService>>,
+        Extension(caller): Extension<Caller>,
+        Path((id, kr_id)): Path<(i64, i64)>,
+    ) -> ApiResponse<Vec<KeyResultCheckInResponse>> {
+        info!("Fetching history of key result {} of goal {}", kr_id, id);
+        handle_service_response!(
+            goal_service.get_history(caller.user_id, id, kr_id).await,
+            "Fetched {} key result check-ins",
+            |data: &Vec<KeyResultCheckInResponse>| data.len(),
+            "Failed to get key result history"
+        )
+    }
+
+    async fn link_tasks_handler(
+        State(goal_service): State<Arc<GoalService>>,
+        Extension(caller): Extension<Caller>,
+        Path((id, kr_id)): Path<(i64, i64)>,
+        Json(payload): Json<LinkTasksRequest>,
+    ) -> ApiResponse<GoalResponse> {
+        info!("Linking tasks {:?} to key result {} of goal {}", payload.task_ids, kr_id, id);
+        if payload.task_ids.is_empty() {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                "Task ids array cannot be empty".to_string(),
+            ));
+        }
+        match goal_service.find_missing_tasks(caller.user_id, &payload.task_ids).await {
+            Ok(missing) if missing.is_empty() => {}
+            Ok(missing) => {
+                return Err((
+                    StatusCode::BAD_REQUEST,
+                    format!("Tasks not found: {:?}", missing),
+                ));
+            }
+            Err(e) => {
+                let error_message = format!("Failed to link tasks: {:?}", e);
+                return Err((StatusCode::INTERNAL_SERVER_ERROR, error_message));
+            }
+        }
+        goal_or_not_found(
+            goal_service.link_tasks(caller.user_id, id, kr_id, &payload.task_ids).await,
+            "Goal or key result not found",
+            "Failed to link tasks",
+        )
+    }
+
+    async fn unlink_task_handler(
+        State(goal_service): State<Arc<GoalService>>,
+        Extension(caller): Extension<Caller>,
+        Path((id, kr_id, task_id)): Path<(i64, i64, i64)>,
+    ) -> ApiResponse<GoalResponse> {
+        info!("Unlinking task {} from key result {} of goal {}", task_id, kr_id, id);
+        goal_or_not_found(
+            goal_service.unlink_task(caller.user_id, id, kr_id, task_id).await,
+            "Goal or key result not found",
+            "Failed to unlink task",
+        )
+    }
+
+    async fn sync_key_result_handler(
+        State(goal_service): State<Arc<GoalService>>,
+        Extension(caller): Extension<Caller>,
+        Path((id, kr_id)): Path<(i64, i64)>,
+    ) -> ApiResponse<GoalResponse> {
+        info!("Syncing key result {} of goal {} with linked tasks", kr_id, id);
+        goal_or_not_found(
+            goal_service.sync_key_result(caller.user_id, id, kr_id).await,
+            "Goal or key result not found",
+            "Failed to sync key result",
+        )
+    }
+}
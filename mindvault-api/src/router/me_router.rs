@@ -0,0 +1,71 @@
+use crate::auth::Caller;
+use crate::models::ApiResponse;
+use crate::services::user_service::UserService;
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::routing::{get, put};
+use axum::{Extension, Json, Router};
+use mindvault_core::models::AppDatabase;
+use mindvault_shared::dtos::user_dtos::UpdatePreferencesRequest;
+use mindvault_shared::models::users_model::UserResponse;
+use std::sync::Arc;
+use tracing::info;
+
+/// The calling user's own account, including the time zone and date order used to read and
+/// show their dates
+pub(crate) struct MeRouter {
+    user_service: Arc<UserService>,
+}
+
+impl MeRouter {
+    pub(crate) fn new(app_database: AppDatabase) -> Self {
+        let user_service = Arc::new(UserService::new(app_database));
+        Self { user_service }
+    }
+
+    pub(crate) fn get_routes(&self) -> Router {
+        Router::new()
+            .route("/", get(MeRouter::get_me_handler))
+            .route("/preferences", put(MeRouter::update_preferences_handler))
+            .with_state(self.user_service.clone())
+    }
+
+    async fn get_me_handler(
+        State(user_service): State<Arc<UserService>>,
+        Extension(caller): Extension<Caller>,
+    ) -> ApiResponse<UserResponse> {
+        info!("Fetching user {}", caller.user_id);
+        match user_service.get_user(caller.user_id).await {
+            Ok(Some(user)) => Ok(Json(user)),
+            Ok(None) => Err((StatusCode::NOT_FOUND, "User not found".to_string())),
+            Err(e) => {
+                let error_message = format!("Failed to get user: {:?}", e);
+                Err((StatusCode::INTERNAL_SERVER_ERROR, error_message))
+            }
+        }
+    }
+
+    async fn update_preferences_handler(
+        State(user_service): State<Arc<UserService>>,
+        Extension(caller): Extension<Caller>,
+        Json(payload): Json<UpdatePreferencesRequest>,
+    ) -> ApiResponse<UserResponse> {
+        info!("Updating preferences of user {} with {:?}", caller.user_id, payload);
+        if payload.time_zone.is_none() && payload.date_order.is_none() {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                "At least one of timeZone and dateOrder must be provided".to_string(),
+            ));
+        }
+
+        match user_service.update_preferences(caller.user_id, payload).await {
+            Ok(Ok(Some(user))) => Ok(Json(user)),
+            Ok(Ok(None)) => Err((StatusCode::NOT_FOUND, "User not found".to_string())),
+            Ok(Err(message)) => Err((StatusCode::BAD_REQUEST, message)),
+            Err(e) => {
+                let error_message = format!("Failed to update preferences: {:?}", e);
+                Err((StatusCode::INTERNAL_SERVER_ERROR, error_message))
+            }
+        }
+    }
+}
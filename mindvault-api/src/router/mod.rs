@@ -1,19 +1,77 @@
+mod admin_router;
+mod api_doc_router;
+mod auth_router;
+mod board_router;
+mod calendar_router;
+mod digest_router;
+mod event_router;
+mod feedback_router;
+mod goal_router;
+mod me_router;
+mod notification_router;
+mod reminder_router;
+mod report_router;
+mod reportee_router;
+mod review_router;
+mod review_template_router;
+mod saved_filter_router;
 mod task_router;
+mod transfer_router;
+mod versioning;
+mod webhook_router;
+mod workflow_router;
+mod workspace_router;
 
 use axum::extract::State;
+use axum::middleware;
 use axum::response::Html;
 use axum::Router;
 use axum::routing::get;
 use mindvault_core::models::AppDatabase;
+use mindvault_shared::models::access_tokens_model::ETokenScope;
+use mindvault_shared::models::tasks_model::{TaskResponse, TaskResponseV2};
+use std::sync::Arc;
+use crate::auth::{require_scopes, RequiredScopes};
+use crate::events::ChangeFeed;
+use crate::router::admin_router::AdminRouter;
+use crate::router::api_doc_router::ApiDocRouter;
+use crate::router::auth_router::AuthRouter;
+use crate::router::board_router::BoardRouter;
+use crate::router::calendar_router::CalendarRouter;
+use crate::router::digest_router::DigestRouter;
+use crate::router::event_router::EventRouter;
+use crate::router::feedback_router::FeedbackRouter;
+use crate::router::goal_router::GoalRouter;
+use crate::router::me_router::MeRouter;
+use crate::router::notification_router::NotificationRouter;
+use crate::router::reminder_router::ReminderRouter;
+use crate::router::report_router::ReportRouter;
+use crate::router::reportee_router::ReporteeRouter;
+use crate::router::review_router::ReviewRouter;
+use crate::router::review_template_router::ReviewTemplateRouter;
+use crate::router::saved_filter_router::SavedFilterRouter;
 use crate::router::task_router::TaskRouter;
+use crate::router::transfer_router::TransferRouter;
+use crate::router::webhook_router::WebhookRouter;
+use crate::router::versioning::{deprecate_unversioned, CURRENT_VERSION_PREFIX};
+use crate::router::workflow_router::WorkflowRouter;
+use crate::router::workspace_router::WorkspaceRouter;
+use crate::services::access_token_service::AccessTokenService;
 
 pub struct MindVaultRouter {
     pub db_client: AppDatabase,
+    change_feed: Arc<ChangeFeed>,
+    access_token_service: Arc<AccessTokenService>,
 }
 
 impl MindVaultRouter {
-    pub(crate) fn new(db_client: AppDatabase) -> Self {
-        MindVaultRouter { db_client }
+    pub(crate) fn new(db_client: AppDatabase, change_feed: Arc<ChangeFeed>) -> Self {
+        let access_token_service = Arc::new(AccessTokenService::new(db_client.clone()));
+        MindVaultRouter {
+            db_client,
+            change_feed,
+            access_token_service,
+        }
     }
 
     pub(crate) fn get_router(&self) -> Router {
@@ -21,15 +79,179 @@ impl MindVaultRouter {
             .format("%d/%m/%y %H:%M %Z")
             .to_string()
             .to_string();
-        let router = Router::new()
+        let v1_routes = self.get_v1_routes();
+        Router::new()
             .route("/", get(root_handler).with_state(server_up_since))
-            .nest("/tasks", self.get_task_routes());
-        router
+            .merge(ApiDocRouter::get_routes())
+            .nest(CURRENT_VERSION_PREFIX, v1_routes.clone())
+            .nest("/v2", self.get_v2_routes())
+            // Unversioned paths from before /v1, kept working until their sunset date
+            .merge(v1_routes.layer(middleware::from_fn(deprecate_unversioned)))
+    }
+
+    /// Only `/tasks` has a v2 shape so far; everything else stays on /v1
+    fn get_v2_routes(&self) -> Router {
+        let task_router = TaskRouter::new(self.db_client.clone(), self.change_feed.clone());
+        let task_routes = task_router.get_routes::<TaskResponseV2>();
+        Router::new().nest(
+            "/tasks",
+            self.protect(task_routes, ETokenScope::TasksRead, ETokenScope::TasksWrite),
+        )
+    }
+
+    fn get_v1_routes(&self) -> Router {
+        use ETokenScope::{Admin, PeopleRead, PeopleWrite, TasksRead, TasksWrite};
+        Router::new()
+            .nest("/tasks", self.protect(self.get_task_routes(), TasksRead, TasksWrite))
+            .nest("/me", self.protect(self.get_me_routes(), TasksRead, TasksWrite))
+            .nest(
+                "/workspaces",
+                self.protect(self.get_workspace_routes(), TasksRead, TasksWrite),
+            )
+            .merge(self.protect(self.get_transfer_routes(), TasksRead, TasksWrite))
+            .merge(self.protect(self.get_calendar_routes(), TasksRead, TasksWrite))
+            .nest("/reportees", self.protect(self.get_reportee_routes(), PeopleRead, PeopleWrite))
+            .nest("/reviews", self.protect(self.get_review_routes(), PeopleRead, PeopleWrite))
+            .nest(
+                "/review-templates",
+                self.protect(self.get_review_template_routes(), PeopleRead, PeopleWrite),
+            )
+            .nest("/feedback", self.protect(self.get_feedback_routes(), PeopleRead, PeopleWrite))
+            .nest("/goals", self.protect(self.get_goal_routes(), PeopleRead, PeopleWrite))
+            .nest("/reminders", self.protect(self.get_reminder_routes(), TasksRead, TasksWrite))
+            .nest("/filters", self.protect(self.get_saved_filter_routes(), TasksRead, TasksWrite))
+            .nest("/workflow", self.protect(self.get_workflow_routes(), TasksRead, TasksWrite))
+            .nest("/board", self.protect(self.get_board_routes(), TasksRead, TasksWrite))
+            .nest("/reports", self.protect(self.get_report_routes(), TasksRead, TasksWrite))
+            .nest("/events", self.protect_events(self.get_event_routes()))
+            .nest("/webhooks", self.protect(self.get_webhook_routes(), Admin, Admin))
+            .nest("/digest", self.protect(self.get_digest_routes(), TasksRead, TasksWrite))
+            .nest("/notifications", self.protect(self.get_notification_routes(), Admin, Admin))
+            .nest("/admin", self.protect(self.get_admin_routes(), Admin, Admin))
+            .nest("/auth", self.protect(self.get_auth_routes(), Admin, Admin))
+    }
+
+    /// Requires a personal access token with `read` for GET/HEAD and `write` otherwise
+    fn protect(&self, routes: Router, read: ETokenScope, write: ETokenScope) -> Router {
+        let required = RequiredScopes::new(self.access_token_service.clone(), read, write);
+        routes.route_layer(middleware::from_fn_with_state(required, require_scopes))
+    }
+
+    /// Like `protect` with the task scopes, also taking the token from the query string as
+    /// `EventSource` and browser WebSocket clients cannot set headers
+    fn protect_events(&self, routes: Router) -> Router {
+        let required = RequiredScopes::new(
+            self.access_token_service.clone(),
+            ETokenScope::TasksRead,
+            ETokenScope::TasksWrite,
+        )
+        .allowing_query_token();
+        routes.route_layer(middleware::from_fn_with_state(required, require_scopes))
     }
 
     fn get_task_routes(&self) -> Router {
-        let task_router = TaskRouter::new(self.db_client.clone());
-        task_router.get_routes()
+        let task_router = TaskRouter::new(self.db_client.clone(), self.change_feed.clone());
+        task_router.get_routes::<TaskResponse>()
+    }
+
+    fn get_me_routes(&self) -> Router {
+        let me_router = MeRouter::new(self.db_client.clone());
+        me_router.get_routes()
+    }
+
+    fn get_board_routes(&self) -> Router {
+        let board_router = BoardRouter::new(self.db_client.clone(), self.change_feed.clone());
+        board_router.get_routes()
+    }
+
+    fn get_report_routes(&self) -> Router {
+        let report_router = ReportRouter::new(self.db_client.clone());
+        report_router.get_routes()
+    }
+
+    fn get_workflow_routes(&self) -> Router {
+        let workflow_router = WorkflowRouter::new(self.db_client.clone());
+        workflow_router.get_routes()
+    }
+
+    fn get_workspace_routes(&self) -> Router {
+        let workspace_router = WorkspaceRouter::new(self.db_client.clone());
+        workspace_router.get_routes()
+    }
+
+    fn get_transfer_routes(&self) -> Router {
+        let transfer_router = TransferRouter::new(self.db_client.clone(), self.change_feed.clone());
+        transfer_router.get_routes()
+    }
+
+    fn get_calendar_routes(&self) -> Router {
+        let calendar_router = CalendarRouter::new(self.db_client.clone());
+        calendar_router.get_routes()
+    }
+
+    fn get_reportee_routes(&self) -> Router {
+        let reportee_router = ReporteeRouter::new(self.db_client.clone());
+        reportee_router.get_routes()
+    }
+
+    fn get_review_routes(&self) -> Router {
+        let review_router = ReviewRouter::new(self.db_client.clone());
+        review_router.get_routes()
+    }
+
+    fn get_review_template_routes(&self) -> Router {
+        let review_template_router = ReviewTemplateRouter::new(self.db_client.clone());
+        review_template_router.get_routes()
+    }
+
+    fn get_feedback_routes(&self) -> Router {
+        let feedback_router = FeedbackRouter::new(self.db_client.clone());
+        feedback_router.get_routes()
+    }
+
+    fn get_goal_routes(&self) -> Router {
+        let goal_router = GoalRouter::new(self.db_client.clone());
+        goal_router.get_routes()
+    }
+
+    fn get_reminder_routes(&self) -> Router {
+        let reminder_router = ReminderRouter::new(self.db_client.clone());
+        reminder_router.get_routes()
+    }
+
+    fn get_saved_filter_routes(&self) -> Router {
+        let saved_filter_router = SavedFilterRouter::new(self.db_client.clone());
+        saved_filter_router.get_routes()
+    }
+
+    fn get_event_routes(&self) -> Router {
+        let event_router = EventRouter::new(self.db_client.clone(), self.change_feed.clone());
+        event_router.get_routes()
+    }
+
+    fn get_webhook_routes(&self) -> Router {
+        let webhook_router = WebhookRouter::new(self.db_client.clone());
+        webhook_router.get_routes()
+    }
+
+    fn get_digest_routes(&self) -> Router {
+        let digest_router = DigestRouter::new(self.db_client.clone());
+        digest_router.get_routes()
+    }
+
+    fn get_notification_routes(&self) -> Router {
+        let notification_router = NotificationRouter::new(self.db_client.clone());
+        notification_router.get_routes()
+    }
+
+    fn get_admin_routes(&self) -> Router {
+        let admin_router = AdminRouter::new(self.db_client.clone());
+        admin_router.get_routes()
+    }
+
+    fn get_auth_routes(&self) -> Router {
+        let auth_router = AuthRouter::new(self.access_token_service.clone());
+        auth_router.get_routes()
     }
 }
 
@@ -44,4 +266,37 @@ async fn root_handler(State(server_up_since): State<String>) -> Html<String> {
         server_up_since, current_time
     );
     Html(resp)
-}
\ No newline at end of file
+}
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use axum::body::Body;
+    use axum::http::{Method, Request};
+    use axum::response::Response;
+    use tower::ServiceExt;
+
+    /// A database on a port nothing listens on; requests are answered before any query runs
+    /// or fail after a short server selection timeout
+    pub(crate) async fn offline_database() -> AppDatabase {
+        mongodb::Client::with_uri_str("mongodb://127.0.0.1:1/?serverSelectionTimeoutMS=200")
+            .await
+            .unwrap()
+            .database("mindvault_test")
+    }
+
+    /// The full API router on top of `offline_database`
+    pub(crate) async fn offline_router() -> Router {
+        let db_client = offline_database().await;
+        let change_feed = ChangeFeed::start(db_client.clone()).await;
+        MindVaultRouter::new(db_client, change_feed).get_router()
+    }
+
+    pub(crate) async fn send(router: Router, method: Method, uri: &str) -> Response {
+        let request = Request::builder()
+            .method(method)
+            .uri(uri)
+            .body(Body::empty())
+            .unwrap();
+        router.oneshot(request).await.unwrap()
+    }
+}
@@ -0,0 +1,271 @@
+use crate::auth::Caller;
+use crate::handle_service_response;
+use crate::models::{ApiResponse, ApiTextResponse};
+use crate::services::notification_service::NotificationService;
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::routing::{get, post};
+use axum::{Extension, Json, Router};
+use mindvault_core::models::AppDatabase;
+use mindvault_shared::dtos::notification_dtos::{
+    CreateChannelRequest, DeliverySearchParams, UpdateChannelRequest,
+};
+use mindvault_shared::models::notifications_model::{
+    EChannelTarget, EDeliveryState, NotificationChannelResponse, NotificationDeliveryResponse,
+};
+use std::sync::Arc;
+use tracing::info;
+
+pub(crate) struct NotificationRouter {
+    notification_service: Arc<NotificationService>,
+}
+
+impl NotificationRouter {
+    pub(crate) fn new(app_database: AppDatabase) -> Self {
+        let notification_service = Arc::new(NotificationService::new(app_database));
+        Self {
+            notification_service,
+        }
+    }
+
+    pub(crate) fn get_routes(&self) -> Router {
+        Router::new()
+            .route(
+                "/channels",
+                get(NotificationRouter::get_channels_handler)
+                    .post(NotificationRouter::create_channel_handler),
+            )
+            .route(
+                "/channels/{:id}",
+                get(NotificationRouter::get_channel_by_id_handler)
+                    .put(NotificationRouter::update_channel_handler)
+                    .delete(NotificationRouter::delete_channel_handler),
+            )
+            .route(
+                "/channels/{:id}/test",
+                post(NotificationRouter::test_channel_handler),
+            )
+            .route(
+                "/deliveries",
+                get(NotificationRouter::search_deliveries_handler),
+            )
+            .route(
+                "/deliveries/{:id}",
+                get(NotificationRouter::get_delivery_by_id_handler),
+            )
+            .route(
+                "/deliveries/{:id}/retry",
+                post(NotificationRouter::retry_delivery_handler),
+            )
+            .route(
+                "/dead-letters",
+                get(NotificationRouter::dead_letters_handler),
+            )
+            .with_state(self.notification_service.clone())
+    }
+
+    fn validate_target(
+        notification_service: &NotificationService,
+        target: &EChannelTarget,
+    ) -> Result<(), (StatusCode, String)> {
+        let error = match target {
+            EChannelTarget::Webhook { url, .. }
+                if !(url.starts_with("http://") || url.starts_with("https://")) =>
+            {
+                Some("Webhook url must start with http:// or https://")
+            }
+            EChannelTarget::Email { to } if !to.contains('@') => {
+                Some("Email target must be an email address")
+            }
+            EChannelTarget::Command { .. } if !notification_service.command_channels_allowed() => {
+                Some("Command channels are disabled; set MINDVAULT_NOTIFY_ALLOW_COMMANDS=true to enable them")
+            }
+            EChannelTarget::Command { program, .. } if program.trim().is_empty() => {
+                Some("Command program is required")
+            }
+            _ => None,
+        };
+        match error {
+            Some(message) => Err((StatusCode::BAD_REQUEST, message.to_string())),
+            None => Ok(()),
+        }
+    }
+
+    async fn get_channels_handler(
+        State(notification_service): State<Arc<NotificationService>>,
+        Extension(caller): Extension<Caller>,
+    ) -> ApiResponse<Vec<NotificationChannelResponse>> {
+        info!("Fetching all notification channels");
+        handle_service_response!(
+            notification_service.get_channels(caller.user_id).await,
+            "Found {} notification channels",
+            |data: &Vec<NotificationChannelResponse>| data.len(),
+            "Failed to get notification channels"
+        )
+    }
+
+    async fn create_channel_handler(
+        State(notification_service): State<Arc<NotificationService>>,
+        Extension(caller): Extension<Caller>,
+        Json(payload): Json<CreateChannelRequest>,
+    ) -> ApiResponse<NotificationChannelResponse> {
+        if payload.name.trim().is_empty() {
+            return Err((StatusCode::BAD_REQUEST, "Name field is required".to_string()));
+        }
+        NotificationRouter::validate_target(&notification_service, &payload.target)?;
+        handle_service_response!(
+            notification_service.create_channel(caller.user_id, payload).await,
+            "Created a new notification channel {}",
+            |data: &NotificationChannelResponse| data.id,
+            "Unable to insert a new notification channel into database"
+        )
+    }
+
+    async fn get_channel_by_id_handler(
+        State(notification_service): State<Arc<NotificationService>>,
+        Extension(caller): Extension<Caller>,
+        Path(id): Path<i64>,
+    ) -> ApiResponse<NotificationChannelResponse> {
+        info!("Fetching notification channel with id {}", id);
+        match notification_service.get_channel(caller.user_id, id).await {
+            Ok(Some(channel)) => Ok(Json(channel)),
+            Ok(None) => Err((StatusCode::NOT_FOUND, "Channel not found".to_string())),
+            Err(e) => {
+                let error_message = format!("Failed to get channel: {:?}", e);
+                Err((StatusCode::INTERNAL_SERVER_ERROR, error_message))
+            }
+        }
+    }
+
+    async fn update_channel_handler(
+        State(notification_service): State<Arc<NotificationService>>,
+        Extension(caller): Extension<Caller>,
+        Path(id): Path<i64>,
+        Json(payload): Json<UpdateChannelRequest>,
+    ) -> ApiResponse<NotificationChannelResponse> {
+        info!("Updating notification channel with id {}", id);
+
+        if payload.name.is_none()
+            && payload.target.is_none()
+            && payload.notification_types.is_none()
+            && payload.enabled.is_none()
+        {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                "At least one field must be provided for update".to_string(),
+            ));
+        }
+        if let Some(target) = &payload.target {
+            NotificationRouter::validate_target(&notification_service, target)?;
+        }
+
+        match notification_service.update_channel(caller.user_id, id, payload).await {
+            Ok(Some(channel)) => Ok(Json(channel)),
+            Ok(None) => Err((StatusCode::NOT_FOUND, "Channel not found".to_string())),
+            Err(e) => {
+                let error_message = format!("Failed to update channel: {:?}", e);
+                Err((StatusCode::INTERNAL_SERVER_ERROR, error_message))
+            }
+        }
+    }
+
+    async fn delete_channel_handler(
+        State(notification_service): State<Arc<NotificationService>>,
+        Extension(caller): Extension<Caller>,
+        Path(id): Path<i64>,
+    ) -> ApiTextResponse {
+        info!("Soft deleting notification channel with id {}", id);
+        match notification_service.delete_channel(caller.user_id, id).await {
+            Ok(true) => Ok("Channel deleted successfully".to_string()),
+            Ok(false) => Err((StatusCode::NOT_FOUND, "Channel not found".to_string())),
+            Err(e) => {
+                let error_message = format!("Failed to delete channel: {:?}", e);
+                Err((StatusCode::INTERNAL_SERVER_ERROR, error_message))
+            }
+        }
+    }
+
+    /// Sends a test message immediately and returns the delivery with its first attempt
+    async fn test_channel_handler(
+        State(notification_service): State<Arc<NotificationService>>,
+        Extension(caller): Extension<Caller>,
+        Path(id): Path<i64>,
+    ) -> ApiResponse<NotificationDeliveryResponse> {
+        info!("Sending test notification to channel {}", id);
+        match notification_service.send_test(caller.user_id, id).await {
+            Ok(Some(delivery)) => Ok(Json(delivery)),
+            Ok(None) => Err((StatusCode::NOT_FOUND, "Channel not found".to_string())),
+            Err(e) => {
+                let error_message = format!("Failed to send test notification: {:?}", e);
+                Err((StatusCode::INTERNAL_SERVER_ERROR, error_message))
+            }
+        }
+    }
+
+    // ?state=DeadLetter&channel_id=1&notification_type=ReminderDue&limit=20
+    async fn search_deliveries_handler(
+        State(notification_service): State<Arc<NotificationService>>,
+        Extension(caller): Extension<Caller>,
+        Query(params): Query<DeliverySearchParams>,
+    ) -> ApiResponse<Vec<NotificationDeliveryResponse>> {
+        info!("Searching notification deliveries with params {:?}", params);
+        handle_service_response!(
+            notification_service.search_deliveries(caller.user_id, params).await,
+            "Found {} notification deliveries",
+            |data: &Vec<NotificationDeliveryResponse>| data.len(),
+            "Failed to search notification deliveries"
+        )
+    }
+
+    async fn dead_letters_handler(
+        State(notification_service): State<Arc<NotificationService>>,
+        Extension(caller): Extension<Caller>,
+    ) -> ApiResponse<Vec<NotificationDeliveryResponse>> {
+        let params = DeliverySearchParams {
+            state: Some(EDeliveryState::DeadLetter),
+            channel_id: None,
+            notification_type: None,
+            limit: None,
+        };
+        handle_service_response!(
+            notification_service.search_deliveries(caller.user_id, params).await,
+            "Found {} dead-lettered deliveries",
+            |data: &Vec<NotificationDeliveryResponse>| data.len(),
+            "Failed to get dead-lettered deliveries"
+        )
+    }
+
+    async fn get_delivery_by_id_handler(
+        State(notification_service): State<Arc<NotificationService>>,
+        Extension(caller): Extension<Caller>,
+        Path(id): Path<i64>,
+    ) -> ApiResponse<NotificationDeliveryResponse> {
+        match notification_service.get_delivery(caller.user_id, id).await {
+            Ok(Some(delivery)) => Ok(Json(delivery)),
+            Ok(None) => Err((StatusCode::NOT_FOUND, "Delivery not found".to_string())),
+            Err(e) => {
+                let error_message = format!("Failed to get delivery: {:?}", e);
+                Err((StatusCode::INTERNAL_SERVER_ERROR, error_message))
+            }
+        }
+    }
+
+    async fn retry_delivery_handler(
+        State(notification_service): State<Arc<NotificationService>>,
+        Extension(caller): Extension<Caller>,
+        Path(id): Path<i64>,
+    ) -> ApiResponse<NotificationDeliveryResponse> {
+        info!("Retrying notification delivery {}", id);
+        match notification_service.retry_delivery(caller.user_id, id).await {
+            Ok(Some(delivery)) => Ok(Json(delivery)),
+            Ok(None) => Err((
+                StatusCode::CONFLICT,
+                "Delivery not found or already delivered".to_string(),
+            )),
+            Err(e) => {
+                let error_message = format!("Failed to retry delivery: {:?}", e);
+                Err((StatusCode::INTERNAL_SERVER_ERROR, error_message))
+            }
+        }
+    }
+}
@@ -0,0 +1,375 @@
+use crate::auth::Caller;
+use crate::handle_service_response;
+use crate::models::{ApiResponse, ApiTextResponse};
+use crate::services::reminder_service::ReminderService;
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::routing::{get, post};
+use axum::{Extension, Json, Router};
+use bson::DateTime as BsonDateTime;
+use mindvault_core::models::AppDatabase;
+use mindvault_core::repository::reminder_repo::{NewReminder, ReminderChanges};
+use mindvault_shared::dtos::reminder_dtos::{
+    CreateReminderRequest, DueRemindersParams, ReminderSearchParams, SnoozeReminderRequest,
+    UpdateReminderRequest,
+};
+use mindvault_shared::models::reminders_model::{EReminderTrigger, ReminderResponse};
+use mindvault_shared::utils::time_zone::{parse_time_zone, FlexibleDateTime, DEFAULT_TIME_ZONE};
+use std::sync::Arc;
+use tracing::info;
+
+pub(crate) struct ReminderRouter {
+    reminder_service: Arc<ReminderService>,
+}
+
+impl ReminderRouter {
+    pub(crate) fn new(app_database: AppDatabase) -> Self {
+        let reminder_service = Arc::new(ReminderService::new(app_database));
+        Self { reminder_service }
+    }
+
+    pub(crate) fn get_routes(&self) -> Router {
+        Router::new()
+            .route(
+                "/",
+                get(ReminderRouter::search_reminders_handler)
+                    .post(ReminderRouter::create_reminder_handler),
+            )
+            .route("/due", get(ReminderRouter::due_reminders_handler))
+            .route(
+                "/{:id}",
+                get(ReminderRouter::get_reminder_by_id_handler)
+                    .put(ReminderRouter::update_reminder_handler)
+                    .delete(ReminderRouter::delete_reminder_handler),
+            )
+            .route("/{:id}/fire", post(ReminderRouter::fire_reminder_handler))
+            .route("/{:id}/snooze", post(ReminderRouter::snooze_reminder_handler))
+            .route("/{:id}/dismiss", post(ReminderRouter::dismiss_reminder_handler))
+            .with_state(self.reminder_service.clone())
+    }
+
+    fn validate_time_zone(time_zone: Option<String>) -> Result<String, (StatusCode, String)> {
+        let time_zone = time_zone.unwrap_or_else(|| DEFAULT_TIME_ZONE.to_string());
+        parse_time_zone(&time_zone).map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+        Ok(time_zone)
+    }
+
+    fn to_bson(
+        date_time: FlexibleDateTime,
+        time_zone: &str,
+    ) -> Result<BsonDateTime, (StatusCode, String)> {
+        date_time
+            .to_utc(time_zone)
+            .map(BsonDateTime::from)
+            .map_err(|e| (StatusCode::BAD_REQUEST, e))
+    }
+
+    /// Builds the trigger from either an absolute time or an offset to the task due date,
+    /// and works out when it fires
+    async fn resolve_trigger(
+        reminder_service: &ReminderService,
+        owner_id: i64,
+        task_id: Option<i64>,
+        remind_at: Option<FlexibleDateTime>,
+        offset_minutes: Option<i64>,
+        time_zone: &str,
+    ) -> Result<(EReminderTrigger, Option<BsonDateTime>), (StatusCode, String)> {
+        let trigger = match (remind_at, offset_minutes) {
+            (Some(remind_at), None) => EReminderTrigger::Absolute {
+                at: ReminderRouter::to_bson(remind_at, time_zone)?,
+            },
+            (None, Some(offset_minutes)) if task_id.is_some() => {
+                EReminderTrigger::BeforeDueDate { offset_minutes }
+            }
+            (None, Some(_)) => {
+                return Err((
+                    StatusCode::BAD_REQUEST,
+                    "offsetMinutes requires the reminder to be attached to a task".to_string(),
+                ));
+            }
+            _ => {
+                return Err((
+                    StatusCode::BAD_REQUEST,
+                    "Provide either remindAt or offsetMinutes".to_string(),
+                ));
+            }
+        };
+
+        let task_due_date = match task_id {
+            Some(task_id) => match reminder_service.task_due_date(owner_id, task_id).await {
+                Ok(Some(due_date)) => due_date,
+                Ok(None) => {
+                    return Err((
+                        StatusCode::BAD_REQUEST,
+                        format!("Task with id {} not found", task_id),
+                    ));
+                }
+                Err(e) => {
+                    let error_message = format!("Failed to load task: {:?}", e);
+                    return Err((StatusCode::INTERNAL_SERVER_ERROR, error_message));
+                }
+            },
+            None => None,
+        };
+
+        let fire_at = trigger.fire_at(task_due_date);
+        Ok((trigger, fire_at))
+    }
+
+    async fn create_reminder_handler(
+        State(reminder_service): State<Arc<ReminderService>>,
+        Extension(caller): Extension<Caller>,
+        Json(payload): Json<CreateReminderRequest>,
+    ) -> ApiResponse<ReminderResponse> {
+        if payload.title.trim().is_empty() {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                "Title field is required".to_string(),
+            ));
+        }
+        let time_zone = ReminderRouter::validate_time_zone(payload.time_zone)?;
+        let (trigger, fire_at) = ReminderRouter::resolve_trigger(
+            &reminder_service,
+            caller.user_id,
+            payload.task_id,
+            payload.remind_at,
+            payload.offset_minutes,
+            &time_zone,
+        )
+        .await?;
+
+        let new_reminder = NewReminder {
+            title: payload.title,
+            note: payload.note,
+            task_id: payload.task_id,
+            trigger,
+            time_zone,
+            fire_at,
+        };
+        handle_service_response!(
+            reminder_service.create_reminder(caller.user_id, new_reminder).await,
+            "Created a new reminder {}",
+            |data: &ReminderResponse| data.id,
+            "Unable to insert a new reminder into database"
+        )
+    }
+
+    async fn search_reminders_handler(
+        State(reminder_service): State<Arc<ReminderService>>,
+        Extension(caller): Extension<Caller>,
+        Query(params): Query<ReminderSearchParams>,
+    ) -> ApiResponse<Vec<ReminderResponse>> {
+        info!("Searching reminders with params {:?}", params);
+        handle_service_response!(
+            reminder_service.search_reminders(caller.user_id, params).await,
+            "Found {} reminders",
+            |data: &Vec<ReminderResponse>| data.len(),
+            "Failed to search reminders"
+        )
+    }
+
+    // ?until=2026-10-20T18:00&time_zone=Europe/Berlin
+    async fn due_reminders_handler(
+        State(reminder_service): State<Arc<ReminderService>>,
+        Extension(caller): Extension<Caller>,
+        Query(params): Query<DueRemindersParams>,
+    ) -> ApiResponse<Vec<ReminderResponse>> {
+        info!("Fetching due reminders with params {:?}", params);
+        let time_zone = ReminderRouter::validate_time_zone(params.time_zone)?;
+        let until = match params.until {
+            Some(until) => ReminderRouter::to_bson(until, &time_zone)?,
+            None => BsonDateTime::now(),
+        };
+        handle_service_response!(
+            reminder_service.get_due_reminders(caller.user_id, until).await,
+            "Found {} due reminders",
+            |data: &Vec<ReminderResponse>| data.len(),
+            "Failed to get due reminders"
+        )
+    }
+
+    async fn get_reminder_by_id_handler(
+        State(reminder_service): State<Arc<ReminderService>>,
+        Extension(caller): Extension<Caller>,
+        Path(id): Path<i64>,
+    ) -> ApiResponse<ReminderResponse> {
+        info!("Fetching reminder with id {} from database", id);
+        match reminder_service.find_reminder(caller.user_id, id).await {
+            Ok(Some(reminder)) => Ok(Json(ReminderResponse::from(reminder))),
+            Ok(None) => Err((StatusCode::NOT_FOUND, "Reminder not found".to_string())),
+            Err(e) => {
+                let error_message = format!("Failed to get reminder: {:?}", e);
+                Err((StatusCode::INTERNAL_SERVER_ERROR, error_message))
+            }
+        }
+    }
+
+    async fn update_reminder_handler(
+        State(reminder_service): State<Arc<ReminderService>>,
+        Extension(caller): Extension<Caller>,
+        Path(id): Path<i64>,
+        Json(payload): Json<UpdateReminderRequest>,
+    ) -> ApiResponse<ReminderResponse> {
+        info!("Updating reminder with id {} with payload {:?}", id, payload);
+
+        if payload.title.is_none()
+            && payload.note.is_none()
+            && payload.remind_at.is_none()
+            && payload.offset_minutes.is_none()
+            && payload.time_zone.is_none()
+        {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                "At least one field must be provided for update".to_string(),
+            ));
+        }
+
+        let existing = match reminder_service.find_reminder(caller.user_id, id).await {
+            Ok(Some(reminder)) => reminder,
+            Ok(None) => return Err((StatusCode::NOT_FOUND, "Reminder not found".to_string())),
+            Err(e) => {
+                let error_message = format!("Failed to update reminder: {:?}", e);
+                return Err((StatusCode::INTERNAL_SERVER_ERROR, error_message));
+            }
+        };
+
+        let mut changes = ReminderChanges {
+            title: payload.title,
+            note: payload.note,
+            ..Default::default()
+        };
+        if payload.time_zone.is_some() {
+            changes.time_zone = Some(ReminderRouter::validate_time_zone(payload.time_zone)?);
+        }
+        if payload.remind_at.is_some() || payload.offset_minutes.is_some() {
+            let time_zone = changes.time_zone.as_deref().unwrap_or(&existing.time_zone);
+            let (trigger, fire_at) = ReminderRouter::resolve_trigger(
+                &reminder_service,
+                caller.user_id,
+                existing.task_id,
+                payload.remind_at,
+                payload.offset_minutes,
+                time_zone,
+            )
+            .await?;
+            changes.trigger = Some(trigger);
+            changes.fire_at = Some(fire_at);
+        }
+
+        match reminder_service.update_reminder(caller.user_id, id, changes).await {
+            Ok(Some(reminder)) => Ok(Json(reminder)),
+            Ok(None) => Err((StatusCode::NOT_FOUND, "Reminder not found".to_string())),
+            Err(e) => {
+                let error_message = format!("Failed to update reminder: {:?}", e);
+                Err((StatusCode::INTERNAL_SERVER_ERROR, error_message))
+            }
+        }
+    }
+
+    async fn fire_reminder_handler(
+        State(reminder_service): State<Arc<ReminderService>>,
+        Extension(caller): Extension<Caller>,
+        Path(id): Path<i64>,
+    ) -> ApiResponse<ReminderResponse> {
+        info!("Marking reminder {} as fired", id);
+        match reminder_service.mark_fired(caller.user_id, id).await {
+            Ok(Some(reminder)) => Ok(Json(reminder)),
+            Ok(None) => Err((
+                StatusCode::CONFLICT,
+                "Reminder not found or not pending".to_string(),
+            )),
+            Err(e) => {
+                let error_message = format!("Failed to fire reminder: {:?}", e);
+                Err((StatusCode::INTERNAL_SERVER_ERROR, error_message))
+            }
+        }
+    }
+
+    async fn snooze_reminder_handler(
+        State(reminder_service): State<Arc<ReminderService>>,
+        Extension(caller): Extension<Caller>,
+        Path(id): Path<i64>,
+        Json(payload): Json<SnoozeReminderRequest>,
+    ) -> ApiResponse<ReminderResponse> {
+        info!("Snoozing reminder {} with payload {:?}", id, payload);
+
+        let now = BsonDateTime::now();
+        let until = match (payload.minutes, payload.until) {
+            (Some(minutes), None) if minutes > 0 => {
+                BsonDateTime::from_millis(now.timestamp_millis() + minutes * 60_000)
+            }
+            (None, Some(until)) => {
+                let time_zone = match reminder_service.find_reminder(caller.user_id, id).await {
+                    Ok(Some(reminder)) => reminder.time_zone,
+                    Ok(None) => {
+                        return Err((StatusCode::NOT_FOUND, "Reminder not found".to_string()));
+                    }
+                    Err(e) => {
+                        let error_message = format!("Failed to snooze reminder: {:?}", e);
+                        return Err((StatusCode::INTERNAL_SERVER_ERROR, error_message));
+                    }
+                };
+                ReminderRouter::to_bson(until, &time_zone)?
+            }
+            _ => {
+                return Err((
+                    StatusCode::BAD_REQUEST,
+                    "Provide either a positive number of minutes or an until time".to_string(),
+                ));
+            }
+        };
+        if until <= now {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                "Snooze time must be in the future".to_string(),
+            ));
+        }
+
+        match reminder_service.snooze(caller.user_id, id, until).await {
+            Ok(Some(reminder)) => Ok(Json(reminder)),
+            Ok(None) => Err((
+                StatusCode::CONFLICT,
+                "Reminder not found or already dismissed".to_string(),
+            )),
+            Err(e) => {
+                let error_message = format!("Failed to snooze reminder: {:?}", e);
+                Err((StatusCode::INTERNAL_SERVER_ERROR, error_message))
+            }
+        }
+    }
+
+    async fn dismiss_reminder_handler(
+        State(reminder_service): State<Arc<ReminderService>>,
+        Extension(caller): Extension<Caller>,
+        Path(id): Path<i64>,
+    ) -> ApiResponse<ReminderResponse> {
+        info!("Dismissing reminder {}", id);
+        match reminder_service.dismiss(caller.user_id, id).await {
+            Ok(Some(reminder)) => Ok(Json(reminder)),
+            Ok(None) => Err((
+                StatusCode::CONFLICT,
+                "Reminder not found or already dismissed".to_string(),
+            )),
+            Err(e) => {
+                let error_message = format!("Failed to dismiss reminder: {:?}", e);
+                Err((StatusCode::INTERNAL_SERVER_ERROR, error_message))
+            }
+        }
+    }
+
+    async fn delete_reminder_handler(
+        State(reminder_service): State<Arc<ReminderService>>,
+        Extension(caller): Extension<Caller>,
+        Path(id): Path<i64>,
+    ) -> ApiTextResponse {
+        info!("Soft deleting reminder with id {}", id);
+        match reminder_service.soft_delete_reminder(caller.user_id, id).await {
+            Ok(true) => Ok("Reminder deleted successfully".to_string()),
+            Ok(false) => Err((StatusCode::NOT_FOUND, "Reminder not found".to_string())),
+            Err(e) => {
+                let error_message = format!("Failed to delete reminder: {:?}", e);
+                Err((StatusCode::INTERNAL_SERVER_ERROR, error_message))
+            }
+        }
+    }
+}
@@ -0,0 +1,51 @@
+use crate::auth::workspace_access::split_denial;
+use crate::auth::Caller;
+use crate::handle_service_response;
+use crate::models::ApiResponse;
+use crate::services::time_entry_service::TimeEntryService;
+use axum::extract::{Query, State};
+use axum::routing::get;
+use axum::{Extension, Router};
+use mindvault_core::models::AppDatabase;
+use mindvault_shared::dtos::time_entry_dtos::TimeReportParams;
+use mindvault_shared::models::time_entries_model::TimeReportResponse;
+use std::sync::Arc;
+use tracing::info;
+
+/// Reports computed over the caller's private vault or `?workspace=<id>`. Time is tracked on
+/// tasks with `POST /tasks/{id}/timer/start|stop` and `POST /tasks/{id}/time`.
+pub(crate) struct ReportRouter {
+    time_entry_service: Arc<TimeEntryService>,
+}
+
+impl ReportRouter {
+    pub(crate) fn new(app_database: AppDatabase) -> Self {
+        let time_entry_service = Arc::new(TimeEntryService::new(app_database));
+        Self { time_entry_service }
+    }
+
+    pub(crate) fn get_routes(&self) -> Router {
+        Router::new()
+            .route("/time", get(ReportRouter::time_report_handler))
+            .with_state(self.time_entry_service.clone())
+    }
+
+    /// `?from=&to=&group_by=tag|task|day`
+    async fn time_report_handler(
+        State(time_entry_service): State<Arc<TimeEntryService>>,
+        Extension(caller): Extension<Caller>,
+        Query(params): Query<TimeReportParams>,
+    ) -> ApiResponse<TimeReportResponse> {
+        info!("Building time report with {:?}", params);
+        handle_service_response!(
+            split_denial(
+                time_entry_service
+                    .time_report(caller.user_id, params, &caller.dates)
+                    .await
+            )?,
+            "Built a time report with {} rows",
+            |data: &TimeReportResponse| data.rows.len(),
+            "Failed to build time report"
+        )
+    }
+}
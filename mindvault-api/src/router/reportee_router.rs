@@ -0,0 +1,150 @@
+use crate::auth::Caller;
+use crate::handle_service_response;
+use crate::models::{ApiResponse, ApiTextResponse};
+use crate::services::reportee_service::ReporteeService;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::routing::get;
+use axum::{Extension, Json, Router};
+use mindvault_core::models::AppDatabase;
+use mindvault_shared::dtos::reportee_dtos::{CreateReporteeRequest, UpdateReporteeRequest};
+use mindvault_shared::models::reportees_model::ReporteeResponse;
+use mindvault_shared::models::reviews_model::ReporteeTimelineResponse;
+use std::sync::Arc;
+use tracing::info;
+
+pub(crate) struct ReporteeRouter {
+    reportee_service: Arc<ReporteeService>,
+}
+
+impl ReporteeRouter {
+    pub(crate) fn new(app_database: AppDatabase) -> Self {
+        let reportee_service = Arc::new(ReporteeService::new(app_database));
+        Self { reportee_service }
+    }
+
+    pub(crate) fn get_routes(&self) -> Router {
+        Router::new()
+            .route(
+                "/",
+                get(ReporteeRouter::get_reportees_handler)
+                    .post(ReporteeRouter::create_reportee_handler),
+            )
+            .route(
+                "/{:id}",
+                get(ReporteeRouter::get_reportee_by_id_handler)
+                    .put(ReporteeRouter::update_reportee_handler)
+                    .delete(ReporteeRouter::delete_reportee_handler),
+            )
+            .route(
+                "/{:id}/timeline",
+                get(ReporteeRouter::get_reportee_timeline_handler),
+            )
+            .with_state(self.reportee_service.clone())
+    }
+
+    async fn create_reportee_handler(
+        State(reportee_service): State<Arc<ReporteeService>>,
+        Extension(caller): Extension<Caller>,
+        Json(payload): Json<CreateReporteeRequest>,
+    ) -> ApiResponse<ReporteeResponse> {
+        if payload.name.trim().is_empty() {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                "Name field is required".to_string(),
+            ));
+        }
+        handle_service_response!(
+            reportee_service.create_reportee(caller.user_id, payload).await,
+            "Created a new reportee {}",
+            |data: &ReporteeResponse| data.id,
+            "Unable to insert a new reportee into database"
+        )
+    }
+
+    async fn get_reportees_handler(
+        State(reportee_service): State<Arc<ReporteeService>>,
+        Extension(caller): Extension<Caller>,
+    ) -> ApiResponse<Vec<ReporteeResponse>> {
+        info!("Fetching reportees from database");
+        handle_service_response!(
+            reportee_service.get_all_reportees(caller.user_id).await,
+            "Fetched {} reportees",
+            |data: &Vec<ReporteeResponse>| data.len(),
+            "Failed to get reportees"
+        )
+    }
+
+    async fn get_reportee_by_id_handler(
+        State(reportee_service): State<Arc<ReporteeService>>,
+        Extension(caller): Extension<Caller>,
+        Path(id): Path<i64>,
+    ) -> ApiResponse<ReporteeResponse> {
+        info!("Fetching reportee with id {} from database", id);
+        match reportee_service.get_by_id(caller.user_id, id).await {
+            Ok(Some(reportee)) => Ok(Json(reportee)),
+            Ok(None) => Err((StatusCode::NOT_FOUND, "Reportee not found".to_string())),
+            Err(e) => {
+                let error_message = format!("Failed to get reportee: {:?}", e);
+                Err((StatusCode::INTERNAL_SERVER_ERROR, error_message))
+            }
+        }
+    }
+
+    async fn update_reportee_handler(
+        State(reportee_service): State<Arc<ReporteeService>>,
+        Extension(caller): Extension<Caller>,
+        Path(id): Path<i64>,
+        Json(payload): Json<UpdateReporteeRequest>,
+    ) -> ApiResponse<ReporteeResponse> {
+        info!("Updating reportee with id {} with payload {:?}", id, payload);
+
+        if payload.name.is_none() && payload.email.is_none() && payload.designation.is_none() {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                "At least one field must be provided for update".to_string(),
+            ));
+        }
+
+        match reportee_service.update_reportee(caller.user_id, id, payload).await {
+            Ok(Some(reportee)) => Ok(Json(reportee)),
+            Ok(None) => Err((StatusCode::NOT_FOUND, "Reportee not found".to_string())),
+            Err(e) => {
+                let error_message = format!("Failed to update reportee: {:?}", e);
+                Err((StatusCode::INTERNAL_SERVER_ERROR, error_message))
+            }
+        }
+    }
+
+    async fn delete_reportee_handler(
+        State(reportee_service): State<Arc<ReporteeService>>,
+        Extension(caller): Extension<Caller>,
+        Path(id): Path<i64>,
+    ) -> ApiTextResponse {
+        info!("Soft deleting reportee with id {}", id);
+        match reportee_service.soft_delete_reportee(caller.user_id, id).await {
+            Ok(true) => Ok("Reportee deleted successfully".to_string()),
+            Ok(false) => Err((StatusCode::NOT_FOUND, "Reportee not found".to_string())),
+            Err(e) => {
+                let error_message = format!("Failed to delete reportee: {:?}", e);
+                Err((StatusCode::INTERNAL_SERVER_ERROR, error_message))
+            }
+        }
+    }
+
+    async fn get_reportee_timeline_handler(
+        State(reportee_service): State<Arc<ReporteeService>>,
+        Extension(caller): Extension<Caller>,
+        Path(id): Path<i64>,
+    ) -> ApiResponse<ReporteeTimelineResponse> {
+        info!("Fetching timeline for reportee {}", id);
+        match reportee_service.get_timeline(caller.user_id, id).await {
+            Ok(Some(timeline)) => Ok(Json(timeline)),
+            Ok(None) => Err((StatusCode::NOT_FOUND, "Reportee not found".to_string())),
+            Err(e) => {
+                let error_message = format!("Failed to get reportee timeline: {:?}", e);
+                Err((StatusCode::INTERNAL_SERVER_ERROR, error_message))
+            }
+        }
+    }
+}
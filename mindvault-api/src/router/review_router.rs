@@ -0,0 +1,384 @@
+use crate::auth::Caller;
+use crate::handle_service_response;
+use crate::models::{
+    plain_error, validation_error, ApiResponse, ApiTextResponse, ApiValidatedResponse,
+};
+use crate::services::review_service::ReviewService;
+use crate::services::task_service::resolve_day;
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::routing::{get, post};
+use axum::{Extension, Json, Router};
+use mindvault_core::models::AppDatabase;
+use mindvault_shared::dtos::review_dtos::{
+    CreateReviewRequest, ReviewSearchParams, UpdateReviewRequest, REVIEW_RATING_RANGE,
+};
+use mindvault_shared::models::review_templates_model::{
+    FieldError, ReviewAnswer, ReviewTemplateRef, ValidationErrorResponse,
+};
+use mindvault_shared::models::reviews_model::{EReviewState, ReviewRating, ReviewResponse};
+use std::sync::Arc;
+use tracing::info;
+
+pub(crate) struct ReviewRouter {
+    review_service: Arc<ReviewService>,
+}
+
+impl ReviewRouter {
+    pub(crate) fn new(app_database: AppDatabase) -> Self {
+        let review_service = Arc::new(ReviewService::new(app_database));
+        Self { review_service }
+    }
+
+    pub(crate) fn get_routes(&self) -> Router {
+        Router::new()
+            .route(
+                "/",
+                get(ReviewRouter::search_reviews_handler).post(ReviewRouter::create_review_handler),
+            )
+            .route(
+                "/{:id}",
+                get(ReviewRouter::get_review_by_id_handler)
+                    .put(ReviewRouter::update_review_handler)
+                    .delete(ReviewRouter::delete_review_handler),
+            )
+            .route("/{:id}/submit", post(ReviewRouter::submit_review_handler))
+            .route(
+                "/{:id}/acknowledge",
+                post(ReviewRouter::acknowledge_review_handler),
+            )
+            .with_state(self.review_service.clone())
+    }
+
+    fn validate_ratings(ratings: &[ReviewRating]) -> Result<(), (StatusCode, String)> {
+        for rating in ratings {
+            if rating.category.trim().is_empty() {
+                return Err((
+                    StatusCode::BAD_REQUEST,
+                    "Rating category cannot be empty".to_string(),
+                ));
+            }
+            if !REVIEW_RATING_RANGE.contains(&rating.rating) {
+                return Err((
+                    StatusCode::BAD_REQUEST,
+                    format!(
+                        "Rating for '{}' must be between {} and {}",
+                        rating.category,
+                        REVIEW_RATING_RANGE.start(),
+                        REVIEW_RATING_RANGE.end()
+                    ),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    async fn create_review_handler(
+        State(review_service): State<Arc<ReviewService>>,
+        Extension(caller): Extension<Caller>,
+        Json(mut payload): Json<CreateReviewRequest>,
+    ) -> ApiValidatedResponse<ReviewResponse> {
+        if payload.cycle.trim().is_empty() || payload.reviewer.trim().is_empty() {
+            return Err(validation_error(
+                StatusCode::BAD_REQUEST,
+                "Cycle and reviewer fields are required",
+                Vec::new(),
+            ));
+        }
+        ReviewRouter::validate_ratings(&payload.ratings).map_err(plain_error)?;
+        resolve_day(&mut payload.review_date, &caller.dates)
+            .map_err(|denial| plain_error(denial.into()))?;
+
+        match review_service.reportee_exists(caller.user_id, payload.reportee_id).await {
+            Ok(true) => {}
+            Ok(false) => {
+                return Err(validation_error(
+                    StatusCode::BAD_REQUEST,
+                    format!("Reportee with id {} not found", payload.reportee_id),
+                    Vec::new(),
+                ));
+            }
+            Err(e) => {
+                let error_message = format!("Failed to validate reportee: {:?}", e);
+                return Err(validation_error(
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    error_message,
+                    Vec::new(),
+                ));
+            }
+        }
+
+        let template_ref = match payload.template_id {
+            Some(template_id) => {
+                let template = match review_service
+                    .find_latest_template(caller.user_id, template_id)
+                    .await
+                {
+                    Ok(Some(template)) => template,
+                    Ok(None) => {
+                        return Err(validation_error(
+                            StatusCode::BAD_REQUEST,
+                            "Invalid review",
+                            vec![FieldError::new(
+                                "templateId",
+                                format!("review template {} not found", template_id),
+                            )],
+                        ));
+                    }
+                    Err(e) => {
+                        let error_message = format!("Failed to load review template: {:?}", e);
+                        return Err(validation_error(
+                            StatusCode::INTERNAL_SERVER_ERROR,
+                            error_message,
+                            Vec::new(),
+                        ));
+                    }
+                };
+                let errors = template.validate_answers(&payload.answers, false);
+                if !errors.is_empty() {
+                    return Err(validation_error(
+                        StatusCode::BAD_REQUEST,
+                        "Answers do not match the review template",
+                        errors,
+                    ));
+                }
+                Some(ReviewTemplateRef {
+                    template_id: template.template_id,
+                    version: template.version,
+                })
+            }
+            None if !payload.answers.is_empty() => {
+                return Err(validation_error(
+                    StatusCode::BAD_REQUEST,
+                    "Invalid review",
+                    vec![FieldError::new(
+                        "answers",
+                        "answers can only be given for template-based reviews",
+                    )],
+                ));
+            }
+            None => None,
+        };
+
+        match review_service.create_review(caller.user_id, payload, template_ref).await {
+            Ok(review) => {
+                tracing::debug!("Created a new review {}", review.id);
+                Ok(Json(review))
+            }
+            Err(err) => {
+                let error_message = format!("Unable to insert a new review into database: {:?}", err);
+                tracing::error!("{}", error_message);
+                Err(validation_error(
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    error_message,
+                    Vec::new(),
+                ))
+            }
+        }
+    }
+
+    /// Validates answers against the template version a review was created from
+    async fn validate_review_answers(
+        review_service: &ReviewService,
+        owner_id: i64,
+        id: i64,
+        answers: Option<&[ReviewAnswer]>,
+        enforce_required: bool,
+    ) -> Result<(), (StatusCode, Json<ValidationErrorResponse>)> {
+        let (review, template) = match review_service
+            .find_review_with_template(owner_id, id)
+            .await
+        {
+            Ok(Some(found)) => found,
+            Ok(None) => {
+                return Err(validation_error(
+                    StatusCode::NOT_FOUND,
+                    "Review not found",
+                    Vec::new(),
+                ));
+            }
+            Err(e) => {
+                let error_message = format!("Failed to load review: {:?}", e);
+                return Err(validation_error(
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    error_message,
+                    Vec::new(),
+                ));
+            }
+        };
+
+        let answers = answers.unwrap_or(&review.answers);
+        let errors = match template {
+            Some(template) => template.validate_answers(answers, enforce_required),
+            None if !answers.is_empty() => vec![FieldError::new(
+                "answers",
+                "answers can only be given for template-based reviews",
+            )],
+            None => Vec::new(),
+        };
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(validation_error(
+                StatusCode::BAD_REQUEST,
+                "Answers do not match the review template",
+                errors,
+            ))
+        }
+    }
+
+    async fn search_reviews_handler(
+        State(review_service): State<Arc<ReviewService>>,
+        Extension(caller): Extension<Caller>,
+        Query(params): Query<ReviewSearchParams>,
+    ) -> ApiResponse<Vec<ReviewResponse>> {
+        info!("Searching reviews with params {:?}", params);
+        handle_service_response!(
+            review_service.search_reviews(caller.user_id, params).await,
+            "Found {} reviews",
+            |data: &Vec<ReviewResponse>| data.len(),
+            "Failed to search reviews"
+        )
+    }
+
+    async fn get_review_by_id_handler(
+        State(review_service): State<Arc<ReviewService>>,
+        Extension(caller): Extension<Caller>,
+        Path(id): Path<i64>,
+    ) -> ApiResponse<ReviewResponse> {
+        info!("Fetching review with id {} from database", id);
+        handle_service_response!(
+            review_service.get_by_id(caller.user_id, id).await,
+            "Found review with id {}",
+            |data: &ReviewResponse| data.id,
+            "Failed to get current review"
+        )
+    }
+
+    async fn update_review_handler(
+        State(review_service): State<Arc<ReviewService>>,
+        Extension(caller): Extension<Caller>,
+        Path(id): Path<i64>,
+        Json(mut payload): Json<UpdateReviewRequest>,
+    ) -> ApiValidatedResponse<ReviewResponse> {
+        info!("Updating review with id {}", id);
+
+        if payload.cycle.is_none()
+            && payload.reviewer.is_none()
+            && payload.ratings.is_none()
+            && payload.feedback.is_none()
+            && payload.answers.is_none()
+            && payload.review_date.is_none()
+        {
+            return Err(validation_error(
+                StatusCode::BAD_REQUEST,
+                "At least one field must be provided for update",
+                Vec::new(),
+            ));
+        }
+        if let Some(ratings) = &payload.ratings {
+            ReviewRouter::validate_ratings(ratings).map_err(plain_error)?;
+        }
+        resolve_day(&mut payload.review_date, &caller.dates)
+            .map_err(|denial| plain_error(denial.into()))?;
+        if let Some(answers) = &payload.answers {
+            ReviewRouter::validate_review_answers(
+                &review_service,
+                caller.user_id,
+                id,
+                Some(answers),
+                false,
+            )
+            .await?;
+        }
+
+        match review_service.update_review(caller.user_id, id, payload).await {
+            Ok(Some(review)) => Ok(Json(review)),
+            Ok(None) => Err(validation_error(
+                StatusCode::CONFLICT,
+                "Review not found or no longer in Draft state",
+                Vec::new(),
+            )),
+            Err(e) => {
+                let error_message = format!("Failed to update review: {:?}", e);
+                Err(validation_error(
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    error_message,
+                    Vec::new(),
+                ))
+            }
+        }
+    }
+
+    async fn submit_review_handler(
+        State(review_service): State<Arc<ReviewService>>,
+        Extension(caller): Extension<Caller>,
+        Path(id): Path<i64>,
+    ) -> ApiValidatedResponse<ReviewResponse> {
+        info!("Submitting review with id {}", id);
+        // Required template questions are only enforced once the review leaves Draft
+        ReviewRouter::validate_review_answers(&review_service, caller.user_id, id, None, true)
+            .await?;
+        ReviewRouter::transition(
+            review_service,
+            caller.user_id,
+            id,
+            EReviewState::Draft,
+            EReviewState::Submitted,
+        )
+        .await
+        .map_err(plain_error)
+    }
+
+    async fn acknowledge_review_handler(
+        State(review_service): State<Arc<ReviewService>>,
+        Extension(caller): Extension<Caller>,
+        Path(id): Path<i64>,
+    ) -> ApiResponse<ReviewResponse> {
+        info!("Acknowledging review with id {}", id);
+        ReviewRouter::transition(
+            review_service,
+            caller.user_id,
+            id,
+            EReviewState::Submitted,
+            EReviewState::Acknowledged,
+        )
+        .await
+    }
+
+    async fn transition(
+        review_service: Arc<ReviewService>,
+        owner_id: i64,
+        id: i64,
+        from: EReviewState,
+        to: EReviewState,
+    ) -> ApiResponse<ReviewResponse> {
+        match review_service.transition_review(owner_id, id, from, to).await {
+            Ok(Some(review)) => Ok(Json(review)),
+            Ok(None) => Err((
+                StatusCode::CONFLICT,
+                format!("Review not found or not in {:?} state", from),
+            )),
+            Err(e) => {
+                let error_message = format!("Failed to change review state: {:?}", e);
+                Err((StatusCode::INTERNAL_SERVER_ERROR, error_message))
+            }
+        }
+    }
+
+    async fn delete_review_handler(
+        State(review_service): State<Arc<ReviewService>>,
+        Extension(caller): Extension<Caller>,
+        Path(id): Path<i64>,
+    ) -> ApiTextResponse {
+        info!("Soft deleting review with id {}", id);
+        match review_service.soft_delete_review(caller.user_id, id).await {
+            Ok(true) => Ok("Review deleted successfully".to_string()),
+            Ok(false) => Err((StatusCode::NOT_FOUND, "Review not found".to_string())),
+            Err(e) => {
+                let error_message = format!("Failed to delete review: {:?}", e);
+                Err((StatusCode::INTERNAL_SERVER_ERROR, error_message))
+            }
+        }
+    }
+}
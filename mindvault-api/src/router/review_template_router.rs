@@ -0,0 +1,195 @@
+use crate::auth::Caller;
+use crate::handle_service_response;
+use crate::models::{validation_error, ApiResponse, ApiTextResponse, ApiValidatedResponse};
+use crate::services::review_template_service::ReviewTemplateService;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::routing::get;
+use axum::{Extension, Json, Router};
+use mindvault_core::models::AppDatabase;
+use mindvault_shared::dtos::review_template_dtos::SaveReviewTemplateRequest;
+use mindvault_shared::models::review_templates_model::{
+    FieldError, ReviewTemplate, ReviewTemplateResponse, ValidationErrorResponse,
+};
+use std::sync::Arc;
+use tracing::info;
+
+pub(crate) struct ReviewTemplateRouter {
+    template_service: Arc<ReviewTemplateService>,
+}
+
+impl ReviewTemplateRouter {
+    pub(crate) fn new(app_database: AppDatabase) -> Self {
+        let template_service = Arc::new(ReviewTemplateService::new(app_database));
+        Self { template_service }
+    }
+
+    pub(crate) fn get_routes(&self) -> Router {
+        Router::new()
+            .route(
+                "/",
+                get(ReviewTemplateRouter::get_templates_handler)
+                    .post(ReviewTemplateRouter::create_template_handler),
+            )
+            .route(
+                "/{:id}",
+                get(ReviewTemplateRouter::get_template_handler)
+                    .put(ReviewTemplateRouter::publish_version_handler)
+                    .delete(ReviewTemplateRouter::delete_template_handler),
+            )
+            .route(
+                "/{:id}/versions",
+                get(ReviewTemplateRouter::get_template_versions_handler),
+            )
+            .route(
+                "/{:id}/versions/{:version}",
+                get(ReviewTemplateRouter::get_template_version_handler),
+            )
+            .with_state(self.template_service.clone())
+    }
+
+    fn validate_request(
+        payload: &SaveReviewTemplateRequest,
+    ) -> Result<(), (StatusCode, Json<ValidationErrorResponse>)> {
+        let mut errors = ReviewTemplate::validate_definition(&payload.sections);
+        if payload.name.trim().is_empty() {
+            errors.insert(0, FieldError::new("name", "template name is required"));
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(validation_error(
+                StatusCode::BAD_REQUEST,
+                "Invalid review template",
+                errors,
+            ))
+        }
+    }
+
+    async fn create_template_handler(
+        State(template_service): State<Arc<ReviewTemplateService>>,
+        Extension(caller): Extension<Caller>,
+        Json(payload): Json<SaveReviewTemplateRequest>,
+    ) -> ApiValidatedResponse<ReviewTemplateResponse> {
+        ReviewTemplateRouter::validate_request(&payload)?;
+        match template_service.create_template(caller.user_id, payload).await {
+            Ok(template) => {
+                tracing::debug!("Created review template {}", template.template_id);
+                Ok(Json(template))
+            }
+            Err(e) => {
+                let error_message = format!("Unable to insert a new review template: {:?}", e);
+                tracing::error!("{}", error_message);
+                Err(validation_error(
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    error_message,
+                    Vec::new(),
+                ))
+            }
+        }
+    }
+
+    async fn publish_version_handler(
+        State(template_service): State<Arc<ReviewTemplateService>>,
+        Extension(caller): Extension<Caller>,
+        Path(id): Path<i64>,
+        Json(payload): Json<SaveReviewTemplateRequest>,
+    ) -> ApiValidatedResponse<ReviewTemplateResponse> {
+        info!("Publishing a new version of review template {}", id);
+        ReviewTemplateRouter::validate_request(&payload)?;
+        match template_service.create_version(caller.user_id, id, payload).await {
+            Ok(Some(template)) => Ok(Json(template)),
+            Ok(None) => Err(validation_error(
+                StatusCode::NOT_FOUND,
+                "Review template not found",
+                Vec::new(),
+            )),
+            Err(e) => {
+                let error_message = format!("Failed to publish review template: {:?}", e);
+                Err(validation_error(
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    error_message,
+                    Vec::new(),
+                ))
+            }
+        }
+    }
+
+    async fn get_templates_handler(
+        State(template_service): State<Arc<ReviewTemplateService>>,
+        Extension(caller): Extension<Caller>,
+    ) -> ApiResponse<Vec<ReviewTemplateResponse>> {
+        info!("Fetching review templates from database");
+        handle_service_response!(
+            template_service.get_all_templates(caller.user_id).await,
+            "Fetched {} review templates",
+            |data: &Vec<ReviewTemplateResponse>| data.len(),
+            "Failed to get review templates"
+        )
+    }
+
+    async fn get_template_handler(
+        State(template_service): State<Arc<ReviewTemplateService>>,
+        Extension(caller): Extension<Caller>,
+        Path(id): Path<i64>,
+    ) -> ApiResponse<ReviewTemplateResponse> {
+        info!("Fetching review template {}", id);
+        match template_service.get_latest(caller.user_id, id).await {
+            Ok(Some(template)) => Ok(Json(template)),
+            Ok(None) => Err((StatusCode::NOT_FOUND, "Review template not found".to_string())),
+            Err(e) => {
+                let error_message = format!("Failed to get review template: {:?}", e);
+                Err((StatusCode::INTERNAL_SERVER_ERROR, error_message))
+            }
+        }
+    }
+
+    async fn get_template_versions_handler(
+        State(template_service): State<Arc<ReviewTemplateService>>,
+        Extension(caller): Extension<Caller>,
+        Path(id): Path<i64>,
+    ) -> ApiResponse<Vec<ReviewTemplateResponse>> {
+        info!("Fetching versions of review template {}", id);
+        handle_service_response!(
+            template_service.get_versions(caller.user_id, id).await,
+            "Fetched {} review template versions",
+            |data: &Vec<ReviewTemplateResponse>| data.len(),
+            "Failed to get review template versions"
+        )
+    }
+
+    async fn get_template_version_handler(
+        State(template_service): State<Arc<ReviewTemplateService>>,
+        Extension(caller): Extension<Caller>,
+        Path((id, version)): Path<(i64, i32)>,
+    ) -> ApiResponse<ReviewTemplateResponse> {
+        info!("Fetching version {} of review template {}", version, id);
+        match template_service.get_version(caller.user_id, id, version).await {
+            Ok(Some(template)) => Ok(Json(template)),
+            Ok(None) => Err((
+                StatusCode::NOT_FOUND,
+                "Review template version not found".to_string(),
+            )),
+            Err(e) => {
+                let error_message = format!("Failed to get review template version: {:?}", e);
+                Err((StatusCode::INTERNAL_SERVER_ERROR, error_message))
+            }
+        }
+    }
+
+    async fn delete_template_handler(
+        State(template_service): State<Arc<ReviewTemplateService>>,
+        Extension(caller): Extension<Caller>,
+        Path(id): Path<i64>,
+    ) -> ApiTextResponse {
+        info!("Soft deleting review template {}", id);
+        match template_service.soft_delete_template(caller.user_id, id).await {
+            Ok(true) => Ok("Review template deleted successfully".to_string()),
+            Ok(false) => Err((StatusCode::NOT_FOUND, "Review template not found".to_string())),
+            Err(e) => {
+                let error_message = format!("Failed to delete review template: {:?}", e);
+                Err((StatusCode::INTERNAL_SERVER_ERROR, error_message))
+            }
+        }
+    }
+}
@@ -0,0 +1,147 @@
+use crate::auth::Caller;
+use crate::handle_service_response;
+use crate::models::{ApiResponse, ApiTextResponse};
+use crate::services::saved_filter_service::SavedFilterService;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::routing::get;
+use axum::{Extension, Json, Router};
+use mindvault_core::models::AppDatabase;
+use mindvault_shared::dtos::saved_filter_dtos::{CreateSavedFilterRequest, UpdateSavedFilterRequest};
+use mindvault_shared::models::saved_filters_model::SavedFilterResponse;
+use mindvault_shared::models::tasks_model::TaskResponse;
+use std::sync::Arc;
+use tracing::info;
+
+pub(crate) struct SavedFilterRouter {
+    saved_filter_service: Arc<SavedFilterService>,
+}
+
+impl SavedFilterRouter {
+    pub(crate) fn new(app_database: AppDatabase) -> Self {
+        let saved_filter_service = Arc::new(SavedFilterService::new(app_database));
+        Self {
+            saved_filter_service,
+        }
+    }
+
+    pub(crate) fn get_routes(&self) -> Router {
+        Router::new()
+            .route(
+                "/",
+                get(SavedFilterRouter::get_filters_handler)
+                    .post(SavedFilterRouter::create_filter_handler),
+            )
+            .route(
+                "/{:id}",
+                get(SavedFilterRouter::get_filter_by_id_handler)
+                    .put(SavedFilterRouter::update_filter_handler)
+                    .delete(SavedFilterRouter::delete_filter_handler),
+            )
+            .route("/{:id}/tasks", get(SavedFilterRouter::get_filter_tasks_handler))
+            .with_state(self.saved_filter_service.clone())
+    }
+
+    async fn get_filters_handler(
+        State(saved_filter_service): State<Arc<SavedFilterService>>,
+        Extension(caller): Extension<Caller>,
+    ) -> ApiResponse<Vec<SavedFilterResponse>> {
+        info!("Fetching all saved filters");
+        handle_service_response!(
+            saved_filter_service.get_filters(caller.user_id).await,
+            "Found {} saved filters",
+            |data: &Vec<SavedFilterResponse>| data.len(),
+            "Failed to get saved filters"
+        )
+    }
+
+    async fn create_filter_handler(
+        State(saved_filter_service): State<Arc<SavedFilterService>>,
+        Extension(caller): Extension<Caller>,
+        Json(payload): Json<CreateSavedFilterRequest>,
+    ) -> ApiResponse<SavedFilterResponse> {
+        if payload.name.trim().is_empty() {
+            return Err((StatusCode::BAD_REQUEST, "Name field is required".to_string()));
+        }
+        handle_service_response!(
+            saved_filter_service.create_filter(caller.user_id, payload).await,
+            "Created a new saved filter {}",
+            |data: &SavedFilterResponse| data.id,
+            "Unable to insert a new saved filter into database"
+        )
+    }
+
+    async fn get_filter_by_id_handler(
+        State(saved_filter_service): State<Arc<SavedFilterService>>,
+        Extension(caller): Extension<Caller>,
+        Path(id): Path<i64>,
+    ) -> ApiResponse<SavedFilterResponse> {
+        info!("Fetching saved filter with id {}", id);
+        match saved_filter_service.find_filter(caller.user_id, id).await {
+            Ok(Some(filter)) => Ok(Json(SavedFilterResponse::from(filter))),
+            Ok(None) => Err((StatusCode::NOT_FOUND, "Saved filter not found".to_string())),
+            Err(e) => {
+                let error_message = format!("Failed to get saved filter: {:?}", e);
+                Err((StatusCode::INTERNAL_SERVER_ERROR, error_message))
+            }
+        }
+    }
+
+    async fn update_filter_handler(
+        State(saved_filter_service): State<Arc<SavedFilterService>>,
+        Extension(caller): Extension<Caller>,
+        Path(id): Path<i64>,
+        Json(payload): Json<UpdateSavedFilterRequest>,
+    ) -> ApiResponse<SavedFilterResponse> {
+        info!("Updating saved filter with id {}", id);
+        if payload.name.is_none() && payload.criteria.is_none() {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                "At least one field must be provided for update".to_string(),
+            ));
+        }
+        match saved_filter_service.update_filter(caller.user_id, id, payload).await {
+            Ok(Some(filter)) => Ok(Json(filter)),
+            Ok(None) => Err((StatusCode::NOT_FOUND, "Saved filter not found".to_string())),
+            Err(e) => {
+                let error_message = format!("Failed to update saved filter: {:?}", e);
+                Err((StatusCode::INTERNAL_SERVER_ERROR, error_message))
+            }
+        }
+    }
+
+    async fn delete_filter_handler(
+        State(saved_filter_service): State<Arc<SavedFilterService>>,
+        Extension(caller): Extension<Caller>,
+        Path(id): Path<i64>,
+    ) -> ApiTextResponse {
+        info!("Soft deleting saved filter with id {}", id);
+        match saved_filter_service.delete_filter(caller.user_id, id).await {
+            Ok(true) => Ok("Saved filter deleted successfully".to_string()),
+            Ok(false) => Err((StatusCode::NOT_FOUND, "Saved filter not found".to_string())),
+            Err(e) => {
+                let error_message = format!("Failed to delete saved filter: {:?}", e);
+                Err((StatusCode::INTERNAL_SERVER_ERROR, error_message))
+            }
+        }
+    }
+
+    async fn get_filter_tasks_handler(
+        State(saved_filter_service): State<Arc<SavedFilterService>>,
+        Extension(caller): Extension<Caller>,
+        Path(id): Path<i64>,
+    ) -> ApiResponse<Vec<TaskResponse>> {
+        info!("Fetching tasks for saved filter {}", id);
+        let tasks = saved_filter_service
+            .get_filter_tasks(caller.user_id, id, &caller.dates)
+            .await;
+        match tasks {
+            Ok(Some(tasks)) => Ok(Json(tasks)),
+            Ok(None) => Err((StatusCode::NOT_FOUND, "Saved filter not found".to_string())),
+            Err(e) => {
+                let error_message = format!("Failed to get tasks for saved filter: {:?}", e);
+                Err((StatusCode::INTERNAL_SERVER_ERROR, error_message))
+            }
+        }
+    }
+}
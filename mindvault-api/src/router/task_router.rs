@@ -1,223 +1,915 @@
+use crate::auth::workspace_access::split_denial;
+use crate::auth::Caller;
 use crate::handle_service_response;
 use crate::models::{ApiResponse, ApiTextResponse};
+use crate::events::ChangeFeed;
 use crate::services::task_service::TaskService;
-use axum::extract::{Path, Query, State};
+use crate::services::time_entry_service::TimeEntryService;
+use axum::extract::{FromRef, Path, Query, State};
+#[cfg(test)]
+use axum::http::Method;
 use axum::http::StatusCode;
 use axum::routing::{delete, get, post};
-use axum::{Json, Router};
+use axum::{Extension, Json, Router};
 use mindvault_core::models::AppDatabase;
 use mindvault_shared::dtos::task_dtos::{
-    BulkCreateTaskRequest, CreateTaskRequest, SearchAndUpdateRequest, TaskSearchParams,
+    BulkCreateTaskRequest, CreateTaskRequest, MoveTaskRequest, QuickAddParams, QuickAddRequest,
+    ReorderTasksRequest, SearchAndUpdateRequest, TaskSearchParams, TaskVaultParams,
     UpdateTaskRequest,
 };
-use mindvault_shared::models::tasks_model::{ETaskStatus, TaskResponse};
+use mindvault_shared::dtos::time_entry_dtos::{LogTimeRequest, TimerRequest};
+use mindvault_shared::models::tasks_model::{
+    ETaskStatus, QuickAddResponse, TaskResponse, TaskResponseV2,
+};
+use mindvault_shared::models::time_entries_model::{
+    TaskTimeResponse, TimeEntryResponse, TimerResponse,
+};
+use mindvault_shared::utils::date_input::DatePreferences;
+use serde::Serialize;
+use std::collections::HashSet;
 use std::sync::Arc;
 use tracing::info;
 
-pub(crate) struct TaskRouter {
+/// Shape a task is returned in; each API version picks its own
+pub(crate) trait TaskShape: Serialize + Send + Sized + 'static {
+    fn from_task(task: TaskResponse, dates: &DatePreferences) -> Self;
+
+    fn task_id(&self) -> i64;
+
+    fn from_vec(tasks: Vec<TaskResponse>, dates: &DatePreferences) -> Vec<Self> {
+        tasks.into_iter().map(|task| Self::from_task(task, dates)).collect()
+    }
+}
+
+impl TaskShape for TaskResponse {
+    fn from_task(task: TaskResponse, _dates: &DatePreferences) -> Self {
+        task
+    }
+
+    fn task_id(&self) -> i64 {
+        self.id
+    }
+}
+
+impl TaskShape for TaskResponseV2 {
+    fn from_task(task: TaskResponse, dates: &DatePreferences) -> Self {
+        TaskResponseV2::new(task, dates.tz())
+    }
+
+    fn task_id(&self) -> i64 {
+        self.id
+    }
+}
+
+/// Task handlers take the service they need; time tracking has its own
+#[derive(Clone)]
+pub(crate) struct TaskRouterState {
     task_service: Arc<TaskService>,
+    time_entry_service: Arc<TimeEntryService>,
+}
+
+impl FromRef<TaskRouterState> for Arc<TaskService> {
+    fn from_ref(state: &TaskRouterState) -> Self {
+        state.task_service.clone()
+    }
+}
+
+impl FromRef<TaskRouterState> for Arc<TimeEntryService> {
+    fn from_ref(state: &TaskRouterState) -> Self {
+        state.time_entry_service.clone()
+    }
+}
+
+/// Every task route with the methods it answers, relative to where the router is nested.
+/// The API docs test checks that each is documented, and the tests below that `get_routes`
+/// mounts exactly these.
+#[cfg(test)]
+pub(crate) const TASK_ROUTES: [(&str, &[Method]); 12] = [
+    ("/", &[Method::GET, Method::POST]),
+    ("/{:id}", &[Method::GET, Method::PUT, Method::DELETE]),
+    ("/search", &[Method::GET, Method::PUT]),
+    ("/bulk", &[Method::POST]),
+    ("/quick", &[Method::POST]),
+    ("/{:id}/move", &[Method::POST]),
+    ("/reorder", &[Method::POST]),
+    ("/{:id}/timer/start", &[Method::POST]),
+    ("/{:id}/timer/stop", &[Method::POST]),
+    ("/{:id}/time", &[Method::GET, Method::POST]),
+    ("/{:id}/time/{:entry_id}", &[Method::DELETE]),
+    ("/status/{:status}", &[Method::DELETE]),
+];
+
+pub(crate) struct TaskRouter {
+    state: TaskRouterState,
 }
 
 impl TaskRouter {
-    pub(crate) fn new(app_database: AppDatabase) -> Self {
-        let task_service = Arc::new(TaskService::new(app_database));
-        Self { task_service }
+    pub(crate) fn new(app_database: AppDatabase, change_feed: Arc<ChangeFeed>) -> Self {
+        let time_entry_service = Arc::new(TimeEntryService::new(app_database.clone()));
+        let task_service = Arc::new(TaskService::new(app_database, change_feed));
+        Self {
+            state: TaskRouterState {
+                task_service,
+                time_entry_service,
+            },
+        }
     }
 
-    pub(crate) fn get_routes(&self) -> Router {
+    /// Task routes answering with `R`, the task shape of the API version they are mounted in
+    pub(crate) fn get_routes<R: TaskShape>(&self) -> Router {
         Router::new()
-            .route(
-                "/",
-                get(TaskRouter::get_tasks_handler).post(TaskRouter::create_task_handler),
-            )
+            .route("/", get(get_tasks_handler::<R>).post(create_task_handler::<R>))
             .route(
                 "/{:id}",
-                get(TaskRouter::get_task_by_id_handler)
-                    .put(TaskRouter::update_task_handler)
-                    .delete(TaskRouter::delete_task_handler),
+                get(get_task_by_id_handler::<R>)
+                    .put(update_task_handler::<R>)
+                    .delete(delete_task_handler),
             )
             .route(
                 "/search",
-                get(TaskRouter::search_tasks_by_text_handler)
-                    .put(TaskRouter::search_and_update_handler),
-            )
-            .route("/bulk", post(TaskRouter::bulk_create_tasks_handler))
-            .route(
-                "/status/{:status}",
-                delete(TaskRouter::bulk_delete_by_status_handler),
+                get(search_tasks_by_text_handler::<R>).put(search_and_update_handler::<R>),
             )
-            .with_state(self.task_service.clone())
+            .route("/bulk", post(bulk_create_tasks_handler::<R>))
+            .route("/quick", post(quick_add_handler::<R>))
+            .route("/{:id}/move", post(move_task_handler::<R>))
+            .route("/reorder", post(reorder_tasks_handler::<R>))
+            .route("/{:id}/timer/start", post(start_timer_handler))
+            .route("/{:id}/timer/stop", post(stop_timer_handler))
+            .route("/{:id}/time", get(get_task_time_handler).post(log_time_handler))
+            .route("/{:id}/time/{:entry_id}", delete(delete_time_entry_handler))
+            .route("/status/{:status}", delete(bulk_delete_by_status_handler))
+            .with_state(self.state.clone())
     }
+}
 
-    async fn create_task_handler(
-        State(task_service): State<Arc<TaskService>>,
-        Json(payload): Json<CreateTaskRequest>,
-    ) -> ApiResponse<TaskResponse> {
-        if payload.name.trim().is_empty() {
-            return Err((
-                StatusCode::BAD_REQUEST,
-                "Name field is required".to_string(),
-            ));
+// The handlers are free functions rather than associated ones because `#[utoipa::path]`
+// generates trait impls next to the function, which an `impl` block cannot hold. Their paths
+// are relative to the `/v1` server; `/v2` serves the same operations.
+
+#[utoipa::path(
+    post,
+    path = "/tasks",
+    tag = "tasks",
+    params(TaskVaultParams),
+    request_body = CreateTaskRequest,
+    responses(
+        (status = 200, description = "The created task", body = TaskResponse),
+        (status = 400, description = "Missing name, unknown assignee or unknown workflow \
+            status", body = String),
+        (status = 403, description = "Role too low for the workspace", body = String),
+        (status = 404, description = "Unknown workspace", body = String),
+    )
+)]
+async fn create_task_handler<R: TaskShape>(
+    State(task_service): State<Arc<TaskService>>,
+    Extension(caller): Extension<Caller>,
+    Query(vault): Query<TaskVaultParams>,
+    Json(payload): Json<CreateTaskRequest>,
+) -> ApiResponse<R> {
+    if payload.name.trim().is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "Name field is required".to_string(),
+        ));
+    }
+    handle_service_response!(
+        split_denial(
+            task_service
+                .create_task(caller.user_id, vault.workspace, payload, &caller.dates)
+                .await
+        )?
+        .map(|task| R::from_task(task, &caller.dates)),
+        "Created a new task {}",
+        |data: &R| data.task_id(),
+        "Unable to insert a new task into database"
+    )
+}
+
+/// Parses free text like "Pay rent every month on the 1st #home !high" into a task. With
+/// `?preview=true` nothing is created and only the parse is returned
+#[utoipa::path(
+    post,
+    path = "/tasks/quick",
+    tag = "tasks",
+    params(QuickAddParams),
+    request_body = QuickAddRequest,
+    responses(
+        (status = 200, description = "The created task and how the text was read",
+            body = QuickAddResponse<TaskResponse>),
+        (status = 400, description = "Empty text, no task name or an ambiguous date",
+            body = String),
+        (status = 403, description = "Role too low for the workspace", body = String),
+        (status = 404, description = "Unknown workspace", body = String),
+    )
+)]
+async fn quick_add_handler<R: TaskShape>(
+    State(task_service): State<Arc<TaskService>>,
+    Extension(caller): Extension<Caller>,
+    Query(params): Query<QuickAddParams>,
+    Json(payload): Json<QuickAddRequest>,
+) -> ApiResponse<QuickAddResponse<R>> {
+    info!("Quick adding '{}' (preview: {})", payload.text, params.preview);
+    if payload.text.trim().is_empty() {
+        return Err((StatusCode::BAD_REQUEST, "Text is required".to_string()));
+    }
+    handle_service_response!(
+        split_denial(
+            task_service
+                .quick_add(
+                    caller.user_id,
+                    params.workspace,
+                    &payload.text,
+                    params.preview,
+                    &caller.dates,
+                )
+                .await
+        )?
+        .map(|response| QuickAddResponse {
+            task: response.task.map(|task| R::from_task(task, &caller.dates)),
+            parsed: response.parsed,
+        }),
+        "Quick added '{}'",
+        |data: &QuickAddResponse<R>| data.parsed.name.clone(),
+        "Unable to quick add a task"
+    )
+}
+
+/// Drops the task into a board column between two of its tasks, changing its status when
+/// the column differs
+#[utoipa::path(
+    post,
+    path = "/tasks/{id}/move",
+    tag = "tasks",
+    params(("id" = i64, Path, description = "Task id"), TaskVaultParams),
+    request_body = MoveTaskRequest,
+    responses(
+        (status = 200, description = "The moved task with its new rank", body = TaskResponse),
+        (status = 400, description = "Neighbours not adjacent in the target column, or a \
+            status move the workflow does not allow", body = String),
+        (status = 403, description = "Role too low for the workspace", body = String),
+        (status = 404, description = "Unknown task or workspace", body = String),
+    )
+)]
+async fn move_task_handler<R: TaskShape>(
+    State(task_service): State<Arc<TaskService>>,
+    Extension(caller): Extension<Caller>,
+    Query(vault): Query<TaskVaultParams>,
+    Path(id): Path<i64>,
+    Json(payload): Json<MoveTaskRequest>,
+) -> ApiResponse<R> {
+    info!("Moving task {} with {:?}", id, payload);
+    if payload.previous_id == Some(id) || payload.next_id == Some(id) {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "A task cannot be its own neighbour".to_string(),
+        ));
+    }
+
+    let moved = task_service
+        .move_task(caller.user_id, vault.workspace, id, payload)
+        .await;
+    match split_denial(moved)? {
+        Ok(Some(task)) => Ok(Json(R::from_task(task, &caller.dates))),
+        Ok(None) => Err((StatusCode::NOT_FOUND, "Task not found".to_string())),
+        Err(e) => {
+            let error_message = format!("Failed to move task: {:?}", e);
+            Err((StatusCode::INTERNAL_SERVER_ERROR, error_message))
         }
-        handle_service_response!(
-            task_service.create_task(payload).await,
-            "Created a new task {}",
-            |data: &TaskResponse| data.id,
-            "Unable to insert a new task into database"
-        )
-    }
-
-    async fn bulk_create_tasks_handler(
-        State(task_service): State<Arc<TaskService>>,
-        Json(payload): Json<BulkCreateTaskRequest>,
-    ) -> ApiResponse<Vec<TaskResponse>> {
-        if payload.tasks.is_empty() {
+    }
+}
+
+/// Orders the listed tasks of one board column first; the column's other tasks follow
+#[utoipa::path(
+    post,
+    path = "/tasks/reorder",
+    tag = "tasks",
+    params(TaskVaultParams),
+    request_body = ReorderTasksRequest,
+    responses(
+        (status = 200, description = "The whole column in its new order",
+            body = Vec<TaskResponse>),
+        (status = 400, description = "No ids, repeated ids or tasks from different columns",
+            body = String),
+        (status = 403, description = "Role too low for the workspace", body = String),
+        (status = 404, description = "Unknown workspace", body = String),
+    )
+)]
+async fn reorder_tasks_handler<R: TaskShape>(
+    State(task_service): State<Arc<TaskService>>,
+    Extension(caller): Extension<Caller>,
+    Query(vault): Query<TaskVaultParams>,
+    Json(payload): Json<ReorderTasksRequest>,
+) -> ApiResponse<Vec<R>> {
+    info!("Reordering tasks {:?}", payload.task_ids);
+    if payload.task_ids.is_empty() {
+        return Err((StatusCode::BAD_REQUEST, "taskIds cannot be empty".to_string()));
+    }
+    let mut seen = HashSet::new();
+    if let Some(repeated) = payload.task_ids.iter().find(|id| !seen.insert(**id)) {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            format!("Task {} is listed more than once", repeated),
+        ));
+    }
+
+    handle_service_response!(
+        split_denial(
+            task_service
+                .reorder_tasks(caller.user_id, vault.workspace, &payload.task_ids)
+                .await
+        )?
+        .map(|tasks| R::from_vec(tasks, &caller.dates)),
+        "Reordered a column of {} tasks",
+        |data: &Vec<R>| data.len(),
+        "Failed to reorder tasks"
+    )
+}
+
+#[utoipa::path(
+    post,
+    path = "/tasks/bulk",
+    tag = "tasks",
+    params(TaskVaultParams),
+    request_body = BulkCreateTaskRequest,
+    responses(
+        (status = 200, description = "The created tasks", body = Vec<TaskResponse>),
+        (status = 400, description = "Assignee not in the workspace", body = String),
+        (status = 403, description = "Role too low for the workspace", body = String),
+        (status = 404, description = "Unknown workspace", body = String),
+    )
+)]
+async fn bulk_create_tasks_handler<R: TaskShape>(
+    State(task_service): State<Arc<TaskService>>,
+    Extension(caller): Extension<Caller>,
+    Query(vault): Query<TaskVaultParams>,
+    Json(payload): Json<BulkCreateTaskRequest>,
+) -> ApiResponse<Vec<R>> {
+    if payload.tasks.is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "Tasks array cannot be empty".to_string(),
+        ));
+    }
+
+    // Validate that all tasks have non-empty names
+    for task in &payload.tasks {
+        if task.name.trim().is_empty() {
             return Err((
                 StatusCode::BAD_REQUEST,
-                "Tasks array cannot be empty".to_string(),
+                "All tasks must have non-empty names".to_string(),
             ));
         }
+    }
 
-        // Validate that all tasks have non-empty names
-        for task in &payload.tasks {
-            if task.name.trim().is_empty() {
-                return Err((
-                    StatusCode::BAD_REQUEST,
-                    "All tasks must have non-empty names".to_string(),
-                ));
-            }
+    handle_service_response!(
+        split_denial(
+            task_service
+                .bulk_create_tasks(caller.user_id, vault.workspace, payload, &caller.dates)
+                .await
+        )?
+        .map(|tasks| R::from_vec(tasks, &caller.dates)),
+        "Bulk created {} tasks",
+        |data: &Vec<R>| data.len(),
+        "Unable to bulk insert tasks into database"
+    )
+}
+
+/// Tasks in the caller's vault, or in `?workspace=<id>`
+#[utoipa::path(
+    get,
+    path = "/tasks",
+    tag = "tasks",
+    params(TaskVaultParams),
+    responses(
+        (status = 200, description = "All tasks", body = Vec<TaskResponse>),
+        (status = 403, description = "Role too low for the workspace", body = String),
+        (status = 404, description = "Unknown workspace", body = String),
+    )
+)]
+async fn get_tasks_handler<R: TaskShape>(
+    State(task_service): State<Arc<TaskService>>,
+    Extension(caller): Extension<Caller>,
+    Query(vault): Query<TaskVaultParams>,
+) -> ApiResponse<Vec<R>> {
+    info!("Fetching tasks from database");
+    handle_service_response!(
+        split_denial(task_service.get_all_tasks(caller.user_id, vault.workspace).await)?
+            .map(|tasks| R::from_vec(tasks, &caller.dates)),
+        "Fetched {} tasks",
+        |data: &Vec<R>| data.len(),
+        "Failed to get tasks"
+    )
+}
+
+#[utoipa::path(
+    get,
+    path = "/tasks/{id}",
+    tag = "tasks",
+    params(("id" = i64, Path, description = "Task id"), TaskVaultParams),
+    responses(
+        (status = 200, description = "The task", body = TaskResponse),
+        (status = 403, description = "Role too low for the workspace", body = String),
+        (status = 404, description = "Unknown workspace", body = String),
+        (status = 500, description = "Task not found", body = String),
+    )
+)]
+async fn get_task_by_id_handler<R: TaskShape>(
+    State(task_service): State<Arc<TaskService>>,
+    Extension(caller): Extension<Caller>,
+    Query(vault): Query<TaskVaultParams>,
+    Path(id): Path<i64>,
+) -> ApiResponse<R> {
+    info!("Fetching task with id {} from database", id);
+    handle_service_response!(
+        split_denial(task_service.get_by_id(caller.user_id, vault.workspace, id).await)?
+            .map(|task| R::from_task(task, &caller.dates)),
+        "Found task with id {}",
+        |data: &R| data.task_id(),
+        "Failed to get current task"
+    )
+}
+
+/// Filters tasks; every parameter is optional and they are combined with AND
+#[utoipa::path(
+    get,
+    path = "/tasks/search",
+    tag = "tasks",
+    params(TaskSearchParams),
+    responses(
+        (status = 200, description = "Matching tasks", body = Vec<TaskResponse>),
+        (status = 403, description = "Role too low for the workspace", body = String),
+        (status = 404, description = "Unknown workspace", body = String),
+    )
+)]
+async fn search_tasks_by_text_handler<R: TaskShape>(
+    State(task_service): State<Arc<TaskService>>,
+    Extension(caller): Extension<Caller>,
+    Query(params): Query<TaskSearchParams>,
+) -> ApiResponse<Vec<R>> {
+    info!("Searching tasks with params {:?}", params);
+    handle_service_response!(
+        split_denial(
+            task_service
+                .search_tasks(caller.user_id, params, &caller.dates)
+                .await
+        )?
+        .map(|tasks| R::from_vec(tasks, &caller.dates)),
+        "Found tasks with params {:?}",
+        |data: &Vec<R>| data.len(),
+        "Failed to search tasks"
+    )
+}
+
+/// Soft deletes the task
+#[utoipa::path(
+    delete,
+    path = "/tasks/{id}",
+    tag = "tasks",
+    params(("id" = i64, Path, description = "Task id"), TaskVaultParams),
+    responses(
+        (status = 200, description = "Task deleted successfully", body = String),
+        (status = 403, description = "Role too low for the workspace", body = String),
+        (status = 404, description = "Unknown task or workspace", body = String),
+    )
+)]
+async fn delete_task_handler(
+    State(task_service): State<Arc<TaskService>>,
+    Extension(caller): Extension<Caller>,
+    Query(vault): Query<TaskVaultParams>,
+    Path(id): Path<i64>,
+) -> ApiTextResponse {
+    info!("Soft deleting task with id {}", id);
+    let deleted = task_service
+        .soft_delete_task(caller.user_id, vault.workspace, id)
+        .await;
+    match split_denial(deleted)? {
+        Ok(true) => Ok("Task deleted successfully".to_string()),
+        Ok(false) => Err((StatusCode::NOT_FOUND, "Task not found".to_string())),
+        Err(e) => {
+            let error_message = format!("Failed to delete task: {:?}", e);
+            Err((StatusCode::INTERNAL_SERVER_ERROR, error_message))
         }
+    }
+}
 
-        handle_service_response!(
-            task_service.bulk_create_tasks(payload).await,
-            "Bulk created {} tasks",
-            |data: &Vec<TaskResponse>| data.len(),
-            "Unable to bulk insert tasks into database"
-        )
-    }
-
-    async fn get_tasks_handler(
-        State(task_service): State<Arc<TaskService>>,
-    ) -> ApiResponse<Vec<TaskResponse>> {
-        info!("Fetching tasks from database");
-        handle_service_response!(
-            task_service.get_all_tasks().await,
-            "Fetched {} tasks",
-            |data: &Vec<TaskResponse>| data.len(),
-            "Failed to get tasks"
-        )
-    }
-
-    async fn get_task_by_id_handler(
-        State(task_service): State<Arc<TaskService>>,
-        Path(id): Path<i64>,
-    ) -> ApiResponse<TaskResponse> {
-        info!("Fetching task with id {} from database", id);
-        handle_service_response!(
-            task_service.get_by_id(id).await,
-            "Found task with id {}",
-            |data: &TaskResponse| data.id,
-            "Failed to get current task"
-        )
-    }
-
-    // ?search=Update Document
-    async fn search_tasks_by_text_handler(
-        State(task_service): State<Arc<TaskService>>,
-        Query(params): Query<TaskSearchParams>,
-    ) -> ApiResponse<Vec<TaskResponse>> {
-        info!("Searching tasks with params {:?}", params);
-        handle_service_response!(
-            task_service.search_tasks(params).await,
-            "Found tasks with params {:?}",
-            |data: &Vec<TaskResponse>| data.len(),
-            "Failed to search tasks"
-        )
-    }
-
-    async fn delete_task_handler(
-        State(task_service): State<Arc<TaskService>>,
-        Path(id): Path<i64>,
-    ) -> ApiTextResponse {
-        info!("Soft deleting task with id {}", id);
-        match task_service.soft_delete_task(id).await {
-            Ok(true) => Ok("Task deleted successfully".to_string()),
-            Ok(false) => Err((StatusCode::NOT_FOUND, "Task not found".to_string())),
-            Err(e) => {
-                let error_message = format!("Failed to delete task: {:?}", e);
-                Err((StatusCode::INTERNAL_SERVER_ERROR, error_message))
-            }
+/// Updates the given fields; at least one is required
+#[utoipa::path(
+    put,
+    path = "/tasks/{id}",
+    tag = "tasks",
+    params(("id" = i64, Path, description = "Task id"), TaskVaultParams),
+    request_body = UpdateTaskRequest,
+    responses(
+        (status = 200, description = "The updated task", body = TaskResponse),
+        (status = 400, description = "No field given, empty name or a status move the \
+            workflow does not allow", body = String),
+        (status = 403, description = "Role too low for the workspace", body = String),
+        (status = 404, description = "Unknown task or workspace", body = String),
+    )
+)]
+async fn update_task_handler<R: TaskShape>(
+    State(task_service): State<Arc<TaskService>>,
+    Extension(caller): Extension<Caller>,
+    Query(vault): Query<TaskVaultParams>,
+    Path(id): Path<i64>,
+    Json(payload): Json<UpdateTaskRequest>,
+) -> ApiResponse<R> {
+    info!("Updating task with id {}", id);
+
+    // Validate that at least one field is provided
+    if payload.name.is_none()
+        && payload.status.is_none()
+        && payload.due_date.is_none()
+        && payload.priority.is_none()
+        && payload.tags.is_none()
+        && payload.recurrence.is_none()
+        && payload.estimate_minutes.is_none()
+        && payload.notes.is_none()
+        && payload.assignee_id.is_none()
+    {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "At least one field must be provided for update".to_string(),
+        ));
+    }
+
+    if payload.name.as_ref().is_some_and(|name| name.trim().is_empty()) {
+        return Err((StatusCode::BAD_REQUEST, "Name cannot be empty".to_string()));
+    }
+
+    let updated = task_service
+        .update_task(caller.user_id, vault.workspace, id, payload, &caller.dates)
+        .await;
+    match split_denial(updated)? {
+        Ok(Some(task)) => Ok(Json(R::from_task(task, &caller.dates))),
+        Ok(None) => Err((StatusCode::NOT_FOUND, "Task not found".to_string())),
+        Err(e) => {
+            let error_message = format!("Failed to update task: {:?}", e);
+            Err((StatusCode::INTERNAL_SERVER_ERROR, error_message))
         }
     }
+}
+
+/// Soft deletes every task with the given status
+#[utoipa::path(
+    delete,
+    path = "/tasks/status/{status}",
+    tag = "tasks",
+    params(("status" = ETaskStatus, Path, description = "Status to delete"), TaskVaultParams),
+    responses(
+        (status = 200, description = "Deleted N tasks", body = String),
+        (status = 400, description = "Invalid status", body = String),
+        (status = 403, description = "Role too low for the workspace", body = String),
+        (status = 404, description = "Unknown workspace", body = String),
+    )
+)]
+async fn bulk_delete_by_status_handler(
+    State(task_service): State<Arc<TaskService>>,
+    Extension(caller): Extension<Caller>,
+    Query(vault): Query<TaskVaultParams>,
+    Path(status_str): Path<String>,
+) -> ApiTextResponse {
+    info!("Bulk deleting tasks with status {}", status_str);
+
+    let status = status_str
+        .parse::<ETaskStatus>()
+        .map_err(|message| (StatusCode::BAD_REQUEST, message))?;
+
+    let deleted = task_service
+        .bulk_delete_by_status(caller.user_id, vault.workspace, status)
+        .await;
+    match split_denial(deleted)? {
+        Ok(count) => Ok(format!("Deleted {} tasks", count)),
+        Err(e) => {
+            let error_message = format!("Failed to bulk delete tasks: {:?}", e);
+            Err((StatusCode::INTERNAL_SERVER_ERROR, error_message))
+        }
+    }
+}
+
+/// Applies the update fields to every task matching the filter fields
+#[utoipa::path(
+    put,
+    path = "/tasks/search",
+    tag = "tasks",
+    params(TaskVaultParams),
+    request_body = SearchAndUpdateRequest,
+    responses(
+        (status = 200, description = "The updated tasks", body = Vec<TaskResponse>),
+        (status = 400, description = "No update field given", body = String),
+        (status = 403, description = "Role too low for the workspace", body = String),
+        (status = 404, description = "Unknown workspace", body = String),
+    )
+)]
+async fn search_and_update_handler<R: TaskShape>(
+    State(task_service): State<Arc<TaskService>>,
+    Extension(caller): Extension<Caller>,
+    Query(vault): Query<TaskVaultParams>,
+    Json(payload): Json<SearchAndUpdateRequest>,
+) -> ApiResponse<Vec<R>> {
+    info!("Search and update with payload {:?}", payload);
 
-    async fn update_task_handler(
-        State(task_service): State<Arc<TaskService>>,
-        Path(id): Path<i64>,
-        Json(payload): Json<UpdateTaskRequest>,
-    ) -> ApiResponse<TaskResponse> {
-        info!("Updating task with id {} with payload {:?}", id, payload);
+    // Validate that at least one update field is provided
+    if payload.status.is_none() && payload.due_date.is_none() && payload.priority.is_none() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "At least one update field must be provided".to_string(),
+        ));
+    }
+
+    handle_service_response!(
+        split_denial(
+            task_service
+                .search_and_update_tasks(
+                    caller.user_id,
+                    vault.workspace,
+                    payload,
+                    &caller.dates,
+                )
+                .await
+        )?
+        .map(|tasks| R::from_vec(tasks, &caller.dates)),
+        "Updated {} tasks",
+        |data: &Vec<R>| data.len(),
+        "Failed to search and update tasks"
+    )
+}
+
+/// Starts the caller's timer on the task. A timer they had running on another task is
+/// stopped first; one already running on this task is left as it is.
+#[utoipa::path(
+    post,
+    path = "/tasks/{id}/timer/start",
+    tag = "tasks",
+    params(("id" = i64, Path, description = "Task id"), TaskVaultParams),
+    request_body(content = Option<TimerRequest>, description = "Optional note"),
+    responses(
+        (status = 200, description = "The running timer and the one it stopped",
+            body = TimerResponse),
+        (status = 403, description = "Role too low for the workspace", body = String),
+        (status = 404, description = "Unknown task or workspace", body = String),
+    )
+)]
+async fn start_timer_handler(
+    State(time_entry_service): State<Arc<TimeEntryService>>,
+    Extension(caller): Extension<Caller>,
+    Query(vault): Query<TaskVaultParams>,
+    Path(id): Path<i64>,
+    payload: Option<Json<TimerRequest>>,
+) -> ApiResponse<TimerResponse> {
+    info!("Starting timer on task {}", id);
+    let Json(payload) = payload.unwrap_or_default();
+    let started = time_entry_service
+        .start_timer(caller.user_id, vault.workspace, id, payload.note)
+        .await;
+    match split_denial(started)? {
+        Ok(Some(timer)) => Ok(Json(timer)),
+        Ok(None) => Err((StatusCode::NOT_FOUND, "Task not found".to_string())),
+        Err(e) => {
+            let error_message = format!("Failed to start timer: {:?}", e);
+            Err((StatusCode::INTERNAL_SERVER_ERROR, error_message))
+        }
+    }
+}
 
-        // Validate that at least one field is provided
-        if payload.status.is_none() && payload.due_date.is_none() && payload.priority.is_none() {
+/// Stops the caller's timer on the task and adds its time to the task
+#[utoipa::path(
+    post,
+    path = "/tasks/{id}/timer/stop",
+    tag = "tasks",
+    params(("id" = i64, Path, description = "Task id"), TaskVaultParams),
+    request_body(content = Option<TimerRequest>, description = "Optional note"),
+    responses(
+        (status = 200, description = "The stopped timer", body = TimerResponse),
+        (status = 403, description = "Role too low for the workspace", body = String),
+        (status = 404, description = "No timer running on the task, or unknown workspace",
+            body = String),
+    )
+)]
+async fn stop_timer_handler(
+    State(time_entry_service): State<Arc<TimeEntryService>>,
+    Extension(caller): Extension<Caller>,
+    Query(vault): Query<TaskVaultParams>,
+    Path(id): Path<i64>,
+    payload: Option<Json<TimerRequest>>,
+) -> ApiResponse<TimerResponse> {
+    info!("Stopping timer on task {}", id);
+    let Json(payload) = payload.unwrap_or_default();
+    let stopped = time_entry_service
+        .stop_timer(caller.user_id, vault.workspace, id, payload.note)
+        .await;
+    match split_denial(stopped)? {
+        Ok(Some(timer)) => Ok(Json(timer)),
+        Ok(None) => Err((
+            StatusCode::NOT_FOUND,
+            format!("No timer is running on task {}", id),
+        )),
+        Err(e) => {
+            let error_message = format!("Failed to stop timer: {:?}", e);
+            Err((StatusCode::INTERNAL_SERVER_ERROR, error_message))
+        }
+    }
+}
+
+/// Every member's time entries on the task, newest first, with its estimate and the time
+/// tracked against it
+#[utoipa::path(
+    get,
+    path = "/tasks/{id}/time",
+    tag = "tasks",
+    params(("id" = i64, Path, description = "Task id"), TaskVaultParams),
+    responses(
+        (status = 200, description = "The task's time", body = TaskTimeResponse),
+        (status = 403, description = "Role too low for the workspace", body = String),
+        (status = 404, description = "Unknown task or workspace", body = String),
+    )
+)]
+async fn get_task_time_handler(
+    State(time_entry_service): State<Arc<TimeEntryService>>,
+    Extension(caller): Extension<Caller>,
+    Query(vault): Query<TaskVaultParams>,
+    Path(id): Path<i64>,
+) -> ApiResponse<TaskTimeResponse> {
+    info!("Fetching time entries of task {}", id);
+    let time = time_entry_service
+        .get_task_time(caller.user_id, vault.workspace, id)
+        .await;
+    match split_denial(time)? {
+        Ok(Some(time)) => Ok(Json(time)),
+        Ok(None) => Err((StatusCode::NOT_FOUND, "Task not found".to_string())),
+        Err(e) => {
+            let error_message = format!("Failed to get time entries: {:?}", e);
+            Err((StatusCode::INTERNAL_SERVER_ERROR, error_message))
+        }
+    }
+}
+
+/// Records time spent on the task without a timer
+#[utoipa::path(
+    post,
+    path = "/tasks/{id}/time",
+    tag = "tasks",
+    params(("id" = i64, Path, description = "Task id"), TaskVaultParams),
+    request_body = LogTimeRequest,
+    responses(
+        (status = 200, description = "The new time entry", body = TimeEntryResponse),
+        (status = 400, description = "Neither or both of endedAt and durationMinutes, or \
+            an entry that ends before it starts", body = String),
+        (status = 403, description = "Role too low for the workspace", body = String),
+        (status = 404, description = "Unknown task or workspace", body = String),
+    )
+)]
+async fn log_time_handler(
+    State(time_entry_service): State<Arc<TimeEntryService>>,
+    Extension(caller): Extension<Caller>,
+    Query(vault): Query<TaskVaultParams>,
+    Path(id): Path<i64>,
+    Json(payload): Json<LogTimeRequest>,
+) -> ApiResponse<TimeEntryResponse> {
+    info!("Logging time on task {} with {:?}", id, payload);
+    match (&payload.ended_at, payload.duration_minutes) {
+        (Some(_), Some(_)) | (None, None) => {
             return Err((
                 StatusCode::BAD_REQUEST,
-                "At least one field must be provided for update".to_string(),
+                "Exactly one of endedAt and durationMinutes is required".to_string(),
             ));
         }
+        (None, Some(0)) => {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                "durationMinutes must be at least 1".to_string(),
+            ));
+        }
+        _ => {}
+    }
 
-        match task_service.update_task(id, payload).await {
-            Ok(Some(task)) => Ok(Json(task)),
-            Ok(None) => Err((StatusCode::NOT_FOUND, "Task not found".to_string())),
-            Err(e) => {
-                let error_message = format!("Failed to update task: {:?}", e);
-                Err((StatusCode::INTERNAL_SERVER_ERROR, error_message))
-            }
+    let logged = time_entry_service
+        .log_time(caller.user_id, vault.workspace, id, payload, &caller.dates)
+        .await;
+    match split_denial(logged)? {
+        Ok(Some(entry)) => Ok(Json(entry)),
+        Ok(None) => Err((StatusCode::NOT_FOUND, "Task not found".to_string())),
+        Err(e) => {
+            let error_message = format!("Failed to log time: {:?}", e);
+            Err((StatusCode::INTERNAL_SERVER_ERROR, error_message))
         }
     }
+}
 
-    async fn bulk_delete_by_status_handler(
-        State(task_service): State<Arc<TaskService>>,
-        Path(status_str): Path<String>,
-    ) -> ApiTextResponse {
-        info!("Bulk deleting tasks with status {}", status_str);
+/// Deletes one of the caller's time entries and takes its time off the task
+#[utoipa::path(
+    delete,
+    path = "/tasks/{id}/time/{entry_id}",
+    tag = "tasks",
+    params(
+        ("id" = i64, Path, description = "Task id"),
+        ("entry_id" = i64, Path, description = "Time entry id"),
+        TaskVaultParams,
+    ),
+    responses(
+        (status = 200, description = "Time entry deleted", body = String),
+        (status = 403, description = "Role too low for the workspace", body = String),
+        (status = 404, description = "Unknown time entry or workspace", body = String),
+    )
+)]
+async fn delete_time_entry_handler(
+    State(time_entry_service): State<Arc<TimeEntryService>>,
+    Extension(caller): Extension<Caller>,
+    Query(vault): Query<TaskVaultParams>,
+    Path((id, entry_id)): Path<(i64, i64)>,
+) -> ApiTextResponse {
+    info!("Deleting time entry {} of task {}", entry_id, id);
+    let deleted = time_entry_service
+        .delete_time_entry(caller.user_id, vault.workspace, id, entry_id)
+        .await;
+    match split_denial(deleted)? {
+        Ok(true) => Ok("Time entry deleted successfully".to_string()),
+        Ok(false) => Err((StatusCode::NOT_FOUND, "Time entry not found".to_string())),
+        Err(e) => {
+            let error_message = format!("Failed to delete time entry: {:?}", e);
+            Err((StatusCode::INTERNAL_SERVER_ERROR, error_message))
+        }
+    }
+}
 
-        // Parse the status string
-        let status = match status_str.as_str() {
-            "NotStarted" => ETaskStatus::NotStarted,
-            "Pending" => ETaskStatus::Pending,
-            "InProgress" => ETaskStatus::InProgress,
-            "Completed" => ETaskStatus::Completed,
-            _ => return Err((StatusCode::BAD_REQUEST, "Invalid status".to_string())),
-        };
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::router::tests::{offline_database, send};
+    use bson::DateTime as BsonDateTime;
+    use chrono::{TimeZone, Utc};
+    use mindvault_shared::models::tasks_model::ETaskPriority;
 
-        match task_service.bulk_delete_by_status(status).await {
-            Ok(count) => Ok(format!("Deleted {} tasks", count)),
-            Err(e) => {
-                let error_message = format!("Failed to bulk delete tasks: {:?}", e);
-                Err((StatusCode::INTERNAL_SERVER_ERROR, error_message))
+    /// A concrete path for a route pattern of `TASK_ROUTES`
+    fn concrete(path: &str) -> String {
+        path.replace("{:id}", "7")
+            .replace("{:entry_id}", "3")
+            .replace("{:status}", "Completed")
+    }
+
+    async fn check_routes<R: TaskShape>() {
+        let database = offline_database().await;
+        let change_feed = ChangeFeed::start(database.clone()).await;
+        let routes = TaskRouter::new(database, change_feed).get_routes::<R>();
+        for (path, methods) in TASK_ROUTES {
+            for method in methods {
+                let response = send(routes.clone(), method.clone(), &concrete(path)).await;
+                assert!(
+                    ![StatusCode::NOT_FOUND, StatusCode::METHOD_NOT_ALLOWED]
+                        .contains(&response.status()),
+                    "{} {} is not mounted",
+                    method,
+                    path
+                );
             }
         }
+        let response = send(routes, Method::PATCH, "/7").await;
+        assert_eq!(response.status(), StatusCode::METHOD_NOT_ALLOWED);
     }
 
-    async fn search_and_update_handler(
-        State(task_service): State<Arc<TaskService>>,
-        Json(payload): Json<SearchAndUpdateRequest>,
-    ) -> ApiResponse<Vec<TaskResponse>> {
-        info!("Search and update with payload {:?}", payload);
+    #[tokio::test]
+    async fn v1_mounts_every_route_in_the_table() {
+        check_routes::<TaskResponse>().await;
+    }
 
-        // Validate that at least one update field is provided
-        if payload.status.is_none() && payload.due_date.is_none() && payload.priority.is_none() {
-            return Err((
-                StatusCode::BAD_REQUEST,
-                "At least one update field must be provided".to_string(),
-            ));
-        }
+    #[tokio::test]
+    async fn v2_mounts_every_route_in_the_table() {
+        check_routes::<TaskResponseV2>().await;
+    }
 
-        handle_service_response!(
-            task_service.search_and_update_tasks(payload).await,
-            "Updated {} tasks",
-            |data: &Vec<TaskResponse>| data.len(),
-            "Failed to search and update tasks"
-        )
+    #[test]
+    fn v2_tasks_carry_the_caller_s_offset() {
+        let at =
+            |hour| BsonDateTime::from(Utc.with_ymd_and_hms(2026, 10, 19, hour, 30, 0).unwrap());
+        let task = TaskResponse {
+            id: 7,
+            owner_id: 1,
+            workspace_id: None,
+            assignee_id: None,
+            reportee_ids: Vec::new(),
+            name: "Call the landlord".to_string(),
+            priority: ETaskPriority::Normal,
+            status: ETaskStatus::NotStarted,
+            workflow_status: None,
+            rank: None,
+            due_date: Some(at(16)),
+            due_has_time: true,
+            created_at: at(8),
+            updated_at: None,
+            completed_at: None,
+            tags: Vec::new(),
+            recurrence: None,
+            estimate_minutes: None,
+            tracked_seconds: 0,
+            notes: None,
+            external_id: None,
+        };
+        let dates = DatePreferences {
+            time_zone: "Europe/Berlin".to_string(),
+            date_order: None,
+        };
+        let payload = serde_json::to_value(TaskResponseV2::from_task(task, &dates)).unwrap();
+        assert_eq!(payload["dueDate"], "2026-10-19T18:30:00+02:00");
+        assert_eq!(payload["createdAt"], "2026-10-19T10:30:00+02:00");
     }
 }
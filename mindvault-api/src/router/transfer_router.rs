@@ -0,0 +1,131 @@
+use crate::auth::Caller;
+use crate::events::ChangeFeed;
+use crate::services::transfer_service::TransferService;
+use axum::body::Body;
+use axum::extract::{Query, State};
+use axum::http::{header, HeaderMap, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Extension, Json, Router};
+use mindvault_core::models::AppDatabase;
+use mindvault_shared::dtos::transfer_dtos::{ExportTasksParams, ImportTasksParams};
+use mindvault_shared::models::transfer_model::{ETransferFormat, ImportReport};
+use std::sync::Arc;
+use tracing::info;
+
+pub(crate) struct TransferRouter {
+    transfer_service: Arc<TransferService>,
+}
+
+impl TransferRouter {
+    pub(crate) fn new(app_database: AppDatabase, change_feed: Arc<ChangeFeed>) -> Self {
+        let transfer_service = Arc::new(TransferService::new(app_database, change_feed));
+        Self { transfer_service }
+    }
+
+    pub(crate) fn get_routes(&self) -> Router {
+        Router::new()
+            .route("/export/tasks", get(TransferRouter::export_tasks_handler))
+            .route("/import/tasks", post(TransferRouter::import_tasks_handler))
+            .route("/import/ics", post(TransferRouter::import_ics_handler))
+            .with_state(self.transfer_service.clone())
+    }
+
+    // ?format=csv&status=InProgress
+    async fn export_tasks_handler(
+        State(transfer_service): State<Arc<TransferService>>,
+        Extension(caller): Extension<Caller>,
+        Query(mut params): Query<ExportTasksParams>,
+    ) -> Result<Response, (StatusCode, String)> {
+        let format = params.format.unwrap_or(ETransferFormat::Json);
+        info!("Exporting tasks as {:?} with params {:?}", format, params);
+        if let Some(due_date) = params.due_date.take() {
+            let due_date = due_date
+                .resolve(&caller.dates)
+                .map_err(|message| (StatusCode::BAD_REQUEST, message))?;
+            params.due_date = Some(due_date);
+        }
+        let stream = transfer_service
+            .export_tasks(caller.user_id, format, &params, &caller.dates)
+            .await
+            .map_err(|e| {
+                let error_message = format!("Failed to export tasks: {:?}", e);
+                (StatusCode::INTERNAL_SERVER_ERROR, error_message)
+            })?;
+        let disposition = format!("attachment; filename=\"tasks.{}\"", format.extension());
+        Ok((
+            [
+                (header::CONTENT_TYPE, format.content_type().to_string()),
+                (header::CONTENT_DISPOSITION, disposition),
+            ],
+            Body::from_stream(stream),
+        )
+            .into_response())
+    }
+
+    // ?format=csv&dry_run=true&name_column=Title&due_date_column=Deadline
+    async fn import_tasks_handler(
+        State(transfer_service): State<Arc<TransferService>>,
+        Extension(caller): Extension<Caller>,
+        Query(params): Query<ImportTasksParams>,
+        headers: HeaderMap,
+        body: String,
+    ) -> Result<Json<ImportReport>, (StatusCode, String)> {
+        let format = params
+            .format
+            .or_else(|| {
+                headers
+                    .get(header::CONTENT_TYPE)
+                    .and_then(|value| value.to_str().ok())
+                    .and_then(ETransferFormat::from_content_type)
+            })
+            .ok_or_else(|| {
+                (
+                    StatusCode::BAD_REQUEST,
+                    "Import format is required; pass format=csv|json|md|ics|todotxt|taskwarrior".to_string(),
+                )
+            })?;
+        TransferRouter::import(&transfer_service, &caller, format, &body, &params).await
+    }
+
+    /// Ingests the VTODOs of an iCalendar file; takes the same `dry_run` flag as `/import/tasks`
+    async fn import_ics_handler(
+        State(transfer_service): State<Arc<TransferService>>,
+        Extension(caller): Extension<Caller>,
+        Query(params): Query<ImportTasksParams>,
+        body: String,
+    ) -> Result<Json<ImportReport>, (StatusCode, String)> {
+        TransferRouter::import(
+            &transfer_service,
+            &caller,
+            ETransferFormat::Ics,
+            &body,
+            &params,
+        )
+        .await
+    }
+
+    async fn import(
+        transfer_service: &TransferService,
+        caller: &Caller,
+        format: ETransferFormat,
+        body: &str,
+        params: &ImportTasksParams,
+    ) -> Result<Json<ImportReport>, (StatusCode, String)> {
+        info!(
+            "Importing tasks as {:?} (dry run: {})",
+            format, params.dry_run
+        );
+        let imported = transfer_service
+            .import_tasks(caller.user_id, format, body, params, &caller.dates)
+            .await;
+        match imported {
+            Ok(Ok(report)) => Ok(Json(report)),
+            Ok(Err(message)) => Err((StatusCode::BAD_REQUEST, message)),
+            Err(e) => {
+                let error_message = format!("Failed to import tasks: {:?}", e);
+                Err((StatusCode::INTERNAL_SERVER_ERROR, error_message))
+            }
+        }
+    }
+}
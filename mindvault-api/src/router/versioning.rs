@@ -0,0 +1,68 @@
+use axum::extract::Request;
+use axum::http::header::LINK;
+use axum::http::HeaderValue;
+use axum::middleware::Next;
+use axum::response::Response;
+
+/// Prefix the current API is served under
+pub(crate) const CURRENT_VERSION_PREFIX: &str = "/v1";
+
+/// When the unversioned paths were deprecated, as an RFC 9745 `@<unix seconds>` date (2026-10-19)
+const UNVERSIONED_DEPRECATED_AT: &str = "@1792368000";
+
+/// When the unversioned paths stop answering, as an RFC 8594 HTTP-date
+const UNVERSIONED_SUNSET: &str = "Sat, 01 May 2027 00:00:00 GMT";
+
+/// Marks responses from the unversioned compatibility paths as deprecated and points at the
+/// same path under `/v1`
+pub(crate) async fn deprecate_unversioned(request: Request, next: Next) -> Response {
+    let successor = format!(
+        "<{}{}>; rel=\"successor-version\"",
+        CURRENT_VERSION_PREFIX,
+        request.uri().path()
+    );
+    let mut response = next.run(request).await;
+    let headers = response.headers_mut();
+    headers.insert("deprecation", HeaderValue::from_static(UNVERSIONED_DEPRECATED_AT));
+    headers.insert("sunset", HeaderValue::from_static(UNVERSIONED_SUNSET));
+    if let Ok(link) = HeaderValue::from_str(&successor) {
+        headers.append(LINK, link);
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::router::tests::{offline_router, send};
+    use axum::http::{Method, StatusCode};
+
+    #[tokio::test]
+    async fn unversioned_paths_point_at_their_v1_successor() {
+        let response = send(offline_router().await, Method::GET, "/tasks/search?q=rent").await;
+        // Unauthenticated, but the compatibility headers go on every answer
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        let headers = response.headers();
+        assert_eq!(headers["deprecation"], UNVERSIONED_DEPRECATED_AT);
+        assert_eq!(headers["sunset"], "Sat, 01 May 2027 00:00:00 GMT");
+        assert_eq!(headers[LINK], "</v1/tasks/search>; rel=\"successor-version\"");
+    }
+
+    #[tokio::test]
+    async fn versioned_paths_are_not_deprecated() {
+        let router = offline_router().await;
+        for uri in ["/v1/tasks", "/v2/tasks", "/v1/reviews"] {
+            let response = send(router.clone(), Method::GET, uri).await;
+            assert_eq!(response.status(), StatusCode::UNAUTHORIZED, "{}", uri);
+            assert!(!response.headers().contains_key("deprecation"), "{}", uri);
+            assert!(!response.headers().contains_key("sunset"), "{}", uri);
+        }
+    }
+
+    #[test]
+    fn the_deprecation_date_is_the_day_v1_arrived() {
+        let seconds: i64 = UNVERSIONED_DEPRECATED_AT[1..].parse().unwrap();
+        let deprecated_at = chrono::DateTime::from_timestamp(seconds, 0).unwrap();
+        assert_eq!(deprecated_at.date_naive().to_string(), "2026-10-19");
+    }
+}
@@ -0,0 +1,232 @@
+use crate::auth::Caller;
+use crate::handle_service_response;
+use crate::models::{ApiResponse, ApiTextResponse};
+use crate::services::webhook_service::WebhookService;
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::routing::{get, post};
+use axum::{Extension, Json, Router};
+use mindvault_core::models::AppDatabase;
+use mindvault_shared::dtos::webhook_dtos::{
+    CreateWebhookRequest, UpdateWebhookRequest, WebhookDeliverySearchParams,
+};
+use mindvault_shared::models::webhooks_model::{
+    WebhookDeliveryResponse, WebhookSubscriptionResponse,
+};
+use std::sync::Arc;
+use tracing::info;
+
+pub(crate) struct WebhookRouter {
+    webhook_service: Arc<WebhookService>,
+}
+
+impl WebhookRouter {
+    pub(crate) fn new(app_database: AppDatabase) -> Self {
+        let webhook_service = Arc::new(WebhookService::new(app_database));
+        Self { webhook_service }
+    }
+
+    pub(crate) fn get_routes(&self) -> Router {
+        Router::new()
+            .route(
+                "/",
+                get(WebhookRouter::get_webhooks_handler).post(WebhookRouter::create_webhook_handler),
+            )
+            .route(
+                "/deliveries",
+                get(WebhookRouter::search_deliveries_handler),
+            )
+            .route(
+                "/deliveries/{:id}",
+                get(WebhookRouter::get_delivery_by_id_handler),
+            )
+            .route(
+                "/deliveries/{:id}/replay",
+                post(WebhookRouter::replay_delivery_handler),
+            )
+            .route(
+                "/{:id}",
+                get(WebhookRouter::get_webhook_by_id_handler)
+                    .put(WebhookRouter::update_webhook_handler)
+                    .delete(WebhookRouter::delete_webhook_handler),
+            )
+            .route("/{:id}/replay", post(WebhookRouter::replay_dead_letters_handler))
+            .with_state(self.webhook_service.clone())
+    }
+
+    fn validate_url(url: &str) -> Result<(), (StatusCode, String)> {
+        if url.starts_with("http://") || url.starts_with("https://") {
+            Ok(())
+        } else {
+            Err((
+                StatusCode::BAD_REQUEST,
+                "Webhook url must start with http:// or https://".to_string(),
+            ))
+        }
+    }
+
+    async fn get_webhooks_handler(
+        State(webhook_service): State<Arc<WebhookService>>,
+        Extension(caller): Extension<Caller>,
+    ) -> ApiResponse<Vec<WebhookSubscriptionResponse>> {
+        info!("Fetching all webhooks");
+        handle_service_response!(
+            webhook_service.get_subscriptions(caller.user_id).await,
+            "Found {} webhooks",
+            |data: &Vec<WebhookSubscriptionResponse>| data.len(),
+            "Failed to get webhooks"
+        )
+    }
+
+    async fn create_webhook_handler(
+        State(webhook_service): State<Arc<WebhookService>>,
+        Extension(caller): Extension<Caller>,
+        Json(payload): Json<CreateWebhookRequest>,
+    ) -> ApiResponse<WebhookSubscriptionResponse> {
+        WebhookRouter::validate_url(&payload.url)?;
+        handle_service_response!(
+            webhook_service.create_subscription(caller.user_id, payload).await,
+            "Created a new webhook {}",
+            |data: &WebhookSubscriptionResponse| data.id,
+            "Unable to insert a new webhook into database"
+        )
+    }
+
+    async fn get_webhook_by_id_handler(
+        State(webhook_service): State<Arc<WebhookService>>,
+        Extension(caller): Extension<Caller>,
+        Path(id): Path<i64>,
+    ) -> ApiResponse<WebhookSubscriptionResponse> {
+        info!("Fetching webhook with id {}", id);
+        match webhook_service.get_subscription(caller.user_id, id).await {
+            Ok(Some(subscription)) => Ok(Json(subscription)),
+            Ok(None) => Err((StatusCode::NOT_FOUND, "Webhook not found".to_string())),
+            Err(e) => {
+                let error_message = format!("Failed to get webhook: {:?}", e);
+                Err((StatusCode::INTERNAL_SERVER_ERROR, error_message))
+            }
+        }
+    }
+
+    async fn update_webhook_handler(
+        State(webhook_service): State<Arc<WebhookService>>,
+        Extension(caller): Extension<Caller>,
+        Path(id): Path<i64>,
+        Json(payload): Json<UpdateWebhookRequest>,
+    ) -> ApiResponse<WebhookSubscriptionResponse> {
+        info!("Updating webhook with id {}", id);
+
+        if payload.url.is_none()
+            && payload.secret.is_none()
+            && payload.events.is_none()
+            && payload.description.is_none()
+            && payload.enabled.is_none()
+        {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                "At least one field must be provided for update".to_string(),
+            ));
+        }
+        if let Some(url) = &payload.url {
+            WebhookRouter::validate_url(url)?;
+        }
+
+        match webhook_service.update_subscription(caller.user_id, id, payload).await {
+            Ok(Some(subscription)) => Ok(Json(subscription)),
+            Ok(None) => Err((StatusCode::NOT_FOUND, "Webhook not found".to_string())),
+            Err(e) => {
+                let error_message = format!("Failed to update webhook: {:?}", e);
+                Err((StatusCode::INTERNAL_SERVER_ERROR, error_message))
+            }
+        }
+    }
+
+    async fn delete_webhook_handler(
+        State(webhook_service): State<Arc<WebhookService>>,
+        Extension(caller): Extension<Caller>,
+        Path(id): Path<i64>,
+    ) -> ApiTextResponse {
+        info!("Soft deleting webhook with id {}", id);
+        match webhook_service.delete_subscription(caller.user_id, id).await {
+            Ok(true) => Ok("Webhook deleted successfully".to_string()),
+            Ok(false) => Err((StatusCode::NOT_FOUND, "Webhook not found".to_string())),
+            Err(e) => {
+                let error_message = format!("Failed to delete webhook: {:?}", e);
+                Err((StatusCode::INTERNAL_SERVER_ERROR, error_message))
+            }
+        }
+    }
+
+    /// Requeues every dead-lettered delivery of the webhook
+    async fn replay_dead_letters_handler(
+        State(webhook_service): State<Arc<WebhookService>>,
+        Extension(caller): Extension<Caller>,
+        Path(id): Path<i64>,
+    ) -> ApiTextResponse {
+        info!("Replaying dead-lettered deliveries of webhook {}", id);
+        match webhook_service.get_subscription(caller.user_id, id).await {
+            Ok(Some(_)) => {}
+            Ok(None) => return Err((StatusCode::NOT_FOUND, "Webhook not found".to_string())),
+            Err(e) => {
+                let error_message = format!("Failed to replay deliveries: {:?}", e);
+                return Err((StatusCode::INTERNAL_SERVER_ERROR, error_message));
+            }
+        }
+        match webhook_service.replay_dead_letters(caller.user_id, id).await {
+            Ok(requeued) => Ok(format!("Requeued {} deliveries", requeued)),
+            Err(e) => {
+                let error_message = format!("Failed to replay deliveries: {:?}", e);
+                Err((StatusCode::INTERNAL_SERVER_ERROR, error_message))
+            }
+        }
+    }
+
+    // ?subscription_id=1&state=DeadLetter&event=task.completed&task_id=4&limit=20
+    async fn search_deliveries_handler(
+        State(webhook_service): State<Arc<WebhookService>>,
+        Extension(caller): Extension<Caller>,
+        Query(params): Query<WebhookDeliverySearchParams>,
+    ) -> ApiResponse<Vec<WebhookDeliveryResponse>> {
+        info!("Searching webhook deliveries with params {:?}", params);
+        handle_service_response!(
+            webhook_service.search_deliveries(caller.user_id, params).await,
+            "Found {} webhook deliveries",
+            |data: &Vec<WebhookDeliveryResponse>| data.len(),
+            "Failed to search webhook deliveries"
+        )
+    }
+
+    async fn get_delivery_by_id_handler(
+        State(webhook_service): State<Arc<WebhookService>>,
+        Extension(caller): Extension<Caller>,
+        Path(id): Path<i64>,
+    ) -> ApiResponse<WebhookDeliveryResponse> {
+        match webhook_service.get_delivery(caller.user_id, id).await {
+            Ok(Some(delivery)) => Ok(Json(delivery)),
+            Ok(None) => Err((StatusCode::NOT_FOUND, "Delivery not found".to_string())),
+            Err(e) => {
+                let error_message = format!("Failed to get delivery: {:?}", e);
+                Err((StatusCode::INTERNAL_SERVER_ERROR, error_message))
+            }
+        }
+    }
+
+    async fn replay_delivery_handler(
+        State(webhook_service): State<Arc<WebhookService>>,
+        Extension(caller): Extension<Caller>,
+        Path(id): Path<i64>,
+    ) -> ApiResponse<WebhookDeliveryResponse> {
+        info!("Replaying webhook delivery {}", id);
+        match webhook_service.replay_delivery(caller.user_id, id).await {
+            Ok(Some(delivery)) => Ok(Json(delivery)),
+            Ok(None) => Err((
+                StatusCode::CONFLICT,
+                "Delivery not found or already delivered".to_string(),
+            )),
+            Err(e) => {
+                let error_message = format!("Failed to replay delivery: {:?}", e);
+                Err((StatusCode::INTERNAL_SERVER_ERROR, error_message))
+            }
+        }
+    }
+}
@@ -0,0 +1,137 @@
+use crate::auth::workspace_access::split_denial;
+use crate::auth::Caller;
+use crate::handle_service_response;
+use crate::models::{ApiResponse, ApiTextResponse};
+use crate::services::workflow_service::WorkflowService;
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use axum::routing::get;
+use axum::{Extension, Json, Router};
+use mindvault_core::models::AppDatabase;
+use mindvault_shared::dtos::task_dtos::TaskVaultParams;
+use mindvault_shared::dtos::workflow_dtos::SetWorkflowRequest;
+use mindvault_shared::models::workflows_model::WorkflowResponse;
+use std::collections::HashSet;
+use std::sync::Arc;
+use tracing::info;
+
+/// Custom task statuses and the moves allowed between them, for the caller's private vault or
+/// `?workspace=<id>`
+pub(crate) struct WorkflowRouter {
+    workflow_service: Arc<WorkflowService>,
+}
+
+impl WorkflowRouter {
+    pub(crate) fn new(app_database: AppDatabase) -> Self {
+        let workflow_service = Arc::new(WorkflowService::new(app_database));
+        Self { workflow_service }
+    }
+
+    pub(crate) fn get_routes(&self) -> Router {
+        Router::new()
+            .route(
+                "/",
+                get(WorkflowRouter::get_workflow_handler)
+                    .put(WorkflowRouter::set_workflow_handler)
+                    .delete(WorkflowRouter::delete_workflow_handler),
+            )
+            .with_state(self.workflow_service.clone())
+    }
+
+    /// Status names must be unique regardless of case, and transitions may only name them
+    fn validate_workflow(request: &SetWorkflowRequest) -> Result<(), (StatusCode, String)> {
+        if request.statuses.is_empty() {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                "A workflow needs at least one status".to_string(),
+            ));
+        }
+        let mut names = HashSet::new();
+        for status in &request.statuses {
+            if status.name.is_empty() {
+                return Err((StatusCode::BAD_REQUEST, "Status name cannot be empty".to_string()));
+            }
+            if status.wip_limit == Some(0) {
+                return Err((
+                    StatusCode::BAD_REQUEST,
+                    format!("WIP limit of '{}' must be at least 1", status.name),
+                ));
+            }
+            if !names.insert(status.name.to_lowercase()) {
+                return Err((
+                    StatusCode::BAD_REQUEST,
+                    format!("Status '{}' is listed more than once", status.name),
+                ));
+            }
+        }
+        for transition in &request.transitions {
+            for name in [&transition.from, &transition.to] {
+                if !names.contains(&name.trim().to_lowercase()) {
+                    return Err((
+                        StatusCode::BAD_REQUEST,
+                        format!("Transition names unknown status '{}'", name),
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    async fn get_workflow_handler(
+        State(workflow_service): State<Arc<WorkflowService>>,
+        Extension(caller): Extension<Caller>,
+        Query(vault): Query<TaskVaultParams>,
+    ) -> ApiResponse<WorkflowResponse> {
+        info!("Fetching workflow");
+        match split_denial(workflow_service.get_workflow(caller.user_id, vault.workspace).await)? {
+            Ok(Some(workflow)) => Ok(Json(workflow)),
+            Ok(None) => Err((StatusCode::NOT_FOUND, "No workflow is set up".to_string())),
+            Err(e) => {
+                let error_message = format!("Failed to get workflow: {:?}", e);
+                Err((StatusCode::INTERNAL_SERVER_ERROR, error_message))
+            }
+        }
+    }
+
+    async fn set_workflow_handler(
+        State(workflow_service): State<Arc<WorkflowService>>,
+        Extension(caller): Extension<Caller>,
+        Query(vault): Query<TaskVaultParams>,
+        Json(mut payload): Json<SetWorkflowRequest>,
+    ) -> ApiResponse<WorkflowResponse> {
+        for status in &mut payload.statuses {
+            status.name = status.name.trim().to_string();
+        }
+        WorkflowRouter::validate_workflow(&payload)?;
+
+        handle_service_response!(
+            split_denial(
+                workflow_service
+                    .set_workflow(caller.user_id, vault.workspace, payload)
+                    .await
+            )?,
+            "Saved a workflow with {} statuses",
+            |data: &WorkflowResponse| data.statuses.len(),
+            "Failed to save workflow"
+        )
+    }
+
+    async fn delete_workflow_handler(
+        State(workflow_service): State<Arc<WorkflowService>>,
+        Extension(caller): Extension<Caller>,
+        Query(vault): Query<TaskVaultParams>,
+    ) -> ApiTextResponse {
+        info!("Deleting workflow");
+        let deleted = workflow_service
+            .delete_workflow(caller.user_id, vault.workspace)
+            .await;
+        match split_denial(deleted)? {
+            Ok(true) => Ok("Workflow deleted".to_string()),
+            Ok(false) => Err((StatusCode::NOT_FOUND, "No workflow is set up".to_string())),
+            Err(e) => {
+                let error_message = format!("Failed to delete workflow: {:?}", e);
+                Err((StatusCode::INTERNAL_SERVER_ERROR, error_message))
+            }
+        }
+    }
+}
@@ -0,0 +1,170 @@
+use crate::auth::workspace_access::split_denial;
+use crate::auth::Caller;
+use crate::handle_service_response;
+use crate::models::{ApiResponse, ApiTextResponse};
+use crate::services::workspace_service::WorkspaceService;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::routing::{delete, get, put};
+use axum::{Extension, Json, Router};
+use mindvault_core::models::AppDatabase;
+use mindvault_shared::dtos::workspace_dtos::{
+    CreateWorkspaceRequest, UpdateWorkspaceRequest, WorkspaceMemberRequest,
+};
+use mindvault_shared::models::workspaces_model::WorkspaceResponse;
+use std::sync::Arc;
+use tracing::info;
+
+pub(crate) struct WorkspaceRouter {
+    workspace_service: Arc<WorkspaceService>,
+}
+
+impl WorkspaceRouter {
+    pub(crate) fn new(app_database: AppDatabase) -> Self {
+        let workspace_service = Arc::new(WorkspaceService::new(app_database));
+        Self { workspace_service }
+    }
+
+    pub(crate) fn get_routes(&self) -> Router {
+        Router::new()
+            .route(
+                "/",
+                get(WorkspaceRouter::get_workspaces_handler)
+                    .post(WorkspaceRouter::create_workspace_handler),
+            )
+            .route(
+                "/{:id}",
+                get(WorkspaceRouter::get_workspace_by_id_handler)
+                    .put(WorkspaceRouter::update_workspace_handler)
+                    .delete(WorkspaceRouter::delete_workspace_handler),
+            )
+            .route("/{:id}/members", put(WorkspaceRouter::put_member_handler))
+            .route(
+                "/{:id}/members/{:user_id}",
+                delete(WorkspaceRouter::remove_member_handler),
+            )
+            .with_state(self.workspace_service.clone())
+    }
+
+    async fn get_workspaces_handler(
+        State(workspace_service): State<Arc<WorkspaceService>>,
+        Extension(caller): Extension<Caller>,
+    ) -> ApiResponse<Vec<WorkspaceResponse>> {
+        info!("Fetching workspaces of user {}", caller.user_id);
+        handle_service_response!(
+            workspace_service.get_workspaces(caller.user_id).await,
+            "Found {} workspaces",
+            |data: &Vec<WorkspaceResponse>| data.len(),
+            "Failed to get workspaces"
+        )
+    }
+
+    async fn create_workspace_handler(
+        State(workspace_service): State<Arc<WorkspaceService>>,
+        Extension(caller): Extension<Caller>,
+        Json(payload): Json<CreateWorkspaceRequest>,
+    ) -> ApiResponse<WorkspaceResponse> {
+        if payload.name.trim().is_empty() {
+            return Err((StatusCode::BAD_REQUEST, "Name field is required".to_string()));
+        }
+        handle_service_response!(
+            workspace_service.create_workspace(caller.user_id, payload).await,
+            "Created a new workspace {}",
+            |data: &WorkspaceResponse| data.id,
+            "Unable to insert a new workspace into database"
+        )
+    }
+
+    async fn get_workspace_by_id_handler(
+        State(workspace_service): State<Arc<WorkspaceService>>,
+        Extension(caller): Extension<Caller>,
+        Path(id): Path<i64>,
+    ) -> ApiResponse<WorkspaceResponse> {
+        info!("Fetching workspace with id {}", id);
+        handle_service_response!(
+            split_denial(workspace_service.get_by_id(caller.user_id, id).await)?,
+            "Found workspace with id {}",
+            |data: &WorkspaceResponse| data.id,
+            "Failed to get workspace"
+        )
+    }
+
+    async fn update_workspace_handler(
+        State(workspace_service): State<Arc<WorkspaceService>>,
+        Extension(caller): Extension<Caller>,
+        Path(id): Path<i64>,
+        Json(payload): Json<UpdateWorkspaceRequest>,
+    ) -> ApiResponse<WorkspaceResponse> {
+        info!("Updating workspace with id {}", id);
+        if payload.name.is_none() && payload.description.is_none() {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                "At least one field must be provided for update".to_string(),
+            ));
+        }
+        if payload.name.as_ref().is_some_and(|name| name.trim().is_empty()) {
+            return Err((StatusCode::BAD_REQUEST, "Name cannot be empty".to_string()));
+        }
+        handle_service_response!(
+            split_denial(
+                workspace_service
+                    .update_workspace(caller.user_id, id, payload)
+                    .await
+            )?,
+            "Updated workspace with id {}",
+            |data: &WorkspaceResponse| data.id,
+            "Failed to update workspace"
+        )
+    }
+
+    async fn delete_workspace_handler(
+        State(workspace_service): State<Arc<WorkspaceService>>,
+        Extension(caller): Extension<Caller>,
+        Path(id): Path<i64>,
+    ) -> ApiTextResponse {
+        info!("Soft deleting workspace with id {}", id);
+        match split_denial(workspace_service.delete_workspace(caller.user_id, id).await)? {
+            Ok(()) => Ok("Workspace deleted successfully".to_string()),
+            Err(e) => {
+                let error_message = format!("Failed to delete workspace: {:?}", e);
+                Err((StatusCode::INTERNAL_SERVER_ERROR, error_message))
+            }
+        }
+    }
+
+    async fn put_member_handler(
+        State(workspace_service): State<Arc<WorkspaceService>>,
+        Extension(caller): Extension<Caller>,
+        Path(id): Path<i64>,
+        Json(payload): Json<WorkspaceMemberRequest>,
+    ) -> ApiResponse<WorkspaceResponse> {
+        info!(
+            "Making '{}' {} of workspace {}",
+            payload.username, payload.role, id
+        );
+        handle_service_response!(
+            split_denial(workspace_service.put_member(caller.user_id, id, payload).await)?,
+            "Updated members of workspace {}",
+            |data: &WorkspaceResponse| data.id,
+            "Failed to update workspace members"
+        )
+    }
+
+    async fn remove_member_handler(
+        State(workspace_service): State<Arc<WorkspaceService>>,
+        Extension(caller): Extension<Caller>,
+        Path((id, user_id)): Path<(i64, i64)>,
+    ) -> ApiResponse<WorkspaceResponse> {
+        info!("Removing user {} from workspace {}", user_id, id);
+        handle_service_response!(
+            split_denial(
+                workspace_service
+                    .remove_member(caller.user_id, id, user_id)
+                    .await
+            )?,
+            "Updated members of workspace {}",
+            |data: &WorkspaceResponse| data.id,
+            "Failed to update workspace members"
+        )
+    }
+}
@@ -0,0 +1,147 @@
+use crate::scheduler::{JobSchedule, Scheduler};
+use crate::services::digest_service::DigestService;
+use crate::services::goal_service::GoalService;
+use crate::services::notification_service::NotificationService;
+use crate::services::reminder_service::ReminderService;
+use crate::services::webhook_service::WebhookService;
+use mindvault_core::models::AppDatabase;
+use mindvault_core::scheduler_config::SchedulerConfig;
+use mindvault_shared::models::digest_model::EDigestPeriod;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::info;
+
+/// Builds the scheduler with every background job the API runs
+pub(crate) fn build_scheduler(app_database: AppDatabase) -> Scheduler {
+    let mut scheduler = Scheduler::new(app_database.clone(), SchedulerConfig::from_env());
+
+    let reminder_service = Arc::new(ReminderService::new(app_database.clone()));
+    scheduler.add_job(
+        "refresh_reminders",
+        JobSchedule::every(Duration::from_secs(60)),
+        true,
+        move || {
+            let reminder_service = reminder_service.clone();
+            async move {
+                reminder_service
+                    .refresh_task_relative()
+                    .await
+                    .map(|_| ())
+                    .map_err(|e| format!("{:?}", e))
+            }
+        },
+    );
+
+    let goal_service = Arc::new(GoalService::new(app_database.clone()));
+    scheduler.add_job(
+        "sync_goal_progress",
+        JobSchedule::cron("0 0 * * * *").expect("valid cron expression"),
+        true,
+        move || {
+            let goal_service = goal_service.clone();
+            async move {
+                let synced = goal_service
+                    .sync_all_linked_key_results()
+                    .await
+                    .map_err(|e| format!("{:?}", e))?;
+                info!("Synced {} task-linked key results", synced);
+                Ok(())
+            }
+        },
+    );
+
+    let notification_service = Arc::new(NotificationService::new(app_database.clone()));
+    let service = notification_service.clone();
+    scheduler.add_job(
+        "fire_reminders",
+        JobSchedule::every(Duration::from_secs(60)),
+        true,
+        move || {
+            let notification_service = service.clone();
+            async move {
+                notification_service
+                    .notify_due_reminders()
+                    .await
+                    .map(|_| ())
+                    .map_err(|e| format!("{:?}", e))
+            }
+        },
+    );
+
+    let service = notification_service.clone();
+    scheduler.add_job(
+        "overdue_alerts",
+        JobSchedule::cron("0 0 * * * *").expect("valid cron expression"),
+        true,
+        move || {
+            let notification_service = service.clone();
+            async move {
+                let queued = notification_service
+                    .notify_overdue_tasks()
+                    .await
+                    .map_err(|e| format!("{:?}", e))?;
+                info!("Queued {} overdue task alerts", queued);
+                Ok(())
+            }
+        },
+    );
+
+    // Cron schedules run in UTC; the digest's "today" follows MINDVAULT_DIGEST_TIME_ZONE
+    let digest_service = Arc::new(DigestService::new(app_database.clone()));
+    for (name, expression, period) in [
+        ("daily_digest", "0 0 7 * * *", EDigestPeriod::Daily),
+        ("weekly_digest", "0 0 7 * * Mon", EDigestPeriod::Weekly),
+    ] {
+        let digest_service = digest_service.clone();
+        scheduler.add_job(
+            name,
+            JobSchedule::cron(expression).expect("valid cron expression"),
+            true,
+            move || {
+                let digest_service = digest_service.clone();
+                async move {
+                    digest_service
+                        .deliver_digest(period)
+                        .await
+                        .map(|_| ())
+                        .map_err(|e| format!("{:?}", e))
+                }
+            },
+        );
+    }
+
+    scheduler.add_job(
+        "deliver_notifications",
+        JobSchedule::every(Duration::from_secs(30)),
+        true,
+        move || {
+            let notification_service = notification_service.clone();
+            async move {
+                notification_service
+                    .deliver_pending()
+                    .await
+                    .map(|_| ())
+                    .map_err(|e| format!("{:?}", e))
+            }
+        },
+    );
+
+    let webhook_service = Arc::new(WebhookService::new(app_database.clone()));
+    scheduler.add_job(
+        "deliver_webhooks",
+        JobSchedule::every(Duration::from_secs(10)),
+        true,
+        move || {
+            let webhook_service = webhook_service.clone();
+            async move {
+                webhook_service
+                    .deliver_pending()
+                    .await
+                    .map(|_| ())
+                    .map_err(|e| format!("{:?}", e))
+            }
+        },
+    );
+
+    scheduler
+}
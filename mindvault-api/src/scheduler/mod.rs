@@ -0,0 +1,241 @@
+pub(crate) mod jobs;
+
+use bson::DateTime as BsonDateTime;
+use chrono::{DateTime, Utc};
+use mindvault_core::models::AppDatabase;
+use mindvault_core::repository::job_repo::JobRepository;
+use mindvault_core::scheduler_config::SchedulerConfig;
+use std::future::Future;
+use std::pin::Pin;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tracing::{error, info, warn};
+
+pub(crate) type JobFuture = Pin<Box<dyn Future<Output = Result<(), String>> + Send>>;
+pub(crate) type JobFn = Arc<dyn Fn() -> JobFuture + Send + Sync>;
+
+const DEFAULT_LEASE: Duration = Duration::from_secs(10 * 60);
+
+/// When a job runs: a fixed interval or a cron expression (with seconds, as in `0 0 * * * *`)
+pub(crate) enum JobSchedule {
+    Interval(Duration),
+    Cron(Box<cron::Schedule>),
+}
+
+impl JobSchedule {
+    pub(crate) fn every(interval: Duration) -> Self {
+        JobSchedule::Interval(interval)
+    }
+
+    pub(crate) fn cron(expression: &str) -> Result<Self, String> {
+        cron::Schedule::from_str(expression)
+            .map(|schedule| JobSchedule::Cron(Box::new(schedule)))
+            .map_err(|e| format!("invalid cron expression '{}': {}", expression, e))
+    }
+
+    fn next_after(&self, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        match self {
+            JobSchedule::Interval(interval) => {
+                chrono::Duration::from_std(*interval).ok().map(|d| after + d)
+            }
+            JobSchedule::Cron(schedule) => schedule.after(&after).next(),
+        }
+    }
+
+    fn describe(&self) -> String {
+        match self {
+            JobSchedule::Interval(interval) => format!("every {}s", interval.as_secs()),
+            JobSchedule::Cron(schedule) => format!("cron {}", schedule),
+        }
+    }
+}
+
+struct ScheduledJob {
+    name: String,
+    schedule: JobSchedule,
+    enabled: bool,
+    lease: Duration,
+    run: JobFn,
+}
+
+/// In-process job runner. Job state lives in the database so that several API instances
+/// can share it: a job only runs on the instance that wins its lease, and a run that was
+/// missed while every instance was down happens on the first tick after start-up.
+pub(crate) struct Scheduler {
+    job_repository: Arc<JobRepository>,
+    config: SchedulerConfig,
+    instance_id: String,
+    jobs: Vec<Arc<ScheduledJob>>,
+}
+
+impl Scheduler {
+    pub(crate) fn new(app_database: AppDatabase, config: SchedulerConfig) -> Self {
+        let host = std::env::var("HOSTNAME").unwrap_or_else(|_| "localhost".to_string());
+        let instance_id = format!(
+            "{}-{}-{}",
+            host,
+            std::process::id(),
+            Utc::now().timestamp_millis()
+        );
+        Self {
+            job_repository: Arc::new(JobRepository::new(app_database)),
+            config,
+            instance_id,
+            jobs: Vec::new(),
+        }
+    }
+
+    /// Adds a job; `enabled_by_default` applies unless `MINDVAULT_JOB_<NAME>_ENABLED` says otherwise
+    pub(crate) fn add_job<F, Fut>(
+        &mut self,
+        name: &str,
+        schedule: JobSchedule,
+        enabled_by_default: bool,
+        run: F,
+    ) -> &mut Self
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<(), String>> + Send + 'static,
+    {
+        let enabled = self.config.job_enabled(name, enabled_by_default);
+        self.jobs.push(Arc::new(ScheduledJob {
+            name: name.to_string(),
+            schedule,
+            enabled,
+            lease: DEFAULT_LEASE,
+            run: Arc::new(move || Box::pin(run())),
+        }));
+        self
+    }
+
+    /// Registers all jobs and then checks for due jobs on every tick, forever
+    pub(crate) async fn run(self) {
+        if !self.config.enabled {
+            info!("Background scheduler disabled by configuration");
+            return;
+        }
+
+        let now = Utc::now();
+        for job in &self.jobs {
+            let first_run_at = job.schedule.next_after(now).unwrap_or(now);
+            if let Err(e) = self
+                .job_repository
+                .register(
+                    &job.name,
+                    &job.schedule.describe(),
+                    job.enabled,
+                    BsonDateTime::from(first_run_at),
+                )
+                .await
+            {
+                error!("Failed to register job {}: {:?}", job.name, e);
+            }
+        }
+        info!(
+            "Scheduler {} started with {} jobs",
+            self.instance_id,
+            self.jobs.len()
+        );
+
+        let mut ticker = tokio::time::interval(Duration::from_secs(self.config.tick_seconds));
+        loop {
+            ticker.tick().await;
+            for job in self.jobs.iter().filter(|job| job.enabled) {
+                self.try_run(job.clone()).await;
+            }
+        }
+    }
+
+    async fn try_run(&self, job: Arc<ScheduledJob>) {
+        let now = Utc::now();
+        let lease_expires_at = now + chrono::Duration::from_std(job.lease).unwrap_or_default();
+        let acquired = self
+            .job_repository
+            .try_acquire_lease(
+                &job.name,
+                &self.instance_id,
+                BsonDateTime::from(now),
+                BsonDateTime::from(lease_expires_at),
+            )
+            .await;
+        match acquired {
+            Ok(Some(_)) => {}
+            Ok(None) => return,
+            Err(e) => {
+                error!("Failed to acquire lease for job {}: {:?}", job.name, e);
+                return;
+            }
+        }
+
+        let job_repository = self.job_repository.clone();
+        let owner = self.instance_id.clone();
+        tokio::spawn(async move {
+            info!("Running job {}", job.name);
+            let started = Instant::now();
+            // Never outlive the lease, otherwise another instance could start the same job
+            let result = match tokio::time::timeout(job.lease, (job.run)()).await {
+                Ok(result) => result,
+                Err(_) => Err(format!("timed out after {}s", job.lease.as_secs())),
+            };
+            let duration_ms = started.elapsed().as_millis() as i64;
+            if let Err(e) = &result {
+                warn!("Job {} failed after {}ms: {}", job.name, duration_ms, e);
+            } else {
+                info!("Job {} finished in {}ms", job.name, duration_ms);
+            }
+
+            let finished_at = Utc::now();
+            let next_run_at = job.schedule.next_after(finished_at).map(BsonDateTime::from);
+            if let Err(e) = job_repository
+                .complete_run(
+                    &job.name,
+                    &owner,
+                    BsonDateTime::from(finished_at),
+                    duration_ms,
+                    result.err(),
+                    next_run_at,
+                )
+                .await
+            {
+                error!("Failed to record run of job {}: {:?}", job.name, e);
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn utc(hour: u32, minute: u32, second: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 10, 19, hour, minute, second).unwrap()
+    }
+
+    #[test]
+    fn cron_jobs_run_at_the_next_matching_time() {
+        let hourly = JobSchedule::cron("0 0 * * * *").unwrap();
+        assert_eq!(hourly.next_after(utc(9, 15, 0)), Some(utc(10, 0, 0)));
+        assert_eq!(hourly.next_after(utc(10, 0, 0)), Some(utc(11, 0, 0)));
+
+        let morning = JobSchedule::cron("0 30 7 * * *").unwrap();
+        assert_eq!(
+            morning.next_after(utc(8, 0, 0)),
+            Some(Utc.with_ymd_and_hms(2026, 10, 20, 7, 30, 0).unwrap())
+        );
+    }
+
+    #[test]
+    fn interval_jobs_run_one_interval_later() {
+        let every_minute = JobSchedule::every(Duration::from_secs(60));
+        assert_eq!(every_minute.next_after(utc(9, 15, 30)), Some(utc(9, 16, 30)));
+        assert_eq!(every_minute.describe(), "every 60s");
+    }
+
+    #[test]
+    fn invalid_cron_expressions_are_rejected() {
+        let error = JobSchedule::cron("every hour").err().unwrap();
+        assert!(error.starts_with("invalid cron expression 'every hour'"));
+    }
+}
@@ -0,0 +1,114 @@
+use axum::response::ErrorResponse;
+use mindvault_core::access_tokens::{generate_access_token, hash_access_token, ACCESS_TOKEN_PREFIX};
+use mindvault_core::models::{AppDatabase, DbError};
+use mindvault_core::repository::access_token_repo::AccessTokenRepository;
+use mindvault_core::repository::user_repo::UserRepository;
+use mindvault_shared::dtos::access_token_dtos::CreateAccessTokenRequest;
+use mindvault_shared::models::access_tokens_model::{
+    AccessToken, AccessTokenResponse, CreatedAccessTokenResponse,
+};
+use mindvault_shared::models::users_model::User;
+use tracing::{error, warn};
+
+pub(crate) struct AccessTokenService {
+    access_token_repository: AccessTokenRepository,
+    user_repository: UserRepository,
+}
+
+fn service_error(context: &str, e: DbError) -> ErrorResponse {
+    let error_message = format!("{}: {:?}", context, e);
+    error!("{}", error_message);
+    ErrorResponse::from(error_message)
+}
+
+impl AccessTokenService {
+    pub(crate) fn new(app_database: AppDatabase) -> Self {
+        Self {
+            access_token_repository: AccessTokenRepository::new(app_database.clone()),
+            user_repository: UserRepository::new(app_database),
+        }
+    }
+
+    pub(crate) async fn get_tokens(&self) -> Result<Vec<AccessTokenResponse>, ErrorResponse> {
+        match self.access_token_repository.find_all().await {
+            Ok(tokens) => Ok(AccessTokenResponse::from_vec(tokens)),
+            Err(e) => Err(service_error("Error fetching access tokens", e)),
+        }
+    }
+
+    /// Tokens belong to the caller's user unless the request names another one. The inner
+    /// `Err` is a validation message for the caller.
+    pub(crate) async fn create_token(
+        &self,
+        caller_user_id: i64,
+        request: CreateAccessTokenRequest,
+    ) -> Result<Result<CreatedAccessTokenResponse, String>, ErrorResponse> {
+        let name = request.name.trim().to_string();
+        if name.is_empty() {
+            return Ok(Err("Name field is required".to_string()));
+        }
+        if request.scopes.is_empty() {
+            return Ok(Err("At least one scope is required".to_string()));
+        }
+
+        let user_id = request.user_id.unwrap_or(caller_user_id);
+        match self.user_repository.find_by_id(user_id).await {
+            Ok(Some(user)) if !user.disabled => {}
+            Ok(Some(_)) => return Ok(Err(format!("User {} is disabled", user_id))),
+            Ok(None) => return Ok(Err(format!("User {} not found", user_id))),
+            Err(e) => return Err(service_error("Error finding user", e)),
+        }
+
+        let new_token = generate_access_token();
+        match self
+            .access_token_repository
+            .create_token(user_id, name, request.scopes, new_token.hash, new_token.prefix)
+            .await
+        {
+            Ok(token) => Ok(Ok(CreatedAccessTokenResponse {
+                token: new_token.secret,
+                details: AccessTokenResponse::from(token),
+            })),
+            Err(e) => Err(service_error("Error creating access token", e)),
+        }
+    }
+
+    pub(crate) async fn revoke_token(
+        &self,
+        id: i64,
+    ) -> Result<Option<AccessTokenResponse>, ErrorResponse> {
+        match self.access_token_repository.revoke_by_id(id).await {
+            Ok(token) => Ok(token.map(AccessTokenResponse::from)),
+            Err(e) => Err(service_error("Error revoking access token", e)),
+        }
+    }
+
+    /// The unrevoked token matching `secret` and its user, who must not be disabled, recording
+    /// that the token was used
+    pub(crate) async fn authenticate(
+        &self,
+        secret: &str,
+    ) -> Result<Option<(AccessToken, User)>, ErrorResponse> {
+        if !secret.starts_with(ACCESS_TOKEN_PREFIX) {
+            return Ok(None);
+        }
+        let token = match self
+            .access_token_repository
+            .find_active_by_hash(&hash_access_token(secret))
+            .await
+        {
+            Ok(Some(token)) => token,
+            Ok(None) => return Ok(None),
+            Err(e) => return Err(service_error("Error checking access token", e)),
+        };
+        let user = match self.user_repository.find_by_id(token.user_id).await {
+            Ok(Some(user)) if !user.disabled => user,
+            Ok(_) => return Ok(None),
+            Err(e) => return Err(service_error("Error checking token user", e)),
+        };
+        if let Err(e) = self.access_token_repository.touch_last_used(token.id).await {
+            warn!("Could not record use of access token {}: {:?}", token.id, e);
+        }
+        Ok(Some((token, user)))
+    }
+}
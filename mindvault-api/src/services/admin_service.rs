@@ -0,0 +1,110 @@
+use axum::response::ErrorResponse;
+use mindvault_core::backup::{backup_dir_from_env, write_backup_file};
+use mindvault_core::models::AppDatabase;
+use mindvault_core::repository::job_repo::JobRepository;
+use mindvault_core::repository::user_repo::UserRepository;
+use mindvault_shared::dtos::user_dtos::CreateUserRequest;
+use mindvault_shared::models::backup_model::BackupSummary;
+use mindvault_shared::models::jobs_model::JobStateResponse;
+use mindvault_shared::models::users_model::{normalize_username, UserResponse};
+use tracing::error;
+
+pub(crate) struct AdminService {
+    app_database: AppDatabase,
+    job_repository: JobRepository,
+    user_repository: UserRepository,
+}
+
+impl AdminService {
+    pub(crate) fn new(app_database: AppDatabase) -> Self {
+        let job_repository = JobRepository::new(app_database.clone());
+        let user_repository = UserRepository::new(app_database.clone());
+        Self {
+            app_database,
+            job_repository,
+            user_repository,
+        }
+    }
+
+    /// Writes a full backup archive to the configured backup directory
+    pub(crate) async fn create_backup(&self) -> Result<BackupSummary, ErrorResponse> {
+        match write_backup_file(&self.app_database, &backup_dir_from_env()).await {
+            Ok(summary) => Ok(summary),
+            Err(e) => {
+                let error_message = format!("Error creating backup: {}", e);
+                error!(error_message);
+                Err(ErrorResponse::from(error_message))
+            }
+        }
+    }
+
+    pub(crate) async fn get_jobs(&self) -> Result<Vec<JobStateResponse>, ErrorResponse> {
+        let jobs = self.job_repository.find_all().await;
+        match jobs {
+            Ok(jobs) => Ok(JobStateResponse::from_vec(jobs)),
+            Err(e) => {
+                let error_message = format!("Error finding scheduler jobs: {}", e);
+                error!(error_message);
+                Err(ErrorResponse::from(error_message))
+            }
+        }
+    }
+
+    pub(crate) async fn get_users(&self) -> Result<Vec<UserResponse>, ErrorResponse> {
+        match self.user_repository.find_all().await {
+            Ok(users) => Ok(UserResponse::from_vec(users)),
+            Err(e) => {
+                let error_message = format!("Error finding users: {}", e);
+                error!(error_message);
+                Err(ErrorResponse::from(error_message))
+            }
+        }
+    }
+
+    /// The inner `Err` is a validation message for the caller
+    pub(crate) async fn create_user(
+        &self,
+        request: CreateUserRequest,
+    ) -> Result<Result<UserResponse, String>, ErrorResponse> {
+        let username = match normalize_username(&request.username) {
+            Ok(username) => username,
+            Err(message) => return Ok(Err(message)),
+        };
+        let display_name = request
+            .display_name
+            .map(|name| name.trim().to_string())
+            .filter(|name| !name.is_empty());
+
+        let created = async {
+            if self.user_repository.find_by_username(&username).await?.is_some() {
+                return Ok(Err(format!("User '{}' already exists", username)));
+            }
+            let user = self.user_repository.create_user(&username, display_name).await?;
+            Ok::<_, mindvault_core::models::DbError>(Ok(UserResponse::from(user)))
+        };
+        match created.await {
+            Ok(result) => Ok(result),
+            Err(e) => {
+                let error_message = format!("Error creating user: {:?}", e);
+                error!("{}", error_message);
+                Err(ErrorResponse::from(error_message))
+            }
+        }
+    }
+
+    /// Disables or re-enables a user; `None` if the user does not exist
+    pub(crate) async fn set_user_disabled(
+        &self,
+        id: i64,
+        disabled: bool,
+    ) -> Result<Option<UserResponse>, ErrorResponse> {
+        match self.user_repository.set_disabled(id, disabled).await {
+            Ok(user) => Ok(user.map(UserResponse::from)),
+            Err(e) => {
+                let error_message = format!("Error updating user: {:?}", e);
+                error!("{}", error_message);
+                Err(ErrorResponse::from(error_message))
+            }
+        }
+    }
+}
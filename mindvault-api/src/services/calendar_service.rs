@@ -0,0 +1,62 @@
+use crate::services::saved_filter_service::SavedFilterService;
+use axum::response::ErrorResponse;
+use mindvault_core::calendar::render_calendar;
+use mindvault_core::models::{AppDatabase, DbError};
+use mindvault_core::repository::task_repo::{TaskRepository, TaskVault};
+use mindvault_shared::dtos::calendar_dtos::CalendarParams;
+use mindvault_shared::dtos::task_dtos::TaskSearchParams;
+use mindvault_shared::utils::date_input::DatePreferences;
+use tracing::error;
+
+pub(crate) struct CalendarService {
+    task_repository: TaskRepository,
+    saved_filter_service: SavedFilterService,
+}
+
+fn service_error(context: &str, e: DbError) -> ErrorResponse {
+    let error_message = format!("{}: {:?}", context, e);
+    error!("{}", error_message);
+    ErrorResponse::from(error_message)
+}
+
+impl CalendarService {
+    pub(crate) fn new(app_database: AppDatabase) -> Self {
+        Self {
+            task_repository: TaskRepository::new(app_database.clone()),
+            saved_filter_service: SavedFilterService::new(app_database),
+        }
+    }
+
+    /// Renders the owner's tasks with a due date as an iCalendar feed; `None` when the saved
+    /// filter does not exist
+    pub(crate) async fn get_calendar(
+        &self,
+        owner_id: i64,
+        params: &CalendarParams,
+        dates: &DatePreferences,
+    ) -> Result<Option<String>, ErrorResponse> {
+        let mut search_params = match params.filter_id {
+            Some(filter_id) => match self
+                .saved_filter_service
+                .find_filter(owner_id, filter_id)
+                .await?
+            {
+                Some(filter) => TaskSearchParams::from(filter.criteria),
+                None => return Ok(None),
+            },
+            None => TaskSearchParams::default(),
+        };
+        if params.tag.is_some() {
+            search_params.tag = params.tag.clone();
+        }
+
+        let mut tasks = self
+            .task_repository
+            .search_task(TaskVault::User(owner_id), search_params, dates)
+            .await
+            .map_err(|e| service_error("Error fetching calendar tasks", e))?;
+        tasks.retain(|task| task.due_date.is_some());
+        tasks.sort_by_key(|task| (task.due_date, task.id));
+        Ok(Some(render_calendar(&tasks, params.include_events)))
+    }
+}
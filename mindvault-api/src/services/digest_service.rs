@@ -0,0 +1,93 @@
+use crate::notifications::NotificationDispatcher;
+use axum::response::ErrorResponse;
+use chrono::Utc;
+use mindvault_core::digest::{render_subject, render_text, DigestGenerator, DigestOptions};
+use mindvault_core::models::AppDatabase;
+use mindvault_core::repository::user_repo::UserRepository;
+use mindvault_shared::models::digest_model::{EDigestPeriod, TaskDigest};
+use mindvault_shared::models::notifications_model::{ENotificationType, NotificationMessage};
+use tracing::{error, info};
+
+pub(crate) struct DigestService {
+    digest_generator: DigestGenerator,
+    dispatcher: NotificationDispatcher,
+    user_repository: UserRepository,
+}
+
+impl DigestService {
+    pub(crate) fn new(app_database: AppDatabase) -> Self {
+        Self {
+            digest_generator: DigestGenerator::new(app_database.clone()),
+            dispatcher: NotificationDispatcher::new(app_database.clone()),
+            user_repository: UserRepository::new(app_database),
+        }
+    }
+
+    pub(crate) async fn get_digest(
+        &self,
+        owner_id: i64,
+        period: EDigestPeriod,
+        options: &DigestOptions,
+    ) -> Result<TaskDigest, ErrorResponse> {
+        match self
+            .digest_generator
+            .generate(owner_id, period, Utc::now(), options)
+            .await
+        {
+            Ok(digest) => Ok(digest),
+            Err(e) => {
+                let error_message = format!("Error generating digest: {:?}", e);
+                error!("{}", error_message);
+                Err(ErrorResponse::from(error_message))
+            }
+        }
+    }
+
+    /// Queues each active user's digest for that user's channels subscribed to the period's
+    /// notification type, at most once per user and local date
+    pub(crate) async fn deliver_digest(
+        &self,
+        period: EDigestPeriod,
+    ) -> Result<usize, ErrorResponse> {
+        let users = match self.user_repository.find_active().await {
+            Ok(users) => users,
+            Err(e) => {
+                let error_message = format!("Error loading users for digest: {:?}", e);
+                error!("{}", error_message);
+                return Err(ErrorResponse::from(error_message));
+            }
+        };
+        let notification_type = match period {
+            EDigestPeriod::Daily => ENotificationType::DailyDigest,
+            EDigestPeriod::Weekly => ENotificationType::WeeklyDigest,
+        };
+
+        let mut queued = 0;
+        for user in users {
+            let mut options = DigestOptions::from_env();
+            if let Some(time_zone) = &user.time_zone {
+                options.time_zone = time_zone.clone();
+            }
+            let digest = self.get_digest(user.id, period, &options).await?;
+            let message = NotificationMessage {
+                notification_type,
+                title: format!("{} ({})", render_subject(&digest), user.username),
+                body: render_text(&digest),
+                entity_type: None,
+                entity_id: None,
+            };
+            let dedupe_key = format!("{:?}:{}:{}", notification_type, user.id, digest.date);
+
+            match self.dispatcher.notify(&[user.id], message, Some(dedupe_key)).await {
+                Ok(count) => queued += count,
+                Err(e) => {
+                    let error_message = format!("Error queueing digest: {:?}", e);
+                    error!("{}", error_message);
+                    return Err(ErrorResponse::from(error_message));
+                }
+            }
+        }
+        info!("Queued {} {} digests", queued, period.label().to_lowercase());
+        Ok(queued)
+    }
+}
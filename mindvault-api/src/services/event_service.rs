@@ -0,0 +1,138 @@
+use crate::events::{ChangeFeed, EventFilter};
+use crate::services::saved_filter_service::SavedFilterService;
+use axum::response::ErrorResponse;
+use futures_util::{Stream, StreamExt};
+use mindvault_core::models::AppDatabase;
+use mindvault_core::repository::workspace_repo::WorkspaceRepository;
+use mindvault_shared::dtos::event_dtos::EventStreamParams;
+use mindvault_shared::models::events_model::FeedMessage;
+use mindvault_shared::models::saved_filters_model::TaskFilterCriteria;
+use mindvault_shared::models::webhooks_model::ETaskEvent;
+use std::sync::Arc;
+use tracing::error;
+
+pub(crate) struct EventService {
+    change_feed: Arc<ChangeFeed>,
+    saved_filter_service: SavedFilterService,
+    workspace_repository: Arc<WorkspaceRepository>,
+}
+
+fn split_list(value: Option<&str>) -> Vec<String> {
+    value
+        .unwrap_or_default()
+        .split(',')
+        .map(|item| item.trim().to_string())
+        .filter(|item| !item.is_empty())
+        .collect()
+}
+
+impl EventService {
+    pub(crate) fn new(app_database: AppDatabase, change_feed: Arc<ChangeFeed>) -> Self {
+        Self {
+            change_feed,
+            saved_filter_service: SavedFilterService::new(app_database.clone()),
+            workspace_repository: Arc::new(WorkspaceRepository::new(app_database)),
+        }
+    }
+
+    pub(crate) fn source(&self) -> &'static str {
+        self.change_feed.source()
+    }
+
+    /// Builds the subscription filter; the inner `Err` is a bad request, the outer one a
+    /// failure to load the saved filter
+    pub(crate) async fn resolve_filter(
+        &self,
+        owner_id: i64,
+        params: &EventStreamParams,
+    ) -> Result<Result<EventFilter, String>, ErrorResponse> {
+        let mut events = Vec::new();
+        for name in split_list(params.events.as_deref()) {
+            match serde_json::from_value::<ETaskEvent>(serde_json::Value::String(name.clone())) {
+                Ok(event) => events.push(event),
+                Err(_) => return Ok(Err(format!("Unknown event '{}'", name))),
+            }
+        }
+
+        let mut criteria = match params.filter_id {
+            Some(filter_id) => match self
+                .saved_filter_service
+                .find_filter(owner_id, filter_id)
+                .await?
+            {
+                Some(filter) => Some(filter.criteria),
+                None => return Ok(Err(format!("Saved filter {} not found", filter_id))),
+            },
+            None => None,
+        };
+        if params.query.is_some()
+            || params.status.is_some()
+            || params.priority.is_some()
+            || params.tag.is_some()
+        {
+            let criteria = criteria.get_or_insert_with(TaskFilterCriteria::default);
+            if params.query.is_some() {
+                criteria.query = params.query.clone();
+            }
+            if params.status.is_some() {
+                criteria.status = params.status.clone();
+            }
+            if params.priority.is_some() {
+                criteria.priority = params.priority.clone();
+            }
+            if params.tag.is_some() {
+                criteria.tag = params.tag.clone();
+            }
+        }
+
+        Ok(Ok(EventFilter {
+            owner_id,
+            entity_types: split_list(params.entity.as_deref()),
+            events,
+            criteria,
+        }))
+    }
+
+    /// Subscribes to the feed. Membership is read again for every workspace event, so events
+    /// stop as soon as the subscriber leaves or is removed, and start for workspaces joined
+    /// after subscribing.
+    pub(crate) fn subscribe(
+        &self,
+        last_event_id: Option<&str>,
+        filter: EventFilter,
+    ) -> impl Stream<Item = FeedMessage> + Send + use<> {
+        let user_id = filter.owner_id;
+        let workspace_repository = self.workspace_repository.clone();
+        self.change_feed
+            .subscribe(last_event_id, filter)
+            .filter_map(move |message| {
+                let workspace_repository = workspace_repository.clone();
+                async move {
+                    let workspace_id = match &message {
+                        FeedMessage::Change(event) => {
+                            event.task.as_ref().and_then(|task| task.workspace_id)
+                        }
+                        FeedMessage::Reset { .. } => None,
+                    };
+                    let Some(workspace_id) = workspace_id else {
+                        return Some(message);
+                    };
+                    match workspace_repository.find_by_id(workspace_id).await {
+                        Ok(workspace) => workspace
+                            .filter(|workspace| workspace.role_of(user_id).is_some())
+                            .map(|_| message),
+                        Err(e) => {
+                            let error_message = format!(
+                                "Error checking membership of workspace {}: {}",
+                                workspace_id, e
+                            );
+                            error!("{}", error_message);
+                            Some(FeedMessage::Reset {
+                                reason: "workspace membership could not be checked".to_string(),
+                            })
+                        }
+                    }
+                }
+            })
+    }
+}
@@ -0,0 +1,161 @@
+use axum::response::ErrorResponse;
+use mindvault_core::models::AppDatabase;
+use mindvault_core::repository::date_utils::naive_date_to_bson;
+use mindvault_core::repository::feedback_repo::FeedbackRepository;
+use mindvault_core::repository::reportee_repo::ReporteeRepository;
+use mindvault_shared::dtos::feedback_dtos::{
+    CreateFeedbackRequest, FeedbackSearchParams, FeedbackSummaryParams, UpdateFeedbackRequest,
+};
+use mindvault_shared::models::feedback_model::{FeedbackEntryResponse, FeedbackSummaryResponse};
+use mindvault_shared::models::reportees_model::ReporteeResponse;
+use tracing::error;
+
+pub(crate) struct FeedbackService {
+    feedback_repository: FeedbackRepository,
+    reportee_repository: ReporteeRepository,
+}
+
+impl FeedbackService {
+    pub(crate) fn new(app_database: AppDatabase) -> Self {
+        let feedback_repository = FeedbackRepository::new(app_database.clone());
+        let reportee_repository = ReporteeRepository::new(app_database.clone());
+        Self {
+            feedback_repository,
+            reportee_repository,
+        }
+    }
+
+    pub(crate) async fn find_reportee(
+        &self,
+        owner_id: i64,
+        reportee_id: i64,
+    ) -> Result<Option<ReporteeResponse>, ErrorResponse> {
+        match self.reportee_repository.find_by_id(owner_id, reportee_id).await {
+            Ok(reportee) => Ok(reportee.map(ReporteeResponse::from)),
+            Err(e) => {
+                let error_message = format!("Error finding reportee: {}", e);
+                error!("{}", error_message);
+                Err(ErrorResponse::from(error_message))
+            }
+        }
+    }
+
+    pub(crate) async fn create_entry(
+        &self,
+        owner_id: i64,
+        new_entry: CreateFeedbackRequest,
+    ) -> Result<FeedbackEntryResponse, ErrorResponse> {
+        let created_entry = self.feedback_repository.create_entry(owner_id, new_entry).await;
+        match created_entry {
+            Ok(entry) => Ok(FeedbackEntryResponse::from(entry)),
+            Err(e) => {
+                let error_message = format!("Error creating feedback entry: {:?}", e);
+                error!("{}", error_message);
+                Err(ErrorResponse::from(error_message))
+            }
+        }
+    }
+
+    pub(crate) async fn get_by_id(
+        &self,
+        owner_id: i64,
+        entry_id: i64,
+    ) -> Result<Option<FeedbackEntryResponse>, ErrorResponse> {
+        let entry = self.feedback_repository.find_by_id(owner_id, entry_id).await;
+        match entry {
+            Ok(entry) => Ok(entry.map(FeedbackEntryResponse::from)),
+            Err(e) => {
+                let error_message = format!("Error finding feedback entry: {}", e);
+                error!("{}", error_message);
+                Err(ErrorResponse::from(error_message))
+            }
+        }
+    }
+
+    pub(crate) async fn search_entries(
+        &self,
+        owner_id: i64,
+        params: FeedbackSearchParams,
+    ) -> Result<Vec<FeedbackEntryResponse>, ErrorResponse> {
+        let entries = self.feedback_repository.search_entries(owner_id, params).await;
+        match entries {
+            Ok(entries) => Ok(FeedbackEntryResponse::from_vec(entries)),
+            Err(e) => {
+                let error_message = format!("Error searching feedback: {}", e);
+                error!(error_message);
+                Err(ErrorResponse::from(error_message))
+            }
+        }
+    }
+
+    pub(crate) async fn update_entry(
+        &self,
+        owner_id: i64,
+        id: i64,
+        update_request: UpdateFeedbackRequest,
+    ) -> Result<Option<FeedbackEntryResponse>, ErrorResponse> {
+        let result = self
+            .feedback_repository
+            .update_entry_by_id(owner_id, id, update_request)
+            .await;
+        match result {
+            Ok(entry) => Ok(entry.map(FeedbackEntryResponse::from)),
+            Err(e) => {
+                let error_message = format!("Error updating feedback entry: {:?}", e);
+                error!("{}", error_message);
+                Err(ErrorResponse::from(error_message))
+            }
+        }
+    }
+
+    pub(crate) async fn soft_delete_entry(
+        &self,
+        owner_id: i64,
+        id: i64,
+    ) -> Result<bool, ErrorResponse> {
+        let result = self.feedback_repository.soft_delete_by_id(owner_id, id).await;
+        match result {
+            Ok(deleted) => Ok(deleted),
+            Err(e) => {
+                let error_message = format!("Error deleting feedback entry: {:?}", e);
+                error!("{}", error_message);
+                Err(ErrorResponse::from(error_message))
+            }
+        }
+    }
+
+    /// All feedback for a reportee in the window, grouped for review preparation.
+    /// Returns `None` when the reportee does not exist.
+    pub(crate) async fn summarize(
+        &self,
+        owner_id: i64,
+        params: FeedbackSummaryParams,
+    ) -> Result<Option<FeedbackSummaryResponse>, ErrorResponse> {
+        let reportee = match self.find_reportee(owner_id, params.reportee_id).await? {
+            Some(reportee) => reportee,
+            None => return Ok(None),
+        };
+
+        let search_params = FeedbackSearchParams {
+            reportee_id: Some(params.reportee_id),
+            sentiment: None,
+            tag: None,
+            from: params.from.clone(),
+            to: params.to.clone(),
+        };
+        let entries = self.feedback_repository.search_entries(owner_id, search_params).await;
+        match entries {
+            Ok(entries) => Ok(Some(FeedbackSummaryResponse::from_entries(
+                reportee,
+                params.from.and_then(|date| date.as_day()).map(naive_date_to_bson),
+                params.to.and_then(|date| date.as_day()).map(naive_date_to_bson),
+                entries,
+            ))),
+            Err(e) => {
+                let error_message = format!("Error summarizing feedback: {}", e);
+                error!("{}", error_message);
+                Err(ErrorResponse::from(error_message))
+            }
+        }
+    }
+}
@@ -0,0 +1,399 @@
+use axum::response::ErrorResponse;
+use mindvault_core::models::AppDatabase;
+use mindvault_core::repository::goal_repo::GoalRepository;
+use mindvault_core::repository::task_repo::{TaskRepository, TaskVault};
+use mindvault_core::repository::user_repo::UserRepository;
+use mindvault_shared::dtos::goal_dtos::{
+    CreateGoalRequest, CreateKeyResultRequest, GoalSearchParams, KeyResultCheckInRequest,
+    UpdateGoalRequest, UpdateKeyResultRequest,
+};
+use mindvault_shared::models::goals_model::{
+    EProgressSource, Goal, GoalResponse, KeyResultCheckInResponse,
+};
+use mindvault_shared::models::tasks_model::ETaskStatus;
+use std::collections::HashMap;
+use tracing::error;
+
+pub(crate) struct GoalService {
+    goal_repository: GoalRepository,
+    task_repository: TaskRepository,
+    user_repository: UserRepository,
+}
+
+fn service_error(context: &str, e: impl std::fmt::Debug) -> ErrorResponse {
+    let error_message = format!("{}: {:?}", context, e);
+    error!("{}", error_message);
+    ErrorResponse::from(error_message)
+}
+
+impl GoalService {
+    pub(crate) fn new(app_database: AppDatabase) -> Self {
+        let goal_repository = GoalRepository::new(app_database.clone());
+        let task_repository = TaskRepository::new(app_database.clone());
+        let user_repository = UserRepository::new(app_database);
+        Self {
+            goal_repository,
+            task_repository,
+            user_repository,
+        }
+    }
+
+    /// Replaces the current value of task-driven key results with the live completion ratio
+    async fn apply_linked_task_progress(&self, goal: &mut Goal) -> Result<(), ErrorResponse> {
+        let linked_ids: Vec<i64> = goal
+            .key_results
+            .iter()
+            .filter(|kr| kr.progress_source == EProgressSource::LinkedTasks)
+            .flat_map(|kr| kr.linked_task_ids.iter().copied())
+            .collect();
+        if linked_ids.is_empty() {
+            return Ok(());
+        }
+
+        let tasks = self
+            .task_repository
+            .find_by_ids(TaskVault::User(goal.owner_id), &linked_ids)
+            .await
+            .map_err(|e| service_error("Error loading linked tasks", e))?;
+        let completed_by_id: HashMap<i64, bool> = tasks
+            .iter()
+            .map(|task| (task.id, matches!(task.status, ETaskStatus::Completed)))
+            .collect();
+
+        for key_result in goal
+            .key_results
+            .iter_mut()
+            .filter(|kr| kr.progress_source == EProgressSource::LinkedTasks)
+        {
+            // Tasks deleted since they were linked no longer count towards the total
+            let live: Vec<bool> = key_result
+                .linked_task_ids
+                .iter()
+                .filter_map(|id| completed_by_id.get(id).copied())
+                .collect();
+            let completed = live.iter().filter(|done| **done).count();
+            key_result.current_value = key_result.value_from_task_completion(completed, live.len());
+        }
+        Ok(())
+    }
+
+    async fn to_response(&self, mut goal: Goal) -> Result<GoalResponse, ErrorResponse> {
+        self.apply_linked_task_progress(&mut goal).await?;
+        Ok(GoalResponse::from(goal))
+    }
+
+    pub(crate) async fn create_goal(
+        &self,
+        owner_id: i64,
+        new_goal: CreateGoalRequest,
+    ) -> Result<GoalResponse, ErrorResponse> {
+        let goal = self
+            .goal_repository
+            .create_goal(owner_id, new_goal)
+            .await
+            .map_err(|e| service_error("Error creating goal", e))?;
+        Ok(GoalResponse::from(goal))
+    }
+
+    pub(crate) async fn search_goals(
+        &self,
+        owner_id: i64,
+        params: GoalSearchParams,
+    ) -> Result<Vec<GoalResponse>, ErrorResponse> {
+        let goals = self
+            .goal_repository
+            .search_goals(owner_id, params)
+            .await
+            .map_err(|e| service_error("Error searching goals", e))?;
+        let mut responses = Vec::with_capacity(goals.len());
+        for goal in goals {
+            responses.push(self.to_response(goal).await?);
+        }
+        Ok(responses)
+    }
+
+    pub(crate) async fn get_by_id(
+        &self,
+        owner_id: i64,
+        id: i64,
+    ) -> Result<Option<GoalResponse>, ErrorResponse> {
+        let goal = self
+            .goal_repository
+            .find_by_id(owner_id, id)
+            .await
+            .map_err(|e| service_error("Error finding goal", e))?;
+        match goal {
+            Some(goal) => Ok(Some(self.to_response(goal).await?)),
+            None => Ok(None),
+        }
+    }
+
+    pub(crate) async fn update_goal(
+        &self,
+        owner_id: i64,
+        id: i64,
+        update_request: UpdateGoalRequest,
+    ) -> Result<Option<GoalResponse>, ErrorResponse> {
+        let goal = self
+            .goal_repository
+            .update_goal_by_id(owner_id, id, update_request)
+            .await
+            .map_err(|e| service_error("Error updating goal", e))?;
+        match goal {
+            Some(goal) => Ok(Some(self.to_response(goal).await?)),
+            None => Ok(None),
+        }
+    }
+
+    pub(crate) async fn soft_delete_goal(
+        &self,
+        owner_id: i64,
+        id: i64,
+    ) -> Result<bool, ErrorResponse> {
+        self.goal_repository
+            .soft_delete_by_id(owner_id, id)
+            .await
+            .map_err(|e| service_error("Error deleting goal", e))
+    }
+
+    pub(crate) async fn add_key_result(
+        &self,
+        owner_id: i64,
+        goal_id: i64,
+        request: CreateKeyResultRequest,
+    ) -> Result<Option<GoalResponse>, ErrorResponse> {
+        let goal = self
+            .goal_repository
+            .add_key_result(owner_id, goal_id, request)
+            .await
+            .map_err(|e| service_error("Error adding key result", e))?;
+        match goal {
+            Some(goal) => Ok(Some(self.to_response(goal).await?)),
+            None => Ok(None),
+        }
+    }
+
+    pub(crate) async fn update_key_result(
+        &self,
+        owner_id: i64,
+        goal_id: i64,
+        key_result_id: i64,
+        update_request: UpdateKeyResultRequest,
+    ) -> Result<Option<GoalResponse>, ErrorResponse> {
+        let goal = self
+            .goal_repository
+            .update_key_result_by_id(owner_id, goal_id, key_result_id, update_request)
+            .await
+            .map_err(|e| service_error("Error updating key result", e))?;
+        match goal {
+            Some(goal) => Ok(Some(self.to_response(goal).await?)),
+            None => Ok(None),
+        }
+    }
+
+    pub(crate) async fn remove_key_result(
+        &self,
+        owner_id: i64,
+        goal_id: i64,
+        key_result_id: i64,
+    ) -> Result<Option<GoalResponse>, ErrorResponse> {
+        let goal = self
+            .goal_repository
+            .remove_key_result(owner_id, goal_id, key_result_id)
+            .await
+            .map_err(|e| service_error("Error removing key result", e))?;
+        match goal {
+            Some(goal) => Ok(Some(self.to_response(goal).await?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Ids among `task_ids` that do not refer to an existing task
+    pub(crate) async fn find_missing_tasks(
+        &self,
+        owner_id: i64,
+        task_ids: &[i64],
+    ) -> Result<Vec<i64>, ErrorResponse> {
+        let tasks = self
+            .task_repository
+            .find_by_ids(TaskVault::User(owner_id), task_ids)
+            .await
+            .map_err(|e| service_error("Error loading tasks", e))?;
+        Ok(task_ids
+            .iter()
+            .copied()
+            .filter(|id| !tasks.iter().any(|task| task.id == *id))
+            .collect())
+    }
+
+    pub(crate) async fn link_tasks(
+        &self,
+        owner_id: i64,
+        goal_id: i64,
+        key_result_id: i64,
+        task_ids: &[i64],
+    ) -> Result<Option<GoalResponse>, ErrorResponse> {
+        let linked = self
+            .goal_repository
+            .link_tasks(owner_id, goal_id, key_result_id, task_ids)
+            .await
+            .map_err(|e| service_error("Error linking tasks", e))?;
+        if linked.is_none() {
+            return Ok(None);
+        }
+        self.sync_key_result(owner_id, goal_id, key_result_id).await
+    }
+
+    pub(crate) async fn unlink_task(
+        &self,
+        owner_id: i64,
+        goal_id: i64,
+        key_result_id: i64,
+        task_id: i64,
+    ) -> Result<Option<GoalResponse>, ErrorResponse> {
+        let unlinked = self
+            .goal_repository
+            .unlink_task(owner_id, goal_id, key_result_id, task_id)
+            .await
+            .map_err(|e| service_error("Error unlinking task", e))?;
+        if unlinked.is_none() {
+            return Ok(None);
+        }
+        self.sync_key_result(owner_id, goal_id, key_result_id).await
+    }
+
+    /// Persists the live value of a task-driven key result, adding a history entry when it moved
+    pub(crate) async fn sync_key_result(
+        &self,
+        owner_id: i64,
+        goal_id: i64,
+        key_result_id: i64,
+    ) -> Result<Option<GoalResponse>, ErrorResponse> {
+        let stored = match self.goal_repository.find_by_id(owner_id, goal_id).await {
+            Ok(Some(goal)) => goal,
+            Ok(None) => return Ok(None),
+            Err(e) => return Err(service_error("Error finding goal", e)),
+        };
+        let mut live = stored.clone();
+        self.apply_linked_task_progress(&mut live).await?;
+
+        let stored_kr = stored.key_results.iter().find(|kr| kr.id == key_result_id);
+        let live_kr = live.key_results.iter().find(|kr| kr.id == key_result_id);
+        let (stored_kr, live_kr) = match (stored_kr, live_kr) {
+            (Some(stored_kr), Some(live_kr)) => (stored_kr, live_kr),
+            _ => return Ok(None),
+        };
+
+        if live_kr.progress_source == EProgressSource::LinkedTasks
+            && live_kr.current_value != stored_kr.current_value
+        {
+            self.goal_repository
+                .record_progress(
+                    owner_id,
+                    goal_id,
+                    key_result_id,
+                    live_kr.current_value,
+                    None,
+                    EProgressSource::LinkedTasks,
+                )
+                .await
+                .map_err(|e| service_error("Error recording key result progress", e))?;
+        }
+        Ok(Some(GoalResponse::from(live)))
+    }
+
+    /// Syncs every task-driven key result across the goals of all users that are not disabled;
+    /// returns how many were checked
+    pub(crate) async fn sync_all_linked_key_results(&self) -> Result<usize, ErrorResponse> {
+        let users = self
+            .user_repository
+            .find_active()
+            .await
+            .map_err(|e| service_error("Error loading users", e))?;
+        let mut goals = Vec::new();
+        for user in users {
+            let params = GoalSearchParams {
+                owner_reportee_id: None,
+                period: None,
+            };
+            goals.extend(
+                self.goal_repository
+                    .search_goals(user.id, params)
+                    .await
+                    .map_err(|e| service_error("Error searching goals", e))?,
+            );
+        }
+
+        let mut synced = 0;
+        for goal in goals {
+            for key_result in goal
+                .key_results
+                .iter()
+                .filter(|kr| kr.progress_source == EProgressSource::LinkedTasks)
+            {
+                self.sync_key_result(goal.owner_id, goal.id, key_result.id).await?;
+                synced += 1;
+            }
+        }
+        Ok(synced)
+    }
+
+    /// Current progress source of a key result, or `None` if goal or key result do not exist
+    pub(crate) async fn key_result_source(
+        &self,
+        owner_id: i64,
+        goal_id: i64,
+        key_result_id: i64,
+    ) -> Result<Option<EProgressSource>, ErrorResponse> {
+        let goal = self
+            .goal_repository
+            .find_by_id(owner_id, goal_id)
+            .await
+            .map_err(|e| service_error("Error finding goal", e))?;
+        Ok(goal.and_then(|goal| {
+            goal.key_results
+                .iter()
+                .find(|kr| kr.id == key_result_id)
+                .map(|kr| kr.progress_source)
+        }))
+    }
+
+    pub(crate) async fn check_in(
+        &self,
+        owner_id: i64,
+        goal_id: i64,
+        key_result_id: i64,
+        request: KeyResultCheckInRequest,
+    ) -> Result<Option<GoalResponse>, ErrorResponse> {
+        let goal = self
+            .goal_repository
+            .record_progress(
+                owner_id,
+                goal_id,
+                key_result_id,
+                request.value,
+                request.note,
+                EProgressSource::Manual,
+            )
+            .await
+            .map_err(|e| service_error("Error recording check-in", e))?;
+        match goal {
+            Some(goal) => Ok(Some(self.to_response(goal).await?)),
+            None => Ok(None),
+        }
+    }
+
+    pub(crate) async fn get_history(
+        &self,
+        owner_id: i64,
+        goal_id: i64,
+        key_result_id: i64,
+    ) -> Result<Vec<KeyResultCheckInResponse>, ErrorResponse> {
+        let check_ins = self
+            .goal_repository
+            .find_check_ins(owner_id, goal_id, key_result_id)
+            .await
+            .map_err(|e| service_error("Error finding key result history", e))?;
+        Ok(KeyResultCheckInResponse::from_vec(check_ins))
+    }
+}
@@ -1 +1,20 @@
-pub mod task_service;
\ No newline at end of file
+pub mod task_service;
+pub mod reportee_service;
+pub mod review_service;
+pub mod feedback_service;
+pub mod review_template_service;
+pub mod goal_service;
+pub mod reminder_service;
+pub mod admin_service;
+pub mod notification_service;
+pub mod digest_service;
+pub mod webhook_service;
+pub mod saved_filter_service;
+pub mod event_service;
+pub mod transfer_service;
+pub mod calendar_service;
+pub mod access_token_service;
+pub mod workspace_service;
+pub mod user_service;
+pub mod workflow_service;
+pub mod time_entry_service;
@@ -0,0 +1,301 @@
+use crate::notifications::NotificationDispatcher;
+use axum::response::ErrorResponse;
+use bson::DateTime as BsonDateTime;
+use chrono::Utc;
+use mindvault_core::models::{AppDatabase, DbError};
+use mindvault_core::repository::notification_repo::NotificationRepository;
+use mindvault_core::repository::reminder_repo::ReminderRepository;
+use mindvault_core::repository::task_repo::{TaskRepository, TaskVault};
+use mindvault_core::repository::user_repo::UserRepository;
+use mindvault_shared::dtos::notification_dtos::{
+    CreateChannelRequest, DeliverySearchParams, UpdateChannelRequest,
+};
+use mindvault_shared::models::notifications_model::{
+    ENotificationType, NotificationChannelResponse, NotificationDeliveryResponse,
+    NotificationMessage,
+};
+use std::collections::HashMap;
+use tracing::{error, info};
+
+pub(crate) struct NotificationService {
+    notification_repository: NotificationRepository,
+    reminder_repository: ReminderRepository,
+    task_repository: TaskRepository,
+    user_repository: UserRepository,
+    dispatcher: NotificationDispatcher,
+}
+
+fn service_error(context: &str, e: DbError) -> ErrorResponse {
+    let error_message = format!("{}: {:?}", context, e);
+    error!("{}", error_message);
+    ErrorResponse::from(error_message)
+}
+
+impl NotificationService {
+    pub(crate) fn new(app_database: AppDatabase) -> Self {
+        Self {
+            notification_repository: NotificationRepository::new(app_database.clone()),
+            reminder_repository: ReminderRepository::new(app_database.clone()),
+            task_repository: TaskRepository::new(app_database.clone()),
+            user_repository: UserRepository::new(app_database.clone()),
+            dispatcher: NotificationDispatcher::new(app_database),
+        }
+    }
+
+    pub(crate) fn command_channels_allowed(&self) -> bool {
+        self.dispatcher.config().allow_commands
+    }
+
+    pub(crate) async fn get_channels(
+        &self,
+        owner_id: i64,
+    ) -> Result<Vec<NotificationChannelResponse>, ErrorResponse> {
+        match self.notification_repository.find_channels(owner_id).await {
+            Ok(channels) => Ok(NotificationChannelResponse::from_vec(channels)),
+            Err(e) => Err(service_error("Error fetching notification channels", e)),
+        }
+    }
+
+    pub(crate) async fn get_channel(
+        &self,
+        owner_id: i64,
+        id: i64,
+    ) -> Result<Option<NotificationChannelResponse>, ErrorResponse> {
+        match self.notification_repository.find_channel_by_id(owner_id, id).await {
+            Ok(channel) => Ok(channel.map(NotificationChannelResponse::from)),
+            Err(e) => Err(service_error("Error finding notification channel", e)),
+        }
+    }
+
+    pub(crate) async fn create_channel(
+        &self,
+        owner_id: i64,
+        request: CreateChannelRequest,
+    ) -> Result<NotificationChannelResponse, ErrorResponse> {
+        match self.notification_repository.create_channel(owner_id, request).await {
+            Ok(channel) => Ok(NotificationChannelResponse::from(channel)),
+            Err(e) => Err(service_error("Error creating notification channel", e)),
+        }
+    }
+
+    pub(crate) async fn update_channel(
+        &self,
+        owner_id: i64,
+        id: i64,
+        request: UpdateChannelRequest,
+    ) -> Result<Option<NotificationChannelResponse>, ErrorResponse> {
+        match self
+            .notification_repository
+            .update_channel_by_id(owner_id, id, request)
+            .await
+        {
+            Ok(channel) => Ok(channel.map(NotificationChannelResponse::from)),
+            Err(e) => Err(service_error("Error updating notification channel", e)),
+        }
+    }
+
+    pub(crate) async fn delete_channel(
+        &self,
+        owner_id: i64,
+        id: i64,
+    ) -> Result<bool, ErrorResponse> {
+        match self.notification_repository.soft_delete_channel_by_id(owner_id, id).await {
+            Ok(deleted) => Ok(deleted),
+            Err(e) => Err(service_error("Error deleting notification channel", e)),
+        }
+    }
+
+    /// Sends a test message to one channel right away; `None` if the channel does not exist
+    pub(crate) async fn send_test(
+        &self,
+        owner_id: i64,
+        channel_id: i64,
+    ) -> Result<Option<NotificationDeliveryResponse>, ErrorResponse> {
+        let send = async {
+            let Some(channel) = self
+                .notification_repository
+                .find_channel_by_id(owner_id, channel_id)
+                .await?
+            else {
+                return Ok(None);
+            };
+            let message = NotificationMessage {
+                notification_type: ENotificationType::Test,
+                title: "MindVault test notification".to_string(),
+                body: format!("Channel '{}' is set up correctly.", channel.name),
+                entity_type: None,
+                entity_id: None,
+            };
+            let Some(delivery) = self
+                .notification_repository
+                .enqueue_delivery(channel.id, message, None)
+                .await?
+            else {
+                return Ok(None);
+            };
+            self.dispatcher.deliver(delivery).await
+        };
+
+        match send.await {
+            Ok(delivery) => Ok(delivery.map(NotificationDeliveryResponse::from)),
+            Err(e) => Err(service_error("Error sending test notification", e)),
+        }
+    }
+
+    pub(crate) async fn search_deliveries(
+        &self,
+        owner_id: i64,
+        params: DeliverySearchParams,
+    ) -> Result<Vec<NotificationDeliveryResponse>, ErrorResponse> {
+        match self.notification_repository.search_deliveries(owner_id, params).await {
+            Ok(deliveries) => Ok(NotificationDeliveryResponse::from_vec(deliveries)),
+            Err(e) => Err(service_error("Error searching notification deliveries", e)),
+        }
+    }
+
+    pub(crate) async fn get_delivery(
+        &self,
+        owner_id: i64,
+        id: i64,
+    ) -> Result<Option<NotificationDeliveryResponse>, ErrorResponse> {
+        match self.notification_repository.find_delivery_by_id(owner_id, id).await {
+            Ok(delivery) => Ok(delivery.map(NotificationDeliveryResponse::from)),
+            Err(e) => Err(service_error("Error finding notification delivery", e)),
+        }
+    }
+
+    /// Requeues an undelivered (typically dead-lettered) delivery and attempts it immediately
+    pub(crate) async fn retry_delivery(
+        &self,
+        owner_id: i64,
+        id: i64,
+    ) -> Result<Option<NotificationDeliveryResponse>, ErrorResponse> {
+        let retry = async {
+            match self.notification_repository.requeue_delivery(owner_id, id).await? {
+                Some(delivery) => self.dispatcher.deliver(delivery).await,
+                None => Ok(None),
+            }
+        };
+
+        match retry.await {
+            Ok(delivery) => Ok(delivery.map(NotificationDeliveryResponse::from)),
+            Err(e) => Err(service_error("Error retrying notification delivery", e)),
+        }
+    }
+
+    pub(crate) async fn deliver_pending(&self) -> Result<usize, ErrorResponse> {
+        self.dispatcher
+            .deliver_pending()
+            .await
+            .map_err(|e| service_error("Error delivering notifications", e))
+    }
+
+    /// Notifies owners about their reminders that are due and marks them fired. Reminders are
+    /// left pending when none of the owner's channels listens for them, so clients polling
+    /// `/reminders/due` still see them.
+    pub(crate) async fn notify_due_reminders(&self) -> Result<usize, ErrorResponse> {
+        let notify = async {
+            let reminders = self
+                .reminder_repository
+                .find_due(None, BsonDateTime::now())
+                .await?;
+            let mut listening = HashMap::new();
+            let mut notified = 0;
+            for reminder in reminders {
+                let owner_id = reminder.owner_id;
+                let listens = match listening.get(&owner_id) {
+                    Some(&listens) => listens,
+                    None => {
+                        let listens = !self
+                            .notification_repository
+                            .find_channels_for_type(ENotificationType::ReminderDue, &[owner_id])
+                            .await?
+                            .is_empty();
+                        listening.insert(owner_id, listens);
+                        listens
+                    }
+                };
+                if !listens {
+                    continue;
+                }
+                let dedupe_key = format!(
+                    "ReminderDue:{}:{}",
+                    reminder.id,
+                    reminder.fire_at.map(|at| at.timestamp_millis()).unwrap_or_default()
+                );
+                let message = NotificationMessage {
+                    notification_type: ENotificationType::ReminderDue,
+                    title: reminder.title.clone(),
+                    body: reminder
+                        .note
+                        .clone()
+                        .unwrap_or_else(|| format!("Reminder: {}", reminder.title)),
+                    entity_type: Some("reminder".to_string()),
+                    entity_id: Some(reminder.id),
+                };
+                self.dispatcher.notify(&[owner_id], message, Some(dedupe_key)).await?;
+                self.reminder_repository.mark_fired(reminder.id).await?;
+                notified += 1;
+            }
+            Ok::<usize, DbError>(notified)
+        };
+
+        match notify.await {
+            Ok(notified) => {
+                if notified > 0 {
+                    info!("Queued notifications for {} due reminders", notified);
+                }
+                Ok(notified)
+            }
+            Err(e) => Err(service_error("Error notifying due reminders", e)),
+        }
+    }
+
+    /// Queues one overdue alert per task per day of the user's calendar to the user's own
+    /// channels, for every user that is not disabled. Tasks due on a day become overdue once
+    /// that day has ended for the user.
+    pub(crate) async fn notify_overdue_tasks(&self) -> Result<usize, ErrorResponse> {
+        let notify = async {
+            let now = Utc::now();
+            let mut tasks = Vec::new();
+            for user in self.user_repository.find_active().await? {
+                let tz = user.date_preferences().tz();
+                let today = now.with_timezone(&tz).format("%Y-%m-%d").to_string();
+                let overdue = self
+                    .task_repository
+                    .find_overdue(TaskVault::User(user.id), now, tz)
+                    .await?;
+                tasks.extend(overdue.into_iter().map(|task| (task, tz, today.clone())));
+            }
+            let mut queued = 0;
+            for (task, tz, today) in tasks {
+                let due = match (task.due_date, task.due_day(tz)) {
+                    (Some(due_at), Some(_)) if task.due_has_time => due_at
+                        .to_chrono()
+                        .with_timezone(&tz)
+                        .format("%d/%m/%y %H:%M")
+                        .to_string(),
+                    (_, Some(due_day)) => due_day.format("%d/%m/%y").to_string(),
+                    _ => String::new(),
+                };
+                let message = NotificationMessage {
+                    notification_type: ENotificationType::TaskOverdue,
+                    title: format!("Overdue: {}", task.name),
+                    body: format!("Task #{} '{}' was due on {}.", task.id, task.name, due),
+                    entity_type: Some("task".to_string()),
+                    entity_id: Some(task.id),
+                };
+                let dedupe_key = format!("TaskOverdue:{}:{}:{}", task.owner_id, task.id, today);
+                queued += self
+                    .dispatcher
+                    .notify(&task.audience(None), message, Some(dedupe_key))
+                    .await?;
+            }
+            Ok::<usize, DbError>(queued)
+        };
+
+        notify
+            .await
+            .map_err(|e| service_error("Error notifying overdue tasks", e))
+    }
+}
@@ -0,0 +1,254 @@
+use axum::response::ErrorResponse;
+use bson::DateTime as BsonDateTime;
+use mindvault_core::models::AppDatabase;
+use mindvault_core::repository::reminder_repo::{NewReminder, ReminderChanges, ReminderRepository};
+use mindvault_core::repository::task_repo::{TaskRepository, TaskVault};
+use mindvault_shared::dtos::reminder_dtos::ReminderSearchParams;
+use mindvault_shared::models::reminders_model::{Reminder, ReminderResponse};
+use std::collections::HashMap;
+use tracing::{error, info};
+
+pub(crate) struct ReminderService {
+    reminder_repository: ReminderRepository,
+    task_repository: TaskRepository,
+}
+
+impl ReminderService {
+    pub(crate) fn new(app_database: AppDatabase) -> Self {
+        let reminder_repository = ReminderRepository::new(app_database.clone());
+        let task_repository = TaskRepository::new(app_database.clone());
+        Self {
+            reminder_repository,
+            task_repository,
+        }
+    }
+
+    /// Due date of a task; the outer `None` means the task does not exist
+    pub(crate) async fn task_due_date(
+        &self,
+        owner_id: i64,
+        task_id: i64,
+    ) -> Result<Option<Option<BsonDateTime>>, ErrorResponse> {
+        match self.task_repository.find_by_id(TaskVault::User(owner_id), task_id).await {
+            Ok(task) => Ok(task.map(|task| task.due_date)),
+            Err(e) => {
+                let error_message = format!("Error finding task: {}", e);
+                error!("{}", error_message);
+                Err(ErrorResponse::from(error_message))
+            }
+        }
+    }
+
+    pub(crate) async fn find_reminder(
+        &self,
+        owner_id: i64,
+        id: i64,
+    ) -> Result<Option<Reminder>, ErrorResponse> {
+        match self.reminder_repository.find_by_id(owner_id, id).await {
+            Ok(reminder) => Ok(reminder),
+            Err(e) => {
+                let error_message = format!("Error finding reminder: {}", e);
+                error!("{}", error_message);
+                Err(ErrorResponse::from(error_message))
+            }
+        }
+    }
+
+    pub(crate) async fn create_reminder(
+        &self,
+        owner_id: i64,
+        new_reminder: NewReminder,
+    ) -> Result<ReminderResponse, ErrorResponse> {
+        let created_reminder = self
+            .reminder_repository
+            .create_reminder(owner_id, new_reminder)
+            .await;
+        match created_reminder {
+            Ok(reminder) => Ok(ReminderResponse::from(reminder)),
+            Err(e) => {
+                let error_message = format!("Error creating reminder: {:?}", e);
+                error!("{}", error_message);
+                Err(ErrorResponse::from(error_message))
+            }
+        }
+    }
+
+    pub(crate) async fn search_reminders(
+        &self,
+        owner_id: i64,
+        params: ReminderSearchParams,
+    ) -> Result<Vec<ReminderResponse>, ErrorResponse> {
+        let reminders = self.reminder_repository.search_reminders(owner_id, params).await;
+        match reminders {
+            Ok(reminders) => Ok(ReminderResponse::from_vec(reminders)),
+            Err(e) => {
+                let error_message = format!("Error searching reminders: {}", e);
+                error!(error_message);
+                Err(ErrorResponse::from(error_message))
+            }
+        }
+    }
+
+    pub(crate) async fn update_reminder(
+        &self,
+        owner_id: i64,
+        id: i64,
+        changes: ReminderChanges,
+    ) -> Result<Option<ReminderResponse>, ErrorResponse> {
+        let result = self
+            .reminder_repository
+            .update_reminder_by_id(owner_id, id, changes)
+            .await;
+        match result {
+            Ok(reminder) => Ok(reminder.map(ReminderResponse::from)),
+            Err(e) => {
+                let error_message = format!("Error updating reminder: {:?}", e);
+                error!("{}", error_message);
+                Err(ErrorResponse::from(error_message))
+            }
+        }
+    }
+
+    /// Re-derives the fire time of task-relative reminders from the tasks' current due dates,
+    /// so moving a due date also moves its reminders
+    pub(crate) async fn refresh_task_relative(&self) -> Result<usize, ErrorResponse> {
+        let refresh = async {
+            let reminders = self.reminder_repository.find_pending_task_relative().await?;
+            // Task ids are per owner, so look the tasks up in each owner's vault
+            let mut task_ids: HashMap<i64, Vec<i64>> = HashMap::new();
+            for reminder in &reminders {
+                if let Some(task_id) = reminder.task_id {
+                    task_ids.entry(reminder.owner_id).or_default().push(task_id);
+                }
+            }
+            if task_ids.is_empty() {
+                return Ok(0);
+            }
+            let mut due_dates: HashMap<(i64, i64), Option<BsonDateTime>> = HashMap::new();
+            for (owner_id, ids) in task_ids {
+                let vault = TaskVault::User(owner_id);
+                for task in self.task_repository.find_by_ids(vault, &ids).await? {
+                    due_dates.insert((owner_id, task.id), task.due_date);
+                }
+            }
+
+            let mut refreshed = 0;
+            for reminder in reminders {
+                let due_date = reminder.task_id.and_then(|task_id| {
+                    due_dates
+                        .get(&(reminder.owner_id, task_id))
+                        .copied()
+                        .flatten()
+                });
+                let fire_at = reminder.trigger.fire_at(due_date);
+                if fire_at != reminder.fire_at {
+                    self.reminder_repository
+                        .set_fire_at(reminder.id, fire_at)
+                        .await?;
+                    refreshed += 1;
+                }
+            }
+            Ok::<usize, mindvault_core::models::DbError>(refreshed)
+        };
+
+        match refresh.await {
+            Ok(refreshed) => {
+                if refreshed > 0 {
+                    info!("Refreshed fire time of {} task reminders", refreshed);
+                }
+                Ok(refreshed)
+            }
+            Err(e) => {
+                let error_message = format!("Error refreshing task reminders: {:?}", e);
+                error!("{}", error_message);
+                Err(ErrorResponse::from(error_message))
+            }
+        }
+    }
+
+    /// Pending reminders that should have fired by `until`
+    pub(crate) async fn get_due_reminders(
+        &self,
+        owner_id: i64,
+        until: BsonDateTime,
+    ) -> Result<Vec<ReminderResponse>, ErrorResponse> {
+        self.refresh_task_relative().await?;
+        let reminders = self.reminder_repository.find_due(Some(owner_id), until).await;
+        match reminders {
+            Ok(reminders) => Ok(ReminderResponse::from_vec(reminders)),
+            Err(e) => {
+                let error_message = format!("Error finding due reminders: {}", e);
+                error!("{}", error_message);
+                Err(ErrorResponse::from(error_message))
+            }
+        }
+    }
+
+    pub(crate) async fn mark_fired(
+        &self,
+        owner_id: i64,
+        id: i64,
+    ) -> Result<Option<ReminderResponse>, ErrorResponse> {
+        let fire = async {
+            if self.reminder_repository.find_by_id(owner_id, id).await?.is_none() {
+                return Ok(None);
+            }
+            self.reminder_repository.mark_fired(id).await
+        };
+        match fire.await {
+            Ok(reminder) => Ok(reminder.map(ReminderResponse::from)),
+            Err(e) => {
+                let error_message = format!("Error firing reminder: {:?}", e);
+                error!("{}", error_message);
+                Err(ErrorResponse::from(error_message))
+            }
+        }
+    }
+
+    pub(crate) async fn dismiss(
+        &self,
+        owner_id: i64,
+        id: i64,
+    ) -> Result<Option<ReminderResponse>, ErrorResponse> {
+        match self.reminder_repository.dismiss(owner_id, id).await {
+            Ok(reminder) => Ok(reminder.map(ReminderResponse::from)),
+            Err(e) => {
+                let error_message = format!("Error dismissing reminder: {:?}", e);
+                error!("{}", error_message);
+                Err(ErrorResponse::from(error_message))
+            }
+        }
+    }
+
+    pub(crate) async fn snooze(
+        &self,
+        owner_id: i64,
+        id: i64,
+        until: BsonDateTime,
+    ) -> Result<Option<ReminderResponse>, ErrorResponse> {
+        match self.reminder_repository.snooze(owner_id, id, until).await {
+            Ok(reminder) => Ok(reminder.map(ReminderResponse::from)),
+            Err(e) => {
+                let error_message = format!("Error snoozing reminder: {:?}", e);
+                error!("{}", error_message);
+                Err(ErrorResponse::from(error_message))
+            }
+        }
+    }
+
+    pub(crate) async fn soft_delete_reminder(
+        &self,
+        owner_id: i64,
+        id: i64,
+    ) -> Result<bool, ErrorResponse> {
+        let result = self.reminder_repository.soft_delete_by_id(owner_id, id).await;
+        match result {
+            Ok(deleted) => Ok(deleted),
+            Err(e) => {
+                let error_message = format!("Error deleting reminder: {:?}", e);
+                error!("{}", error_message);
+                Err(ErrorResponse::from(error_message))
+            }
+        }
+    }
+}
@@ -0,0 +1,141 @@
+use axum::response::ErrorResponse;
+use mindvault_core::models::AppDatabase;
+use mindvault_core::repository::reportee_repo::ReporteeRepository;
+use mindvault_core::repository::review_repo::ReviewRepository;
+use mindvault_shared::dtos::reportee_dtos::{CreateReporteeRequest, UpdateReporteeRequest};
+use mindvault_shared::dtos::review_dtos::ReviewSearchParams;
+use mindvault_shared::models::reportees_model::ReporteeResponse;
+use mindvault_shared::models::reviews_model::{ReporteeTimelineResponse, ReviewResponse};
+use tracing::error;
+
+pub(crate) struct ReporteeService {
+    reportee_repository: ReporteeRepository,
+    review_repository: ReviewRepository,
+}
+
+impl ReporteeService {
+    pub(crate) fn new(app_database: AppDatabase) -> Self {
+        let reportee_repository = ReporteeRepository::new(app_database.clone());
+        let review_repository = ReviewRepository::new(app_database.clone());
+        Self {
+            reportee_repository,
+            review_repository,
+        }
+    }
+
+    pub(crate) async fn create_reportee(
+        &self,
+        owner_id: i64,
+        new_reportee: CreateReporteeRequest,
+    ) -> Result<ReporteeResponse, ErrorResponse> {
+        let created_reportee = self
+            .reportee_repository
+            .create_reportee(owner_id, new_reportee)
+            .await;
+        match created_reportee {
+            Ok(reportee) => Ok(ReporteeResponse::from(reportee)),
+            Err(e) => {
+                let error_message = format!("Error creating reportee: {:?}", e);
+                error!("{}", error_message);
+                Err(ErrorResponse::from(error_message))
+            }
+        }
+    }
+
+    pub(crate) async fn get_all_reportees(
+        &self,
+        owner_id: i64,
+    ) -> Result<Vec<ReporteeResponse>, ErrorResponse> {
+        let reportees = self.reportee_repository.find_all(owner_id).await;
+        match reportees {
+            Ok(reportees) => Ok(ReporteeResponse::from_vec(reportees)),
+            Err(e) => {
+                let error_message = format!("Error finding reportees: {}", e);
+                error!(error_message);
+                Err(ErrorResponse::from(error_message))
+            }
+        }
+    }
+
+    pub(crate) async fn get_by_id(
+        &self,
+        owner_id: i64,
+        reportee_id: i64,
+    ) -> Result<Option<ReporteeResponse>, ErrorResponse> {
+        let reportee = self.reportee_repository.find_by_id(owner_id, reportee_id).await;
+        match reportee {
+            Ok(reportee) => Ok(reportee.map(ReporteeResponse::from)),
+            Err(e) => {
+                let error_message = format!("Error finding reportee: {}", e);
+                error!("{}", error_message);
+                Err(ErrorResponse::from(error_message))
+            }
+        }
+    }
+
+    pub(crate) async fn update_reportee(
+        &self,
+        owner_id: i64,
+        id: i64,
+        update_request: UpdateReporteeRequest,
+    ) -> Result<Option<ReporteeResponse>, ErrorResponse> {
+        let result = self
+            .reportee_repository
+            .update_reportee_by_id(owner_id, id, update_request)
+            .await;
+        match result {
+            Ok(reportee) => Ok(reportee.map(ReporteeResponse::from)),
+            Err(e) => {
+                let error_message = format!("Error updating reportee: {:?}", e);
+                error!("{}", error_message);
+                Err(ErrorResponse::from(error_message))
+            }
+        }
+    }
+
+    pub(crate) async fn soft_delete_reportee(
+        &self,
+        owner_id: i64,
+        id: i64,
+    ) -> Result<bool, ErrorResponse> {
+        let result = self.reportee_repository.soft_delete_by_id(owner_id, id).await;
+        match result {
+            Ok(deleted) => Ok(deleted),
+            Err(e) => {
+                let error_message = format!("Error deleting reportee: {:?}", e);
+                error!("{}", error_message);
+                Err(ErrorResponse::from(error_message))
+            }
+        }
+    }
+
+    /// Reportee with all of their non-deleted reviews, ordered by review date
+    pub(crate) async fn get_timeline(
+        &self,
+        owner_id: i64,
+        reportee_id: i64,
+    ) -> Result<Option<ReporteeTimelineResponse>, ErrorResponse> {
+        let reportee = match self.get_by_id(owner_id, reportee_id).await? {
+            Some(reportee) => reportee,
+            None => return Ok(None),
+        };
+
+        let params = ReviewSearchParams {
+            reportee_id: Some(reportee_id),
+            cycle: None,
+            state: None,
+        };
+        let reviews = self.review_repository.search_reviews(owner_id, params).await;
+        match reviews {
+            Ok(reviews) => Ok(Some(ReporteeTimelineResponse {
+                reportee,
+                reviews: ReviewResponse::from_vec(reviews),
+            })),
+            Err(e) => {
+                let error_message = format!("Error building reportee timeline: {}", e);
+                error!("{}", error_message);
+                Err(ErrorResponse::from(error_message))
+            }
+        }
+    }
+}
@@ -0,0 +1,203 @@
+use axum::response::ErrorResponse;
+use mindvault_core::models::AppDatabase;
+use mindvault_core::repository::reportee_repo::ReporteeRepository;
+use mindvault_core::repository::review_repo::ReviewRepository;
+use mindvault_core::repository::review_template_repo::ReviewTemplateRepository;
+use mindvault_shared::dtos::review_dtos::{
+    CreateReviewRequest, ReviewSearchParams, UpdateReviewRequest,
+};
+use mindvault_shared::models::review_templates_model::{ReviewTemplate, ReviewTemplateRef};
+use mindvault_shared::models::reviews_model::{EReviewState, Review, ReviewResponse};
+use tracing::error;
+
+pub(crate) struct ReviewService {
+    review_repository: ReviewRepository,
+    reportee_repository: ReporteeRepository,
+    template_repository: ReviewTemplateRepository,
+}
+
+impl ReviewService {
+    pub(crate) fn new(app_database: AppDatabase) -> Self {
+        let review_repository = ReviewRepository::new(app_database.clone());
+        let reportee_repository = ReporteeRepository::new(app_database.clone());
+        let template_repository = ReviewTemplateRepository::new(app_database.clone());
+        Self {
+            review_repository,
+            reportee_repository,
+            template_repository,
+        }
+    }
+
+    pub(crate) async fn reportee_exists(
+        &self,
+        owner_id: i64,
+        reportee_id: i64,
+    ) -> Result<bool, ErrorResponse> {
+        match self.reportee_repository.find_by_id(owner_id, reportee_id).await {
+            Ok(reportee) => Ok(reportee.is_some()),
+            Err(e) => {
+                let error_message = format!("Error finding reportee: {}", e);
+                error!("{}", error_message);
+                Err(ErrorResponse::from(error_message))
+            }
+        }
+    }
+
+    /// Latest version of a template, used when a new review instantiates it
+    pub(crate) async fn find_latest_template(
+        &self,
+        owner_id: i64,
+        template_id: i64,
+    ) -> Result<Option<ReviewTemplate>, ErrorResponse> {
+        match self.template_repository.find_latest(owner_id, template_id).await {
+            Ok(template) => Ok(template),
+            Err(e) => {
+                let error_message = format!("Error finding review template: {}", e);
+                error!("{}", error_message);
+                Err(ErrorResponse::from(error_message))
+            }
+        }
+    }
+
+    /// A review together with the exact template version it was created from
+    pub(crate) async fn find_review_with_template(
+        &self,
+        owner_id: i64,
+        review_id: i64,
+    ) -> Result<Option<(Review, Option<ReviewTemplate>)>, ErrorResponse> {
+        let review = match self.review_repository.find_by_id(owner_id, review_id).await {
+            Ok(Some(review)) => review,
+            Ok(None) => return Ok(None),
+            Err(e) => {
+                let error_message = format!("Error finding review: {}", e);
+                error!("{}", error_message);
+                return Err(ErrorResponse::from(error_message));
+            }
+        };
+
+        let template = match review.template {
+            Some(template_ref) => self
+                .template_repository
+                .find_version(owner_id, template_ref.template_id, template_ref.version)
+                .await
+                .map_err(|e| {
+                    let error_message = format!("Error finding review template: {}", e);
+                    error!("{}", error_message);
+                    ErrorResponse::from(error_message)
+                })?,
+            None => None,
+        };
+        Ok(Some((review, template)))
+    }
+
+    pub(crate) async fn create_review(
+        &self,
+        owner_id: i64,
+        new_review: CreateReviewRequest,
+        template: Option<ReviewTemplateRef>,
+    ) -> Result<ReviewResponse, ErrorResponse> {
+        let created_review = self
+            .review_repository
+            .create_review(owner_id, new_review, template)
+            .await;
+        match created_review {
+            Ok(review) => Ok(ReviewResponse::from(review)),
+            Err(e) => {
+                let error_message = format!("Error creating review: {:?}", e);
+                error!("{}", error_message);
+                Err(ErrorResponse::from(error_message))
+            }
+        }
+    }
+
+    pub(crate) async fn get_by_id(
+        &self,
+        owner_id: i64,
+        review_id: i64,
+    ) -> Result<ReviewResponse, ErrorResponse> {
+        let review = self.review_repository.find_by_id(owner_id, review_id).await;
+        match review {
+            Ok(Some(review)) => Ok(ReviewResponse::from(review)),
+            Ok(None) => {
+                let error_message = format!("Review with id {} not found", review_id);
+                error!("{}", error_message);
+                Err(ErrorResponse::from(error_message))
+            }
+            Err(e) => {
+                let error_message = format!("Error finding review: {}", e);
+                error!("{}", error_message);
+                Err(ErrorResponse::from(error_message))
+            }
+        }
+    }
+
+    pub(crate) async fn search_reviews(
+        &self,
+        owner_id: i64,
+        params: ReviewSearchParams,
+    ) -> Result<Vec<ReviewResponse>, ErrorResponse> {
+        let reviews = self.review_repository.search_reviews(owner_id, params).await;
+        match reviews {
+            Ok(reviews) => Ok(ReviewResponse::from_vec(reviews)),
+            Err(e) => {
+                let error_message = format!("Error searching reviews: {}", e);
+                error!(error_message);
+                Err(ErrorResponse::from(error_message))
+            }
+        }
+    }
+
+    pub(crate) async fn update_review(
+        &self,
+        owner_id: i64,
+        id: i64,
+        update_request: UpdateReviewRequest,
+    ) -> Result<Option<ReviewResponse>, ErrorResponse> {
+        let result = self
+            .review_repository
+            .update_draft_by_id(owner_id, id, update_request)
+            .await;
+        match result {
+            Ok(review) => Ok(review.map(ReviewResponse::from)),
+            Err(e) => {
+                let error_message = format!("Error updating review: {:?}", e);
+                error!("{}", error_message);
+                Err(ErrorResponse::from(error_message))
+            }
+        }
+    }
+
+    pub(crate) async fn transition_review(
+        &self,
+        owner_id: i64,
+        id: i64,
+        from: EReviewState,
+        to: EReviewState,
+    ) -> Result<Option<ReviewResponse>, ErrorResponse> {
+        let result = self.review_repository.transition_state(owner_id, id, from, to).await;
+        match result {
+            Ok(review) => Ok(review.map(ReviewResponse::from)),
+            Err(e) => {
+                let error_message = format!("Error changing review state: {:?}", e);
+                error!("{}", error_message);
+                Err(ErrorResponse::from(error_message))
+            }
+        }
+    }
+
+    pub(crate) async fn soft_delete_review(
+        &self,
+        owner_id: i64,
+        id: i64,
+    ) -> Result<bool, ErrorResponse> {
+        let result = self.review_repository.soft_delete_by_id(owner_id, id).await;
+        match result {
+            Ok(deleted) => Ok(deleted),
+            Err(e) => {
+                let error_message = format!("Error deleting review: {:?}", e);
+                error!("{}", error_message);
+                Err(ErrorResponse::from(error_message))
+            }
+        }
+    }
+}
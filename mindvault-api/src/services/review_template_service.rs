@@ -0,0 +1,141 @@
+use axum::response::ErrorResponse;
+use mindvault_core::models::AppDatabase;
+use mindvault_core::repository::review_template_repo::ReviewTemplateRepository;
+use mindvault_shared::dtos::review_template_dtos::SaveReviewTemplateRequest;
+use mindvault_shared::models::review_templates_model::ReviewTemplateResponse;
+use tracing::error;
+
+pub(crate) struct ReviewTemplateService {
+    template_repository: ReviewTemplateRepository,
+}
+
+impl ReviewTemplateService {
+    pub(crate) fn new(app_database: AppDatabase) -> Self {
+        let template_repository = ReviewTemplateRepository::new(app_database.clone());
+        Self {
+            template_repository,
+        }
+    }
+
+    pub(crate) async fn create_template(
+        &self,
+        owner_id: i64,
+        request: SaveReviewTemplateRequest,
+    ) -> Result<ReviewTemplateResponse, ErrorResponse> {
+        let created_template = self.template_repository.create_template(owner_id, request).await;
+        match created_template {
+            Ok(template) => Ok(ReviewTemplateResponse::from(template)),
+            Err(e) => {
+                let error_message = format!("Error creating review template: {:?}", e);
+                error!("{}", error_message);
+                Err(ErrorResponse::from(error_message))
+            }
+        }
+    }
+
+    pub(crate) async fn create_version(
+        &self,
+        owner_id: i64,
+        template_id: i64,
+        request: SaveReviewTemplateRequest,
+    ) -> Result<Option<ReviewTemplateResponse>, ErrorResponse> {
+        let result = self
+            .template_repository
+            .create_version(owner_id, template_id, request)
+            .await;
+        match result {
+            Ok(template) => Ok(template.map(ReviewTemplateResponse::from)),
+            Err(e) => {
+                let error_message = format!("Error publishing review template version: {:?}", e);
+                error!("{}", error_message);
+                Err(ErrorResponse::from(error_message))
+            }
+        }
+    }
+
+    pub(crate) async fn get_all_templates(
+        &self,
+        owner_id: i64,
+    ) -> Result<Vec<ReviewTemplateResponse>, ErrorResponse> {
+        let templates = self.template_repository.find_all_latest(owner_id).await;
+        match templates {
+            Ok(templates) => Ok(ReviewTemplateResponse::from_vec(templates)),
+            Err(e) => {
+                let error_message = format!("Error finding review templates: {}", e);
+                error!(error_message);
+                Err(ErrorResponse::from(error_message))
+            }
+        }
+    }
+
+    pub(crate) async fn get_latest(
+        &self,
+        owner_id: i64,
+        template_id: i64,
+    ) -> Result<Option<ReviewTemplateResponse>, ErrorResponse> {
+        let template = self.template_repository.find_latest(owner_id, template_id).await;
+        match template {
+            Ok(template) => Ok(template.map(ReviewTemplateResponse::from)),
+            Err(e) => {
+                let error_message = format!("Error finding review template: {}", e);
+                error!("{}", error_message);
+                Err(ErrorResponse::from(error_message))
+            }
+        }
+    }
+
+    pub(crate) async fn get_versions(
+        &self,
+        owner_id: i64,
+        template_id: i64,
+    ) -> Result<Vec<ReviewTemplateResponse>, ErrorResponse> {
+        let templates = self.template_repository.find_versions(owner_id, template_id).await;
+        match templates {
+            Ok(templates) => Ok(ReviewTemplateResponse::from_vec(templates)),
+            Err(e) => {
+                let error_message = format!("Error finding review template versions: {}", e);
+                error!("{}", error_message);
+                Err(ErrorResponse::from(error_message))
+            }
+        }
+    }
+
+    pub(crate) async fn get_version(
+        &self,
+        owner_id: i64,
+        template_id: i64,
+        version: i32,
+    ) -> Result<Option<ReviewTemplateResponse>, ErrorResponse> {
+        let template = self
+            .template_repository
+            .find_version(owner_id, template_id, version)
+            .await;
+        match template {
+            Ok(template) => Ok(template.map(ReviewTemplateResponse::from)),
+            Err(e) => {
+                let error_message = format!("Error finding review template version: {}", e);
+                error!("{}", error_message);
+                Err(ErrorResponse::from(error_message))
+            }
+        }
+    }
+
+    pub(crate) async fn soft_delete_template(
+        &self,
+        owner_id: i64,
+        template_id: i64,
+    ) -> Result<bool, ErrorResponse> {
+        let result = self
+            .template_repository
+            .soft_delete_by_template_id(owner_id, template_id)
+            .await;
+        match result {
+            Ok(deleted) => Ok(deleted),
+            Err(e) => {
+                let error_message = format!("Error deleting review template: {:?}", e);
+                error!("{}", error_message);
+                Err(ErrorResponse::from(error_message))
+            }
+        }
+    }
+}
@@ -0,0 +1,108 @@
+use axum::response::ErrorResponse;
+use mindvault_core::models::{AppDatabase, DbError};
+use mindvault_core::repository::saved_filter_repo::SavedFilterRepository;
+use mindvault_core::repository::task_repo::{TaskRepository, TaskVault};
+use mindvault_shared::dtos::saved_filter_dtos::{CreateSavedFilterRequest, UpdateSavedFilterRequest};
+use mindvault_shared::models::saved_filters_model::{SavedFilter, SavedFilterResponse};
+use mindvault_shared::models::tasks_model::TaskResponse;
+use mindvault_shared::utils::date_input::DatePreferences;
+use tracing::error;
+
+pub(crate) struct SavedFilterService {
+    saved_filter_repository: SavedFilterRepository,
+    task_repository: TaskRepository,
+}
+
+fn service_error(context: &str, e: DbError) -> ErrorResponse {
+    let error_message = format!("{}: {:?}", context, e);
+    error!("{}", error_message);
+    ErrorResponse::from(error_message)
+}
+
+impl SavedFilterService {
+    pub(crate) fn new(app_database: AppDatabase) -> Self {
+        Self {
+            saved_filter_repository: SavedFilterRepository::new(app_database.clone()),
+            task_repository: TaskRepository::new(app_database),
+        }
+    }
+
+    pub(crate) async fn get_filters(
+        &self,
+        owner_id: i64,
+    ) -> Result<Vec<SavedFilterResponse>, ErrorResponse> {
+        match self.saved_filter_repository.find_all(owner_id).await {
+            Ok(filters) => Ok(SavedFilterResponse::from_vec(filters)),
+            Err(e) => Err(service_error("Error fetching saved filters", e)),
+        }
+    }
+
+    pub(crate) async fn find_filter(
+        &self,
+        owner_id: i64,
+        id: i64,
+    ) -> Result<Option<SavedFilter>, ErrorResponse> {
+        match self.saved_filter_repository.find_by_id(owner_id, id).await {
+            Ok(filter) => Ok(filter),
+            Err(e) => Err(service_error("Error finding saved filter", e)),
+        }
+    }
+
+    pub(crate) async fn create_filter(
+        &self,
+        owner_id: i64,
+        request: CreateSavedFilterRequest,
+    ) -> Result<SavedFilterResponse, ErrorResponse> {
+        match self.saved_filter_repository.create_filter(owner_id, request).await {
+            Ok(filter) => Ok(SavedFilterResponse::from(filter)),
+            Err(e) => Err(service_error("Error creating saved filter", e)),
+        }
+    }
+
+    pub(crate) async fn update_filter(
+        &self,
+        owner_id: i64,
+        id: i64,
+        request: UpdateSavedFilterRequest,
+    ) -> Result<Option<SavedFilterResponse>, ErrorResponse> {
+        match self
+            .saved_filter_repository
+            .update_filter_by_id(owner_id, id, request)
+            .await
+        {
+            Ok(filter) => Ok(filter.map(SavedFilterResponse::from)),
+            Err(e) => Err(service_error("Error updating saved filter", e)),
+        }
+    }
+
+    pub(crate) async fn delete_filter(
+        &self,
+        owner_id: i64,
+        id: i64,
+    ) -> Result<bool, ErrorResponse> {
+        match self.saved_filter_repository.soft_delete_by_id(owner_id, id).await {
+            Ok(deleted) => Ok(deleted),
+            Err(e) => Err(service_error("Error deleting saved filter", e)),
+        }
+    }
+
+    /// Tasks currently matching a saved filter; `None` if the filter does not exist
+    pub(crate) async fn get_filter_tasks(
+        &self,
+        owner_id: i64,
+        id: i64,
+        dates: &DatePreferences,
+    ) -> Result<Option<Vec<TaskResponse>>, ErrorResponse> {
+        let Some(filter) = self.find_filter(owner_id, id).await? else {
+            return Ok(None);
+        };
+        let tasks = self
+            .task_repository
+            .search_task(TaskVault::User(owner_id), filter.criteria.into(), dates)
+            .await;
+        match tasks {
+            Ok(tasks) => Ok(Some(TaskResponse::from_vec(tasks))),
+            Err(e) => Err(service_error("Error searching tasks for saved filter", e)),
+        }
+    }
+}
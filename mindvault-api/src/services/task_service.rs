@@ -1,29 +1,246 @@
+use crate::auth::workspace_access::{EAccessDenial, TaskAccess, WorkspaceAuthorizer};
+use crate::events::ChangeFeed;
+use crate::webhooks::{TaskChange, TaskEventPublisher};
 use axum::response::ErrorResponse;
+use chrono::Utc;
 use mindvault_core::models::AppDatabase;
-use mindvault_core::repository::task_repo::TaskRepository;
+use mindvault_core::repository::reportee_repo::ReporteeRepository;
+use mindvault_core::repository::task_repo::{TaskRepository, TaskVault};
+use mindvault_core::repository::workflow_repo::WorkflowRepository;
 use mindvault_shared::dtos::task_dtos::{
-    BulkCreateTaskRequest, CreateTaskRequest, SearchAndUpdateRequest, TaskSearchParams, UpdateTaskRequest,
+    BulkCreateTaskRequest, CreateTaskRequest, MoveTaskRequest, SearchAndUpdateRequest,
+    TaskSearchParams, UpdateTaskRequest,
 };
-use mindvault_shared::models::tasks_model::TaskResponse;
+use mindvault_shared::models::boards_model::{board_column, board_order, BoardResponse};
+use mindvault_shared::models::tasks_model::{ETaskStatus, QuickAddResponse, Task, TaskResponse};
+use mindvault_shared::models::workflows_model::Workflow;
+use mindvault_shared::models::workspaces_model::EWorkspaceRole;
+use mindvault_shared::utils::date_input::{DateInput, DatePreferences};
+use mindvault_shared::utils::quick_add::{parse_quick_add, KnownReportee, QuickAddContext};
+use mindvault_shared::utils::rank::{rank_between, spread_ranks};
+use std::sync::Arc;
 use tracing::error;
 
+/// Longest rank a move may produce before its column is ranked afresh
+const MAX_RANK_LENGTH: usize = 32;
+
+/// Reads `date` with the caller's date order and time zone, rejecting ambiguous dates
+pub(crate) fn resolve_date(
+    date: &mut Option<DateInput>,
+    dates: &DatePreferences,
+) -> Result<(), EAccessDenial> {
+    if let Some(input) = date.take() {
+        *date = Some(input.resolve(dates).map_err(EAccessDenial::Rejected)?);
+    }
+    Ok(())
+}
+
+/// Reads a date-only field like `resolve_date`, keeping just its day in the caller's time zone
+pub(crate) fn resolve_day(
+    date: &mut Option<DateInput>,
+    dates: &DatePreferences,
+) -> Result<(), EAccessDenial> {
+    resolve_date(date, dates)?;
+    *date = date.take().and_then(|date| date.day(dates.tz())).map(DateInput::Day);
+    Ok(())
+}
+
+/// Moves a task request through the vault's workflow, if it has one: fills in the workflow
+/// status and the built-in status of its category, and rejects moves the workflow does not
+/// allow. `task` is the task being updated, `None` for a new one.
+pub(crate) fn apply_workflow(
+    workflow: Option<&Workflow>,
+    task: Option<&Task>,
+    status: &mut Option<ETaskStatus>,
+    workflow_status: &mut Option<String>,
+) -> Result<(), EAccessDenial> {
+    let Some(workflow) = workflow else {
+        return match workflow_status {
+            Some(_) => Err(EAccessDenial::Rejected(
+                "No workflow is set up for these tasks".to_string(),
+            )),
+            None => Ok(()),
+        };
+    };
+    let moved = workflow
+        .move_to(task, status.take(), workflow_status.as_deref())
+        .map_err(EAccessDenial::Rejected)?;
+    if let Some((new_status, new_workflow_status)) = moved {
+        *status = Some(new_status);
+        *workflow_status = Some(new_workflow_status);
+    }
+    Ok(())
+}
+
+/// Bulk updates cannot set a status on tasks that follow a workflow, as each task has to be
+/// checked against the transitions from where it stands
+pub(crate) fn check_bulk_status(
+    workflow: Option<&Workflow>,
+    status: Option<&ETaskStatus>,
+) -> Result<(), EAccessDenial> {
+    match (workflow, status) {
+        (Some(_), Some(_)) => Err(EAccessDenial::Rejected(
+            "These tasks follow a workflow; change their status one task at a time".to_string(),
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Evenly spaced ranks for `column` in its current order; only the tasks whose rank changes
+/// are returned
+fn respread_ranks(column: &[Task]) -> Vec<(i64, String)> {
+    column
+        .iter()
+        .zip(spread_ranks(column.len()))
+        .filter(|(task, rank)| task.rank.as_ref() != Some(rank))
+        .map(|(task, rank)| (task.id, rank))
+        .collect()
+}
+
+/// The tasks of `tasks` given a new rank, as `(before, after)`
+fn reranked(tasks: &[Task], ranks: &[(i64, String)]) -> Vec<(Task, Task)> {
+    ranks
+        .iter()
+        .filter_map(|(id, rank)| {
+            let before = tasks.iter().find(|task| task.id == *id)?;
+            let mut after = before.clone();
+            after.rank = Some(rank.clone());
+            Some((before.clone(), after))
+        })
+        .collect()
+}
+
+/// Where a task dropped between `previous_id` and `next_id` lands in `column`, which does not
+/// hold the task itself
+fn drop_position(
+    column: &[Task],
+    name: &str,
+    previous_id: Option<i64>,
+    next_id: Option<i64>,
+) -> Result<usize, EAccessDenial> {
+    let index_of = |task_id: i64| {
+        column.iter().position(|task| task.id == task_id).ok_or_else(|| {
+            EAccessDenial::Rejected(format!("Task {} is not in column '{}'", task_id, name))
+        })
+    };
+    let position = match (previous_id, next_id) {
+        (Some(previous_id), _) => index_of(previous_id)? + 1,
+        (None, Some(next_id)) => index_of(next_id)?,
+        (None, None) => column.len(),
+    };
+    if let (Some(previous_id), Some(next_id)) = (previous_id, next_id)
+        && column.get(position).map(|task| task.id) != Some(next_id)
+    {
+        index_of(next_id)?;
+        return Err(EAccessDenial::Rejected(format!(
+            "Tasks {} and {} are not next to each other in '{}'",
+            previous_id, next_id, name
+        )));
+    }
+    Ok(position)
+}
+
+/// Every method acts on the caller's private vault, or on `workspace_id` when one is given,
+/// after the authorizer has checked the caller's role there. The inner `Err` is the reason
+/// the caller was turned away.
 pub(crate) struct TaskService {
     task_repository: TaskRepository,
+    reportee_repository: ReporteeRepository,
+    workflow_repository: WorkflowRepository,
+    authorizer: WorkspaceAuthorizer,
+    task_events: TaskEventPublisher,
+    change_feed: Arc<ChangeFeed>,
 }
 
 impl TaskService {
-    pub(crate) fn new(app_database: AppDatabase) -> Self {
+    pub(crate) fn new(app_database: AppDatabase, change_feed: Arc<ChangeFeed>) -> Self {
         let task_repository = TaskRepository::new(app_database.clone());
-        Self { task_repository }
+        let reportee_repository = ReporteeRepository::new(app_database.clone());
+        let workflow_repository = WorkflowRepository::new(app_database.clone());
+        let authorizer = WorkspaceAuthorizer::new(app_database.clone());
+        let task_events = TaskEventPublisher::new(app_database.clone());
+        Self {
+            task_repository,
+            reportee_repository,
+            workflow_repository,
+            authorizer,
+            task_events,
+            change_feed,
+        }
+    }
+
+    /// Announces task changes on the live feed and queues them for webhooks, unless the
+    /// outbox relay picks them up from the change stream
+    async fn publish(&self, changes: Vec<TaskChange>) {
+        self.change_feed.publish_task_changes(&changes);
+        if !self.change_feed.relays_webhooks() {
+            self.task_events.publish(changes).await;
+        }
+    }
+
+    async fn authorize(
+        &self,
+        user_id: i64,
+        workspace_id: Option<i64>,
+        required: EWorkspaceRole,
+    ) -> Result<Result<TaskAccess, EAccessDenial>, ErrorResponse> {
+        match self.authorizer.authorize_tasks(user_id, workspace_id, required).await {
+            Ok(access) => Ok(access),
+            Err(e) => {
+                let error_message = format!("Error checking task access: {:?}", e);
+                error!("{}", error_message);
+                Err(ErrorResponse::from(error_message))
+            }
+        }
+    }
+
+    async fn find_workflow(&self, vault: TaskVault) -> Result<Option<Workflow>, ErrorResponse> {
+        match self.workflow_repository.find_by_vault(vault).await {
+            Ok(workflow) => Ok(workflow),
+            Err(e) => {
+                let error_message = format!("Error finding workflow: {:?}", e);
+                error!("{}", error_message);
+                Err(ErrorResponse::from(error_message))
+            }
+        }
     }
 
     pub(crate) async fn create_task(
         &self,
-        new_task: CreateTaskRequest,
-    ) -> Result<TaskResponse, ErrorResponse> {
-        let created_task = self.task_repository.create_task(new_task).await;
+        user_id: i64,
+        workspace_id: Option<i64>,
+        mut new_task: CreateTaskRequest,
+        dates: &DatePreferences,
+    ) -> Result<Result<TaskResponse, EAccessDenial>, ErrorResponse> {
+        let access = match self.authorize(user_id, workspace_id, EWorkspaceRole::Editor).await? {
+            Ok(access) => access,
+            Err(denial) => return Ok(Err(denial)),
+        };
+        if let Err(denial) = access.check_assignee(new_task.assignee_id) {
+            return Ok(Err(denial));
+        }
+        if let Err(denial) = resolve_date(&mut new_task.due_date, dates) {
+            return Ok(Err(denial));
+        }
+        let workflow = self.find_workflow(access.vault).await?;
+        if let Err(denial) = apply_workflow(
+            workflow.as_ref(),
+            None,
+            &mut new_task.status,
+            &mut new_task.workflow_status,
+        ) {
+            return Ok(Err(denial));
+        }
+
+        let created_task = self
+            .task_repository
+            .create_task(access.vault, user_id, new_task)
+            .await;
         match created_task {
-            Ok(task) => Ok(TaskResponse::from(task)),
+            Ok(task) => {
+                self.publish(TaskChange::created(task.clone())).await;
+                Ok(Ok(TaskResponse::from(task)))
+            }
             Err(e) => {
                 let error_message = format!("Error creating task: {:?}", e);
                 error!("{}", error_message);
@@ -32,13 +249,101 @@ impl TaskService {
         }
     }
 
+    /// Reads a one-line task description and, unless `preview` is set, creates the task it
+    /// describes. Text the parser cannot use is rejected.
+    pub(crate) async fn quick_add(
+        &self,
+        user_id: i64,
+        workspace_id: Option<i64>,
+        text: &str,
+        preview: bool,
+        dates: &DatePreferences,
+    ) -> Result<Result<QuickAddResponse<TaskResponse>, EAccessDenial>, ErrorResponse> {
+        let required = if preview {
+            EWorkspaceRole::Viewer
+        } else {
+            EWorkspaceRole::Editor
+        };
+        if let Err(denial) = self.authorize(user_id, workspace_id, required).await? {
+            return Ok(Err(denial));
+        }
+
+        // Mentions only resolve to the caller's own reportees, also in a workspace
+        let reportees: Vec<KnownReportee> = match self.reportee_repository.find_all(user_id).await {
+            Ok(reportees) => reportees
+                .into_iter()
+                .map(|reportee| KnownReportee {
+                    id: reportee.id,
+                    name: reportee.name,
+                })
+                .collect(),
+            Err(e) => {
+                let error_message = format!("Error finding reportees: {:?}", e);
+                error!("{}", error_message);
+                return Err(ErrorResponse::from(error_message));
+            }
+        };
+        let context = QuickAddContext {
+            now: Utc::now(),
+            dates,
+            reportees: &reportees,
+        };
+        let parsed = match parse_quick_add(text, &context) {
+            Ok(parsed) => parsed,
+            Err(message) => return Ok(Err(EAccessDenial::Rejected(message))),
+        };
+        if preview {
+            return Ok(Ok(QuickAddResponse { task: None, parsed }));
+        }
+
+        let created = self
+            .create_task(user_id, workspace_id, parsed.to_request(), dates)
+            .await?;
+        Ok(created.map(|task| QuickAddResponse {
+            task: Some(task),
+            parsed,
+        }))
+    }
+
     pub(crate) async fn bulk_create_tasks(
         &self,
-        bulk_request: BulkCreateTaskRequest,
-    ) -> Result<Vec<TaskResponse>, ErrorResponse> {
-        let created_tasks = self.task_repository.bulk_create_tasks(bulk_request).await;
+        user_id: i64,
+        workspace_id: Option<i64>,
+        mut bulk_request: BulkCreateTaskRequest,
+        dates: &DatePreferences,
+    ) -> Result<Result<Vec<TaskResponse>, EAccessDenial>, ErrorResponse> {
+        let access = match self.authorize(user_id, workspace_id, EWorkspaceRole::Editor).await? {
+            Ok(access) => access,
+            Err(denial) => return Ok(Err(denial)),
+        };
+        let workflow = self.find_workflow(access.vault).await?;
+        for task in &mut bulk_request.tasks {
+            if let Err(denial) = access.check_assignee(task.assignee_id) {
+                return Ok(Err(denial));
+            }
+            if let Err(denial) = resolve_date(&mut task.due_date, dates) {
+                return Ok(Err(denial));
+            }
+            if let Err(denial) = apply_workflow(
+                workflow.as_ref(),
+                None,
+                &mut task.status,
+                &mut task.workflow_status,
+            ) {
+                return Ok(Err(denial));
+            }
+        }
+
+        let created_tasks = self
+            .task_repository
+            .bulk_create_tasks(access.vault, user_id, bulk_request)
+            .await;
         match created_tasks {
-            Ok(tasks) => Ok(TaskResponse::from_vec(tasks)),
+            Ok(tasks) => {
+                let changes = tasks.iter().cloned().flat_map(TaskChange::created).collect();
+                self.publish(changes).await;
+                Ok(Ok(TaskResponse::from_vec(tasks)))
+            }
             Err(e) => {
                 let error_message = format!("Error bulk creating tasks: {:?}", e);
                 error!("{}", error_message);
@@ -47,10 +352,19 @@ impl TaskService {
         }
     }
 
-    pub(crate) async fn get_all_tasks(&self) -> Result<Vec<TaskResponse>, ErrorResponse> {
-        let tasks = self.task_repository.find_all().await;
+    pub(crate) async fn get_all_tasks(
+        &self,
+        user_id: i64,
+        workspace_id: Option<i64>,
+    ) -> Result<Result<Vec<TaskResponse>, EAccessDenial>, ErrorResponse> {
+        let access = match self.authorize(user_id, workspace_id, EWorkspaceRole::Viewer).await? {
+            Ok(access) => access,
+            Err(denial) => return Ok(Err(denial)),
+        };
+
+        let tasks = self.task_repository.find_all(access.vault).await;
         match tasks {
-            Ok(tasks) => Ok(TaskResponse::from_vec(tasks)),
+            Ok(tasks) => Ok(Ok(TaskResponse::from_vec(tasks))),
             Err(e) => {
                 let error_message = format!("Error finding tasks: {}", e);
                 error!(error_message);
@@ -59,10 +373,20 @@ impl TaskService {
         }
     }
 
-    pub(crate) async fn get_by_id(&self, task_id: i64) -> Result<TaskResponse, ErrorResponse> {
-        let task = self.task_repository.find_by_id(task_id).await;
+    pub(crate) async fn get_by_id(
+        &self,
+        user_id: i64,
+        workspace_id: Option<i64>,
+        task_id: i64,
+    ) -> Result<Result<TaskResponse, EAccessDenial>, ErrorResponse> {
+        let access = match self.authorize(user_id, workspace_id, EWorkspaceRole::Viewer).await? {
+            Ok(access) => access,
+            Err(denial) => return Ok(Err(denial)),
+        };
+
+        let task = self.task_repository.find_by_id(access.vault, task_id).await;
         match task {
-            Ok(Some(task)) => Ok(TaskResponse::from(task)),
+            Ok(Some(task)) => Ok(Ok(TaskResponse::from(task))),
             Ok(None) => {
                 let error_message = format!("Task with id {} not found", task_id);
                 error!("{}", error_message);
@@ -76,13 +400,28 @@ impl TaskService {
         }
     }
 
+    /// Searches the workspace in `params.workspace`, or the private vault without one. Due
+    /// dates and "today" follow the caller's calendar.
     pub(crate) async fn search_tasks(
         &self,
-        params: TaskSearchParams,
-    ) -> Result<Vec<TaskResponse>, ErrorResponse> {
-        let tasks = self.task_repository.search_task(params).await;
+        user_id: i64,
+        mut params: TaskSearchParams,
+        dates: &DatePreferences,
+    ) -> Result<Result<Vec<TaskResponse>, EAccessDenial>, ErrorResponse> {
+        let access = match self
+            .authorize(user_id, params.workspace, EWorkspaceRole::Viewer)
+            .await?
+        {
+            Ok(access) => access,
+            Err(denial) => return Ok(Err(denial)),
+        };
+        if let Err(denial) = resolve_date(&mut params.due_date, dates) {
+            return Ok(Err(denial));
+        }
+
+        let tasks = self.task_repository.search_task(access.vault, params, dates).await;
         match tasks {
-            Ok(tasks) => Ok(TaskResponse::from_vec(tasks)),
+            Ok(tasks) => Ok(Ok(TaskResponse::from_vec(tasks))),
             Err(e) => {
                 let error_message = format!("Error searching tasks: {}", e);
                 error!(error_message);
@@ -91,10 +430,31 @@ impl TaskService {
         }
     }
 
-    pub(crate) async fn soft_delete_task(&self, id: i64) -> Result<bool, ErrorResponse> {
-        let result = self.task_repository.soft_delete_by_id(id).await;
-        match result {
-            Ok(deleted) => Ok(deleted),
+    pub(crate) async fn soft_delete_task(
+        &self,
+        user_id: i64,
+        workspace_id: Option<i64>,
+        id: i64,
+    ) -> Result<Result<bool, EAccessDenial>, ErrorResponse> {
+        let access = match self.authorize(user_id, workspace_id, EWorkspaceRole::Editor).await? {
+            Ok(access) => access,
+            Err(denial) => return Ok(Err(denial)),
+        };
+
+        let vault = access.vault;
+        let result = async {
+            let Some(before) = self.task_repository.find_by_id(vault, id).await? else {
+                return Ok(None);
+            };
+            let deleted = self.task_repository.soft_delete_by_id(vault, id).await?;
+            Ok::<_, mindvault_core::models::DbError>(deleted.then_some(before))
+        };
+        match result.await {
+            Ok(Some(before)) => {
+                self.publish(TaskChange::deleted(before)).await;
+                Ok(Ok(true))
+            }
+            Ok(None) => Ok(Ok(false)),
             Err(e) => {
                 let error_message = format!("Error deleting task: {:?}", e);
                 error!("{}", error_message);
@@ -105,13 +465,51 @@ impl TaskService {
 
     pub(crate) async fn update_task(
         &self,
+        user_id: i64,
+        workspace_id: Option<i64>,
         id: i64,
-        update_request: UpdateTaskRequest,
-    ) -> Result<Option<TaskResponse>, ErrorResponse> {
-        let result = self.task_repository.update_task_by_id(id, update_request).await;
-        match result {
-            Ok(Some(task)) => Ok(Some(TaskResponse::from(task))),
-            Ok(None) => Ok(None),
+        mut update_request: UpdateTaskRequest,
+        dates: &DatePreferences,
+    ) -> Result<Result<Option<TaskResponse>, EAccessDenial>, ErrorResponse> {
+        let access = match self.authorize(user_id, workspace_id, EWorkspaceRole::Editor).await? {
+            Ok(access) => access,
+            Err(denial) => return Ok(Err(denial)),
+        };
+        if let Err(denial) = access.check_assignee(update_request.assignee_id) {
+            return Ok(Err(denial));
+        }
+        if let Err(denial) = resolve_date(&mut update_request.due_date, dates) {
+            return Ok(Err(denial));
+        }
+
+        let vault = access.vault;
+        let result = async {
+            let Some(before) = self.task_repository.find_by_id(vault, id).await? else {
+                return Ok(Ok(None));
+            };
+            // The move is checked against the task as stored, before anything is written
+            let workflow = self.workflow_repository.find_by_vault(vault).await?;
+            if let Err(denial) = apply_workflow(
+                workflow.as_ref(),
+                Some(&before),
+                &mut update_request.status,
+                &mut update_request.workflow_status,
+            ) {
+                return Ok(Err(denial));
+            }
+            let after = self
+                .task_repository
+                .update_task_by_id(vault, id, update_request)
+                .await?;
+            Ok::<_, mindvault_core::models::DbError>(Ok(after.map(|after| (before, after))))
+        };
+        match result.await {
+            Ok(Ok(Some((before, task)))) => {
+                self.publish(TaskChange::updated(before, task.clone())).await;
+                Ok(Ok(Some(TaskResponse::from(task))))
+            }
+            Ok(Ok(None)) => Ok(Ok(None)),
+            Ok(Err(denial)) => Ok(Err(denial)),
             Err(e) => {
                 let error_message = format!("Error updating task: {:?}", e);
                 error!("{}", error_message);
@@ -120,13 +518,245 @@ impl TaskService {
         }
     }
 
+    /// Drops a task into a board column between two of its tasks. Changing columns changes
+    /// the task's status, within the workflow's transitions. When the neighbours leave no room
+    /// for a rank, the whole column is ranked afresh.
+    pub(crate) async fn move_task(
+        &self,
+        user_id: i64,
+        workspace_id: Option<i64>,
+        id: i64,
+        request: MoveTaskRequest,
+    ) -> Result<Result<Option<TaskResponse>, EAccessDenial>, ErrorResponse> {
+        let access = match self.authorize(user_id, workspace_id, EWorkspaceRole::Editor).await? {
+            Ok(access) => access,
+            Err(denial) => return Ok(Err(denial)),
+        };
+
+        let vault = access.vault;
+        let result = async {
+            let Some(before) = self.task_repository.find_by_id(vault, id).await? else {
+                return Ok(Ok(None));
+            };
+            let workflow = self.workflow_repository.find_by_vault(vault).await?;
+            let mut status = request.status;
+            let mut workflow_status = request.workflow_status;
+            if let Err(denial) = apply_workflow(
+                workflow.as_ref(),
+                Some(&before),
+                &mut status,
+                &mut workflow_status,
+            ) {
+                return Ok(Err(denial));
+            }
+
+            let mut moved = before.clone();
+            if let Some(status) = &status {
+                moved.status = status.clone();
+                moved.workflow_status = workflow_status.clone();
+            }
+            let Some(name) = board_column(workflow.as_ref(), &moved) else {
+                return Ok(Err(EAccessDenial::Rejected(
+                    "The workflow has no column for the task's status".to_string(),
+                )));
+            };
+            let mut column: Vec<Task> = self
+                .task_repository
+                .find_all(vault)
+                .await?
+                .into_iter()
+                .filter(|task| {
+                    task.id != id
+                        && board_column(workflow.as_ref(), task).as_deref() == Some(name.as_str())
+                })
+                .collect();
+            column.sort_by(board_order);
+            let position =
+                match drop_position(&column, &name, request.previous_id, request.next_id) {
+                    Ok(position) => position,
+                    Err(denial) => return Ok(Err(denial)),
+                };
+
+            let previous = position.checked_sub(1).map(|index| column[index].rank.as_deref());
+            let next = column.get(position).map(|task| task.rank.as_deref());
+            let rank = match (previous, next) {
+                // Unranked neighbours have no rank to fit between
+                (Some(None), _) | (_, Some(None)) => None,
+                (Some(Some(previous)), Some(Some(next))) if previous >= next => None,
+                (previous, next) => Some(rank_between(previous.flatten(), next.flatten()))
+                    .filter(|rank| rank.len() <= MAX_RANK_LENGTH),
+            };
+            let ranks = match rank {
+                Some(rank) => vec![(id, rank)],
+                None => {
+                    column.insert(position, moved);
+                    respread_ranks(&column)
+                }
+            };
+
+            if status.is_some() {
+                let update = UpdateTaskRequest {
+                    status,
+                    workflow_status,
+                    ..Default::default()
+                };
+                self.task_repository.update_task_by_id(vault, id, update).await?;
+            }
+            self.task_repository.set_ranks(vault, &ranks).await?;
+            let Some(after) = self.task_repository.find_by_id(vault, id).await? else {
+                return Ok(Ok(None));
+            };
+            let neighbours: Vec<(Task, Task)> = reranked(&column, &ranks)
+                .into_iter()
+                .filter(|(task, _)| task.id != id)
+                .collect();
+            Ok::<_, mindvault_core::models::DbError>(Ok(Some((before, after, neighbours))))
+        };
+        match result.await {
+            Ok(Ok(Some((before, task, neighbours)))) => {
+                let mut changes = TaskChange::updated(before, task.clone());
+                for (before, after) in neighbours {
+                    changes.extend(TaskChange::updated(before, after));
+                }
+                self.publish(changes).await;
+                Ok(Ok(Some(TaskResponse::from(task))))
+            }
+            Ok(Ok(None)) => Ok(Ok(None)),
+            Ok(Err(denial)) => Ok(Err(denial)),
+            Err(e) => {
+                let error_message = format!("Error moving task: {:?}", e);
+                error!("{}", error_message);
+                Err(ErrorResponse::from(error_message))
+            }
+        }
+    }
+
+    /// Puts the listed tasks of one board column first, in the given order, followed by the
+    /// column's other tasks in their current order. Returns the whole column.
+    pub(crate) async fn reorder_tasks(
+        &self,
+        user_id: i64,
+        workspace_id: Option<i64>,
+        task_ids: &[i64],
+    ) -> Result<Result<Vec<TaskResponse>, EAccessDenial>, ErrorResponse> {
+        let access = match self.authorize(user_id, workspace_id, EWorkspaceRole::Editor).await? {
+            Ok(access) => access,
+            Err(denial) => return Ok(Err(denial)),
+        };
+
+        let vault = access.vault;
+        let result = async {
+            let workflow = self.workflow_repository.find_by_vault(vault).await?;
+            let tasks = self.task_repository.find_all(vault).await?;
+            let first = task_ids
+                .first()
+                .and_then(|first_id| tasks.iter().find(|task| task.id == *first_id));
+            let Some(name) = first.and_then(|task| board_column(workflow.as_ref(), task)) else {
+                let message = format!("Task {} is not on the board", task_ids[0]);
+                return Ok(Err(EAccessDenial::Rejected(message)));
+            };
+            let mut column: Vec<Task> = tasks
+                .into_iter()
+                .filter(|task| {
+                    board_column(workflow.as_ref(), task).as_deref() == Some(name.as_str())
+                })
+                .collect();
+            column.sort_by(board_order);
+
+            let mut ordered = Vec::with_capacity(column.len());
+            for task_id in task_ids {
+                let Some(index) = column.iter().position(|task| task.id == *task_id) else {
+                    let message = format!("Task {} is not in column '{}'", task_id, name);
+                    return Ok(Err(EAccessDenial::Rejected(message)));
+                };
+                ordered.push(column.remove(index));
+            }
+            ordered.append(&mut column);
+
+            let ranks = respread_ranks(&ordered);
+            self.task_repository.set_ranks(vault, &ranks).await?;
+            let changed = reranked(&ordered, &ranks);
+            for task in &mut ordered {
+                if let Some((_, after)) = changed.iter().find(|(before, _)| before.id == task.id) {
+                    task.rank = after.rank.clone();
+                }
+            }
+            Ok::<_, mindvault_core::models::DbError>(Ok((ordered, changed)))
+        };
+        match result.await {
+            Ok(Ok((tasks, changed))) => {
+                let changes = changed
+                    .into_iter()
+                    .flat_map(|(before, after)| TaskChange::updated(before, after))
+                    .collect();
+                self.publish(changes).await;
+                Ok(Ok(TaskResponse::from_vec(tasks)))
+            }
+            Ok(Err(denial)) => Ok(Err(denial)),
+            Err(e) => {
+                let error_message = format!("Error reordering tasks: {:?}", e);
+                error!("{}", error_message);
+                Err(ErrorResponse::from(error_message))
+            }
+        }
+    }
+
+    /// The vault's tasks in board columns, warning about columns over their WIP limit
+    pub(crate) async fn get_board(
+        &self,
+        user_id: i64,
+        workspace_id: Option<i64>,
+    ) -> Result<Result<BoardResponse, EAccessDenial>, ErrorResponse> {
+        let access = match self.authorize(user_id, workspace_id, EWorkspaceRole::Viewer).await? {
+            Ok(access) => access,
+            Err(denial) => return Ok(Err(denial)),
+        };
+
+        let vault = access.vault;
+        let result = async {
+            let workflow = self.workflow_repository.find_by_vault(vault).await?;
+            let tasks = self.task_repository.find_all(vault).await?;
+            Ok::<_, mindvault_core::models::DbError>(BoardResponse::new(
+                workspace_id,
+                workflow.as_ref(),
+                tasks,
+            ))
+        };
+        match result.await {
+            Ok(board) => Ok(Ok(board)),
+            Err(e) => {
+                let error_message = format!("Error building board: {:?}", e);
+                error!("{}", error_message);
+                Err(ErrorResponse::from(error_message))
+            }
+        }
+    }
+
     pub(crate) async fn bulk_delete_by_status(
         &self,
-        status: mindvault_shared::models::tasks_model::ETaskStatus,
-    ) -> Result<u64, ErrorResponse> {
-        let result = self.task_repository.bulk_soft_delete_by_status(status).await;
+        user_id: i64,
+        workspace_id: Option<i64>,
+        status: ETaskStatus,
+    ) -> Result<Result<u64, EAccessDenial>, ErrorResponse> {
+        let access = match self.authorize(user_id, workspace_id, EWorkspaceRole::Editor).await? {
+            Ok(access) => access,
+            Err(denial) => return Ok(Err(denial)),
+        };
+
+        let result = self
+            .task_repository
+            .bulk_soft_delete_by_status(access.vault, status)
+            .await;
         match result {
-            Ok(count) => Ok(count),
+            Ok(deleted_tasks) => {
+                let count = deleted_tasks.len() as u64;
+                let changes = deleted_tasks
+                    .into_iter()
+                    .flat_map(TaskChange::deleted)
+                    .collect();
+                self.publish(changes).await;
+                Ok(Ok(count))
+            }
             Err(e) => {
                 let error_message = format!("Error bulk deleting tasks: {:?}", e);
                 error!("{}", error_message);
@@ -135,13 +765,51 @@ impl TaskService {
         }
     }
 
+    /// A bulk status change cannot follow a workflow's transitions task by task, so it is
+    /// rejected when the vault has one
     pub(crate) async fn search_and_update_tasks(
         &self,
-        request: SearchAndUpdateRequest,
-    ) -> Result<Vec<TaskResponse>, ErrorResponse> {
-        let result = self.task_repository.search_and_update_tasks(request).await;
+        user_id: i64,
+        workspace_id: Option<i64>,
+        mut request: SearchAndUpdateRequest,
+        dates: &DatePreferences,
+    ) -> Result<Result<Vec<TaskResponse>, EAccessDenial>, ErrorResponse> {
+        let access = match self.authorize(user_id, workspace_id, EWorkspaceRole::Editor).await? {
+            Ok(access) => access,
+            Err(denial) => return Ok(Err(denial)),
+        };
+        if !request.has_update() {
+            return Ok(Err(EAccessDenial::Rejected(
+                "At least one update field must be provided".to_string(),
+            )));
+        }
+        for date in [&mut request.due_date_filter, &mut request.due_date] {
+            if let Err(denial) = resolve_date(date, dates) {
+                return Ok(Err(denial));
+            }
+        }
+        if request.status.is_some() {
+            let workflow = self.find_workflow(access.vault).await?;
+            if let Err(denial) = check_bulk_status(workflow.as_ref(), request.status.as_ref()) {
+                return Ok(Err(denial));
+            }
+        }
+
+        let result = self
+            .task_repository
+            .search_and_update_tasks(access.vault, request, dates)
+            .await;
         match result {
-            Ok(tasks) => Ok(TaskResponse::from_vec(tasks)),
+            Ok(updated_tasks) => {
+                let changes = updated_tasks
+                    .iter()
+                    .cloned()
+                    .flat_map(|(before, after)| TaskChange::updated(before, after))
+                    .collect();
+                self.publish(changes).await;
+                let tasks = updated_tasks.into_iter().map(|(_, after)| after).collect();
+                Ok(Ok(TaskResponse::from_vec(tasks)))
+            }
             Err(e) => {
                 let error_message = format!("Error searching and updating tasks: {:?}", e);
                 error!("{}", error_message);
@@ -150,3 +818,115 @@ impl TaskService {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bson::DateTime as BsonDateTime;
+    use mindvault_shared::models::tasks_model::{EStatusCategory, ETaskPriority};
+    use mindvault_shared::models::workflows_model::{WorkflowStatus, WorkflowTransition};
+
+    /// Open -> Doing -> Done, and nothing else
+    fn workflow() -> Workflow {
+        let status = |name: &str, category| WorkflowStatus {
+            name: name.to_string(),
+            category,
+            wip_limit: None,
+        };
+        let transition = |from: &str, to: &str| WorkflowTransition {
+            from: from.to_string(),
+            to: to.to_string(),
+        };
+        Workflow {
+            owner_id: 1,
+            workspace_id: None,
+            statuses: vec![
+                status("Open", EStatusCategory::Todo),
+                status("Doing", EStatusCategory::Active),
+                status("Done", EStatusCategory::Done),
+            ],
+            transitions: vec![transition("Open", "Doing"), transition("Doing", "Done")],
+            updated_at: BsonDateTime::now(),
+        }
+    }
+
+    fn task_in(status: ETaskStatus, workflow_status: &str) -> Task {
+        Task {
+            id: 1,
+            owner_id: 1,
+            workspace_id: None,
+            assignee_id: None,
+            reportee_ids: Vec::new(),
+            name: "Renew the certificate".to_string(),
+            priority: ETaskPriority::Normal,
+            status,
+            workflow_status: Some(workflow_status.to_string()),
+            rank: None,
+            due_date: None,
+            due_has_time: false,
+            created_at: BsonDateTime::now(),
+            updated_at: None,
+            completed_at: None,
+            tags: Vec::new(),
+            recurrence: None,
+            estimate_minutes: None,
+            tracked_seconds: 0,
+            notes: None,
+            notes_index: Vec::new(),
+            external_id: None,
+            deleted: Some(false),
+        }
+    }
+
+    #[test]
+    fn apply_workflow_fills_in_both_statuses() {
+        let workflow = workflow();
+        let task = task_in(ETaskStatus::NotStarted, "Open");
+        let mut status = None;
+        let mut workflow_status = Some("doing".to_string());
+        apply_workflow(Some(&workflow), Some(&task), &mut status, &mut workflow_status).unwrap();
+        assert_eq!(status, Some(ETaskStatus::InProgress));
+        assert_eq!(workflow_status.as_deref(), Some("Doing"));
+
+        let (mut status, mut workflow_status) = (None, None);
+        apply_workflow(Some(&workflow), None, &mut status, &mut workflow_status).unwrap();
+        assert_eq!(status, Some(ETaskStatus::NotStarted));
+        assert_eq!(workflow_status.as_deref(), Some("Open"));
+    }
+
+    #[test]
+    fn apply_workflow_rejects_moves_the_workflow_does_not_allow() {
+        let workflow = workflow();
+        let task = task_in(ETaskStatus::NotStarted, "Open");
+        let mut status = Some(ETaskStatus::Completed);
+        let mut workflow_status = None;
+        let denial =
+            apply_workflow(Some(&workflow), Some(&task), &mut status, &mut workflow_status)
+                .unwrap_err();
+        assert!(matches!(
+            denial,
+            EAccessDenial::Rejected(message)
+                if message == "Tasks in 'Open' cannot move to 'Done', only to: Doing"
+        ));
+    }
+
+    #[test]
+    fn apply_workflow_needs_a_workflow_for_workflow_statuses() {
+        let (mut status, mut workflow_status) = (Some(ETaskStatus::Pending), None);
+        apply_workflow(None, None, &mut status, &mut workflow_status).unwrap();
+        assert_eq!(status, Some(ETaskStatus::Pending));
+
+        let mut workflow_status = Some("Doing".to_string());
+        let denial = apply_workflow(None, None, &mut status, &mut workflow_status).unwrap_err();
+        assert!(matches!(denial, EAccessDenial::Rejected(_)));
+    }
+
+    #[test]
+    fn bulk_updates_cannot_change_the_status_of_workflow_tasks() {
+        let workflow = workflow();
+        let denial = check_bulk_status(Some(&workflow), Some(&ETaskStatus::Completed));
+        assert!(matches!(denial, Err(EAccessDenial::Rejected(_))));
+        assert!(check_bulk_status(Some(&workflow), None).is_ok());
+        assert!(check_bulk_status(None, Some(&ETaskStatus::Completed)).is_ok());
+    }
+}
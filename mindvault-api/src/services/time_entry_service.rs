@@ -0,0 +1,502 @@
+use crate::auth::workspace_access::{EAccessDenial, TaskAccess, WorkspaceAuthorizer};
+use crate::services::task_service::resolve_date;
+use axum::response::ErrorResponse;
+use bson::DateTime as BsonDateTime;
+use chrono::Duration;
+use mindvault_core::models::{AppDatabase, DbError};
+use mindvault_core::repository::date_utils::start_of_day;
+use mindvault_core::repository::task_repo::{TaskRepository, TaskVault};
+use mindvault_core::repository::time_entry_repo::{
+    entry_vault, NewTimeEntry, TimeEntryRepository,
+};
+use mindvault_shared::dtos::time_entry_dtos::{LogTimeRequest, TimeReportParams};
+use mindvault_shared::models::time_entries_model::{
+    TaskTimeResponse, TimeEntry, TimeEntryResponse, TimeReportResponse, TimerResponse,
+};
+use mindvault_shared::models::workspaces_model::EWorkspaceRole;
+use mindvault_shared::utils::date_input::{DateInput, DatePreferences};
+use tracing::error;
+
+/// Time tracked on tasks of the caller's private vault, or of `workspace_id` when one is
+/// given. Tracking time needs the Editor role; reading it needs Viewer. Each stopped or
+/// entered entry is added to its task's tracked time, and taken off again when deleted.
+pub(crate) struct TimeEntryService {
+    time_entry_repository: TimeEntryRepository,
+    task_repository: TaskRepository,
+    authorizer: WorkspaceAuthorizer,
+}
+
+/// Times a timer start retries after losing a race with another start by the same user
+const TIMER_START_ATTEMPTS: usize = 3;
+
+fn service_error(context: &str, e: DbError) -> ErrorResponse {
+    let error_message = format!("{}: {:?}", context, e);
+    error!("{}", error_message);
+    ErrorResponse::from(error_message)
+}
+
+/// Whether `entry` was tracked on task `task_id` of `vault`; task numbers repeat across vaults
+fn runs_on(entry: &TimeEntry, vault: TaskVault, task_id: i64) -> bool {
+    entry_vault(entry) == vault && entry.task_id == task_id
+}
+
+/// Where a manual entry ends: `ended_at` if given, otherwise `duration_minutes` after it
+/// starts. It must end at a time of day and after it starts.
+fn entry_end(
+    started_at: BsonDateTime,
+    ended_at: Option<&DateInput>,
+    duration_minutes: Option<u32>,
+) -> Result<BsonDateTime, EAccessDenial> {
+    let ended_at = match (ended_at, duration_minutes) {
+        (Some(DateInput::Day(_)), _) => {
+            return Err(EAccessDenial::Rejected("endedAt needs a time of day".to_string()));
+        }
+        (Some(input), _) => input.stored().map(|(at, _)| at),
+        (None, Some(minutes)) => {
+            let duration = Duration::minutes(i64::from(minutes));
+            Some(BsonDateTime::from(started_at.to_chrono() + duration))
+        }
+        (None, None) => None,
+    };
+    ended_at
+        .filter(|ended_at| *ended_at > started_at)
+        .ok_or_else(|| EAccessDenial::Rejected("The entry must end after it starts".to_string()))
+}
+
+/// The error a timer start gives up with after its `attempt`th try failed with `e`, or `None`
+/// to try again: only a start that lost a race with another one is retried, while attempts
+/// are left
+fn failed_start(e: DbError, attempt: usize) -> Option<DbError> {
+    if !e.is_duplicate_key() {
+        return Some(e);
+    }
+    (attempt >= TIMER_START_ATTEMPTS).then(|| {
+        DbError::InternalError("Another timer kept being started at the same time".to_string())
+    })
+}
+
+/// Where a manual entry starts: its instant, or midnight in the caller's time zone for a day
+fn entry_start(input: &DateInput, dates: &DatePreferences) -> Option<BsonDateTime> {
+    match input {
+        DateInput::Day(day) => Some(start_of_day(*day, dates.tz())),
+        _ => input.stored().map(|(at, _)| at),
+    }
+}
+
+impl TimeEntryService {
+    pub(crate) fn new(app_database: AppDatabase) -> Self {
+        Self {
+            time_entry_repository: TimeEntryRepository::new(app_database.clone()),
+            task_repository: TaskRepository::new(app_database.clone()),
+            authorizer: WorkspaceAuthorizer::new(app_database),
+        }
+    }
+
+    async fn authorize(
+        &self,
+        user_id: i64,
+        workspace_id: Option<i64>,
+        required: EWorkspaceRole,
+    ) -> Result<Result<TaskAccess, EAccessDenial>, ErrorResponse> {
+        self.authorizer
+            .authorize_tasks(user_id, workspace_id, required)
+            .await
+            .map_err(|e| service_error("Error checking time tracking access", e))
+    }
+
+    /// Stops `running` and adds its time to its task, which may be in another vault
+    async fn stop(
+        &self,
+        running: &TimeEntry,
+        note: Option<String>,
+    ) -> Result<Option<TimeEntry>, DbError> {
+        let Some(stopped) = self.time_entry_repository.stop_entry(running, note).await? else {
+            return Ok(None);
+        };
+        self.task_repository
+            .add_tracked_seconds(entry_vault(&stopped), stopped.task_id, stopped.duration_seconds)
+            .await?;
+        Ok(Some(stopped))
+    }
+
+    /// One attempt at starting `user_id`'s timer on a task: returns the timer already running
+    /// on it, or stops the one running elsewhere, keeping the first stopped in `stopped`, and
+    /// starts a new one
+    async fn try_start(
+        &self,
+        user_id: i64,
+        vault: TaskVault,
+        task_id: i64,
+        note: &Option<String>,
+        stopped: &mut Option<TimeEntry>,
+    ) -> Result<TimeEntry, DbError> {
+        if let Some(running) = self.time_entry_repository.find_running(user_id).await? {
+            if runs_on(&running, vault, task_id) {
+                return Ok(running);
+            }
+            if let Some(entry) = self.stop(&running, None).await? {
+                stopped.get_or_insert(entry);
+            }
+        }
+        let new_entry = NewTimeEntry {
+            task_id,
+            started_at: BsonDateTime::now(),
+            ended_at: None,
+            note: note.clone(),
+        };
+        self.time_entry_repository.create_entry(vault, user_id, new_entry).await
+    }
+
+    /// Starts the caller's timer on a task, stopping the timer they had running on another
+    /// one. A timer already running on the task is left as it is. `None` if there is no such
+    /// task.
+    pub(crate) async fn start_timer(
+        &self,
+        user_id: i64,
+        workspace_id: Option<i64>,
+        task_id: i64,
+        note: Option<String>,
+    ) -> Result<Result<Option<TimerResponse>, EAccessDenial>, ErrorResponse> {
+        let access = match self.authorize(user_id, workspace_id, EWorkspaceRole::Editor).await? {
+            Ok(access) => access,
+            Err(denial) => return Ok(Err(denial)),
+        };
+
+        let vault = access.vault;
+        let result = async {
+            if self.task_repository.find_by_id(vault, task_id).await?.is_none() {
+                return Ok(None);
+            }
+            let mut stopped = None;
+            // A unique index allows one running timer per user, so a start that loses a race
+            // with another one finds that timer on the next attempt
+            let mut attempt = 0;
+            let running = loop {
+                attempt += 1;
+                match self.try_start(user_id, vault, task_id, &note, &mut stopped).await {
+                    Ok(running) => break running,
+                    Err(e) => {
+                        if let Some(e) = failed_start(e, attempt) {
+                            return Err(e);
+                        }
+                    }
+                }
+            };
+            Ok::<_, DbError>(Some(TimerResponse {
+                running: Some(TimeEntryResponse::from(running)),
+                stopped: stopped.map(TimeEntryResponse::from),
+            }))
+        };
+        match result.await {
+            Ok(timer) => Ok(Ok(timer)),
+            Err(e) => Err(service_error("Error starting timer", e)),
+        }
+    }
+
+    /// Stops the caller's timer if it runs on the task; `None` if it does not
+    pub(crate) async fn stop_timer(
+        &self,
+        user_id: i64,
+        workspace_id: Option<i64>,
+        task_id: i64,
+        note: Option<String>,
+    ) -> Result<Result<Option<TimerResponse>, EAccessDenial>, ErrorResponse> {
+        let access = match self.authorize(user_id, workspace_id, EWorkspaceRole::Editor).await? {
+            Ok(access) => access,
+            Err(denial) => return Ok(Err(denial)),
+        };
+
+        let result = async {
+            let running = match self.time_entry_repository.find_running(user_id).await? {
+                Some(running) if runs_on(&running, access.vault, task_id) => running,
+                _ => return Ok(None),
+            };
+            let stopped = self.stop(&running, note).await?;
+            Ok::<_, DbError>(stopped.map(|stopped| TimerResponse {
+                running: None,
+                stopped: Some(TimeEntryResponse::from(stopped)),
+            }))
+        };
+        match result.await {
+            Ok(timer) => Ok(Ok(timer)),
+            Err(e) => Err(service_error("Error stopping timer", e)),
+        }
+    }
+
+    /// Records time spent without a timer. `None` if there is no such task.
+    pub(crate) async fn log_time(
+        &self,
+        user_id: i64,
+        workspace_id: Option<i64>,
+        task_id: i64,
+        request: LogTimeRequest,
+        dates: &DatePreferences,
+    ) -> Result<Result<Option<TimeEntryResponse>, EAccessDenial>, ErrorResponse> {
+        let access = match self.authorize(user_id, workspace_id, EWorkspaceRole::Editor).await? {
+            Ok(access) => access,
+            Err(denial) => return Ok(Err(denial)),
+        };
+
+        let mut started_at = Some(request.started_at);
+        let mut ended_at = request.ended_at;
+        for date in [&mut started_at, &mut ended_at] {
+            if let Err(denial) = resolve_date(date, dates) {
+                return Ok(Err(denial));
+            }
+        }
+        let Some(started_at) = started_at.as_ref().and_then(|input| entry_start(input, dates))
+        else {
+            return Ok(Err(EAccessDenial::Rejected("Invalid startedAt".to_string())));
+        };
+        let ended_at = match entry_end(started_at, ended_at.as_ref(), request.duration_minutes) {
+            Ok(ended_at) => ended_at,
+            Err(denial) => return Ok(Err(denial)),
+        };
+
+        let result = async {
+            if self.task_repository.find_by_id(access.vault, task_id).await?.is_none() {
+                return Ok(None);
+            }
+            let new_entry = NewTimeEntry {
+                task_id,
+                started_at,
+                ended_at: Some(ended_at),
+                note: request.note,
+            };
+            let entry = self
+                .time_entry_repository
+                .create_entry(access.vault, user_id, new_entry)
+                .await?;
+            self.task_repository
+                .add_tracked_seconds(access.vault, task_id, entry.duration_seconds)
+                .await?;
+            Ok::<_, DbError>(Some(TimeEntryResponse::from(entry)))
+        };
+        match result.await {
+            Ok(entry) => Ok(Ok(entry)),
+            Err(e) => Err(service_error("Error logging time", e)),
+        }
+    }
+
+    /// Every member's time entries on a task, with its estimate and tracked time
+    pub(crate) async fn get_task_time(
+        &self,
+        user_id: i64,
+        workspace_id: Option<i64>,
+        task_id: i64,
+    ) -> Result<Result<Option<TaskTimeResponse>, EAccessDenial>, ErrorResponse> {
+        let access = match self.authorize(user_id, workspace_id, EWorkspaceRole::Viewer).await? {
+            Ok(access) => access,
+            Err(denial) => return Ok(Err(denial)),
+        };
+
+        let result = async {
+            let Some(task) = self.task_repository.find_by_id(access.vault, task_id).await? else {
+                return Ok(None);
+            };
+            let entries = self
+                .time_entry_repository
+                .find_by_task(access.vault, task_id)
+                .await?;
+            Ok::<_, DbError>(Some(TaskTimeResponse::new(
+                task.id,
+                task.estimate_minutes,
+                task.tracked_seconds,
+                entries,
+            )))
+        };
+        match result.await {
+            Ok(time) => Ok(Ok(time)),
+            Err(e) => Err(service_error("Error finding time entries", e)),
+        }
+    }
+
+    /// Deletes one of the caller's entries on a task and takes its time off the task. A
+    /// running timer is simply discarded.
+    pub(crate) async fn delete_time_entry(
+        &self,
+        user_id: i64,
+        workspace_id: Option<i64>,
+        task_id: i64,
+        entry_id: i64,
+    ) -> Result<Result<bool, EAccessDenial>, ErrorResponse> {
+        let access = match self.authorize(user_id, workspace_id, EWorkspaceRole::Editor).await? {
+            Ok(access) => access,
+            Err(denial) => return Ok(Err(denial)),
+        };
+
+        let result = async {
+            let Some(entry) = self
+                .time_entry_repository
+                .soft_delete_entry(access.vault, user_id, task_id, entry_id)
+                .await?
+            else {
+                return Ok(false);
+            };
+            if entry.duration_seconds != 0 {
+                self.task_repository
+                    .add_tracked_seconds(access.vault, task_id, -entry.duration_seconds)
+                    .await?;
+            }
+            Ok::<_, DbError>(true)
+        };
+        match result.await {
+            Ok(deleted) => Ok(Ok(deleted)),
+            Err(e) => Err(service_error("Error deleting time entry", e)),
+        }
+    }
+
+    /// Tracked time of the vault grouped by tag, task or day. Running timers count once they
+    /// are stopped.
+    pub(crate) async fn time_report(
+        &self,
+        user_id: i64,
+        params: TimeReportParams,
+        dates: &DatePreferences,
+    ) -> Result<Result<TimeReportResponse, EAccessDenial>, ErrorResponse> {
+        let access = match self
+            .authorize(user_id, params.workspace, EWorkspaceRole::Viewer)
+            .await?
+        {
+            Ok(access) => access,
+            Err(denial) => return Ok(Err(denial)),
+        };
+
+        let tz = dates.tz();
+        let mut bounds = [params.from, params.to];
+        for date in bounds.iter_mut() {
+            if let Err(denial) = resolve_date(date, dates) {
+                return Ok(Err(denial));
+            }
+        }
+        let [from, to] = bounds.map(|date| date.and_then(|date| date.day(tz)));
+        if let (Some(from), Some(to)) = (from, to)
+            && from > to
+        {
+            return Ok(Err(EAccessDenial::Rejected(
+                "from must not be after to".to_string(),
+            )));
+        }
+
+        let report = self
+            .time_entry_repository
+            .report(
+                access.vault,
+                params.group_by,
+                from.map(|from| start_of_day(from, tz)),
+                to.map(|to| start_of_day(to + Duration::days(1), tz)),
+                tz.name(),
+            )
+            .await;
+        match report {
+            Ok((rows, tracked_seconds, entry_count)) => Ok(Ok(TimeReportResponse {
+                workspace_id: params.workspace,
+                group_by: params.group_by,
+                tracked_seconds,
+                entry_count,
+                rows,
+            })),
+            Err(e) => Err(service_error("Error building time report", e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bson::doc;
+    use chrono::{NaiveDate, TimeZone, Utc};
+    use mindvault_shared::utils::time_zone::FlexibleDateTime;
+    use mongodb::error::{ErrorKind, WriteError, WriteFailure};
+
+    fn at(hour: u32, minute: u32) -> BsonDateTime {
+        BsonDateTime::from(Utc.with_ymd_and_hms(2026, 10, 19, hour, minute, 0).unwrap())
+    }
+
+    fn time(hour: u32, minute: u32) -> DateInput {
+        DateInput::Time(FlexibleDateTime::Absolute(at(hour, minute).to_chrono()))
+    }
+
+    fn running_entry(workspace_id: Option<i64>, task_id: i64) -> TimeEntry {
+        TimeEntry {
+            id: 1,
+            owner_id: 1,
+            workspace_id,
+            task_id,
+            started_at: at(9, 0),
+            ended_at: None,
+            duration_seconds: 0,
+            note: None,
+            manual: false,
+            created_at: at(9, 0),
+            deleted: Some(false),
+        }
+    }
+
+    fn duplicate_key() -> DbError {
+        let write_error: WriteError =
+            bson::from_document(doc! { "code": 11000, "errmsg": "E11000 duplicate key" })
+                .unwrap();
+        DbError::MongoError(ErrorKind::Write(WriteFailure::WriteError(write_error)).into())
+    }
+
+    fn rejection(result: Result<BsonDateTime, EAccessDenial>) -> String {
+        match result {
+            Err(EAccessDenial::Rejected(message)) => message,
+            other => panic!("expected a rejection, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn starting_elsewhere_stops_the_running_timer() {
+        let private = running_entry(None, 7);
+        assert!(runs_on(&private, TaskVault::User(1), 7));
+        // Same task number, but another task: the timer is stopped before the new one starts
+        assert!(!runs_on(&private, TaskVault::User(1), 8));
+        assert!(!runs_on(&private, TaskVault::Workspace(3), 7));
+        assert!(!runs_on(&running_entry(Some(3), 7), TaskVault::User(1), 7));
+    }
+
+    #[test]
+    fn starts_that_lose_a_race_are_retried() {
+        for attempt in 1..TIMER_START_ATTEMPTS {
+            assert!(failed_start(duplicate_key(), attempt).is_none(), "attempt {}", attempt);
+        }
+        let gave_up = failed_start(duplicate_key(), TIMER_START_ATTEMPTS);
+        assert!(matches!(
+            gave_up,
+            Some(DbError::InternalError(message)) if message.starts_with("Another timer")
+        ));
+    }
+
+    #[test]
+    fn other_start_failures_are_not_retried() {
+        let failed = failed_start(DbError::InvalidId("7".to_string()), 1);
+        assert!(matches!(failed, Some(DbError::InvalidId(_))));
+    }
+
+    #[test]
+    fn duration_minutes_count_from_the_start() {
+        assert_eq!(entry_end(at(9, 0), None, Some(90)).unwrap(), at(10, 30));
+    }
+
+    #[test]
+    fn an_end_time_wins_over_a_duration() {
+        assert_eq!(entry_end(at(9, 0), Some(&time(9, 45)), Some(90)).unwrap(), at(9, 45));
+    }
+
+    #[test]
+    fn entries_must_end_after_they_start() {
+        let message = "The entry must end after it starts";
+        assert_eq!(rejection(entry_end(at(9, 0), Some(&time(8, 0)), None)), message);
+        assert_eq!(rejection(entry_end(at(9, 0), Some(&time(9, 0)), None)), message);
+        assert_eq!(rejection(entry_end(at(9, 0), None, Some(0))), message);
+        assert_eq!(rejection(entry_end(at(9, 0), None, None)), message);
+    }
+
+    #[test]
+    fn an_end_day_without_a_time_is_rejected() {
+        let day = DateInput::Day(NaiveDate::from_ymd_opt(2026, 10, 20).unwrap());
+        assert_eq!(rejection(entry_end(at(9, 0), Some(&day), None)), "endedAt needs a time of day");
+    }
+}
@@ -0,0 +1,317 @@
+use crate::events::ChangeFeed;
+use crate::services::task_service::{apply_workflow, TaskService};
+use axum::response::ErrorResponse;
+use chrono::NaiveDate;
+use futures_util::{stream, Stream, StreamExt};
+use mindvault_core::models::{AppDatabase, DbError};
+use mindvault_core::repository::task_repo::{TaskRepository, TaskVault};
+use mindvault_core::repository::workflow_repo::WorkflowRepository;
+use mindvault_core::transfer::{parse_import, ImportRow, TaskExportWriter};
+use mindvault_shared::dtos::task_dtos::{BulkCreateTaskRequest, UpdateTaskRequest};
+use mindvault_shared::dtos::transfer_dtos::{
+    ExportTasksParams, ImportColumnMapping, ImportTasksParams,
+};
+use mindvault_shared::models::tasks_model::Task;
+use mindvault_shared::models::transfer_model::{
+    EImportOutcome, ETransferFormat, ImportReport, ImportRowReport,
+};
+use mindvault_shared::utils::date_input::DatePreferences;
+use std::collections::{HashMap, HashSet};
+use std::io;
+use std::sync::Arc;
+use tracing::{error, info};
+
+pub(crate) struct TransferService {
+    task_repository: TaskRepository,
+    workflow_repository: WorkflowRepository,
+    task_service: TaskService,
+}
+
+fn service_error(context: &str, e: DbError) -> ErrorResponse {
+    let error_message = format!("{}: {:?}", context, e);
+    error!("{}", error_message);
+    ErrorResponse::from(error_message)
+}
+
+/// Tasks count as duplicates when both the name and the due day match
+type DuplicateKey = (String, Option<NaiveDate>);
+
+impl TransferService {
+    pub(crate) fn new(app_database: AppDatabase, change_feed: Arc<ChangeFeed>) -> Self {
+        Self {
+            task_repository: TaskRepository::new(app_database.clone()),
+            workflow_repository: WorkflowRepository::new(app_database.clone()),
+            task_service: TaskService::new(app_database, change_feed),
+        }
+    }
+
+    /// Streams the matching tasks in `format`, rendering each one as it comes off the cursor.
+    /// `params.due_date` must already be resolved with `dates`.
+    pub(crate) async fn export_tasks(
+        &self,
+        owner_id: i64,
+        format: ETransferFormat,
+        params: &ExportTasksParams,
+        dates: &DatePreferences,
+    ) -> Result<impl Stream<Item = Result<String, io::Error>> + Send + use<>, ErrorResponse> {
+        let cursor = self
+            .task_repository
+            .stream_tasks(TaskVault::User(owner_id), params.search_params(), dates)
+            .await
+            .map_err(|e| service_error("Error exporting tasks", e))?;
+        let writer = Arc::new(TaskExportWriter::new(format));
+
+        let header = stream::once({
+            let writer = writer.clone();
+            async move { writer.header().map_err(io::Error::other) }
+        });
+        let records = cursor.enumerate().map({
+            let writer = writer.clone();
+            move |(index, task)| {
+                let task = task.map_err(io::Error::other)?;
+                writer.record(index, &task).map_err(io::Error::other)
+            }
+        });
+        let footer = stream::once(async move { Ok(writer.footer()) });
+        Ok(header.chain(records).chain(footer))
+    }
+
+    /// Validates an upload row by row and, unless `dry_run` is set, applies it: rows whose
+    /// external id matches an existing task update that task, the rest are created in one
+    /// bulk insert. Due dates are read with `dates`, so rows with ambiguous dates are invalid
+    /// unless the user has set a date order. The inner error is a problem with the file as a
+    /// whole.
+    pub(crate) async fn import_tasks(
+        &self,
+        owner_id: i64,
+        format: ETransferFormat,
+        body: &str,
+        params: &ImportTasksParams,
+        dates: &DatePreferences,
+    ) -> Result<Result<ImportReport, String>, ErrorResponse> {
+        let rows = match parse_import(format, body, &ImportColumnMapping::from(params)) {
+            Ok(rows) => rows,
+            Err(message) => return Ok(Err(message)),
+        };
+        let rows: Vec<ImportRow> = rows
+            .into_iter()
+            .map(|row| ImportRow {
+                row: row.row,
+                task: row.task.and_then(|mut task| {
+                    if let Some(due_date) = task.due_date.take() {
+                        task.due_date = Some(due_date.resolve(dates)?);
+                    }
+                    Ok(task)
+                }),
+            })
+            .collect();
+        let tz = dates.tz();
+
+        let valid_tasks = || rows.iter().filter_map(|row| row.task.as_ref().ok());
+        let external_ids: Vec<String> = valid_tasks()
+            .filter_map(|task| task.external_id.clone())
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+        let existing_by_external_id: HashMap<String, Task> = if external_ids.is_empty() {
+            HashMap::new()
+        } else {
+            self.task_repository
+                .find_by_external_ids(TaskVault::User(owner_id), &external_ids)
+                .await
+                .map_err(|e| service_error("Error matching imported tasks", e))?
+                .into_iter()
+                .filter_map(|task| task.external_id.clone().map(|external_id| (external_id, task)))
+                .collect()
+        };
+
+        let names: Vec<String> = valid_tasks()
+            .map(|task| task.name.clone())
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+        let mut seen: HashSet<DuplicateKey> = self
+            .task_repository
+            .find_by_names(TaskVault::User(owner_id), &names)
+            .await
+            .map_err(|e| service_error("Error checking for duplicate tasks", e))?
+            .into_iter()
+            .map(|task| {
+                let due_day = task.due_day(tz);
+                (task.name, due_day)
+            })
+            .collect();
+        let mut seen_external_ids = HashSet::new();
+        // Moves are checked here, row by row, so the dry run reports them and the real run
+        // never stops partway through
+        let workflow = self
+            .workflow_repository
+            .find_by_vault(TaskVault::User(owner_id))
+            .await
+            .map_err(|e| service_error("Error finding workflow", e))?;
+
+        let total_rows = rows.len();
+        let mut reports = Vec::with_capacity(total_rows);
+        let mut to_create = Vec::new();
+        let mut create_reports = Vec::new();
+        let mut to_update = Vec::new();
+        for row in rows {
+            let mut task = match row.task {
+                Ok(task) => task,
+                Err(message) => {
+                    reports.push(ImportRowReport {
+                        row: row.row,
+                        outcome: EImportOutcome::Invalid,
+                        name: None,
+                        due_date: None,
+                        priority: None,
+                        status: None,
+                        external_id: None,
+                        task_id: None,
+                        message: Some(message),
+                    });
+                    continue;
+                }
+            };
+            let mut report = ImportRowReport {
+                row: row.row,
+                outcome: EImportOutcome::Valid,
+                name: Some(task.name.clone()),
+                due_date: task.due_date.as_ref().map(ToString::to_string),
+                priority: task.priority.clone(),
+                status: task.status.clone(),
+                external_id: task.external_id.clone(),
+                task_id: None,
+                message: None,
+            };
+
+            if let Some(external_id) = &task.external_id {
+                if !seen_external_ids.insert(external_id.clone()) {
+                    report.outcome = EImportOutcome::Duplicate;
+                    report.message = Some("Repeats the external id of an earlier row".to_string());
+                    reports.push(report);
+                    continue;
+                }
+                if let Some(existing) = existing_by_external_id.get(external_id) {
+                    report.task_id = Some(existing.id);
+                    match apply_workflow(
+                        workflow.as_ref(),
+                        Some(existing),
+                        &mut task.status,
+                        &mut task.workflow_status,
+                    ) {
+                        Ok(()) => {
+                            report.outcome = EImportOutcome::Matched;
+                            to_update.push((reports.len(), existing.id, task));
+                        }
+                        Err(denial) => {
+                            report.outcome = EImportOutcome::Invalid;
+                            report.message = Some(denial.to_string());
+                        }
+                    }
+                    reports.push(report);
+                    continue;
+                }
+            }
+
+            if let Err(denial) = apply_workflow(
+                workflow.as_ref(),
+                None,
+                &mut task.status,
+                &mut task.workflow_status,
+            ) {
+                report.outcome = EImportOutcome::Invalid;
+                report.message = Some(denial.to_string());
+                reports.push(report);
+                continue;
+            }
+            let due_day = task.due_date.as_ref().and_then(|due| due.day(tz));
+            if !seen.insert((task.name.clone(), due_day)) {
+                report.outcome = EImportOutcome::Duplicate;
+                report.message = Some("A task with this name and due date already exists".to_string());
+                reports.push(report);
+                continue;
+            }
+            create_reports.push(reports.len());
+            reports.push(report);
+            to_create.push(task);
+        }
+
+        if !params.dry_run {
+            if !to_create.is_empty() {
+                info!("Importing {} new tasks from {:?}", to_create.len(), format);
+                // Imports go to the private vault, where nothing is assigned or denied
+                let created = self
+                    .task_service
+                    .bulk_create_tasks(
+                        owner_id,
+                        None,
+                        BulkCreateTaskRequest { tasks: to_create },
+                        dates,
+                    )
+                    .await?;
+                match created {
+                    Ok(tasks) => {
+                        for (report_index, task) in create_reports.into_iter().zip(tasks) {
+                            reports[report_index].outcome = EImportOutcome::Created;
+                            reports[report_index].task_id = Some(task.id);
+                        }
+                    }
+                    Err(denial) => {
+                        for report_index in create_reports {
+                            reports[report_index].outcome = EImportOutcome::Invalid;
+                            reports[report_index].message = Some(denial.to_string());
+                        }
+                    }
+                }
+            }
+            for (report_index, task_id, task) in to_update {
+                let update = UpdateTaskRequest {
+                    name: Some(task.name),
+                    due_date: task.due_date,
+                    priority: task.priority,
+                    status: task.status,
+                    workflow_status: task.workflow_status,
+                    tags: Some(task.tags),
+                    recurrence: task.recurrence,
+                    estimate_minutes: task.estimate_minutes,
+                    notes: task.notes,
+                    assignee_id: None,
+                };
+                let report = &mut reports[report_index];
+                match self
+                    .task_service
+                    .update_task(owner_id, None, task_id, update, dates)
+                    .await?
+                {
+                    Ok(Some(_)) => report.outcome = EImportOutcome::Updated,
+                    Ok(None) => {
+                        report.outcome = EImportOutcome::Invalid;
+                        report.message = Some("Matched task was deleted during the import".to_string());
+                    }
+                    Err(denial) => {
+                        report.outcome = EImportOutcome::Invalid;
+                        report.message = Some(denial.to_string());
+                    }
+                }
+            }
+        }
+
+        let count = |outcome: EImportOutcome| {
+            reports
+                .iter()
+                .filter(|report| report.outcome == outcome)
+                .count()
+        };
+        Ok(Ok(ImportReport {
+            format,
+            dry_run: params.dry_run,
+            total_rows,
+            created: count(EImportOutcome::Created),
+            updated: count(EImportOutcome::Updated),
+            duplicates: count(EImportOutcome::Duplicate),
+            invalid: count(EImportOutcome::Invalid),
+            rows: reports,
+        }))
+    }
+}
@@ -0,0 +1,59 @@
+use axum::response::ErrorResponse;
+use mindvault_core::models::AppDatabase;
+use mindvault_core::repository::user_repo::UserRepository;
+use mindvault_shared::dtos::user_dtos::UpdatePreferencesRequest;
+use mindvault_shared::models::users_model::UserResponse;
+use mindvault_shared::utils::time_zone::parse_time_zone;
+use tracing::error;
+
+/// The calling user's own account and preferences
+pub(crate) struct UserService {
+    user_repository: UserRepository,
+}
+
+impl UserService {
+    pub(crate) fn new(app_database: AppDatabase) -> Self {
+        Self {
+            user_repository: UserRepository::new(app_database),
+        }
+    }
+
+    pub(crate) async fn get_user(
+        &self,
+        user_id: i64,
+    ) -> Result<Option<UserResponse>, ErrorResponse> {
+        match self.user_repository.find_by_id(user_id).await {
+            Ok(user) => Ok(user.map(UserResponse::from)),
+            Err(e) => {
+                let error_message = format!("Error finding user: {:?}", e);
+                error!("{}", error_message);
+                Err(ErrorResponse::from(error_message))
+            }
+        }
+    }
+
+    /// Saves the time zone under its canonical IANA name. The inner error is an unknown time
+    /// zone.
+    pub(crate) async fn update_preferences(
+        &self,
+        user_id: i64,
+        request: UpdatePreferencesRequest,
+    ) -> Result<Result<Option<UserResponse>, String>, ErrorResponse> {
+        let time_zone = match request.time_zone.as_deref().map(parse_time_zone).transpose() {
+            Ok(tz) => tz.map(|tz| tz.name().to_string()),
+            Err(message) => return Ok(Err(message)),
+        };
+        let updated = self
+            .user_repository
+            .update_preferences(user_id, time_zone, request.date_order)
+            .await;
+        match updated {
+            Ok(user) => Ok(Ok(user.map(UserResponse::from))),
+            Err(e) => {
+                let error_message = format!("Error updating preferences: {:?}", e);
+                error!("{}", error_message);
+                Err(ErrorResponse::from(error_message))
+            }
+        }
+    }
+}
@@ -0,0 +1,154 @@
+use crate::webhooks::WebhookDispatcher;
+use axum::response::ErrorResponse;
+use mindvault_core::models::{AppDatabase, DbError};
+use mindvault_core::repository::webhook_repo::WebhookRepository;
+use mindvault_shared::dtos::webhook_dtos::{
+    CreateWebhookRequest, UpdateWebhookRequest, WebhookDeliverySearchParams,
+};
+use mindvault_shared::models::webhooks_model::{
+    WebhookDeliveryResponse, WebhookSubscriptionResponse,
+};
+use tracing::error;
+
+pub(crate) struct WebhookService {
+    webhook_repository: WebhookRepository,
+    dispatcher: WebhookDispatcher,
+}
+
+fn service_error(context: &str, e: DbError) -> ErrorResponse {
+    let error_message = format!("{}: {:?}", context, e);
+    error!("{}", error_message);
+    ErrorResponse::from(error_message)
+}
+
+impl WebhookService {
+    pub(crate) fn new(app_database: AppDatabase) -> Self {
+        Self {
+            webhook_repository: WebhookRepository::new(app_database.clone()),
+            dispatcher: WebhookDispatcher::new(app_database),
+        }
+    }
+
+    pub(crate) async fn get_subscriptions(
+        &self,
+        owner_id: i64,
+    ) -> Result<Vec<WebhookSubscriptionResponse>, ErrorResponse> {
+        match self.webhook_repository.find_subscriptions(owner_id).await {
+            Ok(subscriptions) => Ok(WebhookSubscriptionResponse::from_vec(subscriptions)),
+            Err(e) => Err(service_error("Error fetching webhooks", e)),
+        }
+    }
+
+    pub(crate) async fn get_subscription(
+        &self,
+        owner_id: i64,
+        id: i64,
+    ) -> Result<Option<WebhookSubscriptionResponse>, ErrorResponse> {
+        match self.webhook_repository.find_subscription_by_id(owner_id, id).await {
+            Ok(subscription) => Ok(subscription.map(WebhookSubscriptionResponse::from)),
+            Err(e) => Err(service_error("Error finding webhook", e)),
+        }
+    }
+
+    pub(crate) async fn create_subscription(
+        &self,
+        owner_id: i64,
+        request: CreateWebhookRequest,
+    ) -> Result<WebhookSubscriptionResponse, ErrorResponse> {
+        match self.webhook_repository.create_subscription(owner_id, request).await {
+            Ok(subscription) => Ok(WebhookSubscriptionResponse::from(subscription)),
+            Err(e) => Err(service_error("Error creating webhook", e)),
+        }
+    }
+
+    pub(crate) async fn update_subscription(
+        &self,
+        owner_id: i64,
+        id: i64,
+        request: UpdateWebhookRequest,
+    ) -> Result<Option<WebhookSubscriptionResponse>, ErrorResponse> {
+        match self
+            .webhook_repository
+            .update_subscription_by_id(owner_id, id, request)
+            .await
+        {
+            Ok(subscription) => Ok(subscription.map(WebhookSubscriptionResponse::from)),
+            Err(e) => Err(service_error("Error updating webhook", e)),
+        }
+    }
+
+    pub(crate) async fn delete_subscription(
+        &self,
+        owner_id: i64,
+        id: i64,
+    ) -> Result<bool, ErrorResponse> {
+        match self.webhook_repository.soft_delete_subscription_by_id(owner_id, id).await {
+            Ok(deleted) => Ok(deleted),
+            Err(e) => Err(service_error("Error deleting webhook", e)),
+        }
+    }
+
+    pub(crate) async fn search_deliveries(
+        &self,
+        owner_id: i64,
+        params: WebhookDeliverySearchParams,
+    ) -> Result<Vec<WebhookDeliveryResponse>, ErrorResponse> {
+        match self.webhook_repository.search_deliveries(owner_id, params).await {
+            Ok(deliveries) => Ok(WebhookDeliveryResponse::from_vec(deliveries)),
+            Err(e) => Err(service_error("Error searching webhook deliveries", e)),
+        }
+    }
+
+    pub(crate) async fn get_delivery(
+        &self,
+        owner_id: i64,
+        id: i64,
+    ) -> Result<Option<WebhookDeliveryResponse>, ErrorResponse> {
+        match self.webhook_repository.find_delivery_by_id(owner_id, id).await {
+            Ok(delivery) => Ok(delivery.map(WebhookDeliveryResponse::from)),
+            Err(e) => Err(service_error("Error finding webhook delivery", e)),
+        }
+    }
+
+    /// Requeues an undelivered delivery and attempts it immediately with the stored payload
+    pub(crate) async fn replay_delivery(
+        &self,
+        owner_id: i64,
+        id: i64,
+    ) -> Result<Option<WebhookDeliveryResponse>, ErrorResponse> {
+        let replay = async {
+            match self.webhook_repository.requeue_delivery(owner_id, id).await? {
+                Some(delivery) => self.dispatcher.deliver(delivery).await,
+                None => Ok(None),
+            }
+        };
+
+        match replay.await {
+            Ok(delivery) => Ok(delivery.map(WebhookDeliveryResponse::from)),
+            Err(e) => Err(service_error("Error replaying webhook delivery", e)),
+        }
+    }
+
+    /// Requeues every dead-lettered delivery of a subscription for the delivery job
+    pub(crate) async fn replay_dead_letters(
+        &self,
+        owner_id: i64,
+        subscription_id: i64,
+    ) -> Result<u64, ErrorResponse> {
+        match self
+            .webhook_repository
+            .requeue_dead_letters(owner_id, subscription_id)
+            .await
+        {
+            Ok(requeued) => Ok(requeued),
+            Err(e) => Err(service_error("Error replaying webhook deliveries", e)),
+        }
+    }
+
+    pub(crate) async fn deliver_pending(&self) -> Result<usize, ErrorResponse> {
+        self.dispatcher
+            .deliver_pending()
+            .await
+            .map_err(|e| service_error("Error delivering webhooks", e))
+    }
+}
@@ -0,0 +1,118 @@
+use crate::auth::workspace_access::{EAccessDenial, TaskAccess, WorkspaceAuthorizer};
+use axum::response::ErrorResponse;
+use mindvault_core::models::{AppDatabase, DbError};
+use mindvault_core::repository::task_repo::TaskRepository;
+use mindvault_core::repository::workflow_repo::WorkflowRepository;
+use mindvault_shared::dtos::workflow_dtos::SetWorkflowRequest;
+use mindvault_shared::models::workflows_model::WorkflowResponse;
+use mindvault_shared::models::workspaces_model::EWorkspaceRole;
+use tracing::error;
+
+/// The custom workflow of the caller's private vault, or of `workspace_id` when one is given.
+/// Any member may read a workspace's workflow; only admins may change it.
+pub(crate) struct WorkflowService {
+    workflow_repository: WorkflowRepository,
+    task_repository: TaskRepository,
+    authorizer: WorkspaceAuthorizer,
+}
+
+fn service_error(context: &str, e: DbError) -> ErrorResponse {
+    let error_message = format!("{}: {:?}", context, e);
+    error!("{}", error_message);
+    ErrorResponse::from(error_message)
+}
+
+impl WorkflowService {
+    pub(crate) fn new(app_database: AppDatabase) -> Self {
+        Self {
+            workflow_repository: WorkflowRepository::new(app_database.clone()),
+            task_repository: TaskRepository::new(app_database.clone()),
+            authorizer: WorkspaceAuthorizer::new(app_database),
+        }
+    }
+
+    async fn authorize(
+        &self,
+        user_id: i64,
+        workspace_id: Option<i64>,
+        required: EWorkspaceRole,
+    ) -> Result<Result<TaskAccess, EAccessDenial>, ErrorResponse> {
+        self.authorizer
+            .authorize_tasks(user_id, workspace_id, required)
+            .await
+            .map_err(|e| service_error("Error checking workflow access", e))
+    }
+
+    pub(crate) async fn get_workflow(
+        &self,
+        user_id: i64,
+        workspace_id: Option<i64>,
+    ) -> Result<Result<Option<WorkflowResponse>, EAccessDenial>, ErrorResponse> {
+        let access = match self.authorize(user_id, workspace_id, EWorkspaceRole::Viewer).await? {
+            Ok(access) => access,
+            Err(denial) => return Ok(Err(denial)),
+        };
+
+        match self.workflow_repository.find_by_vault(access.vault).await {
+            Ok(workflow) => Ok(Ok(workflow.map(WorkflowResponse::from))),
+            Err(e) => Err(service_error("Error finding workflow", e)),
+        }
+    }
+
+    /// Replaces the workflow. Tasks in a status the new workflow no longer has fall back to
+    /// the first status of their category.
+    pub(crate) async fn set_workflow(
+        &self,
+        user_id: i64,
+        workspace_id: Option<i64>,
+        request: SetWorkflowRequest,
+    ) -> Result<Result<WorkflowResponse, EAccessDenial>, ErrorResponse> {
+        let access = match self.authorize(user_id, workspace_id, EWorkspaceRole::Admin).await? {
+            Ok(access) => access,
+            Err(denial) => return Ok(Err(denial)),
+        };
+
+        let workflow = match self
+            .workflow_repository
+            .set_workflow(access.vault, user_id, request)
+            .await
+        {
+            Ok(workflow) => workflow,
+            Err(e) => return Err(service_error("Error saving workflow", e)),
+        };
+        let names: Vec<String> = workflow
+            .statuses
+            .iter()
+            .map(|status| status.name.clone())
+            .collect();
+        match self
+            .task_repository
+            .clear_workflow_statuses(access.vault, &names)
+            .await
+        {
+            Ok(_) => Ok(Ok(WorkflowResponse::from(workflow))),
+            Err(e) => Err(service_error("Error updating task workflow statuses", e)),
+        }
+    }
+
+    /// Removes the workflow; its tasks keep their built-in status
+    pub(crate) async fn delete_workflow(
+        &self,
+        user_id: i64,
+        workspace_id: Option<i64>,
+    ) -> Result<Result<bool, EAccessDenial>, ErrorResponse> {
+        let access = match self.authorize(user_id, workspace_id, EWorkspaceRole::Admin).await? {
+            Ok(access) => access,
+            Err(denial) => return Ok(Err(denial)),
+        };
+
+        let deleted = match self.workflow_repository.delete_workflow(access.vault).await {
+            Ok(deleted) => deleted,
+            Err(e) => return Err(service_error("Error deleting workflow", e)),
+        };
+        match self.task_repository.clear_workflow_statuses(access.vault, &[]).await {
+            Ok(_) => Ok(Ok(deleted)),
+            Err(e) => Err(service_error("Error updating task workflow statuses", e)),
+        }
+    }
+}
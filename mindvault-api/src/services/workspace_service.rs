@@ -0,0 +1,199 @@
+use crate::auth::workspace_access::{EAccessDenial, WorkspaceAuthorizer};
+use axum::response::ErrorResponse;
+use mindvault_core::models::{AppDatabase, DbError};
+use mindvault_core::repository::user_repo::UserRepository;
+use mindvault_core::repository::workspace_repo::WorkspaceRepository;
+use mindvault_shared::dtos::workspace_dtos::{
+    CreateWorkspaceRequest, UpdateWorkspaceRequest, WorkspaceMemberRequest,
+};
+use mindvault_shared::models::workspaces_model::{EWorkspaceRole, Workspace, WorkspaceResponse};
+use tracing::error;
+
+/// Workspace CRUD and membership. Reading a workspace needs any role, everything else needs
+/// Admin, except that members may always remove themselves. The inner `Err` is the reason the
+/// caller was turned away.
+pub(crate) struct WorkspaceService {
+    workspace_repository: WorkspaceRepository,
+    user_repository: UserRepository,
+    authorizer: WorkspaceAuthorizer,
+}
+
+fn service_error(context: &str, e: DbError) -> ErrorResponse {
+    let error_message = format!("{}: {:?}", context, e);
+    error!("{}", error_message);
+    ErrorResponse::from(error_message)
+}
+
+/// Refuses to leave a workspace without an admin
+fn keeps_an_admin(
+    workspace: &Workspace,
+    user_id: i64,
+    new_role: Option<EWorkspaceRole>,
+) -> Result<(), EAccessDenial> {
+    let losing_admin = workspace.role_of(user_id) == Some(EWorkspaceRole::Admin)
+        && new_role != Some(EWorkspaceRole::Admin);
+    if losing_admin && workspace.admin_count() == 1 {
+        return Err(EAccessDenial::Rejected(format!(
+            "Workspace {} needs at least one other admin first",
+            workspace.id
+        )));
+    }
+    Ok(())
+}
+
+impl WorkspaceService {
+    pub(crate) fn new(app_database: AppDatabase) -> Self {
+        Self {
+            workspace_repository: WorkspaceRepository::new(app_database.clone()),
+            user_repository: UserRepository::new(app_database.clone()),
+            authorizer: WorkspaceAuthorizer::new(app_database),
+        }
+    }
+
+    async fn authorize(
+        &self,
+        user_id: i64,
+        workspace_id: i64,
+        required: EWorkspaceRole,
+    ) -> Result<Result<Workspace, EAccessDenial>, ErrorResponse> {
+        self.authorizer
+            .authorize_workspace(user_id, workspace_id, required)
+            .await
+            .map_err(|e| service_error("Error checking workspace access", e))
+    }
+
+    /// Workspaces the caller is a member of
+    pub(crate) async fn get_workspaces(
+        &self,
+        user_id: i64,
+    ) -> Result<Vec<WorkspaceResponse>, ErrorResponse> {
+        match self.workspace_repository.find_for_member(user_id).await {
+            Ok(workspaces) => Ok(WorkspaceResponse::from_vec(workspaces)),
+            Err(e) => Err(service_error("Error finding workspaces", e)),
+        }
+    }
+
+    pub(crate) async fn get_by_id(
+        &self,
+        user_id: i64,
+        id: i64,
+    ) -> Result<Result<WorkspaceResponse, EAccessDenial>, ErrorResponse> {
+        Ok(self
+            .authorize(user_id, id, EWorkspaceRole::Viewer)
+            .await?
+            .map(WorkspaceResponse::from))
+    }
+
+    /// Creates a workspace with the caller as its admin
+    pub(crate) async fn create_workspace(
+        &self,
+        user_id: i64,
+        request: CreateWorkspaceRequest,
+    ) -> Result<WorkspaceResponse, ErrorResponse> {
+        match self.workspace_repository.create_workspace(user_id, request).await {
+            Ok(workspace) => Ok(WorkspaceResponse::from(workspace)),
+            Err(e) => Err(service_error("Error creating workspace", e)),
+        }
+    }
+
+    pub(crate) async fn update_workspace(
+        &self,
+        user_id: i64,
+        id: i64,
+        request: UpdateWorkspaceRequest,
+    ) -> Result<Result<WorkspaceResponse, EAccessDenial>, ErrorResponse> {
+        if let Err(denial) = self.authorize(user_id, id, EWorkspaceRole::Admin).await? {
+            return Ok(Err(denial));
+        }
+        match self.workspace_repository.update_workspace_by_id(id, request).await {
+            Ok(Some(workspace)) => Ok(Ok(WorkspaceResponse::from(workspace))),
+            Ok(None) => Ok(Err(EAccessDenial::UnknownWorkspace(id))),
+            Err(e) => Err(service_error("Error updating workspace", e)),
+        }
+    }
+
+    /// Soft deletes the workspace; its tasks become unreachable along with it
+    pub(crate) async fn delete_workspace(
+        &self,
+        user_id: i64,
+        id: i64,
+    ) -> Result<Result<(), EAccessDenial>, ErrorResponse> {
+        if let Err(denial) = self.authorize(user_id, id, EWorkspaceRole::Admin).await? {
+            return Ok(Err(denial));
+        }
+        match self.workspace_repository.soft_delete_by_id(id).await {
+            Ok(true) => Ok(Ok(())),
+            Ok(false) => Ok(Err(EAccessDenial::UnknownWorkspace(id))),
+            Err(e) => Err(service_error("Error deleting workspace", e)),
+        }
+    }
+
+    /// Adds a member by username, or changes an existing member's role
+    pub(crate) async fn put_member(
+        &self,
+        user_id: i64,
+        id: i64,
+        request: WorkspaceMemberRequest,
+    ) -> Result<Result<WorkspaceResponse, EAccessDenial>, ErrorResponse> {
+        let workspace = match self.authorize(user_id, id, EWorkspaceRole::Admin).await? {
+            Ok(workspace) => workspace,
+            Err(denial) => return Ok(Err(denial)),
+        };
+        let member = match self.user_repository.find_by_username(&request.username).await {
+            Ok(Some(member)) if !member.disabled => member,
+            Ok(_) => {
+                return Ok(Err(EAccessDenial::Rejected(format!(
+                    "User '{}' not found or disabled",
+                    request.username.trim()
+                ))));
+            }
+            Err(e) => return Err(service_error("Error finding user", e)),
+        };
+        if let Err(denial) = keeps_an_admin(&workspace, member.id, Some(request.role)) {
+            return Ok(Err(denial));
+        }
+
+        match self
+            .workspace_repository
+            .upsert_member(id, member.id, request.role)
+            .await
+        {
+            Ok(Some(workspace)) => Ok(Ok(WorkspaceResponse::from(workspace))),
+            Ok(None) => Ok(Err(EAccessDenial::UnknownWorkspace(id))),
+            Err(e) => Err(service_error("Error updating workspace members", e)),
+        }
+    }
+
+    /// Removes a member; admins can remove anyone, other members only themselves
+    pub(crate) async fn remove_member(
+        &self,
+        user_id: i64,
+        id: i64,
+        member_id: i64,
+    ) -> Result<Result<WorkspaceResponse, EAccessDenial>, ErrorResponse> {
+        let required = if member_id == user_id {
+            EWorkspaceRole::Viewer
+        } else {
+            EWorkspaceRole::Admin
+        };
+        let workspace = match self.authorize(user_id, id, required).await? {
+            Ok(workspace) => workspace,
+            Err(denial) => return Ok(Err(denial)),
+        };
+        if workspace.role_of(member_id).is_none() {
+            return Ok(Err(EAccessDenial::Rejected(format!(
+                "User {} is not a member of workspace {}",
+                member_id, id
+            ))));
+        }
+        if let Err(denial) = keeps_an_admin(&workspace, member_id, None) {
+            return Ok(Err(denial));
+        }
+
+        match self.workspace_repository.remove_member(id, member_id).await {
+            Ok(Some(workspace)) => Ok(Ok(WorkspaceResponse::from(workspace))),
+            Ok(None) => Ok(Err(EAccessDenial::UnknownWorkspace(id))),
+            Err(e) => Err(service_error("Error updating workspace members", e)),
+        }
+    }
+}
@@ -0,0 +1,580 @@
+use crate::notifications::webhook::{sign_payload, EVENT_HEADER, SIGNATURE_HEADER};
+use bson::DateTime as BsonDateTime;
+use chrono::Utc;
+use mindvault_core::models::{AppDatabase, DbError};
+use mindvault_core::notification_config::NotificationConfig;
+use mindvault_core::repository::webhook_repo::{NewWebhookDelivery, WebhookRepository};
+use mindvault_core::repository::workspace_repo::WorkspaceRepository;
+use mindvault_core::task_watcher::{ETaskStreamChange, ResumeToken, TaskChangeStream};
+use mindvault_shared::models::notifications_model::{DeliveryAttempt, EDeliveryState};
+use mindvault_shared::models::tasks_model::{ETaskStatus, Task, TaskResponse};
+use mindvault_shared::models::webhooks_model::{
+    ETaskEvent, WebhookDelivery, WebhookSubscription,
+};
+use mindvault_shared::models::workspaces_model::Workspace;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tracing::{error, info, warn};
+
+pub(crate) const DELIVERY_HEADER: &str = "X-MindVault-Delivery";
+const SEND_TIMEOUT: Duration = Duration::from_secs(15);
+const DELIVERY_BATCH_SIZE: i64 = 100;
+const REOPEN_DELAY: Duration = Duration::from_secs(5);
+
+/// A task lifecycle event with the task as it was before and after the change
+pub(crate) struct TaskChange {
+    pub(crate) event: ETaskEvent,
+    pub(crate) task_id: i64,
+    pub(crate) before: Option<Task>,
+    pub(crate) after: Option<Task>,
+}
+
+impl TaskChange {
+    pub(crate) fn created(task: Task) -> Vec<TaskChange> {
+        vec![TaskChange {
+            event: ETaskEvent::Created,
+            task_id: task.id,
+            before: None,
+            after: Some(task),
+        }]
+    }
+
+    /// `task.updated`, plus `task.completed` when the update moved the task to Completed
+    pub(crate) fn updated(before: Task, after: Task) -> Vec<TaskChange> {
+        let completed = matches!(after.status, ETaskStatus::Completed)
+            && !matches!(before.status, ETaskStatus::Completed);
+        let mut changes = vec![TaskChange {
+            event: ETaskEvent::Updated,
+            task_id: after.id,
+            before: Some(before.clone()),
+            after: Some(after.clone()),
+        }];
+        if completed {
+            changes.push(TaskChange {
+                event: ETaskEvent::Completed,
+                task_id: after.id,
+                before: Some(before),
+                after: Some(after),
+            });
+        }
+        changes
+    }
+
+    pub(crate) fn deleted(before: Task) -> Vec<TaskChange> {
+        vec![TaskChange {
+            event: ETaskEvent::Deleted,
+            task_id: before.id,
+            before: Some(before),
+            after: None,
+        }]
+    }
+
+    /// The changes a task change stream event stands for, as the task service records them
+    fn from_stream(change: ETaskStreamChange) -> Vec<TaskChange> {
+        match change {
+            ETaskStreamChange::Inserted(task) => TaskChange::created(task),
+            ETaskStreamChange::Updated {
+                task,
+                before,
+                changed_fields,
+            } => {
+                let changed = |field: &str| changed_fields.iter().any(|f| f == field);
+                let before = before.map(|before| *before);
+                if changed("deleted") && task.deleted == Some(true) {
+                    return TaskChange::deleted(before.unwrap_or(task));
+                }
+                match before {
+                    Some(before) => TaskChange::updated(before, task),
+                    // Changes made before pre-images were turned on have no earlier version
+                    None => {
+                        let completed = changed("status") && task.status == ETaskStatus::Completed;
+                        let mut changes = vec![TaskChange {
+                            event: ETaskEvent::Updated,
+                            task_id: task.id,
+                            before: None,
+                            after: Some(task.clone()),
+                        }];
+                        if completed {
+                            changes.push(TaskChange {
+                                event: ETaskEvent::Completed,
+                                task_id: task.id,
+                                before: None,
+                                after: Some(task),
+                            });
+                        }
+                        changes
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct TaskEventPayload {
+    id: i64,
+    event: ETaskEvent,
+    occurred_at: String,
+    task_id: i64,
+    before: Option<TaskResponse>,
+    after: Option<TaskResponse>,
+}
+
+/// Writes task events to the webhook outbox for every subscription that wants them and whose
+/// owner may see the task
+pub(crate) struct TaskEventPublisher {
+    webhook_repository: WebhookRepository,
+    workspace_repository: WorkspaceRepository,
+}
+
+impl TaskEventPublisher {
+    pub(crate) fn new(app_database: AppDatabase) -> Self {
+        Self {
+            webhook_repository: WebhookRepository::new(app_database.clone()),
+            workspace_repository: WorkspaceRepository::new(app_database),
+        }
+    }
+
+    /// `Task::audience` of the changed task, with workspace members as they are now
+    async fn audience(
+        &self,
+        change: &TaskChange,
+        workspaces: &mut HashMap<i64, Option<Workspace>>,
+    ) -> Result<Vec<i64>, DbError> {
+        let Some(task) = change.after.as_ref().or(change.before.as_ref()) else {
+            return Ok(Vec::new());
+        };
+        let Some(workspace_id) = task.workspace_id else {
+            return Ok(task.audience(None));
+        };
+        let workspace = match workspaces.get(&workspace_id) {
+            Some(workspace) => workspace.clone(),
+            None => {
+                let workspace = self.workspace_repository.find_by_id(workspace_id).await?;
+                workspaces.insert(workspace_id, workspace.clone());
+                workspace
+            }
+        };
+        Ok(task.audience(workspace.as_ref()))
+    }
+
+    /// Queues the changes; failures are logged rather than returned because the task
+    /// change they describe has already been stored
+    pub(crate) async fn publish(&self, changes: Vec<TaskChange>) {
+        if changes.is_empty() {
+            return;
+        }
+        if let Err(e) = self.enqueue(changes, None).await {
+            error!("Failed to queue task webhook events: {:?}", e);
+        }
+    }
+
+    /// Queues the changes for every subscription that wants them. With a `change_id`, a
+    /// subscription that already has deliveries for that change is left alone.
+    async fn enqueue(
+        &self,
+        changes: Vec<TaskChange>,
+        change_id: Option<&str>,
+    ) -> Result<(), DbError> {
+        let subscriptions = self.webhook_repository.find_active_subscriptions().await?;
+        let mut workspaces = HashMap::new();
+        let mut addressed: Vec<(TaskChange, Vec<&WebhookSubscription>)> = Vec::new();
+        for change in changes {
+            let audience = self.audience(&change, &mut workspaces).await?;
+            let recipients: Vec<&WebhookSubscription> = subscriptions
+                .iter()
+                .filter(|subscription| subscription.receives(change.event, &audience))
+                .collect();
+            if !recipients.is_empty() {
+                addressed.push((change, recipients));
+            }
+        }
+        if addressed.is_empty() {
+            return Ok(());
+        }
+
+        let first_event_id = self
+            .webhook_repository
+            .reserve_event_ids(addressed.len() as i64)
+            .await?;
+        let occurred_at = Utc::now().to_rfc3339();
+        let mut deliveries = Vec::new();
+        for (index, (change, recipients)) in addressed.into_iter().enumerate() {
+            let event_id = first_event_id + index as i64;
+            let payload = TaskEventPayload {
+                id: event_id,
+                event: change.event,
+                occurred_at: occurred_at.clone(),
+                task_id: change.task_id,
+                before: change.before.map(TaskResponse::from),
+                after: change.after.map(TaskResponse::from),
+            };
+            let payload = serde_json::to_string(&payload)
+                .map_err(|e| DbError::InternalError(e.to_string()))?;
+            for subscription in recipients {
+                deliveries.push(NewWebhookDelivery {
+                    subscription_id: subscription.id,
+                    event_id,
+                    event: change.event,
+                    task_id: change.task_id,
+                    payload: payload.clone(),
+                    change_id: change_id.map(str::to_string),
+                });
+            }
+        }
+
+        match self.webhook_repository.enqueue_deliveries(deliveries).await {
+            Ok(queued) => info!("Queued {} task webhook deliveries", queued.len()),
+            // Read again after a restart or by another API process; the rest went in
+            Err(e) if e.is_duplicate_key() => {
+                info!("Task webhook deliveries for change {:?} were already queued", change_id)
+            }
+            Err(e) => return Err(e),
+        }
+        Ok(())
+    }
+}
+
+/// Fills the webhook outbox from the tasks change stream, so a task write and its webhook
+/// events cannot get separated by a crash, and writes from other processes queue webhooks too.
+///
+/// The relay stores how far it has read after queueing each change and resumes from there
+/// on the next start. Without change streams or pre-images (MongoDB before 6.0, or a
+/// standalone server) it stays inactive and the task service queues its own changes.
+pub(crate) struct WebhookOutboxRelay {
+    publisher: TaskEventPublisher,
+    webhook_repository: WebhookRepository,
+    active: AtomicBool,
+}
+
+impl WebhookOutboxRelay {
+    /// Creates the relay and starts following the change stream if it can be opened
+    pub(crate) async fn start(app_database: AppDatabase) -> Arc<Self> {
+        let relay = Arc::new(Self {
+            publisher: TaskEventPublisher::new(app_database.clone()),
+            webhook_repository: WebhookRepository::new(app_database.clone()),
+            active: AtomicBool::new(false),
+        });
+        match relay.open(&app_database).await {
+            Ok(stream) => {
+                info!("Webhook outbox following MongoDB change streams");
+                relay.active.store(true, Ordering::SeqCst);
+                tokio::spawn(relay.clone().follow(app_database, stream));
+            }
+            Err(e) => {
+                info!("Change stream pre-images unavailable ({}); queueing webhooks in-process", e);
+            }
+        }
+        relay
+    }
+
+    /// Whether task changes reach the outbox through the change stream
+    pub(crate) fn is_active(&self) -> bool {
+        self.active.load(Ordering::SeqCst)
+    }
+
+    async fn open(&self, app_database: &AppDatabase) -> Result<TaskChangeStream, DbError> {
+        self.webhook_repository.ensure_change_index().await?;
+        let resume_after = self.webhook_repository.find_relay_resume_token().await?;
+        if resume_after.is_none() {
+            return TaskChangeStream::open_with_before(app_database, None).await;
+        }
+        match TaskChangeStream::open_with_before(app_database, resume_after).await {
+            Ok(stream) => Ok(stream),
+            Err(e) => {
+                let error_message = format!(
+                    "Could not resume the webhook outbox relay, changes since it stopped are not \
+                     queued: {:?}",
+                    e
+                );
+                error!("{}", error_message);
+                TaskChangeStream::open_with_before(app_database, None).await
+            }
+        }
+    }
+
+    async fn follow(self: Arc<Self>, app_database: AppDatabase, mut stream: TaskChangeStream) {
+        loop {
+            let result = match stream.next().await {
+                Some(Ok(change)) => {
+                    let change_id = stream.change_id().map(str::to_string);
+                    self.relay(change, change_id, stream.resume_token()).await
+                }
+                Some(Err(e)) => Err(e),
+                None => {
+                    warn!("Task change stream was invalidated, queueing webhooks in-process");
+                    self.active.store(false, Ordering::SeqCst);
+                    return;
+                }
+            };
+            let Err(e) = result else {
+                continue;
+            };
+            // Reopening from the stored token reads the failed change again
+            warn!("Webhook outbox relay failed: {:?}", e);
+            tokio::time::sleep(REOPEN_DELAY).await;
+            let reopened = match self.webhook_repository.find_relay_resume_token().await {
+                Ok(resume_after) => {
+                    TaskChangeStream::open_with_before(&app_database, resume_after).await
+                }
+                Err(e) => Err(e),
+            };
+            match reopened {
+                Ok(reopened) => stream = reopened,
+                Err(e) => {
+                    let error_message = format!(
+                        "Could not reopen the webhook outbox relay, queueing webhooks in-process: \
+                         {:?}",
+                        e
+                    );
+                    warn!("{}", error_message);
+                    self.active.store(false, Ordering::SeqCst);
+                    return;
+                }
+            }
+        }
+    }
+
+    /// Queues one change, then records that the stream has been read past it
+    async fn relay(
+        &self,
+        change: ETaskStreamChange,
+        change_id: Option<String>,
+        token: Option<ResumeToken>,
+    ) -> Result<(), DbError> {
+        self.publisher
+            .enqueue(TaskChange::from_stream(change), change_id.as_deref())
+            .await?;
+        if let Some(token) = token {
+            self.webhook_repository.save_relay_resume_token(&token).await?;
+        }
+        Ok(())
+    }
+}
+
+/// Sends queued task webhooks, retrying with the notification backoff settings
+pub(crate) struct WebhookDispatcher {
+    webhook_repository: WebhookRepository,
+    config: NotificationConfig,
+    http_client: reqwest::Client,
+}
+
+impl WebhookDispatcher {
+    pub(crate) fn new(app_database: AppDatabase) -> Self {
+        let http_client = reqwest::Client::builder()
+            .timeout(SEND_TIMEOUT)
+            .build()
+            .expect("HTTP client configuration is valid");
+        Self {
+            webhook_repository: WebhookRepository::new(app_database),
+            config: NotificationConfig::from_env(),
+            http_client,
+        }
+    }
+
+    /// Attempts every delivery whose next attempt is due and returns how many were attempted
+    pub(crate) async fn deliver_pending(&self) -> Result<usize, DbError> {
+        let deliveries = self
+            .webhook_repository
+            .find_ready_deliveries(BsonDateTime::now(), DELIVERY_BATCH_SIZE)
+            .await?;
+        let attempted = deliveries.len();
+        for delivery in deliveries {
+            self.deliver(delivery).await?;
+        }
+        Ok(attempted)
+    }
+
+    async fn send(&self, delivery: &WebhookDelivery) -> Result<(), String> {
+        let subscription = self
+            .webhook_repository
+            .find_subscription_for_delivery(delivery.subscription_id)
+            .await
+            .map_err(|e| e.to_string())?
+            .ok_or_else(|| "subscription no longer exists".to_string())?;
+        if !subscription.enabled {
+            return Err("subscription is disabled".to_string());
+        }
+
+        let mut request = self
+            .http_client
+            .post(&subscription.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header(EVENT_HEADER, delivery.event.as_str())
+            .header(DELIVERY_HEADER, delivery.id.to_string());
+        if let Some(secret) = &subscription.secret {
+            request = request.header(
+                SIGNATURE_HEADER,
+                sign_payload(secret, delivery.payload.as_bytes()),
+            );
+        }
+
+        let response = request
+            .body(delivery.payload.clone())
+            .send()
+            .await
+            .map_err(|e| e.to_string())?;
+        let status = response.status();
+        if status.is_success() {
+            Ok(())
+        } else {
+            Err(format!("webhook responded with {}", status))
+        }
+    }
+
+    /// Makes one attempt at a delivery and records the outcome in its log
+    pub(crate) async fn deliver(
+        &self,
+        delivery: WebhookDelivery,
+    ) -> Result<Option<WebhookDelivery>, DbError> {
+        let attempted_at = Utc::now();
+        let started = Instant::now();
+        let result = self.send(&delivery).await;
+
+        let attempts = delivery.attempts.len() as u32 + 1;
+        let (state, next_attempt_at) = match &result {
+            Ok(()) => (EDeliveryState::Delivered, None),
+            Err(_) if attempts >= self.config.max_attempts => (EDeliveryState::DeadLetter, None),
+            Err(_) => {
+                let delay = self.config.retry_delay_seconds(attempts);
+                let next = Utc::now() + chrono::Duration::seconds(delay as i64);
+                (EDeliveryState::Pending, Some(BsonDateTime::from(next)))
+            }
+        };
+        if let Err(e) = &result {
+            warn!(
+                "Webhook delivery {} ({}) failed (attempt {}): {}",
+                delivery.id,
+                delivery.event.as_str(),
+                attempts,
+                e
+            );
+        }
+
+        let attempt = DeliveryAttempt {
+            attempted_at: BsonDateTime::from(attempted_at),
+            duration_ms: started.elapsed().as_millis() as i64,
+            error: result.err(),
+        };
+        self.webhook_repository
+            .record_attempt(delivery.id, attempt, state, next_attempt_at)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mindvault_shared::models::tasks_model::ETaskPriority;
+    use mindvault_shared::models::workspaces_model::{EWorkspaceRole, WorkspaceMember};
+
+    const USER_A: i64 = 1;
+    const USER_B: i64 = 2;
+
+    fn task(owner_id: i64, workspace_id: Option<i64>) -> Task {
+        Task {
+            id: 7,
+            owner_id,
+            workspace_id,
+            assignee_id: None,
+            reportee_ids: Vec::new(),
+            name: "Draft the budget".to_string(),
+            priority: ETaskPriority::Normal,
+            status: ETaskStatus::NotStarted,
+            workflow_status: None,
+            rank: None,
+            due_date: None,
+            due_has_time: false,
+            created_at: BsonDateTime::now(),
+            updated_at: None,
+            completed_at: None,
+            tags: Vec::new(),
+            recurrence: None,
+            estimate_minutes: None,
+            tracked_seconds: 0,
+            notes: Some("salary figures".to_string()),
+            notes_index: Vec::new(),
+            external_id: None,
+            deleted: Some(false),
+        }
+    }
+
+    fn subscription(id: i64, owner_id: i64) -> WebhookSubscription {
+        WebhookSubscription {
+            id,
+            owner_id,
+            url: format!("https://hooks.example.com/{}", owner_id),
+            secret: None,
+            events: Vec::new(),
+            description: None,
+            enabled: true,
+            created_at: BsonDateTime::now(),
+            deleted: Some(false),
+        }
+    }
+
+    fn workspace(member_ids: &[i64]) -> Workspace {
+        Workspace {
+            id: 3,
+            name: "Finance".to_string(),
+            description: None,
+            members: member_ids
+                .iter()
+                .map(|&user_id| WorkspaceMember {
+                    user_id,
+                    role: EWorkspaceRole::Editor,
+                    added_at: BsonDateTime::now(),
+                })
+                .collect(),
+            created_by: member_ids[0],
+            created_at: BsonDateTime::now(),
+            updated_at: None,
+            deleted: None,
+        }
+    }
+
+    #[test]
+    fn user_b_hears_nothing_about_user_a_s_private_task() {
+        let audience = task(USER_A, None).audience(None);
+
+        // Notification channels are looked up for the audience only
+        assert_eq!(audience, vec![USER_A]);
+        assert!(subscription(1, USER_A).receives(ETaskEvent::Updated, &audience));
+        for event in [
+            ETaskEvent::Created,
+            ETaskEvent::Updated,
+            ETaskEvent::Completed,
+            ETaskEvent::Deleted,
+        ] {
+            assert!(!subscription(2, USER_B).receives(event, &audience));
+        }
+    }
+
+    #[test]
+    fn workspace_tasks_go_to_current_members_only() {
+        let shared = task(USER_A, Some(3));
+
+        let audience = shared.audience(Some(&workspace(&[USER_A])));
+        assert!(!subscription(2, USER_B).receives(ETaskEvent::Created, &audience));
+
+        let audience = shared.audience(Some(&workspace(&[USER_A, USER_B])));
+        assert!(subscription(2, USER_B).receives(ETaskEvent::Created, &audience));
+
+        // A deleted workspace has nobody left to tell
+        assert!(shared.audience(None).is_empty());
+    }
+
+    #[test]
+    fn subscriptions_still_filter_by_event() {
+        let mut completions_only = subscription(1, USER_A);
+        completions_only.events = vec![ETaskEvent::Completed];
+        let audience = task(USER_A, None).audience(None);
+
+        assert!(completions_only.receives(ETaskEvent::Completed, &audience));
+        assert!(!completions_only.receives(ETaskEvent::Updated, &audience));
+    }
+}
@@ -0,0 +1,29 @@
+use sha2::{Digest, Sha256};
+
+/// Every personal access token starts with this, so leaked tokens are easy to scan for
+pub const ACCESS_TOKEN_PREFIX: &str = "mvpat_";
+/// Characters of the token kept in clear for display, including the prefix
+const DISPLAY_PREFIX_LEN: usize = ACCESS_TOKEN_PREFIX.len() + 6;
+
+/// A freshly generated token; `secret` is handed to the user and never stored
+pub struct NewAccessToken {
+    pub secret: String,
+    pub hash: String,
+    pub prefix: String,
+}
+
+/// Tokens carry 256 random bits, so a plain SHA-256 is enough to store them safely and still
+/// look them up by hash
+pub fn hash_access_token(secret: &str) -> String {
+    hex::encode(Sha256::digest(secret.as_bytes()))
+}
+
+pub fn generate_access_token() -> NewAccessToken {
+    let random_bytes: [u8; 32] = rand::random();
+    let secret = format!("{}{}", ACCESS_TOKEN_PREFIX, hex::encode(random_bytes));
+    NewAccessToken {
+        hash: hash_access_token(&secret),
+        prefix: secret[..DISPLAY_PREFIX_LEN].to_string(),
+        secret,
+    }
+}
@@ -0,0 +1,419 @@
+use crate::models::AppDatabase;
+use bson::{doc, Bson, Document};
+use chrono::{DateTime, SecondsFormat, Utc};
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use futures_util::TryStreamExt;
+use mindvault_shared::models::backup_model::{
+    BackupCollection, BackupManifest, BackupSummary, RestoreSummary, RestoredCollection,
+};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::{env, fs};
+use thiserror::Error;
+use tracing::info;
+
+pub const BACKUP_FORMAT: &str = "mindvault-backup";
+pub const BACKUP_FORMAT_VERSION: u32 = 1;
+/// Bump when stored documents change shape in a way older code cannot read
+pub const SCHEMA_VERSION: u32 = 1;
+/// Canonical Extended JSON keeps every BSON type (Int64 ids, dates) and is readable by any
+/// MongoDB tooling, so archives are not tied to this server or driver
+const ENCODING: &str = "extended-json-canonical";
+const MANIFEST_FILE: &str = "manifest.json";
+const COLLECTIONS_DIR: &str = "collections";
+const INSERT_BATCH_SIZE: usize = 1000;
+
+const BACKUP_DIR_VAR: &str = "MINDVAULT_BACKUP_DIR";
+const DEFAULT_BACKUP_DIR: &str = "backups";
+
+#[derive(Debug, Error)]
+pub enum BackupError {
+    #[error("MongoDB error: {0}")]
+    MongoError(#[from] mongodb::error::Error),
+    #[error("I/O error: {0}")]
+    IoError(#[from] std::io::Error),
+    #[error("Invalid backup archive: {0}")]
+    InvalidArchive(String),
+    #[error("Checksum mismatch for collection '{0}'")]
+    ChecksumMismatch(String),
+    #[error("Backup schema version {found} is newer than the supported version {supported}")]
+    UnsupportedSchema { found: u32, supported: u32 },
+    #[error("Target database is not empty; collections with data: {0:?}")]
+    TargetNotEmpty(Vec<String>),
+}
+
+/// A validated archive ready to be restored
+pub struct BackupArchive {
+    pub manifest: BackupManifest,
+    collections: Vec<(String, Vec<Document>)>,
+}
+
+/// Directory `POST /admin/backup` and the `backup` command write to
+pub fn backup_dir_from_env() -> PathBuf {
+    env::var(BACKUP_DIR_VAR)
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| PathBuf::from(DEFAULT_BACKUP_DIR))
+}
+
+fn sha256_hex(data: &[u8]) -> String {
+    hex::encode(Sha256::digest(data))
+}
+
+/// Appends `document` to a collection file as one line of canonical Extended JSON
+fn write_document(data: &mut Vec<u8>, document: Document) -> Result<(), std::io::Error> {
+    let json = Bson::Document(document).into_canonical_extjson();
+    serde_json::to_writer(&mut *data, &json)?;
+    data.push(b'\n');
+    Ok(())
+}
+
+/// Gzipped tar of `files`, each given by path and content
+fn tar_gz(files: &[(&str, &[u8])], mtime: u64) -> Result<Vec<u8>, std::io::Error> {
+    let mut builder = tar::Builder::new(GzEncoder::new(Vec::new(), Compression::default()));
+    for (path, data) in files {
+        let mut header = tar::Header::new_gnu();
+        header.set_size(data.len() as u64);
+        header.set_mode(0o644);
+        header.set_mtime(mtime);
+        header.set_cksum();
+        builder.append_data(&mut header, path, *data)?;
+    }
+    builder.into_inner()?.finish()
+}
+
+/// Dumps every collection, including the `ref_auto_increment` counters, into a gzipped tar
+/// holding `manifest.json` and one `collections/<name>.jsonl` per collection
+pub async fn create_backup(
+    app_database: &AppDatabase,
+) -> Result<(BackupManifest, Vec<u8>), BackupError> {
+    let mut names = app_database.list_collection_names().await?;
+    names.retain(|name| !name.starts_with("system."));
+    names.sort();
+
+    let mut collections = Vec::with_capacity(names.len());
+    for name in names {
+        let mut cursor = app_database
+            .collection::<Document>(&name)
+            .find(doc! {})
+            .sort(doc! { "_id": 1 })
+            .await?;
+        let mut data = Vec::new();
+        let mut documents = 0;
+        while let Some(document) = cursor.try_next().await? {
+            write_document(&mut data, document)?;
+            documents += 1;
+        }
+        info!("Backing up {} documents from '{}'", documents, name);
+        collections.push((name, data, documents));
+    }
+    pack_backup(app_database.name(), collections, Utc::now())
+}
+
+/// Builds the archive of `database` from its encoded collection files, given with their
+/// document counts
+fn pack_backup(
+    database: &str,
+    collections: Vec<(String, Vec<u8>, u64)>,
+    created_at: DateTime<Utc>,
+) -> Result<(BackupManifest, Vec<u8>), BackupError> {
+    let mut files = Vec::with_capacity(collections.len());
+    let mut entries = Vec::with_capacity(collections.len());
+    for (name, data, documents) in collections {
+        let file = format!("{}/{}.jsonl", COLLECTIONS_DIR, name);
+        entries.push(BackupCollection {
+            name,
+            file: file.clone(),
+            documents,
+            sha256: sha256_hex(&data),
+        });
+        files.push((file, data));
+    }
+
+    let manifest = BackupManifest {
+        format: BACKUP_FORMAT.to_string(),
+        format_version: BACKUP_FORMAT_VERSION,
+        schema_version: SCHEMA_VERSION,
+        encoding: ENCODING.to_string(),
+        created_at: created_at.to_rfc3339_opts(SecondsFormat::Secs, true),
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        database: database.to_string(),
+        collections: entries,
+    };
+    let manifest_json = serde_json::to_vec_pretty(&manifest).map_err(std::io::Error::from)?;
+
+    let mut archive_files: Vec<(&str, &[u8])> = vec![(MANIFEST_FILE, &manifest_json)];
+    archive_files.extend(files.iter().map(|(file, data)| (file.as_str(), data.as_slice())));
+    let archive = tar_gz(&archive_files, created_at.timestamp().max(0) as u64)?;
+    Ok((manifest, archive))
+}
+
+/// Writes a backup to `dir` as `mindvault-backup-<timestamp>.tar.gz`
+pub async fn write_backup_file(
+    app_database: &AppDatabase,
+    dir: &Path,
+) -> Result<BackupSummary, BackupError> {
+    let (manifest, archive) = create_backup(app_database).await?;
+    fs::create_dir_all(dir)?;
+    let stamp = manifest.created_at.replace([':', '-'], "");
+    let path = dir.join(format!("{}-{}.tar.gz", BACKUP_FORMAT, stamp));
+    fs::write(&path, &archive)?;
+    info!("Wrote backup to {}", path.display());
+    Ok(BackupSummary {
+        path: path.display().to_string(),
+        size_bytes: archive.len() as u64,
+        manifest,
+    })
+}
+
+/// Unpacks an archive and checks its format, schema version, checksums and document counts
+pub fn read_backup(archive: &[u8]) -> Result<BackupArchive, BackupError> {
+    let mut files: HashMap<String, Vec<u8>> = HashMap::new();
+    let mut tar = tar::Archive::new(GzDecoder::new(archive));
+    for entry in tar.entries()? {
+        let mut entry = entry?;
+        let path = entry.path()?.to_string_lossy().to_string();
+        let mut data = Vec::new();
+        entry.read_to_end(&mut data)?;
+        files.insert(path, data);
+    }
+
+    let manifest_data = files
+        .remove(MANIFEST_FILE)
+        .ok_or_else(|| BackupError::InvalidArchive(format!("missing {}", MANIFEST_FILE)))?;
+    let manifest: BackupManifest = serde_json::from_slice(&manifest_data)
+        .map_err(|e| BackupError::InvalidArchive(format!("unreadable manifest: {}", e)))?;
+    if manifest.format != BACKUP_FORMAT || manifest.format_version != BACKUP_FORMAT_VERSION {
+        return Err(BackupError::InvalidArchive(format!(
+            "unsupported format {} v{}",
+            manifest.format, manifest.format_version
+        )));
+    }
+    if manifest.encoding != ENCODING {
+        return Err(BackupError::InvalidArchive(format!(
+            "unsupported encoding {}",
+            manifest.encoding
+        )));
+    }
+    if manifest.schema_version > SCHEMA_VERSION {
+        return Err(BackupError::UnsupportedSchema {
+            found: manifest.schema_version,
+            supported: SCHEMA_VERSION,
+        });
+    }
+
+    let mut collections = Vec::with_capacity(manifest.collections.len());
+    for entry in &manifest.collections {
+        if entry.name.is_empty() || entry.name.starts_with("system.") || entry.name.contains('$') {
+            return Err(BackupError::InvalidArchive(format!(
+                "invalid collection name '{}'",
+                entry.name
+            )));
+        }
+        let data = files.remove(&entry.file).ok_or_else(|| {
+            BackupError::InvalidArchive(format!("missing {} for '{}'", entry.file, entry.name))
+        })?;
+        if sha256_hex(&data) != entry.sha256 {
+            return Err(BackupError::ChecksumMismatch(entry.name.clone()));
+        }
+
+        let mut documents = Vec::new();
+        for (index, line) in data.split(|byte| *byte == b'\n').enumerate() {
+            if line.is_empty() {
+                continue;
+            }
+            let invalid = |reason: String| {
+                BackupError::InvalidArchive(format!("{} line {}: {}", entry.file, index + 1, reason))
+            };
+            let json: serde_json::Value =
+                serde_json::from_slice(line).map_err(|e| invalid(e.to_string()))?;
+            match Bson::try_from(json).map_err(|e| invalid(e.to_string()))? {
+                Bson::Document(document) => documents.push(document),
+                _ => return Err(invalid("not a document".to_string())),
+            }
+        }
+        if documents.len() as u64 != entry.documents {
+            return Err(BackupError::InvalidArchive(format!(
+                "'{}' has {} documents, manifest lists {}",
+                entry.name,
+                documents.len(),
+                entry.documents
+            )));
+        }
+        collections.push((entry.name.clone(), documents));
+    }
+    if let Some(extra) = files.keys().next() {
+        return Err(BackupError::InvalidArchive(format!("unexpected file {}", extra)));
+    }
+
+    Ok(BackupArchive {
+        manifest,
+        collections,
+    })
+}
+
+/// Loads a validated archive into `app_database`, which must not hold any documents yet.
+/// With `dry_run` only the emptiness check runs.
+pub async fn restore_backup(
+    app_database: &AppDatabase,
+    archive: BackupArchive,
+    dry_run: bool,
+) -> Result<RestoreSummary, BackupError> {
+    let existing = app_database.list_collection_names().await?;
+    let mut non_empty = Vec::new();
+    for name in &existing {
+        if name.starts_with("system.") {
+            continue;
+        }
+        let count = app_database
+            .collection::<Document>(name)
+            .estimated_document_count()
+            .await?;
+        if count > 0 {
+            non_empty.push(name.clone());
+        }
+    }
+    if !non_empty.is_empty() {
+        non_empty.sort();
+        return Err(BackupError::TargetNotEmpty(non_empty));
+    }
+
+    let mut restored = Vec::with_capacity(archive.collections.len());
+    for (name, documents) in archive.collections {
+        if !dry_run {
+            if documents.is_empty() && !existing.contains(&name) {
+                app_database.create_collection(&name).await?;
+            }
+            let collection = app_database.collection::<Document>(&name);
+            for batch in documents.chunks(INSERT_BATCH_SIZE) {
+                collection.insert_many(batch).await?;
+            }
+            info!("Restored {} documents into '{}'", documents.len(), name);
+        }
+        restored.push(RestoredCollection {
+            name,
+            documents: documents.len() as u64,
+        });
+    }
+
+    Ok(RestoreSummary {
+        dry_run,
+        database: app_database.name().to_string(),
+        schema_version: archive.manifest.schema_version,
+        collections: restored,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn collection(name: &str, documents: Vec<Document>) -> (String, Vec<u8>, u64) {
+        let count = documents.len() as u64;
+        let mut data = Vec::new();
+        for document in documents {
+            write_document(&mut data, document).unwrap();
+        }
+        (name.to_string(), data, count)
+    }
+
+    fn tasks() -> Vec<Document> {
+        let due = bson::DateTime::from_millis(1_792_434_600_000);
+        vec![
+            doc! {
+                "_id": 1_i64,
+                "name": "Write\nreport",
+                "dueDate": due,
+                "vault": { "user": 7_i64 },
+            },
+            doc! { "_id": 2_i64, "name": "Review", "estimate": 1.5, "tags": ["a", "b"] },
+        ]
+    }
+
+    fn pack() -> (BackupManifest, Vec<u8>) {
+        let created_at = Utc.with_ymd_and_hms(2026, 10, 19, 8, 0, 0).unwrap();
+        let collections = vec![
+            collection("ref_auto_increment", vec![doc! { "_id": "tasks", "seq": 2_i64 }]),
+            collection("tasks", tasks()),
+            collection("webhooks", vec![]),
+        ];
+        pack_backup("mindvault", collections, created_at).unwrap()
+    }
+
+    /// Rebuilds `archive` after letting `tamper` edit each file by path
+    fn repack(archive: &[u8], tamper: impl Fn(&str, &mut Vec<u8>)) -> Vec<u8> {
+        let mut files = Vec::new();
+        let mut tar = tar::Archive::new(GzDecoder::new(archive));
+        for entry in tar.entries().unwrap() {
+            let mut entry = entry.unwrap();
+            let path = entry.path().unwrap().to_string_lossy().to_string();
+            let mut data = Vec::new();
+            entry.read_to_end(&mut data).unwrap();
+            tamper(&path, &mut data);
+            files.push((path, data));
+        }
+        let files: Vec<(&str, &[u8])> =
+            files.iter().map(|(path, data)| (path.as_str(), data.as_slice())).collect();
+        tar_gz(&files, 0).unwrap()
+    }
+
+    #[test]
+    fn archives_round_trip_every_document() {
+        let (manifest, archive) = pack();
+        assert_eq!(manifest.database, "mindvault");
+        assert_eq!(manifest.created_at, "2026-10-19T08:00:00Z");
+        let counts: Vec<_> = manifest.collections.iter().map(|c| c.documents).collect();
+        assert_eq!(counts, vec![1, 2, 0]);
+
+        let restored = read_backup(&archive).unwrap();
+        assert_eq!(restored.manifest.schema_version, SCHEMA_VERSION);
+        assert_eq!(
+            restored.collections,
+            vec![
+                (
+                    "ref_auto_increment".to_string(),
+                    vec![doc! { "_id": "tasks", "seq": 2_i64 }]
+                ),
+                ("tasks".to_string(), tasks()),
+                ("webhooks".to_string(), vec![]),
+            ]
+        );
+    }
+
+    #[test]
+    fn corrupted_collections_fail_their_checksum() {
+        let (_, archive) = pack();
+        let corrupted = repack(&archive, |path, data| {
+            if path == "collections/tasks.jsonl" {
+                *data = String::from_utf8_lossy(data).replace("Review", "Reviev").into_bytes();
+            }
+        });
+
+        match read_backup(&corrupted) {
+            Err(BackupError::ChecksumMismatch(name)) => assert_eq!(name, "tasks"),
+            other => panic!("expected a checksum mismatch, got {:?}", other.err()),
+        }
+    }
+
+    #[test]
+    fn newer_schema_versions_are_rejected() {
+        let (_, archive) = pack();
+        let newer = repack(&archive, |path, data| {
+            if path == MANIFEST_FILE {
+                let mut manifest: BackupManifest = serde_json::from_slice(data).unwrap();
+                manifest.schema_version = SCHEMA_VERSION + 1;
+                *data = serde_json::to_vec(&manifest).unwrap();
+            }
+        });
+
+        assert!(matches!(
+            read_backup(&newer),
+            Err(BackupError::UnsupportedSchema { found, supported: SCHEMA_VERSION })
+                if found == SCHEMA_VERSION + 1
+        ));
+    }
+}
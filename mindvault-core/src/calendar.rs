@@ -0,0 +1,476 @@
+use crate::transfer::ImportRow;
+use bson::DateTime as BsonDateTime;
+use chrono::{Duration, NaiveDate};
+use mindvault_shared::dtos::task_dtos::CreateTaskRequest;
+use mindvault_shared::models::tasks_model::{ETaskPriority, ETaskStatus, Task};
+use mindvault_shared::utils::date_input::DateInput;
+use mindvault_shared::utils::recurrence::parse_rrule;
+
+const PRODUCT_ID: &str = "-//MindVault//Tasks//EN";
+const CALENDAR_NAME: &str = "MindVault Tasks";
+const UID_DOMAIN: &str = "mindvault";
+/// Keeps the exact status through a round trip, since iCalendar has no "Pending"
+const STATUS_EXTENSION: &str = "X-MINDVAULT-STATUS";
+const MAX_LINE_OCTETS: usize = 75;
+
+/// UID of a task's VTODO; derived from the id only so it survives renames and reschedules
+pub fn task_uid(task_id: i64) -> String {
+    format!("task-{}@{}", task_id, UID_DOMAIN)
+}
+
+fn event_uid(task_id: i64) -> String {
+    format!("task-{}-due@{}", task_id, UID_DOMAIN)
+}
+
+pub fn calendar_header() -> String {
+    let mut out = String::new();
+    push_line(&mut out, "BEGIN:VCALENDAR");
+    push_line(&mut out, "VERSION:2.0");
+    push_line(&mut out, &format!("PRODID:{}", PRODUCT_ID));
+    push_line(&mut out, "CALSCALE:GREGORIAN");
+    push_line(&mut out, &format!("X-WR-CALNAME:{}", CALENDAR_NAME));
+    out
+}
+
+pub fn calendar_footer() -> String {
+    let mut out = String::new();
+    push_line(&mut out, "END:VCALENDAR");
+    out
+}
+
+/// Renders `tasks` as VTODOs, plus an all-day VEVENT on the due date when `include_events`
+pub fn render_calendar(tasks: &[Task], include_events: bool) -> String {
+    let mut out = calendar_header();
+    for task in tasks {
+        out.push_str(&render_todo(task));
+        if include_events && let Some(event) = render_event(task) {
+            out.push_str(&event);
+        }
+    }
+    out.push_str(&calendar_footer());
+    out
+}
+
+pub fn render_todo(task: &Task) -> String {
+    let mut out = String::new();
+    push_line(&mut out, "BEGIN:VTODO");
+    push_line(&mut out, &format!("UID:{}", task_uid(task.id)));
+    push_line(&mut out, &format!("DTSTAMP:{}", format_utc(task.last_touched_at())));
+    push_line(&mut out, &format!("CREATED:{}", format_utc(task.created_at)));
+    if let Some(updated_at) = task.updated_at {
+        push_line(&mut out, &format!("LAST-MODIFIED:{}", format_utc(updated_at)));
+    }
+    push_line(&mut out, &format!("SUMMARY:{}", escape_text(&task.name)));
+    if let Some(due_date) = due_date_of(task) {
+        let due = match task.due_date.filter(|_| task.due_has_time) {
+            Some(due_at) => format!(":{}", format_utc(due_at)),
+            None => format!(";VALUE=DATE:{}", format_date(due_date)),
+        };
+        if task.recurrence.is_some() {
+            // Recurrence is anchored on DTSTART, so repeat the due date there
+            push_line(&mut out, &format!("DTSTART{}", due));
+        }
+        push_line(&mut out, &format!("DUE{}", due));
+    }
+    push_line(&mut out, &format!("STATUS:{}", todo_status(&task.status)));
+    push_line(&mut out, &format!("{}:{:?}", STATUS_EXTENSION, task.status));
+    push_line(&mut out, &format!("PRIORITY:{}", todo_priority(&task.priority)));
+    if let Some(completed_at) = task.completed_at
+        && task.status == ETaskStatus::Completed
+    {
+        push_line(&mut out, &format!("COMPLETED:{}", format_utc(completed_at)));
+    }
+    push_categories(&mut out, &task.tags);
+    if let Some(recurrence) = &task.recurrence {
+        push_line(&mut out, &format!("RRULE:{}", recurrence));
+    }
+    push_line(&mut out, "END:VTODO");
+    out
+}
+
+/// All-day event on the due date, for clients that do not show to-dos
+pub fn render_event(task: &Task) -> Option<String> {
+    let due_date = due_date_of(task)?;
+    let mut out = String::new();
+    push_line(&mut out, "BEGIN:VEVENT");
+    push_line(&mut out, &format!("UID:{}", event_uid(task.id)));
+    push_line(&mut out, &format!("DTSTAMP:{}", format_utc(task.last_touched_at())));
+    push_line(&mut out, &format!("SUMMARY:{}", escape_text(&task.name)));
+    push_line(&mut out, &format!("DTSTART;VALUE=DATE:{}", format_date(due_date)));
+    push_line(
+        &mut out,
+        &format!("DTEND;VALUE=DATE:{}", format_date(due_date + Duration::days(1))),
+    );
+    push_line(&mut out, "TRANSP:TRANSPARENT");
+    push_categories(&mut out, &task.tags);
+    if let Some(recurrence) = &task.recurrence {
+        push_line(&mut out, &format!("RRULE:{}", recurrence));
+    }
+    push_line(&mut out, "END:VEVENT");
+    Some(out)
+}
+
+fn due_date_of(task: &Task) -> Option<NaiveDate> {
+    task.due_date.map(|due| due.to_chrono().date_naive())
+}
+
+fn todo_status(status: &ETaskStatus) -> &'static str {
+    match status {
+        ETaskStatus::NotStarted | ETaskStatus::Pending => "NEEDS-ACTION",
+        ETaskStatus::InProgress => "IN-PROCESS",
+        ETaskStatus::Completed => "COMPLETED",
+        ETaskStatus::Cancelled => "CANCELLED",
+    }
+}
+
+/// RFC 5545 priorities run from 1 (highest) to 9 (lowest), with 5 as "medium"
+fn todo_priority(priority: &ETaskPriority) -> u8 {
+    match priority {
+        ETaskPriority::Urgent => 1,
+        ETaskPriority::High => 3,
+        ETaskPriority::Normal => 5,
+        ETaskPriority::Low => 9,
+    }
+}
+
+fn push_categories(out: &mut String, tags: &[String]) {
+    if tags.is_empty() {
+        return;
+    }
+    let categories: Vec<String> = tags.iter().map(|tag| escape_text(tag)).collect();
+    push_line(out, &format!("CATEGORIES:{}", categories.join(",")));
+}
+
+fn format_utc(at: BsonDateTime) -> String {
+    at.to_chrono().format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+fn format_date(date: NaiveDate) -> String {
+    date.format("%Y%m%d").to_string()
+}
+
+fn escape_text(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            ';' => escaped.push_str("\\;"),
+            ',' => escaped.push_str("\\,"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => {}
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Appends a content line, folded at 75 octets without splitting a UTF-8 character
+fn push_line(out: &mut String, line: &str) {
+    let mut octets = 0;
+    for c in line.chars() {
+        let width = c.len_utf8();
+        if octets + width > MAX_LINE_OCTETS {
+            out.push_str("\r\n ");
+            // The leading space of a continuation line counts towards its length
+            octets = 1;
+        }
+        out.push(c);
+        octets += width;
+    }
+    out.push_str("\r\n");
+}
+
+struct ContentLine {
+    row: usize,
+    name: String,
+    params: Vec<(String, String)>,
+    value: String,
+}
+
+/// Joins folded lines back together, remembering where each one started
+fn unfold(body: &str) -> Vec<(usize, String)> {
+    let mut lines: Vec<(usize, String)> = Vec::new();
+    for (index, raw) in body.split('\n').enumerate() {
+        let raw = raw.strip_suffix('\r').unwrap_or(raw);
+        if let Some(continuation) = raw.strip_prefix(' ').or_else(|| raw.strip_prefix('\t'))
+            && let Some((_, last)) = lines.last_mut()
+        {
+            last.push_str(continuation);
+            continue;
+        }
+        if !raw.is_empty() {
+            lines.push((index + 1, raw.to_string()));
+        }
+    }
+    lines
+}
+
+fn parse_content_line(row: usize, line: &str) -> Option<ContentLine> {
+    // The value starts at the first colon outside a quoted parameter value
+    let mut in_quotes = false;
+    let colon = line.char_indices().find_map(|(index, c)| match c {
+        '"' => {
+            in_quotes = !in_quotes;
+            None
+        }
+        ':' if !in_quotes => Some(index),
+        _ => None,
+    })?;
+    let (head, value) = (&line[..colon], &line[colon + 1..]);
+    let mut parts = head.split(';');
+    let name = parts.next()?.trim().to_ascii_uppercase();
+    let params = parts
+        .filter_map(|param| param.split_once('='))
+        .map(|(key, value)| (key.trim().to_ascii_uppercase(), value.trim_matches('"').to_string()))
+        .collect();
+    Some(ContentLine {
+        row,
+        name,
+        params,
+        value: value.to_string(),
+    })
+}
+
+fn unescape_text(text: &str) -> String {
+    let mut unescaped = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') | Some('N') => unescaped.push('\n'),
+            Some(other) => unescaped.push(other),
+            None => unescaped.push('\\'),
+        }
+    }
+    unescaped
+}
+
+/// Splits a comma-separated text list, keeping escaped commas inside items
+fn split_text_list(value: &str) -> Vec<String> {
+    let mut items = Vec::new();
+    let mut current = String::new();
+    let mut escaped = false;
+    for c in value.chars() {
+        if escaped {
+            current.push('\\');
+            current.push(c);
+            escaped = false;
+        } else if c == '\\' {
+            escaped = true;
+        } else if c == ',' {
+            items.push(unescape_text(&current));
+            current.clear();
+        } else {
+            current.push(c);
+        }
+    }
+    items.push(unescape_text(&current));
+    items
+}
+
+/// Reads the VTODO components of a calendar. Events and other components are ignored, as
+/// are VALARMs nested inside a to-do.
+pub fn parse_calendar(body: &str) -> Result<Vec<ImportRow>, String> {
+    let lines: Vec<ContentLine> = unfold(body)
+        .into_iter()
+        .filter_map(|(row, line)| parse_content_line(row, &line))
+        .collect();
+    let is_calendar = lines
+        .first()
+        .is_some_and(|line| line.name == "BEGIN" && line.value.eq_ignore_ascii_case("VCALENDAR"));
+    if !is_calendar {
+        return Err("Not an iCalendar file: expected BEGIN:VCALENDAR".to_string());
+    }
+
+    let mut rows = Vec::new();
+    let mut todo: Option<(usize, Vec<ContentLine>)> = None;
+    let mut nested_depth = 0;
+    for line in lines {
+        let component = line.value.to_ascii_uppercase();
+        match (line.name.as_str(), todo.as_mut()) {
+            ("BEGIN", None) if component == "VTODO" => todo = Some((line.row, Vec::new())),
+            ("BEGIN", Some(_)) => nested_depth += 1,
+            ("END", Some(_)) if nested_depth > 0 => nested_depth -= 1,
+            ("END", Some(_)) if component == "VTODO" => {
+                if let Some((row, properties)) = todo.take() {
+                    rows.push(ImportRow {
+                        row,
+                        task: build_task(&properties),
+                    });
+                }
+            }
+            (_, Some((_, properties))) if nested_depth == 0 => properties.push(line),
+            _ => {}
+        }
+    }
+    if let Some((row, _)) = todo {
+        rows.push(ImportRow {
+            row,
+            task: Err("VTODO is missing END:VTODO".to_string()),
+        });
+    }
+    Ok(rows)
+}
+
+fn build_task(properties: &[ContentLine]) -> Result<CreateTaskRequest, String> {
+    let property = |name: &str| properties.iter().find(|line| line.name == name);
+
+    let name = property("SUMMARY")
+        .map(|line| unescape_text(&line.value).trim().to_string())
+        .filter(|name| !name.is_empty())
+        .ok_or_else(|| "VTODO has no SUMMARY".to_string())?;
+    let due_date = property("DUE").map(parse_ical_date).transpose()?;
+
+    let status = match property(STATUS_EXTENSION) {
+        Some(line) => Some(line.value.parse::<ETaskStatus>()?),
+        None => match property("STATUS").map(|line| line.value.trim().to_ascii_uppercase()) {
+            Some(status) => match status.as_str() {
+                "NEEDS-ACTION" => Some(ETaskStatus::NotStarted),
+                "IN-PROCESS" => Some(ETaskStatus::InProgress),
+                "COMPLETED" => Some(ETaskStatus::Completed),
+                "CANCELLED" => Some(ETaskStatus::Cancelled),
+                other => return Err(format!("Unknown VTODO status '{}'", other)),
+            },
+            None => None,
+        },
+    };
+    let priority = match property("PRIORITY") {
+        Some(line) => match line.value.trim().parse::<u8>() {
+            Ok(0) => None,
+            Ok(1..=2) => Some(ETaskPriority::Urgent),
+            Ok(3..=4) => Some(ETaskPriority::High),
+            Ok(5) => Some(ETaskPriority::Normal),
+            Ok(6..=9) => Some(ETaskPriority::Low),
+            _ => return Err(format!("Invalid PRIORITY '{}'", line.value.trim())),
+        },
+        None => None,
+    };
+    let tags = properties
+        .iter()
+        .filter(|line| line.name == "CATEGORIES")
+        .flat_map(|line| split_text_list(&line.value))
+        .collect();
+    let recurrence = property("RRULE").map(|line| parse_rrule(&line.value)).transpose()?;
+
+    Ok(CreateTaskRequest {
+        name,
+        due_date: due_date.map(DateInput::Day),
+        priority,
+        status,
+        workflow_status: None,
+        tags,
+        recurrence,
+        estimate_minutes: None,
+        notes: None,
+        external_id: None,
+        assignee_id: None,
+        reportee_ids: Vec::new(),
+    })
+}
+
+/// Accepts `DUE;VALUE=DATE:20250727` as well as date-times, which keep only their date
+fn parse_ical_date(line: &ContentLine) -> Result<NaiveDate, String> {
+    let value = line.value.trim();
+    let date_part = value.get(..8).unwrap_or(value);
+    let is_date_only = line
+        .params
+        .iter()
+        .any(|(key, value)| key == "VALUE" && value.eq_ignore_ascii_case("DATE"));
+    if is_date_only && value.len() != 8 {
+        return Err(format!("Invalid DUE date '{}'", value));
+    }
+    NaiveDate::parse_from_str(date_part, "%Y%m%d")
+        .map_err(|_| format!("Invalid DUE value '{}' on line {}", value, line.row))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_calendar, render_calendar, MAX_LINE_OCTETS};
+    use crate::transfer::tests::{day, due_on, task};
+    use bson::DateTime as BsonDateTime;
+    use chrono::{TimeZone, Utc};
+    use mindvault_shared::models::tasks_model::{ETaskPriority, ETaskStatus};
+
+    #[test]
+    fn a_calendar_export_reads_back() {
+        let mut first = task(12, "Call plumber; bring keys, tools and the \\ spare");
+        first.priority = ETaskPriority::Urgent;
+        first.status = ETaskStatus::InProgress;
+        first.tags = vec!["home".to_string(), "errands, weekend".to_string()];
+        first.recurrence = Some("FREQ=WEEKLY;BYDAY=MO".to_string());
+        due_on(&mut first, 2026, 10, 20);
+        let mut second = task(13, &"Überweisung prüfen ".repeat(6));
+        second.name = second.name.trim().to_string();
+        second.status = ETaskStatus::Completed;
+        second.completed_at = Some(second.created_at);
+        // A due time keeps only its UTC date
+        second.due_date = Some(BsonDateTime::from(
+            Utc.with_ymd_and_hms(2026, 10, 21, 13, 0, 0).unwrap(),
+        ));
+        second.due_has_time = true;
+
+        let body = render_calendar(&[first.clone(), second.clone()], true);
+        assert!(body.split("\r\n").all(|line| line.len() <= MAX_LINE_OCTETS), "{}", body);
+
+        let rows = parse_calendar(&body).unwrap();
+        assert_eq!(rows.len(), 2, "{}", body);
+        let imported = rows[0].task.as_ref().unwrap();
+        assert_eq!(imported.name, first.name);
+        assert_eq!(imported.priority, Some(ETaskPriority::Urgent));
+        assert_eq!(imported.status, Some(ETaskStatus::InProgress));
+        assert_eq!(imported.tags, first.tags);
+        assert_eq!(imported.recurrence, first.recurrence);
+        assert_eq!(imported.due_date, day(2026, 10, 20));
+
+        let imported = rows[1].task.as_ref().unwrap();
+        assert_eq!(imported.name, second.name);
+        assert_eq!(imported.status, Some(ETaskStatus::Completed));
+        assert_eq!(imported.priority, Some(ETaskPriority::Normal));
+        assert_eq!(imported.due_date, day(2026, 10, 21));
+    }
+
+    #[test]
+    fn a_malformed_todo_fails_only_its_own_row() {
+        let body = [
+            "BEGIN:VCALENDAR",
+            "BEGIN:VTODO",
+            "SUMMARY:Good",
+            "DUE;VALUE=DATE:20261020",
+            "END:VTODO",
+            "BEGIN:VTODO",
+            "SUMMARY:Bad due",
+            "DUE;VALUE=DATE:2026-10-20",
+            "END:VTODO",
+            "BEGIN:VTODO",
+            "DUE;VALUE=DATE:20261020",
+            "END:VTODO",
+            "BEGIN:VTODO",
+            "SUMMARY:Bad priority",
+            "PRIORITY:high",
+            "END:VTODO",
+            "BEGIN:VTODO",
+            "SUMMARY:Unterminated",
+            "END:VCALENDAR",
+        ]
+        .join("\r\n");
+
+        let rows = parse_calendar(&body).unwrap();
+        let rows: Vec<_> = rows.iter().map(|row| (row.row, row.task.as_ref())).collect();
+        assert_eq!(rows.len(), 5);
+        assert_eq!(rows[0].0, 2);
+        assert_eq!(rows[0].1.unwrap().due_date, day(2026, 10, 20));
+        assert_eq!(rows[1].0, 6);
+        assert_eq!(rows[1].1.unwrap_err(), "Invalid DUE date '2026-10-20'");
+        assert_eq!(rows[2].0, 10);
+        assert_eq!(rows[2].1.unwrap_err(), "VTODO has no SUMMARY");
+        assert_eq!(rows[3].0, 13);
+        assert_eq!(rows[3].1.unwrap_err(), "Invalid PRIORITY 'high'");
+        assert_eq!(rows[4].0, 17);
+        assert_eq!(rows[4].1.unwrap_err(), "VTODO is missing END:VTODO");
+
+        assert!(parse_calendar("SUMMARY:Not a calendar").is_err());
+    }
+}
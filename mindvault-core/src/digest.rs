@@ -0,0 +1,185 @@
+use crate::models::{AppDatabase, DbError};
+use crate::repository::date_utils::naive_date_to_bson;
+use crate::repository::task_repo::{TaskRepository, TaskVault};
+use bson::DateTime as BsonDateTime;
+use chrono::{DateTime, Duration, Utc};
+use mindvault_shared::models::digest_model::{EDigestPeriod, TaskDigest};
+use mindvault_shared::models::tasks_model::TaskResponse;
+use mindvault_shared::utils::time_zone::{parse_time_zone, DEFAULT_TIME_ZONE};
+use std::env;
+
+const TIME_ZONE_VAR: &str = "MINDVAULT_DIGEST_TIME_ZONE";
+const STALLED_DAYS_VAR: &str = "MINDVAULT_DIGEST_STALLED_DAYS";
+const DEFAULT_STALLED_DAYS: i64 = 7;
+const UPCOMING_DAYS: i64 = 7;
+
+/// Settings for a digest; scheduled digests read them from the environment
+pub struct DigestOptions {
+    pub time_zone: String,
+    pub stalled_days: i64,
+}
+
+impl Default for DigestOptions {
+    fn default() -> Self {
+        Self {
+            time_zone: DEFAULT_TIME_ZONE.to_string(),
+            stalled_days: DEFAULT_STALLED_DAYS,
+        }
+    }
+}
+
+impl DigestOptions {
+    pub fn from_env() -> Self {
+        let defaults = DigestOptions::default();
+        Self {
+            time_zone: env::var(TIME_ZONE_VAR)
+                .ok()
+                .filter(|value| parse_time_zone(value).is_ok())
+                .unwrap_or(defaults.time_zone),
+            stalled_days: env::var(STALLED_DAYS_VAR)
+                .ok()
+                .and_then(|value| value.trim().parse().ok())
+                .filter(|days| *days > 0)
+                .unwrap_or(defaults.stalled_days),
+        }
+    }
+}
+
+/// Builds a user's task digests from the task collection.
+///
+/// Due days are compared with the current date in the digest's time zone: floating due dates
+/// as stored, due times by the local day they fall on. "Recently completed" and "stalled"
+/// are measured from the current instant.
+pub struct DigestGenerator {
+    task_repository: TaskRepository,
+}
+
+impl DigestGenerator {
+    pub fn new(app_database: AppDatabase) -> Self {
+        Self {
+            task_repository: TaskRepository::new(app_database),
+        }
+    }
+
+    pub async fn generate(
+        &self,
+        owner_id: i64,
+        period: EDigestPeriod,
+        now: DateTime<Utc>,
+        options: &DigestOptions,
+    ) -> Result<TaskDigest, DbError> {
+        let tz = parse_time_zone(&options.time_zone).map_err(DbError::InternalError)?;
+        let today = now.with_timezone(&tz).date_naive();
+        let upcoming_end = today + Duration::days(1 + UPCOMING_DAYS);
+
+        let vault = TaskVault::User(owner_id);
+        let mut overdue = Vec::new();
+        let mut due_today = Vec::new();
+        let mut due_this_week = Vec::new();
+        // A day of slack, since due times west of UTC fall on an earlier local day
+        let fetch_until = naive_date_to_bson(upcoming_end + Duration::days(1));
+        for task in self.task_repository.find_open_due_before(vault, fetch_until).await? {
+            let Some(due) = task.due_day(tz) else {
+                continue;
+            };
+            if due < today {
+                overdue.push(task);
+            } else if due == today {
+                due_today.push(task);
+            } else if due < upcoming_end {
+                due_this_week.push(task);
+            }
+        }
+
+        let completed_since = BsonDateTime::from(now - Duration::days(period.lookback_days()));
+        let recently_completed = self
+            .task_repository
+            .find_completed_since(vault, completed_since)
+            .await?;
+        let untouched_since = BsonDateTime::from(now - Duration::days(options.stalled_days));
+        let stalled = self.task_repository.find_stalled(vault, untouched_since).await?;
+
+        Ok(TaskDigest {
+            period,
+            date: today,
+            time_zone: options.time_zone.clone(),
+            generated_at: BsonDateTime::from(now),
+            stalled_days: options.stalled_days,
+            overdue: TaskResponse::from_vec(overdue),
+            due_today: TaskResponse::from_vec(due_today),
+            due_this_week: TaskResponse::from_vec(due_this_week),
+            recently_completed: TaskResponse::from_vec(recently_completed),
+            stalled: TaskResponse::from_vec(stalled),
+        })
+    }
+}
+
+fn format_date(date: Option<BsonDateTime>) -> Option<String> {
+    date.map(|date| date.to_chrono().format("%d/%m/%y").to_string())
+}
+
+fn describe_task(task: &TaskResponse) -> String {
+    let mut line = format!("#{} {} ({:?}, {:?})", task.id, task.name, task.status, task.priority);
+    if let Some(due) = format_date(task.due_date) {
+        line.push_str(&format!(" due {}", due));
+    }
+    if let Some(completed) = format_date(task.completed_at) {
+        line.push_str(&format!(" completed {}", completed));
+    }
+    line
+}
+
+fn digest_title(digest: &TaskDigest) -> String {
+    format!(
+        "{} digest for {}",
+        digest.period.label(),
+        digest.date.format("%d/%m/%y")
+    )
+}
+
+/// Subject line used when the digest is delivered as a notification
+pub fn render_subject(digest: &TaskDigest) -> String {
+    format!(
+        "MindVault {} digest: {} overdue, {} due today",
+        digest.period.label().to_lowercase(),
+        digest.overdue.len(),
+        digest.due_today.len()
+    )
+}
+
+pub fn render_markdown(digest: &TaskDigest) -> String {
+    let mut out = format!("# {}\n\n", digest_title(digest));
+    out.push_str(&format!("_Time zone: {}_\n", digest.time_zone));
+    for (heading, tasks) in digest.sections() {
+        out.push_str(&format!("\n## {} ({})\n\n", heading, tasks.len()));
+        if tasks.is_empty() {
+            out.push_str("_Nothing here._\n");
+        }
+        for task in tasks {
+            out.push_str(&format!("- {}\n", describe_task(task)));
+        }
+    }
+    out
+}
+
+/// Plain-text rendering, suitable as an email body
+pub fn render_text(digest: &TaskDigest) -> String {
+    let title = digest_title(digest);
+    let mut out = format!("{}\n{}\n", title, "=".repeat(title.len()));
+    out.push_str(&format!("Time zone: {}\n", digest.time_zone));
+    if digest.is_empty() {
+        out.push_str("\nNothing needs your attention.\n");
+        return out;
+    }
+    for (heading, tasks) in digest.sections() {
+        if tasks.is_empty() {
+            continue;
+        }
+        let heading = format!("{} ({})", heading, tasks.len());
+        out.push_str(&format!("\n{}\n{}\n", heading, "-".repeat(heading.len())));
+        for task in tasks {
+            out.push_str(&format!("  * {}\n", describe_task(task)));
+        }
+    }
+    out
+}
@@ -0,0 +1,202 @@
+use crate::models::{AppDatabase, DbError};
+use bson::{doc, Bson, Document, Regex};
+use futures_util::TryStreamExt;
+use mindvault_shared::models::encryption_model::{KeyRotationSummary, RotatedField};
+use mindvault_shared::utils::field_encryption::{
+    blind_index, encrypted_key_id, keyring, open, seal, EncryptedField, FieldEncryptionError,
+    FieldKeyring, ENCRYPTED_PREFIX,
+};
+use tracing::info;
+
+/// String value at a dotted `path`, e.g. `feedback.goals`
+fn string_at<'a>(document: &'a Document, path: &str) -> Option<&'a str> {
+    let (parent, key) = match path.rsplit_once('.') {
+        Some((parent, key)) => (document.get_document(parent).ok()?, key),
+        None => (document, path),
+    };
+    parent.get_str(key).ok()
+}
+
+fn prefix_pattern(key_id: Option<&str>) -> Regex {
+    let mut pattern = format!("^{}", ENCRYPTED_PREFIX);
+    if let Some(key_id) = key_id {
+        pattern.push_str(&key_id.replace('.', "\\."));
+        pattern.push(':');
+    }
+    Regex {
+        pattern,
+        options: String::new(),
+    }
+}
+
+/// Why `value`, stored in `field`, cannot be read with `keyring`
+fn unreadable_error(
+    keyring: Option<&FieldKeyring>,
+    field: EncryptedField,
+    value: &str,
+) -> FieldEncryptionError {
+    match keyring {
+        Some(_) => {
+            FieldEncryptionError::UnknownKey(encrypted_key_id(value).unwrap_or_default().to_string())
+        }
+        None => FieldEncryptionError::MissingKey(field.name().to_string()),
+    }
+}
+
+/// Fails when stored values were encrypted with a key that is not configured, including when
+/// encryption has been switched off entirely, so the server refuses to start instead of
+/// failing on the first read
+pub async fn check_encryption_keys(app_database: &AppDatabase) -> Result<(), DbError> {
+    let keyring = keyring()?;
+    let known_keys: Vec<Bson> = keyring
+        .map(|keyring| {
+            keyring
+                .key_ids()
+                .map(|key_id| Bson::RegularExpression(prefix_pattern(Some(key_id))))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    for field in EncryptedField::ALL {
+        let collection = app_database.collection::<Document>(field.collection());
+        for path in field.paths() {
+            let filter = doc! {
+                "$and": [
+                    { *path: prefix_pattern(None) },
+                    { *path: { "$nin": known_keys.clone() } }
+                ]
+            };
+            let Some(document) = collection
+                .find_one(filter)
+                .projection(doc! { *path: 1 })
+                .await?
+            else {
+                continue;
+            };
+            let value = string_at(&document, path).unwrap_or_default();
+            return Err(unreadable_error(keyring, field, value).into());
+        }
+    }
+    Ok(())
+}
+
+/// Brings stored values in line with the current settings: values under an older key are
+/// re-encrypted with the active one, plaintext in newly selected fields is encrypted, and fields
+/// no longer selected are decrypted. Blind indexes are rebuilt for every rewritten document,
+/// since they are keyed by the active key.
+pub async fn rotate_encryption_keys(
+    app_database: &AppDatabase,
+    dry_run: bool,
+) -> Result<KeyRotationSummary, DbError> {
+    let keyring = keyring()?;
+    let active_key = keyring.map(|keyring| keyring.active_key_id().to_string());
+    let mut fields = Vec::new();
+
+    for field in EncryptedField::ALL {
+        let encrypted = keyring.is_some_and(|keyring| keyring.encrypts(field));
+        let collection = app_database.collection::<Document>(field.collection());
+        let mut projection = doc! {};
+        for path in field.paths() {
+            projection.insert(*path, 1);
+        }
+
+        let mut scanned = 0;
+        let mut rewritten = 0;
+        let mut cursor = collection.find(doc! {}).projection(projection).await?;
+        while let Some(document) = cursor.try_next().await? {
+            scanned += 1;
+            let mut set_fields = doc! {};
+            let mut texts = Vec::new();
+            for path in field.paths() {
+                let Some(value) = string_at(&document, path) else {
+                    continue;
+                };
+                let stale = match encrypted_key_id(value) {
+                    Some(key_id) => !encrypted || Some(key_id) != active_key.as_deref(),
+                    None => encrypted,
+                };
+                let text = open(field, value.to_string())?;
+                if stale {
+                    set_fields.insert(*path, seal(field, &text)?);
+                }
+                texts.push(text);
+            }
+            if set_fields.is_empty() {
+                continue;
+            }
+            if let Some(index_path) = field.index_path() {
+                set_fields.insert(index_path, blind_index(field, Some(&texts.join(" ")))?);
+            }
+
+            rewritten += 1;
+            if !dry_run {
+                let id = document.get("_id").cloned().unwrap_or(Bson::Null);
+                collection
+                    .update_one(doc! { "_id": id }, doc! { "$set": set_fields })
+                    .await?;
+            }
+        }
+
+        info!(
+            "{}: {} of {} documents {}",
+            field.name(),
+            rewritten,
+            scanned,
+            if dry_run { "need rewriting" } else { "rewritten" }
+        );
+        fields.push(RotatedField {
+            field: field.name().to_string(),
+            collection: field.collection().to_string(),
+            encrypted,
+            scanned,
+            rewritten,
+        });
+    }
+
+    Ok(KeyRotationSummary {
+        dry_run,
+        active_key,
+        fields,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keyring(key_id: &str, byte: u8) -> FieldKeyring {
+        FieldKeyring::new(vec![(key_id.to_string(), [byte; 32])], EncryptedField::ALL.to_vec())
+            .unwrap()
+    }
+
+    #[test]
+    fn stored_values_under_another_key_name_that_key() {
+        let stored = keyring("passphrase-1a2b3c4d", 1).encrypt("Quarterly goals").unwrap();
+        let wrong = keyring("passphrase-9f8e7d6c", 2);
+
+        let error = unreadable_error(Some(&wrong), EncryptedField::ReviewFeedback, &stored);
+        assert!(matches!(
+            &error,
+            FieldEncryptionError::UnknownKey(key_id) if key_id == "passphrase-1a2b3c4d"
+        ));
+    }
+
+    #[test]
+    fn stored_values_without_any_key_ask_for_one() {
+        let stored = keyring("k1", 1).encrypt("Quarterly goals").unwrap();
+
+        let error = unreadable_error(None, EncryptedField::TaskNotes, &stored);
+        assert!(matches!(
+            &error,
+            FieldEncryptionError::MissingKey(field) if field == "tasks.notes"
+        ));
+        assert!(error.to_string().contains("MINDVAULT_ENCRYPTION_KEYFILE"));
+    }
+
+    #[test]
+    fn key_patterns_only_match_their_own_key() {
+        assert_eq!(prefix_pattern(None).pattern, "^mvenc:v1:");
+        // Dots in key ids are literal, so `k.1` does not also cover `kx1`
+        assert_eq!(prefix_pattern(Some("k.1")).pattern, "^mvenc:v1:k\\.1:");
+    }
+}
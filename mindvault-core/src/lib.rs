@@ -1,4 +1,16 @@
 pub mod db;
 pub mod mongo_config;
 pub mod models;
-pub mod repository;
\ No newline at end of file
+pub mod repository;
+pub mod scheduler_config;
+pub mod notification_config;
+pub mod digest;
+pub mod task_watcher;
+pub mod transfer;
+pub mod calendar;
+pub mod todo_txt;
+pub mod taskwarrior;
+pub mod backup;
+pub mod encryption;
+pub mod access_tokens;
+pub mod ownership;
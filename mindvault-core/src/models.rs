@@ -1,4 +1,6 @@
 use bson::Document;
+use mindvault_shared::utils::field_encryption::FieldEncryptionError;
+use mongodb::error::{ErrorKind, InsertManyError, WriteFailure};
 use mongodb::{bson, Collection, Database};
 use thiserror::Error;
 
@@ -16,8 +18,37 @@ pub enum DbError {
     BsonSerError(#[from] bson::ser::Error),
     #[error("Invalid ID format: {0}")]
     InvalidId(String),
+    /// A request the service layer should have validated or resolved first
+    #[error("Invalid input: {0}")]
+    InvalidInput(String),
+    #[error("Field encryption error: {0}")]
+    EncryptionError(#[from] FieldEncryptionError),
     #[error("Not Found")]
     NotFound,
     #[error("Internal Database Error: {0}")]
     InternalError(String),
 }
+
+/// Server code of a write rejected by a unique index
+const DUPLICATE_KEY_CODE: i32 = 11000;
+
+impl DbError {
+    /// Whether a write was rejected because it would break a unique index. For an unordered
+    /// insert of several documents, whether that is the only reason any of them was rejected.
+    pub fn is_duplicate_key(&self) -> bool {
+        let DbError::MongoError(error) = self else {
+            return false;
+        };
+        match error.kind.as_ref() {
+            ErrorKind::Write(WriteFailure::WriteError(write_error)) => {
+                write_error.code == DUPLICATE_KEY_CODE
+            }
+            ErrorKind::InsertMany(InsertManyError {
+                write_errors: Some(write_errors),
+                write_concern_error: None,
+                ..
+            }) => write_errors.iter().all(|e| e.code == DUPLICATE_KEY_CODE),
+            _ => false,
+        }
+    }
+}
@@ -2,6 +2,8 @@ use std::env;
 
 const MONGODB_URI: &str = "mongodb://localhost:27017";
 const MONGO_DB_NAME: &str = "mind_vault_v1";
+const MONGODB_URI_VAR: &str = "MINDVAULT_MONGODB_URI";
+const MONGO_DB_NAME_VAR: &str = "MINDVAULT_MONGODB_DB";
 
 pub struct MongoConfig {
     pub uri: String,
@@ -10,8 +12,8 @@ pub struct MongoConfig {
 
 impl MongoConfig {
     pub fn from_env() -> Result<Self, env::VarError> {
-        let uri = MONGODB_URI.to_owned();
-        let db_name = MONGO_DB_NAME.to_string();
+        let uri = env::var(MONGODB_URI_VAR).unwrap_or_else(|_| MONGODB_URI.to_owned());
+        let db_name = env::var(MONGO_DB_NAME_VAR).unwrap_or_else(|_| MONGO_DB_NAME.to_string());
         Ok(MongoConfig { uri, db_name })
     }
 }
\ No newline at end of file
@@ -0,0 +1,86 @@
+use std::env;
+
+const SMTP_HOST_VAR: &str = "MINDVAULT_SMTP_HOST";
+const SMTP_PORT_VAR: &str = "MINDVAULT_SMTP_PORT";
+const SMTP_TLS_VAR: &str = "MINDVAULT_SMTP_TLS";
+const SMTP_USERNAME_VAR: &str = "MINDVAULT_SMTP_USERNAME";
+const SMTP_PASSWORD_VAR: &str = "MINDVAULT_SMTP_PASSWORD";
+const SMTP_FROM_VAR: &str = "MINDVAULT_SMTP_FROM";
+const MAX_ATTEMPTS_VAR: &str = "MINDVAULT_NOTIFY_MAX_ATTEMPTS";
+const RETRY_BASE_SECONDS_VAR: &str = "MINDVAULT_NOTIFY_RETRY_BASE_SECONDS";
+const ALLOW_COMMANDS_VAR: &str = "MINDVAULT_NOTIFY_ALLOW_COMMANDS";
+
+/// Defaults point at a local SMTP sink such as MailHog or Mailpit on port 1025
+const DEFAULT_SMTP_HOST: &str = "localhost";
+const DEFAULT_SMTP_PORT: u16 = 1025;
+const DEFAULT_SMTP_FROM: &str = "MindVault <mindvault@localhost>";
+const DEFAULT_MAX_ATTEMPTS: u32 = 5;
+const DEFAULT_RETRY_BASE_SECONDS: u64 = 30;
+
+pub struct SmtpConfig {
+    pub host: String,
+    pub port: u16,
+    pub tls: bool,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    pub from: String,
+}
+
+/// Notification delivery settings read from the environment.
+///
+/// A failed delivery is retried after `retry_base_seconds * 2^(attempt - 1)` and moved to the
+/// dead-letter list once `max_attempts` is reached. Command channels run local programs and
+/// are refused unless `MINDVAULT_NOTIFY_ALLOW_COMMANDS=true`.
+pub struct NotificationConfig {
+    pub smtp: SmtpConfig,
+    pub max_attempts: u32,
+    pub retry_base_seconds: u64,
+    pub allow_commands: bool,
+}
+
+fn parse_flag(value: &str) -> bool {
+    matches!(value.to_ascii_lowercase().as_str(), "1" | "true" | "yes" | "on")
+}
+
+fn non_empty_var(name: &str) -> Option<String> {
+    env::var(name)
+        .ok()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+impl NotificationConfig {
+    pub fn from_env() -> Self {
+        let smtp = SmtpConfig {
+            host: non_empty_var(SMTP_HOST_VAR).unwrap_or_else(|| DEFAULT_SMTP_HOST.to_string()),
+            port: non_empty_var(SMTP_PORT_VAR)
+                .and_then(|value| value.parse().ok())
+                .unwrap_or(DEFAULT_SMTP_PORT),
+            tls: non_empty_var(SMTP_TLS_VAR)
+                .map(|value| parse_flag(&value))
+                .unwrap_or(false),
+            username: non_empty_var(SMTP_USERNAME_VAR),
+            password: non_empty_var(SMTP_PASSWORD_VAR),
+            from: non_empty_var(SMTP_FROM_VAR).unwrap_or_else(|| DEFAULT_SMTP_FROM.to_string()),
+        };
+        NotificationConfig {
+            smtp,
+            max_attempts: non_empty_var(MAX_ATTEMPTS_VAR)
+                .and_then(|value| value.parse().ok())
+                .filter(|attempts| *attempts > 0)
+                .unwrap_or(DEFAULT_MAX_ATTEMPTS),
+            retry_base_seconds: non_empty_var(RETRY_BASE_SECONDS_VAR)
+                .and_then(|value| value.parse().ok())
+                .unwrap_or(DEFAULT_RETRY_BASE_SECONDS),
+            allow_commands: non_empty_var(ALLOW_COMMANDS_VAR)
+                .map(|value| parse_flag(&value))
+                .unwrap_or(false),
+        }
+    }
+
+    /// Delay before the next attempt once `attempts` deliveries have failed
+    pub fn retry_delay_seconds(&self, attempts: u32) -> u64 {
+        let exponent = attempts.saturating_sub(1).min(16);
+        self.retry_base_seconds.saturating_mul(1u64 << exponent)
+    }
+}
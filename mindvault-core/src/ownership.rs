@@ -0,0 +1,201 @@
+use crate::models::{AppDatabase, DbError};
+use crate::repository::auto_increment::AUTO_INCREMENT_COLLECTION_NAME;
+use crate::repository::task_repo::TaskVault;
+use crate::repository::user_repo::{UserRepository, COLLECTION_NAME as USERS_COLLECTION_NAME};
+use bson::{doc, Document};
+use mindvault_shared::models::users_model::User;
+use mongodb::options::IndexOptions;
+use mongodb::IndexModel;
+use tracing::info;
+
+/// User that receives everything written before vaults were per user, and the one a fresh
+/// database starts with
+pub const DEFAULT_USERNAME: &str = "owner";
+
+const TASKS_COLLECTION_NAME: &str = "tasks";
+/// Collections whose documents carry an `ownerId` besides tasks
+const OWNED_COLLECTION_NAMES: [&str; 9] = [
+    "reminders",
+    "saved_filters",
+    "goals",
+    "reportees",
+    "reviews",
+    "review_templates",
+    "feedback",
+    "notification_channels",
+    "webhook_subscriptions",
+];
+const ACCESS_TOKENS_COLLECTION_NAME: &str = "access_tokens";
+const TIME_ENTRIES_COLLECTION_NAME: &str = "time_entries";
+/// Unique index on `ownerId` and `taskId` from before workspaces
+const LEGACY_TASK_NUMBER_INDEX: &str = "ownerId_1_taskId_1";
+
+/// Counts documents of `collection_name` that do not have `field` yet
+async fn count_missing(
+    app_database: &AppDatabase,
+    collection_name: &str,
+    field: &str,
+) -> Result<u64, DbError> {
+    app_database
+        .collection::<Document>(collection_name)
+        .count_documents(doc! { field: { "$exists": false } })
+        .await
+        .map_err(Into::into)
+}
+
+async fn default_user(users: &UserRepository) -> Result<User, DbError> {
+    match users.find_by_username(DEFAULT_USERNAME).await? {
+        Some(user) => Ok(user),
+        None => users.create_user(DEFAULT_USERNAME, None).await,
+    }
+}
+
+/// Filter and upsert that continue `owner_id`'s private vault sequence after the legacy task
+/// numbers, up to `legacy_seq`, so new tasks never reuse a migrated task's number
+fn adopt_legacy_sequence(owner_id: i64, legacy_seq: i64) -> (Document, Document) {
+    (
+        doc! { "_id": TaskVault::User(owner_id).sequence() },
+        doc! { "$max": { "seq": legacy_seq } },
+    )
+}
+
+/// Gives data from before per-user vaults to the default user and creates the indexes that
+/// keep usernames and per-vault task numbers unique and each user to one running timer. Every
+/// step only touches documents that were not migrated yet, so running it on every start is safe.
+pub async fn ensure_user_vaults(app_database: &AppDatabase) -> Result<(), DbError> {
+    let users = UserRepository::new(app_database.clone());
+
+    let unowned_tasks = count_missing(app_database, TASKS_COLLECTION_NAME, "ownerId").await?;
+    let mut unowned = unowned_tasks
+        + count_missing(app_database, ACCESS_TOKENS_COLLECTION_NAME, "userId").await?;
+    for collection_name in OWNED_COLLECTION_NAMES {
+        unowned += count_missing(app_database, collection_name, "ownerId").await?;
+    }
+
+    if unowned > 0 || users.find_all().await?.is_empty() {
+        let owner = default_user(&users).await?;
+        if unowned > 0 {
+            info!("Moving {} documents into the vault of '{}'", unowned, owner.username);
+        }
+
+        // Tasks keep their number, which becomes the first stretch of the owner's sequence
+        app_database
+            .collection::<Document>(TASKS_COLLECTION_NAME)
+            .update_many(
+                doc! { "ownerId": { "$exists": false } },
+                vec![doc! { "$set": { "taskId": "$_id", "ownerId": owner.id } }],
+            )
+            .await?;
+        if unowned_tasks > 0 {
+            let counters = app_database.collection::<Document>(AUTO_INCREMENT_COLLECTION_NAME);
+            let legacy_seq = counters
+                .find_one(doc! { "_id": TASKS_COLLECTION_NAME })
+                .await?
+                .and_then(|counter| counter.get_i64("seq").ok())
+                .unwrap_or(0);
+            let (filter, update) = adopt_legacy_sequence(owner.id, legacy_seq);
+            counters.update_one(filter, update).upsert(true).await?;
+        }
+
+        for collection_name in OWNED_COLLECTION_NAMES {
+            app_database
+                .collection::<Document>(collection_name)
+                .update_many(
+                    doc! { "ownerId": { "$exists": false } },
+                    doc! { "$set": { "ownerId": owner.id } },
+                )
+                .await?;
+        }
+        app_database
+            .collection::<Document>(ACCESS_TOKENS_COLLECTION_NAME)
+            .update_many(
+                doc! { "userId": { "$exists": false } },
+                doc! { "$set": { "userId": owner.id } },
+            )
+            .await?;
+    }
+
+    let tasks = app_database.collection::<Document>(TASKS_COLLECTION_NAME);
+    // Workspace tasks record their creator as `ownerId` but are numbered per workspace, so the
+    // original owner-wide index would reject them
+    if tasks
+        .list_index_names()
+        .await?
+        .iter()
+        .any(|name| name == LEGACY_TASK_NUMBER_INDEX)
+    {
+        tasks.drop_index(LEGACY_TASK_NUMBER_INDEX).await?;
+    }
+    let unique = IndexOptions::builder().unique(true).build();
+    tasks
+        .create_index(
+            IndexModel::builder()
+                .keys(doc! { "ownerId": 1, "workspaceId": 1, "taskId": 1 })
+                .options(unique.clone())
+                .build(),
+        )
+        .await?;
+    tasks
+        .create_index(
+            IndexModel::builder()
+                .keys(doc! { "workspaceId": 1, "taskId": 1 })
+                .options(
+                    IndexOptions::builder()
+                        .unique(true)
+                        .partial_filter_expression(doc! { "workspaceId": { "$type": "long" } })
+                        .build(),
+                )
+                .build(),
+        )
+        .await?;
+    app_database
+        .collection::<Document>(USERS_COLLECTION_NAME)
+        .create_index(
+            IndexModel::builder()
+                .keys(doc! { "username": 1 })
+                .options(unique)
+                .build(),
+        )
+        .await?;
+    // Two timer starts racing each other cannot both leave a timer running
+    app_database
+        .collection::<Document>(TIME_ENTRIES_COLLECTION_NAME)
+        .create_index(
+            IndexModel::builder()
+                .keys(doc! { "ownerId": 1 })
+                .options(
+                    IndexOptions::builder()
+                        .unique(true)
+                        .name("ownerId_1_running".to_string())
+                        .partial_filter_expression(doc! {
+                            "endedAt": { "$type": "null" },
+                            "deleted": false,
+                        })
+                        .build(),
+                )
+                .build(),
+        )
+        .await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn migrated_tasks_continue_the_owner_s_sequence() {
+        let (filter, update) = adopt_legacy_sequence(5, 42);
+        assert_eq!(filter, doc! { "_id": "tasks:5" });
+        // `$max` never winds back a sequence the owner has already moved past
+        assert_eq!(update, doc! { "$max": { "seq": 42_i64 } });
+    }
+
+    #[test]
+    fn the_legacy_counter_is_not_the_owner_s_sequence() {
+        let (filter, _) = adopt_legacy_sequence(5, 42);
+        assert_ne!(filter.get_str("_id").unwrap(), TASKS_COLLECTION_NAME);
+        let (other, _) = adopt_legacy_sequence(6, 42);
+        assert_ne!(filter, other);
+    }
+}
@@ -0,0 +1,119 @@
+use crate::models::{AppDatabase, DbCollection, DbError};
+use crate::repository::auto_increment::{get_next_id_for_collection, AUTO_INCREMENT_COLLECTION_NAME};
+use bson::{DateTime as BsonDateTime, Document};
+use futures_util::TryStreamExt;
+use mindvault_shared::models::access_tokens_model::{AccessToken, ETokenScope};
+use mongodb::bson::doc;
+use mongodb::options::{FindOneAndUpdateOptions, ReturnDocument};
+use mongodb::Collection;
+use tracing::info;
+
+pub struct AccessTokenRepository {
+    collection: Collection<AccessToken>,
+    counters_collection: DbCollection<Document>,
+}
+
+const COLLECTION_NAME: &str = "access_tokens";
+
+impl AccessTokenRepository {
+    pub fn new(app_database: AppDatabase) -> Self {
+        let collection = app_database.collection::<AccessToken>(COLLECTION_NAME);
+        let counters_collection =
+            app_database.collection::<Document>(AUTO_INCREMENT_COLLECTION_NAME);
+        Self {
+            collection,
+            counters_collection,
+        }
+    }
+
+    /// Every token, revoked ones included, newest first
+    pub async fn find_all(&self) -> Result<Vec<AccessToken>, DbError> {
+        self.collection
+            .find(doc! {})
+            .sort(doc! { "_id": -1 })
+            .await?
+            .try_collect()
+            .await
+            .map_err(Into::into)
+    }
+
+    /// The unrevoked token whose secret hashes to `token_hash`
+    pub async fn find_active_by_hash(&self, token_hash: &str) -> Result<Option<AccessToken>, DbError> {
+        let query = doc! {
+            "tokenHash": token_hash,
+            "$or": [
+                { "revokedAt": null },
+                { "revokedAt": { "$exists": false } }
+            ]
+        };
+        self.collection.find_one(query).await.map_err(Into::into)
+    }
+
+    pub async fn create_token(
+        &self,
+        user_id: i64,
+        name: String,
+        scopes: Vec<ETokenScope>,
+        token_hash: String,
+        prefix: String,
+    ) -> Result<AccessToken, DbError> {
+        let next_id = get_next_id_for_collection(&self.counters_collection, COLLECTION_NAME).await?;
+
+        let mut unique_scopes = Vec::with_capacity(scopes.len());
+        for scope in scopes {
+            if !unique_scopes.contains(&scope) {
+                unique_scopes.push(scope);
+            }
+        }
+
+        let token = AccessToken {
+            id: next_id,
+            user_id,
+            name,
+            token_hash,
+            prefix,
+            scopes: unique_scopes,
+            created_at: BsonDateTime::now(),
+            last_used_at: None,
+            revoked_at: None,
+        };
+
+        info!(
+            "Created access token {} ({}) for user {}",
+            token.id, token.name, token.user_id
+        );
+
+        match self.collection.insert_one(&token).await {
+            Ok(_) => Ok(token),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn touch_last_used(&self, id: i64) -> Result<(), DbError> {
+        self.collection
+            .update_one(doc! { "_id": id }, doc! { "$set": { "lastUsedAt": BsonDateTime::now() } })
+            .await?;
+        Ok(())
+    }
+
+    /// Revokes a token, returning it as it is now; `None` if it does not exist or was already revoked
+    pub async fn revoke_by_id(&self, id: i64) -> Result<Option<AccessToken>, DbError> {
+        let filter = doc! {
+            "_id": id,
+            "$or": [
+                { "revokedAt": null },
+                { "revokedAt": { "$exists": false } }
+            ]
+        };
+        let update = doc! { "$set": { "revokedAt": BsonDateTime::now() } };
+        let options = FindOneAndUpdateOptions::builder()
+            .return_document(ReturnDocument::After)
+            .build();
+
+        self.collection
+            .find_one_and_update(filter, update)
+            .with_options(options)
+            .await
+            .map_err(Into::into)
+    }
+}
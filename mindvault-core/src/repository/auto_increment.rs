@@ -52,3 +52,8 @@ pub async fn get_next_id_range_for_collection(
     // Return the starting ID (final_seq - count + 1)
     Ok(final_seq - count + 1)
 }
+
+/// Counter name for a sequence kept separately per user, e.g. `tasks:3`
+pub fn owner_sequence(collection_name: &str, owner_id: i64) -> String {
+    format!("{collection_name}:{owner_id}")
+}
@@ -0,0 +1,164 @@
+use bson::{doc, DateTime as BsonDateTime, Document};
+use chrono::{DateTime, Duration, NaiveDate, NaiveTime, TimeZone, Utc};
+use chrono_tz::Tz;
+
+/// Converts a NaiveDate to a BsonDateTime at midnight UTC
+pub fn naive_date_to_bson(date: NaiveDate) -> BsonDateTime {
+    let naive_dt = date.and_hms_opt(0, 0, 0).unwrap();
+    let utc_dt = Utc.from_utc_datetime(&naive_dt);
+    BsonDateTime::from(utc_dt)
+}
+
+/// Builds a `$gte`/`$lt` filter on `field` covering whole days from `from` to `to` inclusive.
+/// Returns `None` when neither bound is set.
+pub fn day_window_filter(
+    field: &str,
+    from: Option<NaiveDate>,
+    to: Option<NaiveDate>,
+) -> Option<bson::Document> {
+    let mut range = bson::Document::new();
+    if let Some(from) = from {
+        range.insert("$gte", naive_date_to_bson(from));
+    }
+    if let Some(to) = to {
+        range.insert("$lt", naive_date_to_bson(to + chrono::Duration::days(1)));
+    }
+    if range.is_empty() {
+        return None;
+    }
+    let mut filter = bson::Document::new();
+    filter.insert(field, range);
+    Some(filter)
+}
+
+/// First instant of `day` in `tz`; a day that starts inside a DST gap begins an hour later
+pub fn start_of_day(day: NaiveDate, tz: Tz) -> BsonDateTime {
+    let midnight = day.and_time(NaiveTime::MIN);
+    let start = tz
+        .from_local_datetime(&midnight)
+        .earliest()
+        .or_else(|| tz.from_local_datetime(&(midnight + Duration::hours(1))).earliest())
+        .map(|start| start.with_timezone(&Utc))
+        .unwrap_or_else(|| Utc.from_utc_datetime(&midnight));
+    BsonDateTime::from(start)
+}
+
+/// Matches tasks due on a day from `from` up to but excluding `until`, as seen in `tz`.
+/// Floating due days are compared as dates; due times by the local day they fall on.
+pub fn due_between_condition(from: NaiveDate, until: NaiveDate, tz: Tz) -> Document {
+    doc! {
+        "$or": [
+            {
+                "dueHasTime": true,
+                "dueDate": { "$gte": start_of_day(from, tz), "$lt": start_of_day(until, tz) }
+            },
+            {
+                "dueHasTime": { "$ne": true },
+                "dueDate": { "$gte": naive_date_to_bson(from), "$lt": naive_date_to_bson(until) }
+            }
+        ]
+    }
+}
+
+/// Matches tasks due before the current day in `tz`, or before `now` for due times
+pub fn overdue_condition(now: DateTime<Utc>, tz: Tz) -> Document {
+    let today = now.with_timezone(&tz).date_naive();
+    doc! {
+        "$or": [
+            { "dueHasTime": true, "dueDate": { "$lt": BsonDateTime::from(now) } },
+            { "dueHasTime": { "$ne": true }, "dueDate": { "$lt": naive_date_to_bson(today) } }
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{due_between_condition, naive_date_to_bson, overdue_condition, start_of_day};
+    use bson::{DateTime as BsonDateTime, Document};
+    use chrono::{NaiveDate, NaiveDateTime, TimeZone, Utc};
+    use chrono_tz::Tz;
+
+    fn day(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    /// A wall-clock time in `tz`, as a due time is stored
+    fn local(tz: Tz, s: &str) -> BsonDateTime {
+        let local = NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M").unwrap();
+        BsonDateTime::from(tz.from_local_datetime(&local).single().unwrap().with_timezone(&Utc))
+    }
+
+    fn utc(s: &str) -> BsonDateTime {
+        local(Tz::UTC, s)
+    }
+
+    /// Evaluates a `dueHasTime`/`dueDate` condition the way MongoDB would for one task
+    fn matches(condition: &Document, due: BsonDateTime, has_time: bool) -> bool {
+        condition.get_array("$or").unwrap().iter().any(|branch| {
+            let branch = branch.as_document().unwrap();
+            let range = branch.get_document("dueDate").unwrap();
+            branch.get_bool("dueHasTime").unwrap_or(false) == has_time
+                && range.get_datetime("$gte").map_or(true, |from| due >= *from)
+                && range.get_datetime("$lt").map_or(true, |until| due < *until)
+        })
+    }
+
+    #[test]
+    fn a_spring_forward_day_is_23_hours_long() {
+        let tz = Tz::Europe__Berlin;
+        assert_eq!(start_of_day(day("2026-03-29"), tz), utc("2026-03-28 23:00"));
+        assert_eq!(start_of_day(day("2026-03-30"), tz), utc("2026-03-29 22:00"));
+
+        let condition = due_between_condition(day("2026-03-29"), day("2026-03-30"), tz);
+        assert!(matches(&condition, local(tz, "2026-03-29 00:30"), true));
+        assert!(matches(&condition, local(tz, "2026-03-29 03:30"), true));
+        assert!(matches(&condition, local(tz, "2026-03-29 23:30"), true));
+        assert!(!matches(&condition, local(tz, "2026-03-30 00:30"), true));
+        assert!(!matches(&condition, local(tz, "2026-03-28 23:30"), true));
+        assert!(matches(&condition, naive_date_to_bson(day("2026-03-29")), false));
+        assert!(!matches(&condition, naive_date_to_bson(day("2026-03-30")), false));
+    }
+
+    #[test]
+    fn a_day_starting_in_a_dst_gap_starts_an_hour_later() {
+        let tz = Tz::America__Santiago;
+        assert_eq!(start_of_day(day("2026-09-06"), tz), local(tz, "2026-09-06 01:00"));
+        assert_eq!(start_of_day(day("2026-09-06"), tz), utc("2026-09-06 04:00"));
+    }
+
+    #[test]
+    fn due_times_around_local_midnight_fall_on_their_local_day() {
+        let tz = Tz::America__New_York;
+        let today = due_between_condition(day("2026-10-19"), day("2026-10-20"), tz);
+        let tomorrow = due_between_condition(day("2026-10-20"), day("2026-10-21"), tz);
+
+        let before_midnight = local(tz, "2026-10-19 23:59");
+        assert_eq!(before_midnight, utc("2026-10-20 03:59"));
+        assert!(matches(&today, before_midnight, true));
+        assert!(!matches(&tomorrow, before_midnight, true));
+
+        let after_midnight = local(tz, "2026-10-20 00:01");
+        assert!(!matches(&today, after_midnight, true));
+        assert!(matches(&tomorrow, after_midnight, true));
+    }
+
+    #[test]
+    fn overdue_follows_the_local_day_and_the_current_time() {
+        let tz = Tz::America__New_York;
+        // 23:58 on 19 October in New York, already 20 October in UTC
+        let now = Utc.with_ymd_and_hms(2026, 10, 20, 3, 58, 0).unwrap();
+        let condition = overdue_condition(now, tz);
+
+        assert!(matches(&condition, local(tz, "2026-10-19 23:57"), true));
+        assert!(!matches(&condition, local(tz, "2026-10-19 23:59"), true));
+        assert!(!matches(&condition, local(tz, "2026-10-20 00:01"), true));
+        assert!(matches(&condition, naive_date_to_bson(day("2026-10-18")), false));
+        assert!(!matches(&condition, naive_date_to_bson(day("2026-10-19")), false));
+
+        let after_midnight = Utc.with_ymd_and_hms(2026, 10, 20, 4, 1, 0).unwrap();
+        let condition = overdue_condition(after_midnight, tz);
+        assert!(matches(&condition, naive_date_to_bson(day("2026-10-19")), false));
+        assert!(matches(&condition, local(tz, "2026-10-19 23:59"), true));
+        assert!(!matches(&condition, local(tz, "2026-10-20 00:01"), true));
+    }
+}
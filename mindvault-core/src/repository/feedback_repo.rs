@@ -0,0 +1,182 @@
+use crate::models::{AppDatabase, DbCollection, DbError};
+use crate::repository::auto_increment::{get_next_id_for_collection, AUTO_INCREMENT_COLLECTION_NAME};
+use crate::repository::date_utils::{day_window_filter, naive_date_to_bson};
+use crate::repository::tag_utils::normalize_tags;
+use bson::{DateTime as BsonDateTime, Document};
+use futures_util::TryStreamExt;
+use mindvault_shared::dtos::feedback_dtos::{
+    CreateFeedbackRequest, FeedbackSearchParams, UpdateFeedbackRequest,
+};
+use mindvault_shared::models::feedback_model::FeedbackEntry;
+use mindvault_shared::utils::field_encryption::{seal, EncryptedField};
+use mongodb::bson::doc;
+use mongodb::{bson, Collection};
+use tracing::info;
+
+pub struct FeedbackRepository {
+    collection: Collection<FeedbackEntry>,
+    counters_collection: DbCollection<Document>,
+}
+
+const COLLECTION_NAME: &str = "feedback";
+
+impl FeedbackRepository {
+    pub fn new(app_database: AppDatabase) -> Self {
+        let collection = app_database.collection::<FeedbackEntry>(COLLECTION_NAME);
+        let counters_collection =
+            app_database.collection::<Document>(AUTO_INCREMENT_COLLECTION_NAME);
+        Self {
+            collection,
+            counters_collection,
+        }
+    }
+
+    pub async fn find_by_id(
+        &self,
+        owner_id: i64,
+        id: i64,
+    ) -> Result<Option<FeedbackEntry>, DbError> {
+        let query = doc! {
+            "_id": id,
+            "ownerId": owner_id,
+            "$or": [
+                { "deleted": { "$ne": true } },
+                { "deleted": { "$exists": false } }
+            ]
+        };
+        self.collection.find_one(query).await.map_err(Into::into)
+    }
+
+    pub async fn create_entry(
+        &self,
+        owner_id: i64,
+        new_entry: CreateFeedbackRequest,
+    ) -> Result<FeedbackEntry, DbError> {
+        let next_id = get_next_id_for_collection(&self.counters_collection, COLLECTION_NAME).await?;
+
+        let created_at = BsonDateTime::now();
+        let observed_on = new_entry
+            .observed_on
+            .and_then(|date| date.as_day())
+            .map(naive_date_to_bson)
+            .unwrap_or(created_at);
+
+        let entry = FeedbackEntry {
+            id: next_id,
+            owner_id,
+            reportee_id: new_entry.reportee_id,
+            note: new_entry.note,
+            sentiment: new_entry.sentiment.unwrap_or_default(),
+            tags: normalize_tags(new_entry.tags),
+            observed_on,
+            created_at,
+            deleted: Some(false),
+        };
+
+        info!("Creating feedback entry {} for reportee {}", entry.id, entry.reportee_id);
+
+        match self.collection.insert_one(&entry).await {
+            Ok(_) => Ok(entry),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Search feedback entries, ordered by the day they were observed
+    pub async fn search_entries(
+        &self,
+        owner_id: i64,
+        params: FeedbackSearchParams,
+    ) -> Result<Vec<FeedbackEntry>, DbError> {
+        let mut conditions = vec![doc! {
+            "ownerId": owner_id,
+            "$or": [
+                { "deleted": { "$ne": true } },
+                { "deleted": { "$exists": false } }
+            ]
+        }];
+
+        if let Some(reportee_id) = params.reportee_id {
+            conditions.push(doc! { "reporteeId": reportee_id });
+        }
+        if let Some(sentiment) = params.sentiment {
+            conditions.push(doc! { "sentiment": bson::to_bson(&sentiment)? });
+        }
+        if let Some(tag) = params.tag {
+            conditions.push(doc! { "tags": tag.trim().to_lowercase() });
+        }
+        if let Some(window) = day_window_filter(
+            "observedOn",
+            params.from.and_then(|date| date.as_day()),
+            params.to.and_then(|date| date.as_day()),
+        ) {
+            conditions.push(window);
+        }
+
+        self.collection
+            .find(doc! { "$and": conditions })
+            .sort(doc! { "observedOn": 1, "_id": 1 })
+            .await?
+            .try_collect()
+            .await
+            .map_err(Into::into)
+    }
+
+    /// Update a feedback entry by ID with partial updates
+    pub async fn update_entry_by_id(
+        &self,
+        owner_id: i64,
+        id: i64,
+        update_request: UpdateFeedbackRequest,
+    ) -> Result<Option<FeedbackEntry>, DbError> {
+        let filter = doc! {
+            "_id": id,
+            "ownerId": owner_id,
+            "$or": [
+                { "deleted": { "$ne": true } },
+                { "deleted": { "$exists": false } }
+            ]
+        };
+
+        let mut set_fields = doc! {};
+        if let Some(note) = update_request.note {
+            set_fields.insert("note", seal(EncryptedField::FeedbackNote, &note)?);
+        }
+        if let Some(sentiment) = update_request.sentiment {
+            set_fields.insert("sentiment", bson::to_bson(&sentiment)?);
+        }
+        if let Some(tags) = update_request.tags {
+            set_fields.insert("tags", normalize_tags(tags));
+        }
+        if let Some(observed_on) = update_request.observed_on.and_then(|date| date.as_day()) {
+            set_fields.insert("observedOn", naive_date_to_bson(observed_on));
+        }
+
+        let update = doc! { "$set": set_fields };
+
+        let options = mongodb::options::FindOneAndUpdateOptions::builder()
+            .return_document(mongodb::options::ReturnDocument::After)
+            .build();
+
+        self.collection
+            .find_one_and_update(filter, update)
+            .with_options(options)
+            .await
+            .map_err(Into::into)
+    }
+
+    /// Softly delete a feedback entry by setting deleted: true
+    pub async fn soft_delete_by_id(&self, owner_id: i64, id: i64) -> Result<bool, DbError> {
+        let filter = doc! {
+            "_id": id,
+            "ownerId": owner_id,
+            "$or": [
+                { "deleted": { "$ne": true } },
+                { "deleted": { "$exists": false } }
+            ]
+        };
+        let update = doc! { "$set": { "deleted": true } };
+
+        let result = self.collection.update_one(filter, update).await?;
+        Ok(result.modified_count > 0)
+    }
+}
@@ -0,0 +1,395 @@
+use crate::models::{AppDatabase, DbCollection, DbError};
+use crate::repository::auto_increment::{
+    get_next_id_for_collection, get_next_id_range_for_collection, AUTO_INCREMENT_COLLECTION_NAME,
+};
+use bson::{DateTime as BsonDateTime, Document};
+use futures_util::TryStreamExt;
+use mindvault_shared::dtos::goal_dtos::{
+    CreateGoalRequest, CreateKeyResultRequest, GoalSearchParams, UpdateGoalRequest,
+    UpdateKeyResultRequest,
+};
+use mindvault_shared::models::goals_model::{
+    EProgressSource, Goal, KeyResult, KeyResultCheckIn,
+};
+use mongodb::bson::doc;
+use mongodb::options::{FindOneAndUpdateOptions, ReturnDocument};
+use mongodb::{bson, Collection};
+use tracing::info;
+
+pub struct GoalRepository {
+    collection: Collection<Goal>,
+    check_ins_collection: Collection<KeyResultCheckIn>,
+    counters_collection: DbCollection<Document>,
+}
+
+const COLLECTION_NAME: &str = "goals";
+const KEY_RESULTS_COUNTER_NAME: &str = "key_results";
+const CHECK_INS_COLLECTION_NAME: &str = "key_result_check_ins";
+
+impl GoalRepository {
+    pub fn new(app_database: AppDatabase) -> Self {
+        let collection = app_database.collection::<Goal>(COLLECTION_NAME);
+        let check_ins_collection =
+            app_database.collection::<KeyResultCheckIn>(CHECK_INS_COLLECTION_NAME);
+        let counters_collection =
+            app_database.collection::<Document>(AUTO_INCREMENT_COLLECTION_NAME);
+        Self {
+            collection,
+            check_ins_collection,
+            counters_collection,
+        }
+    }
+
+    fn build_key_result(id: i64, request: CreateKeyResultRequest) -> KeyResult {
+        let start_value = request.start_value.unwrap_or(0.0);
+        KeyResult {
+            id,
+            title: request.title,
+            start_value,
+            target_value: request.target_value,
+            current_value: start_value,
+            unit: request.unit,
+            progress_source: request.progress_source.unwrap_or_default(),
+            linked_task_ids: Vec::new(),
+        }
+    }
+
+    /// Filter matching a live goal of the owner that contains the given key result
+    fn key_result_filter(owner_id: i64, goal_id: i64, key_result_id: i64) -> Document {
+        doc! {
+            "_id": goal_id,
+            "ownerId": owner_id,
+            "keyResults.id": key_result_id,
+            "$or": [
+                { "deleted": { "$ne": true } },
+                { "deleted": { "$exists": false } }
+            ]
+        }
+    }
+
+    async fn update_key_result(
+        &self,
+        owner_id: i64,
+        goal_id: i64,
+        key_result_id: i64,
+        update: Document,
+    ) -> Result<Option<Goal>, DbError> {
+        let options = FindOneAndUpdateOptions::builder()
+            .return_document(ReturnDocument::After)
+            .build();
+
+        self.collection
+            .find_one_and_update(Self::key_result_filter(owner_id, goal_id, key_result_id), update)
+            .with_options(options)
+            .await
+            .map_err(Into::into)
+    }
+
+    pub async fn find_by_id(&self, owner_id: i64, id: i64) -> Result<Option<Goal>, DbError> {
+        let query = doc! {
+            "_id": id,
+            "ownerId": owner_id,
+            "$or": [
+                { "deleted": { "$ne": true } },
+                { "deleted": { "$exists": false } }
+            ]
+        };
+        self.collection.find_one(query).await.map_err(Into::into)
+    }
+
+    pub async fn search_goals(
+        &self,
+        owner_id: i64,
+        params: GoalSearchParams,
+    ) -> Result<Vec<Goal>, DbError> {
+        let mut conditions = vec![doc! { "ownerId": owner_id }, doc! {
+            "$or": [
+                { "deleted": { "$ne": true } },
+                { "deleted": { "$exists": false } }
+            ]
+        }];
+
+        if let Some(owner_reportee_id) = params.owner_reportee_id {
+            conditions.push(doc! { "ownerReporteeId": owner_reportee_id });
+        }
+        if let Some(period) = params.period {
+            conditions.push(doc! { "period": period });
+        }
+
+        self.collection
+            .find(doc! { "$and": conditions })
+            .sort(doc! { "period": 1, "_id": 1 })
+            .await?
+            .try_collect()
+            .await
+            .map_err(Into::into)
+    }
+
+    pub async fn create_goal(
+        &self,
+        owner_id: i64,
+        new_goal: CreateGoalRequest,
+    ) -> Result<Goal, DbError> {
+        let next_goal_id =
+            get_next_id_for_collection(&self.counters_collection, COLLECTION_NAME).await?;
+
+        let mut key_results = Vec::new();
+        if !new_goal.key_results.is_empty() {
+            let start_id = get_next_id_range_for_collection(
+                &self.counters_collection,
+                KEY_RESULTS_COUNTER_NAME,
+                new_goal.key_results.len() as i64,
+            )
+            .await?;
+            for (index, request) in new_goal.key_results.into_iter().enumerate() {
+                key_results.push(Self::build_key_result(start_id + index as i64, request));
+            }
+        }
+
+        let goal = Goal {
+            id: next_goal_id,
+            owner_id,
+            objective: new_goal.objective,
+            description: new_goal.description,
+            owner_reportee_id: new_goal.owner_reportee_id,
+            period: new_goal.period,
+            key_results,
+            created_at: BsonDateTime::now(),
+            deleted: Some(false),
+        };
+
+        info!("{:?}", goal);
+
+        match self.collection.insert_one(&goal).await {
+            Ok(_) => Ok(goal),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Update a goal by ID with partial updates (key results are managed separately)
+    pub async fn update_goal_by_id(
+        &self,
+        owner_id: i64,
+        id: i64,
+        update_request: UpdateGoalRequest,
+    ) -> Result<Option<Goal>, DbError> {
+        let filter = doc! {
+            "_id": id,
+            "ownerId": owner_id,
+            "$or": [
+                { "deleted": { "$ne": true } },
+                { "deleted": { "$exists": false } }
+            ]
+        };
+
+        let mut set_fields = doc! {};
+        if let Some(objective) = update_request.objective {
+            set_fields.insert("objective", objective);
+        }
+        if let Some(description) = update_request.description {
+            set_fields.insert("description", description);
+        }
+        if let Some(owner_reportee_id) = update_request.owner_reportee_id {
+            set_fields.insert("ownerReporteeId", owner_reportee_id);
+        }
+        if let Some(period) = update_request.period {
+            set_fields.insert("period", period);
+        }
+
+        let update = doc! { "$set": set_fields };
+
+        let options = FindOneAndUpdateOptions::builder()
+            .return_document(ReturnDocument::After)
+            .build();
+
+        self.collection
+            .find_one_and_update(filter, update)
+            .with_options(options)
+            .await
+            .map_err(Into::into)
+    }
+
+    /// Softly delete a goal by setting deleted: true
+    pub async fn soft_delete_by_id(&self, owner_id: i64, id: i64) -> Result<bool, DbError> {
+        let filter = doc! {
+            "_id": id,
+            "ownerId": owner_id,
+            "$or": [
+                { "deleted": { "$ne": true } },
+                { "deleted": { "$exists": false } }
+            ]
+        };
+        let update = doc! { "$set": { "deleted": true } };
+
+        let result = self.collection.update_one(filter, update).await?;
+        Ok(result.modified_count > 0)
+    }
+
+    pub async fn add_key_result(
+        &self,
+        owner_id: i64,
+        goal_id: i64,
+        request: CreateKeyResultRequest,
+    ) -> Result<Option<Goal>, DbError> {
+        let key_result_id =
+            get_next_id_for_collection(&self.counters_collection, KEY_RESULTS_COUNTER_NAME).await?;
+        let key_result = Self::build_key_result(key_result_id, request);
+
+        let filter = doc! {
+            "_id": goal_id,
+            "ownerId": owner_id,
+            "$or": [
+                { "deleted": { "$ne": true } },
+                { "deleted": { "$exists": false } }
+            ]
+        };
+        let update = doc! { "$push": { "keyResults": bson::to_bson(&key_result)? } };
+
+        let options = FindOneAndUpdateOptions::builder()
+            .return_document(ReturnDocument::After)
+            .build();
+
+        self.collection
+            .find_one_and_update(filter, update)
+            .with_options(options)
+            .await
+            .map_err(Into::into)
+    }
+
+    pub async fn update_key_result_by_id(
+        &self,
+        owner_id: i64,
+        goal_id: i64,
+        key_result_id: i64,
+        update_request: UpdateKeyResultRequest,
+    ) -> Result<Option<Goal>, DbError> {
+        let mut set_fields = doc! {};
+        if let Some(title) = update_request.title {
+            set_fields.insert("keyResults.$.title", title);
+        }
+        if let Some(start_value) = update_request.start_value {
+            set_fields.insert("keyResults.$.startValue", start_value);
+        }
+        if let Some(target_value) = update_request.target_value {
+            set_fields.insert("keyResults.$.targetValue", target_value);
+        }
+        if let Some(unit) = update_request.unit {
+            set_fields.insert("keyResults.$.unit", unit);
+        }
+        if let Some(progress_source) = update_request.progress_source {
+            set_fields.insert(
+                "keyResults.$.progressSource",
+                bson::to_bson(&progress_source)?,
+            );
+        }
+
+        self.update_key_result(owner_id, goal_id, key_result_id, doc! { "$set": set_fields })
+            .await
+    }
+
+    pub async fn remove_key_result(
+        &self,
+        owner_id: i64,
+        goal_id: i64,
+        key_result_id: i64,
+    ) -> Result<Option<Goal>, DbError> {
+        self.update_key_result(
+            owner_id,
+            goal_id,
+            key_result_id,
+            doc! { "$pull": { "keyResults": { "id": key_result_id } } },
+        )
+        .await
+    }
+
+    pub async fn link_tasks(
+        &self,
+        owner_id: i64,
+        goal_id: i64,
+        key_result_id: i64,
+        task_ids: &[i64],
+    ) -> Result<Option<Goal>, DbError> {
+        self.update_key_result(
+            owner_id,
+            goal_id,
+            key_result_id,
+            doc! { "$addToSet": { "keyResults.$.linkedTaskIds": { "$each": task_ids } } },
+        )
+        .await
+    }
+
+    pub async fn unlink_task(
+        &self,
+        owner_id: i64,
+        goal_id: i64,
+        key_result_id: i64,
+        task_id: i64,
+    ) -> Result<Option<Goal>, DbError> {
+        self.update_key_result(
+            owner_id,
+            goal_id,
+            key_result_id,
+            doc! { "$pull": { "keyResults.$.linkedTaskIds": task_id } },
+        )
+        .await
+    }
+
+    /// Sets the current value of a key result and appends it to the progress history
+    pub async fn record_progress(
+        &self,
+        owner_id: i64,
+        goal_id: i64,
+        key_result_id: i64,
+        value: f64,
+        note: Option<String>,
+        source: EProgressSource,
+    ) -> Result<Option<Goal>, DbError> {
+        let goal = self
+            .update_key_result(
+                owner_id,
+                goal_id,
+                key_result_id,
+                doc! { "$set": { "keyResults.$.currentValue": value } },
+            )
+            .await?;
+        if goal.is_none() {
+            return Ok(None);
+        }
+
+        let check_in_id =
+            get_next_id_for_collection(&self.counters_collection, CHECK_INS_COLLECTION_NAME)
+                .await?;
+        let check_in = KeyResultCheckIn {
+            id: check_in_id,
+            goal_id,
+            key_result_id,
+            value,
+            note,
+            source,
+            recorded_at: BsonDateTime::now(),
+        };
+        self.check_ins_collection.insert_one(&check_in).await?;
+
+        Ok(goal)
+    }
+
+    /// Progress history of a key result, oldest first; empty unless the owner has the goal
+    pub async fn find_check_ins(
+        &self,
+        owner_id: i64,
+        goal_id: i64,
+        key_result_id: i64,
+    ) -> Result<Vec<KeyResultCheckIn>, DbError> {
+        if self.find_by_id(owner_id, goal_id).await?.is_none() {
+            return Ok(Vec::new());
+        }
+
+        self.check_ins_collection
+            .find(doc! { "goalId": goal_id, "keyResultId": key_result_id })
+            .sort(doc! { "recordedAt": 1, "_id": 1 })
+            .await?
+            .try_collect()
+            .await
+            .map_err(Into::into)
+    }
+}
@@ -0,0 +1,126 @@
+use crate::models::{AppDatabase, DbError};
+use bson::DateTime as BsonDateTime;
+use futures_util::TryStreamExt;
+use mindvault_shared::models::jobs_model::JobState;
+use mongodb::bson::doc;
+use mongodb::options::{FindOneAndUpdateOptions, ReturnDocument};
+use mongodb::{bson, Collection};
+
+pub struct JobRepository {
+    collection: Collection<JobState>,
+}
+
+const COLLECTION_NAME: &str = "scheduler_jobs";
+
+impl JobRepository {
+    pub fn new(app_database: AppDatabase) -> Self {
+        let collection = app_database.collection::<JobState>(COLLECTION_NAME);
+        Self { collection }
+    }
+
+    pub async fn find_all(&self) -> Result<Vec<JobState>, DbError> {
+        self.collection
+            .find(doc! {})
+            .sort(doc! { "_id": 1 })
+            .await?
+            .try_collect()
+            .await
+            .map_err(Into::into)
+    }
+
+    /// Registers a job, keeping any persisted run history. A job seen for the first time, or
+    /// whose schedule changed, is scheduled at `first_run_at`; otherwise it keeps its stored
+    /// next run so that runs missed while no instance was up are caught up immediately.
+    /// `enabled` is only recorded for the admin view: whether a job runs is decided by each
+    /// instance, which leaves the leases of jobs it has disabled alone.
+    pub async fn register(
+        &self,
+        name: &str,
+        schedule: &str,
+        enabled: bool,
+        first_run_at: BsonDateTime,
+    ) -> Result<JobState, DbError> {
+        // Every expression reads the stored document, so `$schedule` is the old schedule
+        let update = vec![doc! { "$set": {
+            "nextRunAt": { "$cond": [
+                { "$eq": ["$schedule", schedule] },
+                "$nextRunAt",
+                first_run_at,
+            ] },
+            "schedule": schedule,
+            "enabled": enabled,
+            "runCount": { "$ifNull": ["$runCount", 0_i64] },
+        } }];
+        let options = FindOneAndUpdateOptions::builder()
+            .upsert(true)
+            .return_document(ReturnDocument::After)
+            .build();
+
+        self.collection
+            .find_one_and_update(doc! { "_id": name }, update)
+            .with_options(options)
+            .await?
+            .ok_or_else(|| DbError::InternalError(format!("job {} was not registered", name)))
+    }
+
+    /// Takes the run lease of a due job unless another instance holds an unexpired one, as
+    /// `JobState::can_take_lease` decides. Returns `None` when the job is not due or is
+    /// leased elsewhere.
+    pub async fn try_acquire_lease(
+        &self,
+        name: &str,
+        owner: &str,
+        now: BsonDateTime,
+        lease_expires_at: BsonDateTime,
+    ) -> Result<Option<JobState>, DbError> {
+        let filter = doc! {
+            "_id": name,
+            "nextRunAt": { "$lte": now },
+            "$or": [
+                { "leaseExpiresAt": null },
+                { "leaseExpiresAt": { "$lte": now } }
+            ]
+        };
+        let update = doc! { "$set": {
+            "leaseOwner": owner,
+            "leaseExpiresAt": lease_expires_at,
+            "lastStartedAt": now,
+        } };
+        let options = FindOneAndUpdateOptions::builder()
+            .return_document(ReturnDocument::After)
+            .build();
+
+        self.collection
+            .find_one_and_update(filter, update)
+            .with_options(options)
+            .await
+            .map_err(Into::into)
+    }
+
+    /// Records the outcome of a run and releases the lease held by `owner`
+    pub async fn complete_run(
+        &self,
+        name: &str,
+        owner: &str,
+        finished_at: BsonDateTime,
+        duration_ms: i64,
+        error: Option<String>,
+        next_run_at: Option<BsonDateTime>,
+    ) -> Result<(), DbError> {
+        let filter = doc! { "_id": name, "leaseOwner": owner };
+        let update = doc! {
+            "$set": {
+                "lastFinishedAt": finished_at,
+                "lastDurationMs": duration_ms,
+                "lastError": error,
+                "nextRunAt": next_run_at,
+                "leaseOwner": bson::Bson::Null,
+                "leaseExpiresAt": bson::Bson::Null,
+            },
+            "$inc": { "runCount": 1_i64 },
+        };
+
+        self.collection.update_one(filter, update).await?;
+        Ok(())
+    }
+}
@@ -1,2 +1,19 @@
 pub mod task_repo;
-pub mod auto_increment;
\ No newline at end of file
+pub mod auto_increment;
+pub mod date_utils;
+pub mod tag_utils;
+pub mod reportee_repo;
+pub mod review_repo;
+pub mod feedback_repo;
+pub mod review_template_repo;
+pub mod goal_repo;
+pub mod reminder_repo;
+pub mod job_repo;
+pub mod notification_repo;
+pub mod webhook_repo;
+pub mod saved_filter_repo;
+pub mod access_token_repo;
+pub mod user_repo;
+pub mod workspace_repo;
+pub mod workflow_repo;
+pub mod time_entry_repo;
@@ -0,0 +1,370 @@
+use crate::models::{AppDatabase, DbCollection, DbError};
+use crate::repository::auto_increment::{get_next_id_for_collection, AUTO_INCREMENT_COLLECTION_NAME};
+use bson::{DateTime as BsonDateTime, Document};
+use futures_util::TryStreamExt;
+use mindvault_shared::dtos::notification_dtos::{
+    CreateChannelRequest, DeliverySearchParams, UpdateChannelRequest,
+};
+use mindvault_shared::models::notifications_model::{
+    DeliveryAttempt, EDeliveryState, ENotificationType, NotificationChannel, NotificationDelivery,
+    NotificationMessage,
+};
+use mongodb::bson::doc;
+use mongodb::options::{FindOneAndUpdateOptions, ReturnDocument};
+use mongodb::{bson, Collection};
+use tracing::info;
+
+pub struct NotificationRepository {
+    channels_collection: Collection<NotificationChannel>,
+    deliveries_collection: Collection<NotificationDelivery>,
+    counters_collection: DbCollection<Document>,
+}
+
+const CHANNELS_COLLECTION_NAME: &str = "notification_channels";
+const DELIVERIES_COLLECTION_NAME: &str = "notification_deliveries";
+const DEFAULT_DELIVERY_LIMIT: i64 = 100;
+
+impl NotificationRepository {
+    pub fn new(app_database: AppDatabase) -> Self {
+        let channels_collection =
+            app_database.collection::<NotificationChannel>(CHANNELS_COLLECTION_NAME);
+        let deliveries_collection =
+            app_database.collection::<NotificationDelivery>(DELIVERIES_COLLECTION_NAME);
+        let counters_collection =
+            app_database.collection::<Document>(AUTO_INCREMENT_COLLECTION_NAME);
+        Self {
+            channels_collection,
+            deliveries_collection,
+            counters_collection,
+        }
+    }
+
+    fn return_after() -> FindOneAndUpdateOptions {
+        FindOneAndUpdateOptions::builder()
+            .return_document(ReturnDocument::After)
+            .build()
+    }
+
+    pub async fn find_channels(&self, owner_id: i64) -> Result<Vec<NotificationChannel>, DbError> {
+        let query = doc! {
+            "ownerId": owner_id,
+            "$or": [
+                { "deleted": { "$ne": true } },
+                { "deleted": { "$exists": false } }
+            ]
+        };
+
+        self.channels_collection
+            .find(query)
+            .sort(doc! { "_id": 1 })
+            .await?
+            .try_collect()
+            .await
+            .map_err(Into::into)
+    }
+
+    pub async fn find_channel_by_id(
+        &self,
+        owner_id: i64,
+        id: i64,
+    ) -> Result<Option<NotificationChannel>, DbError> {
+        let query = doc! {
+            "_id": id,
+            "ownerId": owner_id,
+            "$or": [
+                { "deleted": { "$ne": true } },
+                { "deleted": { "$exists": false } }
+            ]
+        };
+        self.channels_collection
+            .find_one(query)
+            .await
+            .map_err(Into::into)
+    }
+
+    /// The channel a queued delivery goes to, whoever owns it
+    pub async fn find_channel_for_delivery(
+        &self,
+        id: i64,
+    ) -> Result<Option<NotificationChannel>, DbError> {
+        let query = doc! {
+            "_id": id,
+            "$or": [
+                { "deleted": { "$ne": true } },
+                { "deleted": { "$exists": false } }
+            ]
+        };
+        self.channels_collection
+            .find_one(query)
+            .await
+            .map_err(Into::into)
+    }
+
+    /// Ids of the owner's channels, deleted ones included so their delivery log stays theirs
+    async fn channel_ids(&self, owner_id: i64) -> Result<Vec<i64>, DbError> {
+        let ids = self
+            .channels_collection
+            .distinct("_id", doc! { "ownerId": owner_id })
+            .await?;
+        Ok(ids.iter().filter_map(|id| id.as_i64()).collect())
+    }
+
+    /// Enabled channels of `owner_ids` subscribed to the given notification type
+    pub async fn find_channels_for_type(
+        &self,
+        notification_type: ENotificationType,
+        owner_ids: &[i64],
+    ) -> Result<Vec<NotificationChannel>, DbError> {
+        let query = doc! {
+            "enabled": true,
+            "notificationTypes": bson::to_bson(&notification_type)?,
+            "ownerId": { "$in": owner_ids },
+            "$or": [
+                { "deleted": { "$ne": true } },
+                { "deleted": { "$exists": false } }
+            ]
+        };
+
+        self.channels_collection
+            .find(query)
+            .await?
+            .try_collect()
+            .await
+            .map_err(Into::into)
+    }
+
+    pub async fn create_channel(
+        &self,
+        owner_id: i64,
+        request: CreateChannelRequest,
+    ) -> Result<NotificationChannel, DbError> {
+        let next_id =
+            get_next_id_for_collection(&self.counters_collection, CHANNELS_COLLECTION_NAME).await?;
+
+        let channel = NotificationChannel {
+            id: next_id,
+            owner_id,
+            name: request.name,
+            target: request.target,
+            notification_types: request.notification_types,
+            enabled: request.enabled.unwrap_or(true),
+            created_at: BsonDateTime::now(),
+            deleted: Some(false),
+        };
+
+        info!("Creating notification channel {} ({})", channel.id, channel.name);
+
+        match self.channels_collection.insert_one(&channel).await {
+            Ok(_) => Ok(channel),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Update a channel by ID with partial updates
+    pub async fn update_channel_by_id(
+        &self,
+        owner_id: i64,
+        id: i64,
+        request: UpdateChannelRequest,
+    ) -> Result<Option<NotificationChannel>, DbError> {
+        let filter = doc! {
+            "_id": id,
+            "ownerId": owner_id,
+            "$or": [
+                { "deleted": { "$ne": true } },
+                { "deleted": { "$exists": false } }
+            ]
+        };
+
+        let mut set_fields = doc! {};
+        if let Some(name) = request.name {
+            set_fields.insert("name", name);
+        }
+        if let Some(target) = request.target {
+            set_fields.insert("target", bson::to_bson(&target)?);
+        }
+        if let Some(notification_types) = request.notification_types {
+            set_fields.insert("notificationTypes", bson::to_bson(&notification_types)?);
+        }
+        if let Some(enabled) = request.enabled {
+            set_fields.insert("enabled", enabled);
+        }
+
+        self.channels_collection
+            .find_one_and_update(filter, doc! { "$set": set_fields })
+            .with_options(Self::return_after())
+            .await
+            .map_err(Into::into)
+    }
+
+    /// Softly delete a channel by setting deleted: true
+    pub async fn soft_delete_channel_by_id(&self, owner_id: i64, id: i64) -> Result<bool, DbError> {
+        let filter = doc! {
+            "_id": id,
+            "ownerId": owner_id,
+            "$or": [
+                { "deleted": { "$ne": true } },
+                { "deleted": { "$exists": false } }
+            ]
+        };
+        let update = doc! { "$set": { "deleted": true, "enabled": false } };
+
+        let result = self.channels_collection.update_one(filter, update).await?;
+        Ok(result.modified_count > 0)
+    }
+
+    /// Queues a message for a channel; returns `None` when a delivery with the same
+    /// dedupe key was already queued for that channel
+    pub async fn enqueue_delivery(
+        &self,
+        channel_id: i64,
+        message: NotificationMessage,
+        dedupe_key: Option<String>,
+    ) -> Result<Option<NotificationDelivery>, DbError> {
+        if let Some(key) = &dedupe_key {
+            let existing = self
+                .deliveries_collection
+                .find_one(doc! { "channelId": channel_id, "dedupeKey": key })
+                .await?;
+            if existing.is_some() {
+                return Ok(None);
+            }
+        }
+
+        let next_id =
+            get_next_id_for_collection(&self.counters_collection, DELIVERIES_COLLECTION_NAME)
+                .await?;
+        let now = BsonDateTime::now();
+        let delivery = NotificationDelivery {
+            id: next_id,
+            channel_id,
+            message,
+            dedupe_key,
+            state: EDeliveryState::Pending,
+            attempts: Vec::new(),
+            next_attempt_at: Some(now),
+            delivered_at: None,
+            created_at: now,
+        };
+
+        match self.deliveries_collection.insert_one(&delivery).await {
+            Ok(_) => Ok(Some(delivery)),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Pending deliveries whose next attempt is due, oldest first
+    pub async fn find_ready_deliveries(
+        &self,
+        now: BsonDateTime,
+        limit: i64,
+    ) -> Result<Vec<NotificationDelivery>, DbError> {
+        let query = doc! {
+            "state": bson::to_bson(&EDeliveryState::Pending)?,
+            "nextAttemptAt": { "$lte": now },
+        };
+
+        self.deliveries_collection
+            .find(query)
+            .sort(doc! { "nextAttemptAt": 1, "_id": 1 })
+            .limit(limit)
+            .await?
+            .try_collect()
+            .await
+            .map_err(Into::into)
+    }
+
+    /// A delivery to one of the owner's channels
+    pub async fn find_delivery_by_id(
+        &self,
+        owner_id: i64,
+        id: i64,
+    ) -> Result<Option<NotificationDelivery>, DbError> {
+        let channel_ids = self.channel_ids(owner_id).await?;
+        self.deliveries_collection
+            .find_one(doc! { "_id": id, "channelId": { "$in": channel_ids } })
+            .await
+            .map_err(Into::into)
+    }
+
+    /// Delivery log of the owner's channels, newest first
+    pub async fn search_deliveries(
+        &self,
+        owner_id: i64,
+        params: DeliverySearchParams,
+    ) -> Result<Vec<NotificationDelivery>, DbError> {
+        let channel_ids: Vec<i64> = self
+            .channel_ids(owner_id)
+            .await?
+            .into_iter()
+            .filter(|id| params.channel_id.is_none_or(|channel_id| channel_id == *id))
+            .collect();
+        let mut query = doc! { "channelId": { "$in": channel_ids } };
+        if let Some(state) = params.state {
+            query.insert("state", bson::to_bson(&state)?);
+        }
+        if let Some(notification_type) = params.notification_type {
+            query.insert("message.notificationType", bson::to_bson(&notification_type)?);
+        }
+
+        self.deliveries_collection
+            .find(query)
+            .sort(doc! { "createdAt": -1, "_id": -1 })
+            .limit(params.limit.unwrap_or(DEFAULT_DELIVERY_LIMIT))
+            .await?
+            .try_collect()
+            .await
+            .map_err(Into::into)
+    }
+
+    /// Appends an attempt to the delivery log and moves the delivery to its next state
+    pub async fn record_attempt(
+        &self,
+        id: i64,
+        attempt: DeliveryAttempt,
+        state: EDeliveryState,
+        next_attempt_at: Option<BsonDateTime>,
+    ) -> Result<Option<NotificationDelivery>, DbError> {
+        let mut set_fields = doc! {
+            "state": bson::to_bson(&state)?,
+            "nextAttemptAt": next_attempt_at,
+        };
+        if state == EDeliveryState::Delivered {
+            set_fields.insert("deliveredAt", attempt.attempted_at);
+        }
+        let update = doc! {
+            "$set": set_fields,
+            "$push": { "attempts": bson::to_bson(&attempt)? },
+        };
+
+        self.deliveries_collection
+            .find_one_and_update(doc! { "_id": id }, update)
+            .with_options(Self::return_after())
+            .await
+            .map_err(Into::into)
+    }
+
+    /// Puts a dead-lettered or pending delivery to one of the owner's channels back in the
+    /// queue for an immediate attempt
+    pub async fn requeue_delivery(
+        &self,
+        owner_id: i64,
+        id: i64,
+    ) -> Result<Option<NotificationDelivery>, DbError> {
+        let filter = doc! {
+            "_id": id,
+            "channelId": { "$in": self.channel_ids(owner_id).await? },
+            "state": { "$ne": bson::to_bson(&EDeliveryState::Delivered)? },
+        };
+        let update = doc! { "$set": {
+            "state": bson::to_bson(&EDeliveryState::Pending)?,
+            "nextAttemptAt": BsonDateTime::now(),
+        } };
+
+        self.deliveries_collection
+            .find_one_and_update(filter, update)
+            .with_options(Self::return_after())
+            .await
+            .map_err(Into::into)
+    }
+}
@@ -0,0 +1,305 @@
+use crate::models::{AppDatabase, DbCollection, DbError};
+use crate::repository::auto_increment::{get_next_id_for_collection, AUTO_INCREMENT_COLLECTION_NAME};
+use bson::{DateTime as BsonDateTime, Document};
+use futures_util::TryStreamExt;
+use mindvault_shared::dtos::reminder_dtos::ReminderSearchParams;
+use mindvault_shared::models::reminders_model::{EReminderState, EReminderTrigger, Reminder};
+use mongodb::bson::doc;
+use mongodb::options::{FindOneAndUpdateOptions, ReturnDocument};
+use mongodb::{bson, Collection};
+use tracing::info;
+
+pub struct ReminderRepository {
+    collection: Collection<Reminder>,
+    counters_collection: DbCollection<Document>,
+}
+
+const COLLECTION_NAME: &str = "reminders";
+
+/// Fields of a reminder resolved by the caller before it is stored
+pub struct NewReminder {
+    pub title: String,
+    pub note: Option<String>,
+    pub task_id: Option<i64>,
+    pub trigger: EReminderTrigger,
+    pub time_zone: String,
+    pub fire_at: Option<BsonDateTime>,
+}
+
+/// Partial update of a reminder; a new trigger also clears any snooze
+#[derive(Default)]
+pub struct ReminderChanges {
+    pub title: Option<String>,
+    pub note: Option<String>,
+    pub trigger: Option<EReminderTrigger>,
+    pub time_zone: Option<String>,
+    pub fire_at: Option<Option<BsonDateTime>>,
+}
+
+impl ReminderRepository {
+    pub fn new(app_database: AppDatabase) -> Self {
+        let collection = app_database.collection::<Reminder>(COLLECTION_NAME);
+        let counters_collection =
+            app_database.collection::<Document>(AUTO_INCREMENT_COLLECTION_NAME);
+        Self {
+            collection,
+            counters_collection,
+        }
+    }
+
+    async fn find_one_and_update(
+        &self,
+        filter: Document,
+        update: Document,
+    ) -> Result<Option<Reminder>, DbError> {
+        let options = FindOneAndUpdateOptions::builder()
+            .return_document(ReturnDocument::After)
+            .build();
+
+        self.collection
+            .find_one_and_update(filter, update)
+            .with_options(options)
+            .await
+            .map_err(Into::into)
+    }
+
+    pub async fn find_by_id(&self, owner_id: i64, id: i64) -> Result<Option<Reminder>, DbError> {
+        let query = doc! {
+            "_id": id,
+            "ownerId": owner_id,
+            "$or": [
+                { "deleted": { "$ne": true } },
+                { "deleted": { "$exists": false } }
+            ]
+        };
+        self.collection.find_one(query).await.map_err(Into::into)
+    }
+
+    pub async fn create_reminder(
+        &self,
+        owner_id: i64,
+        new_reminder: NewReminder,
+    ) -> Result<Reminder, DbError> {
+        let next_id = get_next_id_for_collection(&self.counters_collection, COLLECTION_NAME).await?;
+
+        let reminder = Reminder {
+            id: next_id,
+            owner_id,
+            title: new_reminder.title,
+            note: new_reminder.note,
+            task_id: new_reminder.task_id,
+            trigger: new_reminder.trigger,
+            time_zone: new_reminder.time_zone,
+            fire_at: new_reminder.fire_at,
+            snoozed_until: None,
+            state: EReminderState::Pending,
+            fired_at: None,
+            dismissed_at: None,
+            created_at: BsonDateTime::now(),
+            deleted: Some(false),
+        };
+
+        info!("{:?}", reminder);
+
+        match self.collection.insert_one(&reminder).await {
+            Ok(_) => Ok(reminder),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn search_reminders(
+        &self,
+        owner_id: i64,
+        params: ReminderSearchParams,
+    ) -> Result<Vec<Reminder>, DbError> {
+        let mut conditions = vec![doc! { "ownerId": owner_id }, doc! {
+            "$or": [
+                { "deleted": { "$ne": true } },
+                { "deleted": { "$exists": false } }
+            ]
+        }];
+
+        if let Some(state) = params.state {
+            conditions.push(doc! { "state": bson::to_bson(&state)? });
+        }
+        if let Some(task_id) = params.task_id {
+            conditions.push(doc! { "taskId": task_id });
+        }
+
+        self.collection
+            .find(doc! { "$and": conditions })
+            .sort(doc! { "fireAt": 1, "_id": 1 })
+            .await?
+            .try_collect()
+            .await
+            .map_err(Into::into)
+    }
+
+    /// Pending reminders whose effective fire time is at or before `until`, of one owner or,
+    /// for the scheduler, of everyone
+    pub async fn find_due(
+        &self,
+        owner_id: Option<i64>,
+        until: BsonDateTime,
+    ) -> Result<Vec<Reminder>, DbError> {
+        let mut query = doc! {
+            "state": bson::to_bson(&EReminderState::Pending)?,
+            "fireAt": { "$lte": until },
+            "$or": [
+                { "deleted": { "$ne": true } },
+                { "deleted": { "$exists": false } }
+            ]
+        };
+        if let Some(owner_id) = owner_id {
+            query.insert("ownerId", owner_id);
+        }
+
+        self.collection
+            .find(query)
+            .sort(doc! { "fireAt": 1, "_id": 1 })
+            .await?
+            .try_collect()
+            .await
+            .map_err(Into::into)
+    }
+
+    /// Pending, un-snoozed reminders that follow the due date of a task
+    pub async fn find_pending_task_relative(&self) -> Result<Vec<Reminder>, DbError> {
+        let query = doc! {
+            "state": bson::to_bson(&EReminderState::Pending)?,
+            "trigger.kind": "BeforeDueDate",
+            "snoozedUntil": null,
+            "$or": [
+                { "deleted": { "$ne": true } },
+                { "deleted": { "$exists": false } }
+            ]
+        };
+
+        self.collection
+            .find(query)
+            .await?
+            .try_collect()
+            .await
+            .map_err(Into::into)
+    }
+
+    pub async fn set_fire_at(&self, id: i64, fire_at: Option<BsonDateTime>) -> Result<(), DbError> {
+        self.collection
+            .update_one(doc! { "_id": id }, doc! { "$set": { "fireAt": fire_at } })
+            .await?;
+        Ok(())
+    }
+
+    /// Update a reminder by ID with partial updates
+    pub async fn update_reminder_by_id(
+        &self,
+        owner_id: i64,
+        id: i64,
+        changes: ReminderChanges,
+    ) -> Result<Option<Reminder>, DbError> {
+        let filter = doc! {
+            "_id": id,
+            "ownerId": owner_id,
+            "$or": [
+                { "deleted": { "$ne": true } },
+                { "deleted": { "$exists": false } }
+            ]
+        };
+
+        let mut set_fields = doc! {};
+        if let Some(title) = changes.title {
+            set_fields.insert("title", title);
+        }
+        if let Some(note) = changes.note {
+            set_fields.insert("note", note);
+        }
+        if let Some(time_zone) = changes.time_zone {
+            set_fields.insert("timeZone", time_zone);
+        }
+        if let Some(trigger) = changes.trigger {
+            set_fields.insert("trigger", bson::to_bson(&trigger)?);
+            set_fields.insert("snoozedUntil", bson::Bson::Null);
+        }
+        if let Some(fire_at) = changes.fire_at {
+            set_fields.insert("fireAt", fire_at);
+        }
+
+        self.find_one_and_update(filter, doc! { "$set": set_fields })
+            .await
+    }
+
+    /// Marks a pending reminder as fired
+    pub async fn mark_fired(&self, id: i64) -> Result<Option<Reminder>, DbError> {
+        let filter = doc! {
+            "_id": id,
+            "state": bson::to_bson(&EReminderState::Pending)?,
+            "$or": [
+                { "deleted": { "$ne": true } },
+                { "deleted": { "$exists": false } }
+            ]
+        };
+        let update = doc! { "$set": {
+            "state": bson::to_bson(&EReminderState::Fired)?,
+            "firedAt": BsonDateTime::now(),
+        } };
+        self.find_one_and_update(filter, update).await
+    }
+
+    /// Dismisses a pending or fired reminder
+    pub async fn dismiss(&self, owner_id: i64, id: i64) -> Result<Option<Reminder>, DbError> {
+        let filter = doc! {
+            "_id": id,
+            "ownerId": owner_id,
+            "state": { "$ne": bson::to_bson(&EReminderState::Dismissed)? },
+            "$or": [
+                { "deleted": { "$ne": true } },
+                { "deleted": { "$exists": false } }
+            ]
+        };
+        let update = doc! { "$set": {
+            "state": bson::to_bson(&EReminderState::Dismissed)?,
+            "dismissedAt": BsonDateTime::now(),
+        } };
+        self.find_one_and_update(filter, update).await
+    }
+
+    /// Pushes a pending or fired reminder back to pending until `until`
+    pub async fn snooze(
+        &self,
+        owner_id: i64,
+        id: i64,
+        until: BsonDateTime,
+    ) -> Result<Option<Reminder>, DbError> {
+        let filter = doc! {
+            "_id": id,
+            "ownerId": owner_id,
+            "state": { "$ne": bson::to_bson(&EReminderState::Dismissed)? },
+            "$or": [
+                { "deleted": { "$ne": true } },
+                { "deleted": { "$exists": false } }
+            ]
+        };
+        let update = doc! { "$set": {
+            "state": bson::to_bson(&EReminderState::Pending)?,
+            "snoozedUntil": until,
+            "fireAt": until,
+        } };
+        self.find_one_and_update(filter, update).await
+    }
+
+    /// Softly delete a reminder by setting deleted: true
+    pub async fn soft_delete_by_id(&self, owner_id: i64, id: i64) -> Result<bool, DbError> {
+        let filter = doc! {
+            "_id": id,
+            "ownerId": owner_id,
+            "$or": [
+                { "deleted": { "$ne": true } },
+                { "deleted": { "$exists": false } }
+            ]
+        };
+        let update = doc! { "$set": { "deleted": true } };
+
+        let result = self.collection.update_one(filter, update).await?;
+        Ok(result.modified_count > 0)
+    }
+}
@@ -0,0 +1,139 @@
+use crate::models::{AppDatabase, DbCollection, DbError};
+use crate::repository::auto_increment::{get_next_id_for_collection, AUTO_INCREMENT_COLLECTION_NAME};
+use bson::{DateTime as BsonDateTime, Document};
+use futures_util::TryStreamExt;
+use mindvault_shared::dtos::reportee_dtos::{CreateReporteeRequest, UpdateReporteeRequest};
+use mindvault_shared::models::reportees_model::Reportee;
+use mongodb::bson::doc;
+use mongodb::Collection;
+use tracing::info;
+
+pub struct ReporteeRepository {
+    collection: Collection<Reportee>,
+    counters_collection: DbCollection<Document>,
+}
+
+const COLLECTION_NAME: &str = "reportees";
+
+impl ReporteeRepository {
+    pub fn new(app_database: AppDatabase) -> Self {
+        let collection = app_database.collection::<Reportee>(COLLECTION_NAME);
+        let counters_collection =
+            app_database.collection::<Document>(AUTO_INCREMENT_COLLECTION_NAME);
+        Self {
+            collection,
+            counters_collection,
+        }
+    }
+
+    pub async fn find_all(&self, owner_id: i64) -> Result<Vec<Reportee>, DbError> {
+        let query = doc! {
+            "ownerId": owner_id,
+            "$or": [
+                { "deleted": { "$ne": true } },
+                { "deleted": { "$exists": false } }
+            ]
+        };
+
+        self.collection
+            .find(query)
+            .sort(doc! { "name": 1 })
+            .await?
+            .try_collect()
+            .await
+            .map_err(Into::into)
+    }
+
+    pub async fn find_by_id(&self, owner_id: i64, id: i64) -> Result<Option<Reportee>, DbError> {
+        let query = doc! {
+            "_id": id,
+            "ownerId": owner_id,
+            "$or": [
+                { "deleted": { "$ne": true } },
+                { "deleted": { "$exists": false } }
+            ]
+        };
+        self.collection.find_one(query).await.map_err(Into::into)
+    }
+
+    pub async fn create_reportee(
+        &self,
+        owner_id: i64,
+        new_reportee: CreateReporteeRequest,
+    ) -> Result<Reportee, DbError> {
+        let next_id = get_next_id_for_collection(&self.counters_collection, COLLECTION_NAME).await?;
+
+        let reportee = Reportee {
+            id: next_id,
+            owner_id,
+            name: new_reportee.name,
+            email: new_reportee.email,
+            designation: new_reportee.designation,
+            created_at: BsonDateTime::now(),
+            deleted: Some(false),
+        };
+
+        info!("{:?}", reportee);
+
+        match self.collection.insert_one(&reportee).await {
+            Ok(_) => Ok(reportee),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Update a reportee by ID with partial updates
+    pub async fn update_reportee_by_id(
+        &self,
+        owner_id: i64,
+        id: i64,
+        update_request: UpdateReporteeRequest,
+    ) -> Result<Option<Reportee>, DbError> {
+        let filter = doc! {
+            "_id": id,
+            "ownerId": owner_id,
+            "$or": [
+                { "deleted": { "$ne": true } },
+                { "deleted": { "$exists": false } }
+            ]
+        };
+
+        let mut set_fields = doc! {};
+        if let Some(name) = update_request.name {
+            set_fields.insert("name", name);
+        }
+        if let Some(email) = update_request.email {
+            set_fields.insert("email", email);
+        }
+        if let Some(designation) = update_request.designation {
+            set_fields.insert("designation", designation);
+        }
+
+        let update = doc! { "$set": set_fields };
+
+        let options = mongodb::options::FindOneAndUpdateOptions::builder()
+            .return_document(mongodb::options::ReturnDocument::After)
+            .build();
+
+        self.collection
+            .find_one_and_update(filter, update)
+            .with_options(options)
+            .await
+            .map_err(Into::into)
+    }
+
+    /// Softly delete a reportee by setting deleted: true
+    pub async fn soft_delete_by_id(&self, owner_id: i64, id: i64) -> Result<bool, DbError> {
+        let filter = doc! {
+            "_id": id,
+            "ownerId": owner_id,
+            "$or": [
+                { "deleted": { "$ne": true } },
+                { "deleted": { "$exists": false } }
+            ]
+        };
+        let update = doc! { "$set": { "deleted": true } };
+
+        let result = self.collection.update_one(filter, update).await?;
+        Ok(result.modified_count > 0)
+    }
+}
@@ -0,0 +1,229 @@
+use crate::models::{AppDatabase, DbCollection, DbError};
+use crate::repository::auto_increment::{get_next_id_for_collection, AUTO_INCREMENT_COLLECTION_NAME};
+use crate::repository::date_utils::naive_date_to_bson;
+use bson::{DateTime as BsonDateTime, Document};
+use futures_util::TryStreamExt;
+use mindvault_shared::dtos::review_dtos::{
+    CreateReviewRequest, ReviewSearchParams, UpdateReviewRequest,
+};
+use mindvault_shared::models::review_templates_model::ReviewTemplateRef;
+use mindvault_shared::models::reviews_model::{EReviewState, Review};
+use mongodb::bson::doc;
+use mongodb::{bson, Collection};
+use tracing::info;
+
+pub struct ReviewRepository {
+    collection: Collection<Review>,
+    counters_collection: DbCollection<Document>,
+}
+
+const COLLECTION_NAME: &str = "reviews";
+
+impl ReviewRepository {
+    pub fn new(app_database: AppDatabase) -> Self {
+        let collection = app_database.collection::<Review>(COLLECTION_NAME);
+        let counters_collection =
+            app_database.collection::<Document>(AUTO_INCREMENT_COLLECTION_NAME);
+        Self {
+            collection,
+            counters_collection,
+        }
+    }
+
+    pub async fn find_by_id(&self, owner_id: i64, id: i64) -> Result<Option<Review>, DbError> {
+        let query = doc! {
+            "_id": id,
+            "ownerId": owner_id,
+            "$or": [
+                { "deleted": { "$ne": true } },
+                { "deleted": { "$exists": false } }
+            ]
+        };
+        self.collection.find_one(query).await.map_err(Into::into)
+    }
+
+    pub async fn create_review(
+        &self,
+        owner_id: i64,
+        new_review: CreateReviewRequest,
+        template: Option<ReviewTemplateRef>,
+    ) -> Result<Review, DbError> {
+        let next_review_id =
+            get_next_id_for_collection(&self.counters_collection, COLLECTION_NAME).await?;
+
+        let created_at = BsonDateTime::now();
+        let review_date = new_review
+            .review_date
+            .and_then(|date| date.as_day())
+            .map(naive_date_to_bson)
+            .unwrap_or(created_at);
+
+        let review = Review {
+            id: next_review_id,
+            owner_id,
+            cycle: new_review.cycle,
+            reviewer: new_review.reviewer,
+            reportee_id: new_review.reportee_id,
+            ratings: new_review.ratings,
+            feedback: new_review.feedback,
+            template,
+            answers: new_review.answers,
+            state: EReviewState::Draft,
+            review_date,
+            submitted_at: None,
+            acknowledged_at: None,
+            created_at,
+            deleted: Some(false),
+        };
+
+        info!("Creating review {} for reportee {}", review.id, review.reportee_id);
+
+        match self.collection.insert_one(&review).await {
+            Ok(_) => Ok(review),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Search reviews by reportee, cycle and state, ordered by review date
+    pub async fn search_reviews(
+        &self,
+        owner_id: i64,
+        params: ReviewSearchParams,
+    ) -> Result<Vec<Review>, DbError> {
+        let mut conditions = vec![doc! {
+            "ownerId": owner_id,
+            "$or": [
+                { "deleted": { "$ne": true } },
+                { "deleted": { "$exists": false } }
+            ]
+        }];
+
+        if let Some(reportee_id) = params.reportee_id {
+            conditions.push(doc! { "reporteeId": reportee_id });
+        }
+        if let Some(cycle) = params.cycle {
+            conditions.push(doc! { "cycle": cycle });
+        }
+        if let Some(state) = params.state {
+            conditions.push(doc! { "state": bson::to_bson(&state)? });
+        }
+
+        self.collection
+            .find(doc! { "$and": conditions })
+            .sort(doc! { "reviewDate": 1, "_id": 1 })
+            .await?
+            .try_collect()
+            .await
+            .map_err(Into::into)
+    }
+
+    /// Update a draft review by ID with partial updates.
+    /// Returns `None` when the review does not exist or is no longer a draft.
+    pub async fn update_draft_by_id(
+        &self,
+        owner_id: i64,
+        id: i64,
+        update_request: UpdateReviewRequest,
+    ) -> Result<Option<Review>, DbError> {
+        let filter = doc! {
+            "_id": id,
+            "ownerId": owner_id,
+            "state": bson::to_bson(&EReviewState::Draft)?,
+            "$or": [
+                { "deleted": { "$ne": true } },
+                { "deleted": { "$exists": false } }
+            ]
+        };
+
+        let mut set_fields = doc! {};
+        if let Some(cycle) = update_request.cycle {
+            set_fields.insert("cycle", cycle);
+        }
+        if let Some(reviewer) = update_request.reviewer {
+            set_fields.insert("reviewer", reviewer);
+        }
+        if let Some(ratings) = update_request.ratings {
+            set_fields.insert("ratings", bson::to_bson(&ratings)?);
+        }
+        if let Some(feedback) = update_request.feedback {
+            set_fields.insert("feedback", bson::to_bson(&feedback.sealed()?)?);
+        }
+        if let Some(answers) = update_request.answers {
+            set_fields.insert("answers", bson::to_bson(&answers)?);
+        }
+        if let Some(review_date) = update_request.review_date.and_then(|date| date.as_day()) {
+            set_fields.insert("reviewDate", naive_date_to_bson(review_date));
+        }
+
+        let update = doc! { "$set": set_fields };
+
+        let options = mongodb::options::FindOneAndUpdateOptions::builder()
+            .return_document(mongodb::options::ReturnDocument::After)
+            .build();
+
+        self.collection
+            .find_one_and_update(filter, update)
+            .with_options(options)
+            .await
+            .map_err(Into::into)
+    }
+
+    /// Move a review from one state to the next, stamping the matching timestamp.
+    /// Returns `None` when the review does not exist or is not in the `from` state.
+    pub async fn transition_state(
+        &self,
+        owner_id: i64,
+        id: i64,
+        from: EReviewState,
+        to: EReviewState,
+    ) -> Result<Option<Review>, DbError> {
+        let filter = doc! {
+            "_id": id,
+            "ownerId": owner_id,
+            "state": bson::to_bson(&from)?,
+            "$or": [
+                { "deleted": { "$ne": true } },
+                { "deleted": { "$exists": false } }
+            ]
+        };
+
+        let mut set_fields = doc! { "state": bson::to_bson(&to)? };
+        match to {
+            EReviewState::Submitted => {
+                set_fields.insert("submittedAt", BsonDateTime::now());
+            }
+            EReviewState::Acknowledged => {
+                set_fields.insert("acknowledgedAt", BsonDateTime::now());
+            }
+            EReviewState::Draft => {}
+        }
+
+        let update = doc! { "$set": set_fields };
+
+        let options = mongodb::options::FindOneAndUpdateOptions::builder()
+            .return_document(mongodb::options::ReturnDocument::After)
+            .build();
+
+        self.collection
+            .find_one_and_update(filter, update)
+            .with_options(options)
+            .await
+            .map_err(Into::into)
+    }
+
+    /// Softly delete a review by setting deleted: true
+    pub async fn soft_delete_by_id(&self, owner_id: i64, id: i64) -> Result<bool, DbError> {
+        let filter = doc! {
+            "_id": id,
+            "ownerId": owner_id,
+            "$or": [
+                { "deleted": { "$ne": true } },
+                { "deleted": { "$exists": false } }
+            ]
+        };
+        let update = doc! { "$set": { "deleted": true } };
+
+        let result = self.collection.update_one(filter, update).await?;
+        Ok(result.modified_count > 0)
+    }
+}
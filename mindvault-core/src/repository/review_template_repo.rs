@@ -0,0 +1,183 @@
+use crate::models::{AppDatabase, DbCollection, DbError};
+use crate::repository::auto_increment::{get_next_id_for_collection, AUTO_INCREMENT_COLLECTION_NAME};
+use bson::{DateTime as BsonDateTime, Document};
+use futures_util::TryStreamExt;
+use mindvault_shared::dtos::review_template_dtos::SaveReviewTemplateRequest;
+use mindvault_shared::models::review_templates_model::ReviewTemplate;
+use mongodb::bson::doc;
+use mongodb::Collection;
+use tracing::info;
+
+pub struct ReviewTemplateRepository {
+    collection: Collection<ReviewTemplate>,
+    counters_collection: DbCollection<Document>,
+}
+
+const COLLECTION_NAME: &str = "review_templates";
+
+impl ReviewTemplateRepository {
+    pub fn new(app_database: AppDatabase) -> Self {
+        let collection = app_database.collection::<ReviewTemplate>(COLLECTION_NAME);
+        let counters_collection =
+            app_database.collection::<Document>(AUTO_INCREMENT_COLLECTION_NAME);
+        Self {
+            collection,
+            counters_collection,
+        }
+    }
+
+    /// Latest version of every non-deleted template
+    pub async fn find_all_latest(&self, owner_id: i64) -> Result<Vec<ReviewTemplate>, DbError> {
+        let query = doc! {
+            "ownerId": owner_id,
+            "latest": true,
+            "$or": [
+                { "deleted": { "$ne": true } },
+                { "deleted": { "$exists": false } }
+            ]
+        };
+
+        self.collection
+            .find(query)
+            .sort(doc! { "templateId": 1 })
+            .await?
+            .try_collect()
+            .await
+            .map_err(Into::into)
+    }
+
+    pub async fn find_latest(
+        &self,
+        owner_id: i64,
+        template_id: i64,
+    ) -> Result<Option<ReviewTemplate>, DbError> {
+        let query = doc! {
+            "templateId": template_id,
+            "ownerId": owner_id,
+            "latest": true,
+            "$or": [
+                { "deleted": { "$ne": true } },
+                { "deleted": { "$exists": false } }
+            ]
+        };
+        self.collection.find_one(query).await.map_err(Into::into)
+    }
+
+    /// Looks up an exact version, including versions of deleted templates,
+    /// so existing reviews can still be validated against what they were created from
+    pub async fn find_version(
+        &self,
+        owner_id: i64,
+        template_id: i64,
+        version: i32,
+    ) -> Result<Option<ReviewTemplate>, DbError> {
+        let query = doc! { "templateId": template_id, "ownerId": owner_id, "version": version };
+        self.collection.find_one(query).await.map_err(Into::into)
+    }
+
+    pub async fn find_versions(
+        &self,
+        owner_id: i64,
+        template_id: i64,
+    ) -> Result<Vec<ReviewTemplate>, DbError> {
+        self.collection
+            .find(doc! { "templateId": template_id, "ownerId": owner_id })
+            .sort(doc! { "version": 1 })
+            .await?
+            .try_collect()
+            .await
+            .map_err(Into::into)
+    }
+
+    pub async fn create_template(
+        &self,
+        owner_id: i64,
+        request: SaveReviewTemplateRequest,
+    ) -> Result<ReviewTemplate, DbError> {
+        let next_id = get_next_id_for_collection(&self.counters_collection, COLLECTION_NAME).await?;
+
+        // The first version's id doubles as the stable template id
+        let template = ReviewTemplate {
+            id: next_id,
+            owner_id,
+            template_id: next_id,
+            version: 1,
+            latest: true,
+            name: request.name,
+            description: request.description,
+            sections: request.sections,
+            created_at: BsonDateTime::now(),
+            deleted: Some(false),
+        };
+
+        info!("{:?}", template);
+
+        match self.collection.insert_one(&template).await {
+            Ok(_) => Ok(template),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Stores the request as a new version of the template, leaving older versions untouched.
+    /// Returns `None` when the template does not exist.
+    pub async fn create_version(
+        &self,
+        owner_id: i64,
+        template_id: i64,
+        request: SaveReviewTemplateRequest,
+    ) -> Result<Option<ReviewTemplate>, DbError> {
+        let current = match self.find_latest(owner_id, template_id).await? {
+            Some(current) => current,
+            None => return Ok(None),
+        };
+
+        let next_id = get_next_id_for_collection(&self.counters_collection, COLLECTION_NAME).await?;
+
+        let template = ReviewTemplate {
+            id: next_id,
+            owner_id,
+            template_id,
+            version: current.version + 1,
+            latest: true,
+            name: request.name,
+            description: request.description,
+            sections: request.sections,
+            created_at: BsonDateTime::now(),
+            deleted: Some(false),
+        };
+
+        self.collection.insert_one(&template).await?;
+        self.collection
+            .update_one(
+                doc! { "_id": current.id },
+                doc! { "$set": { "latest": false } },
+            )
+            .await?;
+
+        info!(
+            "Published version {} of review template {}",
+            template.version, template_id
+        );
+        Ok(Some(template))
+    }
+
+    /// Softly delete every version of a template
+    pub async fn soft_delete_by_template_id(
+        &self,
+        owner_id: i64,
+        template_id: i64,
+    ) -> Result<bool, DbError> {
+        let filter = doc! {
+            "templateId": template_id,
+            "ownerId": owner_id,
+            "$or": [
+                { "deleted": { "$ne": true } },
+                { "deleted": { "$exists": false } }
+            ]
+        };
+        let update = doc! { "$set": { "deleted": true } };
+
+        let result = self.collection.update_many(filter, update).await?;
+        Ok(result.modified_count > 0)
+    }
+}
@@ -0,0 +1,138 @@
+use crate::models::{AppDatabase, DbCollection, DbError};
+use crate::repository::auto_increment::{get_next_id_for_collection, AUTO_INCREMENT_COLLECTION_NAME};
+use bson::{DateTime as BsonDateTime, Document};
+use futures_util::TryStreamExt;
+use mindvault_shared::dtos::saved_filter_dtos::{CreateSavedFilterRequest, UpdateSavedFilterRequest};
+use mindvault_shared::models::saved_filters_model::SavedFilter;
+use mongodb::bson::doc;
+use mongodb::options::{FindOneAndUpdateOptions, ReturnDocument};
+use mongodb::{bson, Collection};
+use tracing::info;
+
+pub struct SavedFilterRepository {
+    collection: Collection<SavedFilter>,
+    counters_collection: DbCollection<Document>,
+}
+
+const COLLECTION_NAME: &str = "saved_filters";
+
+impl SavedFilterRepository {
+    pub fn new(app_database: AppDatabase) -> Self {
+        let collection = app_database.collection::<SavedFilter>(COLLECTION_NAME);
+        let counters_collection =
+            app_database.collection::<Document>(AUTO_INCREMENT_COLLECTION_NAME);
+        Self {
+            collection,
+            counters_collection,
+        }
+    }
+
+    pub async fn find_all(&self, owner_id: i64) -> Result<Vec<SavedFilter>, DbError> {
+        let query = doc! {
+            "ownerId": owner_id,
+            "$or": [
+                { "deleted": { "$ne": true } },
+                { "deleted": { "$exists": false } }
+            ]
+        };
+
+        self.collection
+            .find(query)
+            .sort(doc! { "name": 1 })
+            .await?
+            .try_collect()
+            .await
+            .map_err(Into::into)
+    }
+
+    pub async fn find_by_id(
+        &self,
+        owner_id: i64,
+        id: i64,
+    ) -> Result<Option<SavedFilter>, DbError> {
+        let query = doc! {
+            "_id": id,
+            "ownerId": owner_id,
+            "$or": [
+                { "deleted": { "$ne": true } },
+                { "deleted": { "$exists": false } }
+            ]
+        };
+        self.collection.find_one(query).await.map_err(Into::into)
+    }
+
+    pub async fn create_filter(
+        &self,
+        owner_id: i64,
+        request: CreateSavedFilterRequest,
+    ) -> Result<SavedFilter, DbError> {
+        let next_id = get_next_id_for_collection(&self.counters_collection, COLLECTION_NAME).await?;
+
+        let filter = SavedFilter {
+            id: next_id,
+            owner_id,
+            name: request.name,
+            criteria: request.criteria,
+            created_at: BsonDateTime::now(),
+            deleted: Some(false),
+        };
+
+        info!("{:?}", filter);
+
+        match self.collection.insert_one(&filter).await {
+            Ok(_) => Ok(filter),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Update a saved filter by ID with partial updates
+    pub async fn update_filter_by_id(
+        &self,
+        owner_id: i64,
+        id: i64,
+        request: UpdateSavedFilterRequest,
+    ) -> Result<Option<SavedFilter>, DbError> {
+        let filter = doc! {
+            "_id": id,
+            "ownerId": owner_id,
+            "$or": [
+                { "deleted": { "$ne": true } },
+                { "deleted": { "$exists": false } }
+            ]
+        };
+
+        let mut set_fields = doc! {};
+        if let Some(name) = request.name {
+            set_fields.insert("name", name);
+        }
+        if let Some(criteria) = request.criteria {
+            set_fields.insert("criteria", bson::to_bson(&criteria)?);
+        }
+
+        let options = FindOneAndUpdateOptions::builder()
+            .return_document(ReturnDocument::After)
+            .build();
+
+        self.collection
+            .find_one_and_update(filter, doc! { "$set": set_fields })
+            .with_options(options)
+            .await
+            .map_err(Into::into)
+    }
+
+    /// Softly delete a saved filter by setting deleted: true
+    pub async fn soft_delete_by_id(&self, owner_id: i64, id: i64) -> Result<bool, DbError> {
+        let filter = doc! {
+            "_id": id,
+            "ownerId": owner_id,
+            "$or": [
+                { "deleted": { "$ne": true } },
+                { "deleted": { "$exists": false } }
+            ]
+        };
+        let update = doc! { "$set": { "deleted": true } };
+
+        let result = self.collection.update_one(filter, update).await?;
+        Ok(result.modified_count > 0)
+    }
+}
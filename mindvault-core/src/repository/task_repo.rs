@@ -1,25 +1,80 @@
 use crate::models::{AppDatabase, DbCollection, DbError};
 use crate::repository::auto_increment::{
-    get_next_id_for_collection, get_next_id_range_for_collection, AUTO_INCREMENT_COLLECTION_NAME,
+    get_next_id_for_collection, get_next_id_range_for_collection, owner_sequence,
+    AUTO_INCREMENT_COLLECTION_NAME,
 };
-use bson::{DateTime as BsonDateTime, Document};
-use chrono::{Duration, NaiveDate, TimeZone, Utc};
+use crate::repository::date_utils::{due_between_condition, overdue_condition};
+use crate::repository::tag_utils::normalize_tags;
+use bson::{Bson, DateTime as BsonDateTime, Document};
+use chrono::{DateTime, Duration, Utc};
+use chrono_tz::Tz;
 use futures_util::TryStreamExt;
 use mindvault_shared::dtos::task_dtos::{
-    BulkCreateTaskRequest, CreateTaskRequest, SearchAndUpdateRequest, TaskSearchParams,
-    UpdateTaskRequest,
+    BulkCreateTaskRequest, CreateTaskRequest, EDueWindow, ETaskSort, SearchAndUpdateRequest,
+    TaskSearchParams, UpdateTaskRequest,
 };
 use mindvault_shared::models::tasks_model::{ETaskStatus, Task};
+use mindvault_shared::utils::date_input::{DateInput, DatePreferences};
+use mindvault_shared::utils::field_encryption::{
+    blind_index, blind_index_query, seal, EncryptedField,
+};
 use mongodb::bson::doc;
-use mongodb::{bson, Collection};
+use mongodb::{bson, Collection, Cursor};
+use std::cmp::Ordering;
+use std::collections::HashMap;
 use tracing::info;
 
+/// Where a task lives: a user's private vault or a shared workspace
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TaskVault {
+    User(i64),
+    Workspace(i64),
+}
+
+impl TaskVault {
+    /// Adds the conditions that keep `query` inside the vault. Private vaults only hold tasks
+    /// without a workspace, even though workspace tasks record their creator as `ownerId`.
+    pub(crate) fn scope(&self, mut query: Document) -> Document {
+        match self {
+            TaskVault::User(owner_id) => {
+                query.insert("ownerId", *owner_id);
+                query.insert("workspaceId", Bson::Null);
+            }
+            TaskVault::Workspace(workspace_id) => {
+                query.insert("workspaceId", *workspace_id);
+            }
+        }
+        query
+    }
+
+    /// Counter that numbers the vault's tasks
+    pub(crate) fn sequence(&self) -> String {
+        match self {
+            TaskVault::User(owner_id) => owner_sequence(COLLECTION_NAME, *owner_id),
+            TaskVault::Workspace(workspace_id) => {
+                owner_sequence(WORKSPACE_TASKS_SEQUENCE, *workspace_id)
+            }
+        }
+    }
+
+    pub(crate) fn workspace_id(&self) -> Option<i64> {
+        match self {
+            TaskVault::User(_) => None,
+            TaskVault::Workspace(workspace_id) => Some(*workspace_id),
+        }
+    }
+}
+
+/// Every task belongs to exactly one vault. Task ids are numbered per vault, so each method
+/// takes the vault and matches on it together with `taskId`; ids from one vault never reach
+/// another vault's tasks.
 pub struct TaskRepository {
     collection: Collection<Task>,
     counters_collection: DbCollection<Document>,
 }
 
 const COLLECTION_NAME: &str = "tasks";
+const WORKSPACE_TASKS_SEQUENCE: &str = "workspace_tasks";
 
 impl TaskRepository {
     pub fn new(app_database: AppDatabase) -> Self {
@@ -32,22 +87,77 @@ impl TaskRepository {
         }
     }
 
-    /// Converts an optional NaiveDate to an optional BsonDateTime at midnight UTC
-    fn convert_due_date(due_date: Option<NaiveDate>) -> Option<BsonDateTime> {
-        due_date.map(|date: NaiveDate| {
-            let naive_dt = date.and_hms_opt(0, 0, 0).unwrap();
-            let utc_dt = Utc.from_utc_datetime(&naive_dt);
-            BsonDateTime::from(utc_dt)
+    /// Error for a due date that was not resolved with the user's preferences first
+    fn unresolved_due_date(due_date: &DateInput) -> DbError {
+        DbError::InvalidInput(format!("Unresolved due date '{}'", due_date))
+    }
+
+    /// The stored due date and whether it carries a time. Inputs must be resolved with the
+    /// user's preferences first.
+    fn convert_due_date(
+        due_date: Option<&DateInput>,
+    ) -> Result<(Option<BsonDateTime>, bool), DbError> {
+        match due_date {
+            None => Ok((None, false)),
+            Some(due_date) => match due_date.stored() {
+                Some((at, has_time)) => Ok((Some(at), has_time)),
+                None => Err(Self::unresolved_due_date(due_date)),
+            },
+        }
+    }
+
+    /// Statuses of tasks that are done with; open tasks have any other
+    fn closed_statuses() -> Result<Bson, DbError> {
+        Ok(bson::to_bson(&ETaskStatus::closed())?)
+    }
+
+    /// Matches tasks due on the day `due_date` falls on in `tz`
+    fn due_day_condition(due_date: &DateInput, tz: Tz) -> Result<Document, DbError> {
+        let day = due_date.day(tz).ok_or_else(|| Self::unresolved_due_date(due_date))?;
+        Ok(due_between_condition(day, day + Duration::days(1), tz))
+    }
+
+    pub async fn find_all(&self, vault: TaskVault) -> Result<Vec<Task>, DbError> {
+        let query = vault.scope(doc! {
+            "$or": [
+                { "deleted": { "$ne": true } },
+                { "deleted": { "$exists": false } }
+            ]
+        });
+
+        self.collection
+            .find(query)
+            .await?
+            .try_collect()
+            .await
+            .map_err(Into::into)
+    }
+
+    /// The vault's task numbered `id`, unless it was deleted
+    fn live_task_filter(vault: TaskVault, id: i64) -> Document {
+        vault.scope(doc! {
+            "taskId": id,
+            "$or": [
+                { "deleted": { "$ne": true } },
+                { "deleted": { "$exists": false } }
+            ]
         })
     }
 
-    pub async fn find_all(&self) -> Result<Vec<Task>, DbError> {
-        let query = doc! {
+    pub async fn find_by_id(&self, vault: TaskVault, id: i64) -> Result<Option<Task>, DbError> {
+        let query = Self::live_task_filter(vault, id);
+        self.collection.find_one(query).await.map_err(Into::into)
+    }
+
+    /// Finds the non-deleted tasks among the given ids
+    pub async fn find_by_ids(&self, vault: TaskVault, ids: &[i64]) -> Result<Vec<Task>, DbError> {
+        let query = vault.scope(doc! {
+            "taskId": { "$in": ids },
             "$or": [
                 { "deleted": { "$ne": true } },
                 { "deleted": { "$exists": false } }
             ]
-        };
+        });
 
         self.collection
             .find(query)
@@ -57,39 +167,129 @@ impl TaskRepository {
             .map_err(Into::into)
     }
 
-    pub async fn find_by_id(&self, id: i64) -> Result<Option<Task>, DbError> {
-        let query = doc! {
-            "_id": id,
+    /// Tasks not yet completed or cancelled whose due date is before `until`, earliest first
+    pub async fn find_open_due_before(
+        &self,
+        vault: TaskVault,
+        until: BsonDateTime,
+    ) -> Result<Vec<Task>, DbError> {
+        let query = vault.scope(doc! {
+            "dueDate": { "$lt": until },
+            "status": { "$nin": Self::closed_statuses()? },
             "$or": [
                 { "deleted": { "$ne": true } },
                 { "deleted": { "$exists": false } }
             ]
+        });
+
+        self.collection
+            .find(query)
+            .sort(doc! { "dueDate": 1, "taskId": 1 })
+            .await?
+            .try_collect()
+            .await
+            .map_err(Into::into)
+    }
+
+    /// Tasks completed at or after `since`, most recent first
+    pub async fn find_completed_since(
+        &self,
+        vault: TaskVault,
+        since: BsonDateTime,
+    ) -> Result<Vec<Task>, DbError> {
+        let query = vault.scope(doc! {
+            "status": bson::to_bson(&ETaskStatus::Completed)?,
+            "completedAt": { "$gte": since },
+            "$or": [
+                { "deleted": { "$ne": true } },
+                { "deleted": { "$exists": false } }
+            ]
+        });
+
+        self.collection
+            .find(query)
+            .sort(doc! { "completedAt": -1, "taskId": 1 })
+            .await?
+            .try_collect()
+            .await
+            .map_err(Into::into)
+    }
+
+    /// In-progress tasks that have not been touched since `untouched_since`
+    pub async fn find_stalled(
+        &self,
+        vault: TaskVault,
+        untouched_since: BsonDateTime,
+    ) -> Result<Vec<Task>, DbError> {
+        let query = doc! {
+            "$and": [
+                vault.scope(doc! {}),
+                { "status": bson::to_bson(&ETaskStatus::InProgress)? },
+                { "$or": [
+                    { "deleted": { "$ne": true } },
+                    { "deleted": { "$exists": false } }
+                ] },
+                { "$or": [
+                    { "updatedAt": { "$lt": untouched_since } },
+                    { "updatedAt": null, "createdAt": { "$lt": untouched_since } }
+                ] }
+            ]
         };
-        self.collection.find_one(query).await.map_err(Into::into)
+
+        self.collection
+            .find(query)
+            .sort(doc! { "updatedAt": 1, "createdAt": 1 })
+            .await?
+            .try_collect()
+            .await
+            .map_err(Into::into)
     }
 
-    pub async fn create_task(&self, new_task: CreateTaskRequest) -> Result<Task, DbError> {
-        // Get the next task id via your counters' collection
+    /// Creates a task in `vault`; `created_by` is the owner for private vaults and the acting
+    /// member for workspaces
+    pub async fn create_task(
+        &self,
+        vault: TaskVault,
+        created_by: i64,
+        new_task: CreateTaskRequest,
+    ) -> Result<Task, DbError> {
+        // Get the vault's next task id via your counters' collection
         let next_task_id =
-            get_next_id_for_collection(&self.counters_collection, COLLECTION_NAME).await?;
+            get_next_id_for_collection(&self.counters_collection, &vault.sequence()).await?;
 
-        // Convert Option<NaiveDate> to Option<BsonDateTime> at midnight UTC
-        let due_date = Self::convert_due_date(new_task.due_date);
+        let (due_date, due_has_time) = Self::convert_due_date(new_task.due_date.as_ref())?;
 
         // Use current UTC time directly for created_at
         let created_at = BsonDateTime::now();
+        let notes_index = blind_index(EncryptedField::TaskNotes, new_task.notes.as_deref())?;
 
         let task = Task {
             id: next_task_id,
+            owner_id: created_by,
+            workspace_id: vault.workspace_id(),
+            assignee_id: new_task.assignee_id,
+            reportee_ids: new_task.reportee_ids,
             name: new_task.name,
             priority: new_task.priority.unwrap_or_default(),
             status: new_task.status.unwrap_or_default(),
+            workflow_status: new_task.workflow_status,
+            rank: None,
             due_date,
+            due_has_time,
             created_at,
+            updated_at: Some(created_at),
+            completed_at: None,
+            tags: normalize_tags(new_task.tags),
+            recurrence: new_task.recurrence,
+            estimate_minutes: new_task.estimate_minutes.filter(|minutes| *minutes > 0),
+            tracked_seconds: 0,
+            notes: new_task.notes,
+            notes_index,
+            external_id: new_task.external_id,
             deleted: Some(false),
         };
 
-        info!("{:?}", task);
+        info!("Creating task {} in {:?}", task.id, vault);
 
         match self.collection.insert_one(&task).await {
             Ok(_) => Ok(task),
@@ -99,6 +299,8 @@ impl TaskRepository {
 
     pub async fn bulk_create_tasks(
         &self,
+        vault: TaskVault,
+        created_by: i64,
         bulk_request: BulkCreateTaskRequest,
     ) -> Result<Vec<Task>, DbError> {
         if bulk_request.tasks.is_empty() {
@@ -110,7 +312,7 @@ impl TaskRepository {
         // Get the starting ID for the batch using the centralized auto_increment function
         let start_id = get_next_id_range_for_collection(
             &self.counters_collection,
-            COLLECTION_NAME,
+            &vault.sequence(),
             task_count,
         )
         .await?;
@@ -119,16 +321,32 @@ impl TaskRepository {
         let mut tasks_to_insert = Vec::new();
 
         for (index, new_task) in bulk_request.tasks.into_iter().enumerate() {
-            // Convert Option<NaiveDate> to Option<BsonDateTime> at midnight UTC
-            let due_date = Self::convert_due_date(new_task.due_date);
+            let (due_date, due_has_time) = Self::convert_due_date(new_task.due_date.as_ref())?;
+            let notes_index = blind_index(EncryptedField::TaskNotes, new_task.notes.as_deref())?;
 
             let task = Task {
                 id: start_id + index as i64,
+                owner_id: created_by,
+                workspace_id: vault.workspace_id(),
+                assignee_id: new_task.assignee_id,
+                reportee_ids: new_task.reportee_ids,
                 name: new_task.name,
                 priority: new_task.priority.unwrap_or_default(),
                 status: new_task.status.unwrap_or_default(),
+                workflow_status: new_task.workflow_status,
+                rank: None,
                 due_date,
+                due_has_time,
                 created_at,
+                updated_at: Some(created_at),
+                completed_at: None,
+                tags: normalize_tags(new_task.tags),
+                recurrence: new_task.recurrence,
+                estimate_minutes: new_task.estimate_minutes.filter(|minutes| *minutes > 0),
+                tracked_seconds: 0,
+                notes: new_task.notes,
+                notes_index,
+                external_id: new_task.external_id,
                 deleted: Some(false),
             };
 
@@ -143,7 +361,145 @@ impl TaskRepository {
         }
     }
 
-    pub async fn search_task(&self, params: TaskSearchParams) -> Result<Vec<Task>, DbError> {
+    pub async fn search_task(
+        &self,
+        vault: TaskVault,
+        params: TaskSearchParams,
+        dates: &DatePreferences,
+    ) -> Result<Vec<Task>, DbError> {
+        let sort = params.sort;
+        let query = Self::search_query(vault, params, dates)?;
+
+        let mut tasks: Vec<Task> = self.collection.find(query).await?.try_collect().await?;
+        if let Some(sort) = sort {
+            Self::sort_tasks(&mut tasks, sort);
+        }
+        Ok(tasks)
+    }
+
+    /// Priorities are stored by name, so they are ordered here rather than by the database
+    fn sort_tasks(tasks: &mut [Task], sort: ETaskSort) {
+        let by_due = |a: &Task, b: &Task| match (a.due_date, b.due_date) {
+            (Some(a), Some(b)) => a.cmp(&b),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        };
+        match sort {
+            ETaskSort::Priority => tasks.sort_by(|a, b| {
+                b.priority
+                    .cmp(&a.priority)
+                    .then_with(|| by_due(a, b))
+                    .then(a.id.cmp(&b.id))
+            }),
+            ETaskSort::Due => tasks.sort_by(|a, b| by_due(a, b).then(a.id.cmp(&b.id))),
+        }
+    }
+
+    /// Same matching as `search_task`, in id order and without buffering the results
+    pub async fn stream_tasks(
+        &self,
+        vault: TaskVault,
+        params: TaskSearchParams,
+        dates: &DatePreferences,
+    ) -> Result<Cursor<Task>, DbError> {
+        let query = Self::search_query(vault, params, dates)?;
+        self.collection
+            .find(query)
+            .sort(doc! { "taskId": 1 })
+            .await
+            .map_err(Into::into)
+    }
+
+    /// Non-deleted tasks named exactly one of `names`
+    pub async fn find_by_names(
+        &self,
+        vault: TaskVault,
+        names: &[String],
+    ) -> Result<Vec<Task>, DbError> {
+        let query = vault.scope(doc! {
+            "name": { "$in": names },
+            "$or": [
+                { "deleted": { "$ne": true } },
+                { "deleted": { "$exists": false } }
+            ]
+        });
+
+        self.collection
+            .find(query)
+            .await?
+            .try_collect()
+            .await
+            .map_err(Into::into)
+    }
+
+    /// Non-deleted tasks imported under any of `external_ids`
+    pub async fn find_by_external_ids(
+        &self,
+        vault: TaskVault,
+        external_ids: &[String],
+    ) -> Result<Vec<Task>, DbError> {
+        let query = vault.scope(doc! {
+            "externalId": { "$in": external_ids },
+            "$or": [
+                { "deleted": { "$ne": true } },
+                { "deleted": { "$exists": false } }
+            ]
+        });
+
+        self.collection
+            .find(query)
+            .await?
+            .try_collect()
+            .await
+            .map_err(Into::into)
+    }
+
+    /// Substring match on plaintext notes. Encrypted notes fall back to the blind index, which
+    /// only matches tasks containing every whole word of the query.
+    fn notes_condition(notes_query: &str) -> Result<Document, DbError> {
+        match blind_index_query(EncryptedField::TaskNotes, notes_query)? {
+            Some(tokens) => Ok(doc! { "notesIndex": { "$all": tokens } }),
+            None => Ok(doc! { "notes": { "$regex": notes_query, "$options": "i" } }),
+        }
+    }
+
+    /// Tasks not yet completed or cancelled that are overdue at `now` by the calendar of
+    /// `tz`, earliest first
+    pub async fn find_overdue(
+        &self,
+        vault: TaskVault,
+        now: DateTime<Utc>,
+        tz: Tz,
+    ) -> Result<Vec<Task>, DbError> {
+        let query = doc! {
+            "$and": [
+                vault.scope(doc! {}),
+                { "status": { "$nin": Self::closed_statuses()? } },
+                { "$or": [
+                    { "deleted": { "$ne": true } },
+                    { "deleted": { "$exists": false } }
+                ] },
+                overdue_condition(now, tz)
+            ]
+        };
+
+        self.collection
+            .find(query)
+            .sort(doc! { "dueDate": 1, "taskId": 1 })
+            .await?
+            .try_collect()
+            .await
+            .map_err(Into::into)
+    }
+
+    /// `params.workspace` is ignored; callers resolve it into `vault`. Due dates are matched by
+    /// the calendar of the user's time zone.
+    fn search_query(
+        vault: TaskVault,
+        params: TaskSearchParams,
+        dates: &DatePreferences,
+    ) -> Result<Document, DbError> {
         let mut query = doc! {};
 
         // Add deleted filter
@@ -154,7 +510,7 @@ impl TaskRepository {
             ]
         };
 
-        let mut conditions = vec![deleted_filter];
+        let mut conditions = vec![vault.scope(doc! {}), deleted_filter];
 
         if let Some(search_term) = params.query {
             let search_condition = doc! { "name": { "$regex": &search_term , "$options": "i" } };
@@ -166,44 +522,43 @@ impl TaskRepository {
         if let Some(priority_query) = params.priority {
             conditions.push(doc! { "priority": bson::to_bson(&priority_query)? });
         }
+        if let Some(tag) = params.tag {
+            conditions.push(doc! { "tags": tag.trim().to_lowercase() });
+        }
+        if let Some(notes_query) = params.notes {
+            conditions.push(Self::notes_condition(&notes_query)?);
+        }
+        if let Some(assignee_id) = params.assignee {
+            conditions.push(doc! { "assigneeId": assignee_id });
+        }
         if let Some(due_date_query) = params.due_date {
-            // Construct NaiveDateTime bounds for the same day
-            let day_start_naive = due_date_query.and_hms_opt(0, 0, 0).unwrap();
-            let next_day_start_naive = (due_date_query + Duration::days(1))
-                .and_hms_opt(0, 0, 0)
-                .unwrap();
-            let day_start_utc = Utc.from_utc_datetime(&day_start_naive);
-            let next_day_start_utc = Utc.from_utc_datetime(&next_day_start_naive);
-            let desired_min_date = BsonDateTime::from(day_start_utc);
-            let desired_max_date = BsonDateTime::from(next_day_start_utc);
-
-            conditions.push(doc! {
-                "dueDate": {
-                    "$gte": desired_min_date,
-                    "$lt": desired_max_date
+            conditions.push(Self::due_day_condition(&due_date_query, dates.tz())?);
+        }
+        if let Some(due) = params.due {
+            let now = Utc::now();
+            match due {
+                EDueWindow::Today => {
+                    let today = dates.today(now);
+                    conditions.push(due_between_condition(
+                        today,
+                        today + Duration::days(1),
+                        dates.tz(),
+                    ));
+                }
+                EDueWindow::Overdue => {
+                    conditions.push(overdue_condition(now, dates.tz()));
+                    conditions.push(doc! { "status": { "$nin": Self::closed_statuses()? } });
                 }
-            });
+            }
         }
 
         query.insert("$and", conditions);
-
-        self.collection
-            .find(query)
-            .await?
-            .try_collect()
-            .await
-            .map_err(Into::into)
+        Ok(query)
     }
 
     /// Softly delete a task by setting deleted: true
-    pub async fn soft_delete_by_id(&self, id: i64) -> Result<bool, DbError> {
-        let filter = doc! {
-            "_id": id,
-            "$or": [
-                { "deleted": { "$ne": true } },
-                { "deleted": { "$exists": false } }
-            ]
-        };
+    pub async fn soft_delete_by_id(&self, vault: TaskVault, id: i64) -> Result<bool, DbError> {
+        let filter = Self::live_task_filter(vault, id);
         let update = doc! { "$set": { "deleted": true } };
 
         let result = self.collection.update_one(filter, update).await?;
@@ -213,31 +568,67 @@ impl TaskRepository {
     /// Update a task by ID with partial updates (only status, due_date, priority)
     pub async fn update_task_by_id(
         &self,
+        vault: TaskVault,
         id: i64,
         update_request: UpdateTaskRequest,
     ) -> Result<Option<Task>, DbError> {
-        let filter = doc! {
-            "_id": id,
-            "$or": [
-                { "deleted": { "$ne": true } },
-                { "deleted": { "$exists": false } }
-            ]
-        };
+        let filter = Self::live_task_filter(vault, id);
 
-        let mut set_fields = doc! {};
+        let now = BsonDateTime::now();
+        let mut set_fields = doc! { "updatedAt": now };
 
+        if let Some(name) = update_request.name {
+            set_fields.insert("name", name);
+        }
+        let mut unset_fields = doc! {};
         if let Some(status) = update_request.status {
+            if matches!(status, ETaskStatus::Completed) {
+                set_fields.insert("completedAt", now);
+            }
             set_fields.insert("status", bson::to_bson(&status)?);
+            // A workflow status is only kept when it was set alongside
+            if update_request.workflow_status.is_none() {
+                unset_fields.insert("workflowStatus", "");
+            }
+        }
+        if let Some(workflow_status) = update_request.workflow_status {
+            set_fields.insert("workflowStatus", workflow_status);
         }
         if let Some(priority) = update_request.priority {
             set_fields.insert("priority", bson::to_bson(&priority)?);
         }
         if let Some(due_date) = update_request.due_date {
-            let converted_date = Self::convert_due_date(Some(due_date));
-            set_fields.insert("due_date", bson::to_bson(&converted_date)?);
+            let (due_date, due_has_time) = Self::convert_due_date(Some(&due_date))?;
+            set_fields.insert("dueDate", due_date);
+            set_fields.insert("dueHasTime", due_has_time);
+        }
+        if let Some(tags) = update_request.tags {
+            set_fields.insert("tags", normalize_tags(tags));
+        }
+        if let Some(recurrence) = update_request.recurrence {
+            set_fields.insert("recurrence", recurrence);
+        }
+        match update_request.estimate_minutes {
+            Some(0) => {
+                unset_fields.insert("estimateMinutes", "");
+            }
+            Some(minutes) => {
+                set_fields.insert("estimateMinutes", i64::from(minutes));
+            }
+            None => {}
+        }
+        if let Some(notes) = update_request.notes {
+            set_fields.insert("notesIndex", blind_index(EncryptedField::TaskNotes, Some(&notes))?);
+            set_fields.insert("notes", seal(EncryptedField::TaskNotes, &notes)?);
+        }
+        if let Some(assignee_id) = update_request.assignee_id {
+            set_fields.insert("assigneeId", assignee_id);
         }
 
-        let update = doc! { "$set": set_fields };
+        let mut update = doc! { "$set": set_fields };
+        if !unset_fields.is_empty() {
+            update.insert("$unset", unset_fields);
+        }
 
         let options = mongodb::options::FindOneAndUpdateOptions::builder()
             .return_document(mongodb::options::ReturnDocument::After)
@@ -250,94 +641,343 @@ impl TaskRepository {
             .map_err(Into::into)
     }
 
-    /// Bulk soft delete tasks by status
-    pub async fn bulk_soft_delete_by_status(&self, status: ETaskStatus) -> Result<u64, DbError> {
-        let filter = doc! {
+    /// Bulk soft delete tasks by status, returning the tasks as they were before deletion
+    pub async fn bulk_soft_delete_by_status(
+        &self,
+        vault: TaskVault,
+        status: ETaskStatus,
+    ) -> Result<Vec<Task>, DbError> {
+        let filter = vault.scope(doc! {
             "status": bson::to_bson(&status)?,
             "$or": [
                 { "deleted": { "$ne": true } },
                 { "deleted": { "$exists": false } }
             ]
-        };
-        let update = doc! { "$set": { "deleted": true } };
+        });
+        let tasks: Vec<Task> = self.collection.find(filter).await?.try_collect().await?;
+        if tasks.is_empty() {
+            return Ok(tasks);
+        }
 
-        let result = self.collection.update_many(filter, update).await?;
-        Ok(result.modified_count)
+        let ids: Vec<i64> = tasks.iter().map(|task| task.id).collect();
+        let update = doc! { "$set": { "deleted": true, "updatedAt": BsonDateTime::now() } };
+        self.collection
+            .update_many(vault.scope(doc! { "taskId": { "$in": &ids } }), update)
+            .await?;
+        Ok(tasks)
     }
 
-    /// Search and update tasks based on search criteria
-    pub async fn search_and_update_tasks(
+    /// Stores board ranks. Reordering is not an edit, so `updatedAt` is left alone.
+    pub async fn set_ranks(
         &self,
-        request: SearchAndUpdateRequest,
-    ) -> Result<Vec<Task>, DbError> {
-        // Validate that at least one update field is provided
-        if request.status.is_none() && request.due_date.is_none() && request.priority.is_none() {
-            return Err(DbError::InvalidId(
-                "At least one update field must be provided".to_string(),
-            ));
+        vault: TaskVault,
+        ranks: &[(i64, String)],
+    ) -> Result<(), DbError> {
+        for (id, rank) in ranks {
+            self.collection
+                .update_one(
+                    vault.scope(doc! { "taskId": id }),
+                    doc! { "$set": { "rank": rank } },
+                )
+                .await?;
         }
+        Ok(())
+    }
+
+    /// Adds `seconds`, negative to take time off, to the time tracked on a task. Tracking time
+    /// is not an edit, so `updatedAt` is left alone.
+    pub async fn add_tracked_seconds(
+        &self,
+        vault: TaskVault,
+        id: i64,
+        seconds: i64,
+    ) -> Result<(), DbError> {
+        self.collection
+            .update_one(
+                vault.scope(doc! { "taskId": id }),
+                doc! { "$inc": { "trackedSeconds": seconds } },
+            )
+            .await?;
+        Ok(())
+    }
 
-        // Build a search query
-        let mut conditions = vec![doc! {
+    /// Drops the workflow status of the vault's tasks whose status is not among `keep`, after
+    /// a workflow was changed or removed. Returns how many tasks were changed.
+    pub async fn clear_workflow_statuses(
+        &self,
+        vault: TaskVault,
+        keep: &[String],
+    ) -> Result<u64, DbError> {
+        let filter = vault.scope(doc! {
+            "workflowStatus": { "$exists": true, "$nin": keep },
+        });
+        let update = doc! { "$unset": { "workflowStatus": "" } };
+        let result = self.collection.update_many(filter, update).await?;
+        Ok(result.modified_count)
+    }
+
+    /// Tasks of the vault that a search-and-update request applies to
+    fn search_and_update_filter(
+        vault: TaskVault,
+        request: &SearchAndUpdateRequest,
+        dates: &DatePreferences,
+    ) -> Result<Document, DbError> {
+        let mut conditions = vec![vault.scope(doc! {}), doc! {
             "$or": [
                 { "deleted": { "$ne": true } },
                 { "deleted": { "$exists": false } }
             ]
         }];
 
-        if let Some(search_term) = request.query {
-            conditions.push(doc! { "name": { "$regex": &search_term, "$options": "i" } });
-        }
-        if let Some(status_filter) = request.status_filter {
-            conditions.push(doc! { "status": bson::to_bson(&status_filter)? });
-        }
-        if let Some(priority_filter) = request.priority_filter {
-            conditions.push(doc! { "priority": bson::to_bson(&priority_filter)? });
-        }
-        if let Some(due_date_filter) = request.due_date_filter {
-            let day_start_naive = due_date_filter.and_hms_opt(0, 0, 0).unwrap();
-            let next_day_start_naive = (due_date_filter + Duration::days(1))
-                .and_hms_opt(0, 0, 0)
-                .unwrap();
-            let day_start_utc = Utc.from_utc_datetime(&day_start_naive);
-            let next_day_start_utc = Utc.from_utc_datetime(&next_day_start_naive);
-            let desired_min_date = BsonDateTime::from(day_start_utc);
-            let desired_max_date = BsonDateTime::from(next_day_start_utc);
-
-            conditions.push(doc! {
-                "dueDate": {
-                    "$gte": desired_min_date,
-                    "$lt": desired_max_date
-                }
-            });
+        if let Some(search_term) = &request.query {
+            conditions.push(doc! { "name": { "$regex": search_term, "$options": "i" } });
+        }
+        if let Some(status_filter) = &request.status_filter {
+            conditions.push(doc! { "status": bson::to_bson(status_filter)? });
+        }
+        if let Some(priority_filter) = &request.priority_filter {
+            conditions.push(doc! { "priority": bson::to_bson(priority_filter)? });
+        }
+        if let Some(due_date_filter) = &request.due_date_filter {
+            conditions.push(Self::due_day_condition(due_date_filter, dates.tz())?);
         }
 
-        let filter = doc! { "$and": conditions };
+        Ok(doc! { "$and": conditions })
+    }
+
+    /// Search and update tasks based on search criteria.
+    /// Returns each matched task as `(before, after)`. Only the vault's tasks are matched or
+    /// updated, whatever the search criteria.
+    pub async fn search_and_update_tasks(
+        &self,
+        vault: TaskVault,
+        request: SearchAndUpdateRequest,
+        dates: &DatePreferences,
+    ) -> Result<Vec<(Task, Task)>, DbError> {
+        // Validate that at least one update field is provided
+        if !request.has_update() {
+            return Err(DbError::InvalidInput(
+                "At least one update field must be provided".to_string(),
+            ));
+        }
+
+        let filter = Self::search_and_update_filter(vault, &request, dates)?;
 
         // Build update document
-        let mut set_fields = doc! {};
+        let now = BsonDateTime::now();
+        let mut set_fields = doc! { "updatedAt": now };
+        let mut update = doc! {};
         if let Some(status) = request.status {
+            if matches!(status, ETaskStatus::Completed) {
+                set_fields.insert("completedAt", now);
+            }
             set_fields.insert("status", bson::to_bson(&status)?);
+            // Tasks fall back to the first workflow status of the new status's category
+            update.insert("$unset", doc! { "workflowStatus": "" });
         }
         if let Some(priority) = request.priority {
             set_fields.insert("priority", bson::to_bson(&priority)?);
         }
         if let Some(due_date) = request.due_date {
-            let converted_date = Self::convert_due_date(Some(due_date));
-            set_fields.insert("due_date", bson::to_bson(&converted_date)?);
+            let (due_date, due_has_time) = Self::convert_due_date(Some(&due_date))?;
+            set_fields.insert("dueDate", due_date);
+            set_fields.insert("dueHasTime", due_has_time);
         }
+        update.insert("$set", set_fields);
 
-        let update = doc! { "$set": set_fields };
+        // Snapshot the matches first; the update may change the fields the filter matched on
+        let before: Vec<Task> = self.collection.find(filter).await?.try_collect().await?;
+        if before.is_empty() {
+            return Ok(Vec::new());
+        }
+        let ids: Vec<i64> = before.iter().map(|task| task.id).collect();
 
         // Update all matching documents
-        self.collection.update_many(filter.clone(), update).await?;
-
-        // Return the updated documents
+        let matched = vault.scope(doc! { "taskId": { "$in": &ids } });
         self.collection
-            .find(filter)
+            .update_many(matched.clone(), update)
+            .await?;
+
+        // Pair each snapshot with the updated document
+        let mut after: HashMap<i64, Task> = self
+            .collection
+            .find(matched)
             .await?
-            .try_collect()
-            .await
-            .map_err(Into::into)
+            .try_collect::<Vec<Task>>()
+            .await?
+            .into_iter()
+            .map(|task| (task.id, task))
+            .collect();
+        Ok(before
+            .into_iter()
+            .filter_map(|task| after.remove(&task.id).map(|updated| (task, updated)))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::transfer::tests::task;
+
+    const USER_A: i64 = 1;
+    const USER_B: i64 = 2;
+
+    /// Whether MongoDB would match `document` with `filter`, for the operators vault filters
+    /// use; anything else fails the test rather than matching by accident
+    pub(crate) fn matches(filter: &Document, document: &Document) -> bool {
+        filter.iter().all(|(key, condition)| match key.as_str() {
+            "$and" => clauses(condition).iter().all(|clause| matches(clause, document)),
+            "$or" => clauses(condition).iter().any(|clause| matches(clause, document)),
+            field => field_matches(document.get(field), condition),
+        })
+    }
+
+    fn clauses(condition: &Bson) -> Vec<Document> {
+        condition
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|clause| clause.as_document().unwrap().clone())
+            .collect()
+    }
+
+    fn equals(value: Option<&Bson>, expected: &Bson) -> bool {
+        match (value, expected) {
+            (None | Some(Bson::Null), Bson::Null) => true,
+            (Some(value), expected) => value == expected,
+            (None, _) => false,
+        }
+    }
+
+    fn compare(value: Option<&Bson>, operand: &Bson) -> Option<Ordering> {
+        match (value?, operand) {
+            (Bson::DateTime(value), Bson::DateTime(operand)) => Some(value.cmp(operand)),
+            (Bson::Int64(value), Bson::Int64(operand)) => Some(value.cmp(operand)),
+            _ => None,
+        }
+    }
+
+    fn field_matches(value: Option<&Bson>, condition: &Bson) -> bool {
+        let Some(operators) = condition.as_document().filter(|d| {
+            d.keys().next().is_some_and(|key| key.starts_with('$'))
+        }) else {
+            return equals(value, condition);
+        };
+        operators.iter().all(|(operator, operand)| match operator.as_str() {
+            "$ne" => !equals(value, operand),
+            "$exists" => value.is_some() == operand.as_bool().unwrap(),
+            "$in" => operand.as_array().unwrap().iter().any(|item| equals(value, item)),
+            "$gte" => compare(value, operand).is_some_and(Ordering::is_ge),
+            "$lt" => compare(value, operand).is_some_and(Ordering::is_lt),
+            other => panic!("unsupported operator {}", other),
+        })
+    }
+
+    /// User A's private task number 7, as stored
+    fn stored_task_of_a() -> Document {
+        let mut task = task(7, "Pay the rent");
+        task.owner_id = USER_A;
+        bson::to_document(&task).unwrap()
+    }
+
+    fn update_request() -> SearchAndUpdateRequest {
+        SearchAndUpdateRequest {
+            query: None,
+            status_filter: None,
+            priority_filter: None,
+            due_date_filter: None,
+            due_date: None,
+            priority: None,
+            status: Some(ETaskStatus::Completed),
+        }
+    }
+
+    #[test]
+    fn task_ids_only_reach_the_owner_s_vault() {
+        let stored = stored_task_of_a();
+        let own = TaskRepository::live_task_filter(TaskVault::User(USER_A), 7);
+        let other = TaskRepository::live_task_filter(TaskVault::User(USER_B), 7);
+        // The same filter serves reading, updating and deleting by id
+        assert!(matches(&own, &stored));
+        let workspace = TaskRepository::live_task_filter(TaskVault::Workspace(USER_A), 7);
+        assert!(!matches(&other, &stored));
+        assert!(!matches(&workspace, &stored));
+    }
+
+    #[test]
+    fn deleted_tasks_are_not_found_by_id() {
+        let mut stored = stored_task_of_a();
+        stored.insert("deleted", true);
+        let own = TaskRepository::live_task_filter(TaskVault::User(USER_A), 7);
+        assert!(!matches(&own, &stored));
+    }
+
+    #[test]
+    fn searches_stay_in_the_caller_s_vault() {
+        let stored = stored_task_of_a();
+        let dates = DatePreferences::default();
+        let search = |vault| {
+            TaskRepository::search_query(vault, TaskSearchParams::default(), &dates).unwrap()
+        };
+        assert!(matches(&search(TaskVault::User(USER_A)), &stored));
+        assert!(!matches(&search(TaskVault::User(USER_B)), &stored));
+    }
+
+    #[test]
+    fn bulk_updates_stay_in_the_caller_s_vault() {
+        let stored = stored_task_of_a();
+        let dates = DatePreferences::default();
+        let filter = |vault| {
+            TaskRepository::search_and_update_filter(vault, &update_request(), &dates).unwrap()
+        };
+        assert!(matches(&filter(TaskVault::User(USER_A)), &stored));
+        assert!(!matches(&filter(TaskVault::User(USER_B)), &stored));
+    }
+
+    #[test]
+    fn workspace_tasks_are_not_in_their_creator_s_private_vault() {
+        let mut stored = stored_task_of_a();
+        stored.insert("workspaceId", 3_i64);
+        let private = TaskRepository::live_task_filter(TaskVault::User(USER_A), 7);
+        let workspace = TaskRepository::live_task_filter(TaskVault::Workspace(3), 7);
+        assert!(!matches(&private, &stored));
+        assert!(matches(&workspace, &stored));
+    }
+
+    #[test]
+    fn unresolved_due_dates_are_invalid_input() {
+        let day = |day| chrono::NaiveDate::from_ymd_opt(2026, 10, day).unwrap();
+        let ambiguous = DateInput::Ambiguous {
+            input: "10/11/2026".to_string(),
+            day_first: day(10),
+            month_first: day(11),
+            time: None,
+        };
+        assert!(matches!(
+            TaskRepository::convert_due_date(Some(&ambiguous)),
+            Err(DbError::InvalidInput(message)) if message.contains("10/11/2026")
+        ));
+        assert!(matches!(
+            TaskRepository::due_day_condition(&ambiguous, chrono_tz::UTC),
+            Err(DbError::InvalidInput(_))
+        ));
+
+        let resolved = DateInput::Day(day(19));
+        let (due_date, has_time) = TaskRepository::convert_due_date(Some(&resolved)).unwrap();
+        assert!(due_date.is_some());
+        assert!(!has_time);
+    }
+
+    #[test]
+    fn every_vault_numbers_its_own_tasks() {
+        let sequences = [
+            TaskVault::User(USER_A).sequence(),
+            TaskVault::User(USER_B).sequence(),
+            TaskVault::Workspace(USER_A).sequence(),
+        ];
+        assert_eq!(sequences[0], "tasks:1");
+        assert_ne!(sequences[0], sequences[1]);
+        assert_ne!(sequences[0], sequences[2]);
     }
 }
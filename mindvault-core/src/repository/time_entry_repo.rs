@@ -0,0 +1,433 @@
+use crate::models::{AppDatabase, DbCollection, DbError};
+use crate::repository::auto_increment::{get_next_id_for_collection, AUTO_INCREMENT_COLLECTION_NAME};
+use crate::repository::task_repo::TaskVault;
+use bson::{Bson, DateTime as BsonDateTime, Document};
+use futures_util::TryStreamExt;
+use mindvault_shared::models::time_entries_model::{ETimeReportGroup, TimeEntry, TimeReportRow};
+use mongodb::bson::doc;
+use mongodb::options::{FindOneAndUpdateOptions, ReturnDocument};
+use mongodb::{bson, Collection};
+use tracing::info;
+
+/// Time entries carry the `ownerId` and `workspaceId` of the task they were tracked on, so
+/// a vault's entries are found by the same scope as its tasks. Entries are numbered from one
+/// sequence for all users.
+pub struct TimeEntryRepository {
+    collection: Collection<TimeEntry>,
+    counters_collection: DbCollection<Document>,
+}
+
+const COLLECTION_NAME: &str = "time_entries";
+const TASKS_COLLECTION_NAME: &str = "tasks";
+
+/// Fields of a time entry resolved by the caller before it is stored; without `ended_at` the
+/// entry is a running timer
+pub struct NewTimeEntry {
+    pub task_id: i64,
+    pub started_at: BsonDateTime,
+    pub ended_at: Option<BsonDateTime>,
+    pub note: Option<String>,
+}
+
+/// The vault a time entry was tracked in
+pub fn entry_vault(entry: &TimeEntry) -> TaskVault {
+    match entry.workspace_id {
+        Some(workspace_id) => TaskVault::Workspace(workspace_id),
+        None => TaskVault::User(entry.owner_id),
+    }
+}
+
+fn seconds_between(started_at: BsonDateTime, ended_at: BsonDateTime) -> i64 {
+    ((ended_at.timestamp_millis() - started_at.timestamp_millis()) / 1000).max(0)
+}
+
+impl TimeEntryRepository {
+    pub fn new(app_database: AppDatabase) -> Self {
+        let collection = app_database.collection::<TimeEntry>(COLLECTION_NAME);
+        let counters_collection =
+            app_database.collection::<Document>(AUTO_INCREMENT_COLLECTION_NAME);
+        Self {
+            collection,
+            counters_collection,
+        }
+    }
+
+    fn not_deleted() -> Document {
+        doc! {
+            "$or": [
+                { "deleted": { "$ne": true } },
+                { "deleted": { "$exists": false } }
+            ]
+        }
+    }
+
+    /// The user's running timer, in whichever vault it was started
+    pub async fn find_running(&self, owner_id: i64) -> Result<Option<TimeEntry>, DbError> {
+        let query = doc! {
+            "ownerId": owner_id,
+            "endedAt": Bson::Null,
+            "$and": [Self::not_deleted()],
+        };
+        self.collection.find_one(query).await.map_err(Into::into)
+    }
+
+    /// Time entries of a task, newest first
+    pub async fn find_by_task(
+        &self,
+        vault: TaskVault,
+        task_id: i64,
+    ) -> Result<Vec<TimeEntry>, DbError> {
+        let query = vault.scope(doc! { "taskId": task_id, "$and": [Self::not_deleted()] });
+        self.collection
+            .find(query)
+            .sort(doc! { "startedAt": -1 })
+            .await?
+            .try_collect()
+            .await
+            .map_err(Into::into)
+    }
+
+    pub async fn create_entry(
+        &self,
+        vault: TaskVault,
+        owner_id: i64,
+        new_entry: NewTimeEntry,
+    ) -> Result<TimeEntry, DbError> {
+        let next_id = get_next_id_for_collection(&self.counters_collection, COLLECTION_NAME).await?;
+
+        let entry = TimeEntry {
+            id: next_id,
+            owner_id,
+            workspace_id: vault.workspace_id(),
+            task_id: new_entry.task_id,
+            started_at: new_entry.started_at,
+            ended_at: new_entry.ended_at,
+            duration_seconds: new_entry
+                .ended_at
+                .map(|ended_at| seconds_between(new_entry.started_at, ended_at))
+                .unwrap_or(0),
+            note: new_entry.note,
+            manual: new_entry.ended_at.is_some(),
+            created_at: BsonDateTime::now(),
+            deleted: Some(false),
+        };
+
+        info!("Creating time entry {} for task {}", entry.id, entry.task_id);
+
+        match self.collection.insert_one(&entry).await {
+            Ok(_) => Ok(entry),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Stops `running` now, replacing its note when one is given. Returns `None` when the
+    /// timer was stopped in the meantime.
+    pub async fn stop_entry(
+        &self,
+        running: &TimeEntry,
+        note: Option<String>,
+    ) -> Result<Option<TimeEntry>, DbError> {
+        let ended_at = BsonDateTime::now();
+        let mut set_fields = doc! {
+            "endedAt": ended_at,
+            "durationSeconds": seconds_between(running.started_at, ended_at),
+        };
+        if let Some(note) = note {
+            set_fields.insert("note", note);
+        }
+        let options = FindOneAndUpdateOptions::builder()
+            .return_document(ReturnDocument::After)
+            .build();
+
+        self.collection
+            .find_one_and_update(
+                doc! { "_id": running.id, "endedAt": Bson::Null },
+                doc! { "$set": set_fields },
+            )
+            .with_options(options)
+            .await
+            .map_err(Into::into)
+    }
+
+    /// Soft deletes one of `owner_id`'s entries on a task, returning it as it was
+    pub async fn soft_delete_entry(
+        &self,
+        vault: TaskVault,
+        owner_id: i64,
+        task_id: i64,
+        id: i64,
+    ) -> Result<Option<TimeEntry>, DbError> {
+        let mut filter = vault.scope(doc! {
+            "_id": id,
+            "taskId": task_id,
+            "$and": [Self::not_deleted()],
+        });
+        // Only the member who tracked a workspace entry may delete it
+        filter.insert("ownerId", owner_id);
+
+        self.collection
+            .find_one_and_update(filter, doc! { "$set": { "deleted": true } })
+            .await
+            .map_err(Into::into)
+    }
+
+    /// Groups entries by `key`, adding up their time
+    fn group_stage(key: Bson) -> Document {
+        doc! {
+            "$group": {
+                "_id": key,
+                "trackedSeconds": { "$sum": "$durationSeconds" },
+                "entryCount": { "$sum": 1 },
+            }
+        }
+    }
+
+    /// The vault's stopped entries that started from `from` up to but excluding `until`
+    fn report_match(
+        vault: TaskVault,
+        from: Option<BsonDateTime>,
+        until: Option<BsonDateTime>,
+    ) -> Document {
+        let mut started_at = doc! {};
+        if let Some(from) = from {
+            started_at.insert("$gte", from);
+        }
+        if let Some(until) = until {
+            started_at.insert("$lt", until);
+        }
+        let mut conditions = vault.scope(doc! {
+            "endedAt": { "$ne": Bson::Null },
+            "$and": [Self::not_deleted()],
+        });
+        if !started_at.is_empty() {
+            conditions.insert("startedAt", started_at);
+        }
+        doc! { "$match": conditions }
+    }
+
+    /// Report rows of the entries `matching` selects, grouped by `group_by`
+    fn report_pipeline(
+        vault: TaskVault,
+        group_by: ETimeReportGroup,
+        matching: Document,
+        time_zone: &str,
+    ) -> Vec<Document> {
+        // Task numbers are only unique within a vault
+        let task_lookup = |fields: Document| {
+            doc! {
+                "$lookup": {
+                    "from": TASKS_COLLECTION_NAME,
+                    "let": { "taskId": "$taskId" },
+                    "pipeline": [
+                        { "$match": vault.scope(doc! {
+                            "$expr": { "$eq": ["$taskId", "$$taskId"] }
+                        }) },
+                        { "$project": fields },
+                    ],
+                    "as": "task",
+                }
+            }
+        };
+        let mut pipeline = vec![matching];
+        match group_by {
+            ETimeReportGroup::Tag => pipeline.extend([
+                task_lookup(doc! { "tags": 1 }),
+                doc! { "$unwind": { "path": "$task", "preserveNullAndEmptyArrays": true } },
+                doc! { "$unwind": { "path": "$task.tags", "preserveNullAndEmptyArrays": true } },
+                Self::group_stage("$task.tags".into()),
+                doc! { "$sort": { "trackedSeconds": -1, "_id": 1 } },
+            ]),
+            ETimeReportGroup::Task => pipeline.extend([
+                Self::group_stage("$taskId".into()),
+                doc! { "$sort": { "trackedSeconds": -1, "_id": 1 } },
+                doc! { "$addFields": { "taskId": "$_id" } },
+                task_lookup(doc! { "name": 1, "estimateMinutes": 1 }),
+                doc! { "$unwind": { "path": "$task", "preserveNullAndEmptyArrays": true } },
+                doc! { "$addFields": {
+                    "_id": { "$toString": "$_id" },
+                    "taskName": "$task.name",
+                    "estimateMinutes": "$task.estimateMinutes",
+                } },
+            ]),
+            ETimeReportGroup::Day => pipeline.extend([
+                Self::group_stage(Bson::Document(doc! {
+                    "$dateToString": {
+                        "format": "%Y-%m-%d",
+                        "date": "$startedAt",
+                        "timezone": time_zone,
+                    }
+                })),
+                doc! { "$sort": { "_id": 1 } },
+            ]),
+        }
+        pipeline.push(doc! { "$project": {
+            "_id": 0,
+            "key": "$_id",
+            "taskName": 1,
+            "estimateMinutes": 1,
+            "trackedSeconds": 1,
+            "entryCount": 1,
+        } });
+        pipeline
+    }
+
+    /// Stopped time of the vault's entries that started from `from` up to but excluding
+    /// `until`, grouped in the database. Returns the rows and the totals of the period, which
+    /// count an entry once however many tags its task has.
+    pub async fn report(
+        &self,
+        vault: TaskVault,
+        group_by: ETimeReportGroup,
+        from: Option<BsonDateTime>,
+        until: Option<BsonDateTime>,
+        time_zone: &str,
+    ) -> Result<(Vec<TimeReportRow>, i64, i64), DbError> {
+        let matching = Self::report_match(vault, from, until);
+        let pipeline = Self::report_pipeline(vault, group_by, matching.clone(), time_zone);
+
+        let rows: Vec<Document> = self.collection.aggregate(pipeline).await?.try_collect().await?;
+        let rows = rows
+            .into_iter()
+            .map(bson::from_document)
+            .collect::<Result<Vec<TimeReportRow>, _>>()?;
+
+        let total_pipeline = vec![matching, Self::group_stage(Bson::Null)];
+        let total = self.collection.aggregate(total_pipeline).await?.try_next().await?;
+        let (tracked_seconds, entry_count) = match total {
+            Some(total) => {
+                let total: TimeReportRow = bson::from_document(total)?;
+                (total.tracked_seconds, total.entry_count)
+            }
+            None => (0, 0),
+        };
+        Ok((rows, tracked_seconds, entry_count))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repository::task_repo::tests::matches;
+    use chrono::{TimeZone, Utc};
+
+    fn at(day: u32, hour: u32, minute: u32) -> BsonDateTime {
+        BsonDateTime::from(Utc.with_ymd_and_hms(2026, 10, day, hour, minute, 0).unwrap())
+    }
+
+    fn stopped_entry(started_at: BsonDateTime, minutes: i64) -> Document {
+        let ended_at = BsonDateTime::from_millis(started_at.timestamp_millis() + minutes * 60_000);
+        let entry = TimeEntry {
+            id: 1,
+            owner_id: 1,
+            workspace_id: None,
+            task_id: 7,
+            started_at,
+            ended_at: Some(ended_at),
+            duration_seconds: seconds_between(started_at, ended_at),
+            note: None,
+            manual: true,
+            created_at: started_at,
+            deleted: Some(false),
+        };
+        bson::to_document(&entry).unwrap()
+    }
+
+    fn stages(pipeline: &[Document]) -> Vec<&str> {
+        pipeline.iter().filter_map(|stage| stage.keys().next()).map(String::as_str).collect()
+    }
+
+    #[test]
+    fn durations_are_whole_seconds_and_never_negative() {
+        assert_eq!(seconds_between(at(19, 9, 0), at(19, 10, 30)), 5_400);
+        let almost_a_second = BsonDateTime::from_millis(at(19, 9, 0).timestamp_millis() + 999);
+        assert_eq!(seconds_between(at(19, 9, 0), almost_a_second), 0);
+        assert_eq!(seconds_between(at(19, 10, 0), at(19, 9, 0)), 0);
+    }
+
+    #[test]
+    fn entries_belong_to_the_vault_of_their_task() {
+        let entry: TimeEntry = bson::from_document(stopped_entry(at(19, 9, 0), 30)).unwrap();
+        assert_eq!(entry_vault(&entry), TaskVault::User(1));
+        let shared = TimeEntry {
+            workspace_id: Some(3),
+            ..entry
+        };
+        assert_eq!(entry_vault(&shared), TaskVault::Workspace(3));
+    }
+
+    #[test]
+    fn reports_cover_stopped_entries_started_in_the_period() {
+        let period = TimeEntryRepository::report_match(
+            TaskVault::User(1),
+            Some(at(19, 0, 0)),
+            Some(at(20, 0, 0)),
+        );
+        let period = period.get_document("$match").unwrap();
+        assert!(matches(period, &stopped_entry(at(19, 0, 0), 30)));
+        assert!(matches(period, &stopped_entry(at(19, 23, 45), 30)));
+        assert!(!matches(period, &stopped_entry(at(20, 0, 0), 30)));
+        assert!(!matches(period, &stopped_entry(at(18, 23, 59), 30)));
+
+        let mut running = stopped_entry(at(19, 9, 0), 30);
+        running.insert("endedAt", Bson::Null);
+        assert!(!matches(period, &running));
+        let other_vault = TimeEntryRepository::report_match(TaskVault::User(2), None, None);
+        let other_vault = other_vault.get_document("$match").unwrap();
+        assert!(!matches(other_vault, &stopped_entry(at(19, 9, 0), 30)));
+    }
+
+    #[test]
+    fn days_are_grouped_in_the_caller_s_time_zone() {
+        let matching = TimeEntryRepository::report_match(TaskVault::User(1), None, None);
+        let pipeline = TimeEntryRepository::report_pipeline(
+            TaskVault::User(1),
+            ETimeReportGroup::Day,
+            matching,
+            "Europe/Berlin",
+        );
+        assert_eq!(stages(&pipeline), ["$match", "$group", "$sort", "$project"]);
+        let key = pipeline[1].get_document("$group").unwrap().get_document("_id").unwrap();
+        let day = key.get_document("$dateToString").unwrap();
+        assert_eq!(day.get_str("timezone").unwrap(), "Europe/Berlin");
+        assert_eq!(day.get_str("format").unwrap(), "%Y-%m-%d");
+    }
+
+    #[test]
+    fn tags_keep_untagged_time() {
+        let matching = TimeEntryRepository::report_match(TaskVault::User(1), None, None);
+        let pipeline = TimeEntryRepository::report_pipeline(
+            TaskVault::User(1),
+            ETimeReportGroup::Tag,
+            matching,
+            "UTC",
+        );
+        assert_eq!(
+            stages(&pipeline),
+            ["$match", "$lookup", "$unwind", "$unwind", "$group", "$sort", "$project"]
+        );
+        for unwind in &pipeline[2..4] {
+            let unwind = unwind.get_document("$unwind").unwrap();
+            assert!(unwind.get_bool("preserveNullAndEmptyArrays").unwrap());
+        }
+        let group = pipeline[4].get_document("$group").unwrap();
+        assert_eq!(group.get_str("_id").unwrap(), "$task.tags");
+    }
+
+    #[test]
+    fn task_names_are_looked_up_in_the_same_vault() {
+        let vault = TaskVault::Workspace(3);
+        let matching = TimeEntryRepository::report_match(vault, None, None);
+        let pipeline =
+            TimeEntryRepository::report_pipeline(vault, ETimeReportGroup::Task, matching, "UTC");
+        let lookup = pipeline
+            .iter()
+            .find_map(|stage| stage.get_document("$lookup").ok())
+            .unwrap();
+        let task_match = lookup.get_array("pipeline").unwrap()[0]
+            .as_document()
+            .unwrap()
+            .get_document("$match")
+            .unwrap();
+        assert_eq!(task_match.get_i64("workspaceId").unwrap(), 3);
+    }
+}
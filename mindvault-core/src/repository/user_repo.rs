@@ -0,0 +1,131 @@
+use crate::models::{AppDatabase, DbCollection, DbError};
+use crate::repository::auto_increment::{get_next_id_for_collection, AUTO_INCREMENT_COLLECTION_NAME};
+use bson::{DateTime as BsonDateTime, Document};
+use futures_util::TryStreamExt;
+use mindvault_shared::models::users_model::User;
+use mindvault_shared::utils::date_input::EDateOrder;
+use mongodb::bson::doc;
+use mongodb::options::{FindOneAndUpdateOptions, ReturnDocument};
+use mongodb::{bson, Collection};
+use tracing::info;
+
+pub struct UserRepository {
+    collection: Collection<User>,
+    counters_collection: DbCollection<Document>,
+}
+
+pub const COLLECTION_NAME: &str = "users";
+
+impl UserRepository {
+    pub fn new(app_database: AppDatabase) -> Self {
+        let collection = app_database.collection::<User>(COLLECTION_NAME);
+        let counters_collection =
+            app_database.collection::<Document>(AUTO_INCREMENT_COLLECTION_NAME);
+        Self {
+            collection,
+            counters_collection,
+        }
+    }
+
+    /// Every user, disabled ones included, oldest first
+    pub async fn find_all(&self) -> Result<Vec<User>, DbError> {
+        self.collection
+            .find(doc! {})
+            .sort(doc! { "_id": 1 })
+            .await?
+            .try_collect()
+            .await
+            .map_err(Into::into)
+    }
+
+    pub async fn find_active(&self) -> Result<Vec<User>, DbError> {
+        self.collection
+            .find(doc! { "disabled": { "$ne": true } })
+            .sort(doc! { "_id": 1 })
+            .await?
+            .try_collect()
+            .await
+            .map_err(Into::into)
+    }
+
+    pub async fn find_by_id(&self, id: i64) -> Result<Option<User>, DbError> {
+        self.collection
+            .find_one(doc! { "_id": id })
+            .await
+            .map_err(Into::into)
+    }
+
+    pub async fn find_by_username(&self, username: &str) -> Result<Option<User>, DbError> {
+        self.collection
+            .find_one(doc! { "username": username.trim().to_lowercase() })
+            .await
+            .map_err(Into::into)
+    }
+
+    /// Creates a user; the caller normalizes the username and checks it is free first
+    pub async fn create_user(
+        &self,
+        username: &str,
+        display_name: Option<String>,
+    ) -> Result<User, DbError> {
+        let next_id = get_next_id_for_collection(&self.counters_collection, COLLECTION_NAME).await?;
+
+        let user = User {
+            id: next_id,
+            username: username.to_string(),
+            display_name,
+            disabled: false,
+            created_at: BsonDateTime::now(),
+            disabled_at: None,
+            time_zone: None,
+            date_order: None,
+        };
+
+        info!("Created user {} ({})", user.id, user.username);
+
+        match self.collection.insert_one(&user).await {
+            Ok(_) => Ok(user),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Disables or re-enables a user, returning it as it is now
+    pub async fn set_disabled(&self, id: i64, disabled: bool) -> Result<Option<User>, DbError> {
+        let disabled_at = disabled.then(BsonDateTime::now);
+        let update = doc! { "$set": { "disabled": disabled, "disabledAt": disabled_at } };
+        let options = FindOneAndUpdateOptions::builder()
+            .return_document(ReturnDocument::After)
+            .build();
+
+        self.collection
+            .find_one_and_update(doc! { "_id": id }, update)
+            .with_options(options)
+            .await
+            .map_err(Into::into)
+    }
+
+    /// Sets the given date preferences, leaving the others as they are
+    pub async fn update_preferences(
+        &self,
+        id: i64,
+        time_zone: Option<String>,
+        date_order: Option<EDateOrder>,
+    ) -> Result<Option<User>, DbError> {
+        let mut set_fields = doc! {};
+        if let Some(time_zone) = time_zone {
+            set_fields.insert("timeZone", time_zone);
+        }
+        if let Some(date_order) = date_order {
+            set_fields.insert("dateOrder", bson::to_bson(&date_order)?);
+        }
+        let options = FindOneAndUpdateOptions::builder()
+            .return_document(ReturnDocument::After)
+            .build();
+
+        self.collection
+            .find_one_and_update(doc! { "_id": id }, doc! { "$set": set_fields })
+            .with_options(options)
+            .await
+            .map_err(Into::into)
+    }
+}
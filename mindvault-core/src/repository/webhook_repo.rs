@@ -0,0 +1,483 @@
+use crate::models::{AppDatabase, DbCollection, DbError};
+use crate::repository::auto_increment::{
+    get_next_id_for_collection, get_next_id_range_for_collection, AUTO_INCREMENT_COLLECTION_NAME,
+};
+use bson::{DateTime as BsonDateTime, Document};
+use futures_util::TryStreamExt;
+use mindvault_shared::dtos::webhook_dtos::{
+    CreateWebhookRequest, UpdateWebhookRequest, WebhookDeliverySearchParams,
+};
+use mindvault_shared::models::notifications_model::{DeliveryAttempt, EDeliveryState};
+use mindvault_shared::models::webhooks_model::{ETaskEvent, WebhookDelivery, WebhookSubscription};
+use mongodb::bson::doc;
+use mongodb::change_stream::event::ResumeToken;
+use mongodb::options::{FindOneAndUpdateOptions, IndexOptions, ReturnDocument};
+use mongodb::{bson, Collection, IndexModel};
+use tracing::info;
+
+pub struct WebhookRepository {
+    subscriptions_collection: Collection<WebhookSubscription>,
+    deliveries_collection: Collection<WebhookDelivery>,
+    counters_collection: DbCollection<Document>,
+    relay_collection: DbCollection<Document>,
+}
+
+const SUBSCRIPTIONS_COLLECTION_NAME: &str = "webhook_subscriptions";
+const DELIVERIES_COLLECTION_NAME: &str = "webhook_deliveries";
+/// Counter for event ids; an event sent to several subscriptions keeps one id
+const EVENTS_COUNTER_NAME: &str = "webhook_events";
+const DEFAULT_DELIVERY_LIMIT: i64 = 100;
+/// Where the outbox relay keeps how far it has read the task change stream
+const RELAY_COLLECTION_NAME: &str = "webhook_relay";
+const RELAY_ID: &str = "tasks";
+
+/// An event rendered for one subscription, ready for the outbox
+pub struct NewWebhookDelivery {
+    pub subscription_id: i64,
+    pub event_id: i64,
+    pub event: ETaskEvent,
+    pub task_id: i64,
+    pub payload: String,
+    /// Change stream event the delivery was queued from; a subscription gets one delivery per
+    /// change however often the change is read
+    pub change_id: Option<String>,
+}
+
+impl WebhookRepository {
+    pub fn new(app_database: AppDatabase) -> Self {
+        let subscriptions_collection =
+            app_database.collection::<WebhookSubscription>(SUBSCRIPTIONS_COLLECTION_NAME);
+        let deliveries_collection =
+            app_database.collection::<WebhookDelivery>(DELIVERIES_COLLECTION_NAME);
+        let counters_collection =
+            app_database.collection::<Document>(AUTO_INCREMENT_COLLECTION_NAME);
+        let relay_collection = app_database.collection::<Document>(RELAY_COLLECTION_NAME);
+        Self {
+            subscriptions_collection,
+            deliveries_collection,
+            counters_collection,
+            relay_collection,
+        }
+    }
+
+    fn return_after() -> FindOneAndUpdateOptions {
+        FindOneAndUpdateOptions::builder()
+            .return_document(ReturnDocument::After)
+            .build()
+    }
+
+    pub async fn find_subscriptions(
+        &self,
+        owner_id: i64,
+    ) -> Result<Vec<WebhookSubscription>, DbError> {
+        let query = doc! {
+            "ownerId": owner_id,
+            "$or": [
+                { "deleted": { "$ne": true } },
+                { "deleted": { "$exists": false } }
+            ]
+        };
+
+        self.subscriptions_collection
+            .find(query)
+            .sort(doc! { "_id": 1 })
+            .await?
+            .try_collect()
+            .await
+            .map_err(Into::into)
+    }
+
+    /// Enabled subscriptions of every user; events only go to the ones whose owner is in the
+    /// task's audience, see `WebhookSubscription::receives`
+    pub async fn find_active_subscriptions(&self) -> Result<Vec<WebhookSubscription>, DbError> {
+        let query = doc! {
+            "enabled": true,
+            "$or": [
+                { "deleted": { "$ne": true } },
+                { "deleted": { "$exists": false } }
+            ]
+        };
+
+        self.subscriptions_collection
+            .find(query)
+            .await?
+            .try_collect()
+            .await
+            .map_err(Into::into)
+    }
+
+    pub async fn find_subscription_by_id(
+        &self,
+        owner_id: i64,
+        id: i64,
+    ) -> Result<Option<WebhookSubscription>, DbError> {
+        let query = doc! {
+            "_id": id,
+            "ownerId": owner_id,
+            "$or": [
+                { "deleted": { "$ne": true } },
+                { "deleted": { "$exists": false } }
+            ]
+        };
+        self.subscriptions_collection
+            .find_one(query)
+            .await
+            .map_err(Into::into)
+    }
+
+    /// The subscription a queued delivery goes to, whoever owns it
+    pub async fn find_subscription_for_delivery(
+        &self,
+        id: i64,
+    ) -> Result<Option<WebhookSubscription>, DbError> {
+        let query = doc! {
+            "_id": id,
+            "$or": [
+                { "deleted": { "$ne": true } },
+                { "deleted": { "$exists": false } }
+            ]
+        };
+        self.subscriptions_collection
+            .find_one(query)
+            .await
+            .map_err(Into::into)
+    }
+
+    /// Ids of the owner's subscriptions, deleted ones included so their delivery log stays
+    /// theirs
+    async fn subscription_ids(&self, owner_id: i64) -> Result<Vec<i64>, DbError> {
+        let ids = self
+            .subscriptions_collection
+            .distinct("_id", doc! { "ownerId": owner_id })
+            .await?;
+        Ok(ids.iter().filter_map(|id| id.as_i64()).collect())
+    }
+
+    pub async fn create_subscription(
+        &self,
+        owner_id: i64,
+        request: CreateWebhookRequest,
+    ) -> Result<WebhookSubscription, DbError> {
+        let next_id =
+            get_next_id_for_collection(&self.counters_collection, SUBSCRIPTIONS_COLLECTION_NAME)
+                .await?;
+
+        let subscription = WebhookSubscription {
+            id: next_id,
+            owner_id,
+            url: request.url,
+            secret: request.secret.filter(|secret| !secret.is_empty()),
+            events: request.events,
+            description: request.description,
+            enabled: request.enabled.unwrap_or(true),
+            created_at: BsonDateTime::now(),
+            deleted: Some(false),
+        };
+
+        info!("Creating webhook subscription {} for {}", subscription.id, subscription.url);
+
+        match self.subscriptions_collection.insert_one(&subscription).await {
+            Ok(_) => Ok(subscription),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Update a subscription by ID with partial updates; an empty secret removes signing
+    pub async fn update_subscription_by_id(
+        &self,
+        owner_id: i64,
+        id: i64,
+        request: UpdateWebhookRequest,
+    ) -> Result<Option<WebhookSubscription>, DbError> {
+        let filter = doc! {
+            "_id": id,
+            "ownerId": owner_id,
+            "$or": [
+                { "deleted": { "$ne": true } },
+                { "deleted": { "$exists": false } }
+            ]
+        };
+
+        let mut set_fields = doc! {};
+        if let Some(url) = request.url {
+            set_fields.insert("url", url);
+        }
+        if let Some(secret) = request.secret {
+            let secret = Some(secret).filter(|secret| !secret.is_empty());
+            set_fields.insert("secret", secret);
+        }
+        if let Some(events) = request.events {
+            set_fields.insert("events", bson::to_bson(&events)?);
+        }
+        if let Some(description) = request.description {
+            set_fields.insert("description", description);
+        }
+        if let Some(enabled) = request.enabled {
+            set_fields.insert("enabled", enabled);
+        }
+
+        self.subscriptions_collection
+            .find_one_and_update(filter, doc! { "$set": set_fields })
+            .with_options(Self::return_after())
+            .await
+            .map_err(Into::into)
+    }
+
+    /// Softly delete a subscription by setting deleted: true
+    pub async fn soft_delete_subscription_by_id(
+        &self,
+        owner_id: i64,
+        id: i64,
+    ) -> Result<bool, DbError> {
+        let filter = doc! {
+            "_id": id,
+            "ownerId": owner_id,
+            "$or": [
+                { "deleted": { "$ne": true } },
+                { "deleted": { "$exists": false } }
+            ]
+        };
+        let update = doc! { "$set": { "deleted": true, "enabled": false } };
+
+        let result = self
+            .subscriptions_collection
+            .update_one(filter, update)
+            .await?;
+        Ok(result.modified_count > 0)
+    }
+
+    /// Reserves `count` consecutive event ids and returns the first
+    pub async fn reserve_event_ids(&self, count: i64) -> Result<i64, DbError> {
+        get_next_id_range_for_collection(&self.counters_collection, EVENTS_COUNTER_NAME, count)
+            .await
+            .map_err(Into::into)
+    }
+
+    /// Keeps a change from being queued twice for the same subscription
+    pub async fn ensure_change_index(&self) -> Result<(), DbError> {
+        self.deliveries_collection
+            .create_index(
+                IndexModel::builder()
+                    .keys(doc! { "subscriptionId": 1, "changeId": 1 })
+                    .options(
+                        IndexOptions::builder()
+                            .unique(true)
+                            .partial_filter_expression(doc! { "changeId": { "$type": "string" } })
+                            .build(),
+                    )
+                    .build(),
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Writes deliveries to the outbox; they are sent by the delivery job. The insert is
+    /// unordered, so a delivery rejected as a duplicate does not hold back the others.
+    pub async fn enqueue_deliveries(
+        &self,
+        new_deliveries: Vec<NewWebhookDelivery>,
+    ) -> Result<Vec<WebhookDelivery>, DbError> {
+        if new_deliveries.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let start_id = get_next_id_range_for_collection(
+            &self.counters_collection,
+            DELIVERIES_COLLECTION_NAME,
+            new_deliveries.len() as i64,
+        )
+        .await?;
+        let now = BsonDateTime::now();
+        let deliveries: Vec<WebhookDelivery> = new_deliveries
+            .into_iter()
+            .enumerate()
+            .map(|(index, delivery)| WebhookDelivery {
+                id: start_id + index as i64,
+                subscription_id: delivery.subscription_id,
+                event_id: delivery.event_id,
+                event: delivery.event,
+                task_id: delivery.task_id,
+                payload: delivery.payload,
+                state: EDeliveryState::Pending,
+                attempts: Vec::new(),
+                next_attempt_at: Some(now),
+                delivered_at: None,
+                created_at: now,
+                change_id: delivery.change_id,
+            })
+            .collect();
+
+        match self
+            .deliveries_collection
+            .insert_many(&deliveries)
+            .ordered(false)
+            .await
+        {
+            Ok(_) => Ok(deliveries),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Where the outbox relay stopped reading the task change stream
+    pub async fn find_relay_resume_token(&self) -> Result<Option<ResumeToken>, DbError> {
+        let Some(relay) = self.relay_collection.find_one(doc! { "_id": RELAY_ID }).await? else {
+            return Ok(None);
+        };
+        match relay.get("resumeToken") {
+            Some(token) => Ok(Some(bson::from_bson(token.clone())?)),
+            None => Ok(None),
+        }
+    }
+
+    pub async fn save_relay_resume_token(&self, token: &ResumeToken) -> Result<(), DbError> {
+        self.relay_collection
+            .update_one(
+                doc! { "_id": RELAY_ID },
+                doc! { "$set": { "resumeToken": bson::to_bson(token)? } },
+            )
+            .upsert(true)
+            .await?;
+        Ok(())
+    }
+
+    /// Pending deliveries whose next attempt is due, oldest first so events keep their order
+    pub async fn find_ready_deliveries(
+        &self,
+        now: BsonDateTime,
+        limit: i64,
+    ) -> Result<Vec<WebhookDelivery>, DbError> {
+        let query = doc! {
+            "state": bson::to_bson(&EDeliveryState::Pending)?,
+            "nextAttemptAt": { "$lte": now },
+        };
+
+        self.deliveries_collection
+            .find(query)
+            .sort(doc! { "_id": 1 })
+            .limit(limit)
+            .await?
+            .try_collect()
+            .await
+            .map_err(Into::into)
+    }
+
+    /// A delivery to one of the owner's subscriptions
+    pub async fn find_delivery_by_id(
+        &self,
+        owner_id: i64,
+        id: i64,
+    ) -> Result<Option<WebhookDelivery>, DbError> {
+        let subscription_ids = self.subscription_ids(owner_id).await?;
+        self.deliveries_collection
+            .find_one(doc! { "_id": id, "subscriptionId": { "$in": subscription_ids } })
+            .await
+            .map_err(Into::into)
+    }
+
+    /// Delivery log of the owner's subscriptions, newest first
+    pub async fn search_deliveries(
+        &self,
+        owner_id: i64,
+        params: WebhookDeliverySearchParams,
+    ) -> Result<Vec<WebhookDelivery>, DbError> {
+        let subscription_ids: Vec<i64> = self
+            .subscription_ids(owner_id)
+            .await?
+            .into_iter()
+            .filter(|id| params.subscription_id.is_none_or(|wanted| wanted == *id))
+            .collect();
+        let mut query = doc! { "subscriptionId": { "$in": subscription_ids } };
+        if let Some(state) = params.state {
+            query.insert("state", bson::to_bson(&state)?);
+        }
+        if let Some(event) = params.event {
+            query.insert("event", bson::to_bson(&event)?);
+        }
+        if let Some(task_id) = params.task_id {
+            query.insert("taskId", task_id);
+        }
+
+        self.deliveries_collection
+            .find(query)
+            .sort(doc! { "_id": -1 })
+            .limit(params.limit.unwrap_or(DEFAULT_DELIVERY_LIMIT))
+            .await?
+            .try_collect()
+            .await
+            .map_err(Into::into)
+    }
+
+    /// Appends an attempt to the delivery log and moves the delivery to its next state
+    pub async fn record_attempt(
+        &self,
+        id: i64,
+        attempt: DeliveryAttempt,
+        state: EDeliveryState,
+        next_attempt_at: Option<BsonDateTime>,
+    ) -> Result<Option<WebhookDelivery>, DbError> {
+        let mut set_fields = doc! {
+            "state": bson::to_bson(&state)?,
+            "nextAttemptAt": next_attempt_at,
+        };
+        if state == EDeliveryState::Delivered {
+            set_fields.insert("deliveredAt", attempt.attempted_at);
+        }
+        let update = doc! {
+            "$set": set_fields,
+            "$push": { "attempts": bson::to_bson(&attempt)? },
+        };
+
+        self.deliveries_collection
+            .find_one_and_update(doc! { "_id": id }, update)
+            .with_options(Self::return_after())
+            .await
+            .map_err(Into::into)
+    }
+
+    /// Puts an undelivered delivery to one of the owner's subscriptions back in the queue for
+    /// an immediate attempt
+    pub async fn requeue_delivery(
+        &self,
+        owner_id: i64,
+        id: i64,
+    ) -> Result<Option<WebhookDelivery>, DbError> {
+        let filter = doc! {
+            "_id": id,
+            "subscriptionId": { "$in": self.subscription_ids(owner_id).await? },
+            "state": { "$ne": bson::to_bson(&EDeliveryState::Delivered)? },
+        };
+        let update = doc! { "$set": {
+            "state": bson::to_bson(&EDeliveryState::Pending)?,
+            "nextAttemptAt": BsonDateTime::now(),
+        } };
+
+        self.deliveries_collection
+            .find_one_and_update(filter, update)
+            .with_options(Self::return_after())
+            .await
+            .map_err(Into::into)
+    }
+
+    /// Requeues every dead-lettered delivery of one of the owner's subscriptions and returns
+    /// how many
+    pub async fn requeue_dead_letters(
+        &self,
+        owner_id: i64,
+        subscription_id: i64,
+    ) -> Result<u64, DbError> {
+        if !self.subscription_ids(owner_id).await?.contains(&subscription_id) {
+            return Ok(0);
+        }
+        let filter = doc! {
+            "subscriptionId": subscription_id,
+            "state": bson::to_bson(&EDeliveryState::DeadLetter)?,
+        };
+        let update = doc! { "$set": {
+            "state": bson::to_bson(&EDeliveryState::Pending)?,
+            "nextAttemptAt": BsonDateTime::now(),
+        } };
+
+        let result = self.deliveries_collection.update_many(filter, update).await?;
+        Ok(result.modified_count)
+    }
+}
@@ -0,0 +1,60 @@
+use crate::models::{AppDatabase, DbError};
+use crate::repository::task_repo::TaskVault;
+use bson::DateTime as BsonDateTime;
+use mindvault_shared::dtos::workflow_dtos::SetWorkflowRequest;
+use mindvault_shared::models::workflows_model::Workflow;
+use mongodb::bson::doc;
+use mongodb::Collection;
+use tracing::info;
+
+/// At most one workflow per vault, found by the same scope as the vault's tasks
+pub struct WorkflowRepository {
+    collection: Collection<Workflow>,
+}
+
+const COLLECTION_NAME: &str = "workflows";
+
+impl WorkflowRepository {
+    pub fn new(app_database: AppDatabase) -> Self {
+        let collection = app_database.collection::<Workflow>(COLLECTION_NAME);
+        Self { collection }
+    }
+
+    pub async fn find_by_vault(&self, vault: TaskVault) -> Result<Option<Workflow>, DbError> {
+        self.collection
+            .find_one(vault.scope(doc! {}))
+            .await
+            .map_err(Into::into)
+    }
+
+    /// Creates or replaces the vault's workflow; `set_by` is the owner for private vaults and
+    /// the acting member for workspaces
+    pub async fn set_workflow(
+        &self,
+        vault: TaskVault,
+        set_by: i64,
+        request: SetWorkflowRequest,
+    ) -> Result<Workflow, DbError> {
+        let workflow = Workflow {
+            owner_id: set_by,
+            workspace_id: vault.workspace_id(),
+            statuses: request.statuses,
+            transitions: request.transitions,
+            updated_at: BsonDateTime::now(),
+        };
+
+        info!("{:?}", workflow);
+
+        self.collection
+            .replace_one(vault.scope(doc! {}), &workflow)
+            .upsert(true)
+            .await?;
+        Ok(workflow)
+    }
+
+    /// Removes the vault's workflow, returning whether there was one
+    pub async fn delete_workflow(&self, vault: TaskVault) -> Result<bool, DbError> {
+        let result = self.collection.delete_one(vault.scope(doc! {})).await?;
+        Ok(result.deleted_count > 0)
+    }
+}
@@ -0,0 +1,204 @@
+use crate::models::{AppDatabase, DbCollection, DbError};
+use crate::repository::auto_increment::{get_next_id_for_collection, AUTO_INCREMENT_COLLECTION_NAME};
+use bson::{DateTime as BsonDateTime, Document};
+use futures_util::TryStreamExt;
+use mindvault_shared::dtos::workspace_dtos::{CreateWorkspaceRequest, UpdateWorkspaceRequest};
+use mindvault_shared::models::workspaces_model::{EWorkspaceRole, Workspace, WorkspaceMember};
+use mongodb::bson::doc;
+use mongodb::options::{FindOneAndUpdateOptions, ReturnDocument};
+use mongodb::{bson, Collection};
+use tracing::info;
+
+pub struct WorkspaceRepository {
+    collection: Collection<Workspace>,
+    counters_collection: DbCollection<Document>,
+}
+
+const COLLECTION_NAME: &str = "workspaces";
+
+impl WorkspaceRepository {
+    pub fn new(app_database: AppDatabase) -> Self {
+        let collection = app_database.collection::<Workspace>(COLLECTION_NAME);
+        let counters_collection =
+            app_database.collection::<Document>(AUTO_INCREMENT_COLLECTION_NAME);
+        Self {
+            collection,
+            counters_collection,
+        }
+    }
+
+    /// Workspaces `user_id` is a member of, by name
+    pub async fn find_for_member(&self, user_id: i64) -> Result<Vec<Workspace>, DbError> {
+        let query = doc! {
+            "members.userId": user_id,
+            "$or": [
+                { "deleted": { "$ne": true } },
+                { "deleted": { "$exists": false } }
+            ]
+        };
+
+        self.collection
+            .find(query)
+            .sort(doc! { "name": 1 })
+            .await?
+            .try_collect()
+            .await
+            .map_err(Into::into)
+    }
+
+    pub async fn find_by_id(&self, id: i64) -> Result<Option<Workspace>, DbError> {
+        let query = doc! {
+            "_id": id,
+            "$or": [
+                { "deleted": { "$ne": true } },
+                { "deleted": { "$exists": false } }
+            ]
+        };
+        self.collection.find_one(query).await.map_err(Into::into)
+    }
+
+    /// Creates a workspace with `created_by` as its only member and admin
+    pub async fn create_workspace(
+        &self,
+        created_by: i64,
+        request: CreateWorkspaceRequest,
+    ) -> Result<Workspace, DbError> {
+        let next_id = get_next_id_for_collection(&self.counters_collection, COLLECTION_NAME).await?;
+        let created_at = BsonDateTime::now();
+
+        let workspace = Workspace {
+            id: next_id,
+            name: request.name,
+            description: request.description,
+            members: vec![WorkspaceMember {
+                user_id: created_by,
+                role: EWorkspaceRole::Admin,
+                added_at: created_at,
+            }],
+            created_by,
+            created_at,
+            updated_at: Some(created_at),
+            deleted: Some(false),
+        };
+
+        info!("{:?}", workspace);
+
+        match self.collection.insert_one(&workspace).await {
+            Ok(_) => Ok(workspace),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn after_update() -> FindOneAndUpdateOptions {
+        FindOneAndUpdateOptions::builder()
+            .return_document(ReturnDocument::After)
+            .build()
+    }
+
+    /// Update a workspace's name or description
+    pub async fn update_workspace_by_id(
+        &self,
+        id: i64,
+        request: UpdateWorkspaceRequest,
+    ) -> Result<Option<Workspace>, DbError> {
+        let filter = doc! {
+            "_id": id,
+            "$or": [
+                { "deleted": { "$ne": true } },
+                { "deleted": { "$exists": false } }
+            ]
+        };
+
+        let mut set_fields = doc! { "updatedAt": BsonDateTime::now() };
+        if let Some(name) = request.name {
+            set_fields.insert("name", name);
+        }
+        if let Some(description) = request.description {
+            set_fields.insert("description", description);
+        }
+
+        self.collection
+            .find_one_and_update(filter, doc! { "$set": set_fields })
+            .with_options(Self::after_update())
+            .await
+            .map_err(Into::into)
+    }
+
+    /// Softly delete a workspace by setting deleted: true; its tasks stay in the database
+    pub async fn soft_delete_by_id(&self, id: i64) -> Result<bool, DbError> {
+        let filter = doc! {
+            "_id": id,
+            "$or": [
+                { "deleted": { "$ne": true } },
+                { "deleted": { "$exists": false } }
+            ]
+        };
+        let update = doc! { "$set": { "deleted": true, "updatedAt": BsonDateTime::now() } };
+
+        let result = self.collection.update_one(filter, update).await?;
+        Ok(result.modified_count > 0)
+    }
+
+    /// Adds `user_id` as a member, or changes their role if they already are one
+    pub async fn upsert_member(
+        &self,
+        id: i64,
+        user_id: i64,
+        role: EWorkspaceRole,
+    ) -> Result<Option<Workspace>, DbError> {
+        let now = BsonDateTime::now();
+        let updated = self
+            .collection
+            .find_one_and_update(
+                doc! { "_id": id, "members.userId": user_id },
+                doc! { "$set": { "members.$.role": bson::to_bson(&role)?, "updatedAt": now } },
+            )
+            .with_options(Self::after_update())
+            .await?;
+        if updated.is_some() {
+            return Ok(updated);
+        }
+
+        let member = WorkspaceMember {
+            user_id,
+            role,
+            added_at: now,
+        };
+        self.collection
+            .find_one_and_update(
+                doc! {
+                    "_id": id,
+                    "members.userId": { "$ne": user_id },
+                    "$or": [
+                        { "deleted": { "$ne": true } },
+                        { "deleted": { "$exists": false } }
+                    ]
+                },
+                doc! {
+                    "$push": { "members": bson::to_bson(&member)? },
+                    "$set": { "updatedAt": now }
+                },
+            )
+            .with_options(Self::after_update())
+            .await
+            .map_err(Into::into)
+    }
+
+    pub async fn remove_member(
+        &self,
+        id: i64,
+        user_id: i64,
+    ) -> Result<Option<Workspace>, DbError> {
+        self.collection
+            .find_one_and_update(
+                doc! { "_id": id, "members.userId": user_id },
+                doc! {
+                    "$pull": { "members": { "userId": user_id } },
+                    "$set": { "updatedAt": BsonDateTime::now() }
+                },
+            )
+            .with_options(Self::after_update())
+            .await
+            .map_err(Into::into)
+    }
+}
@@ -0,0 +1,50 @@
+use std::env;
+
+const JOBS_ENABLED_VAR: &str = "MINDVAULT_JOBS_ENABLED";
+const TICK_SECONDS_VAR: &str = "MINDVAULT_SCHEDULER_TICK_SECONDS";
+const DEFAULT_TICK_SECONDS: u64 = 15;
+
+/// Scheduler settings read from the environment.
+///
+/// `MINDVAULT_JOBS_ENABLED=false` turns the scheduler off entirely, and each job can be
+/// toggled with `MINDVAULT_JOB_<NAME>_ENABLED` (e.g. `MINDVAULT_JOB_REFRESH_REMINDERS_ENABLED=false`).
+pub struct SchedulerConfig {
+    pub enabled: bool,
+    pub tick_seconds: u64,
+}
+
+fn parse_flag(value: &str) -> Option<bool> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "1" | "true" | "yes" | "on" => Some(true),
+        "0" | "false" | "no" | "off" => Some(false),
+        _ => None,
+    }
+}
+
+impl SchedulerConfig {
+    pub fn from_env() -> Self {
+        let enabled = env::var(JOBS_ENABLED_VAR)
+            .ok()
+            .and_then(|value| parse_flag(&value))
+            .unwrap_or(true);
+        let tick_seconds = env::var(TICK_SECONDS_VAR)
+            .ok()
+            .and_then(|value| value.trim().parse().ok())
+            .filter(|seconds| *seconds > 0)
+            .unwrap_or(DEFAULT_TICK_SECONDS);
+        SchedulerConfig {
+            enabled,
+            tick_seconds,
+        }
+    }
+
+    /// Whether a job should run on this instance, falling back to `default` when unset
+    pub fn job_enabled(&self, job_name: &str, default: bool) -> bool {
+        let var = format!("MINDVAULT_JOB_{}_ENABLED", job_name.to_ascii_uppercase());
+        self.enabled
+            && env::var(var)
+                .ok()
+                .and_then(|value| parse_flag(&value))
+                .unwrap_or(default)
+    }
+}
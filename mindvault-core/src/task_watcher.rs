@@ -0,0 +1,125 @@
+use crate::models::{AppDatabase, DbError};
+use futures_util::StreamExt;
+use mindvault_shared::models::tasks_model::Task;
+use mongodb::change_stream::event::{ChangeStreamEvent, OperationType};
+use mongodb::change_stream::ChangeStream;
+use mongodb::bson::doc;
+use mongodb::options::{FullDocumentBeforeChangeType, FullDocumentType};
+
+pub use mongodb::change_stream::event::ResumeToken;
+
+const COLLECTION_NAME: &str = "tasks";
+
+/// A change to the tasks collection as seen by a change stream. Hard deletes are skipped:
+/// their document key says nothing about the task's owner or number.
+pub enum ETaskStreamChange {
+    Inserted(Task),
+    /// The task after the change, with the top-level fields that changed. `before` is only
+    /// read by streams opened with `open_with_before`.
+    Updated {
+        task: Task,
+        before: Option<Box<Task>>,
+        changed_fields: Vec<String>,
+    },
+}
+
+/// Change stream over the tasks collection, including writes made by other processes such
+/// as the MCP server. Opening fails on a standalone MongoDB, which has no change streams.
+pub struct TaskChangeStream {
+    stream: ChangeStream<ChangeStreamEvent<Task>>,
+    change_id: Option<String>,
+}
+
+impl TaskChangeStream {
+    pub async fn open(
+        app_database: &AppDatabase,
+        resume_after: Option<ResumeToken>,
+    ) -> Result<Self, DbError> {
+        let stream = app_database
+            .collection::<Task>(COLLECTION_NAME)
+            .watch()
+            .full_document(FullDocumentType::UpdateLookup)
+            .resume_after(resume_after)
+            .await?;
+        Ok(Self {
+            stream,
+            change_id: None,
+        })
+    }
+
+    /// Like `open`, but updates also carry the task as it was before. Turns on pre-images for
+    /// the tasks collection first, which needs MongoDB 6.0 or later.
+    pub async fn open_with_before(
+        app_database: &AppDatabase,
+        resume_after: Option<ResumeToken>,
+    ) -> Result<Self, DbError> {
+        app_database
+            .run_command(doc! {
+                "collMod": COLLECTION_NAME,
+                "changeStreamPreAndPostImages": { "enabled": true },
+            })
+            .await?;
+        let stream = app_database
+            .collection::<Task>(COLLECTION_NAME)
+            .watch()
+            .full_document(FullDocumentType::UpdateLookup)
+            .full_document_before_change(FullDocumentBeforeChangeType::WhenAvailable)
+            .resume_after(resume_after)
+            .await?;
+        Ok(Self {
+            stream,
+            change_id: None,
+        })
+    }
+
+    /// Token to reopen the stream after the last change returned
+    pub fn resume_token(&self) -> Option<ResumeToken> {
+        self.stream.resume_token()
+    }
+
+    /// Identifies the change last returned by `next`; every stream that sees a change gives
+    /// it the same id
+    pub fn change_id(&self) -> Option<&str> {
+        self.change_id.as_deref()
+    }
+
+    /// Waits for the next task change; `None` when the stream was invalidated
+    pub async fn next(&mut self) -> Option<Result<ETaskStreamChange, DbError>> {
+        loop {
+            let event = match self.stream.next().await? {
+                Ok(event) => event,
+                Err(e) => return Some(Err(e.into())),
+            };
+            self.change_id = bson::to_bson(&event.id).ok().map(|id| id.to_string());
+            let change = match event.operation_type {
+                OperationType::Insert => event.full_document.map(ETaskStreamChange::Inserted),
+                OperationType::Update | OperationType::Replace => {
+                    let changed_fields = event
+                        .update_description
+                        .map(|description| {
+                            description
+                                .updated_fields
+                                .keys()
+                                .chain(description.removed_fields.iter())
+                                .map(|field| field.split('.').next().unwrap_or(field).to_string())
+                                .collect()
+                        })
+                        .unwrap_or_default();
+                    let before = event.full_document_before_change.map(Box::new);
+                    event
+                        .full_document
+                        .map(|task| ETaskStreamChange::Updated {
+                            task,
+                            before,
+                            changed_fields,
+                        })
+                }
+                OperationType::Invalidate => return None,
+                _ => None,
+            };
+            if let Some(change) = change {
+                return Some(Ok(change));
+            }
+        }
+    }
+}
@@ -0,0 +1,299 @@
+use crate::transfer::ImportRow;
+use bson::DateTime as BsonDateTime;
+use chrono::{DateTime, NaiveDate, NaiveDateTime};
+use mindvault_shared::dtos::task_dtos::CreateTaskRequest;
+use mindvault_shared::models::tasks_model::{ETaskPriority, ETaskStatus, Task};
+use mindvault_shared::utils::date_input::DateInput;
+use serde::{Deserialize, Serialize};
+
+pub const EXTERNAL_ID_PREFIX: &str = "taskwarrior:";
+const DATE_FORMAT: &str = "%Y%m%dT%H%M%SZ";
+
+#[derive(Debug, Serialize, Deserialize)]
+struct TaskwarriorAnnotation {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    entry: Option<String>,
+    description: String,
+}
+
+/// The fields of a `task export` record MindVault reads
+#[derive(Debug, Deserialize)]
+struct TaskwarriorTask {
+    uuid: Option<String>,
+    description: Option<String>,
+    status: Option<String>,
+    priority: Option<String>,
+    due: Option<String>,
+    start: Option<String>,
+    project: Option<String>,
+    recur: Option<String>,
+    #[serde(default)]
+    tags: Vec<String>,
+    #[serde(default)]
+    annotations: Vec<TaskwarriorAnnotation>,
+}
+
+#[derive(Debug, Serialize)]
+struct TaskwarriorExport {
+    uuid: String,
+    description: String,
+    status: &'static str,
+    entry: String,
+    modified: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    due: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    start: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    end: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    priority: Option<&'static str>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    tags: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    annotations: Vec<TaskwarriorAnnotation>,
+}
+
+/// Renders a task as a `task import`-compatible JSON object
+pub fn render_task(task: &Task) -> Result<String, String> {
+    let modified = format_date(task.last_touched_at());
+    let status = match task.status {
+        ETaskStatus::Completed => "completed",
+        // Taskwarrior keeps deleted tasks around, which is the closest it has to won't-do
+        ETaskStatus::Cancelled => "deleted",
+        ETaskStatus::NotStarted | ETaskStatus::Pending | ETaskStatus::InProgress => "pending",
+    };
+    let export = TaskwarriorExport {
+        uuid: task_uuid(task),
+        description: task.name.clone(),
+        status,
+        entry: format_date(task.created_at),
+        modified: modified.clone(),
+        due: task.due_date.map(format_date),
+        start: (task.status == ETaskStatus::InProgress).then(|| modified.clone()),
+        end: task
+            .completed_at
+            .filter(|_| task.status == ETaskStatus::Completed)
+            .or_else(|| (task.status == ETaskStatus::Cancelled).then_some(task.last_touched_at()))
+            .map(format_date),
+        priority: match task.priority {
+            ETaskPriority::Urgent | ETaskPriority::High => Some("H"),
+            ETaskPriority::Normal => None,
+            ETaskPriority::Low => Some("L"),
+        },
+        tags: task.tags.clone(),
+        annotations: task
+            .notes
+            .iter()
+            .flat_map(|notes| notes.lines())
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| TaskwarriorAnnotation {
+                entry: Some(modified.clone()),
+                description: line.to_string(),
+            })
+            .collect(),
+    };
+    serde_json::to_string(&export).map_err(|e| e.to_string())
+}
+
+/// The Taskwarrior UUID a task was imported with, or a fixed one derived from its id
+fn task_uuid(task: &Task) -> String {
+    task.external_id
+        .as_deref()
+        .and_then(|external_id| external_id.strip_prefix(EXTERNAL_ID_PREFIX))
+        .map(str::to_string)
+        .unwrap_or_else(|| format!("00000000-0000-4000-8000-{:012x}", task.id))
+}
+
+fn format_date(at: BsonDateTime) -> String {
+    at.to_chrono().format(DATE_FORMAT).to_string()
+}
+
+fn parse_date(value: &str) -> Result<NaiveDate, String> {
+    NaiveDateTime::parse_from_str(value, DATE_FORMAT)
+        .map(|at| at.date())
+        .or_else(|_| DateTime::parse_from_rfc3339(value).map(|at| at.date_naive()))
+        .map_err(|_| format!("invalid Taskwarrior date '{}'", value))
+}
+
+/// Accepts the JSON array written by `task export`, or the one-object-per-line output of
+/// older Taskwarrior versions
+pub fn parse_taskwarrior(body: &str) -> Result<Vec<ImportRow>, String> {
+    if body.trim_start().starts_with('[') {
+        let items: Vec<serde_json::Value> =
+            serde_json::from_str(body).map_err(|e| format!("Invalid JSON: {}", e))?;
+        return Ok(items
+            .iter()
+            .enumerate()
+            .map(|(index, item)| ImportRow {
+                row: index + 1,
+                task: TaskwarriorTask::deserialize(item)
+                    .map_err(|e| e.to_string())
+                    .and_then(build_task),
+            })
+            .collect());
+    }
+
+    Ok(body
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| ImportRow {
+            row: index + 1,
+            task: serde_json::from_str::<TaskwarriorTask>(line.trim().trim_end_matches(','))
+                .map_err(|e| e.to_string())
+                .and_then(build_task),
+        })
+        .collect())
+}
+
+fn build_task(task: TaskwarriorTask) -> Result<CreateTaskRequest, String> {
+    let name = task
+        .description
+        .map(|description| description.trim().to_string())
+        .filter(|description| !description.is_empty())
+        .ok_or_else(|| "Task has no description".to_string())?;
+
+    let status = match task.status.as_deref().unwrap_or("pending") {
+        "pending" if task.start.is_some() => ETaskStatus::InProgress,
+        "pending" => ETaskStatus::NotStarted,
+        "waiting" => ETaskStatus::Pending,
+        "completed" => ETaskStatus::Completed,
+        "deleted" => return Err("Deleted in Taskwarrior; not imported".to_string()),
+        "recurring" => {
+            return Err("Recurring template; its pending instances are imported".to_string());
+        }
+        other => return Err(format!("Unknown Taskwarrior status '{}'", other)),
+    };
+    let priority = match task.priority.as_deref() {
+        Some("H") => Some(ETaskPriority::High),
+        Some("M") => Some(ETaskPriority::Normal),
+        Some("L") => Some(ETaskPriority::Low),
+        Some("") | None => None,
+        Some(other) => return Err(format!("Unknown Taskwarrior priority '{}'", other)),
+    };
+
+    let mut tags = task.tags;
+    if let Some(project) = task.project.filter(|project| !project.is_empty()) {
+        tags.push(project);
+    }
+    let notes: Vec<String> = task
+        .annotations
+        .into_iter()
+        .map(|annotation| annotation.description)
+        .collect();
+
+    Ok(CreateTaskRequest {
+        name,
+        due_date: task.due.as_deref().map(parse_date).transpose()?.map(DateInput::Day),
+        priority,
+        status: Some(status),
+        workflow_status: None,
+        tags,
+        recurrence: task.recur.as_deref().and_then(recurrence_rule),
+        estimate_minutes: None,
+        notes: (!notes.is_empty()).then(|| notes.join("\n")),
+        external_id: task
+            .uuid
+            .map(|uuid| format!("{}{}", EXTERNAL_ID_PREFIX, uuid)),
+        assignee_id: None,
+        reportee_ids: Vec::new(),
+    })
+}
+
+/// Maps the common Taskwarrior `recur` periods onto RRULEs; others are dropped
+fn recurrence_rule(recur: &str) -> Option<String> {
+    let rule = match recur.trim().to_ascii_lowercase().as_str() {
+        "daily" | "day" | "1d" => "FREQ=DAILY",
+        "weekdays" => "FREQ=WEEKLY;BYDAY=MO,TU,WE,TH,FR",
+        "weekly" | "week" | "1w" => "FREQ=WEEKLY",
+        "biweekly" | "fortnight" | "2w" => "FREQ=WEEKLY;INTERVAL=2",
+        "monthly" | "month" | "1mo" => "FREQ=MONTHLY",
+        "quarterly" | "3mo" => "FREQ=MONTHLY;INTERVAL=3",
+        "yearly" | "annual" | "1y" => "FREQ=YEARLY",
+        _ => return None,
+    };
+    Some(rule.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_taskwarrior, render_task};
+    use crate::transfer::tests::{day, due_on, task};
+    use mindvault_shared::models::tasks_model::{ETaskPriority, ETaskStatus};
+
+    #[test]
+    fn a_taskwarrior_export_reads_back() {
+        let mut started = task(12, "Call \"the\" plumber");
+        started.priority = ETaskPriority::High;
+        started.status = ETaskStatus::InProgress;
+        started.tags = vec!["home".to_string()];
+        started.notes = Some("Ask about the boiler\n\nBring keys".to_string());
+        due_on(&mut started, 2026, 10, 20);
+        let mut waiting = task(13, "Renew passport");
+        waiting.priority = ETaskPriority::Low;
+        waiting.status = ETaskStatus::Pending;
+        waiting.external_id =
+            Some("taskwarrior:2a5b8c1e-3f4d-4e6a-9b7c-0d1e2f3a4b5c".to_string());
+        let mut done = task(14, "File taxes");
+        done.status = ETaskStatus::Completed;
+        done.completed_at = Some(done.created_at);
+
+        // The one-object-per-line form older versions write
+        let body = [&started, &waiting, &done]
+            .into_iter()
+            .map(|task| render_task(task).unwrap())
+            .collect::<Vec<_>>()
+            .join("\n");
+        let rows = parse_taskwarrior(&body).unwrap();
+        let tasks: Vec<_> = rows.into_iter().map(|row| row.task.unwrap()).collect();
+
+        assert_eq!(tasks[0].name, started.name);
+        assert_eq!(tasks[0].priority, Some(ETaskPriority::High));
+        assert_eq!(tasks[0].status, Some(ETaskStatus::InProgress));
+        assert_eq!(tasks[0].tags, started.tags);
+        assert_eq!(tasks[0].due_date, day(2026, 10, 20));
+        assert_eq!(tasks[0].notes.as_deref(), Some("Ask about the boiler\nBring keys"));
+        assert_eq!(
+            tasks[0].external_id.as_deref(),
+            Some("taskwarrior:00000000-0000-4000-8000-00000000000c")
+        );
+        assert_eq!(tasks[1].priority, Some(ETaskPriority::Low));
+        assert_eq!(tasks[1].status, Some(ETaskStatus::NotStarted));
+        assert_eq!(tasks[1].external_id, waiting.external_id);
+        assert_eq!(tasks[2].priority, None);
+        assert_eq!(tasks[2].status, Some(ETaskStatus::Completed));
+    }
+
+    #[test]
+    fn a_malformed_record_fails_only_its_own_row() {
+        let body = [
+            r#"{"uuid":"a","description":"Good","due":"20261020T000000Z"}"#,
+            r#"{"uuid":"b","description":"Bad due","due":"tomorrow"}"#,
+            r#"{"uuid":"c","description":"Bad status","status":"someday"}"#,
+            r#"{"uuid":"d","description":"Bad priority","priority":"X"}"#,
+            r#"{"uuid":"e","description":"Gone","status":"deleted"}"#,
+            r#"{"uuid":"f","status":"pending"}"#,
+            r#"{"uuid":"g","description": }"#,
+        ]
+        .join("\n");
+        let rows = parse_taskwarrior(&body).unwrap();
+        let rows: Vec<_> = rows.iter().map(|row| (row.row, row.task.as_ref())).collect();
+        assert_eq!(rows.len(), 7);
+        assert_eq!(rows[0].1.unwrap().due_date, day(2026, 10, 20));
+        assert_eq!(rows[1].1.unwrap_err(), "invalid Taskwarrior date 'tomorrow'");
+        assert_eq!(rows[2].1.unwrap_err(), "Unknown Taskwarrior status 'someday'");
+        assert_eq!(rows[3].1.unwrap_err(), "Unknown Taskwarrior priority 'X'");
+        assert_eq!(rows[4].1.unwrap_err(), "Deleted in Taskwarrior; not imported");
+        assert_eq!(rows[5].1.unwrap_err(), "Task has no description");
+        assert_eq!(rows[6].0, 7);
+        assert!(rows[6].1.is_err());
+
+        // In the array form a bad item fails alone, but broken JSON fails the file
+        let rows = parse_taskwarrior(r#"[{"description":"Good"}, {"description":7}]"#).unwrap();
+        assert!(rows[0].task.is_ok());
+        assert_eq!(rows[1].row, 2);
+        assert!(rows[1].task.is_err());
+        assert!(parse_taskwarrior(r#"[{"description":"Good"},"#).is_err());
+    }
+}
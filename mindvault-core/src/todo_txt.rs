@@ -0,0 +1,273 @@
+use crate::transfer::ImportRow;
+use chrono::NaiveDate;
+use mindvault_shared::dtos::task_dtos::CreateTaskRequest;
+use mindvault_shared::models::tasks_model::{ETaskPriority, ETaskStatus, Task};
+use mindvault_shared::utils::date_input::DateInput;
+
+pub const EXTERNAL_ID_PREFIX: &str = "todotxt:";
+
+/// Renders a task as one todo.txt line, e.g.
+/// `(A) 2025-07-01 Call plumber +home @phone due:2025-07-27 id:mv-12`
+pub fn render_line(task: &Task) -> String {
+    let mut parts: Vec<String> = Vec::new();
+    // todo.txt only knows done lines; cancelled ones are kept apart by a `status:` key
+    let completed = task.status.category().is_closed();
+    let letter = priority_letter(&task.priority);
+    if completed {
+        parts.push("x".to_string());
+        // A creation date may only follow a completion date
+        if let Some(completed_at) = task.completed_at {
+            parts.push(format_date(completed_at.to_chrono().date_naive()));
+            parts.push(format_date(task.created_at.to_chrono().date_naive()));
+        }
+    } else {
+        if let Some(letter) = letter {
+            parts.push(format!("({})", letter));
+        }
+        parts.push(format_date(task.created_at.to_chrono().date_naive()));
+    }
+    parts.push(task.name.split_whitespace().collect::<Vec<_>>().join(" "));
+    for tag in &task.tags {
+        let tag = tag.split_whitespace().collect::<Vec<_>>().join("-");
+        if tag.starts_with('@') {
+            parts.push(tag);
+        } else {
+            parts.push(format!("+{}", tag));
+        }
+    }
+    if completed && let Some(letter) = letter {
+        // Completed lines lose their leading priority, so keep it as a key
+        parts.push(format!("pri:{}", letter));
+    }
+    if task.status == ETaskStatus::Cancelled {
+        parts.push("status:cancelled".to_string());
+    }
+    if let Some(due_date) = task.due_date {
+        parts.push(format!("due:{}", format_date(due_date.to_chrono().date_naive())));
+    }
+    parts.push(format!("id:{}", line_id(task)));
+    parts.join(" ") + "\n"
+}
+
+/// The todo.txt id a task was imported with, or one derived from its MindVault id
+fn line_id(task: &Task) -> String {
+    task.external_id
+        .as_deref()
+        .and_then(|external_id| external_id.strip_prefix(EXTERNAL_ID_PREFIX))
+        .map(str::to_string)
+        .unwrap_or_else(|| format!("mv-{}", task.id))
+}
+
+fn format_date(date: NaiveDate) -> String {
+    date.format("%Y-%m-%d").to_string()
+}
+
+fn parse_date(token: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(token, "%Y-%m-%d").ok()
+}
+
+/// Normal tasks carry no priority letter
+fn priority_letter(priority: &ETaskPriority) -> Option<&'static str> {
+    match priority {
+        ETaskPriority::Urgent => Some("A"),
+        ETaskPriority::High => Some("B"),
+        ETaskPriority::Normal => None,
+        ETaskPriority::Low => Some("C"),
+    }
+}
+
+/// `A` is the most urgent todo.txt priority and `B` the next; everything from `C` down is Low
+fn parse_priority_letter(letter: &str) -> Option<ETaskPriority> {
+    match letter {
+        "A" => Some(ETaskPriority::Urgent),
+        "B" => Some(ETaskPriority::High),
+        _ if letter.len() == 1 && letter.chars().all(|c| c.is_ascii_uppercase()) => {
+            Some(ETaskPriority::Low)
+        }
+        _ => None,
+    }
+}
+
+/// Reads one task per non-blank line. Lines without an `id:` key are identified by their
+/// text, so re-importing an unchanged file updates the same tasks.
+pub fn parse_todo_txt(body: &str) -> Vec<ImportRow> {
+    body.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| ImportRow {
+            row: index + 1,
+            task: parse_line(line),
+        })
+        .collect()
+}
+
+fn parse_line(line: &str) -> Result<CreateTaskRequest, String> {
+    let tokens: Vec<&str> = line.split_whitespace().collect();
+    let mut position = 0;
+    let mut completed = false;
+    let mut priority = None;
+
+    if tokens.first() == Some(&"x") {
+        completed = true;
+        position = 1;
+        // Completion date, then optionally the creation date
+        while position < 3 && tokens.get(position).and_then(|t| parse_date(t)).is_some() {
+            position += 1;
+        }
+    } else {
+        if let Some(letter) = tokens
+            .first()
+            .and_then(|token| token.strip_prefix('('))
+            .and_then(|token| token.strip_suffix(')'))
+        {
+            priority = parse_priority_letter(letter);
+            if priority.is_some() {
+                position = 1;
+            }
+        }
+        if tokens.get(position).and_then(|t| parse_date(t)).is_some() {
+            position += 1;
+        }
+    }
+
+    let mut words = Vec::new();
+    let mut tags = Vec::new();
+    let mut due_date = None;
+    let mut line_id = None;
+    let mut status = completed.then_some(ETaskStatus::Completed);
+    for token in &tokens[position..] {
+        if let Some(project) = token.strip_prefix('+').filter(|p| !p.is_empty()) {
+            tags.push(project.to_string());
+        } else if token.len() > 1 && token.starts_with('@') {
+            tags.push(token.to_string());
+        } else if let Some(value) = token.strip_prefix("due:") {
+            due_date = Some(DateInput::parse(value)?);
+        } else if let Some(value) = token.strip_prefix("id:").filter(|v| !v.is_empty()) {
+            line_id = Some(value.to_string());
+        } else if let Some(value) = token.strip_prefix("status:").filter(|v| !v.is_empty()) {
+            status = Some(value.parse::<ETaskStatus>()?);
+        } else if let Some(letter) = token.strip_prefix("pri:") {
+            priority = Some(
+                parse_priority_letter(letter)
+                    .ok_or_else(|| format!("invalid todo.txt priority '{}'", letter))?,
+            );
+        } else {
+            words.push(*token);
+        }
+    }
+
+    let name = words.join(" ");
+    if name.is_empty() {
+        return Err("Line has no task description".to_string());
+    }
+    let line_id = line_id.unwrap_or_else(|| format!("{:016x}", fnv1a(&name.to_lowercase())));
+    Ok(CreateTaskRequest {
+        name,
+        due_date,
+        priority,
+        status,
+        workflow_status: None,
+        tags,
+        recurrence: None,
+        estimate_minutes: None,
+        notes: None,
+        external_id: Some(format!("{}{}", EXTERNAL_ID_PREFIX, line_id)),
+        assignee_id: None,
+        reportee_ids: Vec::new(),
+    })
+}
+
+/// Stable 64-bit FNV-1a, used to identify lines that carry no `id:`
+fn fnv1a(text: &str) -> u64 {
+    text.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_todo_txt, render_line};
+    use crate::transfer::tests::{day, due_on, task};
+    use bson::DateTime as BsonDateTime;
+    use chrono::{TimeZone, Utc};
+    use mindvault_shared::models::tasks_model::{ETaskPriority, ETaskStatus};
+    use mindvault_shared::utils::date_input::DateInput;
+
+    #[test]
+    fn a_todo_txt_export_reads_back() {
+        let mut open = task(12, "Call  plumber");
+        open.priority = ETaskPriority::Urgent;
+        open.tags = vec!["home".to_string(), "@phone".to_string()];
+        due_on(&mut open, 2026, 10, 20);
+        let mut done = task(13, "File taxes");
+        done.priority = ETaskPriority::High;
+        done.status = ETaskStatus::Completed;
+        done.completed_at =
+            Some(BsonDateTime::from(Utc.with_ymd_and_hms(2026, 10, 5, 9, 0, 0).unwrap()));
+        done.external_id = Some("todotxt:taxes".to_string());
+        let mut cancelled = task(14, "Book flights");
+        cancelled.priority = ETaskPriority::Low;
+        cancelled.status = ETaskStatus::Cancelled;
+
+        let body: String = [&open, &done, &cancelled].into_iter().map(render_line).collect();
+        assert_eq!(
+            body.lines().collect::<Vec<_>>(),
+            [
+                "(A) 2026-10-01 Call plumber +home @phone due:2026-10-20 id:mv-12",
+                "x 2026-10-05 2026-10-01 File taxes pri:B id:taxes",
+                "x Book flights pri:C status:cancelled id:mv-14",
+            ]
+        );
+
+        let rows = parse_todo_txt(&body);
+        let tasks: Vec<_> = rows.into_iter().map(|row| row.task.unwrap()).collect();
+        assert_eq!(tasks[0].name, "Call plumber");
+        assert_eq!(tasks[0].priority, Some(ETaskPriority::Urgent));
+        assert_eq!(tasks[0].status, None);
+        assert_eq!(tasks[0].tags, open.tags);
+        assert_eq!(tasks[0].due_date, day(2026, 10, 20));
+        assert_eq!(tasks[0].external_id.as_deref(), Some("todotxt:mv-12"));
+        assert_eq!(tasks[1].name, "File taxes");
+        assert_eq!(tasks[1].priority, Some(ETaskPriority::High));
+        assert_eq!(tasks[1].status, Some(ETaskStatus::Completed));
+        assert_eq!(tasks[1].external_id, done.external_id);
+        assert_eq!(tasks[2].name, "Book flights");
+        assert_eq!(tasks[2].priority, Some(ETaskPriority::Low));
+        assert_eq!(tasks[2].status, Some(ETaskStatus::Cancelled));
+    }
+
+    #[test]
+    fn lines_without_an_id_keep_the_same_id_on_reimport() {
+        let first = parse_todo_txt("Water the plants +home\n").remove(0).task.unwrap();
+        let again = parse_todo_txt("\nwater the PLANTS +garden\n").remove(0).task.unwrap();
+        assert!(first.external_id.is_some());
+        assert_eq!(first.external_id, again.external_id);
+    }
+
+    #[test]
+    fn a_malformed_line_fails_only_its_own_row() {
+        let body = "Good line due:2026-10-20\n\
+                    +home @phone\n\
+                    \n\
+                    Bad due due:2026-13-45\n\
+                    Bad priority pri:a\n\
+                    Bad status status:someday\n\
+                    Ambiguous due due:03/04/2026\n";
+        let rows = parse_todo_txt(body);
+        let rows: Vec<_> = rows.iter().map(|row| (row.row, row.task.as_ref())).collect();
+        assert_eq!(rows.len(), 6);
+        assert_eq!(rows[0].0, 1);
+        assert_eq!(rows[0].1.unwrap().due_date, day(2026, 10, 20));
+        assert_eq!(rows[1].0, 2);
+        assert_eq!(rows[1].1.unwrap_err(), "Line has no task description");
+        assert_eq!(rows[2].0, 4);
+        assert!(rows[2].1.unwrap_err().starts_with("Invalid date '2026-13-45'"));
+        assert_eq!(rows[3].0, 5);
+        assert_eq!(rows[3].1.unwrap_err(), "invalid todo.txt priority 'a'");
+        assert_eq!(rows[4].0, 6);
+        assert!(rows[4].1.unwrap_err().starts_with("invalid status 'someday'"));
+        // Read with the importing user's date order later on
+        assert_eq!(rows[5].0, 7);
+        assert!(matches!(rows[5].1.unwrap().due_date, Some(DateInput::Ambiguous { .. })));
+    }
+}
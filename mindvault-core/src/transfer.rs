@@ -0,0 +1,419 @@
+use crate::calendar::{calendar_footer, calendar_header, parse_calendar, render_todo};
+use crate::taskwarrior::{parse_taskwarrior, render_task};
+use crate::todo_txt::{parse_todo_txt, render_line};
+use mindvault_shared::dtos::task_dtos::CreateTaskRequest;
+use mindvault_shared::dtos::transfer_dtos::ImportColumnMapping;
+use mindvault_shared::models::tasks_model::{ETaskPriority, ETaskStatus, Task};
+use mindvault_shared::models::transfer_model::{ETransferFormat, TaskExportRecord};
+use mindvault_shared::utils::date_input::DateInput;
+use serde::Deserialize;
+
+const CSV_HEADERS: [&str; 7] = [
+    "id",
+    "name",
+    "priority",
+    "status",
+    "dueDate",
+    "createdAt",
+    "completedAt",
+];
+const DEFAULT_NAME_COLUMNS: [&str; 3] = ["name", "title", "task"];
+const DEFAULT_DUE_DATE_COLUMNS: [&str; 2] = ["duedate", "due"];
+const DEFAULT_PRIORITY_COLUMNS: [&str; 1] = ["priority"];
+const DEFAULT_STATUS_COLUMNS: [&str; 1] = ["status"];
+
+/// One row of an upload, either a task ready for `bulk_create_tasks` or why it was rejected
+pub struct ImportRow {
+    pub row: usize,
+    pub task: Result<CreateTaskRequest, String>,
+}
+
+/// Renders an export piece by piece so tasks can be streamed straight from the cursor
+pub struct TaskExportWriter {
+    format: ETransferFormat,
+}
+
+impl TaskExportWriter {
+    pub fn new(format: ETransferFormat) -> Self {
+        Self { format }
+    }
+
+    pub fn header(&self) -> Result<String, String> {
+        match self.format {
+            ETransferFormat::Csv => write_csv(|writer| writer.write_record(CSV_HEADERS)),
+            ETransferFormat::Json | ETransferFormat::Taskwarrior => Ok("[".to_string()),
+            ETransferFormat::Md => Ok("# Tasks\n\n".to_string()),
+            ETransferFormat::Ics => Ok(calendar_header()),
+            ETransferFormat::TodoTxt => Ok(String::new()),
+        }
+    }
+
+    /// Renders the task at position `index` (from 0) of the export
+    pub fn record(&self, index: usize, task: &Task) -> Result<String, String> {
+        let record = TaskExportRecord::from(task);
+        let separator = if index == 0 { "\n  " } else { ",\n  " };
+        match self.format {
+            ETransferFormat::Csv => write_csv(|writer| writer.serialize(&record)),
+            ETransferFormat::Json => {
+                let json = serde_json::to_string(&record).map_err(|e| e.to_string())?;
+                Ok(format!("{}{}", separator, json))
+            }
+            ETransferFormat::Md => Ok(render_checklist_item(&record)),
+            ETransferFormat::Ics => Ok(render_todo(task)),
+            ETransferFormat::TodoTxt => Ok(render_line(task)),
+            ETransferFormat::Taskwarrior => Ok(format!("{}{}", separator, render_task(task)?)),
+        }
+    }
+
+    pub fn footer(&self) -> String {
+        match self.format {
+            ETransferFormat::Json | ETransferFormat::Taskwarrior => "\n]\n".to_string(),
+            ETransferFormat::Ics => calendar_footer(),
+            ETransferFormat::Csv | ETransferFormat::Md | ETransferFormat::TodoTxt => String::new(),
+        }
+    }
+}
+
+fn write_csv(
+    write: impl FnOnce(&mut csv::Writer<Vec<u8>>) -> Result<(), csv::Error>,
+) -> Result<String, String> {
+    let mut writer = csv::WriterBuilder::new()
+        .has_headers(false)
+        .from_writer(Vec::new());
+    write(&mut writer).map_err(|e| e.to_string())?;
+    let bytes = writer.into_inner().map_err(|e| e.to_string())?;
+    String::from_utf8(bytes).map_err(|e| e.to_string())
+}
+
+fn render_checklist_item(record: &TaskExportRecord) -> String {
+    let checkbox = if record.status.category().is_closed() {
+        "[x]"
+    } else {
+        "[ ]"
+    };
+    let mut details = Vec::new();
+    if let Some(due_date) = &record.due_date {
+        details.push(format!("due: {}", due_date));
+    }
+    details.push(format!("priority: {:?}", record.priority));
+    details.push(format!("status: {:?}", record.status));
+    format!("- {} {} ({})\n", checkbox, record.name, details.join(", "))
+}
+
+/// Splits an upload into rows. Problems with a single row are reported on that row; only an
+/// unreadable file or an unmappable CSV header fails the whole import.
+pub fn parse_import(
+    format: ETransferFormat,
+    body: &str,
+    mapping: &ImportColumnMapping,
+) -> Result<Vec<ImportRow>, String> {
+    match format {
+        ETransferFormat::Csv => parse_csv(body, mapping),
+        ETransferFormat::Json => parse_json(body),
+        ETransferFormat::Md => Ok(parse_checklist(body)),
+        ETransferFormat::Ics => parse_calendar(body),
+        ETransferFormat::TodoTxt => Ok(parse_todo_txt(body)),
+        ETransferFormat::Taskwarrior => parse_taskwarrior(body),
+    }
+}
+
+fn normalize_header(header: &str) -> String {
+    header
+        .chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Position of the mapped column, or of the first default header present
+fn find_column(
+    headers: &[String],
+    mapped: Option<&String>,
+    defaults: &[&str],
+) -> Result<Option<usize>, String> {
+    if let Some(mapped) = mapped {
+        let wanted = normalize_header(mapped);
+        return headers
+            .iter()
+            .position(|header| *header == wanted)
+            .map(Some)
+            .ok_or_else(|| format!("CSV has no column named '{}'", mapped));
+    }
+    Ok(defaults
+        .iter()
+        .find_map(|default| headers.iter().position(|header| header == default)))
+}
+
+fn parse_csv(body: &str, mapping: &ImportColumnMapping) -> Result<Vec<ImportRow>, String> {
+    let mut reader = csv::ReaderBuilder::new()
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_reader(body.as_bytes());
+    let headers: Vec<String> = reader
+        .headers()
+        .map_err(|e| format!("Unable to read CSV header: {}", e))?
+        .iter()
+        .map(normalize_header)
+        .collect();
+
+    let name_column = find_column(&headers, mapping.name.as_ref(), &DEFAULT_NAME_COLUMNS)?
+        .ok_or_else(|| "CSV has no name column; map one with name_column".to_string())?;
+    let due_date_column =
+        find_column(&headers, mapping.due_date.as_ref(), &DEFAULT_DUE_DATE_COLUMNS)?;
+    let priority_column =
+        find_column(&headers, mapping.priority.as_ref(), &DEFAULT_PRIORITY_COLUMNS)?;
+    let status_column = find_column(&headers, mapping.status.as_ref(), &DEFAULT_STATUS_COLUMNS)?;
+
+    let mut rows = Vec::new();
+    for (index, record) in reader.records().enumerate() {
+        // Header is line 1, so data starts on line 2 unless the reader knows better
+        let fallback_line = index + 2;
+        let record = match record {
+            Ok(record) => record,
+            Err(e) => {
+                let row = e
+                    .position()
+                    .map(|position| position.line() as usize)
+                    .unwrap_or(fallback_line);
+                rows.push(ImportRow {
+                    row,
+                    task: Err(e.to_string()),
+                });
+                continue;
+            }
+        };
+        let row = record
+            .position()
+            .map(|position| position.line() as usize)
+            .unwrap_or(fallback_line);
+        let cell = |column: Option<usize>| {
+            column
+                .and_then(|column| record.get(column))
+                .filter(|value| !value.is_empty())
+        };
+        if record.iter().all(str::is_empty) {
+            continue;
+        }
+        rows.push(ImportRow {
+            row,
+            task: build_task(
+                cell(Some(name_column)),
+                cell(due_date_column),
+                cell(priority_column),
+                cell(status_column),
+            ),
+        });
+    }
+    Ok(rows)
+}
+
+fn build_task(
+    name: Option<&str>,
+    due_date: Option<&str>,
+    priority: Option<&str>,
+    status: Option<&str>,
+) -> Result<CreateTaskRequest, String> {
+    let name = name
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .ok_or_else(|| "Name is required".to_string())?;
+    Ok(CreateTaskRequest {
+        name: name.to_string(),
+        due_date: due_date.map(DateInput::parse).transpose()?,
+        priority: priority.map(str::parse::<ETaskPriority>).transpose()?,
+        status: status.map(str::parse::<ETaskStatus>).transpose()?,
+        workflow_status: None,
+        tags: Vec::new(),
+        recurrence: None,
+        estimate_minutes: None,
+        notes: None,
+        external_id: None,
+        assignee_id: None,
+        reportee_ids: Vec::new(),
+    })
+}
+
+/// Accepts a bare array of tasks or the `{"tasks": [...]}` body used by bulk create
+fn parse_json(body: &str) -> Result<Vec<ImportRow>, String> {
+    let value: serde_json::Value =
+        serde_json::from_str(body).map_err(|e| format!("Invalid JSON: {}", e))?;
+    let items = match value {
+        serde_json::Value::Array(items) => items,
+        serde_json::Value::Object(mut object) => match object.remove("tasks") {
+            Some(serde_json::Value::Array(items)) => items,
+            _ => return Err("Expected a JSON array of tasks".to_string()),
+        },
+        _ => return Err("Expected a JSON array of tasks".to_string()),
+    };
+
+    Ok(items
+        .iter()
+        .enumerate()
+        .map(|(index, item)| ImportRow {
+            row: index + 1,
+            task: CreateTaskRequest::deserialize(item)
+                .map_err(|e| e.to_string())
+                .and_then(|task| {
+                    if task.name.trim().is_empty() {
+                        Err("Name is required".to_string())
+                    } else {
+                        Ok(task)
+                    }
+                }),
+        })
+        .collect())
+}
+
+/// Reads `- [ ] name (due: 2025-07-27, priority: High)` lines; anything else is ignored
+fn parse_checklist(body: &str) -> Vec<ImportRow> {
+    body.lines()
+        .enumerate()
+        .filter_map(|(index, line)| {
+            let item = line
+                .trim_start()
+                .strip_prefix("- ")
+                .or_else(|| line.trim_start().strip_prefix("* "))?
+                .trim_start();
+            let (done, text) = if let Some(text) = item.strip_prefix("[ ]") {
+                (false, text)
+            } else if let Some(text) = item
+                .strip_prefix("[x]")
+                .or_else(|| item.strip_prefix("[X]"))
+            {
+                (true, text)
+            } else {
+                return None;
+            };
+            Some(ImportRow {
+                row: index + 1,
+                task: parse_checklist_item(done, text.trim()),
+            })
+        })
+        .collect()
+}
+
+fn parse_checklist_item(done: bool, text: &str) -> Result<CreateTaskRequest, String> {
+    let (name, details) = split_checklist_details(text);
+    let mut due_date = None;
+    let mut priority = None;
+    let mut status = None;
+    for (key, value) in details {
+        match key.as_str() {
+            "due" => due_date = Some(value),
+            "priority" => priority = Some(value),
+            "status" => status = Some(value),
+            _ => {}
+        }
+    }
+    let mut task = build_task(Some(name), due_date, priority, status)?;
+    // A ticked box is Completed unless the details say it was cancelled
+    if done && !task.status.as_ref().is_some_and(|status| status.category().is_closed()) {
+        task.status = Some(ETaskStatus::Completed);
+    }
+    Ok(task)
+}
+
+/// Separates a trailing `(key: value, ...)` group from the task name. Parentheses that are
+/// not made of known keys stay part of the name.
+fn split_checklist_details(text: &str) -> (&str, Vec<(String, &str)>) {
+    let Some(open) = text.rfind('(') else {
+        return (text, Vec::new());
+    };
+    let Some(inner) = text[open + 1..].strip_suffix(')') else {
+        return (text, Vec::new());
+    };
+    let mut details = Vec::new();
+    for part in inner.split(',') {
+        let Some((key, value)) = part.split_once(':') else {
+            return (text, Vec::new());
+        };
+        let key = key.trim().to_ascii_lowercase();
+        if !["due", "priority", "status"].contains(&key.as_str()) {
+            return (text, Vec::new());
+        }
+        details.push((key, value.trim()));
+    }
+    (text[..open].trim_end(), details)
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::{parse_import, TaskExportWriter};
+    use bson::DateTime as BsonDateTime;
+    use chrono::{NaiveDate, TimeZone, Utc};
+    use mindvault_shared::dtos::transfer_dtos::ImportColumnMapping;
+    use mindvault_shared::models::tasks_model::{ETaskPriority, ETaskStatus, Task};
+    use mindvault_shared::models::transfer_model::ETransferFormat;
+    use mindvault_shared::utils::date_input::{floating_day, DateInput};
+
+    /// An open task created on 1 October 2026 with nothing else set
+    pub(crate) fn task(id: i64, name: &str) -> Task {
+        Task {
+            id,
+            owner_id: 1,
+            workspace_id: None,
+            assignee_id: None,
+            reportee_ids: Vec::new(),
+            name: name.to_string(),
+            priority: ETaskPriority::Normal,
+            status: ETaskStatus::NotStarted,
+            workflow_status: None,
+            rank: None,
+            due_date: None,
+            due_has_time: false,
+            created_at: BsonDateTime::from(Utc.with_ymd_and_hms(2026, 10, 1, 9, 0, 0).unwrap()),
+            updated_at: None,
+            completed_at: None,
+            tags: Vec::new(),
+            recurrence: None,
+            estimate_minutes: None,
+            tracked_seconds: 0,
+            notes: None,
+            notes_index: Vec::new(),
+            external_id: None,
+            deleted: Some(false),
+        }
+    }
+
+    pub(crate) fn due_on(task: &mut Task, year: i32, month: u32, day: u32) {
+        task.due_date = Some(floating_day(NaiveDate::from_ymd_opt(year, month, day).unwrap()));
+        task.due_has_time = false;
+    }
+
+    pub(crate) fn day(year: i32, month: u32, day: u32) -> Option<DateInput> {
+        NaiveDate::from_ymd_opt(year, month, day).map(DateInput::Day)
+    }
+
+    fn export(format: ETransferFormat, tasks: &[Task]) -> String {
+        let writer = TaskExportWriter::new(format);
+        let mut out = writer.header().unwrap();
+        for (index, task) in tasks.iter().enumerate() {
+            out.push_str(&writer.record(index, task).unwrap());
+        }
+        out.push_str(&writer.footer());
+        out
+    }
+
+    #[test]
+    fn exports_read_back_through_the_import() {
+        let mut first = task(12, "Call plumber");
+        first.priority = ETaskPriority::High;
+        first.tags = vec!["home".to_string()];
+        due_on(&mut first, 2026, 10, 20);
+        let second = task(13, "Renew passport");
+
+        for format in [ETransferFormat::Ics, ETransferFormat::TodoTxt, ETransferFormat::Taskwarrior]
+        {
+            let body = export(format, &[first.clone(), second.clone()]);
+            let rows = parse_import(format, &body, &ImportColumnMapping::default()).unwrap();
+            let tasks: Vec<_> = rows.into_iter().map(|row| row.task.unwrap()).collect();
+            assert_eq!(tasks.len(), 2, "{:?}: {}", format, body);
+            assert_eq!(tasks[0].name, "Call plumber", "{:?}", format);
+            assert_eq!(tasks[0].due_date, day(2026, 10, 20), "{:?}", format);
+            assert_eq!(tasks[0].priority, Some(ETaskPriority::High), "{:?}", format);
+            assert_eq!(tasks[0].tags, vec!["home"], "{:?}", format);
+            assert_eq!(tasks[1].name, "Renew passport", "{:?}", format);
+            assert_eq!(tasks[1].due_date, None, "{:?}", format);
+        }
+    }
+}
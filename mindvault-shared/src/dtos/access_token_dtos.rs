@@ -0,0 +1,11 @@
+use crate::models::access_tokens_model::ETokenScope;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateAccessTokenRequest {
+    pub name: String,
+    pub scopes: Vec<ETokenScope>,
+    /// Defaults to the user of the token making the request
+    pub user_id: Option<i64>,
+}
@@ -0,0 +1,12 @@
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct CalendarParams {
+    /// Saved filter whose criteria select the tasks
+    pub filter_id: Option<i64>,
+    pub tag: Option<String>,
+    /// Also emit an all-day VEVENT per task for clients that hide to-dos
+    #[serde(default)]
+    pub include_events: bool,
+}
@@ -0,0 +1,11 @@
+use crate::models::digest_model::{EDigestFormat, EDigestPeriod};
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct DigestParams {
+    pub period: Option<EDigestPeriod>,
+    pub format: Option<EDigestFormat>,
+    pub time_zone: Option<String>,
+    pub stalled_days: Option<i64>,
+}
@@ -0,0 +1,18 @@
+use crate::models::tasks_model::{ETaskPriority, ETaskStatus};
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct EventStreamParams {
+    /// Comma-separated entity types, e.g. `task`
+    pub entity: Option<String>,
+    /// Comma-separated event names, e.g. `task.created,task.completed`
+    pub events: Option<String>,
+    pub filter_id: Option<i64>,
+    pub query: Option<String>,
+    pub status: Option<ETaskStatus>,
+    pub priority: Option<ETaskPriority>,
+    pub tag: Option<String>,
+    /// Alternative to the `Last-Event-ID` header, e.g. for WebSocket clients
+    pub last_event_id: Option<String>,
+}
@@ -0,0 +1,48 @@
+use crate::models::feedback_model::EFeedbackSentiment;
+use crate::utils::date_input::DateInput;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateFeedbackRequest {
+    pub reportee_id: i64,
+    pub note: String,
+    pub sentiment: Option<EFeedbackSentiment>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub observed_on: Option<DateInput>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateFeedbackRequest {
+    pub note: Option<String>,
+    pub sentiment: Option<EFeedbackSentiment>,
+    pub tags: Option<Vec<String>>,
+    #[serde(default)]
+    pub observed_on: Option<DateInput>,
+}
+
+/// Filters for listing feedback; `from` and `to` are inclusive days on `observedOn`
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct FeedbackSearchParams {
+    pub reportee_id: Option<i64>,
+    pub sentiment: Option<EFeedbackSentiment>,
+    pub tag: Option<String>,
+    #[serde(default)]
+    pub from: Option<DateInput>,
+    #[serde(default)]
+    pub to: Option<DateInput>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct FeedbackSummaryParams {
+    pub reportee_id: i64,
+    #[serde(default)]
+    pub from: Option<DateInput>,
+    #[serde(default)]
+    pub to: Option<DateInput>,
+}
@@ -0,0 +1,62 @@
+use crate::models::goals_model::EProgressSource;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct GoalSearchParams {
+    pub owner_reportee_id: Option<i64>,
+    pub period: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateKeyResultRequest {
+    pub title: String,
+    pub start_value: Option<f64>,
+    pub target_value: f64,
+    pub unit: Option<String>,
+    pub progress_source: Option<EProgressSource>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateGoalRequest {
+    pub objective: String,
+    pub description: Option<String>,
+    pub owner_reportee_id: Option<i64>,
+    pub period: String,
+    #[serde(default)]
+    pub key_results: Vec<CreateKeyResultRequest>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateGoalRequest {
+    pub objective: Option<String>,
+    pub description: Option<String>,
+    pub owner_reportee_id: Option<i64>,
+    pub period: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateKeyResultRequest {
+    pub title: Option<String>,
+    pub start_value: Option<f64>,
+    pub target_value: Option<f64>,
+    pub unit: Option<String>,
+    pub progress_source: Option<EProgressSource>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyResultCheckInRequest {
+    pub value: f64,
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LinkTasksRequest {
+    pub task_ids: Vec<i64>,
+}
@@ -1 +1,19 @@
-pub mod task_dtos;
\ No newline at end of file
+pub mod task_dtos;
+pub mod reportee_dtos;
+pub mod review_dtos;
+pub mod feedback_dtos;
+pub mod review_template_dtos;
+pub mod goal_dtos;
+pub mod reminder_dtos;
+pub mod notification_dtos;
+pub mod digest_dtos;
+pub mod webhook_dtos;
+pub mod saved_filter_dtos;
+pub mod event_dtos;
+pub mod transfer_dtos;
+pub mod calendar_dtos;
+pub mod access_token_dtos;
+pub mod user_dtos;
+pub mod workspace_dtos;
+pub mod workflow_dtos;
+pub mod time_entry_dtos;
@@ -0,0 +1,30 @@
+use crate::models::notifications_model::{EChannelTarget, EDeliveryState, ENotificationType};
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateChannelRequest {
+    pub name: String,
+    pub target: EChannelTarget,
+    #[serde(default)]
+    pub notification_types: Vec<ENotificationType>,
+    pub enabled: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateChannelRequest {
+    pub name: Option<String>,
+    pub target: Option<EChannelTarget>,
+    pub notification_types: Option<Vec<ENotificationType>>,
+    pub enabled: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct DeliverySearchParams {
+    pub state: Option<EDeliveryState>,
+    pub channel_id: Option<i64>,
+    pub notification_type: Option<ENotificationType>,
+    pub limit: Option<i64>,
+}
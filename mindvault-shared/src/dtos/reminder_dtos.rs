@@ -0,0 +1,55 @@
+use crate::models::reminders_model::EReminderState;
+use crate::utils::date_time_serde::deserialize_optional_flexible_datetime;
+use crate::utils::time_zone::FlexibleDateTime;
+use serde::Deserialize;
+
+/// Either `remindAt` (absolute) or `taskId` + `offsetMinutes` (relative to the task's due date)
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateReminderRequest {
+    pub title: String,
+    pub note: Option<String>,
+    pub task_id: Option<i64>,
+    /// Wall-clock time in `timeZone`, or an RFC 3339 timestamp with its own offset
+    #[serde(default, deserialize_with = "deserialize_optional_flexible_datetime")]
+    pub remind_at: Option<FlexibleDateTime>,
+    /// Minutes relative to the task due date, e.g. `-1440` for one day before
+    pub offset_minutes: Option<i64>,
+    pub time_zone: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateReminderRequest {
+    pub title: Option<String>,
+    pub note: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_optional_flexible_datetime")]
+    pub remind_at: Option<FlexibleDateTime>,
+    pub offset_minutes: Option<i64>,
+    pub time_zone: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct ReminderSearchParams {
+    pub state: Option<EReminderState>,
+    pub task_id: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct DueRemindersParams {
+    /// Defaults to now
+    #[serde(default, deserialize_with = "deserialize_optional_flexible_datetime")]
+    pub until: Option<FlexibleDateTime>,
+    pub time_zone: Option<String>,
+}
+
+/// Snooze either for a number of minutes or until a given time
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnoozeReminderRequest {
+    pub minutes: Option<i64>,
+    #[serde(default, deserialize_with = "deserialize_optional_flexible_datetime")]
+    pub until: Option<FlexibleDateTime>,
+}
@@ -0,0 +1,17 @@
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateReporteeRequest {
+    pub name: String,
+    pub email: Option<String>,
+    pub designation: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateReporteeRequest {
+    pub name: Option<String>,
+    pub email: Option<String>,
+    pub designation: Option<String>,
+}
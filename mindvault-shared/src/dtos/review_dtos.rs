@@ -0,0 +1,46 @@
+use crate::models::review_templates_model::ReviewAnswer;
+use crate::models::reviews_model::{EReviewState, ReviewFeedback, ReviewRating};
+use crate::utils::date_input::DateInput;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct ReviewSearchParams {
+    pub reportee_id: Option<i64>,
+    pub cycle: Option<String>,
+    pub state: Option<EReviewState>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateReviewRequest {
+    pub cycle: String,
+    pub reviewer: String,
+    pub reportee_id: i64,
+    #[serde(default)]
+    pub ratings: Vec<ReviewRating>,
+    #[serde(default)]
+    pub feedback: ReviewFeedback,
+    /// Instantiates the latest version of this template; answers are validated against it
+    pub template_id: Option<i64>,
+    #[serde(default)]
+    pub answers: Vec<ReviewAnswer>,
+    #[serde(default)]
+    pub review_date: Option<DateInput>,
+}
+
+/// Partial update of a review; only reviews still in `Draft` can be edited.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateReviewRequest {
+    pub cycle: Option<String>,
+    pub reviewer: Option<String>,
+    pub ratings: Option<Vec<ReviewRating>>,
+    pub feedback: Option<ReviewFeedback>,
+    pub answers: Option<Vec<ReviewAnswer>>,
+    #[serde(default)]
+    pub review_date: Option<DateInput>,
+}
+
+/// Minimum and maximum accepted value for a category rating.
+pub const REVIEW_RATING_RANGE: std::ops::RangeInclusive<i32> = 1..=5;
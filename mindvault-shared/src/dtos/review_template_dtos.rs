@@ -0,0 +1,12 @@
+use crate::models::review_templates_model::TemplateSection;
+use serde::Deserialize;
+
+/// Body for creating a template and for publishing a new version of an existing one
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SaveReviewTemplateRequest {
+    pub name: String,
+    pub description: Option<String>,
+    #[serde(default)]
+    pub sections: Vec<TemplateSection>,
+}
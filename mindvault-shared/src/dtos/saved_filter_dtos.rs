@@ -0,0 +1,35 @@
+use crate::dtos::task_dtos::TaskSearchParams;
+use crate::models::saved_filters_model::TaskFilterCriteria;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateSavedFilterRequest {
+    pub name: String,
+    #[serde(default)]
+    pub criteria: TaskFilterCriteria,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateSavedFilterRequest {
+    pub name: Option<String>,
+    pub criteria: Option<TaskFilterCriteria>,
+}
+
+impl From<TaskFilterCriteria> for TaskSearchParams {
+    fn from(criteria: TaskFilterCriteria) -> Self {
+        TaskSearchParams {
+            query: criteria.query,
+            status: criteria.status,
+            priority: criteria.priority,
+            due_date: None,
+            due: None,
+            tag: criteria.tag,
+            notes: None,
+            assignee: None,
+            workspace: None,
+            sort: None,
+        }
+    }
+}
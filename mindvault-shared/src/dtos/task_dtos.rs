@@ -1,56 +1,175 @@
 use crate::models::tasks_model::{ETaskPriority, ETaskStatus};
-use chrono::NaiveDate;
 use serde::Deserialize;
-use crate::utils::date_time_serde::deserialize_multiple_formats;
+use utoipa::{IntoParams, ToSchema};
+use crate::utils::date_input::DateInput;
+use crate::utils::date_time_serde::date_input_schema;
+use crate::utils::recurrence::deserialize_optional_rrule;
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Default, Deserialize, IntoParams)]
 #[serde(rename_all = "snake_case")]
+#[into_params(parameter_in = Query)]
 pub struct TaskSearchParams {
     pub query: Option<String>,
     pub status: Option<ETaskStatus>,
     pub priority: Option<ETaskPriority>,
-    #[serde(default, deserialize_with = "deserialize_multiple_formats")]
-    pub due_date: Option<NaiveDate>,
+    #[serde(default)]
+    #[param(schema_with = date_input_schema)]
+    pub due_date: Option<DateInput>,
+    /// Due today or overdue, by the calendar of the caller's time zone
+    pub due: Option<EDueWindow>,
+    pub tag: Option<String>,
+    /// Words to look for in the notes
+    pub notes: Option<String>,
+    /// User id of the workspace member the tasks are assigned to
+    pub assignee: Option<i64>,
+    /// Searches this workspace's tasks instead of the caller's private vault
+    pub workspace: Option<i64>,
+    pub sort: Option<ETaskSort>,
 }
 
-#[derive(Debug, Deserialize)]
+/// Orders search results; without it they come in storage order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ETaskSort {
+    /// Most pressing first, then by due date
+    Priority,
+    /// Earliest due first; tasks without a due date last
+    Due,
+}
+
+/// Due dates relative to now in the caller's time zone
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum EDueWindow {
+    /// Due on the current day
+    Today,
+    /// Not completed or cancelled and due before today, or before now for due times
+    Overdue,
+}
+
+/// `?workspace=<id>` acts on that workspace's tasks instead of the caller's private vault
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[serde(rename_all = "snake_case")]
+#[into_params(parameter_in = Query)]
+pub struct TaskVaultParams {
+    pub workspace: Option<i64>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct CreateTaskRequest {
     pub name: String,
-    #[serde(default, deserialize_with = "deserialize_multiple_formats")]
-    pub due_date: Option<NaiveDate>,
+    #[serde(default)]
+    #[schema(schema_with = date_input_schema)]
+    pub due_date: Option<DateInput>,
     pub priority: Option<ETaskPriority>,
     pub status: Option<ETaskStatus>,
+    /// Status in the vault's workflow; `status` is then taken from its category
+    pub workflow_status: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default, deserialize_with = "deserialize_optional_rrule")]
+    pub recurrence: Option<String>,
+    /// Expected effort, compared against the time tracked on the task
+    pub estimate_minutes: Option<u32>,
+    pub notes: Option<String>,
+    /// Identifier in another system; imports use it to update instead of duplicate
+    pub external_id: Option<String>,
+    /// Workspace member to assign the task to; only for workspace tasks
+    pub assignee_id: Option<i64>,
+    /// Reportees the task is about
+    #[serde(default)]
+    pub reportee_ids: Vec<i64>,
 }
 
-#[derive(Debug, Deserialize)]
+/// One line of free text describing a task, e.g. `Call Priya tomorrow 3pm !high #work`
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct QuickAddRequest {
+    pub text: String,
+}
+
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[serde(rename_all = "snake_case")]
+#[into_params(parameter_in = Query)]
+pub struct QuickAddParams {
+    /// Creates the task in this workspace instead of the caller's private vault
+    pub workspace: Option<i64>,
+    /// Only parse the text; nothing is saved
+    #[serde(default)]
+    pub preview: bool,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct BulkCreateTaskRequest {
     pub tasks: Vec<CreateTaskRequest>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Default, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct UpdateTaskRequest {
-    #[serde(default, deserialize_with = "deserialize_multiple_formats")]
-    pub due_date: Option<NaiveDate>,
+    pub name: Option<String>,
+    #[serde(default)]
+    #[schema(schema_with = date_input_schema)]
+    pub due_date: Option<DateInput>,
     pub priority: Option<ETaskPriority>,
     pub status: Option<ETaskStatus>,
+    /// Status in the vault's workflow; the move must be one of its transitions
+    pub workflow_status: Option<String>,
+    pub tags: Option<Vec<String>>,
+    #[serde(default, deserialize_with = "deserialize_optional_rrule")]
+    pub recurrence: Option<String>,
+    /// Expected effort; 0 removes the estimate
+    pub estimate_minutes: Option<u32>,
+    pub notes: Option<String>,
+    /// Workspace member to assign the task to; only for workspace tasks
+    pub assignee_id: Option<i64>,
+}
+
+/// Drops a task into a board column between two of its tasks. Without a status the task stays
+/// in its column; without neighbours it goes to the bottom.
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct MoveTaskRequest {
+    pub status: Option<ETaskStatus>,
+    /// Status in the vault's workflow; the move must be one of its transitions
+    pub workflow_status: Option<String>,
+    /// Task the moved task goes right after
+    pub previous_id: Option<i64>,
+    /// Task the moved task goes right before
+    pub next_id: Option<i64>,
 }
 
-#[derive(Debug, Deserialize)]
+/// Tasks of one board column in their new order; the column's other tasks follow them
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ReorderTasksRequest {
+    pub task_ids: Vec<i64>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct SearchAndUpdateRequest {
     // Search parameters
     pub query: Option<String>,
     pub status_filter: Option<ETaskStatus>,
     pub priority_filter: Option<ETaskPriority>,
-    #[serde(default, deserialize_with = "deserialize_multiple_formats")]
-    pub due_date_filter: Option<NaiveDate>,
+    #[serde(default)]
+    #[schema(schema_with = date_input_schema)]
+    pub due_date_filter: Option<DateInput>,
 
     // Update fields
-    #[serde(default, deserialize_with = "deserialize_multiple_formats")]
-    pub due_date: Option<NaiveDate>,
+    #[serde(default)]
+    #[schema(schema_with = date_input_schema)]
+    pub due_date: Option<DateInput>,
     pub priority: Option<ETaskPriority>,
     pub status: Option<ETaskStatus>,
-}
\ No newline at end of file
+}
+
+impl SearchAndUpdateRequest {
+    /// Whether the request sets anything on the matched tasks
+    pub fn has_update(&self) -> bool {
+        self.due_date.is_some() || self.priority.is_some() || self.status.is_some()
+    }
+}
@@ -0,0 +1,42 @@
+use crate::models::time_entries_model::ETimeReportGroup;
+use crate::utils::date_input::DateInput;
+use crate::utils::date_time_serde::date_input_schema;
+use serde::Deserialize;
+use utoipa::ToSchema;
+
+/// Optional body of a timer start or stop
+#[derive(Debug, Default, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TimerRequest {
+    /// What the time went into; on stop, replaces the note given at start
+    pub note: Option<String>,
+}
+
+/// Time entered by hand: a start and either an end or a duration. A day without a time
+/// starts at midnight in the caller's time zone.
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct LogTimeRequest {
+    #[schema(schema_with = date_input_schema)]
+    pub started_at: DateInput,
+    #[serde(default)]
+    #[schema(schema_with = date_input_schema)]
+    pub ended_at: Option<DateInput>,
+    pub duration_minutes: Option<u32>,
+    pub note: Option<String>,
+}
+
+/// Tracked time in the caller's private vault or `?workspace=<id>`, where it covers every
+/// member's entries. `from` and `to` are inclusive days in the caller's time zone.
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct TimeReportParams {
+    pub workspace: Option<i64>,
+    #[serde(default)]
+    pub from: Option<DateInput>,
+    #[serde(default)]
+    pub to: Option<DateInput>,
+    /// `tag`, `task` or `day`; defaults to `task`
+    #[serde(default)]
+    pub group_by: ETimeReportGroup,
+}
@@ -0,0 +1,69 @@
+use crate::dtos::task_dtos::TaskSearchParams;
+use crate::models::tasks_model::{ETaskPriority, ETaskStatus};
+use crate::models::transfer_model::ETransferFormat;
+use crate::utils::date_input::DateInput;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct ExportTasksParams {
+    pub format: Option<ETransferFormat>,
+    pub query: Option<String>,
+    pub status: Option<ETaskStatus>,
+    pub priority: Option<ETaskPriority>,
+    #[serde(default)]
+    pub due_date: Option<DateInput>,
+    pub tag: Option<String>,
+}
+
+impl ExportTasksParams {
+    pub fn search_params(&self) -> TaskSearchParams {
+        TaskSearchParams {
+            query: self.query.clone(),
+            status: self.status.clone(),
+            priority: self.priority.clone(),
+            due_date: self.due_date.clone(),
+            due: None,
+            tag: self.tag.clone(),
+            notes: None,
+            assignee: None,
+            workspace: None,
+            sort: None,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct ImportTasksParams {
+    /// Falls back to the request's Content-Type
+    pub format: Option<ETransferFormat>,
+    #[serde(default)]
+    pub dry_run: bool,
+    /// CSV header holding the task name; defaults to `name`, `title` or `task`
+    pub name_column: Option<String>,
+    /// CSV header holding the due date; defaults to `dueDate`, `due_date` or `due`
+    pub due_date_column: Option<String>,
+    pub priority_column: Option<String>,
+    pub status_column: Option<String>,
+}
+
+/// CSV headers to read each task field from, `None` meaning the default header names
+#[derive(Debug, Clone, Default)]
+pub struct ImportColumnMapping {
+    pub name: Option<String>,
+    pub due_date: Option<String>,
+    pub priority: Option<String>,
+    pub status: Option<String>,
+}
+
+impl From<&ImportTasksParams> for ImportColumnMapping {
+    fn from(params: &ImportTasksParams) -> Self {
+        Self {
+            name: params.name_column.clone(),
+            due_date: params.due_date_column.clone(),
+            priority: params.priority_column.clone(),
+            status: params.status_column.clone(),
+        }
+    }
+}
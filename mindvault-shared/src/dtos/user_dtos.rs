@@ -0,0 +1,17 @@
+use crate::utils::date_input::EDateOrder;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateUserRequest {
+    pub username: String,
+    pub display_name: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdatePreferencesRequest {
+    /// IANA name, e.g. `Europe/Berlin`
+    pub time_zone: Option<String>,
+    pub date_order: Option<EDateOrder>,
+}
@@ -0,0 +1,34 @@
+use crate::models::notifications_model::EDeliveryState;
+use crate::models::webhooks_model::ETaskEvent;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateWebhookRequest {
+    pub url: String,
+    pub secret: Option<String>,
+    #[serde(default)]
+    pub events: Vec<ETaskEvent>,
+    pub description: Option<String>,
+    pub enabled: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateWebhookRequest {
+    pub url: Option<String>,
+    pub secret: Option<String>,
+    pub events: Option<Vec<ETaskEvent>>,
+    pub description: Option<String>,
+    pub enabled: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct WebhookDeliverySearchParams {
+    pub subscription_id: Option<i64>,
+    pub state: Option<EDeliveryState>,
+    pub event: Option<ETaskEvent>,
+    pub task_id: Option<i64>,
+    pub limit: Option<i64>,
+}
@@ -0,0 +1,12 @@
+use crate::models::workflows_model::{WorkflowStatus, WorkflowTransition};
+use serde::Deserialize;
+
+/// Replaces the vault's workflow. The first status is where new tasks start.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetWorkflowRequest {
+    pub statuses: Vec<WorkflowStatus>,
+    /// Allowed moves; leave empty to allow every move
+    #[serde(default)]
+    pub transitions: Vec<WorkflowTransition>,
+}
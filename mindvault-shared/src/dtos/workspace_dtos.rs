@@ -0,0 +1,25 @@
+use crate::models::workspaces_model::EWorkspaceRole;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateWorkspaceRequest {
+    pub name: String,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateWorkspaceRequest {
+    pub name: Option<String>,
+    pub description: Option<String>,
+}
+
+/// Adds a member, or changes the role of an existing one
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceMemberRequest {
+    pub username: String,
+    pub role: EWorkspaceRole,
+}
+
@@ -0,0 +1,203 @@
+use crate::utils::date_time_serde::{
+    serialize_bson_datetime_as_chrono_date, serialize_option_bson_datetime_as_chrono_date,
+};
+use bson::DateTime as BsonDateTime;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// What a personal access token may do. `admin` implies every other scope and a `:write`
+/// scope implies the matching `:read`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ETokenScope {
+    #[serde(rename = "tasks:read")]
+    TasksRead,
+    #[serde(rename = "tasks:write")]
+    TasksWrite,
+    /// Reportees, reviews, review templates, feedback and goals
+    #[serde(rename = "people:read")]
+    PeopleRead,
+    #[serde(rename = "people:write")]
+    PeopleWrite,
+    #[serde(rename = "admin")]
+    Admin,
+}
+
+impl ETokenScope {
+    pub const ALL: [ETokenScope; 5] = [
+        ETokenScope::TasksRead,
+        ETokenScope::TasksWrite,
+        ETokenScope::PeopleRead,
+        ETokenScope::PeopleWrite,
+        ETokenScope::Admin,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ETokenScope::TasksRead => "tasks:read",
+            ETokenScope::TasksWrite => "tasks:write",
+            ETokenScope::PeopleRead => "people:read",
+            ETokenScope::PeopleWrite => "people:write",
+            ETokenScope::Admin => "admin",
+        }
+    }
+
+    /// Whether holding `self` is enough for something that requires `required`
+    pub fn grants(self, required: ETokenScope) -> bool {
+        self == required
+            || self == ETokenScope::Admin
+            || matches!(
+                (self, required),
+                (ETokenScope::TasksWrite, ETokenScope::TasksRead)
+                    | (ETokenScope::PeopleWrite, ETokenScope::PeopleRead)
+            )
+    }
+}
+
+impl fmt::Display for ETokenScope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for ETokenScope {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let scope = s.trim().to_ascii_lowercase();
+        ETokenScope::ALL
+            .into_iter()
+            .find(|candidate| candidate.as_str() == scope)
+            .ok_or_else(|| {
+                let known: Vec<&str> = ETokenScope::ALL.iter().map(|scope| scope.as_str()).collect();
+                format!("invalid scope '{}', expected one of: {}", s.trim(), known.join(", "))
+            })
+    }
+}
+
+fn default_utc_now() -> BsonDateTime {
+    BsonDateTime::now()
+}
+
+/// A personal access token. Only the SHA-256 of the secret is stored; the secret itself is
+/// shown once, when the token is created.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccessToken {
+    #[serde(rename = "_id")]
+    pub id: i64,
+    /// User whose vault the token acts on
+    pub user_id: i64,
+    /// Who or what uses the token, e.g. `mcp`; logged with every request it makes
+    pub name: String,
+    /// Hex SHA-256 of the full token
+    pub token_hash: String,
+    /// Leading characters of the token, enough to recognise it in a list
+    pub prefix: String,
+    #[serde(default)]
+    pub scopes: Vec<ETokenScope>,
+    #[serde(default = "default_utc_now")]
+    pub created_at: BsonDateTime,
+    #[serde(default)]
+    pub last_used_at: Option<BsonDateTime>,
+    #[serde(default)]
+    pub revoked_at: Option<BsonDateTime>,
+}
+
+impl AccessToken {
+    pub fn has_scope(&self, required: ETokenScope) -> bool {
+        self.scopes.iter().any(|scope| scope.grants(required))
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccessTokenResponse {
+    pub id: i64,
+    pub user_id: i64,
+    pub name: String,
+    pub prefix: String,
+    pub scopes: Vec<ETokenScope>,
+    #[serde(
+        serialize_with = "serialize_bson_datetime_as_chrono_date"
+    )]
+    pub created_at: BsonDateTime,
+    #[serde(
+        serialize_with = "serialize_option_bson_datetime_as_chrono_date",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub last_used_at: Option<BsonDateTime>,
+    #[serde(
+        serialize_with = "serialize_option_bson_datetime_as_chrono_date",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub revoked_at: Option<BsonDateTime>,
+}
+
+impl From<AccessToken> for AccessTokenResponse {
+    fn from(token: AccessToken) -> Self {
+        Self {
+            id: token.id,
+            user_id: token.user_id,
+            name: token.name,
+            prefix: token.prefix,
+            scopes: token.scopes,
+            created_at: token.created_at,
+            last_used_at: token.last_used_at,
+            revoked_at: token.revoked_at,
+        }
+    }
+}
+
+impl AccessTokenResponse {
+    pub fn from_vec(tokens: Vec<AccessToken>) -> Vec<Self> {
+        tokens.into_iter().map(AccessTokenResponse::from).collect()
+    }
+}
+
+/// Returned once on creation; `token` cannot be retrieved again
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreatedAccessTokenResponse {
+    pub token: String,
+    #[serde(flatten)]
+    pub details: AccessTokenResponse,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn admin_implies_every_scope() {
+        for required in ETokenScope::ALL {
+            assert!(ETokenScope::Admin.grants(required), "{}", required);
+        }
+    }
+
+    #[test]
+    fn write_implies_the_matching_read_only() {
+        assert!(ETokenScope::TasksWrite.grants(ETokenScope::TasksRead));
+        assert!(ETokenScope::PeopleWrite.grants(ETokenScope::PeopleRead));
+        assert!(!ETokenScope::TasksWrite.grants(ETokenScope::PeopleRead));
+        assert!(!ETokenScope::PeopleWrite.grants(ETokenScope::TasksRead));
+        assert!(!ETokenScope::TasksWrite.grants(ETokenScope::Admin));
+    }
+
+    #[test]
+    fn read_grants_nothing_but_itself() {
+        for read in [ETokenScope::TasksRead, ETokenScope::PeopleRead] {
+            let granted: Vec<ETokenScope> = ETokenScope::ALL
+                .into_iter()
+                .filter(|required| read.grants(*required))
+                .collect();
+            assert_eq!(granted, vec![read]);
+        }
+    }
+
+    #[test]
+    fn scopes_parse_without_regard_to_case() {
+        assert_eq!(" Tasks:Write ".parse::<ETokenScope>(), Ok(ETokenScope::TasksWrite));
+        assert!("tasks".parse::<ETokenScope>().unwrap_err().starts_with("invalid scope 'tasks'"));
+    }
+}
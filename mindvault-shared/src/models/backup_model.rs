@@ -0,0 +1,55 @@
+use serde::{Deserialize, Serialize};
+
+/// `manifest.json` at the root of a backup archive
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupManifest {
+    /// Always `mindvault-backup`
+    pub format: String,
+    /// Layout of the archive itself
+    pub format_version: u32,
+    /// Shape of the stored documents; restore refuses archives newer than it understands
+    pub schema_version: u32,
+    /// How documents are written, e.g. `extended-json-canonical`
+    pub encoding: String,
+    /// RFC 3339
+    pub created_at: String,
+    pub app_version: String,
+    pub database: String,
+    pub collections: Vec<BackupCollection>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupCollection {
+    pub name: String,
+    /// Path inside the archive, one document per line
+    pub file: String,
+    pub documents: u64,
+    /// Hex SHA-256 of `file`
+    pub sha256: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupSummary {
+    pub path: String,
+    pub size_bytes: u64,
+    pub manifest: BackupManifest,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RestoredCollection {
+    pub name: String,
+    pub documents: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RestoreSummary {
+    pub dry_run: bool,
+    pub database: String,
+    pub schema_version: u32,
+    pub collections: Vec<RestoredCollection>,
+}
@@ -0,0 +1,121 @@
+use crate::models::tasks_model::{EStatusCategory, ETaskStatus, Task, TaskResponse};
+use crate::models::workflows_model::Workflow;
+use serde::Serialize;
+use std::cmp::Ordering;
+
+/// Name of the board column `task` sits in: its workflow status, or its built-in status when
+/// the vault has no workflow. `None` when the workflow has no status for the task's category.
+pub fn board_column(workflow: Option<&Workflow>, task: &Task) -> Option<String> {
+    match workflow {
+        Some(workflow) => workflow
+            .current_status(task)
+            .map(|status| status.name.clone()),
+        None => Some(format!("{:?}", task.status)),
+    }
+}
+
+/// Order within a column: ranked tasks by rank, then unranked ones by id
+pub fn board_order(a: &Task, b: &Task) -> Ordering {
+    match (&a.rank, &b.rank) {
+        (Some(a_rank), Some(b_rank)) => a_rank.cmp(b_rank),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
+    .then(a.id.cmp(&b.id))
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BoardColumn {
+    pub name: String,
+    pub category: EStatusCategory,
+    /// Built-in status of the column's tasks
+    pub status: ETaskStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wip_limit: Option<u32>,
+    pub task_count: usize,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub over_wip_limit: bool,
+    pub tasks: Vec<TaskResponse>,
+}
+
+/// A vault's tasks in columns, one per workflow status or built-in status
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BoardResponse {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workspace_id: Option<i64>,
+    pub columns: Vec<BoardColumn>,
+    /// One line per column holding more tasks than its WIP limit
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+}
+
+impl BoardResponse {
+    pub fn new(workspace_id: Option<i64>, workflow: Option<&Workflow>, tasks: Vec<Task>) -> Self {
+        let mut columns: Vec<(BoardColumn, Vec<Task>)> = match workflow {
+            Some(workflow) => workflow
+                .statuses
+                .iter()
+                .map(|status| {
+                    let status_of = status.category.default_status();
+                    (Self::column(status.name.clone(), status_of, status.wip_limit), Vec::new())
+                })
+                .collect(),
+            None => ETaskStatus::ALL
+                .into_iter()
+                .map(|status| (Self::column(format!("{:?}", status), status, None), Vec::new()))
+                .collect(),
+        };
+        for task in tasks {
+            let Some(name) = board_column(workflow, &task) else {
+                continue;
+            };
+            if let Some((_, column_tasks)) =
+                columns.iter_mut().find(|(column, _)| column.name == name)
+            {
+                column_tasks.push(task);
+            }
+        }
+
+        let mut warnings = Vec::new();
+        let columns = columns
+            .into_iter()
+            .map(|(mut column, mut tasks)| {
+                tasks.sort_by(board_order);
+                column.task_count = tasks.len();
+                if let Some(limit) = column.wip_limit
+                    && tasks.len() > limit as usize
+                {
+                    column.over_wip_limit = true;
+                    warnings.push(format!(
+                        "'{}' holds {} tasks, over its WIP limit of {}",
+                        column.name,
+                        tasks.len(),
+                        limit
+                    ));
+                }
+                column.tasks = TaskResponse::from_vec(tasks);
+                column
+            })
+            .collect();
+        Self {
+            workspace_id,
+            columns,
+            warnings,
+        }
+    }
+
+    fn column(name: String, status: ETaskStatus, wip_limit: Option<u32>) -> BoardColumn {
+        BoardColumn {
+            name,
+            category: status.category(),
+            status,
+            wip_limit,
+            task_count: 0,
+            over_wip_limit: false,
+            tasks: Vec::new(),
+        }
+    }
+}
@@ -0,0 +1,81 @@
+use crate::models::tasks_model::TaskResponse;
+use crate::utils::date_time_serde::serialize_bson_datetime_as_chrono_date;
+use bson::DateTime as BsonDateTime;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EDigestPeriod {
+    #[default]
+    Daily,
+    Weekly,
+}
+
+impl EDigestPeriod {
+    /// How far back "recently completed" reaches
+    pub fn lookback_days(&self) -> i64 {
+        match self {
+            EDigestPeriod::Daily => 1,
+            EDigestPeriod::Weekly => 7,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            EDigestPeriod::Daily => "Daily",
+            EDigestPeriod::Weekly => "Weekly",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EDigestFormat {
+    #[default]
+    Json,
+    Markdown,
+    Text,
+}
+
+/// Summary of where tasks stand on `date` in `time_zone`
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskDigest {
+    pub period: EDigestPeriod,
+    pub date: NaiveDate,
+    pub time_zone: String,
+    #[serde(
+        serialize_with = "serialize_bson_datetime_as_chrono_date"
+    )]
+    pub generated_at: BsonDateTime,
+    pub stalled_days: i64,
+    pub overdue: Vec<TaskResponse>,
+    pub due_today: Vec<TaskResponse>,
+    /// Due in the seven days after today
+    pub due_this_week: Vec<TaskResponse>,
+    pub recently_completed: Vec<TaskResponse>,
+    /// In progress and untouched for at least `stalled_days`
+    pub stalled: Vec<TaskResponse>,
+}
+
+impl TaskDigest {
+    pub fn is_empty(&self) -> bool {
+        self.overdue.is_empty()
+            && self.due_today.is_empty()
+            && self.due_this_week.is_empty()
+            && self.recently_completed.is_empty()
+            && self.stalled.is_empty()
+    }
+
+    /// Sections in display order with their headings
+    pub fn sections(&self) -> [(&'static str, &[TaskResponse]); 5] {
+        [
+            ("Overdue", &self.overdue),
+            ("Due today", &self.due_today),
+            ("Due this week", &self.due_this_week),
+            ("Recently completed", &self.recently_completed),
+            ("Stalled", &self.stalled),
+        ]
+    }
+}
@@ -0,0 +1,22 @@
+use serde::Serialize;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RotatedField {
+    /// e.g. `tasks.notes`
+    pub field: String,
+    pub collection: String,
+    /// Whether the field is now stored encrypted
+    pub encrypted: bool,
+    pub scanned: u64,
+    pub rewritten: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyRotationSummary {
+    pub dry_run: bool,
+    /// Key every encrypted value uses after the rotation, `None` when encryption is off
+    pub active_key: Option<String>,
+    pub fields: Vec<RotatedField>,
+}
@@ -0,0 +1,32 @@
+use crate::models::tasks_model::TaskResponse;
+use crate::models::webhooks_model::ETaskEvent;
+use serde::Serialize;
+
+pub const TASK_ENTITY_TYPE: &str = "task";
+
+/// One entry of the live change feed
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangeEvent {
+    /// Resumable id, sent back as `Last-Event-ID` to continue after this event
+    pub id: String,
+    #[serde(skip)]
+    pub sequence: u64,
+    pub entity_type: String,
+    pub entity_id: i64,
+    pub event: ETaskEvent,
+    pub occurred_at: String,
+    /// The task after the change, or before it for deletions when known
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub task: Option<TaskResponse>,
+}
+
+/// What a feed subscriber receives
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum FeedMessage {
+    Change(Box<ChangeEvent>),
+    /// Events were missed (unknown or expired resume id, or a slow consumer);
+    /// the client should reload its data
+    Reset { reason: String },
+}
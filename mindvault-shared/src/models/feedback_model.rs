@@ -0,0 +1,194 @@
+use crate::models::reportees_model::ReporteeResponse;
+use crate::utils::date_time_serde::{
+    serialize_bson_datetime_as_chrono_date, serialize_option_bson_datetime_as_chrono_date,
+};
+use crate::utils::field_encryption::{deserialize_opened, serialize_sealed, EncryptedField};
+use bson::DateTime as BsonDateTime;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::BTreeMap;
+
+/// Tag group used in summaries for entries recorded without any tag
+pub const UNTAGGED_FEEDBACK_TAG: &str = "untagged";
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub enum EFeedbackSentiment {
+    Positive,
+    #[default]
+    Neutral,
+    Negative,
+}
+
+fn default_utc_now() -> BsonDateTime {
+    BsonDateTime::now()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeedbackEntry {
+    #[serde(rename = "_id")]
+    pub id: i64,
+    pub owner_id: i64,
+    pub reportee_id: i64,
+    /// Encrypted at rest when `feedback.note` encryption is enabled
+    #[serde(
+        serialize_with = "serialize_sealed_note",
+        deserialize_with = "deserialize_opened_note"
+    )]
+    pub note: String,
+    #[serde(default)]
+    pub sentiment: EFeedbackSentiment,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Day the observation was made, which can be earlier than `created_at`
+    #[serde(default = "default_utc_now")]
+    pub observed_on: BsonDateTime,
+    #[serde(default = "default_utc_now")]
+    pub created_at: BsonDateTime,
+    #[serde(default)]
+    pub deleted: Option<bool>,
+}
+
+fn serialize_sealed_note<S: Serializer>(note: &str, serializer: S) -> Result<S::Ok, S::Error> {
+    serialize_sealed(EncryptedField::FeedbackNote, note, serializer)
+}
+
+fn deserialize_opened_note<'de, D: Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+    deserialize_opened(EncryptedField::FeedbackNote, deserializer)
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeedbackEntryResponse {
+    pub id: i64,
+    pub reportee_id: i64,
+    pub note: String,
+    pub sentiment: EFeedbackSentiment,
+    pub tags: Vec<String>,
+    #[serde(
+        serialize_with = "serialize_bson_datetime_as_chrono_date"
+    )]
+    pub observed_on: BsonDateTime,
+    #[serde(
+        serialize_with = "serialize_bson_datetime_as_chrono_date"
+    )]
+    pub created_at: BsonDateTime,
+}
+
+impl From<FeedbackEntry> for FeedbackEntryResponse {
+    fn from(entry: FeedbackEntry) -> Self {
+        Self {
+            id: entry.id,
+            reportee_id: entry.reportee_id,
+            note: entry.note,
+            sentiment: entry.sentiment,
+            tags: entry.tags,
+            observed_on: entry.observed_on,
+            created_at: entry.created_at,
+        }
+    }
+}
+
+impl FeedbackEntryResponse {
+    pub fn from_vec(entries: Vec<FeedbackEntry>) -> Vec<Self> {
+        entries.into_iter().map(FeedbackEntryResponse::from).collect()
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeedbackSentimentGroup {
+    pub sentiment: EFeedbackSentiment,
+    pub count: usize,
+    pub entries: Vec<FeedbackEntryResponse>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeedbackTagGroup {
+    pub tag: String,
+    pub count: usize,
+    pub sentiments: Vec<FeedbackSentimentGroup>,
+}
+
+/// Feedback for one reportee in a date window, grouped by tag and then by sentiment.
+/// An entry with several tags appears once under each of its tags.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeedbackSummaryResponse {
+    pub reportee: ReporteeResponse,
+    #[serde(
+        serialize_with = "serialize_option_bson_datetime_as_chrono_date",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub from: Option<BsonDateTime>,
+    #[serde(
+        serialize_with = "serialize_option_bson_datetime_as_chrono_date",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub to: Option<BsonDateTime>,
+    pub total_entries: usize,
+    pub sentiment_totals: BTreeMap<EFeedbackSentiment, usize>,
+    pub tags: Vec<FeedbackTagGroup>,
+}
+
+impl FeedbackSummaryResponse {
+    pub fn from_entries(
+        reportee: ReporteeResponse,
+        from: Option<BsonDateTime>,
+        to: Option<BsonDateTime>,
+        entries: Vec<FeedbackEntry>,
+    ) -> Self {
+        let total_entries = entries.len();
+        let mut sentiment_totals = BTreeMap::new();
+        let mut grouped: BTreeMap<String, BTreeMap<EFeedbackSentiment, Vec<FeedbackEntryResponse>>> =
+            BTreeMap::new();
+
+        for entry in entries {
+            *sentiment_totals.entry(entry.sentiment).or_insert(0) += 1;
+            let tags = if entry.tags.is_empty() {
+                vec![UNTAGGED_FEEDBACK_TAG.to_string()]
+            } else {
+                entry.tags.clone()
+            };
+            let sentiment = entry.sentiment;
+            let response = FeedbackEntryResponse::from(entry);
+            for tag in tags {
+                grouped
+                    .entry(tag)
+                    .or_default()
+                    .entry(sentiment)
+                    .or_default()
+                    .push(response.clone());
+            }
+        }
+
+        let tags = grouped
+            .into_iter()
+            .map(|(tag, by_sentiment)| {
+                let sentiments: Vec<FeedbackSentimentGroup> = by_sentiment
+                    .into_iter()
+                    .map(|(sentiment, entries)| FeedbackSentimentGroup {
+                        sentiment,
+                        count: entries.len(),
+                        entries,
+                    })
+                    .collect();
+                FeedbackTagGroup {
+                    tag,
+                    count: sentiments.iter().map(|group| group.count).sum(),
+                    sentiments,
+                }
+            })
+            .collect();
+
+        Self {
+            reportee,
+            from,
+            to,
+            total_entries,
+            sentiment_totals,
+            tags,
+        }
+    }
+}
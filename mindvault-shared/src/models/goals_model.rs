@@ -0,0 +1,209 @@
+use crate::utils::date_time_serde::serialize_bson_datetime_as_chrono_date;
+use bson::DateTime as BsonDateTime;
+use serde::{Deserialize, Serialize};
+
+/// Where a key result takes its current value from
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub enum EProgressSource {
+    /// Updated through check-ins
+    #[default]
+    Manual,
+    /// Derived from the share of linked tasks that are completed
+    LinkedTasks,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyResult {
+    pub id: i64,
+    pub title: String,
+    #[serde(default)]
+    pub start_value: f64,
+    pub target_value: f64,
+    #[serde(default)]
+    pub current_value: f64,
+    pub unit: Option<String>,
+    #[serde(default)]
+    pub progress_source: EProgressSource,
+    #[serde(default)]
+    pub linked_task_ids: Vec<i64>,
+}
+
+impl KeyResult {
+    /// Fraction of the way from start to target, clamped to 0..=1
+    pub fn progress(&self) -> f64 {
+        let span = self.target_value - self.start_value;
+        if span == 0.0 {
+            return if self.current_value >= self.target_value { 1.0 } else { 0.0 };
+        }
+        ((self.current_value - self.start_value) / span).clamp(0.0, 1.0)
+    }
+
+    /// Current value implied by `completed` out of `total` linked tasks being done
+    pub fn value_from_task_completion(&self, completed: usize, total: usize) -> f64 {
+        if total == 0 {
+            return self.start_value;
+        }
+        let fraction = completed as f64 / total as f64;
+        self.start_value + (self.target_value - self.start_value) * fraction
+    }
+}
+
+fn default_utc_now() -> BsonDateTime {
+    BsonDateTime::now()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Goal {
+    #[serde(rename = "_id")]
+    pub id: i64,
+    /// User whose vault the goal and its linked tasks live in
+    pub owner_id: i64,
+    pub objective: String,
+    pub description: Option<String>,
+    pub owner_reportee_id: Option<i64>,
+    /// Period label, e.g. `2026-Q3`
+    pub period: String,
+    #[serde(default)]
+    pub key_results: Vec<KeyResult>,
+    #[serde(default = "default_utc_now")]
+    pub created_at: BsonDateTime,
+    #[serde(default)]
+    pub deleted: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyResultResponse {
+    pub id: i64,
+    pub title: String,
+    pub start_value: f64,
+    pub target_value: f64,
+    pub current_value: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unit: Option<String>,
+    pub progress_source: EProgressSource,
+    pub linked_task_ids: Vec<i64>,
+    pub progress: f64,
+}
+
+impl From<KeyResult> for KeyResultResponse {
+    fn from(key_result: KeyResult) -> Self {
+        let progress = key_result.progress();
+        Self {
+            id: key_result.id,
+            title: key_result.title,
+            start_value: key_result.start_value,
+            target_value: key_result.target_value,
+            current_value: key_result.current_value,
+            unit: key_result.unit,
+            progress_source: key_result.progress_source,
+            linked_task_ids: key_result.linked_task_ids,
+            progress,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GoalResponse {
+    pub id: i64,
+    pub objective: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub owner_reportee_id: Option<i64>,
+    pub period: String,
+    pub key_results: Vec<KeyResultResponse>,
+    /// Average progress of all key results
+    pub progress: f64,
+    #[serde(
+        serialize_with = "serialize_bson_datetime_as_chrono_date"
+    )]
+    pub created_at: BsonDateTime,
+}
+
+impl From<Goal> for GoalResponse {
+    fn from(goal: Goal) -> Self {
+        let key_results: Vec<KeyResultResponse> = goal
+            .key_results
+            .into_iter()
+            .map(KeyResultResponse::from)
+            .collect();
+        let progress = if key_results.is_empty() {
+            0.0
+        } else {
+            key_results.iter().map(|kr| kr.progress).sum::<f64>() / key_results.len() as f64
+        };
+        Self {
+            id: goal.id,
+            objective: goal.objective,
+            description: goal.description,
+            owner_reportee_id: goal.owner_reportee_id,
+            period: goal.period,
+            key_results,
+            progress,
+            created_at: goal.created_at,
+        }
+    }
+}
+
+impl GoalResponse {
+    pub fn from_vec(goals: Vec<Goal>) -> Vec<Self> {
+        goals.into_iter().map(GoalResponse::from).collect()
+    }
+}
+
+/// One point in a key result's progress history
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyResultCheckIn {
+    #[serde(rename = "_id")]
+    pub id: i64,
+    pub goal_id: i64,
+    pub key_result_id: i64,
+    pub value: f64,
+    pub note: Option<String>,
+    pub source: EProgressSource,
+    #[serde(default = "default_utc_now")]
+    pub recorded_at: BsonDateTime,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyResultCheckInResponse {
+    pub id: i64,
+    pub key_result_id: i64,
+    pub value: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    pub source: EProgressSource,
+    #[serde(
+        serialize_with = "serialize_bson_datetime_as_chrono_date"
+    )]
+    pub recorded_at: BsonDateTime,
+}
+
+impl From<KeyResultCheckIn> for KeyResultCheckInResponse {
+    fn from(check_in: KeyResultCheckIn) -> Self {
+        Self {
+            id: check_in.id,
+            key_result_id: check_in.key_result_id,
+            value: check_in.value,
+            note: check_in.note,
+            source: check_in.source,
+            recorded_at: check_in.recorded_at,
+        }
+    }
+}
+
+impl KeyResultCheckInResponse {
+    pub fn from_vec(check_ins: Vec<KeyResultCheckIn>) -> Vec<Self> {
+        check_ins
+            .into_iter()
+            .map(KeyResultCheckInResponse::from)
+            .collect()
+    }
+}
@@ -0,0 +1,139 @@
+use crate::utils::date_time_serde::serialize_option_bson_datetime_as_chrono_date;
+use bson::DateTime as BsonDateTime;
+use serde::{Deserialize, Serialize};
+
+/// Persisted state of a background job, shared by every API instance
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JobState {
+    /// Job name, unique across the deployment
+    #[serde(rename = "_id")]
+    pub name: String,
+    /// Human readable schedule, e.g. `every 60s` or `cron 0 0 * * * *`
+    pub schedule: String,
+    /// As configured on the instance that registered the job last. Each instance decides on
+    /// its own whether it runs the job.
+    #[serde(default)]
+    pub enabled: bool,
+    pub next_run_at: Option<BsonDateTime>,
+    pub last_started_at: Option<BsonDateTime>,
+    pub last_finished_at: Option<BsonDateTime>,
+    pub last_duration_ms: Option<i64>,
+    pub last_error: Option<String>,
+    #[serde(default)]
+    pub run_count: i64,
+    /// Instance currently holding the run lease, if any
+    pub lease_owner: Option<String>,
+    pub lease_expires_at: Option<BsonDateTime>,
+}
+
+impl JobState {
+    /// Whether an instance holds an unexpired run lease at `now`
+    pub fn is_leased(&self, now: BsonDateTime) -> bool {
+        self.lease_expires_at.is_some_and(|expires_at| expires_at > now)
+    }
+
+    /// Whether an instance may take the run lease at `now`: the job is due and nobody holds
+    /// an unexpired lease. `JobRepository::try_acquire_lease` filters on the same terms.
+    pub fn can_take_lease(&self, now: BsonDateTime) -> bool {
+        self.next_run_at.is_some_and(|next_run_at| next_run_at <= now) && !self.is_leased(now)
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JobStateResponse {
+    pub name: String,
+    pub schedule: String,
+    pub enabled: bool,
+    pub running: bool,
+    #[serde(
+        serialize_with = "serialize_option_bson_datetime_as_chrono_date",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub next_run_at: Option<BsonDateTime>,
+    #[serde(
+        serialize_with = "serialize_option_bson_datetime_as_chrono_date",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub last_run_at: Option<BsonDateTime>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_duration_ms: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+    pub run_count: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lease_owner: Option<String>,
+}
+
+impl From<JobState> for JobStateResponse {
+    fn from(state: JobState) -> Self {
+        let running = state.is_leased(BsonDateTime::now());
+        Self {
+            name: state.name,
+            schedule: state.schedule,
+            enabled: state.enabled,
+            running,
+            next_run_at: state.next_run_at,
+            last_run_at: state.last_started_at,
+            last_duration_ms: state.last_duration_ms,
+            last_error: state.last_error,
+            run_count: state.run_count,
+            lease_owner: if running { state.lease_owner } else { None },
+        }
+    }
+}
+
+impl JobStateResponse {
+    pub fn from_vec(states: Vec<JobState>) -> Vec<Self> {
+        states.into_iter().map(JobStateResponse::from).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(seconds: i64) -> BsonDateTime {
+        BsonDateTime::from_millis(seconds * 1000)
+    }
+
+    fn job(next_run_at: i64, lease_expires_at: Option<i64>) -> JobState {
+        JobState {
+            name: "digest".to_string(),
+            schedule: "every 60s".to_string(),
+            enabled: true,
+            next_run_at: Some(at(next_run_at)),
+            last_started_at: None,
+            last_finished_at: None,
+            last_duration_ms: None,
+            last_error: None,
+            run_count: 0,
+            lease_owner: lease_expires_at.map(|_| "api-1".to_string()),
+            lease_expires_at: lease_expires_at.map(at),
+        }
+    }
+
+    #[test]
+    fn due_jobs_without_a_lease_can_be_taken() {
+        assert!(job(100, None).can_take_lease(at(100)));
+        assert!(job(100, None).can_take_lease(at(160)));
+        assert!(!job(100, None).can_take_lease(at(99)));
+    }
+
+    #[test]
+    fn a_held_lease_blocks_other_instances_until_it_expires() {
+        let leased = job(100, Some(700));
+        assert!(leased.is_leased(at(300)));
+        assert!(!leased.can_take_lease(at(300)));
+        assert!(!leased.is_leased(at(700)));
+        assert!(leased.can_take_lease(at(700)));
+    }
+
+    #[test]
+    fn jobs_without_a_next_run_are_never_taken() {
+        let mut finished = job(100, None);
+        finished.next_run_at = None;
+        assert!(!finished.can_take_lease(at(1_000)));
+    }
+}
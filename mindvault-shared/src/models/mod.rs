@@ -1 +1,22 @@
-pub mod tasks_model;
\ No newline at end of file
+pub mod tasks_model;
+pub mod reportees_model;
+pub mod reviews_model;
+pub mod feedback_model;
+pub mod review_templates_model;
+pub mod goals_model;
+pub mod reminders_model;
+pub mod jobs_model;
+pub mod notifications_model;
+pub mod digest_model;
+pub mod webhooks_model;
+pub mod saved_filters_model;
+pub mod events_model;
+pub mod transfer_model;
+pub mod backup_model;
+pub mod encryption_model;
+pub mod access_tokens_model;
+pub mod users_model;
+pub mod workspaces_model;
+pub mod workflows_model;
+pub mod boards_model;
+pub mod time_entries_model;
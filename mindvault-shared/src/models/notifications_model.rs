@@ -0,0 +1,218 @@
+use crate::utils::date_time_serde::{
+    serialize_bson_datetime_as_chrono_date, serialize_option_bson_datetime_as_chrono_date,
+};
+use bson::DateTime as BsonDateTime;
+use serde::{Deserialize, Serialize};
+
+/// What a notification is about; channels subscribe to one or more types
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub enum ENotificationType {
+    ReminderDue,
+    TaskOverdue,
+    DailyDigest,
+    WeeklyDigest,
+    Test,
+}
+
+/// Transport used by a channel
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "PascalCase")]
+pub enum EChannelTarget {
+    /// JSON POST signed with HMAC-SHA256 of the body when a secret is set
+    Webhook { url: String, secret: Option<String> },
+    /// Email via the SMTP server from the notification config
+    Email { to: String },
+    /// Local program, e.g. `notify-send`; `{title}` and `{body}` in args are substituted
+    Command { program: String, args: Vec<String> },
+}
+
+fn default_utc_now() -> BsonDateTime {
+    BsonDateTime::now()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotificationChannel {
+    #[serde(rename = "_id")]
+    pub id: i64,
+    /// User whose notifications the channel receives
+    pub owner_id: i64,
+    pub name: String,
+    pub target: EChannelTarget,
+    #[serde(default)]
+    pub notification_types: Vec<ENotificationType>,
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "default_utc_now")]
+    pub created_at: BsonDateTime,
+    #[serde(default)]
+    pub deleted: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotificationChannelResponse {
+    pub id: i64,
+    pub name: String,
+    pub target: EChannelTarget,
+    pub notification_types: Vec<ENotificationType>,
+    pub enabled: bool,
+    #[serde(
+        serialize_with = "serialize_bson_datetime_as_chrono_date"
+    )]
+    pub created_at: BsonDateTime,
+}
+
+impl From<NotificationChannel> for NotificationChannelResponse {
+    fn from(channel: NotificationChannel) -> Self {
+        // Never echo webhook secrets back to clients
+        let target = match channel.target {
+            EChannelTarget::Webhook { url, secret } => EChannelTarget::Webhook {
+                url,
+                secret: secret.map(|_| "********".to_string()),
+            },
+            other => other,
+        };
+        Self {
+            id: channel.id,
+            name: channel.name,
+            target,
+            notification_types: channel.notification_types,
+            enabled: channel.enabled,
+            created_at: channel.created_at,
+        }
+    }
+}
+
+impl NotificationChannelResponse {
+    pub fn from_vec(channels: Vec<NotificationChannel>) -> Vec<Self> {
+        channels
+            .into_iter()
+            .map(NotificationChannelResponse::from)
+            .collect()
+    }
+}
+
+/// Content of a notification, independent of how it is delivered
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotificationMessage {
+    pub notification_type: ENotificationType,
+    pub title: String,
+    pub body: String,
+    pub entity_type: Option<String>,
+    pub entity_id: Option<i64>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub enum EDeliveryState {
+    #[default]
+    Pending,
+    Delivered,
+    /// Gave up after the maximum number of attempts
+    DeadLetter,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeliveryAttempt {
+    pub attempted_at: BsonDateTime,
+    pub duration_ms: i64,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeliveryAttemptResponse {
+    #[serde(
+        serialize_with = "serialize_bson_datetime_as_chrono_date"
+    )]
+    pub attempted_at: BsonDateTime,
+    pub duration_ms: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl From<DeliveryAttempt> for DeliveryAttemptResponse {
+    fn from(attempt: DeliveryAttempt) -> Self {
+        Self {
+            attempted_at: attempt.attempted_at,
+            duration_ms: attempt.duration_ms,
+            error: attempt.error,
+        }
+    }
+}
+
+/// One message queued for one channel, with its delivery log
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotificationDelivery {
+    #[serde(rename = "_id")]
+    pub id: i64,
+    pub channel_id: i64,
+    pub message: NotificationMessage,
+    /// Prevents the same event from being queued twice, e.g. `TaskOverdue:12:2026-10-19`
+    pub dedupe_key: Option<String>,
+    #[serde(default)]
+    pub state: EDeliveryState,
+    #[serde(default)]
+    pub attempts: Vec<DeliveryAttempt>,
+    pub next_attempt_at: Option<BsonDateTime>,
+    pub delivered_at: Option<BsonDateTime>,
+    #[serde(default = "default_utc_now")]
+    pub created_at: BsonDateTime,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotificationDeliveryResponse {
+    pub id: i64,
+    pub channel_id: i64,
+    pub message: NotificationMessage,
+    pub state: EDeliveryState,
+    pub attempts: Vec<DeliveryAttemptResponse>,
+    #[serde(
+        serialize_with = "serialize_option_bson_datetime_as_chrono_date",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub next_attempt_at: Option<BsonDateTime>,
+    #[serde(
+        serialize_with = "serialize_option_bson_datetime_as_chrono_date",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub delivered_at: Option<BsonDateTime>,
+    #[serde(
+        serialize_with = "serialize_bson_datetime_as_chrono_date"
+    )]
+    pub created_at: BsonDateTime,
+}
+
+impl From<NotificationDelivery> for NotificationDeliveryResponse {
+    fn from(delivery: NotificationDelivery) -> Self {
+        Self {
+            id: delivery.id,
+            channel_id: delivery.channel_id,
+            message: delivery.message,
+            state: delivery.state,
+            attempts: delivery
+                .attempts
+                .into_iter()
+                .map(DeliveryAttemptResponse::from)
+                .collect(),
+            next_attempt_at: delivery.next_attempt_at,
+            delivered_at: delivery.delivered_at,
+            created_at: delivery.created_at,
+        }
+    }
+}
+
+impl NotificationDeliveryResponse {
+    pub fn from_vec(deliveries: Vec<NotificationDelivery>) -> Vec<Self> {
+        deliveries
+            .into_iter()
+            .map(NotificationDeliveryResponse::from)
+            .collect()
+    }
+}
@@ -0,0 +1,135 @@
+use crate::utils::date_time_serde::{
+    serialize_bson_datetime_as_chrono_date, serialize_option_bson_datetime_as_chrono_date,
+};
+use bson::DateTime as BsonDateTime;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub enum EReminderState {
+    #[default]
+    Pending,
+    Fired,
+    Dismissed,
+}
+
+/// When a reminder goes off
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "PascalCase")]
+pub enum EReminderTrigger {
+    /// A fixed instant, stored in UTC
+    Absolute { at: BsonDateTime },
+    /// An offset from the due date of the attached task; negative means before
+    #[serde(rename_all = "camelCase")]
+    BeforeDueDate { offset_minutes: i64 },
+}
+
+fn default_utc_now() -> BsonDateTime {
+    BsonDateTime::now()
+}
+
+fn default_time_zone() -> String {
+    crate::utils::time_zone::DEFAULT_TIME_ZONE.to_string()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Reminder {
+    #[serde(rename = "_id")]
+    pub id: i64,
+    pub owner_id: i64,
+    pub title: String,
+    pub note: Option<String>,
+    pub task_id: Option<i64>,
+    pub trigger: EReminderTrigger,
+    /// Time zone the reminder was set in, used to present and re-interpret wall-clock times
+    #[serde(default = "default_time_zone")]
+    pub time_zone: String,
+    /// Effective instant the reminder is due, kept in sync with the trigger and snoozes.
+    /// `None` for task-relative reminders whose task has no due date.
+    pub fire_at: Option<BsonDateTime>,
+    pub snoozed_until: Option<BsonDateTime>,
+    #[serde(default)]
+    pub state: EReminderState,
+    pub fired_at: Option<BsonDateTime>,
+    pub dismissed_at: Option<BsonDateTime>,
+    #[serde(default = "default_utc_now")]
+    pub created_at: BsonDateTime,
+    #[serde(default)]
+    pub deleted: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReminderResponse {
+    pub id: i64,
+    pub title: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub task_id: Option<i64>,
+    pub trigger: EReminderTrigger,
+    pub time_zone: String,
+    #[serde(
+        serialize_with = "serialize_option_bson_datetime_as_chrono_date",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub fire_at: Option<BsonDateTime>,
+    #[serde(
+        serialize_with = "serialize_option_bson_datetime_as_chrono_date",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub snoozed_until: Option<BsonDateTime>,
+    pub state: EReminderState,
+    #[serde(
+        serialize_with = "serialize_option_bson_datetime_as_chrono_date",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub fired_at: Option<BsonDateTime>,
+    #[serde(
+        serialize_with = "serialize_option_bson_datetime_as_chrono_date",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub dismissed_at: Option<BsonDateTime>,
+    #[serde(
+        serialize_with = "serialize_bson_datetime_as_chrono_date"
+    )]
+    pub created_at: BsonDateTime,
+}
+
+impl From<Reminder> for ReminderResponse {
+    fn from(reminder: Reminder) -> Self {
+        Self {
+            id: reminder.id,
+            title: reminder.title,
+            note: reminder.note,
+            task_id: reminder.task_id,
+            trigger: reminder.trigger,
+            time_zone: reminder.time_zone,
+            fire_at: reminder.fire_at,
+            snoozed_until: reminder.snoozed_until,
+            state: reminder.state,
+            fired_at: reminder.fired_at,
+            dismissed_at: reminder.dismissed_at,
+            created_at: reminder.created_at,
+        }
+    }
+}
+
+impl ReminderResponse {
+    pub fn from_vec(reminders: Vec<Reminder>) -> Vec<Self> {
+        reminders.into_iter().map(ReminderResponse::from).collect()
+    }
+}
+
+impl EReminderTrigger {
+    /// Instant the trigger points at, given the due date of the attached task (if any)
+    pub fn fire_at(&self, task_due_date: Option<BsonDateTime>) -> Option<BsonDateTime> {
+        match self {
+            EReminderTrigger::Absolute { at } => Some(*at),
+            EReminderTrigger::BeforeDueDate { offset_minutes } => task_due_date.map(|due| {
+                BsonDateTime::from_millis(due.timestamp_millis() + offset_minutes * 60_000)
+            }),
+        }
+    }
+}
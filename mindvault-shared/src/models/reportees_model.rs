@@ -0,0 +1,56 @@
+use crate::utils::date_time_serde::serialize_bson_datetime_as_chrono_date;
+use bson::DateTime as BsonDateTime;
+use serde::{Deserialize, Serialize};
+
+fn default_utc_now() -> BsonDateTime {
+    BsonDateTime::now()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Reportee {
+    #[serde(rename = "_id")]
+    pub id: i64,
+    /// User who manages the reportee
+    pub owner_id: i64,
+    pub name: String,
+    pub email: Option<String>,
+    pub designation: Option<String>,
+    #[serde(default = "default_utc_now")]
+    pub created_at: BsonDateTime,
+    #[serde(default)]
+    pub deleted: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReporteeResponse {
+    pub id: i64,
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub designation: Option<String>,
+    #[serde(
+        serialize_with = "serialize_bson_datetime_as_chrono_date"
+    )]
+    pub created_at: BsonDateTime,
+}
+
+impl From<Reportee> for ReporteeResponse {
+    fn from(reportee: Reportee) -> Self {
+        Self {
+            id: reportee.id,
+            name: reportee.name,
+            email: reportee.email,
+            designation: reportee.designation,
+            created_at: reportee.created_at,
+        }
+    }
+}
+
+impl ReporteeResponse {
+    pub fn from_vec(reportees: Vec<Reportee>) -> Vec<Self> {
+        reportees.into_iter().map(ReporteeResponse::from).collect()
+    }
+}
@@ -0,0 +1,302 @@
+use crate::utils::date_time_serde::serialize_bson_datetime_as_chrono_date;
+use bson::DateTime as BsonDateTime;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// Kind of answer a template question expects
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "PascalCase")]
+pub enum EAnswerType {
+    #[serde(rename_all = "camelCase")]
+    RatingScale { min: i32, max: i32 },
+    Text,
+    #[serde(rename_all = "camelCase")]
+    MultipleChoice {
+        options: Vec<String>,
+        #[serde(default)]
+        allow_multiple: bool,
+    },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TemplateQuestion {
+    /// Stable identifier answers refer to, unique within a template
+    pub key: String,
+    pub prompt: String,
+    pub answer_type: EAnswerType,
+    #[serde(default)]
+    pub required: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TemplateSection {
+    pub title: String,
+    #[serde(default)]
+    pub questions: Vec<TemplateQuestion>,
+}
+
+fn default_utc_now() -> BsonDateTime {
+    BsonDateTime::now()
+}
+
+/// One version of a review template. Editing a template stores a new version
+/// under the same `template_id`, so reviews keep pointing at the version they were created from.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReviewTemplate {
+    #[serde(rename = "_id")]
+    pub id: i64,
+    pub owner_id: i64,
+    pub template_id: i64,
+    pub version: i32,
+    /// Set on the newest version of each template only
+    #[serde(default)]
+    pub latest: bool,
+    pub name: String,
+    pub description: Option<String>,
+    #[serde(default)]
+    pub sections: Vec<TemplateSection>,
+    #[serde(default = "default_utc_now")]
+    pub created_at: BsonDateTime,
+    #[serde(default)]
+    pub deleted: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReviewTemplateResponse {
+    pub template_id: i64,
+    pub version: i32,
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub sections: Vec<TemplateSection>,
+    #[serde(
+        serialize_with = "serialize_bson_datetime_as_chrono_date"
+    )]
+    pub created_at: BsonDateTime,
+}
+
+impl From<ReviewTemplate> for ReviewTemplateResponse {
+    fn from(template: ReviewTemplate) -> Self {
+        Self {
+            template_id: template.template_id,
+            version: template.version,
+            name: template.name,
+            description: template.description,
+            sections: template.sections,
+            created_at: template.created_at,
+        }
+    }
+}
+
+impl ReviewTemplateResponse {
+    pub fn from_vec(templates: Vec<ReviewTemplate>) -> Vec<Self> {
+        templates.into_iter().map(ReviewTemplateResponse::from).collect()
+    }
+}
+
+/// Pins a review to the exact template version it was created from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReviewTemplateRef {
+    pub template_id: i64,
+    pub version: i32,
+}
+
+/// Value given for a template question; the JSON shape follows the question's answer type
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ReviewAnswerValue {
+    Rating(i32),
+    Text(String),
+    Choices(Vec<String>),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReviewAnswer {
+    pub question_key: String,
+    pub value: ReviewAnswerValue,
+}
+
+/// A single validation failure, addressed by the offending field path
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FieldError {
+    pub field: String,
+    pub message: String,
+}
+
+impl FieldError {
+    pub fn new(field: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            field: field.into(),
+            message: message.into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidationErrorResponse {
+    pub message: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<FieldError>,
+}
+
+impl ReviewTemplate {
+    /// Checks that a template definition is usable before it is stored
+    pub fn validate_definition(sections: &[TemplateSection]) -> Vec<FieldError> {
+        let mut errors = Vec::new();
+        let mut seen_keys = HashSet::new();
+
+        if sections.is_empty() {
+            errors.push(FieldError::new("sections", "at least one section is required"));
+        }
+
+        for (section_index, section) in sections.iter().enumerate() {
+            let section_path = format!("sections[{}]", section_index);
+            if section.title.trim().is_empty() {
+                errors.push(FieldError::new(
+                    format!("{}.title", section_path),
+                    "section title is required",
+                ));
+            }
+            if section.questions.is_empty() {
+                errors.push(FieldError::new(
+                    format!("{}.questions", section_path),
+                    "at least one question is required",
+                ));
+            }
+
+            for (question_index, question) in section.questions.iter().enumerate() {
+                let question_path = format!("{}.questions[{}]", section_path, question_index);
+                if question.key.trim().is_empty() {
+                    errors.push(FieldError::new(
+                        format!("{}.key", question_path),
+                        "question key is required",
+                    ));
+                } else if !seen_keys.insert(question.key.as_str()) {
+                    errors.push(FieldError::new(
+                        format!("{}.key", question_path),
+                        format!("duplicate question key '{}'", question.key),
+                    ));
+                }
+                if question.prompt.trim().is_empty() {
+                    errors.push(FieldError::new(
+                        format!("{}.prompt", question_path),
+                        "question prompt is required",
+                    ));
+                }
+                match &question.answer_type {
+                    EAnswerType::RatingScale { min, max } if min >= max => {
+                        errors.push(FieldError::new(
+                            format!("{}.answerType", question_path),
+                            "rating scale min must be lower than max",
+                        ));
+                    }
+                    EAnswerType::MultipleChoice { options, .. } if options.is_empty() => {
+                        errors.push(FieldError::new(
+                            format!("{}.answerType.options", question_path),
+                            "multiple choice questions need at least one option",
+                        ));
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        errors
+    }
+
+    fn questions(&self) -> impl Iterator<Item = &TemplateQuestion> {
+        self.sections.iter().flat_map(|section| section.questions.iter())
+    }
+
+    /// Validates answers against this template version. Required questions are
+    /// only enforced when `enforce_required` is set, so drafts may be partial.
+    pub fn validate_answers(
+        &self,
+        answers: &[ReviewAnswer],
+        enforce_required: bool,
+    ) -> Vec<FieldError> {
+        let mut errors = Vec::new();
+        let mut answered = HashSet::new();
+
+        for answer in answers {
+            let field = format!("answers.{}", answer.question_key);
+            if !answered.insert(answer.question_key.as_str()) {
+                errors.push(FieldError::new(field, "question answered more than once"));
+                continue;
+            }
+            let question = match self.questions().find(|q| q.key == answer.question_key) {
+                Some(question) => question,
+                None => {
+                    errors.push(FieldError::new(field, "unknown question for this template"));
+                    continue;
+                }
+            };
+            if let Some(message) = Self::check_answer(&question.answer_type, &answer.value) {
+                errors.push(FieldError::new(field, message));
+            }
+        }
+
+        if enforce_required {
+            for question in self.questions() {
+                if question.required && !answered.contains(question.key.as_str()) {
+                    errors.push(FieldError::new(
+                        format!("answers.{}", question.key),
+                        "answer is required",
+                    ));
+                }
+            }
+        }
+
+        errors
+    }
+
+    fn check_answer(answer_type: &EAnswerType, value: &ReviewAnswerValue) -> Option<String> {
+        match (answer_type, value) {
+            (EAnswerType::RatingScale { min, max }, ReviewAnswerValue::Rating(rating)) => {
+                if rating < min || rating > max {
+                    Some(format!("rating must be between {} and {}", min, max))
+                } else {
+                    None
+                }
+            }
+            (EAnswerType::RatingScale { .. }, _) => Some("expected a numeric rating".to_string()),
+            (EAnswerType::Text, ReviewAnswerValue::Text(text)) => {
+                if text.trim().is_empty() {
+                    Some("text answer cannot be empty".to_string())
+                } else {
+                    None
+                }
+            }
+            (EAnswerType::Text, _) => Some("expected a text answer".to_string()),
+            (EAnswerType::MultipleChoice { options, allow_multiple }, value) => {
+                let choices = match value {
+                    ReviewAnswerValue::Text(choice) => vec![choice.clone()],
+                    ReviewAnswerValue::Choices(choices) => choices.clone(),
+                    ReviewAnswerValue::Rating(_) => {
+                        return Some("expected one of the listed options".to_string());
+                    }
+                };
+                if choices.is_empty() {
+                    return Some("at least one option must be chosen".to_string());
+                }
+                if !allow_multiple && choices.len() > 1 {
+                    return Some("only one option may be chosen".to_string());
+                }
+                choices
+                    .iter()
+                    .find(|choice| !options.contains(choice))
+                    .map(|choice| {
+                        format!("'{}' is not one of the options {:?}", choice, options)
+                    })
+            }
+        }
+    }
+}
@@ -0,0 +1,184 @@
+use crate::models::review_templates_model::{ReviewAnswer, ReviewTemplateRef};
+use crate::utils::date_time_serde::{
+    serialize_bson_datetime_as_chrono_date, serialize_option_bson_datetime_as_chrono_date,
+};
+use crate::utils::field_encryption::{open, seal, EncryptedField, FieldEncryptionError};
+use bson::DateTime as BsonDateTime;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Lifecycle of a review: drafted by the reviewer, submitted, then acknowledged by the reportee.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub enum EReviewState {
+    #[default]
+    Draft,
+    Submitted,
+    Acknowledged,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReviewRating {
+    pub category: String,
+    pub rating: i32,
+    pub comment: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReviewFeedback {
+    #[serde(default)]
+    pub strengths: Option<String>,
+    #[serde(default)]
+    pub growth_areas: Option<String>,
+    #[serde(default)]
+    pub goals: Option<String>,
+}
+
+impl ReviewFeedback {
+    fn map_texts(
+        self,
+        f: impl Fn(String) -> Result<String, FieldEncryptionError>,
+    ) -> Result<Self, FieldEncryptionError> {
+        Ok(ReviewFeedback {
+            strengths: self.strengths.map(&f).transpose()?,
+            growth_areas: self.growth_areas.map(&f).transpose()?,
+            goals: self.goals.map(&f).transpose()?,
+        })
+    }
+
+    /// Copy as stored, encrypted when `reviews.feedback` encryption is enabled
+    pub fn sealed(&self) -> Result<Self, FieldEncryptionError> {
+        self.clone()
+            .map_texts(|text| seal(EncryptedField::ReviewFeedback, &text))
+    }
+
+    fn opened(self) -> Result<Self, FieldEncryptionError> {
+        self.map_texts(|text| open(EncryptedField::ReviewFeedback, text))
+    }
+}
+
+fn serialize_sealed_feedback<S: Serializer>(
+    feedback: &ReviewFeedback,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    feedback
+        .sealed()
+        .map_err(serde::ser::Error::custom)?
+        .serialize(serializer)
+}
+
+fn deserialize_opened_feedback<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<ReviewFeedback, D::Error> {
+    ReviewFeedback::deserialize(deserializer)?
+        .opened()
+        .map_err(serde::de::Error::custom)
+}
+
+fn default_utc_now() -> BsonDateTime {
+    BsonDateTime::now()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Review {
+    #[serde(rename = "_id")]
+    pub id: i64,
+    pub owner_id: i64,
+    /// Review cycle label, e.g. `2026-H2`
+    pub cycle: String,
+    pub reviewer: String,
+    pub reportee_id: i64,
+    #[serde(default)]
+    pub ratings: Vec<ReviewRating>,
+    #[serde(
+        default,
+        serialize_with = "serialize_sealed_feedback",
+        deserialize_with = "deserialize_opened_feedback"
+    )]
+    pub feedback: ReviewFeedback,
+    /// Template version the review was instantiated from, if any
+    #[serde(default)]
+    pub template: Option<ReviewTemplateRef>,
+    #[serde(default)]
+    pub answers: Vec<ReviewAnswer>,
+    #[serde(default)]
+    pub state: EReviewState,
+    #[serde(default = "default_utc_now")]
+    pub review_date: BsonDateTime,
+    pub submitted_at: Option<BsonDateTime>,
+    pub acknowledged_at: Option<BsonDateTime>,
+    #[serde(default = "default_utc_now")]
+    pub created_at: BsonDateTime,
+    #[serde(default)]
+    pub deleted: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReviewResponse {
+    pub id: i64,
+    pub cycle: String,
+    pub reviewer: String,
+    pub reportee_id: i64,
+    pub ratings: Vec<ReviewRating>,
+    pub feedback: ReviewFeedback,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub template: Option<ReviewTemplateRef>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub answers: Vec<ReviewAnswer>,
+    pub state: EReviewState,
+    #[serde(
+        serialize_with = "serialize_bson_datetime_as_chrono_date"
+    )]
+    pub review_date: BsonDateTime,
+    #[serde(
+        serialize_with = "serialize_option_bson_datetime_as_chrono_date",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub submitted_at: Option<BsonDateTime>,
+    #[serde(
+        serialize_with = "serialize_option_bson_datetime_as_chrono_date",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub acknowledged_at: Option<BsonDateTime>,
+    #[serde(
+        serialize_with = "serialize_bson_datetime_as_chrono_date"
+    )]
+    pub created_at: BsonDateTime,
+}
+
+impl From<Review> for ReviewResponse {
+    fn from(review: Review) -> Self {
+        Self {
+            id: review.id,
+            cycle: review.cycle,
+            reviewer: review.reviewer,
+            reportee_id: review.reportee_id,
+            ratings: review.ratings,
+            feedback: review.feedback,
+            template: review.template,
+            answers: review.answers,
+            state: review.state,
+            review_date: review.review_date,
+            submitted_at: review.submitted_at,
+            acknowledged_at: review.acknowledged_at,
+            created_at: review.created_at,
+        }
+    }
+}
+
+impl ReviewResponse {
+    pub fn from_vec(reviews: Vec<Review>) -> Vec<Self> {
+        reviews.into_iter().map(ReviewResponse::from).collect()
+    }
+}
+
+/// A reportee together with their reviews, oldest first.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReporteeTimelineResponse {
+    pub reportee: crate::models::reportees_model::ReporteeResponse,
+    pub reviews: Vec<ReviewResponse>,
+}
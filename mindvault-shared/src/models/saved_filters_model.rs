@@ -0,0 +1,81 @@
+use crate::models::tasks_model::{ETaskPriority, ETaskStatus, TaskResponse};
+use crate::utils::date_time_serde::serialize_bson_datetime_as_chrono_date;
+use bson::DateTime as BsonDateTime;
+use serde::{Deserialize, Serialize};
+
+/// Task conditions a saved filter applies; unset fields match everything
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskFilterCriteria {
+    pub query: Option<String>,
+    pub status: Option<ETaskStatus>,
+    pub priority: Option<ETaskPriority>,
+    #[serde(default)]
+    pub tag: Option<String>,
+}
+
+impl TaskFilterCriteria {
+    /// In-memory check used for live events; `query` is a case-insensitive name match
+    pub fn matches(&self, task: &TaskResponse) -> bool {
+        let query_matches = self.query.as_ref().is_none_or(|query| {
+            task.name.to_lowercase().contains(&query.to_lowercase())
+        });
+        query_matches
+            && self.status.as_ref().is_none_or(|status| *status == task.status)
+            && self
+                .priority
+                .as_ref()
+                .is_none_or(|priority| *priority == task.priority)
+            && self.tag.as_ref().is_none_or(|tag| {
+                let tag = tag.trim().to_lowercase();
+                task.tags.contains(&tag)
+            })
+    }
+}
+
+fn default_utc_now() -> BsonDateTime {
+    BsonDateTime::now()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SavedFilter {
+    #[serde(rename = "_id")]
+    pub id: i64,
+    pub owner_id: i64,
+    pub name: String,
+    pub criteria: TaskFilterCriteria,
+    #[serde(default = "default_utc_now")]
+    pub created_at: BsonDateTime,
+    #[serde(default)]
+    pub deleted: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SavedFilterResponse {
+    pub id: i64,
+    pub name: String,
+    pub criteria: TaskFilterCriteria,
+    #[serde(
+        serialize_with = "serialize_bson_datetime_as_chrono_date"
+    )]
+    pub created_at: BsonDateTime,
+}
+
+impl From<SavedFilter> for SavedFilterResponse {
+    fn from(filter: SavedFilter) -> Self {
+        Self {
+            id: filter.id,
+            name: filter.name,
+            criteria: filter.criteria,
+            created_at: filter.created_at,
+        }
+    }
+}
+
+impl SavedFilterResponse {
+    pub fn from_vec(filters: Vec<SavedFilter>) -> Vec<Self> {
+        filters.into_iter().map(SavedFilterResponse::from).collect()
+    }
+}
@@ -1,34 +1,155 @@
 use crate::utils::date_time_serde::{
-    serialize_bson_datetime_as_chrono_date, serialize_option_bson_datetime_as_chrono_date,
+    date_time_response_schema, iso8601_response_schema, serialize_bson_datetime_as_chrono_date,
+    serialize_date_time_as_iso8601, serialize_option_bson_datetime_as_chrono_date,
+    serialize_option_date_time_as_iso8601,
+};
+use crate::models::workspaces_model::Workspace;
+use crate::utils::quick_add::QuickAddParse;
+use crate::utils::field_encryption::{
+    deserialize_opened_option, serialize_sealed_option, EncryptedField,
 };
 use bson::DateTime as BsonDateTime;
-use serde::{Deserialize, Serialize};
+use chrono::{DateTime, FixedOffset, NaiveDate, SecondsFormat};
+use chrono_tz::Tz;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::str::FromStr;
+use utoipa::ToSchema;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Declared from least to most pressing, so the derived ordering sorts by importance
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "PascalCase")]
 pub enum ETaskPriority {
+    Low,
+    #[default]
     Normal,
     High,
+    Urgent,
 }
 
-impl Default for ETaskPriority {
-    fn default() -> Self {
-        ETaskPriority::Normal
+impl ETaskPriority {
+    pub const ALL: [ETaskPriority; 4] = [
+        ETaskPriority::Low,
+        ETaskPriority::Normal,
+        ETaskPriority::High,
+        ETaskPriority::Urgent,
+    ];
+
+    /// Position on the scale, 0 for Low up to 3 for Urgent
+    pub fn rank(&self) -> u8 {
+        match self {
+            ETaskPriority::Low => 0,
+            ETaskPriority::Normal => 1,
+            ETaskPriority::High => 2,
+            ETaskPriority::Urgent => 3,
+        }
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "PascalCase")]
 pub enum ETaskStatus {
+    #[default]
     NotStarted,
     Pending,
     InProgress,
     Completed,
+    /// Closed without being done. Unlike deletion the task stays visible and searchable.
+    #[serde(alias = "WontDo")]
+    Cancelled,
+}
+
+/// What a status means for the task, whatever it is called in a workflow
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "PascalCase")]
+pub enum EStatusCategory {
+    Todo,
+    Active,
+    Done,
+    Cancelled,
+}
+
+impl EStatusCategory {
+    /// Done and cancelled tasks no longer need attention
+    pub fn is_closed(&self) -> bool {
+        matches!(self, EStatusCategory::Done | EStatusCategory::Cancelled)
+    }
+
+    /// Built-in status a task gets when it enters a workflow status of this category
+    pub fn default_status(&self) -> ETaskStatus {
+        match self {
+            EStatusCategory::Todo => ETaskStatus::NotStarted,
+            EStatusCategory::Active => ETaskStatus::InProgress,
+            EStatusCategory::Done => ETaskStatus::Completed,
+            EStatusCategory::Cancelled => ETaskStatus::Cancelled,
+        }
+    }
+}
+
+impl ETaskStatus {
+    pub const ALL: [ETaskStatus; 5] = [
+        ETaskStatus::NotStarted,
+        ETaskStatus::Pending,
+        ETaskStatus::InProgress,
+        ETaskStatus::Completed,
+        ETaskStatus::Cancelled,
+    ];
+
+    pub fn category(&self) -> EStatusCategory {
+        match self {
+            ETaskStatus::NotStarted | ETaskStatus::Pending => EStatusCategory::Todo,
+            ETaskStatus::InProgress => EStatusCategory::Active,
+            ETaskStatus::Completed => EStatusCategory::Done,
+            ETaskStatus::Cancelled => EStatusCategory::Cancelled,
+        }
+    }
+
+    /// Statuses of tasks that are finished with, one way or another
+    pub fn closed() -> Vec<ETaskStatus> {
+        vec![ETaskStatus::Completed, ETaskStatus::Cancelled]
+    }
+}
+
+/// Lowercases a label and drops separators so "In Progress", "in_progress" and "InProgress" agree
+fn normalize_label(s: &str) -> String {
+    s.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
 }
 
-impl Default for ETaskStatus {
-    fn default() -> Self {
-        ETaskStatus::NotStarted
+impl FromStr for ETaskPriority {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match normalize_label(s).as_str() {
+            "low" => Ok(ETaskPriority::Low),
+            "normal" => Ok(ETaskPriority::Normal),
+            "high" => Ok(ETaskPriority::High),
+            "urgent" => Ok(ETaskPriority::Urgent),
+            _ => Err(format!(
+                "invalid priority '{}', expected Low, Normal, High or Urgent",
+                s.trim()
+            )),
+        }
+    }
+}
+
+impl FromStr for ETaskStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match normalize_label(s).as_str() {
+            "notstarted" => Ok(ETaskStatus::NotStarted),
+            "pending" => Ok(ETaskStatus::Pending),
+            "inprogress" => Ok(ETaskStatus::InProgress),
+            "completed" => Ok(ETaskStatus::Completed),
+            "cancelled" | "canceled" | "wontdo" => Ok(ETaskStatus::Cancelled),
+            _ => Err(format!(
+                "invalid status '{}', expected NotStarted, Pending, InProgress, Completed or \
+                 Cancelled",
+                s.trim()
+            )),
+        }
     }
 }
 
@@ -39,47 +160,186 @@ fn default_utc_now() -> BsonDateTime {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Task {
-    #[serde(rename = "_id")]
+    /// Number within the owner's vault or the workspace, from its own sequence; the
+    /// document's `_id` is not used by the application
+    #[serde(rename = "taskId")]
     pub id: i64,
+    /// User whose private vault holds the task; for workspace tasks, the member who created it
+    pub owner_id: i64,
+    /// Workspace that owns the task instead of `owner_id`'s private vault
+    #[serde(default)]
+    pub workspace_id: Option<i64>,
+    /// Workspace member the task is assigned to
+    #[serde(default)]
+    pub assignee_id: Option<i64>,
+    /// Reportees the task is about
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub reportee_ids: Vec<i64>,
     pub name: String,
     #[serde(default)]
     pub priority: ETaskPriority,
     #[serde(default)]
     pub status: ETaskStatus,
+    /// Status in the vault's custom workflow; `status` follows its category
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub workflow_status: Option<String>,
+    /// Position in its board column; ranks compare as plain strings and unranked tasks come
+    /// last, by id
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rank: Option<String>,
+    /// An instant when `due_has_time`, otherwise a floating calendar day stored at midnight UTC
     pub due_date: Option<BsonDateTime>,
+    #[serde(default)]
+    pub due_has_time: bool,
     #[serde(default = "default_utc_now")]
     pub created_at: BsonDateTime,
+    /// Last change to the task; older tasks only have `created_at`
+    #[serde(default)]
+    pub updated_at: Option<BsonDateTime>,
+    /// When the task last moved to Completed
+    #[serde(default)]
+    pub completed_at: Option<BsonDateTime>,
+    /// Trimmed and lowercased
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// RFC 5545 RRULE value, e.g. `FREQ=WEEKLY;BYDAY=MO`
+    #[serde(default)]
+    pub recurrence: Option<String>,
+    /// Expected effort, compared against `tracked_seconds`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub estimate_minutes: Option<u32>,
+    /// Time of the task's stopped timers and manual entries, kept in step with them
+    #[serde(default)]
+    pub tracked_seconds: i64,
+    /// Encrypted at rest when `tasks.notes` encryption is enabled
+    #[serde(
+        default,
+        serialize_with = "serialize_sealed_notes",
+        deserialize_with = "deserialize_opened_notes"
+    )]
+    pub notes: Option<String>,
+    /// Blind index of the words in `notes`, only kept while notes are encrypted
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub notes_index: Vec<String>,
+    /// Identifier in the system the task was imported from, e.g. `taskwarrior:<uuid>`
+    #[serde(default)]
+    pub external_id: Option<String>,
     #[serde(default)]
     pub deleted: Option<bool>,
 }
 
-#[derive(Debug, Clone, Serialize)]
+fn serialize_sealed_notes<S: Serializer>(notes: &Option<String>, serializer: S) -> Result<S::Ok, S::Error> {
+    serialize_sealed_option(EncryptedField::TaskNotes, notes, serializer)
+}
+
+fn deserialize_opened_notes<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<String>, D::Error> {
+    deserialize_opened_option(EncryptedField::TaskNotes, deserializer)
+}
+
+impl Task {
+    /// When the task was last touched, falling back to its creation time
+    pub fn last_touched_at(&self) -> BsonDateTime {
+        self.updated_at.unwrap_or(self.created_at)
+    }
+
+    /// The calendar day the task is due on in `tz`; floating days are the same everywhere
+    pub fn due_day(&self, tz: Tz) -> Option<NaiveDate> {
+        due_day(self.due_date, self.due_has_time, tz)
+    }
+
+    /// Users who may hear about the task through their notification channels and webhooks:
+    /// the owner of a private task, or the current members of the task's `workspace`
+    pub fn audience(&self, workspace: Option<&Workspace>) -> Vec<i64> {
+        match (self.workspace_id, workspace) {
+            (None, _) => vec![self.owner_id],
+            (Some(workspace_id), Some(workspace)) if workspace.id == workspace_id => {
+                workspace.members.iter().map(|member| member.user_id).collect()
+            }
+            (Some(_), _) => Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct TaskResponse {
     pub id: i64,
+    pub owner_id: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workspace_id: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub assignee_id: Option<i64>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub reportee_ids: Vec<i64>,
     pub name: String,
     pub priority: ETaskPriority,
     pub status: ETaskStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workflow_status: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rank: Option<String>,
     #[serde(
         serialize_with = "serialize_option_bson_datetime_as_chrono_date",
         skip_serializing_if = "Option::is_none"
     )]
+    #[schema(schema_with = date_time_response_schema)]
     pub due_date: Option<BsonDateTime>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub due_has_time: bool,
     #[serde(
         serialize_with = "serialize_bson_datetime_as_chrono_date"
     )]
+    #[schema(schema_with = date_time_response_schema)]
     pub created_at: BsonDateTime,
+    #[serde(
+        serialize_with = "serialize_option_bson_datetime_as_chrono_date",
+        skip_serializing_if = "Option::is_none"
+    )]
+    #[schema(schema_with = date_time_response_schema)]
+    pub updated_at: Option<BsonDateTime>,
+    #[serde(
+        serialize_with = "serialize_option_bson_datetime_as_chrono_date",
+        skip_serializing_if = "Option::is_none"
+    )]
+    #[schema(schema_with = date_time_response_schema)]
+    pub completed_at: Option<BsonDateTime>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recurrence: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub estimate_minutes: Option<u32>,
+    pub tracked_seconds: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub external_id: Option<String>,
 }
 
 impl From<Task> for TaskResponse {
     fn from(task: Task) -> Self {
         Self {
             id: task.id,
+            owner_id: task.owner_id,
+            workspace_id: task.workspace_id,
+            assignee_id: task.assignee_id,
+            reportee_ids: task.reportee_ids,
             name: task.name,
             priority: task.priority,
             status: task.status,
+            workflow_status: task.workflow_status,
+            rank: task.rank,
             due_date: task.due_date,
+            due_has_time: task.due_has_time,
             created_at: task.created_at,
+            updated_at: task.updated_at,
+            completed_at: task.completed_at,
+            tags: task.tags,
+            recurrence: task.recurrence,
+            estimate_minutes: task.estimate_minutes,
+            tracked_seconds: task.tracked_seconds,
+            notes: task.notes,
+            external_id: task.external_id,
         }
     }
 }
@@ -89,3 +349,108 @@ impl TaskResponse {
         tasks.into_iter().map(TaskResponse::from).collect()
     }
 }
+
+/// A quick-add text as it was read, with the task created from it unless previewing
+#[derive(Debug, Clone, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct QuickAddResponse<T> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub task: Option<T>,
+    pub parsed: QuickAddParse,
+}
+
+fn due_day(due_date: Option<BsonDateTime>, due_has_time: bool, tz: Tz) -> Option<NaiveDate> {
+    let due = due_date?.to_chrono();
+    Some(if due_has_time {
+        due.with_timezone(&tz).date_naive()
+    } else {
+        due.date_naive()
+    })
+}
+
+/// `TaskResponse` as served under `/v2`: timestamps are ISO-8601 with the user's UTC offset
+/// instead of day-first `DD/MM/YY HH:MM:SS` strings in UTC
+#[derive(Debug, Clone, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskResponseV2 {
+    pub id: i64,
+    pub owner_id: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workspace_id: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub assignee_id: Option<i64>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub reportee_ids: Vec<i64>,
+    pub name: String,
+    pub priority: ETaskPriority,
+    pub status: ETaskStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workflow_status: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rank: Option<String>,
+    /// `YYYY-MM-DD` for due days, an ISO-8601 timestamp for due times
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(examples("2025-07-27", "2025-07-27T17:00:00+02:00"))]
+    pub due_date: Option<String>,
+    #[serde(serialize_with = "serialize_date_time_as_iso8601")]
+    #[schema(schema_with = iso8601_response_schema)]
+    pub created_at: DateTime<FixedOffset>,
+    #[serde(
+        serialize_with = "serialize_option_date_time_as_iso8601",
+        skip_serializing_if = "Option::is_none"
+    )]
+    #[schema(schema_with = iso8601_response_schema)]
+    pub updated_at: Option<DateTime<FixedOffset>>,
+    #[serde(
+        serialize_with = "serialize_option_date_time_as_iso8601",
+        skip_serializing_if = "Option::is_none"
+    )]
+    #[schema(schema_with = iso8601_response_schema)]
+    pub completed_at: Option<DateTime<FixedOffset>>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recurrence: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub estimate_minutes: Option<u32>,
+    pub tracked_seconds: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub external_id: Option<String>,
+}
+
+impl TaskResponseV2 {
+    /// Shows the task's timestamps in `tz`
+    pub fn new(task: TaskResponse, tz: Tz) -> Self {
+        let local = |at: BsonDateTime| at.to_chrono().with_timezone(&tz).fixed_offset();
+        let due_date = if task.due_has_time {
+            task.due_date
+                .map(|due| local(due).to_rfc3339_opts(SecondsFormat::Secs, false))
+        } else {
+            due_day(task.due_date, false, tz).map(|day| day.format("%Y-%m-%d").to_string())
+        };
+        Self {
+            id: task.id,
+            owner_id: task.owner_id,
+            workspace_id: task.workspace_id,
+            assignee_id: task.assignee_id,
+            reportee_ids: task.reportee_ids,
+            name: task.name,
+            priority: task.priority,
+            status: task.status,
+            workflow_status: task.workflow_status,
+            rank: task.rank,
+            due_date,
+            created_at: local(task.created_at),
+            updated_at: task.updated_at.map(local),
+            completed_at: task.completed_at.map(local),
+            tags: task.tags,
+            recurrence: task.recurrence,
+            estimate_minutes: task.estimate_minutes,
+            tracked_seconds: task.tracked_seconds,
+            notes: task.notes,
+            external_id: task.external_id,
+        }
+    }
+}
@@ -0,0 +1,177 @@
+use crate::utils::date_time_serde::{
+    date_time_response_schema, serialize_bson_datetime_as_chrono_date,
+    serialize_option_bson_datetime_as_chrono_date,
+};
+use bson::DateTime as BsonDateTime;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+fn default_utc_now() -> BsonDateTime {
+    BsonDateTime::now()
+}
+
+/// Time a user spent on a task, from a timer or entered by hand. A user has at most one
+/// running timer, across all vaults.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TimeEntry {
+    #[serde(rename = "_id")]
+    pub id: i64,
+    /// User who tracked the time
+    pub owner_id: i64,
+    /// Workspace of the task, `None` for a task in `owner_id`'s private vault
+    #[serde(default)]
+    pub workspace_id: Option<i64>,
+    pub task_id: i64,
+    pub started_at: BsonDateTime,
+    /// `None` while the timer runs
+    pub ended_at: Option<BsonDateTime>,
+    /// Whole seconds from start to end; 0 while the timer runs
+    #[serde(default)]
+    pub duration_seconds: i64,
+    pub note: Option<String>,
+    /// Entered by hand rather than with a timer
+    #[serde(default)]
+    pub manual: bool,
+    #[serde(default = "default_utc_now")]
+    pub created_at: BsonDateTime,
+    #[serde(default)]
+    pub deleted: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TimeEntryResponse {
+    pub id: i64,
+    pub owner_id: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workspace_id: Option<i64>,
+    pub task_id: i64,
+    #[serde(serialize_with = "serialize_bson_datetime_as_chrono_date")]
+    #[schema(schema_with = date_time_response_schema)]
+    pub started_at: BsonDateTime,
+    #[serde(
+        serialize_with = "serialize_option_bson_datetime_as_chrono_date",
+        skip_serializing_if = "Option::is_none"
+    )]
+    #[schema(schema_with = date_time_response_schema)]
+    pub ended_at: Option<BsonDateTime>,
+    pub running: bool,
+    /// Seconds tracked so far for a running timer
+    pub duration_seconds: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    pub manual: bool,
+}
+
+impl From<TimeEntry> for TimeEntryResponse {
+    fn from(entry: TimeEntry) -> Self {
+        let duration_seconds = match entry.ended_at {
+            Some(_) => entry.duration_seconds,
+            None => {
+                let elapsed = BsonDateTime::now().timestamp_millis()
+                    - entry.started_at.timestamp_millis();
+                (elapsed / 1000).max(0)
+            }
+        };
+        Self {
+            id: entry.id,
+            owner_id: entry.owner_id,
+            workspace_id: entry.workspace_id,
+            task_id: entry.task_id,
+            started_at: entry.started_at,
+            ended_at: entry.ended_at,
+            running: entry.ended_at.is_none(),
+            duration_seconds,
+            note: entry.note,
+            manual: entry.manual,
+        }
+    }
+}
+
+impl TimeEntryResponse {
+    pub fn from_vec(entries: Vec<TimeEntry>) -> Vec<Self> {
+        entries.into_iter().map(TimeEntryResponse::from).collect()
+    }
+}
+
+/// The timer a start or stop left running, if any, and the one it stopped
+#[derive(Debug, Clone, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TimerResponse {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub running: Option<TimeEntryResponse>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stopped: Option<TimeEntryResponse>,
+}
+
+/// A task's time entries, newest first, with its estimate against the time tracked
+#[derive(Debug, Clone, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskTimeResponse {
+    pub task_id: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub estimate_minutes: Option<u32>,
+    /// Time of stopped timers and manual entries
+    pub tracked_seconds: i64,
+    /// Estimate left after the tracked time; negative once the task runs over
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remaining_seconds: Option<i64>,
+    pub entries: Vec<TimeEntryResponse>,
+}
+
+impl TaskTimeResponse {
+    pub fn new(
+        task_id: i64,
+        estimate_minutes: Option<u32>,
+        tracked_seconds: i64,
+        entries: Vec<TimeEntry>,
+    ) -> Self {
+        Self {
+            task_id,
+            estimate_minutes,
+            tracked_seconds,
+            remaining_seconds: estimate_minutes
+                .map(|minutes| i64::from(minutes) * 60 - tracked_seconds),
+            entries: TimeEntryResponse::from_vec(entries),
+        }
+    }
+}
+
+/// How `GET /reports/time` groups tracked time
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ETimeReportGroup {
+    /// Per tag of the task; time on a task with several tags counts towards each
+    Tag,
+    #[default]
+    Task,
+    /// Per day the entry started on, in the caller's time zone
+    Day,
+}
+
+/// One group of a time report, as the database aggregated it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TimeReportRow {
+    /// Tag, `YYYY-MM-DD` day or task id; `None` for time on untagged tasks
+    pub key: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub task_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub estimate_minutes: Option<u32>,
+    pub tracked_seconds: i64,
+    pub entry_count: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TimeReportResponse {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workspace_id: Option<i64>,
+    pub group_by: ETimeReportGroup,
+    /// Time in the period; less than the sum of the rows when tasks have several tags
+    pub tracked_seconds: i64,
+    pub entry_count: i64,
+    pub rows: Vec<TimeReportRow>,
+}
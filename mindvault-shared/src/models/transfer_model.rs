@@ -0,0 +1,133 @@
+use crate::models::tasks_model::{ETaskPriority, ETaskStatus, Task};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ETransferFormat {
+    Csv,
+    Json,
+    Md,
+    Ics,
+    /// One task per line in the todo.txt format
+    TodoTxt,
+    /// JSON as written by `task export`
+    Taskwarrior,
+}
+
+impl ETransferFormat {
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            ETransferFormat::Csv => "text/csv; charset=utf-8",
+            ETransferFormat::Json => "application/json",
+            ETransferFormat::Md => "text/markdown; charset=utf-8",
+            ETransferFormat::Ics => "text/calendar; charset=utf-8",
+            ETransferFormat::TodoTxt => "text/plain; charset=utf-8",
+            ETransferFormat::Taskwarrior => "application/json",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ETransferFormat::Csv => "csv",
+            ETransferFormat::Json => "json",
+            ETransferFormat::Md => "md",
+            ETransferFormat::Ics => "ics",
+            ETransferFormat::TodoTxt => "txt",
+            ETransferFormat::Taskwarrior => "json",
+        }
+    }
+
+    /// Guesses the format of an upload from its Content-Type header
+    pub fn from_content_type(content_type: &str) -> Option<Self> {
+        let mime = content_type.split(';').next()?.trim().to_ascii_lowercase();
+        match mime.as_str() {
+            "text/csv" => Some(ETransferFormat::Csv),
+            "application/json" => Some(ETransferFormat::Json),
+            "text/markdown" => Some(ETransferFormat::Md),
+            "text/calendar" => Some(ETransferFormat::Ics),
+            _ => None,
+        }
+    }
+}
+
+/// Flat, round-trippable shape of a task used by the CSV and JSON exports
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskExportRecord {
+    pub id: i64,
+    pub name: String,
+    pub priority: ETaskPriority,
+    pub status: ETaskStatus,
+    /// `YYYY-MM-DD`
+    pub due_date: Option<String>,
+    /// RFC 3339
+    pub created_at: String,
+    pub completed_at: Option<String>,
+}
+
+impl From<&Task> for TaskExportRecord {
+    fn from(task: &Task) -> Self {
+        Self {
+            id: task.id,
+            name: task.name.clone(),
+            priority: task.priority.clone(),
+            status: task.status.clone(),
+            due_date: task
+                .due_date
+                .map(|due| due.to_chrono().date_naive().format("%Y-%m-%d").to_string()),
+            created_at: task.created_at.to_chrono().to_rfc3339(),
+            completed_at: task.completed_at.map(|at| at.to_chrono().to_rfc3339()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub enum EImportOutcome {
+    /// Task was inserted
+    Created,
+    /// Existing task with the same external id was updated
+    Updated,
+    /// Dry run: task would be inserted
+    Valid,
+    /// Dry run: existing task with the same external id would be updated
+    Matched,
+    /// Same name and due date as an existing task, or repeats an earlier row
+    Duplicate,
+    Invalid,
+}
+
+/// What happened to one row of an import; rows are numbered from 1 as they appear in the upload
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportRowReport {
+    pub row: usize,
+    pub outcome: EImportOutcome,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub due_date: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub priority: Option<ETaskPriority>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<ETaskStatus>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub external_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub task_id: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportReport {
+    pub format: ETransferFormat,
+    pub dry_run: bool,
+    pub total_rows: usize,
+    pub created: usize,
+    pub updated: usize,
+    pub duplicates: usize,
+    pub invalid: usize,
+    pub rows: Vec<ImportRowReport>,
+}
@@ -0,0 +1,118 @@
+use crate::utils::date_time_serde::{
+    serialize_bson_datetime_as_chrono_date, serialize_option_bson_datetime_as_chrono_date,
+};
+use crate::utils::date_input::{DatePreferences, EDateOrder};
+use crate::utils::time_zone::DEFAULT_TIME_ZONE;
+use bson::DateTime as BsonDateTime;
+use serde::{Deserialize, Serialize};
+
+fn default_utc_now() -> BsonDateTime {
+    BsonDateTime::now()
+}
+
+/// Someone with their own vault. Tasks, reminders, saved filters and goals belong to exactly
+/// one user and are never visible to another.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct User {
+    #[serde(rename = "_id")]
+    pub id: i64,
+    /// Unique, lowercased
+    pub username: String,
+    #[serde(default)]
+    pub display_name: Option<String>,
+    /// Disabled users keep their data but every one of their tokens is rejected
+    #[serde(default)]
+    pub disabled: bool,
+    #[serde(default = "default_utc_now")]
+    pub created_at: BsonDateTime,
+    #[serde(default)]
+    pub disabled_at: Option<BsonDateTime>,
+    /// IANA name used to read and show the user's dates; UTC when unset
+    #[serde(default)]
+    pub time_zone: Option<String>,
+    #[serde(default)]
+    pub date_order: Option<EDateOrder>,
+}
+
+impl User {
+    pub fn date_preferences(&self) -> DatePreferences {
+        DatePreferences {
+            time_zone: self
+                .time_zone
+                .clone()
+                .unwrap_or_else(|| DEFAULT_TIME_ZONE.to_string()),
+            date_order: self.date_order,
+        }
+    }
+}
+
+const MAX_USERNAME_LENGTH: usize = 64;
+
+/// Trims and lowercases a username, rejecting anything but letters, digits, `.`, `-` and `_`
+pub fn normalize_username(username: &str) -> Result<String, String> {
+    let username = username.trim().to_lowercase();
+    if username.is_empty() {
+        return Err("Username is required".to_string());
+    }
+    if username.len() > MAX_USERNAME_LENGTH {
+        return Err(format!(
+            "Username must be at most {} characters",
+            MAX_USERNAME_LENGTH
+        ));
+    }
+    if !username
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_'))
+    {
+        return Err(format!(
+            "Username '{}' may only contain letters, digits, '.', '-' and '_'",
+            username
+        ));
+    }
+    Ok(username)
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserResponse {
+    pub id: i64,
+    pub username: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub display_name: Option<String>,
+    pub disabled: bool,
+    #[serde(
+        serialize_with = "serialize_bson_datetime_as_chrono_date"
+    )]
+    pub created_at: BsonDateTime,
+    #[serde(
+        serialize_with = "serialize_option_bson_datetime_as_chrono_date",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub disabled_at: Option<BsonDateTime>,
+    pub time_zone: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date_order: Option<EDateOrder>,
+}
+
+impl From<User> for UserResponse {
+    fn from(user: User) -> Self {
+        let preferences = user.date_preferences();
+        Self {
+            id: user.id,
+            username: user.username,
+            display_name: user.display_name,
+            disabled: user.disabled,
+            created_at: user.created_at,
+            disabled_at: user.disabled_at,
+            time_zone: preferences.time_zone,
+            date_order: preferences.date_order,
+        }
+    }
+}
+
+impl UserResponse {
+    pub fn from_vec(users: Vec<User>) -> Vec<Self> {
+        users.into_iter().map(UserResponse::from).collect()
+    }
+}
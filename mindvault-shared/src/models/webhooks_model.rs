@@ -0,0 +1,208 @@
+use crate::models::notifications_model::{DeliveryAttempt, DeliveryAttemptResponse, EDeliveryState};
+use crate::utils::date_time_serde::{
+    serialize_bson_datetime_as_chrono_date, serialize_option_bson_datetime_as_chrono_date,
+};
+use crate::utils::field_encryption::{deserialize_opened, serialize_sealed, EncryptedField};
+use bson::DateTime as BsonDateTime;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Task lifecycle events a webhook can subscribe to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ETaskEvent {
+    #[serde(rename = "task.created")]
+    Created,
+    #[serde(rename = "task.updated")]
+    Updated,
+    /// Sent in addition to `task.updated` when a task moves to Completed
+    #[serde(rename = "task.completed")]
+    Completed,
+    #[serde(rename = "task.deleted")]
+    Deleted,
+}
+
+impl ETaskEvent {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ETaskEvent::Created => "task.created",
+            ETaskEvent::Updated => "task.updated",
+            ETaskEvent::Completed => "task.completed",
+            ETaskEvent::Deleted => "task.deleted",
+        }
+    }
+}
+
+fn default_utc_now() -> BsonDateTime {
+    BsonDateTime::now()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookSubscription {
+    #[serde(rename = "_id")]
+    pub id: i64,
+    /// User whose tasks the subscription hears about
+    pub owner_id: i64,
+    pub url: String,
+    pub secret: Option<String>,
+    /// Events to send; empty means every event
+    #[serde(default)]
+    pub events: Vec<ETaskEvent>,
+    pub description: Option<String>,
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "default_utc_now")]
+    pub created_at: BsonDateTime,
+    #[serde(default)]
+    pub deleted: Option<bool>,
+}
+
+impl WebhookSubscription {
+    pub fn wants(&self, event: ETaskEvent) -> bool {
+        self.events.is_empty() || self.events.contains(&event)
+    }
+
+    /// Whether an event about a task with the given `Task::audience` goes to this subscription
+    pub fn receives(&self, event: ETaskEvent, audience: &[i64]) -> bool {
+        self.wants(event) && audience.contains(&self.owner_id)
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookSubscriptionResponse {
+    pub id: i64,
+    pub url: String,
+    pub has_secret: bool,
+    pub events: Vec<ETaskEvent>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub enabled: bool,
+    #[serde(
+        serialize_with = "serialize_bson_datetime_as_chrono_date"
+    )]
+    pub created_at: BsonDateTime,
+}
+
+impl From<WebhookSubscription> for WebhookSubscriptionResponse {
+    fn from(subscription: WebhookSubscription) -> Self {
+        Self {
+            id: subscription.id,
+            url: subscription.url,
+            has_secret: subscription.secret.is_some(),
+            events: subscription.events,
+            description: subscription.description,
+            enabled: subscription.enabled,
+            created_at: subscription.created_at,
+        }
+    }
+}
+
+impl WebhookSubscriptionResponse {
+    pub fn from_vec(subscriptions: Vec<WebhookSubscription>) -> Vec<Self> {
+        subscriptions
+            .into_iter()
+            .map(WebhookSubscriptionResponse::from)
+            .collect()
+    }
+}
+
+/// One event queued for one subscription. The JSON body is stored as sent so that
+/// retries and replays deliver exactly the same bytes, and encrypted at rest along with the
+/// task notes it contains.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookDelivery {
+    #[serde(rename = "_id")]
+    pub id: i64,
+    pub subscription_id: i64,
+    pub event_id: i64,
+    pub event: ETaskEvent,
+    pub task_id: i64,
+    #[serde(
+        serialize_with = "serialize_sealed_payload",
+        deserialize_with = "deserialize_opened_payload"
+    )]
+    pub payload: String,
+    #[serde(default)]
+    pub state: EDeliveryState,
+    #[serde(default)]
+    pub attempts: Vec<DeliveryAttempt>,
+    pub next_attempt_at: Option<BsonDateTime>,
+    pub delivered_at: Option<BsonDateTime>,
+    #[serde(default = "default_utc_now")]
+    pub created_at: BsonDateTime,
+    /// Task change stream event the delivery was queued from
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub change_id: Option<String>,
+}
+
+fn serialize_sealed_payload<S: Serializer>(
+    payload: &str,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serialize_sealed(EncryptedField::WebhookPayload, payload, serializer)
+}
+
+fn deserialize_opened_payload<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<String, D::Error> {
+    deserialize_opened(EncryptedField::WebhookPayload, deserializer)
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookDeliveryResponse {
+    pub id: i64,
+    pub subscription_id: i64,
+    pub event_id: i64,
+    pub event: ETaskEvent,
+    pub task_id: i64,
+    pub payload: String,
+    pub state: EDeliveryState,
+    pub attempts: Vec<DeliveryAttemptResponse>,
+    #[serde(
+        serialize_with = "serialize_option_bson_datetime_as_chrono_date",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub next_attempt_at: Option<BsonDateTime>,
+    #[serde(
+        serialize_with = "serialize_option_bson_datetime_as_chrono_date",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub delivered_at: Option<BsonDateTime>,
+    #[serde(
+        serialize_with = "serialize_bson_datetime_as_chrono_date"
+    )]
+    pub created_at: BsonDateTime,
+}
+
+impl From<WebhookDelivery> for WebhookDeliveryResponse {
+    fn from(delivery: WebhookDelivery) -> Self {
+        Self {
+            id: delivery.id,
+            subscription_id: delivery.subscription_id,
+            event_id: delivery.event_id,
+            event: delivery.event,
+            task_id: delivery.task_id,
+            payload: delivery.payload,
+            state: delivery.state,
+            attempts: delivery
+                .attempts
+                .into_iter()
+                .map(DeliveryAttemptResponse::from)
+                .collect(),
+            next_attempt_at: delivery.next_attempt_at,
+            delivered_at: delivery.delivered_at,
+            created_at: delivery.created_at,
+        }
+    }
+}
+
+impl WebhookDeliveryResponse {
+    pub fn from_vec(deliveries: Vec<WebhookDelivery>) -> Vec<Self> {
+        deliveries
+            .into_iter()
+            .map(WebhookDeliveryResponse::from)
+            .collect()
+    }
+}
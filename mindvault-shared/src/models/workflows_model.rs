@@ -0,0 +1,320 @@
+use crate::models::tasks_model::{EStatusCategory, ETaskStatus, Task};
+use crate::utils::date_time_serde::serialize_bson_datetime_as_chrono_date;
+use bson::DateTime as BsonDateTime;
+use serde::{Deserialize, Serialize};
+
+/// A named step of a workflow, e.g. "In Review" in the Active category
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkflowStatus {
+    pub name: String,
+    pub category: EStatusCategory,
+    /// Most tasks the status's board column should hold; the board warns beyond it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wip_limit: Option<u32>,
+}
+
+/// A move a task may make between two statuses of the workflow, by name
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkflowTransition {
+    pub from: String,
+    pub to: String,
+}
+
+/// Custom statuses for the tasks of one vault. New tasks start in the first status; without
+/// transitions a task may move between any two statuses.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Workflow {
+    /// Owner of the private vault, or the member who last set a workspace's workflow
+    pub owner_id: i64,
+    #[serde(default)]
+    pub workspace_id: Option<i64>,
+    pub statuses: Vec<WorkflowStatus>,
+    #[serde(default)]
+    pub transitions: Vec<WorkflowTransition>,
+    pub updated_at: BsonDateTime,
+}
+
+impl Workflow {
+    /// Status names are matched without regard to case
+    pub fn status(&self, name: &str) -> Option<&WorkflowStatus> {
+        let name = name.trim();
+        self.statuses
+            .iter()
+            .find(|status| status.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Where `task` stands in the workflow. Tasks from before the workflow, or whose status
+    /// was removed from it, stand in the first status of their category.
+    pub fn current_status(&self, task: &Task) -> Option<&WorkflowStatus> {
+        task.workflow_status
+            .as_deref()
+            .and_then(|name| self.status(name))
+            .or_else(|| self.first_in(task.status.category()))
+    }
+
+    fn first_in(&self, category: EStatusCategory) -> Option<&WorkflowStatus> {
+        self.statuses.iter().find(|status| status.category == category)
+    }
+
+    fn allows(&self, from: &WorkflowStatus, to: &WorkflowStatus) -> bool {
+        self.transitions.is_empty()
+            || self.transitions.iter().any(|transition| {
+                transition.from.eq_ignore_ascii_case(&from.name)
+                    && transition.to.eq_ignore_ascii_case(&to.name)
+            })
+    }
+
+    /// Names `from` may move to, for error messages
+    fn next_names(&self, from: &WorkflowStatus) -> Vec<&str> {
+        self.transitions
+            .iter()
+            .filter(|transition| transition.from.eq_ignore_ascii_case(&from.name))
+            .map(|transition| transition.to.as_str())
+            .collect()
+    }
+
+    /// The built-in and workflow status a task ends up in when a request sets `status`,
+    /// `workflow_status` or both. `task` is the task being updated, `None` when creating one.
+    /// Returns `None` when an update leaves the status alone, and the reason when the move
+    /// is not allowed.
+    pub fn move_to(
+        &self,
+        task: Option<&Task>,
+        status: Option<ETaskStatus>,
+        workflow_status: Option<&str>,
+    ) -> Result<Option<(ETaskStatus, String)>, String> {
+        let target = match (workflow_status, &status) {
+            (Some(name), _) => self.status(name).ok_or_else(|| {
+                let names: Vec<&str> = self.statuses.iter().map(|s| s.name.as_str()).collect();
+                format!(
+                    "Unknown workflow status '{}', expected one of: {}",
+                    name,
+                    names.join(", ")
+                )
+            })?,
+            (None, Some(status)) => self.first_in(status.category()).ok_or_else(|| {
+                format!("The workflow has no {:?} status for {:?}", status.category(), status)
+            })?,
+            (None, None) if task.is_some() => return Ok(None),
+            (None, None) => match self.statuses.first() {
+                Some(initial) => initial,
+                None => return Ok(None),
+            },
+        };
+        let status = match status {
+            Some(status) if status.category() != target.category => {
+                return Err(format!(
+                    "Status {:?} does not match '{}', which is {:?}",
+                    status, target.name, target.category
+                ));
+            }
+            Some(status) => status,
+            None => target.category.default_status(),
+        };
+
+        if let Some(current) = task.and_then(|task| self.current_status(task))
+            && !current.name.eq_ignore_ascii_case(&target.name)
+            && !self.allows(current, target)
+        {
+            let next = self.next_names(current);
+            return Err(if next.is_empty() {
+                format!("Tasks in '{}' cannot move to another status", current.name)
+            } else {
+                format!(
+                    "Tasks in '{}' cannot move to '{}', only to: {}",
+                    current.name,
+                    target.name,
+                    next.join(", ")
+                )
+            });
+        }
+        Ok(Some((status, target.name.clone())))
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkflowResponse {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workspace_id: Option<i64>,
+    pub statuses: Vec<WorkflowStatus>,
+    pub transitions: Vec<WorkflowTransition>,
+    #[serde(serialize_with = "serialize_bson_datetime_as_chrono_date")]
+    pub updated_at: BsonDateTime,
+}
+
+impl From<Workflow> for WorkflowResponse {
+    fn from(workflow: Workflow) -> Self {
+        Self {
+            workspace_id: workflow.workspace_id,
+            statuses: workflow.statuses,
+            transitions: workflow.transitions,
+            updated_at: workflow.updated_at,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::tasks_model::ETaskPriority;
+
+    fn status(name: &str, category: EStatusCategory) -> WorkflowStatus {
+        WorkflowStatus {
+            name: name.to_string(),
+            category,
+            wip_limit: None,
+        }
+    }
+
+    /// Backlog, Doing, Review and Shipped, with the given transitions
+    fn workflow(transitions: &[(&str, &str)]) -> Workflow {
+        Workflow {
+            owner_id: 1,
+            workspace_id: None,
+            statuses: vec![
+                status("Backlog", EStatusCategory::Todo),
+                status("Doing", EStatusCategory::Active),
+                status("Review", EStatusCategory::Active),
+                status("Shipped", EStatusCategory::Done),
+            ],
+            transitions: transitions
+                .iter()
+                .map(|(from, to)| WorkflowTransition {
+                    from: from.to_string(),
+                    to: to.to_string(),
+                })
+                .collect(),
+            updated_at: BsonDateTime::now(),
+        }
+    }
+
+    /// Backlog -> Doing -> Review -> Shipped, with Review able to go back to Doing
+    fn guarded() -> Workflow {
+        workflow(&[
+            ("Backlog", "Doing"),
+            ("Doing", "Review"),
+            ("Review", "Doing"),
+            ("Review", "Shipped"),
+        ])
+    }
+
+    fn task_in(status: ETaskStatus, workflow_status: Option<&str>) -> Task {
+        Task {
+            id: 1,
+            owner_id: 1,
+            workspace_id: None,
+            assignee_id: None,
+            reportee_ids: Vec::new(),
+            name: "Write the release notes".to_string(),
+            priority: ETaskPriority::Normal,
+            status,
+            workflow_status: workflow_status.map(str::to_string),
+            rank: None,
+            due_date: None,
+            due_has_time: false,
+            created_at: BsonDateTime::now(),
+            updated_at: None,
+            completed_at: None,
+            tags: Vec::new(),
+            recurrence: None,
+            estimate_minutes: None,
+            tracked_seconds: 0,
+            notes: None,
+            notes_index: Vec::new(),
+            external_id: None,
+            deleted: Some(false),
+        }
+    }
+
+    #[test]
+    fn new_tasks_start_in_the_first_status() {
+        let moved = guarded().move_to(None, None, None).unwrap();
+        assert_eq!(moved, Some((ETaskStatus::NotStarted, "Backlog".to_string())));
+    }
+
+    #[test]
+    fn allowed_moves_take_the_built_in_status_of_their_category() {
+        let task = task_in(ETaskStatus::NotStarted, Some("Backlog"));
+        let moved = guarded().move_to(Some(&task), None, Some("Doing")).unwrap();
+        assert_eq!(moved, Some((ETaskStatus::InProgress, "Doing".to_string())));
+
+        let task = task_in(ETaskStatus::InProgress, Some("Review"));
+        let moved = guarded().move_to(Some(&task), None, Some("Shipped")).unwrap();
+        assert_eq!(moved, Some((ETaskStatus::Completed, "Shipped".to_string())));
+    }
+
+    #[test]
+    fn forbidden_moves_name_the_allowed_ones() {
+        let task = task_in(ETaskStatus::NotStarted, Some("Backlog"));
+        let error = guarded().move_to(Some(&task), None, Some("Shipped")).unwrap_err();
+        assert_eq!(error, "Tasks in 'Backlog' cannot move to 'Shipped', only to: Doing");
+
+        let task = task_in(ETaskStatus::Completed, Some("Shipped"));
+        let error = guarded().move_to(Some(&task), None, Some("Doing")).unwrap_err();
+        assert_eq!(error, "Tasks in 'Shipped' cannot move to another status");
+    }
+
+    #[test]
+    fn status_names_ignore_case() {
+        let task = task_in(ETaskStatus::InProgress, Some("doing"));
+        let moved = guarded().move_to(Some(&task), None, Some("  REVIEW ")).unwrap();
+        assert_eq!(moved, Some((ETaskStatus::InProgress, "Review".to_string())));
+    }
+
+    #[test]
+    fn a_status_outside_the_target_category_is_rejected() {
+        let task = task_in(ETaskStatus::NotStarted, Some("Backlog"));
+        let error = guarded()
+            .move_to(Some(&task), Some(ETaskStatus::Completed), Some("Doing"))
+            .unwrap_err();
+        assert_eq!(error, "Status Completed does not match 'Doing', which is Active");
+    }
+
+    #[test]
+    fn a_built_in_status_alone_moves_to_the_first_status_of_its_category() {
+        let task = task_in(ETaskStatus::NotStarted, Some("Backlog"));
+        let moved = guarded()
+            .move_to(Some(&task), Some(ETaskStatus::InProgress), None)
+            .unwrap();
+        assert_eq!(moved, Some((ETaskStatus::InProgress, "Doing".to_string())));
+
+        let error = guarded()
+            .move_to(Some(&task), Some(ETaskStatus::Cancelled), None)
+            .unwrap_err();
+        assert_eq!(error, "The workflow has no Cancelled status for Cancelled");
+    }
+
+    #[test]
+    fn without_transitions_any_move_is_allowed() {
+        let task = task_in(ETaskStatus::NotStarted, Some("Backlog"));
+        let moved = workflow(&[]).move_to(Some(&task), None, Some("Shipped")).unwrap();
+        assert_eq!(moved, Some((ETaskStatus::Completed, "Shipped".to_string())));
+    }
+
+    #[test]
+    fn updates_that_leave_the_status_alone_change_nothing() {
+        let task = task_in(ETaskStatus::Completed, Some("Shipped"));
+        assert_eq!(guarded().move_to(Some(&task), None, None).unwrap(), None);
+    }
+
+    #[test]
+    fn unknown_names_list_the_workflow_statuses() {
+        let error = guarded().move_to(None, None, Some("Done")).unwrap_err();
+        assert_eq!(
+            error,
+            "Unknown workflow status 'Done', expected one of: Backlog, Doing, Review, Shipped"
+        );
+    }
+
+    #[test]
+    fn tasks_from_before_the_workflow_stand_in_the_first_status_of_their_category() {
+        let task = task_in(ETaskStatus::InProgress, None);
+        let current = guarded().current_status(&task).map(|status| status.name.clone());
+        assert_eq!(current.as_deref(), Some("Doing"));
+        assert!(guarded().move_to(Some(&task), None, Some("Review")).is_ok());
+    }
+}
@@ -0,0 +1,146 @@
+use crate::utils::date_time_serde::{
+    serialize_bson_datetime_as_chrono_date, serialize_option_bson_datetime_as_chrono_date,
+};
+use bson::DateTime as BsonDateTime;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// What a member may do in a workspace; each role includes the ones before it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub enum EWorkspaceRole {
+    /// Reads the workspace's tasks
+    Viewer,
+    /// Also creates, updates, assigns and deletes tasks
+    Editor,
+    /// Also renames or deletes the workspace and manages its members and task workflow
+    Admin,
+}
+
+impl fmt::Display for EWorkspaceRole {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            EWorkspaceRole::Viewer => "Viewer",
+            EWorkspaceRole::Editor => "Editor",
+            EWorkspaceRole::Admin => "Admin",
+        };
+        f.write_str(label)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceMember {
+    pub user_id: i64,
+    pub role: EWorkspaceRole,
+    #[serde(default = "default_utc_now")]
+    pub added_at: BsonDateTime,
+}
+
+fn default_utc_now() -> BsonDateTime {
+    BsonDateTime::now()
+}
+
+/// Team space that owns tasks (and their notes) instead of a single user. Members reach the
+/// workspace's tasks according to their role; everyone else cannot tell it exists.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Workspace {
+    #[serde(rename = "_id")]
+    pub id: i64,
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Always has at least one admin
+    #[serde(default)]
+    pub members: Vec<WorkspaceMember>,
+    pub created_by: i64,
+    #[serde(default = "default_utc_now")]
+    pub created_at: BsonDateTime,
+    #[serde(default)]
+    pub updated_at: Option<BsonDateTime>,
+    #[serde(default)]
+    pub deleted: Option<bool>,
+}
+
+impl Workspace {
+    /// Role of `user_id`, `None` for non-members
+    pub fn role_of(&self, user_id: i64) -> Option<EWorkspaceRole> {
+        self.members
+            .iter()
+            .find(|member| member.user_id == user_id)
+            .map(|member| member.role)
+    }
+
+    pub fn admin_count(&self) -> usize {
+        self.members
+            .iter()
+            .filter(|member| member.role == EWorkspaceRole::Admin)
+            .count()
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceMemberResponse {
+    pub user_id: i64,
+    pub role: EWorkspaceRole,
+    #[serde(
+        serialize_with = "serialize_bson_datetime_as_chrono_date"
+    )]
+    pub added_at: BsonDateTime,
+}
+
+impl From<WorkspaceMember> for WorkspaceMemberResponse {
+    fn from(member: WorkspaceMember) -> Self {
+        Self {
+            user_id: member.user_id,
+            role: member.role,
+            added_at: member.added_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceResponse {
+    pub id: i64,
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub members: Vec<WorkspaceMemberResponse>,
+    pub created_by: i64,
+    #[serde(
+        serialize_with = "serialize_bson_datetime_as_chrono_date"
+    )]
+    pub created_at: BsonDateTime,
+    #[serde(
+        serialize_with = "serialize_option_bson_datetime_as_chrono_date",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub updated_at: Option<BsonDateTime>,
+}
+
+impl From<Workspace> for WorkspaceResponse {
+    fn from(workspace: Workspace) -> Self {
+        Self {
+            id: workspace.id,
+            name: workspace.name,
+            description: workspace.description,
+            members: workspace
+                .members
+                .into_iter()
+                .map(WorkspaceMemberResponse::from)
+                .collect(),
+            created_by: workspace.created_by,
+            created_at: workspace.created_at,
+            updated_at: workspace.updated_at,
+        }
+    }
+}
+
+impl WorkspaceResponse {
+    pub fn from_vec(workspaces: Vec<Workspace>) -> Vec<Self> {
+        workspaces.into_iter().map(WorkspaceResponse::from).collect()
+    }
+}
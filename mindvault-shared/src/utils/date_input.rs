@@ -0,0 +1,306 @@
+use crate::utils::time_zone::{parse_time_zone, FlexibleDateTime, DEFAULT_TIME_ZONE};
+use bson::DateTime as BsonDateTime;
+use chrono::{DateTime, NaiveDate, NaiveTime, TimeZone, Utc};
+use chrono_tz::Tz;
+use serde::{Deserialize, Deserializer, Serialize};
+use std::fmt;
+
+/// How a user writes dates whose first two numbers could be either day or month
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "PascalCase")]
+pub enum EDateOrder {
+    /// `03/04/2026` is 3 April
+    DayFirst,
+    /// `03/04/2026` is 4 March
+    MonthFirst,
+}
+
+/// A user's settings for reading and showing dates
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DatePreferences {
+    /// IANA name, e.g. `Europe/Berlin`
+    pub time_zone: String,
+    /// Without one, dates that read differently day-first and month-first are rejected
+    pub date_order: Option<EDateOrder>,
+}
+
+impl Default for DatePreferences {
+    fn default() -> Self {
+        Self {
+            time_zone: DEFAULT_TIME_ZONE.to_string(),
+            date_order: None,
+        }
+    }
+}
+
+impl DatePreferences {
+    /// The time zone; names are checked when saved, so a bad one falls back to UTC
+    pub fn tz(&self) -> Tz {
+        parse_time_zone(&self.time_zone).unwrap_or(Tz::UTC)
+    }
+
+    /// The current date in the user's time zone
+    pub fn today(&self, now: DateTime<Utc>) -> NaiveDate {
+        now.with_timezone(&self.tz()).date_naive()
+    }
+}
+
+/// A date or date-time as a client sent it, before the user's preferences are applied.
+///
+/// Accepts `YYYY-MM-DD` and `YYYY/MM/DD`, and `DD/MM/YYYY` or `MM/DD/YYYY` (also with `-` and
+/// two-digit years), each optionally followed by `THH:MM[:SS]` or ` HH:MM[:SS]`, as well as
+/// RFC 3339 timestamps with an offset.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DateInput {
+    /// A calendar day without a time; stored as a floating date that is the same day in
+    /// every time zone
+    Day(NaiveDate),
+    /// A moment, either with its own offset or as a wall-clock time in the user's time zone
+    Time(FlexibleDateTime),
+    /// Both the day-first and the month-first reading are valid and differ
+    Ambiguous {
+        input: String,
+        day_first: NaiveDate,
+        month_first: NaiveDate,
+        time: Option<NaiveTime>,
+    },
+}
+
+impl DateInput {
+    pub fn parse(s: &str) -> Result<Self, String> {
+        let s = s.trim();
+        if let Ok(dt) = DateTime::parse_from_rfc3339(s) {
+            return Ok(DateInput::Time(FlexibleDateTime::Absolute(dt.with_timezone(&Utc))));
+        }
+
+        let (date_part, time_part) = match s.find(['T', ' ']) {
+            Some(split) => (&s[..split], Some(s[split + 1..].trim())),
+            None => (s, None),
+        };
+        let time = time_part
+            .map(|time| {
+                NaiveTime::parse_from_str(time, "%H:%M:%S")
+                    .or_else(|_| NaiveTime::parse_from_str(time, "%H:%M"))
+                    .map_err(|_| format!("Invalid time '{}' in '{}', expected HH:MM[:SS]", time, s))
+            })
+            .transpose()?;
+
+        let with_time = |date: NaiveDate| match time {
+            Some(time) => DateInput::Time(FlexibleDateTime::Local(date.and_time(time))),
+            None => DateInput::Day(date),
+        };
+        match read_date(date_part) {
+            Some(DateReadings::One(date)) => Ok(with_time(date)),
+            Some(DateReadings::Two {
+                day_first,
+                month_first,
+            }) => Ok(DateInput::Ambiguous {
+                input: s.to_string(),
+                day_first,
+                month_first,
+                time,
+            }),
+            None => Err(format!(
+                "Invalid date '{}', expected YYYY-MM-DD, DD/MM/YYYY or MM/DD/YYYY, optionally \
+                followed by a time",
+                s
+            )),
+        }
+    }
+
+    /// Applies the user's date order and time zone, leaving only `Day` or an absolute `Time`
+    pub fn resolve(self, preferences: &DatePreferences) -> Result<Self, String> {
+        match self {
+            DateInput::Day(date) => Ok(DateInput::Day(date)),
+            DateInput::Time(time) => time
+                .to_utc(&preferences.time_zone)
+                .map(|at| DateInput::Time(FlexibleDateTime::Absolute(at))),
+            DateInput::Ambiguous {
+                input,
+                day_first,
+                month_first,
+                time,
+            } => {
+                let date = match preferences.date_order {
+                    Some(EDateOrder::DayFirst) => day_first,
+                    Some(EDateOrder::MonthFirst) => month_first,
+                    None => {
+                        return Err(format!(
+                            "{}; send it as YYYY-MM-DD or set a date order preference",
+                            ambiguity(&input, day_first, month_first)
+                        ));
+                    }
+                };
+                let resolved = match time {
+                    Some(time) => DateInput::Time(FlexibleDateTime::Local(date.and_time(time))),
+                    None => DateInput::Day(date),
+                };
+                resolved.resolve(preferences)
+            }
+        }
+    }
+
+    /// How a resolved input is stored: the instant and whether it carries a time of day.
+    /// Floating days are stored at midnight UTC. `None` until `resolve` has run.
+    pub fn stored(&self) -> Option<(BsonDateTime, bool)> {
+        match self {
+            DateInput::Day(date) => Some((floating_day(*date), false)),
+            DateInput::Time(FlexibleDateTime::Absolute(at)) => {
+                Some((BsonDateTime::from(*at), true))
+            }
+            _ => None,
+        }
+    }
+
+    /// The calendar day of a resolved input in `tz`
+    pub fn day(&self, tz: Tz) -> Option<NaiveDate> {
+        match self {
+            DateInput::Day(date) => Some(*date),
+            DateInput::Time(FlexibleDateTime::Absolute(at)) => {
+                Some(at.with_timezone(&tz).date_naive())
+            }
+            _ => None,
+        }
+    }
+
+    /// The calendar day of a `Day` input; `None` for anything else
+    pub fn as_day(&self) -> Option<NaiveDate> {
+        match self {
+            DateInput::Day(date) => Some(*date),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for DateInput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DateInput::Day(date) => write!(f, "{}", date.format("%Y-%m-%d")),
+            DateInput::Time(FlexibleDateTime::Absolute(at)) => write!(f, "{}", at.to_rfc3339()),
+            DateInput::Time(FlexibleDateTime::Local(local)) => {
+                write!(f, "{}", local.format("%Y-%m-%dT%H:%M:%S"))
+            }
+            DateInput::Ambiguous { input, .. } => f.write_str(input),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for DateInput {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        DateInput::parse(&s).map_err(serde::de::Error::custom)
+    }
+}
+
+/// Floating calendar days are stored at midnight UTC
+pub fn floating_day(date: NaiveDate) -> BsonDateTime {
+    BsonDateTime::from(Utc.from_utc_datetime(&date.and_time(NaiveTime::MIN)))
+}
+
+/// Explains both readings of an ambiguous date
+fn ambiguity(input: &str, day_first: NaiveDate, month_first: NaiveDate) -> String {
+    format!(
+        "'{}' is ambiguous: it is {} ({}) read day first, or {} ({}) read month first",
+        input,
+        day_first.format("%-d %B %Y"),
+        day_first.format("%Y-%m-%d"),
+        month_first.format("%-d %B %Y"),
+        month_first.format("%Y-%m-%d")
+    )
+}
+
+enum DateReadings {
+    One(NaiveDate),
+    Two {
+        day_first: NaiveDate,
+        month_first: NaiveDate,
+    },
+}
+
+/// Reads a date without a time; `None` when it is no valid date in any accepted format
+fn read_date(s: &str) -> Option<DateReadings> {
+    let separator = s.chars().find(|c| matches!(c, '-' | '/'))?;
+    let parts: Vec<&str> = s.split(separator).collect();
+    if parts.len() != 3 || parts.iter().any(|part| part.is_empty()) {
+        return None;
+    }
+    if parts[0].len() == 4 {
+        let format = format!("%Y{0}%m{0}%d", separator);
+        return NaiveDate::parse_from_str(s, &format).ok().map(DateReadings::One);
+    }
+
+    let year = match parts[2].len() {
+        4 => "%Y",
+        2 => "%y",
+        _ => return None,
+    };
+    let day_first = NaiveDate::parse_from_str(s, &format!("%d{0}%m{0}{1}", separator, year));
+    let month_first = NaiveDate::parse_from_str(s, &format!("%m{0}%d{0}{1}", separator, year));
+    match (day_first, month_first) {
+        (Ok(day_first), Ok(month_first)) if day_first != month_first => Some(DateReadings::Two {
+            day_first,
+            month_first,
+        }),
+        (Ok(date), _) | (_, Ok(date)) => Some(DateReadings::One(date)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DateInput, DatePreferences, EDateOrder};
+    use crate::utils::time_zone::FlexibleDateTime;
+    use chrono::{NaiveDate, TimeZone, Utc};
+
+    fn preferences(time_zone: &str, date_order: Option<EDateOrder>) -> DatePreferences {
+        DatePreferences {
+            time_zone: time_zone.to_string(),
+            date_order,
+        }
+    }
+
+    fn day(year: i32, month: u32, day: u32) -> DateInput {
+        DateInput::Day(NaiveDate::from_ymd_opt(year, month, day).unwrap())
+    }
+
+    fn resolve(input: &str, preferences: &DatePreferences) -> Result<DateInput, String> {
+        DateInput::parse(input)?.resolve(preferences)
+    }
+
+    #[test]
+    fn an_ambiguous_date_follows_the_date_order() {
+        let day_first = preferences("UTC", Some(EDateOrder::DayFirst));
+        let month_first = preferences("UTC", Some(EDateOrder::MonthFirst));
+        assert_eq!(resolve("03/04/2026", &day_first), Ok(day(2026, 4, 3)));
+        assert_eq!(resolve("03/04/2026", &month_first), Ok(day(2026, 3, 4)));
+        assert_eq!(resolve("03-04-26", &day_first), Ok(day(2026, 4, 3)));
+    }
+
+    #[test]
+    fn an_ambiguous_date_without_a_date_order_is_rejected_with_both_readings() {
+        let error = resolve("03/04/2026", &preferences("UTC", None)).unwrap_err();
+        assert!(error.contains("'03/04/2026' is ambiguous"), "{}", error);
+        assert!(error.contains("3 April 2026 (2026-04-03)"), "{}", error);
+        assert!(error.contains("4 March 2026 (2026-03-04)"), "{}", error);
+    }
+
+    #[test]
+    fn unambiguous_dates_need_no_date_order() {
+        let none = preferences("UTC", None);
+        assert_eq!(resolve("2026-04-03", &none), Ok(day(2026, 4, 3)));
+        assert_eq!(resolve("13/04/2026", &none), Ok(day(2026, 4, 13)));
+        assert_eq!(resolve("04/13/2026", &none), Ok(day(2026, 4, 13)));
+        assert_eq!(resolve("04/04/2026", &none), Ok(day(2026, 4, 4)));
+        assert!(DateInput::parse("13/13/2026").is_err());
+    }
+
+    #[test]
+    fn a_time_is_read_in_the_user_time_zone() {
+        let berlin = preferences("Europe/Berlin", Some(EDateOrder::DayFirst));
+        let at = Utc.with_ymd_and_hms(2026, 4, 3, 8, 0, 0).unwrap();
+        let expected = DateInput::Time(FlexibleDateTime::Absolute(at));
+        assert_eq!(resolve("03/04/2026 10:00", &berlin), Ok(expected.clone()));
+        assert_eq!(resolve("2026-04-03T10:00", &berlin), Ok(expected.clone()));
+        assert_eq!(resolve("2026-04-03T08:00:00Z", &berlin), Ok(expected));
+    }
+}
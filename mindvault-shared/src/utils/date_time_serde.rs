@@ -1,6 +1,8 @@
-use chrono::{NaiveDate, NaiveDateTime};
+use chrono::{DateTime, FixedOffset, NaiveDateTime, SecondsFormat};
 use serde::{self, Deserialize, Deserializer, Serializer};
 use bson::DateTime as BsonDateTime;
+use crate::utils::time_zone::FlexibleDateTime;
+use utoipa::openapi::schema::{KnownFormat, Object, ObjectBuilder, SchemaFormat, Type};
 
 /// Serializer for `Option<BsonDateTime>` that serializes Some(...) as formatted string,
 /// and None as null (or skips if `skip_serializing_if` is used).
@@ -42,37 +44,103 @@ where
     serializer.serialize_str(&formatted)
 }
 
-pub fn deserialize_multiple_formats<'de, D>(
+/// Serializes as ISO-8601 with the timestamp's own offset, e.g. `2025-07-27T16:30:00+02:00`;
+/// the `/v2` shape
+pub fn serialize_date_time_as_iso8601<S>(
+    dt: &DateTime<FixedOffset>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.serialize_str(&dt.to_rfc3339_opts(SecondsFormat::Secs, false))
+}
+
+/// `serialize_date_time_as_iso8601` for optional timestamps
+pub fn serialize_option_date_time_as_iso8601<S>(
+    opt_dt: &Option<DateTime<FixedOffset>>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    match opt_dt {
+        Some(dt) => serialize_date_time_as_iso8601(dt, serializer),
+        None => serializer.serialize_none(),
+    }
+}
+
+/// OpenAPI schema for request dates read as a `DateInput`
+pub fn date_input_schema() -> Object {
+    ObjectBuilder::new()
+        .schema_type(Type::String)
+        .description(Some(
+            "YYYY-MM-DD, or DD/MM/YYYY or MM/DD/YYYY read with the user's date order (also with \
+            `-` or two-digit years), optionally followed by a time as THH:MM[:SS]. Times \
+            without an offset are in the user's time zone; RFC 3339 timestamps are accepted \
+            too. Dates that read differently day-first and month-first are rejected when the \
+            user has no date order.",
+        ))
+        .examples(["2025-07-27"])
+        .build()
+}
+
+/// OpenAPI schema for timestamps written by `serialize_bson_datetime_as_chrono_date`
+pub fn date_time_response_schema() -> Object {
+    ObjectBuilder::new()
+        .schema_type(Type::String)
+        .description(Some("UTC timestamp formatted as DD/MM/YY HH:MM:SS"))
+        .examples(["27/07/25 14:30:00"])
+        .build()
+}
+
+/// OpenAPI schema for timestamps written by `serialize_date_time_as_iso8601`
+pub fn iso8601_response_schema() -> Object {
+    ObjectBuilder::new()
+        .schema_type(Type::String)
+        .format(Some(SchemaFormat::KnownFormat(KnownFormat::DateTime)))
+        .description(Some("ISO-8601 timestamp with the user's UTC offset"))
+        .examples(["2025-07-27T16:30:00+02:00"])
+        .build()
+}
+
+/// Deserializes an optional timestamp given either as RFC 3339 (`2026-10-20T09:00:00+02:00`)
+/// or as a zone-less wall-clock time (`2026-10-20T09:00`) to be localized by the caller.
+pub fn deserialize_optional_flexible_datetime<'de, D>(
     deserializer: D,
-) -> Result<Option<NaiveDate>, D::Error>
+) -> Result<Option<FlexibleDateTime>, D::Error>
 where
     D: Deserializer<'de>,
 {
-    let s: Option<&str> = Option::deserialize(deserializer)?;
-    if s.is_none() {
-        return Ok(None);
+    let s: Option<String> = Option::deserialize(deserializer)?;
+    let s = match s {
+        Some(s) => s,
+        None => return Ok(None),
+    };
+    parse_flexible_datetime(s.trim())
+        .map(Some)
+        .map_err(serde::de::Error::custom)
+}
+
+pub fn parse_flexible_datetime(s: &str) -> Result<FlexibleDateTime, String> {
+    if let Ok(dt) = chrono::DateTime::parse_from_rfc3339(s) {
+        return Ok(FlexibleDateTime::Absolute(dt.with_timezone(&chrono::Utc)));
     }
-    let s = s.unwrap().trim();
 
-    // List of allowed date formats, ordered by priority
     let formats = [
-        "%Y-%m-%d",     // 2025-07-27
-        "%m/%d/%Y",     // 07/27/2025
-        "%d/%m/%Y",     // 27/07/2025
-        "%d/%m/%y",     // 27/07/25
-        "%m-%d-%Y",     // 07-27-2025
-        "%d-%m-%Y",     // 27-07-2025
-        "%Y/%m/%d",     // 2025/07/27
+        "%Y-%m-%dT%H:%M:%S", // 2026-10-20T09:00:00
+        "%Y-%m-%dT%H:%M",    // 2026-10-20T09:00
+        "%Y-%m-%d %H:%M:%S", // 2026-10-20 09:00:00
+        "%Y-%m-%d %H:%M",    // 2026-10-20 09:00
     ];
-
     for fmt in formats.iter() {
-        if let Ok(date) = NaiveDate::parse_from_str(s, fmt) {
-            return Ok(Some(date));
+        if let Ok(dt) = NaiveDateTime::parse_from_str(s, fmt) {
+            return Ok(FlexibleDateTime::Local(dt));
         }
     }
 
-    Err(serde::de::Error::custom(format!(
-        "invalid date format: '{}', expected one of: {:?}",
+    Err(format!(
+        "invalid date-time: '{}', expected RFC 3339 or one of: {:?}",
         s, formats
-    )))
+    ))
 }
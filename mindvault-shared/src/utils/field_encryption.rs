@@ -0,0 +1,613 @@
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng};
+use aes_gcm::{Aes256Gcm, Nonce};
+use argon2::Argon2;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Deserializer, Serializer};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeSet;
+use std::env;
+use std::fs;
+use std::sync::OnceLock;
+use thiserror::Error;
+
+const KEYFILE_VAR: &str = "MINDVAULT_ENCRYPTION_KEYFILE";
+const PASSPHRASE_VAR: &str = "MINDVAULT_ENCRYPTION_PASSPHRASE";
+const PREVIOUS_PASSPHRASE_VAR: &str = "MINDVAULT_ENCRYPTION_PREVIOUS_PASSPHRASE";
+const SALT_VAR: &str = "MINDVAULT_ENCRYPTION_SALT";
+const FIELDS_VAR: &str = "MINDVAULT_ENCRYPTED_FIELDS";
+
+const DEFAULT_SALT: &str = "mindvault-field-encryption";
+/// Stored values look like `mvenc:v1:<key id>:<base64 of nonce || ciphertext>`
+pub const ENCRYPTED_PREFIX: &str = "mvenc:v1:";
+const NONCE_LEN: usize = 12;
+const KEY_LEN: usize = 32;
+/// Bytes of the keyed hash kept per indexed word
+const INDEX_TOKEN_LEN: usize = 12;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Free-text fields that can be encrypted at rest
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EncryptedField {
+    TaskNotes,
+    FeedbackNote,
+    ReviewFeedback,
+    /// Queued task webhook bodies, which carry task notes
+    WebhookPayload,
+}
+
+impl EncryptedField {
+    pub const ALL: [EncryptedField; 4] = [
+        EncryptedField::TaskNotes,
+        EncryptedField::FeedbackNote,
+        EncryptedField::ReviewFeedback,
+        EncryptedField::WebhookPayload,
+    ];
+
+    /// Name used in `MINDVAULT_ENCRYPTED_FIELDS`
+    pub fn name(self) -> &'static str {
+        match self {
+            EncryptedField::TaskNotes => "tasks.notes",
+            EncryptedField::FeedbackNote => "feedback.note",
+            EncryptedField::ReviewFeedback => "reviews.feedback",
+            EncryptedField::WebhookPayload => "webhook_deliveries.payload",
+        }
+    }
+
+    pub fn collection(self) -> &'static str {
+        match self {
+            EncryptedField::TaskNotes => "tasks",
+            EncryptedField::FeedbackNote => "feedback",
+            EncryptedField::ReviewFeedback => "reviews",
+            EncryptedField::WebhookPayload => "webhook_deliveries",
+        }
+    }
+
+    /// Dotted document paths holding the field's string values
+    pub fn paths(self) -> &'static [&'static str] {
+        match self {
+            EncryptedField::TaskNotes => &["notes"],
+            EncryptedField::FeedbackNote => &["note"],
+            EncryptedField::ReviewFeedback => {
+                &["feedback.strengths", "feedback.growthAreas", "feedback.goals"]
+            }
+            EncryptedField::WebhookPayload => &["payload"],
+        }
+    }
+
+    /// Path of the blind index kept next to the field, for fields that can be searched
+    pub fn index_path(self) -> Option<&'static str> {
+        match self {
+            EncryptedField::TaskNotes => Some("notesIndex"),
+            _ => None,
+        }
+    }
+
+    fn parse(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|field| field.name() == name)
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum FieldEncryptionError {
+    #[error(
+        "{0} holds encrypted data but no encryption key is configured; set {KEYFILE_VAR} or {PASSPHRASE_VAR}"
+    )]
+    MissingKey(String),
+    #[error("value was encrypted with key '{0}', which is not in the configured keyring")]
+    UnknownKey(String),
+    #[error("invalid encryption configuration: {0}")]
+    InvalidConfig(String),
+    #[error("encrypted value is malformed or does not match its key")]
+    Corrupt,
+}
+
+struct FieldKey {
+    id: String,
+    cipher: Aes256Gcm,
+}
+
+/// Keys used for field encryption, loaded once from the environment.
+///
+/// The first key encrypts new values; the others are only kept to read values written before a
+/// rotation. Keys come either from a keyfile with one `<key id> <64 hex chars>` line per key,
+/// newest first, or from a passphrase stretched with Argon2id. A passphrase can be rotated by
+/// moving the old one to `MINDVAULT_ENCRYPTION_PREVIOUS_PASSPHRASE`.
+pub struct FieldKeyring {
+    keys: Vec<FieldKey>,
+    index_key: [u8; KEY_LEN],
+    fields: Vec<EncryptedField>,
+}
+
+fn non_empty_var(name: &str) -> Option<String> {
+    env::var(name)
+        .ok()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+fn valid_key_id(id: &str) -> bool {
+    !id.is_empty()
+        && id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.')
+}
+
+fn derive_passphrase_key(passphrase: &str, salt: &str) -> Result<(String, [u8; KEY_LEN]), FieldEncryptionError> {
+    let mut key = [0u8; KEY_LEN];
+    Argon2::default()
+        .hash_password_into(passphrase.as_bytes(), salt.as_bytes(), &mut key)
+        .map_err(|e| FieldEncryptionError::InvalidConfig(format!("cannot derive key: {}", e)))?;
+    let fingerprint = hex::encode(&Sha256::digest(key)[..4]);
+    Ok((format!("passphrase-{}", fingerprint), key))
+}
+
+fn parse_keyfile(path: &str, content: &str) -> Result<Vec<(String, [u8; KEY_LEN])>, FieldEncryptionError> {
+    let mut keys: Vec<(String, [u8; KEY_LEN])> = Vec::new();
+    for (index, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let invalid = |reason: &str| {
+            FieldEncryptionError::InvalidConfig(format!("{} line {}: {}", path, index + 1, reason))
+        };
+        let mut parts = line.split_whitespace();
+        let (Some(id), Some(material), None) = (parts.next(), parts.next(), parts.next()) else {
+            return Err(invalid("expected '<key id> <64 hex chars>'"));
+        };
+        if !valid_key_id(id) {
+            return Err(invalid("key ids may only use letters, digits, '-', '_' and '.'"));
+        }
+        if keys.iter().any(|(existing, _)| existing == id) {
+            return Err(invalid("duplicate key id"));
+        }
+        let bytes = hex::decode(material).map_err(|_| invalid("key is not hex"))?;
+        let key: [u8; KEY_LEN] = bytes
+            .try_into()
+            .map_err(|_| invalid("key must be 32 bytes (64 hex chars)"))?;
+        keys.push((id.to_string(), key));
+    }
+    if keys.is_empty() {
+        return Err(FieldEncryptionError::InvalidConfig(format!("{} contains no keys", path)));
+    }
+    Ok(keys)
+}
+
+fn parse_fields(value: Option<String>) -> Result<Vec<EncryptedField>, FieldEncryptionError> {
+    let Some(value) = value else {
+        return Ok(EncryptedField::ALL.to_vec());
+    };
+    let mut fields = Vec::new();
+    for name in value.split(',').map(str::trim).filter(|name| !name.is_empty()) {
+        let field = EncryptedField::parse(name).ok_or_else(|| {
+            let known: Vec<&str> = EncryptedField::ALL.iter().map(|field| field.name()).collect();
+            FieldEncryptionError::InvalidConfig(format!(
+                "unknown field '{}' in {}, expected any of: {}",
+                name,
+                FIELDS_VAR,
+                known.join(", ")
+            ))
+        })?;
+        if !fields.contains(&field) {
+            fields.push(field);
+        }
+    }
+    Ok(fields)
+}
+
+/// Lowercased words of at least two characters, each once
+fn index_words(text: &str) -> BTreeSet<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| word.chars().count() >= 2)
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// Key id of a stored encrypted value, `None` for plaintext
+pub fn encrypted_key_id(value: &str) -> Option<&str> {
+    value
+        .strip_prefix(ENCRYPTED_PREFIX)
+        .and_then(|rest| rest.split_once(':'))
+        .map(|(key_id, _)| key_id)
+}
+
+pub fn is_encrypted(value: &str) -> bool {
+    value.starts_with(ENCRYPTED_PREFIX)
+}
+
+impl FieldKeyring {
+    /// `None` when neither a keyfile nor a passphrase is configured, i.e. encryption is off
+    pub fn from_env() -> Result<Option<Self>, FieldEncryptionError> {
+        let keyfile = non_empty_var(KEYFILE_VAR);
+        let passphrase = non_empty_var(PASSPHRASE_VAR);
+        let keys = match (keyfile, passphrase) {
+            (None, None) => return Ok(None),
+            (Some(_), Some(_)) => {
+                return Err(FieldEncryptionError::InvalidConfig(format!(
+                    "set only one of {} and {}",
+                    KEYFILE_VAR, PASSPHRASE_VAR
+                )));
+            }
+            (Some(path), None) => {
+                let content = fs::read_to_string(&path).map_err(|e| {
+                    FieldEncryptionError::InvalidConfig(format!("cannot read keyfile {}: {}", path, e))
+                })?;
+                parse_keyfile(&path, &content)?
+            }
+            (None, Some(passphrase)) => {
+                let salt = non_empty_var(SALT_VAR).unwrap_or_else(|| DEFAULT_SALT.to_string());
+                let mut keys = vec![derive_passphrase_key(&passphrase, &salt)?];
+                if let Some(previous) = non_empty_var(PREVIOUS_PASSPHRASE_VAR) {
+                    keys.push(derive_passphrase_key(&previous, &salt)?);
+                }
+                keys
+            }
+        };
+        Self::new(keys, parse_fields(non_empty_var(FIELDS_VAR))?).map(Some)
+    }
+
+    /// Builds a keyring from raw keys, newest first
+    pub fn new(
+        keys: Vec<(String, [u8; KEY_LEN])>,
+        fields: Vec<EncryptedField>,
+    ) -> Result<Self, FieldEncryptionError> {
+        let Some((_, active)) = keys.first() else {
+            return Err(FieldEncryptionError::InvalidConfig("no keys configured".to_string()));
+        };
+        let mut mac = <HmacSha256 as Mac>::new_from_slice(active).expect("HMAC accepts any key length");
+        mac.update(b"mindvault blind index v1");
+        let index_key = mac.finalize().into_bytes().into();
+
+        let keys = keys
+            .into_iter()
+            .map(|(id, key)| FieldKey {
+                id,
+                cipher: Aes256Gcm::new(&key.into()),
+            })
+            .collect();
+        Ok(FieldKeyring {
+            keys,
+            index_key,
+            fields,
+        })
+    }
+
+    /// A fresh keyfile line for `key_id`
+    pub fn generate_keyfile_line(key_id: &str) -> Result<String, FieldEncryptionError> {
+        if !valid_key_id(key_id) {
+            return Err(FieldEncryptionError::InvalidConfig(
+                "key ids may only use letters, digits, '-', '_' and '.'".to_string(),
+            ));
+        }
+        let key = Aes256Gcm::generate_key(&mut OsRng);
+        Ok(format!("{} {}", key_id, hex::encode(key)))
+    }
+
+    pub fn active_key_id(&self) -> &str {
+        &self.keys[0].id
+    }
+
+    /// Webhook payloads are also encrypted whenever task notes are, so notes never sit in the
+    /// outbox in plaintext
+    pub fn encrypts(&self, field: EncryptedField) -> bool {
+        self.fields.contains(&field)
+            || (field == EncryptedField::WebhookPayload
+                && self.fields.contains(&EncryptedField::TaskNotes))
+    }
+
+    pub fn has_key(&self, key_id: &str) -> bool {
+        self.keys.iter().any(|key| key.id == key_id)
+    }
+
+    pub fn key_ids(&self) -> impl Iterator<Item = &str> {
+        self.keys.iter().map(|key| key.id.as_str())
+    }
+
+    pub fn encrypt(&self, plaintext: &str) -> Result<String, FieldEncryptionError> {
+        let key = &self.keys[0];
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let ciphertext = key
+            .cipher
+            .encrypt(&nonce, plaintext.as_bytes())
+            .map_err(|_| FieldEncryptionError::Corrupt)?;
+        let mut sealed = nonce.to_vec();
+        sealed.extend_from_slice(&ciphertext);
+        Ok(format!("{}{}:{}", ENCRYPTED_PREFIX, key.id, BASE64.encode(sealed)))
+    }
+
+    pub fn decrypt(&self, value: &str) -> Result<String, FieldEncryptionError> {
+        let Some((key_id, payload)) = value
+            .strip_prefix(ENCRYPTED_PREFIX)
+            .and_then(|rest| rest.split_once(':'))
+        else {
+            return Err(FieldEncryptionError::Corrupt);
+        };
+        let key = self
+            .keys
+            .iter()
+            .find(|key| key.id == key_id)
+            .ok_or_else(|| FieldEncryptionError::UnknownKey(key_id.to_string()))?;
+        let sealed = BASE64.decode(payload).map_err(|_| FieldEncryptionError::Corrupt)?;
+        if sealed.len() < NONCE_LEN {
+            return Err(FieldEncryptionError::Corrupt);
+        }
+        let (nonce, ciphertext) = sealed.split_at(NONCE_LEN);
+        let plaintext = key
+            .cipher
+            .decrypt(Nonce::from_slice(nonce), ciphertext)
+            .map_err(|_| FieldEncryptionError::Corrupt)?;
+        String::from_utf8(plaintext).map_err(|_| FieldEncryptionError::Corrupt)
+    }
+
+    /// Keyed hash of each word in `text`, so encrypted text can still be matched by whole words
+    pub fn blind_index(&self, text: &str) -> Vec<String> {
+        index_words(text)
+            .iter()
+            .map(|word| {
+                let mut mac = <HmacSha256 as Mac>::new_from_slice(&self.index_key)
+                    .expect("HMAC accepts any key length");
+                mac.update(word.as_bytes());
+                hex::encode(&mac.finalize().into_bytes()[..INDEX_TOKEN_LEN])
+            })
+            .collect()
+    }
+}
+
+static KEYRING: OnceLock<Result<Option<FieldKeyring>, String>> = OnceLock::new();
+
+/// The process-wide keyring, read from the environment on first use
+pub fn keyring() -> Result<Option<&'static FieldKeyring>, FieldEncryptionError> {
+    match KEYRING.get_or_init(|| FieldKeyring::from_env().map_err(|e| e.to_string())) {
+        Ok(keyring) => Ok(keyring.as_ref()),
+        Err(message) => Err(FieldEncryptionError::InvalidConfig(message.clone())),
+    }
+}
+
+/// Encrypts `plaintext` if `field` is selected for encryption, otherwise returns it unchanged
+pub fn seal(field: EncryptedField, plaintext: &str) -> Result<String, FieldEncryptionError> {
+    match keyring()? {
+        Some(keyring) if keyring.encrypts(field) => keyring.encrypt(plaintext),
+        _ => Ok(plaintext.to_string()),
+    }
+}
+
+/// Decrypts a stored value; plaintext passes through so fields can be switched on gradually
+pub fn open(field: EncryptedField, value: String) -> Result<String, FieldEncryptionError> {
+    if !is_encrypted(&value) {
+        return Ok(value);
+    }
+    match keyring()? {
+        Some(keyring) => keyring.decrypt(&value),
+        None => Err(FieldEncryptionError::MissingKey(field.name().to_string())),
+    }
+}
+
+/// Blind index to store next to `field`; empty while the field is kept in plaintext
+pub fn blind_index(field: EncryptedField, text: Option<&str>) -> Result<Vec<String>, FieldEncryptionError> {
+    match (keyring()?, text) {
+        (Some(keyring), Some(text)) if keyring.encrypts(field) => Ok(keyring.blind_index(text)),
+        _ => Ok(Vec::new()),
+    }
+}
+
+/// Index tokens to look up for `query` when `field` is encrypted, `None` when it is plaintext
+/// and can be matched directly
+pub fn blind_index_query(
+    field: EncryptedField,
+    query: &str,
+) -> Result<Option<Vec<String>>, FieldEncryptionError> {
+    match keyring()? {
+        Some(keyring) if keyring.encrypts(field) => Ok(Some(keyring.blind_index(query))),
+        _ => Ok(None),
+    }
+}
+
+pub fn serialize_sealed<S>(field: EncryptedField, value: &str, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    let sealed = seal(field, value).map_err(serde::ser::Error::custom)?;
+    serializer.serialize_str(&sealed)
+}
+
+pub fn serialize_sealed_option<S>(
+    field: EncryptedField,
+    value: &Option<String>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    match value {
+        Some(value) => serialize_sealed(field, value, serializer),
+        None => serializer.serialize_none(),
+    }
+}
+
+pub fn deserialize_opened<'de, D>(field: EncryptedField, deserializer: D) -> Result<String, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value = String::deserialize(deserializer)?;
+    open(field, value).map_err(serde::de::Error::custom)
+}
+
+pub fn deserialize_opened_option<'de, D>(
+    field: EncryptedField,
+    deserializer: D,
+) -> Result<Option<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Option::<String>::deserialize(deserializer)?
+        .map(|value| open(field, value))
+        .transpose()
+        .map_err(serde::de::Error::custom)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keyring(keys: &[(&str, u8)]) -> FieldKeyring {
+        let keys = keys.iter().map(|&(id, byte)| (id.to_string(), [byte; KEY_LEN])).collect();
+        FieldKeyring::new(keys, EncryptedField::ALL.to_vec()).unwrap()
+    }
+
+    #[test]
+    fn encrypted_values_round_trip_under_the_active_key() {
+        let keyring = keyring(&[("k2", 2), ("k1", 1)]);
+
+        let first = keyring.encrypt("Discuss the raise").unwrap();
+        let second = keyring.encrypt("Discuss the raise").unwrap();
+
+        assert!(first.starts_with("mvenc:v1:k2:"));
+        assert_eq!(encrypted_key_id(&first), Some("k2"));
+        assert!(!first.contains("raise"));
+        // A fresh nonce per value, so equal notes do not look equal at rest
+        assert_ne!(first, second);
+        assert_eq!(keyring.decrypt(&first).unwrap(), "Discuss the raise");
+        assert_eq!(keyring.decrypt(&second).unwrap(), "Discuss the raise");
+        assert_eq!(keyring.decrypt(&keyring.encrypt("").unwrap()).unwrap(), "");
+    }
+
+    #[test]
+    fn tampered_or_malformed_values_are_rejected() {
+        let keyring = keyring(&[("k1", 1)]);
+        let sealed = keyring.encrypt("Discuss the raise").unwrap();
+        let (prefix, payload) = sealed.rsplit_once(':').unwrap();
+        let mut bytes = BASE64.decode(payload).unwrap();
+        let last = bytes.len() - 1;
+        bytes[last] ^= 1;
+        let tampered = format!("{}:{}", prefix, BASE64.encode(bytes));
+
+        for value in [
+            tampered.as_str(),
+            "mvenc:v1:k1:not base64!",
+            "mvenc:v1:k1:AAAA",
+            "mvenc:v1:k1",
+            "plain text",
+        ] {
+            assert!(
+                matches!(keyring.decrypt(value), Err(FieldEncryptionError::Corrupt)),
+                "{}",
+                value
+            );
+        }
+    }
+
+    #[test]
+    fn rotated_keyrings_read_old_values_and_write_with_the_new_key() {
+        let before = keyring(&[("k1", 1)]);
+        let old_value = before.encrypt("Quarterly goals").unwrap();
+
+        let rotated = keyring(&[("k2", 2), ("k1", 1)]);
+        assert_eq!(rotated.active_key_id(), "k2");
+        assert_eq!(rotated.decrypt(&old_value).unwrap(), "Quarterly goals");
+        assert_eq!(encrypted_key_id(&rotated.encrypt("Quarterly goals").unwrap()), Some("k2"));
+
+        // Once the old key is dropped its values can no longer be read
+        let retired = keyring(&[("k2", 2)]);
+        assert!(matches!(
+            retired.decrypt(&old_value),
+            Err(FieldEncryptionError::UnknownKey(key_id)) if key_id == "k1"
+        ));
+    }
+
+    #[test]
+    fn a_key_with_the_same_id_but_other_material_does_not_decrypt() {
+        let sealed = keyring(&[("k1", 1)]).encrypt("Quarterly goals").unwrap();
+
+        assert!(matches!(
+            keyring(&[("k1", 9)]).decrypt(&sealed),
+            Err(FieldEncryptionError::Corrupt)
+        ));
+    }
+
+    #[test]
+    fn blind_indexes_are_stable_per_active_key() {
+        let keyring = keyring(&[("k2", 2), ("k1", 1)]);
+        let tokens = keyring.blind_index("Budget review, budget REVIEW");
+
+        // Whole lowercased words, each once, in the same order every time
+        assert_eq!(tokens, keyring.blind_index("review budget"));
+        assert_eq!(tokens.len(), 2);
+        assert!(tokens.iter().all(|token| token.len() == INDEX_TOKEN_LEN * 2));
+        assert!(!tokens.iter().any(|token| token.contains("budget")));
+        assert!(tokens.contains(&keyring.blind_index("Budget")[0]));
+        // One-letter words are not indexed
+        assert!(keyring.blind_index("a I").is_empty());
+
+        // The same active key gives the same index across restarts; older keys do not matter
+        assert_eq!(tokens, self::keyring(&[("k2", 2)]).blind_index("budget review"));
+        // A new active key needs the index rebuilt
+        assert_ne!(tokens, self::keyring(&[("k3", 3), ("k2", 2)]).blind_index("budget review"));
+    }
+
+    #[test]
+    fn passphrases_derive_the_same_key_and_id_every_time() {
+        let (id, key) = derive_passphrase_key("correct horse", DEFAULT_SALT).unwrap();
+        let (same_id, same_key) = derive_passphrase_key("correct horse", DEFAULT_SALT).unwrap();
+        assert_eq!((id.as_str(), key), (same_id.as_str(), same_key));
+        assert!(id.starts_with("passphrase-"));
+
+        let (wrong_id, wrong_key) = derive_passphrase_key("wrong horse", DEFAULT_SALT).unwrap();
+        assert_ne!(id, wrong_id);
+        let sealed = FieldKeyring::new(vec![(id.clone(), key)], EncryptedField::ALL.to_vec())
+            .unwrap()
+            .encrypt("Quarterly goals")
+            .unwrap();
+        let wrong = FieldKeyring::new(vec![(wrong_id, wrong_key)], Vec::new()).unwrap();
+        assert!(matches!(
+            wrong.decrypt(&sealed),
+            Err(FieldEncryptionError::UnknownKey(key_id)) if key_id == id
+        ));
+    }
+
+    #[test]
+    fn keyfiles_list_keys_newest_first() {
+        let content = format!(
+            "# rotated 2026-10-01\nk2 {}\n\nk1 {}\n",
+            "22".repeat(KEY_LEN),
+            "11".repeat(KEY_LEN)
+        );
+        let keys = parse_keyfile("keys.txt", &content).unwrap();
+        assert_eq!(keys.len(), 2);
+        assert_eq!(keys[0], ("k2".to_string(), [0x22; KEY_LEN]));
+        assert_eq!(keys[1], ("k1".to_string(), [0x11; KEY_LEN]));
+
+        for (content, reason) in [
+            ("", "contains no keys"),
+            ("k1", "expected '<key id> <64 hex chars>'"),
+            ("k1 zz", "key is not hex"),
+            ("k1 abcd", "key must be 32 bytes"),
+            ("k/1 00", "key ids may only use"),
+        ] {
+            let error = parse_keyfile("keys.txt", content).unwrap_err().to_string();
+            assert!(error.contains(reason), "{}: {}", content, error);
+        }
+        let duplicate = format!("k1 {0}\nk1 {0}", "11".repeat(KEY_LEN));
+        let error = parse_keyfile("keys.txt", &duplicate).unwrap_err().to_string();
+        assert!(error.contains("keys.txt line 2: duplicate key id"), "{}", error);
+    }
+
+    #[test]
+    fn selected_fields_decide_what_is_encrypted() {
+        assert_eq!(parse_fields(None).unwrap(), EncryptedField::ALL.to_vec());
+        assert_eq!(
+            parse_fields(Some("reviews.feedback, tasks.notes,tasks.notes".to_string())).unwrap(),
+            vec![EncryptedField::ReviewFeedback, EncryptedField::TaskNotes]
+        );
+        assert!(parse_fields(Some("tasks.title".to_string())).is_err());
+
+        let key = vec![("k1".to_string(), [1; KEY_LEN])];
+        let notes_only = FieldKeyring::new(key, vec![EncryptedField::TaskNotes]).unwrap();
+        assert!(notes_only.encrypts(EncryptedField::TaskNotes));
+        // Webhook bodies carry notes, so they follow them
+        assert!(notes_only.encrypts(EncryptedField::WebhookPayload));
+        assert!(!notes_only.encrypts(EncryptedField::FeedbackNote));
+    }
+}
@@ -1 +1,7 @@
-pub mod date_time_serde;
\ No newline at end of file
+pub mod date_input;
+pub mod date_time_serde;
+pub mod time_zone;
+pub mod recurrence;
+pub mod field_encryption;
+pub mod quick_add;
+pub mod rank;
@@ -0,0 +1,955 @@
+use crate::dtos::task_dtos::CreateTaskRequest;
+use crate::models::tasks_model::ETaskPriority;
+use crate::utils::date_input::{DateInput, DatePreferences};
+use crate::utils::recurrence::parse_rrule;
+use crate::utils::time_zone::FlexibleDateTime;
+use chrono::{
+    DateTime, Datelike, Duration, Months, NaiveDate, NaiveDateTime, NaiveTime, SecondsFormat,
+    Utc, Weekday,
+};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+/// Words that may introduce a date or time without being part of the task name
+const WHEN_PREFIXES: [&str; 6] = ["on", "at", "by", "due", "until", "before"];
+const WORKDAYS: [Weekday; 5] = [
+    Weekday::Mon,
+    Weekday::Tue,
+    Weekday::Wed,
+    Weekday::Thu,
+    Weekday::Fri,
+];
+
+/// A reportee the parser can recognise in the text
+#[derive(Debug, Clone)]
+pub struct KnownReportee {
+    pub id: i64,
+    pub name: String,
+}
+
+/// What relative expressions are measured from
+pub struct QuickAddContext<'a> {
+    pub now: DateTime<Utc>,
+    pub dates: &'a DatePreferences,
+    pub reportees: &'a [KnownReportee],
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "PascalCase")]
+pub enum EQuickAddPart {
+    Due,
+    Priority,
+    Tag,
+    Recurrence,
+    Reportee,
+}
+
+/// A piece of the text and what it was read as
+#[derive(Debug, Clone, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct QuickAddPart {
+    pub kind: EQuickAddPart,
+    pub text: String,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ReporteeMention {
+    pub id: i64,
+    pub name: String,
+    /// The words that named the reportee
+    pub text: String,
+}
+
+/// Everything read from a quick-add text, in the shape it would be saved
+#[derive(Debug, Clone, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct QuickAddParse {
+    /// The text left once dates, markers and recurrence are taken out
+    pub name: String,
+    /// `YYYY-MM-DD` for due days, an ISO-8601 timestamp in the user's offset for due times
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub due_date: Option<String>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub due_has_time: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub priority: Option<ETaskPriority>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recurrence: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub reportees: Vec<ReporteeMention>,
+    /// `@` mentions that matched no reportee; they stay in the name
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub unknown_mentions: Vec<String>,
+    /// The recognised pieces in the order they appear
+    pub parts: Vec<QuickAddPart>,
+    #[serde(skip)]
+    due: Option<DateInput>,
+}
+
+impl QuickAddParse {
+    /// The task the text describes
+    pub fn to_request(&self) -> CreateTaskRequest {
+        CreateTaskRequest {
+            name: self.name.clone(),
+            due_date: self.due.clone(),
+            priority: self.priority.clone(),
+            status: None,
+            workflow_status: None,
+            tags: self.tags.clone(),
+            recurrence: self.recurrence.clone(),
+            estimate_minutes: None,
+            notes: None,
+            external_id: None,
+            assignee_id: None,
+            reportee_ids: self.reportees.iter().map(|reportee| reportee.id).collect(),
+        }
+    }
+}
+
+/// Reads a one-line task description such as
+/// `Email Priya about offsite tomorrow 3pm !high #planning every friday`.
+///
+/// - Due dates: `today`, `tomorrow`, weekdays (`friday`, `next tue`, `on wed`; always the first
+///   one after today, or today for `this`), `in 3 days`, `in 2 hours`, `next week|month|year`,
+///   `end of week|month|year`, `oct 20`, `20th october` and the formats `DateInput` accepts
+/// - Times: `3pm`, `3:30 pm`, `15:00`, `noon`; a time alone is today, or tomorrow once passed
+/// - `!high` sets the priority, `#tag` adds a tag, `@name` mentions a reportee
+/// - Recurrence: `daily`, `weekly`, `every 2 weeks`, `every other month`, `every weekday`,
+///   `every mon and thu`; weekly rules without a due date are first due on their next day
+///
+/// Reportees named without `@` are recognised by their exact first or full name and stay in the
+/// task name. Dates that read differently day-first and month-first are an error unless the
+/// user has set a date order.
+pub fn parse_quick_add(text: &str, context: &QuickAddContext) -> Result<QuickAddParse, String> {
+    QuickAddParser::new(text, context).parse()
+}
+
+/// When a phrase says a task is due
+enum When {
+    Day(NaiveDate),
+    Time(NaiveTime),
+    DayTime(NaiveDate, NaiveTime),
+    Instant(DateTime<Utc>),
+}
+
+struct Word<'t> {
+    raw: &'t str,
+    /// Without trailing punctuation
+    clean: &'t str,
+    /// `clean` lowercased, for matching keywords
+    key: String,
+}
+
+struct QuickAddParser<'t, 'c> {
+    words: Vec<Word<'t>>,
+    used: Vec<bool>,
+    /// Replaces a word in the name, e.g. an `@mention` without the `@`
+    shown: Vec<Option<String>>,
+    parts: Vec<(usize, QuickAddPart)>,
+    context: &'c QuickAddContext<'c>,
+    now: NaiveDateTime,
+    today: NaiveDate,
+    day: Option<NaiveDate>,
+    time: Option<NaiveTime>,
+    instant: Option<DateTime<Utc>>,
+    priority: Option<ETaskPriority>,
+    tags: Vec<String>,
+    /// The RRULE and, for weekly rules, the days it repeats on
+    recurrence: Option<(String, Vec<Weekday>)>,
+    reportees: Vec<ReporteeMention>,
+    unknown_mentions: Vec<String>,
+}
+
+impl<'t, 'c> QuickAddParser<'t, 'c> {
+    fn new(text: &'t str, context: &'c QuickAddContext<'c>) -> Self {
+        let words: Vec<Word> = text
+            .split_whitespace()
+            .map(|raw| {
+                let clean = raw.trim_end_matches([',', '.', ';', ':', '?', ')']);
+                let clean = if clean.is_empty() { raw } else { clean };
+                Word {
+                    raw,
+                    clean,
+                    key: clean.to_lowercase(),
+                }
+            })
+            .collect();
+        let now = context.now.with_timezone(&context.dates.tz()).naive_local();
+        Self {
+            used: vec![false; words.len()],
+            shown: vec![None; words.len()],
+            words,
+            parts: Vec::new(),
+            context,
+            now,
+            today: now.date(),
+            day: None,
+            time: None,
+            instant: None,
+            priority: None,
+            tags: Vec::new(),
+            recurrence: None,
+            reportees: Vec::new(),
+            unknown_mentions: Vec::new(),
+        }
+    }
+
+    /// The keyword at `index`, unless it is out of range or already read
+    fn key(&self, index: usize) -> Option<&str> {
+        match self.used.get(index) {
+            Some(false) => Some(self.words[index].key.as_str()),
+            _ => None,
+        }
+    }
+
+    fn take(&mut self, start: usize, len: usize, kind: EQuickAddPart) {
+        let text = self.words[start..start + len]
+            .iter()
+            .map(|word| word.clean)
+            .collect::<Vec<_>>()
+            .join(" ");
+        self.used[start..start + len].fill(true);
+        self.parts.push((start, QuickAddPart { kind, text }));
+    }
+
+    fn parse(mut self) -> Result<QuickAddParse, String> {
+        let mut index = 0;
+        while index < self.words.len() {
+            index += match self.read_at(index)? {
+                0 => 1,
+                len => len,
+            };
+        }
+        self.find_named_reportees();
+
+        let name = (0..self.words.len())
+            .filter(|&index| !self.used[index])
+            .map(|index| self.shown[index].as_deref().unwrap_or(self.words[index].raw))
+            .collect::<Vec<_>>()
+            .join(" ");
+        if name.is_empty() {
+            return Err("Nothing is left for the task name".to_string());
+        }
+
+        let due = self.due()?;
+        let tz = self.context.dates.tz();
+        let (due_date, due_has_time) = match &due {
+            Some(DateInput::Day(day)) => (Some(day.format("%Y-%m-%d").to_string()), false),
+            Some(DateInput::Time(FlexibleDateTime::Absolute(at))) => {
+                let local = at.with_timezone(&tz).fixed_offset();
+                (Some(local.to_rfc3339_opts(SecondsFormat::Secs, false)), true)
+            }
+            _ => (None, false),
+        };
+        self.parts.sort_by_key(|(start, _)| *start);
+        Ok(QuickAddParse {
+            name,
+            due_date,
+            due_has_time,
+            priority: self.priority,
+            tags: self.tags,
+            recurrence: self.recurrence.map(|(rule, _)| rule),
+            reportees: self.reportees,
+            unknown_mentions: self.unknown_mentions,
+            parts: self.parts.into_iter().map(|(_, part)| part).collect(),
+            due,
+        })
+    }
+
+    /// Reads whatever starts at `index`, returning how many words it took
+    fn read_at(&mut self, index: usize) -> Result<usize, String> {
+        let clean = self.words[index].clean;
+        if let Some(tag) = clean.strip_prefix('#').filter(|tag| !tag.is_empty()) {
+            let tag = tag.to_lowercase();
+            if !self.tags.contains(&tag) {
+                self.tags.push(tag);
+            }
+            self.take(index, 1, EQuickAddPart::Tag);
+            return Ok(1);
+        }
+        if let Some(marker) = clean.strip_prefix('!')
+            && self.priority.is_none()
+            && let Ok(priority) = marker.parse::<ETaskPriority>()
+        {
+            self.priority = Some(priority);
+            self.take(index, 1, EQuickAddPart::Priority);
+            return Ok(1);
+        }
+        if let Some(mention) = clean.strip_prefix('@').filter(|mention| !mention.is_empty()) {
+            match self.mentioned_reportee(mention) {
+                Some(reportee) => {
+                    let suffix = &self.words[index].raw[clean.len()..];
+                    self.shown[index] = Some(format!("{}{}", &clean[1..], suffix));
+                    self.add_reportee(reportee, clean);
+                    self.parts.push((
+                        index,
+                        QuickAddPart {
+                            kind: EQuickAddPart::Reportee,
+                            text: clean.to_string(),
+                        },
+                    ));
+                }
+                None => self.unknown_mentions.push(mention.to_string()),
+            }
+            return Ok(1);
+        }
+        if self.recurrence.is_none()
+            && let Some((len, rule, days)) = self.recurrence_at(index)
+        {
+            self.recurrence = Some((parse_rrule(&rule)?, days));
+            self.take(index, len, EQuickAddPart::Recurrence);
+            return Ok(len);
+        }
+
+        let prefixed = self
+            .key(index)
+            .is_some_and(|key| WHEN_PREFIXES.contains(&key));
+        let start = if prefixed { index + 1 } else { index };
+        let Some((len, when)) = self.when_at(start, prefixed)? else {
+            return Ok(0);
+        };
+        let free = match when {
+            When::Day(_) => self.day.is_none() && self.instant.is_none(),
+            When::Time(_) => self.time.is_none() && self.instant.is_none(),
+            When::DayTime(..) | When::Instant(_) => {
+                self.day.is_none() && self.time.is_none() && self.instant.is_none()
+            }
+        };
+        if !free {
+            return Ok(0);
+        }
+        match when {
+            When::Day(day) => self.day = Some(day),
+            When::Time(time) => self.time = Some(time),
+            When::DayTime(day, time) => {
+                self.day = Some(day);
+                self.time = Some(time);
+            }
+            When::Instant(at) => self.instant = Some(at),
+        }
+        let len = len + start - index;
+        self.take(index, len, EQuickAddPart::Due);
+        Ok(len)
+    }
+
+    fn due(&self) -> Result<Option<DateInput>, String> {
+        let local = |day: NaiveDate, time: NaiveTime| {
+            DateInput::Time(FlexibleDateTime::Local(day.and_time(time)))
+                .resolve(self.context.dates)
+                .map(Some)
+        };
+        if let Some(at) = self.instant {
+            return Ok(Some(DateInput::Time(FlexibleDateTime::Absolute(at))));
+        }
+        let day = self.day.or_else(|| {
+            // A weekly rule starts on its next day, skipping today once the time has passed
+            let (_, days) = self.recurrence.as_ref()?;
+            let include_today = self.time.is_none_or(|time| time > self.now.time());
+            days.iter().map(|day| upcoming(self.today, *day, include_today)).min()
+        });
+        match (day, self.time) {
+            (Some(day), Some(time)) => local(day, time),
+            (Some(day), None) => Ok(Some(DateInput::Day(day))),
+            (None, Some(time)) if time > self.now.time() => local(self.today, time),
+            (None, Some(time)) => local(self.today + Duration::days(1), time),
+            (None, None) => Ok(None),
+        }
+    }
+
+    /// A date or time starting at `start`. Weekday abbreviations only count after a prefix such
+    /// as `on`, so that words like "sat" in a name are left alone.
+    fn when_at(&self, start: usize, prefixed: bool) -> Result<Option<(usize, When)>, String> {
+        let Some(key) = self.key(start) else {
+            return Ok(None);
+        };
+        let next = self.key(start + 1);
+        let today = self.today;
+        let when = match key {
+            "today" | "tonight" => Some((1, When::Day(today))),
+            "tomorrow" | "tmrw" | "tmr" => Some((1, When::Day(today + Duration::days(1)))),
+            "noon" | "midday" => Some((1, When::Time(NaiveTime::from_hms_opt(12, 0, 0).unwrap()))),
+            "eow" => Some((1, When::Day(end_of_week(today)))),
+            "eom" => Some((1, When::Day(end_of_month(today)))),
+            "eoy" => Some((1, When::Day(end_of_year(today)))),
+            "day" if next == Some("after") && self.key(start + 2) == Some("tomorrow") => {
+                Some((3, When::Day(today + Duration::days(2))))
+            }
+            "next" | "this" => {
+                let this = key == "this";
+                match next {
+                    Some("week") if !this => Some((2, When::Day(next_monday(today)))),
+                    Some("month") if !this => Some((2, When::Day(first_of_next_month(today)))),
+                    Some("year") if !this => {
+                        Some((2, When::Day(NaiveDate::from_ymd_opt(today.year() + 1, 1, 1)
+                            .unwrap())))
+                    }
+                    Some("weekend") => Some((2, When::Day(upcoming(today, Weekday::Sat, this)))),
+                    Some(next) => {
+                        weekday(next).map(|day| (2, When::Day(upcoming(today, day, this))))
+                    }
+                    None => None,
+                }
+            }
+            "weekend" => Some((1, When::Day(upcoming(today, Weekday::Sat, true)))),
+            "end" if next == Some("of") => {
+                let (skip, unit) = match self.key(start + 2) {
+                    Some("the") => (4, self.key(start + 3)),
+                    unit => (3, unit),
+                };
+                match unit {
+                    Some("week") => Some((skip, When::Day(end_of_week(today)))),
+                    Some("month") => Some((skip, When::Day(end_of_month(today)))),
+                    Some("year") => Some((skip, When::Day(end_of_year(today)))),
+                    _ => None,
+                }
+            }
+            "in" => self.offset_at(start + 1).map(|(len, when)| (len + 1, when)),
+            _ => None,
+        };
+        if when.is_some() {
+            return Ok(when);
+        }
+
+        if let Some(day) = weekday(key).filter(|_| prefixed || key.ends_with("day")) {
+            return Ok(Some((1, When::Day(upcoming(today, day, false)))));
+        }
+        if let Some((len, time)) = self.time_at(start) {
+            return Ok(Some((len, When::Time(time))));
+        }
+        if let Some((len, day)) = self.month_day_at(start) {
+            return match self.time_at(start + len) {
+                Some((time_len, time)) => Ok(Some((len + time_len, When::DayTime(day, time)))),
+                None => Ok(Some((len, When::Day(day)))),
+            };
+        }
+        self.explicit_date_at(start)
+    }
+
+    /// `3 days`, `a week`, `2 hours` after `in`
+    fn offset_at(&self, start: usize) -> Option<(usize, When)> {
+        let count = number(self.key(start)?)?;
+        let unit = self.key(start + 1)?;
+        let unit = unit.strip_suffix('s').unwrap_or(unit);
+        let today = self.today;
+        let when = match unit {
+            "min" | "minute" => When::Instant(self.context.now + Duration::minutes(count)),
+            "hour" | "hr" => When::Instant(self.context.now + Duration::hours(count)),
+            "day" => When::Day(today + Duration::days(count)),
+            "week" => When::Day(today + Duration::weeks(count)),
+            "month" => When::Day(today.checked_add_months(Months::new(count as u32))?),
+            "year" => When::Day(today.checked_add_months(Months::new(12 * count as u32))?),
+            _ => return None,
+        };
+        Some((2, when))
+    }
+
+    /// `3pm`, `3:30pm`, `3 pm` or `15:00`
+    fn time_at(&self, start: usize) -> Option<(usize, NaiveTime)> {
+        let key = self.key(start)?;
+        if let Some((digits, meridiem)) = split_meridiem(key) {
+            return twelve_hour(digits, meridiem).map(|time| (1, time));
+        }
+        if let Some(meridiem @ ("am" | "pm")) = self.key(start + 1) {
+            return twelve_hour(key, meridiem).map(|time| (2, time));
+        }
+        if key.contains(':') {
+            return NaiveTime::parse_from_str(key, "%H:%M").ok().map(|time| (1, time));
+        }
+        None
+    }
+
+    /// `oct 20`, `october 20th 2027`, `20 oct` or `20th of october`; without a year, the next
+    /// such day from today
+    fn month_day_at(&self, start: usize) -> Option<(usize, NaiveDate)> {
+        let first = self.key(start)?;
+        let (month, day, mut len) = match month(first) {
+            Some(month) => (month, day_of_month(self.key(start + 1)?)?, 2),
+            None => {
+                let day = day_of_month(first)?;
+                match self.key(start + 1)? {
+                    "of" => (month(self.key(start + 2)?)?, day, 3),
+                    next => (month(next)?, day, 2),
+                }
+            }
+        };
+        match self.key(start + len).and_then(|key| key.parse::<i32>().ok()) {
+            Some(year) if (1970..=9999).contains(&year) => {
+                len += 1;
+                Some((len, NaiveDate::from_ymd_opt(year, month, day)?))
+            }
+            _ => {
+                let year = self.today.year();
+                let date = NaiveDate::from_ymd_opt(year, month, day)
+                    .filter(|date| *date >= self.today)
+                    .or_else(|| NaiveDate::from_ymd_opt(year + 1, month, day))?;
+                Some((len, date))
+            }
+        }
+    }
+
+    /// `2026-10-20`, `20/10/2026` or an RFC 3339 timestamp, read with the user's preferences
+    fn explicit_date_at(&self, start: usize) -> Result<Option<(usize, When)>, String> {
+        let Some(word) = self.key(start).map(|_| self.words[start].clean) else {
+            return Ok(None);
+        };
+        if !word.starts_with(|c: char| c.is_ascii_digit()) || !word.contains(['-', '/']) {
+            return Ok(None);
+        }
+        let Ok(input) = DateInput::parse(word) else {
+            return Ok(None);
+        };
+        Ok(match input.resolve(self.context.dates)? {
+            DateInput::Day(day) => Some((1, When::Day(day))),
+            DateInput::Time(FlexibleDateTime::Absolute(at)) => Some((1, When::Instant(at))),
+            _ => None,
+        })
+    }
+
+    /// A recurrence phrase at `index`: its length, the RRULE and, for weekly rules on given
+    /// days, those days
+    fn recurrence_at(&self, index: usize) -> Option<(usize, String, Vec<Weekday>)> {
+        let key = self.key(index)?;
+        let simple = |frequency: &str| Some((1, format!("FREQ={}", frequency), Vec::new()));
+        match key {
+            "daily" => return simple("DAILY"),
+            "weekly" => return simple("WEEKLY"),
+            "fortnightly" | "biweekly" => {
+                return Some((1, "FREQ=WEEKLY;INTERVAL=2".to_string(), Vec::new()));
+            }
+            "monthly" => return simple("MONTHLY"),
+            "yearly" | "annually" => return simple("YEARLY"),
+            "every" | "each" => {}
+            _ => return None,
+        }
+
+        let next = self.key(index + 1)?;
+        let (interval, unit_at) = match next {
+            "other" => (2, index + 2),
+            _ => match number(next) {
+                Some(count) if count > 0 => (count, index + 2),
+                _ => (1, index + 1),
+            },
+        };
+        if let Some(unit) = self.key(unit_at) {
+            let unit = unit.strip_suffix('s').unwrap_or(unit);
+            let frequency = match unit {
+                "day" => Some("DAILY"),
+                "week" => Some("WEEKLY"),
+                "month" => Some("MONTHLY"),
+                "year" => Some("YEARLY"),
+                _ => None,
+            };
+            if let Some(frequency) = frequency {
+                let rule = match interval {
+                    1 => format!("FREQ={}", frequency),
+                    interval => format!("FREQ={};INTERVAL={}", frequency, interval),
+                };
+                return Some((unit_at - index + 1, rule, Vec::new()));
+            }
+        }
+        if interval != 1 {
+            return None;
+        }
+
+        let (len, days) = match next {
+            "weekday" | "weekdays" => (2, WORKDAYS.to_vec()),
+            "weekend" | "weekends" => (2, vec![Weekday::Sat, Weekday::Sun]),
+            _ => {
+                let mut days = Vec::new();
+                let mut at = index + 1;
+                while let Some(key) = self.key(at) {
+                    if let Some(day) = weekday(key) {
+                        days.push(day);
+                        at += 1;
+                    } else if key == "and" && self.key(at + 1).and_then(weekday).is_some() {
+                        at += 1;
+                    } else {
+                        break;
+                    }
+                }
+                if days.is_empty() {
+                    return None;
+                }
+                days.sort_by_key(Weekday::num_days_from_monday);
+                days.dedup();
+                (at - index, days)
+            }
+        };
+        let codes: Vec<&str> = days.iter().map(|day| rrule_day(*day)).collect();
+        Some((len, format!("FREQ=WEEKLY;BYDAY={}", codes.join(",")), days))
+    }
+
+    /// The reportee an `@mention` names: full name first, then a first name only one has.
+    /// `_` and `.` stand for spaces.
+    fn mentioned_reportee(&self, mention: &str) -> Option<KnownReportee> {
+        let mention = mention.replace(['_', '.'], " ").to_lowercase();
+        let reportees = self.context.reportees;
+        reportees
+            .iter()
+            .find(|reportee| reportee.name.to_lowercase() == mention)
+            .or_else(|| {
+                let mut by_first_name = reportees.iter().filter(|reportee| {
+                    first_name(&reportee.name).to_lowercase() == mention
+                });
+                let reportee = by_first_name.next()?;
+                by_first_name.next().is_none().then_some(reportee)
+            })
+            .cloned()
+    }
+
+    /// Reportees named in the remaining text with their exact full or first name
+    fn find_named_reportees(&mut self) {
+        let reportees = self.context.reportees;
+        let mut index = 0;
+        while index < self.words.len() {
+            if self.used[index] {
+                index += 1;
+                continue;
+            }
+            let full_match = reportees.iter().find_map(|reportee| {
+                let parts: Vec<&str> = reportee.name.split_whitespace().collect();
+                let matches = parts.len() > 1
+                    && parts.iter().enumerate().all(|(offset, part)| {
+                        self.words
+                            .get(index + offset)
+                            .filter(|_| !self.used[index + offset])
+                            .is_some_and(|word| possessive(word.clean) == *part)
+                    });
+                matches.then(|| (parts.len(), reportee.clone()))
+            });
+            let found = full_match.or_else(|| {
+                let word = possessive(self.words[index].clean);
+                let mut by_first_name = reportees
+                    .iter()
+                    .filter(|reportee| first_name(&reportee.name) == word);
+                let reportee = by_first_name.next()?;
+                by_first_name.next().is_none().then(|| (1, reportee.clone()))
+            });
+            match found {
+                Some((len, reportee)) => {
+                    let text = self.words[index..index + len]
+                        .iter()
+                        .map(|word| possessive(word.clean))
+                        .collect::<Vec<_>>()
+                        .join(" ");
+                    if self.add_reportee(reportee, &text) {
+                        self.parts.push((
+                            index,
+                            QuickAddPart {
+                                kind: EQuickAddPart::Reportee,
+                                text,
+                            },
+                        ));
+                    }
+                    index += len;
+                }
+                None => index += 1,
+            }
+        }
+    }
+
+    /// Records a mention unless the reportee is already mentioned
+    fn add_reportee(&mut self, reportee: KnownReportee, text: &str) -> bool {
+        if self.reportees.iter().any(|mention| mention.id == reportee.id) {
+            return false;
+        }
+        self.reportees.push(ReporteeMention {
+            id: reportee.id,
+            name: reportee.name,
+            text: text.to_string(),
+        });
+        true
+    }
+}
+
+fn first_name(name: &str) -> &str {
+    name.split_whitespace().next().unwrap_or(name)
+}
+
+fn possessive(word: &str) -> &str {
+    word.strip_suffix("'s").unwrap_or(word)
+}
+
+fn number(key: &str) -> Option<i64> {
+    let count = match key {
+        "a" | "an" | "one" => 1,
+        "two" => 2,
+        "three" => 3,
+        "four" => 4,
+        "five" => 5,
+        "six" => 6,
+        "seven" => 7,
+        "eight" => 8,
+        "nine" => 9,
+        "ten" => 10,
+        _ => key.parse().ok()?,
+    };
+    (0..=1000).contains(&count).then_some(count)
+}
+
+fn weekday(key: &str) -> Option<Weekday> {
+    Some(match key {
+        "mon" | "monday" => Weekday::Mon,
+        "tue" | "tues" | "tuesday" => Weekday::Tue,
+        "wed" | "weds" | "wednesday" => Weekday::Wed,
+        "thu" | "thur" | "thurs" | "thursday" => Weekday::Thu,
+        "fri" | "friday" => Weekday::Fri,
+        "sat" | "saturday" => Weekday::Sat,
+        "sun" | "sunday" => Weekday::Sun,
+        "mondays" | "tuesdays" | "wednesdays" | "thursdays" | "fridays" | "saturdays"
+        | "sundays" => return weekday(key.strip_suffix('s')?),
+        _ => return None,
+    })
+}
+
+fn rrule_day(day: Weekday) -> &'static str {
+    match day {
+        Weekday::Mon => "MO",
+        Weekday::Tue => "TU",
+        Weekday::Wed => "WE",
+        Weekday::Thu => "TH",
+        Weekday::Fri => "FR",
+        Weekday::Sat => "SA",
+        Weekday::Sun => "SU",
+    }
+}
+
+fn month(key: &str) -> Option<u32> {
+    Some(match key {
+        "jan" | "january" => 1,
+        "feb" | "february" => 2,
+        "mar" | "march" => 3,
+        "apr" | "april" => 4,
+        "may" => 5,
+        "jun" | "june" => 6,
+        "jul" | "july" => 7,
+        "aug" | "august" => 8,
+        "sep" | "sept" | "september" => 9,
+        "oct" | "october" => 10,
+        "nov" | "november" => 11,
+        "dec" | "december" => 12,
+        _ => return None,
+    })
+}
+
+/// `20`, `20th`, `1st`
+fn day_of_month(key: &str) -> Option<u32> {
+    let digits = key.trim_end_matches(|c: char| c.is_ascii_alphabetic());
+    let suffix = &key[digits.len()..];
+    if !matches!(suffix, "" | "st" | "nd" | "rd" | "th") || digits.len() > 2 {
+        return None;
+    }
+    digits.parse().ok().filter(|day| (1..=31).contains(day))
+}
+
+/// `3pm` into `("3", "pm")`
+fn split_meridiem(key: &str) -> Option<(&str, &str)> {
+    let digits = key.strip_suffix("am").or_else(|| key.strip_suffix("pm"))?;
+    Some((digits, &key[digits.len()..]))
+}
+
+/// `3` or `3:30` with `am` or `pm`
+fn twelve_hour(digits: &str, meridiem: &str) -> Option<NaiveTime> {
+    let (hour, minute) = match digits.split_once(':') {
+        Some((hour, minute)) if minute.len() == 2 => (hour.parse().ok()?, minute.parse().ok()?),
+        Some(_) => return None,
+        None => (digits.parse::<u32>().ok()?, 0),
+    };
+    if !(1..=12).contains(&hour) {
+        return None;
+    }
+    let hour = match meridiem {
+        "am" => hour % 12,
+        _ => hour % 12 + 12,
+    };
+    NaiveTime::from_hms_opt(hour, minute, 0)
+}
+
+/// The first `day` after `today`, or from `today` on when `include_today` is set
+fn upcoming(today: NaiveDate, day: Weekday, include_today: bool) -> NaiveDate {
+    let ahead = (7 + day.num_days_from_monday() - today.weekday().num_days_from_monday()) % 7;
+    let ahead = if ahead == 0 && !include_today { 7 } else { ahead };
+    today + Duration::days(ahead as i64)
+}
+
+fn next_monday(today: NaiveDate) -> NaiveDate {
+    upcoming(today, Weekday::Mon, false)
+}
+
+/// The working week ends on Friday; at the weekend that is the coming one
+fn end_of_week(today: NaiveDate) -> NaiveDate {
+    upcoming(today, Weekday::Fri, true)
+}
+
+fn first_of_next_month(today: NaiveDate) -> NaiveDate {
+    let first = today.with_day(1).unwrap();
+    first.checked_add_months(Months::new(1)).unwrap()
+}
+
+fn end_of_month(today: NaiveDate) -> NaiveDate {
+    first_of_next_month(today) - Duration::days(1)
+}
+
+fn end_of_year(today: NaiveDate) -> NaiveDate {
+    NaiveDate::from_ymd_opt(today.year(), 12, 31).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_quick_add, EQuickAddPart, KnownReportee, QuickAddContext, QuickAddParse};
+    use crate::models::tasks_model::ETaskPriority;
+    use crate::utils::date_input::{DateInput, DatePreferences};
+    use chrono::{NaiveDate, TimeZone, Utc};
+
+    /// Monday 19 October 2026, 10:00 in Berlin
+    fn parse(text: &str) -> Result<QuickAddParse, String> {
+        let dates = DatePreferences {
+            time_zone: "Europe/Berlin".to_string(),
+            date_order: None,
+        };
+        let reportees = [
+            KnownReportee {
+                id: 1,
+                name: "Priya Shah".to_string(),
+            },
+            KnownReportee {
+                id: 2,
+                name: "Sam Lee".to_string(),
+            },
+        ];
+        let context = QuickAddContext {
+            now: Utc.with_ymd_and_hms(2026, 10, 19, 8, 0, 0).unwrap(),
+            dates: &dates,
+            reportees: &reportees,
+        };
+        parse_quick_add(text, &context)
+    }
+
+    fn due_day(text: &str) -> Option<NaiveDate> {
+        parse(text).unwrap().to_request().due_date.as_ref().and_then(DateInput::as_day)
+    }
+
+    fn day(month: u32, day: u32) -> Option<NaiveDate> {
+        NaiveDate::from_ymd_opt(2026, month, day)
+    }
+
+    #[test]
+    fn reads_the_documented_example() {
+        let parsed =
+            parse("Email Priya about offsite tomorrow 3pm !high #planning every friday").unwrap();
+        assert_eq!(parsed.name, "Email Priya about offsite");
+        assert_eq!(parsed.due_date.as_deref(), Some("2026-10-20T15:00:00+02:00"));
+        assert!(parsed.due_has_time);
+        assert_eq!(parsed.priority, Some(ETaskPriority::High));
+        assert_eq!(parsed.tags, vec!["planning"]);
+        assert_eq!(parsed.recurrence.as_deref(), Some("FREQ=WEEKLY;BYDAY=FR"));
+        assert_eq!(parsed.reportees.len(), 1);
+        assert_eq!(parsed.reportees[0].id, 1);
+        assert_eq!(parsed.reportees[0].text, "Priya");
+
+        let kinds: Vec<EQuickAddPart> = parsed.parts.iter().map(|part| part.kind).collect();
+        assert_eq!(
+            kinds,
+            [
+                EQuickAddPart::Reportee,
+                EQuickAddPart::Due,
+                EQuickAddPart::Due,
+                EQuickAddPart::Priority,
+                EQuickAddPart::Tag,
+                EQuickAddPart::Recurrence,
+            ]
+        );
+
+        let request = parsed.to_request();
+        assert_eq!(request.reportee_ids, vec![1]);
+        let (at, has_time) = request.due_date.unwrap().stored().unwrap();
+        assert_eq!(at.to_chrono(), Utc.with_ymd_and_hms(2026, 10, 20, 13, 0, 0).unwrap());
+        assert!(has_time);
+    }
+
+    #[test]
+    fn reads_relative_days() {
+        assert_eq!(due_day("Call the bank next tue"), day(10, 20));
+        assert_eq!(due_day("Call the bank next mon"), day(10, 26));
+        assert_eq!(due_day("Call the bank this mon"), day(10, 19));
+        assert_eq!(due_day("Call the bank on mon"), day(10, 26));
+        assert_eq!(due_day("Call the bank in 3 days"), day(10, 22));
+        assert_eq!(due_day("Call the bank in two weeks"), day(11, 2));
+        assert_eq!(due_day("Call the bank end of month"), day(10, 31));
+        assert_eq!(due_day("Call the bank by the end of the month"), day(10, 31));
+        assert_eq!(due_day("Call the bank end of week"), day(10, 23));
+        assert_eq!(due_day("Call the bank oct 20"), day(10, 20));
+        assert_eq!(due_day("Call the bank 18th october"), NaiveDate::from_ymd_opt(2027, 10, 18));
+        assert_eq!(parse("Call the bank in 3 days").unwrap().name, "Call the bank");
+    }
+
+    #[test]
+    fn a_time_alone_is_today_until_it_has_passed() {
+        let later = parse("Stand-up 11:30").unwrap();
+        assert_eq!(later.due_date.as_deref(), Some("2026-10-19T11:30:00+02:00"));
+        let passed = parse("Stand-up 9am").unwrap();
+        assert_eq!(passed.due_date.as_deref(), Some("2026-10-20T09:00:00+02:00"));
+    }
+
+    #[test]
+    fn reads_priority_markers_and_tags() {
+        let parsed = parse("Fix the build !urgent #Work #ci #work !low").unwrap();
+        assert_eq!(parsed.priority, Some(ETaskPriority::Urgent));
+        assert_eq!(parsed.tags, vec!["work", "ci"]);
+        assert_eq!(parsed.name, "Fix the build !low");
+
+        let parsed = parse("Say hi! to the team").unwrap();
+        assert_eq!(parsed.priority, None);
+        assert_eq!(parsed.name, "Say hi! to the team");
+    }
+
+    #[test]
+    fn reads_recurrence_phrases() {
+        let recurrence = |text: &str| parse(text).unwrap().recurrence;
+        assert_eq!(recurrence("Water plants daily").as_deref(), Some("FREQ=DAILY"));
+        assert_eq!(
+            recurrence("Pay rent every other month").as_deref(),
+            Some("FREQ=MONTHLY;INTERVAL=2")
+        );
+        assert_eq!(
+            recurrence("Sync every 2 weeks").as_deref(),
+            Some("FREQ=WEEKLY;INTERVAL=2")
+        );
+        assert_eq!(
+            recurrence("Gym every mon and thu").as_deref(),
+            Some("FREQ=WEEKLY;BYDAY=MO,TH")
+        );
+        assert_eq!(
+            recurrence("Check inbox every weekday").as_deref(),
+            Some("FREQ=WEEKLY;BYDAY=MO,TU,WE,TH,FR")
+        );
+
+        // A weekly rule without a due date is first due on its next day, today included
+        assert_eq!(due_day("Check inbox every weekday"), day(10, 19));
+        assert_eq!(due_day("Gym every thu"), day(10, 22));
+        assert_eq!(parse("Gym every mon and thu").unwrap().name, "Gym");
+    }
+
+    #[test]
+    fn keeps_unknown_mentions_in_the_name() {
+        let parsed = parse("Call @bob about the budget").unwrap();
+        assert_eq!(parsed.name, "Call @bob about the budget");
+        assert_eq!(parsed.unknown_mentions, vec!["bob"]);
+        assert!(parsed.reportees.is_empty());
+
+        let parsed = parse("Lunch with @sam_lee").unwrap();
+        assert_eq!(parsed.name, "Lunch with sam_lee");
+        assert_eq!(parsed.reportees[0].id, 2);
+        assert!(parsed.unknown_mentions.is_empty());
+    }
+
+    #[test]
+    fn rejects_ambiguous_dates_and_empty_names() {
+        let error = parse("Pay rent 03/04/2026").unwrap_err();
+        assert!(error.contains("ambiguous"), "{}", error);
+        assert!(parse("tomorrow 3pm #planning").is_err());
+    }
+}
@@ -0,0 +1,159 @@
+/// Digits of a rank in ascending byte order, so ranks compare correctly as plain strings,
+/// including in the database
+const DIGITS: &[u8; 62] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+
+fn digit_value(digit: u8) -> Option<usize> {
+    DIGITS.iter().position(|d| *d == digit)
+}
+
+/// Whether `rank` can be used: non-empty, base-62 digits only and not ending in `0`, which
+/// would leave no room before it
+pub fn is_valid_rank(rank: &str) -> bool {
+    !rank.is_empty()
+        && !rank.ends_with('0')
+        && rank.bytes().all(|digit| digit_value(digit).is_some())
+}
+
+/// A rank sorting strictly between `before` and `after`; `None` leaves that side open.
+/// `before` must sort below `after`, and both must be valid ranks.
+pub fn rank_between(before: Option<&str>, after: Option<&str>) -> String {
+    midpoint(before.unwrap_or(""), after)
+}
+
+/// Fractional-index midpoint: `low` reads as a base-62 fraction padded with zeros
+fn midpoint(low: &str, high: Option<&str>) -> String {
+    if let Some(high) = high {
+        // Keep the common prefix, treating the end of `low` as zeros
+        let shared = high
+            .bytes()
+            .zip(low.bytes().chain(std::iter::repeat(b'0')))
+            .take_while(|(h, l)| h == l)
+            .count();
+        if shared > 0 {
+            let rest = midpoint(low.get(shared..).unwrap_or(""), Some(&high[shared..]));
+            return format!("{}{}", &high[..shared], rest);
+        }
+    }
+
+    let low_digit = low.bytes().next().and_then(digit_value).unwrap_or(0);
+    let high_digit = high
+        .and_then(|high| high.bytes().next())
+        .and_then(digit_value)
+        .unwrap_or(DIGITS.len());
+    if high_digit - low_digit > 1 {
+        let middle = (low_digit + high_digit).div_ceil(2);
+        return (DIGITS[middle] as char).to_string();
+    }
+    match high {
+        // The first digit of a longer `high` already sorts between the two
+        Some(high) if high.len() > 1 => high[..1].to_string(),
+        _ => format!(
+            "{}{}",
+            DIGITS[low_digit] as char,
+            midpoint(low.get(1..).unwrap_or(""), None)
+        ),
+    }
+}
+
+/// `count` ranks in ascending order, spread evenly so later moves have room between them
+pub fn spread_ranks(count: usize) -> Vec<String> {
+    let base = DIGITS.len() as u128;
+    let mut width = 1;
+    while base.pow(width) <= count as u128 {
+        width += 1;
+    }
+    let span = base.pow(width);
+    (1..=count as u128)
+        .map(|index| {
+            let mut value = index * span / (count as u128 + 1);
+            let mut digits = vec![b'0'; width as usize];
+            for digit in digits.iter_mut().rev() {
+                *digit = DIGITS[(value % base) as usize];
+                value /= base;
+            }
+            let rank = String::from_utf8(digits).unwrap_or_default();
+            rank.trim_end_matches('0').to_string()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{is_valid_rank, rank_between, spread_ranks};
+
+    /// `rank_between` and checks the result sorts strictly between its bounds
+    fn between(before: Option<&str>, after: Option<&str>) -> String {
+        let rank = rank_between(before, after);
+        assert!(is_valid_rank(&rank), "{:?} between {:?} and {:?}", rank, before, after);
+        assert!(before.is_none_or(|before| before < rank.as_str()), "{} after {:?}", rank, before);
+        assert!(after.is_none_or(|after| rank.as_str() < after), "{} before {:?}", rank, after);
+        rank
+    }
+
+    #[test]
+    fn empty_bounds_give_a_middle_rank() {
+        assert_eq!(between(None, None), "V");
+        assert_eq!(between(None, Some("V")), "G");
+        assert_eq!(between(Some("V"), None), "l");
+    }
+
+    #[test]
+    fn adjacent_ranks_get_an_extra_digit() {
+        assert_eq!(between(Some("a"), Some("b")), "aV");
+        assert_eq!(between(Some("az"), Some("b")), "azV");
+        assert_eq!(between(Some("a"), Some("a1")), "a0V");
+        assert_eq!(between(Some("1"), Some("2")), "1V");
+        assert_eq!(between(None, Some("1")), "0V");
+        assert_eq!(between(Some("z"), None), "zV");
+    }
+
+    #[test]
+    fn repeated_inserts_at_the_head_stay_ordered() {
+        let mut head = between(None, None);
+        for _ in 0..200 {
+            head = between(None, Some(&head));
+        }
+        // G, 8, 4, 2, 1 and 0V: each digit takes six inserts
+        assert_eq!(head.len(), 200 / 6 + 1, "{}", head);
+    }
+
+    #[test]
+    fn repeated_inserts_at_the_tail_stay_ordered() {
+        let mut tail = between(None, None);
+        for _ in 0..200 {
+            tail = between(Some(&tail), None);
+        }
+        // l, t, x, z and zV: each digit takes five inserts
+        assert_eq!(tail.len(), 200 / 5 + 1, "{}", tail);
+    }
+
+    #[test]
+    fn repeated_inserts_after_the_same_rank_stay_ordered() {
+        let first = between(None, None);
+        let mut next = between(Some(&first), None);
+        for _ in 0..200 {
+            next = between(Some(&first), Some(&next));
+        }
+    }
+
+    #[test]
+    fn spread_ranks_are_valid_unique_and_ascending() {
+        for count in [0, 1, 2, 61, 62, 1000] {
+            let ranks = spread_ranks(count);
+            assert_eq!(ranks.len(), count);
+            assert!(ranks.iter().all(|rank| is_valid_rank(rank)), "{:?}", ranks);
+            assert!(ranks.windows(2).all(|pair| pair[0] < pair[1]), "{:?}", ranks);
+        }
+        assert_eq!(spread_ranks(1), vec!["V"]);
+        assert!(spread_ranks(61).iter().all(|rank| rank.len() == 1));
+        assert!(spread_ranks(62).iter().any(|rank| rank.len() == 2));
+    }
+
+    #[test]
+    fn rejects_invalid_ranks() {
+        assert!(!is_valid_rank(""));
+        assert!(!is_valid_rank("a0"));
+        assert!(!is_valid_rank("a-b"));
+        assert!(is_valid_rank("a0b"));
+    }
+}
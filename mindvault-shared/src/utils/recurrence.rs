@@ -0,0 +1,49 @@
+use serde::{Deserialize, Deserializer};
+
+const FREQUENCIES: [&str; 7] = [
+    "SECONDLY", "MINUTELY", "HOURLY", "DAILY", "WEEKLY", "MONTHLY", "YEARLY",
+];
+
+/// Normalizes an RFC 5545 recurrence rule such as `FREQ=WEEKLY;BYDAY=MO`, with or without the
+/// `RRULE:` prefix. Only the shape is checked: `KEY=VALUE` parts and a known `FREQ`.
+pub fn parse_rrule(s: &str) -> Result<String, String> {
+    let rule = s.trim();
+    let rule = rule
+        .strip_prefix("RRULE:")
+        .or_else(|| rule.strip_prefix("rrule:"))
+        .unwrap_or(rule)
+        .to_ascii_uppercase();
+
+    let mut frequency = None;
+    for part in rule.split(';') {
+        let Some((key, value)) = part.split_once('=') else {
+            return Err(format!("invalid recurrence rule part '{}' in '{}'", part, s.trim()));
+        };
+        if key.is_empty() || value.is_empty() {
+            return Err(format!("invalid recurrence rule part '{}' in '{}'", part, s.trim()));
+        }
+        if key == "FREQ" {
+            frequency = Some(value);
+        }
+    }
+
+    match frequency {
+        Some(frequency) if FREQUENCIES.contains(&frequency) => Ok(rule.clone()),
+        Some(frequency) => Err(format!(
+            "invalid recurrence frequency '{}', expected one of: {:?}",
+            frequency, FREQUENCIES
+        )),
+        None => Err(format!("recurrence rule '{}' has no FREQ", s.trim())),
+    }
+}
+
+pub fn deserialize_optional_rrule<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let s: Option<String> = Option::deserialize(deserializer)?;
+    match s {
+        Some(s) => parse_rrule(&s).map(Some).map_err(serde::de::Error::custom),
+        None => Ok(None),
+    }
+}
@@ -0,0 +1,42 @@
+use chrono::{DateTime, LocalResult, NaiveDateTime, TimeZone, Utc};
+use chrono_tz::Tz;
+
+pub const DEFAULT_TIME_ZONE: &str = "UTC";
+
+/// Parses an IANA time zone name such as `Europe/Berlin`
+pub fn parse_time_zone(name: &str) -> Result<Tz, String> {
+    name.trim()
+        .parse::<Tz>()
+        .map_err(|_| format!("unknown time zone '{}'", name))
+}
+
+/// Interprets a wall-clock time in the given zone and converts it to UTC.
+/// Ambiguous times (DST fall-back) resolve to the earlier instant; skipped times are rejected.
+pub fn local_to_utc(local: NaiveDateTime, time_zone: &str) -> Result<DateTime<Utc>, String> {
+    let tz = parse_time_zone(time_zone)?;
+    match tz.from_local_datetime(&local) {
+        LocalResult::Single(dt) => Ok(dt.with_timezone(&Utc)),
+        LocalResult::Ambiguous(earliest, _) => Ok(earliest.with_timezone(&Utc)),
+        LocalResult::None => Err(format!(
+            "{} does not exist in time zone {}",
+            local, time_zone
+        )),
+    }
+}
+
+/// A timestamp that either carried its own offset or is a wall-clock time without one
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FlexibleDateTime {
+    Absolute(DateTime<Utc>),
+    Local(NaiveDateTime),
+}
+
+impl FlexibleDateTime {
+    /// Resolves to UTC, using `time_zone` only when no offset was given
+    pub fn to_utc(self, time_zone: &str) -> Result<DateTime<Utc>, String> {
+        match self {
+            FlexibleDateTime::Absolute(dt) => Ok(dt),
+            FlexibleDateTime::Local(local) => local_to_utc(local, time_zone),
+        }
+    }
+}